@@ -293,6 +293,22 @@ impl KaspaCli {
                                     // log_info!("Kaspa NG - received metrics event {metrics:?}")
                                 }
                                 Events::FeeRate { .. } => {},
+                                Events::PayoutProgress { submitted, batches, batch, .. } => {
+                                    tprintln!(this, "{NOTIFY} Payout batch {submitted}/{batches} submitted ({} payouts)", batch.outputs.outputs.len());
+                                },
+                                Events::PaymentPlanExecuted { plan_id, account_id, transaction_ids, amount, fees } => {
+                                    let network_type = this.wallet.network_id().map(NetworkType::from).unwrap_or(NetworkType::Mainnet);
+                                    let amount = sompi_to_kaspa_string_with_suffix(amount, &network_type);
+                                    let fees = sompi_to_kaspa_string_with_suffix(fees, &network_type);
+                                    tprintln!(this, "{NOTIFY} Scheduled payment {plan_id} of account {} executed: {amount} (fees {fees})", account_id.short());
+                                    transaction_ids.iter().for_each(|id| tprintln!(this, "{NOTIFY}   {id}"));
+                                },
+                                Events::PaymentPlanDeferred { plan_id, fee_rate, .. } => {
+                                    tprintln!(this, "{NOTIFY} Scheduled payment {plan_id} deferred: network fee rate {fee_rate:.2} exceeds the plan maximum");
+                                },
+                                Events::PaymentPlanFailed { plan_id, message, .. } => {
+                                    terrorln!(this, "Scheduled payment {plan_id} failed: {message}");
+                                },
                                 Events::Error { message } => { terrorln!(this,"{message}"); },
                                 Events::UtxoProcStart => {},
                                 Events::UtxoProcStop => {},
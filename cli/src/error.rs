@@ -134,6 +134,9 @@ pub enum Error {
 
     #[error("To hex serialization error")]
     PskbSerializeToHexError,
+
+    #[error(transparent)]
+    ScriptAsm(#[from] kaspa_txscript::asm::AsmError),
 }
 
 impl Error {
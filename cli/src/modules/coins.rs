@@ -0,0 +1,59 @@
+use crate::imports::*;
+
+#[derive(Default, Handler)]
+#[help("Coin control: freeze UTXOs to exclude them from transactions")]
+pub struct Coins;
+
+impl Coins {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let action = argv.remove(0);
+
+        match action.as_str() {
+            "frozen" => {
+                let account = ctx.wallet().account()?;
+                let frozen = account.frozen_utxos();
+                if frozen.is_empty() {
+                    tprintln!(ctx, "No frozen UTXOs");
+                }
+                for outpoint in frozen {
+                    tprintln!(ctx, "{}:{}", outpoint.transaction_id, outpoint.index);
+                }
+            }
+            "freeze" | "unfreeze" => {
+                if argv.is_empty() {
+                    return self.display_help(ctx, argv).await;
+                }
+                let account = ctx.wallet().account()?;
+                let outpoints = argv.iter().map(|outpoint| try_parse_outpoint(outpoint)).collect::<Result<Vec<_>>>()?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                account.freeze_utxos(&wallet_secret, &outpoints, action == "freeze").await?;
+                tprintln!(ctx, "{} UTXO(s) {action}d, {} UTXO(s) currently frozen", outpoints.len(), account.frozen_utxos().len());
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("coins frozen", "List the UTXOs frozen in the selected account"),
+                ("coins freeze <txid:index> ...", "Freeze UTXOs so that they are never spent by the transaction generator"),
+                ("coins unfreeze <txid:index> ...", "Release previously frozen UTXOs"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
@@ -2,6 +2,8 @@ use crate::imports::*;
 use kaspa_consensus_core::tx::TransactionId;
 use kaspa_wallet_core::error::Error as WalletError;
 use kaspa_wallet_core::storage::Binding;
+use std::path::Path;
+use workflow_store::fs;
 #[derive(Default, Handler)]
 #[help("Display transaction history")]
 pub struct History;
@@ -64,6 +66,17 @@ impl History {
                 let last = if argv.is_empty() { None } else { argv[0].parse::<usize>().ok() };
                 (last, true)
             }
+            "export" => {
+                if argv.is_empty() {
+                    tprintln!(ctx, "usage: history export <file.csv>");
+                    return Ok(());
+                }
+                let filename = argv.remove(0);
+                let csv = ctx.wallet().transactions_export(*account.id(), network_id).await?;
+                fs::write_string(Path::new(&filename), &csv).await?;
+                tprintln!(ctx, "Transaction history exported to '{filename}'");
+                return Ok(());
+            }
             v => {
                 tprintln!(ctx, "unknown command: '{v}'");
                 self.display_help(ctx, argv).await?;
@@ -145,6 +158,7 @@ impl History {
                 ("list [<last N transactions>]", "List transactions"),
                 ("details [<last N transactions>]", "List transactions with UTXO details"),
                 ("lookup <transaction id>", "Lookup transaction in the history"),
+                ("export <file.csv>", "Export the transaction history as CSV for accounting"),
             ],
             None,
         )?;
@@ -0,0 +1,88 @@
+use crate::imports::*;
+use kaspa_wallet_core::storage::{self, LabelRef};
+use std::path::Path;
+use workflow_store::fs;
+
+#[derive(Default, Handler)]
+#[help("Label addresses, outputs and transactions")]
+pub struct Label;
+
+impl Label {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let action = argv.remove(0);
+
+        match action.as_str() {
+            "list" => {
+                let labels = ctx.wallet().labels_enumerate().await?;
+                if labels.is_empty() {
+                    tprintln!(ctx, "No labels");
+                }
+                for storage::Label { reference, label, .. } in labels {
+                    tprintln!(ctx, "{:<6} {reference}  {label}", reference.kind());
+                }
+            }
+            "set" => {
+                if argv.len() < 2 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let reference = argv.remove(0).parse::<LabelRef>()?;
+                let label = argv.join(" ");
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().labels_update(wallet_secret, vec![storage::Label::new(reference, label)]).await?;
+            }
+            "remove" => {
+                if argv.len() != 1 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let reference = argv.remove(0).parse::<LabelRef>()?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().labels_update(wallet_secret, vec![storage::Label::new(reference, String::new())]).await?;
+            }
+            "import" => {
+                if argv.len() != 1 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let data = fs::read_to_string(Path::new(&argv[0])).await?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                let LabelsImportResponse { imported, skipped } =
+                    ctx.wallet().labels_import_call(LabelsImportRequest { wallet_secret, data }).await?;
+                tprintln!(ctx, "Imported {imported} label(s), skipped {skipped} unsupported record(s)");
+            }
+            "export" => {
+                if argv.len() != 1 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let LabelsExportResponse { data } = ctx.wallet().labels_export_call(LabelsExportRequest {}).await?;
+                fs::write_string(Path::new(&argv[0]), &data).await?;
+                tprintln!(ctx, "Labels exported to '{}'", argv[0]);
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("label list", "List all labels"),
+                ("label set <address|txid|txid:index> <text>", "Attach a label to an address, an output or a transaction"),
+                ("label remove <address|txid|txid:index>", "Remove a label"),
+                ("label import <file>", "Import labels from a BIP-329 JSON Lines file"),
+                ("label export <file>", "Export labels to a BIP-329 JSON Lines file"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
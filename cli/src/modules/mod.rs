@@ -4,6 +4,7 @@ pub mod account;
 pub mod address;
 pub mod broadcast;
 pub mod close;
+pub mod coins;
 pub mod connect;
 #[path = "create-unsigned-tx.rs"]
 pub mod create_unsigned_tx;
@@ -17,6 +18,7 @@ pub mod halt;
 pub mod help;
 pub mod history;
 // pub mod import;
+pub mod label;
 pub mod list;
 pub mod message;
 pub mod miner;
@@ -25,17 +27,22 @@ pub mod mute;
 pub mod network;
 pub mod node;
 pub mod open;
+pub mod payout;
 pub mod ping;
 pub mod pskb;
 pub mod reload;
 pub mod rpc;
+pub mod schedule;
+pub mod script;
 pub mod select;
 pub mod send;
 pub mod server;
 pub mod settings;
 pub mod sign;
+pub mod signer;
 pub mod start;
 pub mod stop;
+pub mod swap;
 pub mod sweep;
 // pub mod test;
 pub mod theme;
@@ -57,9 +64,9 @@ pub fn register_handlers(cli: &Arc<KaspaCli>) -> Result<()> {
         cli,
         cli.handlers(),
         [
-            account, address, close, connect, details, disconnect, estimate, exit, export, guide, help, history, rpc, list, miner,
-            message, monitor, mute, network, node, open, ping, pskb, reload, select, send, server, settings, sweep, track, transfer,
-            wallet,
+            account, address, close, coins, connect, details, disconnect, estimate, exit, export, guide, help, history, label, rpc,
+            list, miner, message, monitor, mute, network, node, open, payout, ping, pskb, reload, schedule, script, select, send,
+            server, settings, signer, swap, sweep, track, transfer, wallet,
             // halt,
             // theme,  start, stop
         ]
@@ -0,0 +1,182 @@
+use crate::imports::*;
+use kaspa_wallet_core::tx::payout::parse_payouts;
+use kaspa_wallet_core::tx::PayoutJob;
+use std::path::{Path, PathBuf};
+use workflow_store::fs;
+
+#[derive(Default, Handler)]
+#[help("Batched payouts from a CSV or JSON file")]
+pub struct Payout;
+
+impl Payout {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.len() < 2 {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let action = argv.remove(0);
+        let filename = PathBuf::from(argv.remove(0));
+        let fee_rate = argv.first().map(|rate| rate.parse::<f64>()).transpose()?;
+        let progress = progress_filename(&filename);
+
+        match action.as_str() {
+            "estimate" => {
+                let account = ctx.wallet().account()?;
+                let mut job = self.load_job(&ctx, &account, &filename, &progress).await?;
+                let abortable = Abortable::default();
+                job.estimate(&account, fee_rate, &abortable).await?;
+                self.display_job(&ctx, &job)?;
+            }
+            "send" => {
+                let account = ctx.wallet().account()?;
+                let mut job = self.load_job(&ctx, &account, &filename, &progress).await?;
+                if job.is_completed() {
+                    tprintln!(ctx, "All {} payouts have already been submitted", job.payouts());
+                    return Ok(());
+                }
+
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+                save_progress(&progress, &job).await?;
+
+                let abortable = Abortable::default();
+                let network_type = NetworkType::from(job.network_id);
+                loop {
+                    let step: Result<Option<usize>> = async {
+                        let Some(prepared) =
+                            job.prepare_next(&account, fee_rate, &wallet_secret, payment_secret.as_ref(), &abortable).await?
+                        else {
+                            return Ok(None);
+                        };
+                        // the batch transactions are recorded before any of them is submitted
+                        save_progress(&progress, &job).await?;
+                        Ok(Some(job.submit_prepared(&account, prepared).await?))
+                    }
+                    .await;
+
+                    match step {
+                        Ok(Some(index)) => {
+                            save_progress(&progress, &job).await?;
+                            let batch = &job.batches[index];
+                            tprintln!(
+                                ctx,
+                                "[{}/{}] {} payouts, {} (fees {})",
+                                job.submitted(),
+                                job.batches.len(),
+                                batch.outputs.outputs.len(),
+                                sompi_to_kaspa_string_with_suffix(batch.amount(), &network_type),
+                                sompi_to_kaspa_string_with_suffix(batch.fees.unwrap_or_default(), &network_type)
+                            );
+                            batch.transaction_ids.iter().for_each(|id| tprintln!(ctx, "      {id}"));
+                        }
+                        Ok(None) => {
+                            // batches found submitted while settling an interrupted payout
+                            save_progress(&progress, &job).await?;
+                            break;
+                        }
+                        Err(err) => {
+                            save_progress(&progress, &job).await?;
+                            tprintln!(
+                                ctx,
+                                "Payout interrupted after {} of {} batches, run the same command to resume",
+                                job.submitted(),
+                                job.batches.len()
+                            );
+                            return Err(err);
+                        }
+                    }
+                }
+
+                tprintln!(
+                    ctx,
+                    "\n{} payouts submitted in {} batches, total {} (fees {})",
+                    job.payouts(),
+                    job.batches.len(),
+                    sompi_to_kaspa_string_with_suffix(job.amount(), &network_type),
+                    sompi_to_kaspa_string_with_suffix(job.fees(), &network_type)
+                );
+                tprintln!(ctx, "Payout record saved to '{}'", progress.display());
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Loads the payout progress record if present, otherwise packs the payouts of the file
+    async fn load_job(&self, ctx: &Arc<KaspaCli>, account: &Arc<dyn Account>, filename: &Path, progress: &Path) -> Result<PayoutJob> {
+        if fs::exists(progress).await? {
+            let job = serde_json::from_str::<PayoutJob>(&fs::read_to_string(progress).await?)?;
+            tprintln!(ctx, "Resuming from '{}': {} of {} batches submitted\n", progress.display(), job.submitted(), job.batches.len());
+            Ok(job)
+        } else {
+            let payouts = parse_payouts(&fs::read_to_string(filename).await?)?;
+            Ok(PayoutJob::try_new(*account.id(), ctx.wallet().network_id()?, payouts)?)
+        }
+    }
+
+    fn display_job(&self, ctx: &Arc<KaspaCli>, job: &PayoutJob) -> Result<()> {
+        let network_type = NetworkType::from(job.network_id);
+        for (n, batch) in job.batches.iter().enumerate() {
+            let state = if batch.is_submitted() {
+                "submitted"
+            } else if batch.pending {
+                "interrupted"
+            } else {
+                "pending"
+            };
+            let bound = if batch.is_storage_mass_bound() { " (storage mass bound)" } else { "" };
+            tprintln!(
+                ctx,
+                "#{:<4} {:>5} payouts {:>22}  compute mass {:>7}  storage mass {:>7}  fees {}  {state}{bound}",
+                n + 1,
+                batch.outputs.outputs.len(),
+                sompi_to_kaspa_string_with_suffix(batch.amount(), &network_type),
+                batch.compute_mass.separated_string(),
+                batch.storage_mass.separated_string(),
+                batch.fees.map(|fees| sompi_to_kaspa_string_with_suffix(fees, &network_type)).unwrap_or_else(|| "n/a".to_string()),
+            );
+        }
+
+        tprintln!(ctx);
+        tprintln!(ctx, "Payouts: {} in {} transaction batches", job.payouts(), job.batches.len());
+        tprintln!(ctx, "Amount:  {}", sompi_to_kaspa_string_with_suffix(job.amount(), &network_type));
+        tprintln!(ctx, "Fees:    {}", sompi_to_kaspa_string_with_suffix(job.fees(), &network_type));
+        let storage_bound = job.batches.iter().filter(|batch| batch.is_storage_mass_bound()).count();
+        if storage_bound > 0 {
+            tprintln!(ctx, "{storage_bound} batch(es) are storage mass bound: their fees are driven by low-value payouts (KIP-9)");
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("payout estimate <file> [fee rate]", "Preview the batches, masses and fees of the payouts"),
+                ("payout send <file> [fee rate]", "Submit the payouts, resuming an interrupted payout if any"),
+            ],
+            None,
+        )?;
+        tprintln!(ctx);
+        tprintln!(ctx, "The file contains '<address>,<amount>' lines or a JSON array of {{ \"address\", \"amount\" }} objects.");
+        tprintln!(ctx, "Amounts are in KAS. Progress is recorded in '<file>.payout.json'.");
+
+        Ok(())
+    }
+}
+
+async fn save_progress(progress: &Path, job: &PayoutJob) -> Result<()> {
+    fs::write_string(progress, &serde_json::to_string_pretty(job)?).await?;
+    Ok(())
+}
+
+fn progress_filename(filename: &Path) -> PathBuf {
+    let mut progress = filename.as_os_str().to_owned();
+    progress.push(".payout.json");
+    PathBuf::from(progress)
+}
@@ -313,6 +313,68 @@ impl Rpc {
 
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetFeeEstimateHistory => {
+                let from_timestamp = if argv.is_empty() { 0 } else { argv.remove(0).parse::<u64>()? };
+                let replay_params = if argv.is_empty() {
+                    None
+                } else {
+                    if argv.len() < 2 {
+                        return Err(Error::custom(
+                            "Please specify total_weight_factor and inclusion_interval_factor [target_time_per_block_seconds]",
+                        ));
+                    }
+                    let total_weight_factor = argv.remove(0).parse::<f64>()?;
+                    let inclusion_interval_factor = argv.remove(0).parse::<f64>()?;
+                    let target_time_per_block_seconds = if argv.is_empty() { None } else { Some(argv.remove(0).parse::<f64>()?) };
+                    Some(RpcFeeEstimateReplayParams { total_weight_factor, inclusion_interval_factor, target_time_per_block_seconds })
+                };
+                // The history is exported in pages bounded by the node
+                let mut request = GetFeeEstimateHistoryRequest::new(from_timestamp, replay_params);
+                loop {
+                    let result = rpc.get_fee_estimate_history_call(None, request.clone()).await?;
+                    let has_more = result.has_more;
+                    request = request.next_page(&result);
+                    self.println(&ctx, result);
+                    if !has_more {
+                        break;
+                    }
+                }
+            }
+            RpcApiOps::GetMempoolInfo => {
+                let include_entries = argv.first().and_then(|x| x.parse::<bool>().ok()).unwrap_or(false);
+                let result = rpc.get_mempool_info_call(None, GetMempoolInfoRequest { include_entries }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetMempoolTransactionGraph => {
+                if argv.is_empty() {
+                    return Err(Error::custom("Missing transaction id argument"));
+                }
+                let transaction_id = RpcTransactionId::from_hex(argv.remove(0).as_str())?;
+                let result =
+                    rpc.get_mempool_transaction_graph_call(None, GetMempoolTransactionGraphRequest { transaction_id }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::PrioritiseTransaction => {
+                if argv.len() < 2 {
+                    return Err(Error::custom("Please specify a transaction id and a fee delta in sompi"));
+                }
+                let transaction_id = RpcTransactionId::from_hex(argv.remove(0).as_str())?;
+                let fee_delta = argv.remove(0).parse::<i64>()?;
+                let result = rpc.prioritise_transaction_call(None, PrioritiseTransactionRequest { transaction_id, fee_delta }).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetPrioritisedTransactions => {
+                let result = rpc.get_prioritised_transactions_call(None, GetPrioritisedTransactionsRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::GetMiningStatus => {
+                let result = rpc.get_mining_status_call(None, GetMiningStatusRequest {}).await?;
+                self.println(&ctx, result);
+            }
+            RpcApiOps::ReloadMempoolPolicy => {
+                let result = rpc.reload_mempool_policy_call(None, ReloadMempoolPolicyRequest {}).await?;
+                self.println(&ctx, result);
+            }
             _ => {
                 tprintln!(ctx, "rpc method exists but is not supported by the cli: '{op_str}'\r\n");
                 return Ok(());
@@ -0,0 +1,173 @@
+use crate::imports::*;
+use kaspa_wallet_core::storage::{PaymentInterval, PaymentPlan, PaymentPlanId};
+use workflow_core::time::unixtime_to_locale_string;
+
+#[derive(Default, Handler)]
+#[help("Scheduled and recurring payments")]
+pub struct Schedule;
+
+impl Schedule {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let action = argv.remove(0);
+
+        match action.as_str() {
+            "list" => {
+                let plans = ctx.wallet().payment_plans_enumerate(None).await?;
+                if plans.is_empty() {
+                    tprintln!(ctx, "No payment plans");
+                }
+                let network_type = ctx.wallet().network_id().map(NetworkType::from).unwrap_or(NetworkType::Mainnet);
+                for plan in plans {
+                    self.display_plan(&ctx, &plan, &network_type);
+                }
+                let state = if ctx.wallet().scheduler().is_running() { "running" } else { "stopped" };
+                tprintln!(ctx, "Payment scheduler is {state}");
+            }
+            "create" => {
+                if argv.len() < 3 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let account = ctx.wallet().account()?;
+                let address = Address::try_from(argv[0].as_str())?;
+                let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.get(1))?;
+                let interval = parse_interval(&argv[2])?;
+                let runs =
+                    argv.get(3).map(|runs| runs.parse::<u32>()).transpose().map_err(|_| Error::custom("Invalid number of runs"))?;
+                let max_fee_rate =
+                    argv.get(4).map(|rate| rate.parse::<f64>()).transpose().map_err(|_| Error::custom("Invalid maximum fee rate"))?;
+                let outputs = PaymentOutputs::from((address, amount_sompi));
+
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                let PaymentPlansCreateResponse { plan } = ctx
+                    .wallet()
+                    .payment_plans_create_call(PaymentPlansCreateRequest {
+                        wallet_secret,
+                        account_id: *account.id(),
+                        name: None,
+                        outputs,
+                        interval,
+                        max_fee_rate,
+                        runs,
+                        start: None,
+                    })
+                    .await?;
+                tprintln!(ctx, "Payment plan {} created", plan.id);
+                if !ctx.wallet().scheduler().is_running() {
+                    tprintln!(ctx, "Use 'schedule start' to start executing payment plans");
+                }
+            }
+            "pause" | "resume" => {
+                if argv.len() != 1 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let plan_id = argv[0].parse::<PaymentPlanId>()?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().payment_plans_pause(wallet_secret, plan_id, action == "pause").await?;
+                tprintln!(ctx, "Payment plan {plan_id} {action}d");
+            }
+            "cancel" => {
+                if argv.len() != 1 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let plan_id = argv[0].parse::<PaymentPlanId>()?;
+                let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+                ctx.wallet().payment_plans_cancel(wallet_secret, plan_id).await?;
+                tprintln!(ctx, "Payment plan {plan_id} cancelled");
+            }
+            "start" => {
+                let account = ctx.wallet().account()?;
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+                ctx.wallet().payment_scheduler_start_call(PaymentSchedulerStartRequest { wallet_secret, payment_secret }).await?;
+                tprintln!(ctx, "Payment scheduler started");
+            }
+            "stop" => {
+                ctx.wallet().payment_scheduler_stop_call(PaymentSchedulerStopRequest {}).await?;
+                tprintln!(ctx, "Payment scheduler stopped");
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn display_plan(&self, ctx: &Arc<KaspaCli>, plan: &PaymentPlan, network_type: &NetworkType) {
+        let state = if plan.is_completed() {
+            "completed"
+        } else if plan.paused {
+            "paused"
+        } else {
+            "active"
+        };
+        let remaining = plan.remaining.map(|remaining| format!("{remaining} left")).unwrap_or_else(|| "open-ended".to_string());
+        let next = match plan.interval {
+            PaymentInterval::Seconds(_) => unixtime_to_locale_string(plan.next),
+            PaymentInterval::DaaScore(_) => format!("DAA {}", plan.next),
+        };
+        let amount = sompi_to_kaspa_string_with_suffix(plan.outputs.amount(), network_type);
+        tprintln!(
+            ctx,
+            "{} [{state}] account {}: {amount} every {} ({remaining}, {} executed), next: {next}",
+            plan.id,
+            plan.account_id.short(),
+            plan.interval,
+            plan.executions
+        );
+        for output in plan.outputs.outputs.iter() {
+            tprintln!(ctx, "    {} {}", output.address, sompi_to_kaspa_string_with_suffix(output.amount, network_type));
+        }
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("schedule list", "List payment plans"),
+                (
+                    "schedule create <address> <amount> <interval> [runs] [max fee rate]",
+                    "Create a recurring payment from the selected account. The interval is a duration \
+                    (e.g. '90s', '30m', '12h', '7d') or a DAA score period (e.g. '86400daa')",
+                ),
+                ("schedule pause <id>", "Pause a payment plan"),
+                ("schedule resume <id>", "Resume a payment plan"),
+                ("schedule cancel <id>", "Cancel a payment plan"),
+                ("schedule start", "Start executing due payments while the wallet is open"),
+                ("schedule stop", "Stop executing payments"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
+
+fn parse_interval(interval: &str) -> Result<PaymentInterval> {
+    let invalid = || Error::custom(format!("Invalid interval '{interval}'"));
+    let (value, multiplier) = if let Some(value) = interval.strip_suffix("daa") {
+        return match value.parse::<u64>() {
+            Ok(value) if value > 0 => Ok(PaymentInterval::DaaScore(value)),
+            _ => Err(invalid()),
+        };
+    } else if let Some(value) = interval.strip_suffix('s') {
+        (value, 1)
+    } else if let Some(value) = interval.strip_suffix('m') {
+        (value, 60)
+    } else if let Some(value) = interval.strip_suffix('h') {
+        (value, 3600)
+    } else if let Some(value) = interval.strip_suffix('d') {
+        (value, 86400)
+    } else {
+        (interval, 1)
+    };
+    match value.parse::<u64>() {
+        Ok(value) if value > 0 => Ok(PaymentInterval::Seconds(value * multiplier)),
+        _ => Err(invalid()),
+    }
+}
@@ -0,0 +1,52 @@
+use crate::imports::*;
+use kaspa_txscript::asm::{assemble, disassemble};
+
+#[derive(Default, Handler)]
+#[help("Assemble or disassemble transaction scripts")]
+pub struct Script;
+
+impl Script {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        match argv.first().unwrap().as_str() {
+            "asm" => {
+                if argv.len() < 2 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let script = assemble(&argv[1..].join(" "))?;
+                tprintln!(ctx, "{}", script.to_hex());
+            }
+            "disasm" => {
+                if argv.len() != 2 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let script = Vec::<u8>::from_hex(argv[1].as_str())?;
+                let text = disassemble(&script)?;
+                tprintln!(ctx, "{text}");
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("asm <script text>", "Assemble a script, e.g. 'OP_DUP OP_BLAKE2B <hex> OP_EQUALVERIFY OP_CHECKSIG', into hex"),
+                ("disasm <hex>", "Disassemble a hex encoded script into its canonical text form"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
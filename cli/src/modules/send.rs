@@ -5,14 +5,19 @@ use crate::imports::*;
 pub struct Send;
 
 impl Send {
-    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, argv: Vec<String>, _cmd: &str) -> Result<()> {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
         // address, amount, priority fee
         let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
 
         let account = ctx.wallet().account()?;
 
+        let utxo_selection = parse_utxo_selection(&mut argv)?;
+
         if argv.len() < 2 {
-            tprintln!(ctx, "usage: send <address> <amount> <priority fee>");
+            tprintln!(
+                ctx,
+                "usage: send <address> <amount> <priority fee> [--strategy <smallest-first|largest-first|branch-and-bound|single-address>] [--include <txid:index>] [--exclude <txid:index>]"
+            );
             return Ok(());
         }
 
@@ -32,6 +37,7 @@ impl Send {
                 fee_rate,
                 priority_fee_sompi.into(),
                 None,
+                utxo_selection,
                 wallet_secret,
                 payment_secret,
                 &abortable,
@@ -48,3 +54,24 @@ impl Send {
         Ok(())
     }
 }
+
+/// Extracts the `--strategy`, `--include` and `--exclude` coin control options from the arguments
+fn parse_utxo_selection(argv: &mut Vec<String>) -> Result<Option<UtxoSelection>> {
+    let mut selection: Option<UtxoSelection> = None;
+    let mut args = std::mem::take(argv).into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--strategy" | "--include" | "--exclude" => {
+                let value = args.next().ok_or_else(|| Error::custom(format!("Missing value for '{arg}'")))?;
+                let selection = selection.get_or_insert_with(UtxoSelection::default);
+                match arg.as_str() {
+                    "--strategy" => selection.strategy = value.parse()?,
+                    "--include" => selection.include.push(try_parse_outpoint(&value)?),
+                    _ => selection.exclude.push(try_parse_outpoint(&value)?),
+                }
+            }
+            _ => argv.push(arg),
+        }
+    }
+    Ok(selection)
+}
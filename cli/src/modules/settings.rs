@@ -12,7 +12,11 @@ impl Settings {
         // let list = WalletSettings::list();
         let list = WalletSettings::into_iter()
             .map(|setting| {
-                let value: String = ctx.wallet().settings().get(setting.clone()).unwrap_or_else(|| "-".to_string());
+                let value = match ctx.wallet().settings().get::<serde_json::Value>(setting.clone()) {
+                    Some(serde_json::Value::String(value)) => value,
+                    Some(value) => value.to_string(),
+                    None => "-".to_string(),
+                };
                 let descr = setting.describe();
                 (setting.as_str().to_lowercase(), value, descr)
             })
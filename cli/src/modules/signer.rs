@@ -0,0 +1,94 @@
+use crate::imports::*;
+use kaspa_wallet_core::signer::{ExternalSigner, SignerProcess};
+
+#[derive(Default, Handler)]
+#[help("Sign with an external signer (hardware wallet, HSM, signing process)")]
+pub struct Signer;
+
+impl Signer {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        let account = ctx.wallet().account()?;
+
+        if argv.is_empty() {
+            match account.external_signer() {
+                Some(external_signer) => tprintln!(ctx, "Account {} signs with {external_signer}", account.name_with_id()),
+                None => tprintln!(ctx, "Account {} signs with the wallet private key data", account.name_with_id()),
+            }
+            return Ok(());
+        }
+
+        let action = argv.remove(0);
+
+        let external_signer = match action.as_str() {
+            "process" => {
+                if argv.len() < 2 {
+                    return self.display_help(ctx, argv).await;
+                }
+                // the command is kept in the local settings, the account only refers to it by id
+                let id = argv.remove(0);
+                let command = argv.remove(0);
+                let process = SignerProcess { command, args: argv, timeout: None };
+                tprintln!(ctx, "Registering '{process}' as signer device '{id}' in the local settings");
+                ctx.wallet().register_signer_process(&id, process).await?;
+                Some(ExternalSigner::Device { id })
+            }
+            "device" => {
+                if argv.len() != 1 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let id = argv.remove(0);
+                if ctx.wallet().signer_device(&id).is_none() {
+                    return Err(Error::custom(format!("Signer device '{id}' is not registered")));
+                }
+                Some(ExternalSigner::Device { id })
+            }
+            "list" => {
+                let processes = ctx.wallet().signer_processes();
+                if processes.is_empty() {
+                    tprintln!(ctx, "No signing processes configured");
+                }
+                for (id, process) in processes {
+                    tprintln!(ctx, "{id}: {process}");
+                }
+                return Ok(());
+            }
+            "clear" => None,
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        };
+
+        let (wallet_secret, _) = ctx.ask_wallet_secret(None).await?;
+        ctx.wallet().accounts_set_external_signer(*account.id(), wallet_secret, external_signer.clone()).await?;
+        match external_signer {
+            Some(external_signer) => tprintln!(ctx, "Account {} now signs with {external_signer}", account.name_with_id()),
+            None => tprintln!(ctx, "Account {} now signs with the wallet private key data", account.name_with_id()),
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("signer", "Show the signer of the selected account"),
+                (
+                    "signer process <id> <command> [args...]",
+                    "Configure a signing process locally and sign transactions of the selected account with it",
+                ),
+                ("signer device <id>", "Sign transactions of the selected account with a registered signer device"),
+                ("signer list", "List the signing processes configured locally"),
+                ("signer clear", "Sign transactions of the selected account with the wallet private key data"),
+            ],
+            None,
+        )?;
+        tprintln!(ctx);
+        tprintln!(ctx, "A signer process receives a serialized PSKB on stdin and must write the signed PSKB to stdout.");
+        tprintln!(ctx, "Signing processes are stored in the local settings, not in the wallet file.");
+
+        Ok(())
+    }
+}
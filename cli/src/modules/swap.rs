@@ -0,0 +1,242 @@
+use crate::imports::*;
+use kaspa_consensus_core::tx::{Transaction, TransactionId};
+use kaspa_txscript::covenants::HTLC_PREIMAGE_LEN;
+use kaspa_wallet_core::account::htlc::{htlc_extract_secret, HtlcContract, HtlcFunding};
+
+#[derive(Default, Handler)]
+#[help("Cross-chain atomic swaps using hash time locked contracts")]
+pub struct Swap;
+
+impl Swap {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let action = argv.remove(0);
+
+        match action.as_str() {
+            "initiate" => {
+                if argv.len() < 3 || argv.len() > 4 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let account = ctx.wallet().account()?;
+                let counterparty = Address::try_from(argv[0].as_str())?;
+                let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.get(1))?;
+                let lock_time = self.lock_time(&ctx, &argv[2]).await?;
+                let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(3))?.unwrap_or(0);
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+                let abortable = Abortable::default();
+
+                let funding = account
+                    .htlc_initiate(
+                        counterparty,
+                        amount_sompi,
+                        lock_time,
+                        None,
+                        priority_fee_sompi.into(),
+                        wallet_secret,
+                        payment_secret,
+                        &abortable,
+                    )
+                    .await?;
+                self.print_funding(&ctx, &funding);
+            }
+            "participate" => {
+                if argv.len() < 3 || argv.len() > 4 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let account = ctx.wallet().account()?;
+                let initiator = self.parse_contract(&ctx, &argv[0])?;
+                let amount_sompi = try_parse_required_nonzero_kaspa_as_sompi_u64(argv.get(1))?;
+                let lock_time = self.lock_time(&ctx, &argv[2]).await?;
+                let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(3))?.unwrap_or(0);
+
+                // The initiator contract must be funded and must remain locked long enough after this contract
+                // gets refundable, otherwise the initiator could redeem this contract and get refunded as well
+                let audit = account.clone().htlc_audit(initiator).await?;
+                if audit.utxos.is_empty() {
+                    return Err(kaspa_wallet_core::error::Error::HtlcNotFunded(audit.contract.address.to_string()).into());
+                }
+                audit.contract.check_participant_lock_time(lock_time)?;
+                tprintln!(
+                    ctx,
+                    "Initiator contract: {} locked for {} until DAA score {}",
+                    sompi_to_kaspa_string(audit.amount()),
+                    audit.contract.receiver_address(),
+                    audit.contract.htlc.lock_time
+                );
+
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+                let abortable = Abortable::default();
+
+                let funding = account
+                    .htlc_participate(
+                        audit.contract.refund_address(),
+                        audit.contract.htlc.payment_hash,
+                        amount_sompi,
+                        lock_time,
+                        None,
+                        priority_fee_sompi.into(),
+                        wallet_secret,
+                        payment_secret,
+                        &abortable,
+                    )
+                    .await?;
+                self.print_funding(&ctx, &funding);
+            }
+            "redeem" => {
+                if argv.is_empty() || argv.len() > 3 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let account = ctx.wallet().account()?;
+                let contract = self.parse_contract(&ctx, &argv.remove(0))?;
+                // The secret is optional for the swap initiator, whose wallet keeps it
+                let secret =
+                    if argv.first().is_some_and(|arg| arg.len() == HTLC_PREIMAGE_LEN * 2) { Some(argv.remove(0)) } else { None };
+                let priority_fee_sompi = self.priority_fee(argv.first())?;
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+                let secret = match secret {
+                    Some(secret) => Vec::<u8>::from_hex(secret.as_str())?,
+                    None => account
+                        .clone()
+                        .htlc_load_secret(&contract.htlc.payment_hash, wallet_secret.clone())
+                        .await?
+                        .ok_or_else(|| Error::custom("The secret of this contract is not stored in the wallet, please supply it"))?
+                        .to_vec(),
+                };
+
+                let id = account.htlc_redeem(&contract, &secret, priority_fee_sompi, wallet_secret, payment_secret).await?;
+                tprintln!(ctx, "Redeemed {}, tx id: {id}", contract.address);
+            }
+            "extract" => {
+                if argv.len() < 2 || argv.len() > 3 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let contract = self.parse_contract(&ctx, &argv[0])?;
+                let transaction_id = TransactionId::from_hex(argv[1].as_str())?;
+                let block_hash = argv.get(2).map(|hash| RpcHash::from_hex(hash.as_str())).transpose()?;
+                let transaction = self.redeem_transaction(&ctx, transaction_id, block_hash).await?;
+                let secret = htlc_extract_secret(&contract, &transaction)?;
+                tprintln!(ctx, "Secret: {}", secret.as_slice().to_hex());
+            }
+            "refund" => {
+                if argv.is_empty() || argv.len() > 2 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let account = ctx.wallet().account()?;
+                let contract = self.parse_contract(&ctx, &argv[0])?;
+                let priority_fee_sompi = self.priority_fee(argv.get(1))?;
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+
+                let id = account.htlc_refund(&contract, priority_fee_sompi, wallet_secret, payment_secret).await?;
+                tprintln!(ctx, "Refunded {}, tx id: {id}", contract.address);
+            }
+            "audit" => {
+                if argv.len() != 1 {
+                    return self.display_help(ctx, argv).await;
+                }
+                let account = ctx.wallet().account()?;
+                let contract = self.parse_contract(&ctx, &argv[0])?;
+                let audit = account.htlc_audit(contract).await?;
+                let contract = &audit.contract;
+
+                tprintln!(ctx, "Contract address: {}", contract.address);
+                tprintln!(ctx, "Recipient address: {}", contract.receiver_address());
+                tprintln!(ctx, "Refund address: {}", contract.refund_address());
+                tprintln!(ctx, "Secret hash: {}", contract.htlc.payment_hash.as_slice().to_hex());
+                tprintln!(ctx, "Locked amount: {} in {} UTXO(s)", sompi_to_kaspa_string(audit.amount()), audit.utxos.len());
+                if audit.is_refundable() {
+                    tprintln!(ctx, "Lock time: DAA score {} (reached, the contract can be refunded)", contract.htlc.lock_time);
+                } else {
+                    tprintln!(
+                        ctx,
+                        "Lock time: DAA score {} (refundable in {} DAA)",
+                        contract.htlc.lock_time,
+                        contract.htlc.lock_time - audit.virtual_daa_score
+                    );
+                }
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Converts a lock duration in DAA score units into an absolute lock time
+    async fn lock_time(&self, ctx: &Arc<KaspaCli>, duration: &str) -> Result<u64> {
+        let duration = duration.parse::<u64>().map_err(|_| Error::custom(format!("Invalid lock duration '{duration}'")))?;
+        let virtual_daa_score = ctx.wallet().rpc_api().get_block_dag_info().await?.virtual_daa_score;
+        Ok(virtual_daa_score + duration)
+    }
+
+    /// Parses an optional priority fee, rejecting negative amounts
+    fn priority_fee(&self, priority_fee: Option<&String>) -> Result<u64> {
+        Ok(priority_fee.map(|fee| try_parse_required_kaspa_as_sompi_u64(Some(fee))).transpose()?.unwrap_or(0))
+    }
+
+    /// Fetches the transaction redeeming a contract from the mempool, or from the block accepting it once confirmed
+    async fn redeem_transaction(
+        &self,
+        ctx: &Arc<KaspaCli>,
+        transaction_id: TransactionId,
+        block_hash: Option<RpcHash>,
+    ) -> Result<Transaction> {
+        let rpc = ctx.wallet().rpc_api();
+        let Some(block_hash) = block_hash else {
+            let entry = rpc.get_mempool_entry(transaction_id, false, false).await.map_err(|err| {
+                Error::custom(format!("Transaction {transaction_id} is not in the mempool ({err}), please supply its block hash"))
+            })?;
+            return Ok(entry.transaction.try_into()?);
+        };
+        let block = rpc.get_block(block_hash, true).await?;
+        for transaction in block.transactions {
+            let transaction = Transaction::try_from(transaction)?;
+            if transaction.id() == transaction_id {
+                return Ok(transaction);
+            }
+        }
+        Err(Error::custom(format!("Transaction {transaction_id} is not included in block {block_hash}")))
+    }
+
+    fn parse_contract(&self, ctx: &Arc<KaspaCli>, contract: &str) -> Result<HtlcContract> {
+        let redeem_script = Vec::<u8>::from_hex(contract)?;
+        Ok(HtlcContract::try_from_redeem_script(&redeem_script, ctx.wallet().address_prefix()?)?)
+    }
+
+    fn print_funding(&self, ctx: &Arc<KaspaCli>, funding: &HtlcFunding) {
+        let contract = &funding.contract;
+        if funding.secret.is_some() {
+            tprintln!(ctx, "Secret: stored encrypted in the wallet");
+        }
+        tprintln!(ctx, "Secret hash: {}", contract.htlc.payment_hash.as_slice().to_hex());
+        tprintln!(ctx, "Lock time: DAA score {}", contract.htlc.lock_time);
+        tprintln!(ctx, "Contract address: {}", contract.address);
+        tprintln!(ctx, "Contract: {}", contract.redeem_script.to_hex());
+        tprintln!(ctx, "Funding - {}", funding.summary);
+        for id in funding.transaction_ids.iter() {
+            tprintln!(ctx, "  tx id: {id}");
+        }
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("swap initiate <address> <amount> <lock duration> [priority fee]", "Generate a secret and lock funds in a contract redeemable by <address> against it. The lock duration is expressed in DAA score units"),
+                ("swap participate <initiator contract> <amount> <lock duration> [priority fee]", "Lock funds in a contract redeemable by the initiator against the secret of <initiator contract>. The lock time must precede the initiator lock time by a safety margin"),
+                ("swap redeem <contract> [secret] [priority fee]", "Redeem the funds locked in a contract by revealing its secret. The secret of a swap initiated by this wallet is loaded from the wallet"),
+                ("swap extract <contract> <redeem tx id> [block hash]", "Recover the secret revealed by the counterparty redeeming <contract>, from the mempool or from the block including the transaction"),
+                ("swap refund <contract> [priority fee]", "Get refunded once the contract lock time has been reached"),
+                ("swap audit <contract>", "Display the parties, secret hash, lock time and locked funds of a contract"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
@@ -7,13 +7,15 @@ use kaspa_consensus_core::{
     api::{BlockCount, BlockValidationFutures, ConsensusApi, ConsensusStats, DynConsensus},
     block::Block,
     blockstatus::BlockStatus,
+    coinbase::{CoinbasePayout, MinerData},
     daa_score_timestamp::DaaScoreTimestamp,
-    errors::consensus::ConsensusResult,
+    errors::{coinbase::CoinbaseResult, consensus::ConsensusResult},
     header::Header,
     mass::{ContextualMasses, NonContextualMasses},
     pruning::{PruningPointProof, PruningPointTrustedData, PruningPointsList},
     trusted::{ExternalGhostdagData, TrustedBlock},
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint, TransactionQueryResult, TransactionType, UtxoEntry},
+    utxo::utxo_diff::UtxoDiff,
     BlockHashSet, BlueWorkType, ChainPath, Hash,
 };
 use kaspa_utils::sync::rwlock::*;
@@ -201,6 +203,11 @@ impl ConsensusSessionOwned {
         self.consensus.calculate_transaction_contextual_masses(transaction)
     }
 
+    pub fn encode_coinbase_payouts(&self, miner_data: MinerData, payouts: &[CoinbasePayout]) -> CoinbaseResult<MinerData> {
+        // This method performs pure calculations so no need for an async wrapper
+        self.consensus.encode_coinbase_payouts(miner_data, payouts)
+    }
+
     pub fn get_virtual_daa_score(&self) -> u64 {
         // Accessing cached virtual fields is lock-free and does not require spawn_blocking
         self.consensus.get_virtual_daa_score()
@@ -422,6 +429,11 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.get_blocks_acceptance_data(&hashes, merged_blocks_limit)).await
     }
 
+    /// Returns the UTXO diff a chain block applied over the UTXO state of its selected parent.
+    pub async fn async_get_chain_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        self.clone().spawn_blocking(move |c| c.get_chain_block_utxo_diff(hash)).await
+    }
+
     pub async fn async_is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         self.clone().spawn_blocking(move |c| c.is_chain_block(hash)).await
     }
@@ -11,6 +11,8 @@ pub struct ProcessingCounters {
     pub chain_block_counts: AtomicU64,
     pub chain_disqualified_counts: AtomicU64,
     pub mass_counts: AtomicU64,
+    pub pruning_counts: AtomicU64,
+    pub pruning_millis: AtomicU64,
 }
 
 impl ProcessingCounters {
@@ -25,6 +27,8 @@ impl ProcessingCounters {
             chain_block_counts: self.chain_block_counts.load(Ordering::Relaxed),
             chain_disqualified_counts: self.chain_disqualified_counts.load(Ordering::Relaxed),
             mass_counts: self.mass_counts.load(Ordering::Relaxed),
+            pruning_counts: self.pruning_counts.load(Ordering::Relaxed),
+            pruning_millis: self.pruning_millis.load(Ordering::Relaxed),
         }
     }
 }
@@ -40,6 +44,8 @@ pub struct ProcessingCountersSnapshot {
     pub chain_block_counts: u64,
     pub chain_disqualified_counts: u64,
     pub mass_counts: u64,
+    pub pruning_counts: u64,
+    pub pruning_millis: u64,
 }
 
 impl core::ops::Sub for &ProcessingCountersSnapshot {
@@ -56,6 +62,8 @@ impl core::ops::Sub for &ProcessingCountersSnapshot {
             chain_block_counts: self.chain_block_counts.saturating_sub(rhs.chain_block_counts),
             chain_disqualified_counts: self.chain_disqualified_counts.saturating_sub(rhs.chain_disqualified_counts),
             mass_counts: self.mass_counts.saturating_sub(rhs.mass_counts),
+            pruning_counts: self.pruning_counts.saturating_sub(rhs.pruning_counts),
+            pruning_millis: self.pruning_millis.saturating_sub(rhs.pruning_millis),
         }
     }
 }
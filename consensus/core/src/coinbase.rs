@@ -1,4 +1,4 @@
-use crate::tx::{ScriptPublicKey, Transaction};
+use crate::tx::{ScriptPublicKey, ScriptVec, Transaction};
 use serde::{Deserialize, Serialize};
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -38,3 +38,130 @@ pub struct CoinbaseTransactionTemplate {
     pub tx: Transaction,
     pub has_red_reward: bool, // Does the last output contain reward for red blocks
 }
+
+/// Magic prefix identifying a payout split record at the start of the coinbase payload extra data
+pub const COINBASE_PAYOUTS_MAGIC: [u8; 4] = *b"kpo1";
+
+/// Maximum number of payouts a payout split record can hold
+pub const MAX_COINBASE_PAYOUTS: usize = 16;
+
+const LENGTH_OF_PAYOUT_WEIGHT: usize = size_of::<u32>();
+const LENGTH_OF_PAYOUT_HEADER: usize = LENGTH_OF_PAYOUT_WEIGHT + size_of::<u16>() + size_of::<u8>();
+
+/// Length of the header opening a payout split record: magic, entries length (u8) and payout count (u8)
+pub const COINBASE_PAYOUTS_HEADER_LEN: usize = COINBASE_PAYOUTS_MAGIC.len() + 2 * size_of::<u8>();
+
+/// A share of a block reward, expressed as a weight relative to the other payouts of the split
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct CoinbasePayout {
+    pub script_public_key: ScriptPublicKey,
+    pub weight: u32,
+}
+
+impl CoinbasePayout {
+    pub fn new(script_public_key: ScriptPublicKey, weight: u32) -> Self {
+        Self { script_public_key, weight }
+    }
+
+    fn encoded_len(&self) -> usize {
+        LENGTH_OF_PAYOUT_HEADER + self.script_public_key.script().len()
+    }
+}
+
+/// Returns the length of the payout split record encoding `payouts`, header included
+pub fn coinbase_payouts_record_len(payouts: &[CoinbasePayout]) -> usize {
+    COINBASE_PAYOUTS_HEADER_LEN + payouts.iter().map(CoinbasePayout::encoded_len).sum::<usize>()
+}
+
+/// Encodes `payouts` as a record meant to be prepended to the coinbase payload extra data.
+///
+/// The record is delimited by its header so that it can be told apart from the extra data following it. Since
+/// the extra data of the templates served by the node always start with the node version, extra data provided by
+/// a miner can never be mistaken for a record.
+///
+/// The caller is expected to validate the payouts beforehand, in particular the number of payouts and the
+/// length of the entries must each fit in a byte.
+pub fn encode_coinbase_payouts(payouts: &[CoinbasePayout]) -> Vec<u8> {
+    let entries_len = coinbase_payouts_record_len(payouts) - COINBASE_PAYOUTS_HEADER_LEN;
+    let mut record = Vec::with_capacity(COINBASE_PAYOUTS_HEADER_LEN + entries_len);
+    record.extend(COINBASE_PAYOUTS_MAGIC); // Magic
+    record.push(entries_len as u8); // Entries length (u8)
+    record.push(payouts.len() as u8); // Payout count (u8)
+    for payout in payouts.iter() {
+        record.extend(payout.weight.to_le_bytes()); // Weight (u32)
+        record.extend(payout.script_public_key.version().to_le_bytes()); // Script public key version (u16)
+        record.push(payout.script_public_key.script().len() as u8); // Script public key length (u8)
+        record.extend(payout.script_public_key.script()); // Script public key
+    }
+    record
+}
+
+/// Extracts the payout split record starting `extra_data`, if any.
+///
+/// Returns the decoded payouts along with the extra data following the record.
+pub fn decode_coinbase_payouts(extra_data: &[u8]) -> Option<(Vec<CoinbasePayout>, &[u8])> {
+    if extra_data.len() < COINBASE_PAYOUTS_HEADER_LEN || !extra_data.starts_with(&COINBASE_PAYOUTS_MAGIC) {
+        return None;
+    }
+    let entries_len = extra_data[COINBASE_PAYOUTS_MAGIC.len()] as usize;
+    let count = extra_data[COINBASE_PAYOUTS_MAGIC.len() + 1] as usize;
+    let entries_end = COINBASE_PAYOUTS_HEADER_LEN + entries_len;
+    if extra_data.len() < entries_end {
+        return None;
+    }
+
+    let mut entries = &extra_data[COINBASE_PAYOUTS_HEADER_LEN..entries_end];
+    let mut payouts = Vec::with_capacity(count);
+    for _ in 0..count {
+        if entries.len() < LENGTH_OF_PAYOUT_HEADER {
+            return None;
+        }
+        let weight = u32::from_le_bytes(entries[..LENGTH_OF_PAYOUT_WEIGHT].try_into().unwrap());
+        let version = u16::from_le_bytes(entries[LENGTH_OF_PAYOUT_WEIGHT..LENGTH_OF_PAYOUT_HEADER - 1].try_into().unwrap());
+        let script_len = entries[LENGTH_OF_PAYOUT_HEADER - 1] as usize;
+        entries = &entries[LENGTH_OF_PAYOUT_HEADER..];
+        if entries.len() < script_len {
+            return None;
+        }
+        let script_public_key = ScriptPublicKey::new(version, ScriptVec::from_slice(&entries[..script_len]));
+        entries = &entries[script_len..];
+        payouts.push(CoinbasePayout { script_public_key, weight });
+    }
+    if !entries.is_empty() {
+        return None;
+    }
+
+    Some((payouts, &extra_data[entries_end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payout(script: &[u8], weight: u32) -> CoinbasePayout {
+        CoinbasePayout::new(ScriptPublicKey::new(0, ScriptVec::from_slice(script)), weight)
+    }
+
+    #[test]
+    fn test_coinbase_payouts_round_trip() {
+        let payouts = vec![payout(&[0xaa; 34], 95), payout(&[0xbb; 35], 5), payout(&[], 1)];
+        let suffix = b"0.17.0/pool";
+        let record = encode_coinbase_payouts(&payouts);
+        assert_eq!(record.len(), coinbase_payouts_record_len(&payouts));
+
+        let extra_data = record.iter().chain(suffix.iter()).copied().collect::<Vec<_>>();
+        let (decoded, remaining) = decode_coinbase_payouts(&extra_data).unwrap();
+        assert_eq!(decoded, payouts);
+        assert_eq!(remaining, suffix);
+
+        // Plain extra data or a corrupted record carry no payout
+        assert!(decode_coinbase_payouts(suffix).is_none());
+        let mut corrupted = extra_data.clone();
+        corrupted[COINBASE_PAYOUTS_MAGIC.len() + 1] += 1;
+        assert!(decode_coinbase_payouts(&corrupted).is_none());
+
+        // Miner extra data containing a record after the version prefix is not mistaken for a split
+        let miner_extra_data = suffix.iter().chain(record.iter()).copied().collect::<Vec<_>>();
+        assert!(decode_coinbase_payouts(&miner_extra_data).is_none());
+    }
+}
@@ -17,6 +17,9 @@ use {
     params::Params,
 };
 
+/// Default maximum time span, in seconds, of missed chain changes a resumed subscription may replay
+pub const DEFAULT_NOTIFICATION_REPLAY_WINDOW: u64 = 600;
+
 /// Various consensus configurations all bundled up under a single struct. Use `Config::new` for directly building from
 /// a `Params` instance. For anything more complex it is recommended to use `ConfigBuilder`. NOTE: this struct can be
 /// implicitly de-refed into `Params`
@@ -38,6 +41,10 @@ pub struct Config {
     /// Enable various sanity checks which might be compute-intensive (mostly performed during pruning)
     pub enable_sanity_checks: bool,
 
+    /// Verify on start-up that the virtual UTXO set matches the UTXO commitment of the virtual state.
+    /// Iterates the entire UTXO set, so it is meant for tests and diagnostics
+    pub verify_utxo_set_on_open: bool,
+
     // TODO: move non-consensus parameters like utxoindex to a higher scoped Config
     /// Enable the UTXO index
     pub utxoindex: bool,
@@ -71,6 +78,13 @@ pub struct Config {
 
     /// The number of days to keep data for
     pub retention_period_days: Option<f64>,
+
+    /// The maximum time span, in seconds, of missed chain changes a resumed subscription may replay.
+    /// Zero disables notification replay.
+    pub notification_replay_window: u64,
+
+    /// Relay locally submitted transactions through a Dandelion++ stem phase before diffusing them
+    pub dandelion_relay: bool,
 }
 
 impl Config {
@@ -85,6 +99,7 @@ impl Config {
             process_genesis: true,
             is_archival: false,
             enable_sanity_checks: false,
+            verify_utxo_set_on_open: false,
             utxoindex: false,
             unsafe_rpc: false,
             enable_unsynced_mining: false,
@@ -99,6 +114,8 @@ impl Config {
             disable_upnp: false,
             ram_scale: 1.0,
             retention_period_days: None,
+            notification_replay_window: DEFAULT_NOTIFICATION_REPLAY_WINDOW,
+            dandelion_relay: false,
         }
     }
 
@@ -171,6 +188,11 @@ impl ConfigBuilder {
         self
     }
 
+    pub fn verify_utxo_set_on_open(mut self) -> Self {
+        self.config.verify_utxo_set_on_open = true;
+        self
+    }
+
     pub fn skip_adding_genesis(mut self) -> Self {
         self.config.process_genesis = false;
         self
@@ -13,6 +13,18 @@ pub enum CoinbaseError {
 
     #[error("coinbase payload length is {0} bytes but it needs to be at least {1} bytes long in order to accommodate the script public key")]
     PayloadCantContainScriptPublicKey(usize, usize),
+
+    #[error("coinbase payout split must contain between 1 and {1} payouts but contains {0}")]
+    PayoutCountOutOfRange(usize, usize),
+
+    #[error("coinbase payout {0} has a zero weight")]
+    ZeroPayoutWeight(usize),
+
+    #[error("coinbase payout {0} script public key length is {1} while the maximum allowed length is {2}")]
+    PayoutScriptPublicKeyLenAboveMax(usize, usize, u8),
+
+    #[error("coinbase payload with its payout split would be {0} bytes long while the maximum allowed length is {1}")]
+    PayoutsPayloadLenAboveMax(usize, usize),
 }
 
 pub type CoinbaseResult<T> = std::result::Result<T, CoinbaseError>;
@@ -17,6 +17,15 @@ pub enum ConfigError {
     #[error("Configuration: --max-tracked-addresses cannot be set above {0}")]
     MaxTrackedAddressesTooHigh(usize),
 
+    #[error("Configuration: --p2p-trusted-key '{0}' is not a valid hex-encoded public key")]
+    InvalidP2pTrustedKey(String),
+
+    #[error("Configuration: failed loading the P2P static key from '{0}': {1}")]
+    P2pStaticKey(String, String),
+
+    #[error("Configuration: unknown mining rule '{0}', expected one of: {1}")]
+    UnknownMiningRule(String, String),
+
     #[cfg(feature = "devnet-prealloc")]
     #[error("Cannot preallocate UTXOs on any network except devnet")]
     PreallocUtxosOnNonDevnet,
@@ -48,6 +48,9 @@ pub enum ConsensusError {
 
     #[error("{0}")]
     GeneralOwned(String),
+
+    #[error("consensus storage is inconsistent, possibly due to an interrupted write: {0}")]
+    InconsistentStorage(String),
 }
 
 pub type ConsensusResult<T> = std::result::Result<T, ConsensusError>;
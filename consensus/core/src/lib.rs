@@ -95,6 +95,19 @@ pub struct ChainPath {
     pub removed: Vec<Hash>,
 }
 
+/// A position along the selected chain, identified by a chain block and its DAA score
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainCursor {
+    pub hash: Hash,
+    pub daa_score: u64,
+}
+
+impl ChainCursor {
+    pub fn new(hash: Hash, daa_score: u64) -> Self {
+        Self { hash, daa_score }
+    }
+}
+
 /// `hashes::Hash` writes 4 u64s so we just use the last one as the hash here
 #[derive(Default, Clone, Copy)]
 pub struct BlockHasher(u64);
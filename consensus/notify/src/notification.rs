@@ -1,5 +1,5 @@
 use derive_more::Display;
-use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, utxo::utxo_diff::UtxoDiff};
+use kaspa_consensus_core::{acceptance_data::AcceptanceData, block::Block, tx::Transaction, utxo::utxo_diff::UtxoDiff, ChainCursor};
 use kaspa_hashes::Hash;
 use kaspa_notify::{
     events::EventType,
@@ -7,7 +7,7 @@ use kaspa_notify::{
     notification::Notification as NotificationTrait,
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{AddressFilteredSubscription, OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         Subscription,
     },
 };
@@ -42,6 +42,12 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "MempoolTransactionAdded notification: transaction id {}", "_0.transaction.id()")]
+    MempoolTransactionAdded(MempoolTransactionAddedNotification),
+
+    #[display(fmt = "MempoolTransactionRemoved notification: transaction id {} ({})", "_0.transaction.id()", "_0.reason")]
+    MempoolTransactionRemoved(MempoolTransactionRemovedNotification),
 }
 }
 
@@ -68,6 +74,7 @@ impl NotificationTrait for Notification {
                             removed_chain_block_hashes: payload.removed_chain_block_hashes.clone(),
                             added_chain_block_hashes: payload.added_chain_block_hashes.clone(),
                             added_chain_blocks_acceptance_data: Arc::new(vec![]),
+                            cursor: payload.cursor,
                         }));
                     }
                 }
@@ -87,6 +94,16 @@ impl NotificationTrait for Notification {
         Some(self.clone())
     }
 
+    fn apply_address_filtered_subscription(
+        &self,
+        _subscription: &AddressFilteredSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        // No effort is made here to apply the subscription addresses.
+        // This will be achieved farther along the notification backbone.
+        Some(self.clone())
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -108,14 +125,17 @@ pub struct VirtualChainChangedNotification {
     pub added_chain_block_hashes: Arc<Vec<Hash>>,
     pub removed_chain_block_hashes: Arc<Vec<Hash>>,
     pub added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+    /// The sink once the chain changes are applied
+    pub cursor: ChainCursor,
 }
 impl VirtualChainChangedNotification {
     pub fn new(
         added_chain_block_hashes: Arc<Vec<Hash>>,
         removed_chain_block_hashes: Arc<Vec<Hash>>,
         added_chain_blocks_acceptance_data: Arc<Vec<Arc<AcceptanceData>>>,
+        cursor: ChainCursor,
     ) -> Self {
-        Self { added_chain_block_hashes, removed_chain_block_hashes, added_chain_blocks_acceptance_data }
+        Self { added_chain_block_hashes, removed_chain_block_hashes, added_chain_blocks_acceptance_data, cursor }
     }
 }
 
@@ -146,11 +166,13 @@ pub struct UtxosChangedNotification {
     /// Accumulated UTXO diff between the last virtual state and the current virtual state
     pub accumulated_utxo_diff: Arc<UtxoDiff>,
     pub virtual_parents: Arc<Vec<Hash>>,
+    /// The sink of the current virtual state
+    pub cursor: ChainCursor,
 }
 
 impl UtxosChangedNotification {
-    pub fn new(accumulated_utxo_diff: Arc<UtxoDiff>, virtual_parents: Arc<Vec<Hash>>) -> Self {
-        Self { accumulated_utxo_diff, virtual_parents }
+    pub fn new(accumulated_utxo_diff: Arc<UtxoDiff>, virtual_parents: Arc<Vec<Hash>>, cursor: ChainCursor) -> Self {
+        Self { accumulated_utxo_diff, virtual_parents, cursor }
     }
 }
 
@@ -181,3 +203,46 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 
 #[derive(Debug, Clone)]
 pub struct NewBlockTemplateNotification {}
+
+#[derive(Debug, Clone)]
+pub struct MempoolTransactionAddedNotification {
+    pub transaction: Arc<Transaction>,
+    pub fee: u64,
+}
+
+impl MempoolTransactionAddedNotification {
+    pub fn new(transaction: Arc<Transaction>, fee: u64) -> Self {
+        Self { transaction, fee }
+    }
+}
+
+/// Reason why a transaction left the mempool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+pub enum MempoolTransactionRemovalReason {
+    #[display(fmt = "accepted")]
+    Accepted,
+    #[display(fmt = "replaced by fee")]
+    ReplacedByFee,
+    #[display(fmt = "double spend")]
+    DoubleSpend,
+    #[display(fmt = "evicted")]
+    Evicted,
+    #[display(fmt = "expired")]
+    Expired,
+    #[display(fmt = "invalid")]
+    Invalid,
+    #[display(fmt = "unspecified")]
+    Unspecified,
+}
+
+#[derive(Debug, Clone)]
+pub struct MempoolTransactionRemovedNotification {
+    pub transaction: Arc<Transaction>,
+    pub reason: MempoolTransactionRemovalReason,
+}
+
+impl MempoolTransactionRemovedNotification {
+    pub fn new(transaction: Arc<Transaction>, reason: MempoolTransactionRemovalReason) -> Self {
+        Self { transaction, reason }
+    }
+}
@@ -25,8 +25,9 @@ use crate::{
             selected_chain::SelectedChainStore,
             statuses::StatusesStoreReader,
             tips::{TipsStore, TipsStoreReader},
+            utxo_diffs::UtxoDiffsStoreReader,
             utxo_set::{UtxoSetStore, UtxoSetStoreReader},
-            virtual_state::VirtualState,
+            virtual_state::{VirtualState, VirtualStateStoreReader},
             DB,
         },
     },
@@ -53,7 +54,7 @@ use kaspa_consensus_core::{
     block::{Block, BlockTemplate, TemplateBuildMode, TemplateTransactionSelector, VirtualStateApproxId},
     blockhash::BlockHashExtensions,
     blockstatus::BlockStatus,
-    coinbase::MinerData,
+    coinbase::{CoinbasePayout, MinerData},
     daa_score_timestamp::DaaScoreTimestamp,
     errors::{
         coinbase::CoinbaseResult,
@@ -74,6 +75,7 @@ use kaspa_consensus_core::{
         MutableTransaction, Transaction, TransactionId, TransactionIndexType, TransactionOutpoint, TransactionQueryResult,
         TransactionType, UtxoEntry,
     },
+    utxo::utxo_diff::UtxoDiff,
     BlockHashSet, BlueWorkType, ChainPath, HashMapCustomHasher,
 };
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
@@ -159,6 +161,7 @@ impl Deref for Consensus {
 }
 
 impl Consensus {
+    /// Opens consensus over `db`, panicking if the stored state is inconsistent. See [`Self::try_new`]
     pub fn new(
         db: Arc<DB>,
         config: Arc<Config>,
@@ -169,6 +172,32 @@ impl Consensus {
         creation_timestamp: u64,
         mining_rules: Arc<MiningRules>,
     ) -> Self {
+        Self::try_new(
+            db,
+            config,
+            pruning_lock,
+            notification_root,
+            counters,
+            tx_script_cache_counters,
+            creation_timestamp,
+            mining_rules,
+        )
+        .unwrap_or_else(|err| panic!("failed to open consensus: {err}"))
+    }
+
+    /// Opens consensus over `db`. Returns [`ConsensusError::InconsistentStorage`] if the stored state breaks
+    /// invariants which every write preserves, as is the case when the process died in the midst of a write
+    /// which the storage did not apply atomically
+    pub fn try_new(
+        db: Arc<DB>,
+        config: Arc<Config>,
+        pruning_lock: SessionLock,
+        notification_root: Arc<ConsensusNotificationRoot>,
+        counters: Arc<ProcessingCounters>,
+        tx_script_cache_counters: Arc<TxScriptCacheCounters>,
+        creation_timestamp: u64,
+        mining_rules: Arc<MiningRules>,
+    ) -> ConsensusResult<Self> {
         let params = &config.params;
         let perf_params = &config.perf;
         let is_consensus_exiting: Arc<AtomicBool> = Arc::new(AtomicBool::new(false));
@@ -282,6 +311,7 @@ impl Consensus {
             &services,
             pruning_lock.clone(),
             config.clone(),
+            counters.clone(),
             is_consensus_exiting.clone(),
         ));
 
@@ -314,10 +344,66 @@ impl Consensus {
             is_consensus_exiting,
         };
 
+        // Refuse to start over an inconsistent state. Note that the init and genesis calls above
+        // have already run by now, but they only populate stores which are still empty; the
+        // database upgrades below and block processing only start once the state is verified
+        this.verify_storage_consistency()?;
+
         // Run database upgrades if any
         this.run_database_upgrades();
 
-        this
+        Ok(this)
+    }
+
+    /// Verifies the invariants between stores which are updated together by the pruning and virtual processors
+    fn verify_storage_consistency(&self) -> ConsensusResult<()> {
+        let inconsistent = |reason: String| Err(ConsensusError::InconsistentStorage(reason));
+
+        // The pruning point and the past pruning points are updated in the same batch
+        let (pruning_point, pruning_point_index) = self.pruning_point_store.read().pruning_point_and_index().unwrap();
+        if self.past_pruning_points_store.get(pruning_point_index).optional().unwrap() != Some(pruning_point) {
+            return inconsistent(format!(
+                "the pruning point {pruning_point} is not the past pruning point at index {pruning_point_index}"
+            ));
+        }
+        if let Some(next) = self.past_pruning_points_store.get(pruning_point_index + 1).optional().unwrap() {
+            return inconsistent(format!("the past pruning point {next} is registered beyond the pruning point {pruning_point}"));
+        }
+
+        // A consensus which has yet to import the pruning point UTXO set has no virtual state
+        let virtual_read = self.virtual_stores.read();
+        let Some(virtual_state) = virtual_read.state.get().optional().unwrap() else {
+            return Ok(());
+        };
+
+        // The virtual state, the selected chain and the virtual UTXO set are updated in the same batch
+        let sink = virtual_state.ghostdag_data.selected_parent;
+        match self.selected_chain_store.read().get_tip().optional().unwrap() {
+            Some((_, tip)) if tip == sink => {}
+            Some((_, tip)) => return inconsistent(format!("the selected chain tip {tip} is not the virtual sink {sink}")),
+            None => return inconsistent(format!("the selected chain is missing while the virtual sink is {sink}")),
+        }
+
+        // An intrusive pruning point update (IBD pruning catch-up) sets the virtual state to the new pruning point and drops
+        // the stable flag in the same batch, while the pruning point UTXO set is only imported and validated later. Such a
+        // state is resumed by IBD, so the sink status and the virtual UTXO set are not expected to be consistent yet.
+        if !self.pruning_meta_stores.read().pruning_utxoset_stable_flag() {
+            return Ok(());
+        }
+        if self.statuses_store.read().get(sink).optional().unwrap() != Some(BlockStatus::StatusUTXOValid) {
+            return inconsistent(format!("the virtual sink {sink} has no valid UTXO state"));
+        }
+        if self.config.verify_utxo_set_on_open {
+            info!("Verifying the virtual UTXO set against the virtual UTXO commitment");
+            let mut multiset = MuHash::new();
+            for (outpoint, entry) in virtual_read.utxo_set.iterator().map(|r| r.unwrap()) {
+                multiset.add_utxo(&outpoint, &entry);
+            }
+            if multiset.finalize() != virtual_state.multiset.clone().finalize() {
+                return inconsistent("the virtual UTXO set does not match the virtual UTXO commitment".to_string());
+            }
+        }
+        Ok(())
     }
 
     /// A procedure for calling database upgrades which are self-contained (i.e., do not require knowing the DB version)
@@ -1052,6 +1138,10 @@ impl ConsensusApi for Consensus {
         self.services.coinbase_manager.modify_coinbase_payload(payload, miner_data)
     }
 
+    fn encode_coinbase_payouts(&self, miner_data: MinerData, payouts: &[CoinbasePayout]) -> CoinbaseResult<MinerData> {
+        self.services.coinbase_manager.encode_coinbase_payouts(miner_data, payouts)
+    }
+
     fn calc_transaction_hash_merkle_root(&self, txs: &[Transaction]) -> Hash {
         calc_hash_merkle_root(txs.iter())
     }
@@ -1309,6 +1399,10 @@ impl ConsensusApi for Consensus {
             .collect::<ConsensusResult<Vec<_>>>()
     }
 
+    fn get_chain_block_utxo_diff(&self, hash: Hash) -> ConsensusResult<Arc<UtxoDiff>> {
+        self.utxo_diffs_store.get(hash).optional().unwrap().ok_or(ConsensusError::MissingData(hash))
+    }
+
     fn is_chain_block(&self, hash: Hash) -> ConsensusResult<bool> {
         self.is_chain_ancestor_of(hash, self.get_sink())
     }
@@ -1,5 +1,6 @@
 use async_channel::Sender;
 use kaspa_consensus_core::coinbase::MinerData;
+use kaspa_consensus_core::errors::consensus::ConsensusResult;
 use kaspa_consensus_core::mining_rules::MiningRules;
 use kaspa_consensus_core::tx::ScriptPublicKey;
 use kaspa_consensus_core::{
@@ -47,10 +48,15 @@ pub struct TestConsensus {
 impl TestConsensus {
     /// Creates a test consensus instance based on `config` with the provided `db` and `notification_sender`
     pub fn with_db(db: Arc<DB>, config: &Config, notification_sender: Sender<Notification>) -> Self {
+        Self::try_with_db(db, config, notification_sender).unwrap_or_else(|err| panic!("failed to open consensus: {err}"))
+    }
+
+    /// Like [`Self::with_db`], but returns an error if consensus refuses to open over the state found in `db`
+    pub fn try_with_db(db: Arc<DB>, config: &Config, notification_sender: Sender<Notification>) -> ConsensusResult<Self> {
         let notification_root = Arc::new(ConsensusNotificationRoot::new(notification_sender));
         let counters = Default::default();
         let tx_script_cache_counters = Default::default();
-        let consensus = Arc::new(Consensus::new(
+        let consensus = Arc::new(Consensus::try_new(
             db,
             Arc::new(config.clone()),
             Default::default(),
@@ -59,10 +65,10 @@ impl TestConsensus {
             tx_script_cache_counters,
             0,
             Arc::new(MiningRules::default()),
-        ));
+        )?);
         let block_builder = TestBlockBuilder::new(consensus.virtual_processor.clone());
 
-        Self { params: config.params.clone(), consensus, block_builder, db_lifetime: Default::default() }
+        Ok(Self { params: config.params.clone(), consensus, block_builder, db_lifetime: Default::default() })
     }
 
     /// Creates a test consensus instance based on `config` with a temp DB and the provided `notification_sender`
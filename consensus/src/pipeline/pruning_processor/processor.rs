@@ -27,6 +27,7 @@ use crate::{
 use crossbeam_channel::Receiver as CrossbeamReceiver;
 use itertools::Itertools;
 use kaspa_consensus_core::{
+    api::counters::ProcessingCounters,
     blockhash::ORIGIN,
     blockstatus::BlockStatus::StatusHeaderOnly,
     config::Config,
@@ -81,6 +82,9 @@ pub struct PruningProcessor {
     // Config
     config: Arc<Config>,
 
+    // Counters
+    counters: Arc<ProcessingCounters>,
+
     // Signals
     is_consensus_exiting: Arc<AtomicBool>,
 }
@@ -101,6 +105,7 @@ impl PruningProcessor {
         services: &Arc<ConsensusServices>,
         pruning_lock: SessionLock,
         config: Arc<Config>,
+        counters: Arc<ProcessingCounters>,
         is_consensus_exiting: Arc<AtomicBool>,
     ) -> Self {
         Self {
@@ -113,6 +118,7 @@ impl PruningProcessor {
             parents_manager: services.parents_manager.clone(),
             pruning_lock,
             config,
+            counters,
             is_consensus_exiting,
         }
     }
@@ -299,6 +305,7 @@ impl PruningProcessor {
             return;
         }
 
+        let pruning_start = Instant::now();
         info!("Header and Block pruning: preparing proof and anticone data...");
 
         let proof = self.pruning_proof_manager.get_pruning_point_proof();
@@ -565,6 +572,8 @@ impl PruningProcessor {
         drop(prune_guard);
 
         info!("Header and Block pruning completed: traversed: {}, pruned {}", traversed, counter);
+        self.counters.pruning_counts.fetch_add(1, Ordering::Relaxed);
+        self.counters.pruning_millis.fetch_add(pruning_start.elapsed().as_millis() as u64, Ordering::Relaxed);
         info!(
             "Header and Block pruning stats: proof size: {}, pruning point and anticone: {}, unique headers in proof and windows: {}, pruning points in history: {}",
             proof.iter().map(|l| l.len()).sum::<usize>(),
@@ -64,7 +64,7 @@ use kaspa_consensus_core::{
         utxo_diff::UtxoDiff,
         utxo_view::{UtxoView, UtxoViewComposition},
     },
-    BlockHashSet, ChainPath,
+    BlockHashSet, ChainCursor, ChainPath,
 };
 use kaspa_consensus_notify::{
     notification::{
@@ -345,11 +345,12 @@ impl VirtualStateProcessor {
         // Emit notifications
         let accumulated_diff = Arc::new(accumulated_diff);
         let virtual_parents = Arc::new(new_virtual_state.parents.clone());
+        let cursor = ChainCursor::new(new_sink, self.headers_store.get_daa_score(new_sink).unwrap());
         self.notification_root
             .notify(Notification::NewBlockTemplate(NewBlockTemplateNotification {}))
             .expect("expecting an open unbounded channel");
         self.notification_root
-            .notify(Notification::UtxosChanged(UtxosChangedNotification::new(accumulated_diff, virtual_parents)))
+            .notify(Notification::UtxosChanged(UtxosChangedNotification::new(accumulated_diff, virtual_parents, cursor)))
             .expect("expecting an open unbounded channel");
         self.notification_root
             .notify(Notification::SinkBlueScoreChanged(SinkBlueScoreChangedNotification::new(compact_sink_ghostdag_data.blue_score)))
@@ -366,6 +367,7 @@ impl VirtualStateProcessor {
                     chain_path.added.into(),
                     chain_path.removed.into(),
                     Arc::new(added_chain_blocks_acceptance_data),
+                    cursor,
                 )))
                 .expect("expecting an open unbounded channel");
         }
@@ -182,6 +182,42 @@ impl CoinbaseManager {
         Ok(payload)
     }
 
+    /// Records a weighted split of the block reward in the extra data of `miner_data`.
+    ///
+    /// Consensus pays the reward of a block with a single output to the script public key of its coinbase
+    /// payload, so the reward keeps being paid to the miner data script public key. The split is prepended
+    /// to the extra data as a payout record (see [`decode_coinbase_payouts`]) for pools to settle it.
+    pub fn encode_coinbase_payouts(&self, mut miner_data: MinerData, payouts: &[CoinbasePayout]) -> CoinbaseResult<MinerData> {
+        if payouts.is_empty() || payouts.len() > MAX_COINBASE_PAYOUTS {
+            return Err(CoinbaseError::PayoutCountOutOfRange(payouts.len(), MAX_COINBASE_PAYOUTS));
+        }
+        for (i, payout) in payouts.iter().enumerate() {
+            if payout.weight == 0 {
+                return Err(CoinbaseError::ZeroPayoutWeight(i));
+            }
+            let script_pub_key_len = payout.script_public_key.script().len();
+            if script_pub_key_len > self.coinbase_payload_script_public_key_max_len as usize {
+                return Err(CoinbaseError::PayoutScriptPublicKeyLenAboveMax(
+                    i,
+                    script_pub_key_len,
+                    self.coinbase_payload_script_public_key_max_len,
+                ));
+            }
+        }
+
+        let record_len = coinbase_payouts_record_len(payouts);
+        let payload_len = MIN_PAYLOAD_LENGTH + miner_data.script_public_key.script().len() + miner_data.extra_data.len() + record_len;
+        // The length of the record entries is encoded in a single byte
+        if payload_len > self.max_coinbase_payload_len || record_len - COINBASE_PAYOUTS_HEADER_LEN > u8::MAX as usize {
+            return Err(CoinbaseError::PayoutsPayloadLenAboveMax(payload_len, self.max_coinbase_payload_len));
+        }
+
+        let mut extra_data = encode_coinbase_payouts(payouts);
+        extra_data.extend(miner_data.extra_data);
+        miner_data.extra_data = extra_data;
+        Ok(miner_data)
+    }
+
     pub fn deserialize_coinbase_payload<'a>(&self, payload: &'a [u8]) -> CoinbaseResult<CoinbaseData<&'a [u8]>> {
         if payload.len() < MIN_PAYLOAD_LENGTH {
             return Err(CoinbaseError::PayloadLenBelowMin(payload.len(), MIN_PAYLOAD_LENGTH));
@@ -589,6 +625,39 @@ mod tests {
         assert_eq!(data2, deserialized_data);
     }
 
+    #[test]
+    fn coinbase_payouts_test() {
+        let cbm = create_manager(&MAINNET_PARAMS);
+
+        let pool_script = ScriptPublicKey::new(0, ScriptVec::from_slice(&[0xaa; 34]));
+        let fee_script = ScriptPublicKey::new(0, ScriptVec::from_slice(&[0xbb; 34]));
+        let payouts = vec![CoinbasePayout::new(pool_script.clone(), 98), CoinbasePayout::new(fee_script.clone(), 2)];
+        let miner_data = MinerData::new(pool_script.clone(), b"0.17.0/".to_vec());
+
+        let encoded = cbm.encode_coinbase_payouts(miner_data.clone(), &payouts).unwrap();
+        assert_eq!(encoded.script_public_key, pool_script, "the reward must keep being paid to the miner data script");
+        let data = CoinbaseData { blue_score: 56, subsidy: 44000000000, miner_data: encoded };
+        let payload = cbm.serialize_coinbase_payload(&data).unwrap();
+        let deserialized_data = cbm.deserialize_coinbase_payload(&payload).unwrap();
+        let (decoded, extra_data) = decode_coinbase_payouts(deserialized_data.miner_data.extra_data).unwrap();
+        assert_eq!(decoded, payouts);
+        assert_eq!(extra_data, miner_data.extra_data.as_slice());
+
+        assert!(matches!(cbm.encode_coinbase_payouts(miner_data.clone(), &[]), Err(CoinbaseError::PayoutCountOutOfRange(0, _))));
+        assert!(matches!(
+            cbm.encode_coinbase_payouts(miner_data.clone(), &[CoinbasePayout::new(fee_script.clone(), 0)]),
+            Err(CoinbaseError::ZeroPayoutWeight(0))
+        ));
+        let long_script = ScriptPublicKey::new(0, ScriptVec::from_slice(&[0xcc; 151]));
+        assert!(matches!(
+            cbm.encode_coinbase_payouts(miner_data.clone(), &[CoinbasePayout::new(long_script, 1)]),
+            Err(CoinbaseError::PayoutScriptPublicKeyLenAboveMax(0, 151, 150))
+        ));
+        // Mainnet payloads are limited to 204 bytes, leaving room for 3 P2PK payouts at most
+        let too_many = vec![CoinbasePayout::new(fee_script, 1); 4];
+        assert!(matches!(cbm.encode_coinbase_payouts(miner_data, &too_many), Err(CoinbaseError::PayoutsPayloadLenAboveMax(..))));
+    }
+
     fn create_manager(params: &Params) -> CoinbaseManager {
         CoinbaseManager::new(
             params.coinbase_payload_script_public_key_max_len,
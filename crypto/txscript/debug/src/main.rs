@@ -0,0 +1,272 @@
+use clap::{Arg, ArgAction, Command};
+use kaspa_consensus_core::{
+    hashing::sighash::SigHashReusedValuesUnsync,
+    tx::{PopulatedTransaction, Transaction, UtxoEntry},
+};
+use kaspa_txscript::{
+    asm::mnemonic,
+    caches::Cache,
+    opcodes::OpCond,
+    trace::{ExecutionTrace, TraceEntry, REDEEM_SCRIPT_INDEX, SCRIPT_PUBLIC_KEY_INDEX, SIGNATURE_SCRIPT_INDEX},
+    TxScriptEngine,
+};
+use kaspa_utils::hex::ToHex;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{self, Read},
+    process::ExitCode,
+};
+
+/// The debugger input: a transaction along with the UTXO entries spent by each of its inputs
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DebugInput {
+    transaction: Transaction,
+    utxo_entries: Vec<UtxoEntry>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InputReport {
+    input: usize,
+    error: Option<String>,
+    steps: Vec<StepReport>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StepReport {
+    script: &'static str,
+    pc: usize,
+    opcode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<String>,
+    executed: bool,
+    stack: Vec<String>,
+    alt_stack: Vec<String>,
+    cond_stack: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl From<&TraceEntry> for StepReport {
+    fn from(entry: &TraceEntry) -> Self {
+        Self {
+            script: match entry.script_index {
+                SIGNATURE_SCRIPT_INDEX => "signature",
+                SCRIPT_PUBLIC_KEY_INDEX => "spk",
+                REDEEM_SCRIPT_INDEX => "redeem",
+                _ => "unknown",
+            },
+            pc: entry.pc,
+            opcode: mnemonic(entry.opcode),
+            data: (!entry.data.is_empty()).then(|| entry.data.to_hex()),
+            executed: entry.executed,
+            stack: entry.dstack.iter().map(|item| item.to_hex()).collect(),
+            alt_stack: entry.astack.iter().map(|item| item.to_hex()).collect(),
+            cond_stack: entry
+                .cond_stack
+                .iter()
+                .map(|cond| match cond {
+                    OpCond::True => "true",
+                    OpCond::False => "false",
+                    OpCond::Skip => "skip",
+                })
+                .collect(),
+            error: entry.error.as_ref().map(|err| err.to_string()),
+        }
+    }
+}
+
+pub fn cli() -> Command {
+    Command::new("kaspa-script-debug")
+        .about(format!("{} v{}", env!("CARGO_PKG_DESCRIPTION"), env!("CARGO_PKG_VERSION")))
+        .version(env!("CARGO_PKG_VERSION"))
+        .arg(
+            Arg::new("file")
+                .value_name("FILE")
+                .default_value("-")
+                .help("JSON file holding `transaction` and `utxoEntries` (one per input). Use `-` to read from stdin"),
+        )
+        .arg(
+            Arg::new("input")
+                .long("input")
+                .short('i')
+                .value_name("INDEX")
+                .value_parser(clap::value_parser!(usize))
+                .help("Only debug the input at the given index. All inputs are debugged by default"),
+        )
+        .arg(Arg::new("json").long("json").action(ArgAction::SetTrue).help("Dump the execution trace as JSON"))
+}
+
+fn read_input(path: &str) -> Result<DebugInput, String> {
+    let text = match path {
+        "-" => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(|err| format!("failed reading stdin: {err}"))?;
+            text
+        }
+        _ => fs::read_to_string(path).map_err(|err| format!("failed reading {path}: {err}"))?,
+    };
+    let mut value: serde_json::Value = serde_json::from_str(&text).map_err(|err| format!("invalid JSON: {err}"))?;
+
+    // The transaction id is irrelevant to script execution, so allow omitting it and compute it below
+    if let Some(tx) = value.get_mut("transaction").and_then(|tx| tx.as_object_mut()) {
+        tx.entry("id").or_insert_with(|| [0u8; 32].as_slice().to_hex().into());
+    }
+    let mut input: DebugInput = serde_json::from_value(value).map_err(|err| format!("invalid input: {err}"))?;
+    input.transaction.finalize();
+
+    if input.utxo_entries.len() != input.transaction.inputs.len() {
+        return Err(format!(
+            "expected {} UTXO entries (one per transaction input) but got {}",
+            input.transaction.inputs.len(),
+            input.utxo_entries.len()
+        ));
+    }
+    Ok(input)
+}
+
+fn debug_input(populated: &PopulatedTransaction, index: usize) -> InputReport {
+    let sig_cache = Cache::new(0);
+    let reused_values = SigHashReusedValuesUnsync::new();
+    let mut trace = ExecutionTrace::new();
+    let mut vm = TxScriptEngine::from_transaction_input(
+        populated,
+        &populated.tx.inputs[index],
+        index,
+        &populated.entries[index],
+        &reused_values,
+        &sig_cache,
+    )
+    .with_tracer(&mut trace);
+    let result = vm.execute();
+
+    InputReport { input: index, error: result.err().map(|err| err.to_string()), steps: trace.steps.iter().map(Into::into).collect() }
+}
+
+/// Renders the trace of an input as text, one step per line followed by the stacks after the step
+fn format_report(report: &InputReport) -> String {
+    let mut lines = vec![format!("input #{}", report.input)];
+    for step in report.steps.iter() {
+        let op = match &step.data {
+            Some(data) => format!("{} <{}>", step.opcode, data),
+            None => step.opcode.clone(),
+        };
+        lines.push(format!("  {:>9} {:>5}  {}{}", step.script, step.pc, op, if step.executed { "" } else { " (skipped)" }));
+        lines.push(format!("  {:>15}  stack: [{}]", "", step.stack.join(", ")));
+        if !step.alt_stack.is_empty() {
+            lines.push(format!("  {:>15}  alt stack: [{}]", "", step.alt_stack.join(", ")));
+        }
+        if !step.cond_stack.is_empty() {
+            lines.push(format!("  {:>15}  cond stack: [{}]", "", step.cond_stack.join(", ")));
+        }
+        if let Some(err) = &step.error {
+            lines.push(format!("  {:>15}  error: {}", "", err));
+        }
+    }
+    match &report.error {
+        Some(err) => lines.push(format!("  result: FAILED: {err}")),
+        None => lines.push("  result: OK".to_string()),
+    }
+    lines.join("\n")
+}
+
+/// Returns the indexes of the inputs to debug: the requested one or all of them
+fn input_indexes(transaction: &Transaction, index: Option<usize>) -> Result<Vec<usize>, String> {
+    match index {
+        Some(index) if index >= transaction.inputs.len() => {
+            Err(format!("input index {index} is out of range, the transaction has {} inputs", transaction.inputs.len()))
+        }
+        Some(index) => Ok(vec![index]),
+        None => Ok((0..transaction.inputs.len()).collect()),
+    }
+}
+
+fn main() -> ExitCode {
+    let m = cli().get_matches();
+    let input = match read_input(m.get_one::<String>("file").unwrap()) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let indexes = match input_indexes(&input.transaction, m.get_one::<usize>("input").copied()) {
+        Ok(indexes) => indexes,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let populated = PopulatedTransaction::new(&input.transaction, input.utxo_entries);
+    let reports = indexes.into_iter().map(|index| debug_input(&populated, index)).collect::<Vec<_>>();
+
+    if m.get_flag("json") {
+        println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+    } else {
+        reports.iter().for_each(|report| println!("{}", format_report(report)));
+    }
+
+    match reports.iter().all(|report| report.error.is_none()) {
+        true => ExitCode::SUCCESS,
+        false => ExitCode::FAILURE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, TransactionId, TransactionInput, TransactionOutpoint},
+    };
+    use kaspa_txscript::asm::assemble;
+
+    fn spend(script_public_key: &str) -> (Transaction, UtxoEntry) {
+        let outpoint = TransactionOutpoint::new(TransactionId::from_u64_word(1), 0);
+        let transaction =
+            Transaction::new(0, vec![TransactionInput::new(outpoint, vec![], 0, 1)], vec![], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let script_public_key = ScriptPublicKey::new(0, assemble(script_public_key).unwrap().into());
+        (transaction, UtxoEntry::new(1000, script_public_key, 0, false))
+    }
+
+    #[test]
+    fn test_failing_report() {
+        let (transaction, entry) = spend("OP_1 OP_2 OP_EQUALVERIFY");
+        let populated = PopulatedTransaction::new(&transaction, vec![entry]);
+        let report = debug_input(&populated, 0);
+        assert!(report.error.is_some());
+        assert_eq!(report.steps.iter().filter(|step| step.error.is_some()).count(), 1);
+
+        let expected = [
+            "input #0",
+            "        spk     0  OP_TRUE",
+            "                   stack: [01]",
+            "        spk     1  OP_2",
+            "                   stack: [01, 02]",
+            "        spk     2  OP_EQUALVERIFY",
+            "                   stack: []",
+            "                   error: script ran, but verification failed",
+            "  result: FAILED: script ran, but verification failed",
+        ];
+        assert_eq!(format_report(&report), expected.join("\n"));
+
+        let (transaction, entry) = spend("OP_1");
+        let report = debug_input(&PopulatedTransaction::new(&transaction, vec![entry]), 0);
+        assert!(report.error.is_none());
+        assert_eq!(format_report(&report).lines().last(), Some("  result: OK"));
+    }
+
+    #[test]
+    fn test_input_indexes() {
+        let (transaction, _) = spend("OP_1");
+        assert_eq!(input_indexes(&transaction, None), Ok(vec![0]));
+        assert_eq!(input_indexes(&transaction, Some(0)), Ok(vec![0]));
+        let err = input_indexes(&transaction, Some(1)).unwrap_err();
+        assert!(err.contains("input index 1 is out of range"), "{err}");
+    }
+}
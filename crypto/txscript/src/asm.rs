@@ -0,0 +1,227 @@
+//!
+//! Human-readable script assembler and disassembler.
+//!
+//! The text form of a script is a sequence of whitespace separated tokens:
+//! - opcodes by name, e.g. `OP_DUP` or `OP_CHECKSIG`. Names are case insensitive and underscores are
+//!   ignored, so `OpCheckSig` is accepted as well. `OP_0` and `OP_1` are aliases of `OP_FALSE` and `OP_TRUE`
+//! - data pushes as hex enclosed in angle brackets, e.g. `<deadbeef>`, encoded with the canonical push opcode
+//! - explicit pushes as a push opcode followed by its data, e.g. `OP_PUSHDATA1 <01>`, encoded as is
+//! - decimal integers, e.g. `-1` or `1000`, encoded as minimal script numbers
+//!
+//! [`disassemble`] produces the canonical text of a script, which [`assemble`] turns back into the exact same bytes.
+//!
+
+use crate::{
+    opcodes::{codes, opcode_name, OP_DATA_MAX_VAL, OP_DATA_MIN_VAL},
+    script_builder::{ScriptBuilder, ScriptBuilderError},
+};
+use kaspa_utils::hex::{FromHex, ToHex};
+use std::iter::once;
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum AsmError {
+    #[error("unknown token '{0}'")]
+    UnknownToken(String),
+
+    #[error("invalid hex data '{0}'")]
+    InvalidHex(String),
+
+    #[error("{0} must be followed by its data")]
+    MissingPushData(String),
+
+    #[error("{0} cannot push {1} bytes of data")]
+    InvalidPushLength(String, usize),
+
+    #[error("script is truncated at offset {0}: {1} requires {2} more bytes but only {3} remain")]
+    Truncated(usize, String, usize, usize),
+
+    #[error(transparent)]
+    ScriptBuilder(#[from] ScriptBuilderError),
+}
+
+pub type AsmResult<T> = std::result::Result<T, AsmError>;
+
+/// Returns the canonical mnemonic of an opcode (e.g. `OP_CHECKSIG` for `0xac`)
+pub fn mnemonic(opcode: u8) -> String {
+    format!("OP_{}", opcode_name(opcode)[2..].to_uppercase())
+}
+
+/// Parses an opcode mnemonic, ignoring case and underscores
+pub fn parse_mnemonic(token: &str) -> Option<u8> {
+    let normalized = token.replace('_', "").to_uppercase();
+    match normalized.as_str() {
+        "OP0" => Some(codes::Op0),
+        "OP1" => Some(codes::Op1),
+        _ => (0..=u8::MAX).find(|&opcode| opcode_name(opcode).to_uppercase() == normalized),
+    }
+}
+
+/// Assembles the text form of a script into its bytes
+pub fn assemble(text: &str) -> AsmResult<Vec<u8>> {
+    let mut builder = ScriptBuilder::new();
+    let mut tokens = text.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if let Some(data) = parse_data(token)? {
+            builder.add_data(&data)?;
+        } else if token.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+            builder.add_i64(token.parse().map_err(|_| AsmError::UnknownToken(token.to_string()))?)?;
+        } else {
+            let opcode = parse_mnemonic(token).ok_or_else(|| AsmError::UnknownToken(token.to_string()))?;
+            if is_push_with_data(opcode) {
+                let data =
+                    tokens.next().map(parse_data).transpose()?.flatten().ok_or_else(|| AsmError::MissingPushData(mnemonic(opcode)))?;
+                builder.add_ops(&encode_push(opcode, &data)?)?;
+            } else {
+                builder.add_op(opcode)?;
+            }
+        }
+    }
+    Ok(builder.drain())
+}
+
+/// Disassembles script bytes into their canonical text form. Data pushes encoded canonically are rendered
+/// as `<hex>` while other pushes keep their opcode so that assembling the text yields the same bytes.
+pub fn disassemble(script: &[u8]) -> AsmResult<String> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    while offset < script.len() {
+        let opcode = script[offset];
+        if !is_push_with_data(opcode) {
+            tokens.push(mnemonic(opcode));
+            offset += 1;
+            continue;
+        }
+
+        let length_size = match opcode {
+            codes::OpPushData1 => 1,
+            codes::OpPushData2 => 2,
+            codes::OpPushData4 => 4,
+            _ => 0,
+        };
+        let length_bytes = read(script, offset + 1, length_size, opcode)?;
+        let data_len = match length_size {
+            0 => opcode as usize,
+            _ => length_bytes.iter().rev().fold(0usize, |len, &byte| (len << 8) | byte as usize),
+        };
+        let data_offset = offset + 1 + length_size;
+        let data = read(script, data_offset, data_len, opcode)?;
+        let end = data_offset + data_len;
+
+        let mut canonical = ScriptBuilder::new();
+        if canonical.add_data(data).is_ok_and(|builder| builder.script() == &script[offset..end]) {
+            tokens.push(format!("<{}>", data.to_hex()));
+        } else {
+            tokens.push(format!("{} <{}>", mnemonic(opcode), data.to_hex()));
+        }
+        offset = end;
+    }
+    Ok(tokens.join(" "))
+}
+
+#[inline]
+fn is_push_with_data(opcode: u8) -> bool {
+    (OP_DATA_MIN_VAL..=codes::OpPushData4).contains(&opcode)
+}
+
+fn read(script: &[u8], offset: usize, len: usize, opcode: u8) -> AsmResult<&[u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| script.get(offset..end))
+        .ok_or_else(|| AsmError::Truncated(offset, mnemonic(opcode), len, script.len().saturating_sub(offset)))
+}
+
+fn parse_data(token: &str) -> AsmResult<Option<Vec<u8>>> {
+    match token.strip_prefix('<').and_then(|token| token.strip_suffix('>')) {
+        Some(hex) => Vec::<u8>::from_hex(hex).map(Some).map_err(|_| AsmError::InvalidHex(token.to_string())),
+        None => Ok(None),
+    }
+}
+
+fn encode_push(opcode: u8, data: &[u8]) -> AsmResult<Vec<u8>> {
+    let len = data.len();
+    let header = match opcode {
+        codes::OpPushData1 if len <= u8::MAX as usize => vec![opcode, len as u8],
+        codes::OpPushData2 if len <= u16::MAX as usize => once(opcode).chain((len as u16).to_le_bytes()).collect(),
+        codes::OpPushData4 if len <= u32::MAX as usize => once(opcode).chain((len as u32).to_le_bytes()).collect(),
+        OP_DATA_MIN_VAL..=OP_DATA_MAX_VAL if len == opcode as usize => vec![opcode],
+        _ => return Err(AsmError::InvalidPushLength(mnemonic(opcode), len)),
+    };
+    Ok([header.as_slice(), data].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::opcodes::codes::*;
+
+    #[test]
+    fn test_assemble_p2pkh_like() {
+        let hash = [0x11u8; 32];
+        let text = format!("OP_DUP OP_BLAKE2B <{}> OP_EQUALVERIFY OP_CHECKSIG", hash.as_slice().to_hex());
+        let script = assemble(&text).unwrap();
+        let expected = [[OpDup, OpBlake2b, OpData32].as_slice(), hash.as_slice(), [OpEqualVerify, OpCheckSig].as_slice()].concat();
+        assert_eq!(script, expected);
+        assert_eq!(disassemble(&script).unwrap(), text);
+
+        // Alternative spellings are accepted
+        assert_eq!(
+            assemble(&format!("OpDup op_blake2b <{}> OP_EQUAL_VERIFY OpCheckSig", hash.as_slice().to_hex())).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_all_opcodes_roundtrip() {
+        for opcode in (0..=u8::MAX).filter(|&opcode| !is_push_with_data(opcode)) {
+            let text = disassemble(&[opcode]).unwrap();
+            assert_eq!(assemble(&text).unwrap(), vec![opcode], "opcode {opcode:#04x} rendered as {text}");
+        }
+        assert_eq!(disassemble(&[OpTxInputScriptSig, OpTxOutputSpk]).unwrap(), "OP_TXINPUTSCRIPTSIG OP_TXOUTPUTSPK");
+        assert_eq!(assemble("OP_0 OP_1 OP_16").unwrap(), vec![OpFalse, OpTrue, Op16]);
+    }
+
+    #[test]
+    fn test_pushes_roundtrip() {
+        let scripts = [
+            // Canonical pushes
+            vec![OpData2, 0xab, 0xcd],
+            [[OpPushData1, 80].as_slice(), &[7u8; 80]].concat(),
+            [[OpPushData2, 0x00, 0x02].as_slice(), &[7u8; 512]].concat(),
+            // Non canonical pushes
+            vec![OpData1, 0x05],
+            vec![OpData1, 0x00],
+            vec![OpPushData1, 2, 0xab, 0xcd],
+            vec![OpPushData4, 1, 0, 0, 0, 0xff],
+            [[OpPushData2, 0x10, 0x02].as_slice(), &[7u8; 528]].concat(),
+        ];
+        for script in scripts {
+            let text = disassemble(&script).unwrap();
+            assert_eq!(assemble(&text).unwrap(), script, "{text}");
+        }
+        assert_eq!(disassemble(&[OpData1, 0x05]).unwrap(), "OP_DATA1 <05>");
+        assert_eq!(disassemble(&[OpPushData1, 2, 0xab, 0xcd]).unwrap(), "OP_PUSHDATA1 <abcd>");
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(assemble("0 1 16 -1 17 1000").unwrap(), vec![OpFalse, OpTrue, Op16, Op1Negate, OpData1, 17, OpData2, 0xe8, 0x03]);
+        assert_eq!(disassemble(&assemble("1000").unwrap()).unwrap(), "<e803>");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(assemble("OP_FOO"), Err(AsmError::UnknownToken("OP_FOO".to_string())));
+        assert_eq!(assemble("12a"), Err(AsmError::UnknownToken("12a".to_string())));
+        assert_eq!(assemble("<abc>"), Err(AsmError::InvalidHex("<abc>".to_string())));
+        assert_eq!(assemble("OP_PUSHDATA1"), Err(AsmError::MissingPushData("OP_PUSHDATA1".to_string())));
+        assert_eq!(assemble("OP_PUSHDATA1 OP_DUP"), Err(AsmError::MissingPushData("OP_PUSHDATA1".to_string())));
+        assert_eq!(assemble("OP_DATA2 <ab>"), Err(AsmError::InvalidPushLength("OP_DATA2".to_string(), 1)));
+        assert_eq!(disassemble(&[OpDup, OpData2, 0xab]), Err(AsmError::Truncated(2, "OP_DATA2".to_string(), 2, 1)));
+        assert_eq!(disassemble(&[OpPushData2, 0x01]), Err(AsmError::Truncated(1, "OP_PUSHDATA2".to_string(), 2, 1)));
+        assert_eq!(
+            disassemble(&[OpPushData4, 0xff, 0xff, 0xff, 0xff, 0xab]),
+            Err(AsmError::Truncated(5, "OP_PUSHDATA4".to_string(), u32::MAX as usize, 1))
+        );
+    }
+}
@@ -1,6 +1,7 @@
 extern crate alloc;
 extern crate core;
 
+pub mod asm;
 pub mod caches;
 mod data_stack;
 pub mod error;
@@ -9,6 +10,7 @@ pub mod result;
 pub mod script_builder;
 pub mod script_class;
 pub mod standard;
+pub mod trace;
 #[cfg(feature = "wasm32-sdk")]
 pub mod wasm;
 
@@ -28,6 +30,7 @@ use log::trace;
 use opcodes::codes::OpReturn;
 use opcodes::{codes, to_small_int, OpCond};
 use script_class::ScriptClass;
+use trace::{ScriptTracer, TraceStep, REDEEM_SCRIPT_INDEX};
 
 pub mod prelude {
     pub use super::standard::*;
@@ -91,6 +94,8 @@ pub struct TxScriptEngine<'a, T: VerifiableTransaction, Reused: SigHashReusedVal
 
     num_ops: i32,
     runtime_sig_op_counter: RuntimeSigOpCounter,
+
+    tracer: Option<&'a mut dyn ScriptTracer>,
 }
 
 fn parse_script<T: VerifiableTransaction, Reused: SigHashReusedValues>(
@@ -232,6 +237,7 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             cond_stack: vec![],
             num_ops: 0,
             runtime_sig_op_counter: RuntimeSigOpCounter::new(u8::MAX),
+            tracer: None,
         }
     }
 
@@ -278,6 +284,7 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             cond_stack: Default::default(),
             num_ops: 0,
             runtime_sig_op_counter: RuntimeSigOpCounter::new(input.sig_op_count),
+            tracer: None,
         }
     }
 
@@ -292,9 +299,16 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             num_ops: 0,
             // Runtime sig op counting is not needed for standalone scripts, only inputs have sig op count value
             runtime_sig_op_counter: RuntimeSigOpCounter::new(u8::MAX),
+            tracer: None,
         }
     }
 
+    /// Attaches a tracer which is notified after each opcode processed by the engine
+    pub fn with_tracer(mut self, tracer: &'a mut dyn ScriptTracer) -> Self {
+        self.tracer = Some(tracer);
+        self
+    }
+
     #[inline]
     pub fn is_executing(&self) -> bool {
         self.cond_stack.is_empty() || *self.cond_stack.last().expect("Checked not empty") == OpCond::True
@@ -322,28 +336,21 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
         }
     }
 
-    fn execute_script(&mut self, script: &[u8], verify_only_push: bool) -> Result<(), TxScriptError> {
+    fn execute_script(&mut self, script: &[u8], script_index: usize, verify_only_push: bool) -> Result<(), TxScriptError> {
+        let mut pc = 0;
         let script_result = parse_script(script).try_for_each(|opcode| {
             let opcode = opcode?;
-            if opcode.is_disabled() {
-                return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
-            }
-
-            if opcode.always_illegal() {
-                return Err(TxScriptError::OpcodeReserved(format!("{:?}", opcode)));
-            }
-
-            if verify_only_push && !opcode.is_push_opcode() {
-                return Err(TxScriptError::SignatureScriptNotPushOnly);
-            }
+            let step = self.tracer.is_some().then(|| {
+                (opcode.value(), opcode.get_data().to_vec(), opcode.serialize().len(), self.is_executing() || opcode.is_conditional())
+            });
 
-            self.execute_opcode(opcode)?;
+            let result = self.execute_next_opcode(opcode, verify_only_push);
 
-            let combined_size = self.astack.len() + self.dstack.len();
-            if combined_size > MAX_STACK_SIZE {
-                return Err(TxScriptError::StackSizeExceeded(combined_size, MAX_STACK_SIZE));
+            if let Some((value, data, len, executed)) = step {
+                self.trace_step(script_index, pc, value, &data, executed, result.as_ref().err());
+                pc += len;
             }
-            Ok(())
+            result
         });
 
         // Moving between scripts - we can't be inside an if
@@ -358,6 +365,48 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
         script_result
     }
 
+    fn execute_next_opcode(
+        &mut self,
+        opcode: DynOpcodeImplementation<T, Reused>,
+        verify_only_push: bool,
+    ) -> Result<(), TxScriptError> {
+        if opcode.is_disabled() {
+            return Err(TxScriptError::OpcodeDisabled(format!("{:?}", opcode)));
+        }
+
+        if opcode.always_illegal() {
+            return Err(TxScriptError::OpcodeReserved(format!("{:?}", opcode)));
+        }
+
+        if verify_only_push && !opcode.is_push_opcode() {
+            return Err(TxScriptError::SignatureScriptNotPushOnly);
+        }
+
+        self.execute_opcode(opcode)?;
+
+        let combined_size = self.astack.len() + self.dstack.len();
+        if combined_size > MAX_STACK_SIZE {
+            return Err(TxScriptError::StackSizeExceeded(combined_size, MAX_STACK_SIZE));
+        }
+        Ok(())
+    }
+
+    fn trace_step(&mut self, script_index: usize, pc: usize, opcode: u8, data: &[u8], executed: bool, error: Option<&TxScriptError>) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.on_step(&TraceStep {
+                script_index,
+                pc,
+                opcode,
+                data,
+                executed,
+                dstack: &self.dstack,
+                astack: &self.astack,
+                cond_stack: &self.cond_stack,
+                error,
+            });
+        }
+    }
+
     pub fn execute(&mut self) -> Result<(), TxScriptError> {
         let (scripts, is_p2sh) = match &self.script_source {
             ScriptSource::TxInput { input, utxo_entry, is_p2sh, .. } => {
@@ -396,14 +445,14 @@ impl<'a, T: VerifiableTransaction, Reused: SigHashReusedValues> TxScriptEngine<'
             if is_p2sh && idx == 1 {
                 saved_stack = Some(self.dstack.clone());
             }
-            self.execute_script(s, verify_only_push)
+            self.execute_script(s, idx, verify_only_push)
         })?;
 
         if is_p2sh {
             self.check_error_condition(false)?;
             self.dstack = saved_stack.ok_or(TxScriptError::EmptyStack)?;
             let script = self.dstack.pop().ok_or(TxScriptError::EmptyStack)?;
-            self.execute_script(script.as_slice(), false)?
+            self.execute_script(script.as_slice(), REDEEM_SCRIPT_INDEX, false)?
         }
 
         self.check_error_condition(true)?;
@@ -851,6 +900,41 @@ mod tests {
         run_test_script_cases(test_cases)
     }
 
+    #[test]
+    fn test_execution_trace() {
+        let sig_cache = Cache::new(10_000);
+        let reused_values = SigHashReusedValuesUnsync::new();
+
+        // OpFalse, OpIf, OpData1 0x05, OpEndIf, OpData2 0xabcd, OpDup, OpEqual
+        let script = [OpFalse, OpIf, OpData1, 0x05, OpEndIf, OpData2, 0xab, 0xcd, OpDup, OpEqual];
+        let mut trace = trace::ExecutionTrace::new();
+        let mut vm =
+            TxScriptEngine::<VerifiableTransactionMock, SigHashReusedValuesUnsync>::from_script(&script, &reused_values, &sig_cache)
+                .with_tracer(&mut trace);
+        assert_eq!(vm.execute(), Ok(()));
+
+        let steps = &trace.steps;
+        assert_eq!(steps.iter().map(|step| step.pc).collect_vec(), vec![0, 1, 2, 4, 5, 8, 9]);
+        assert_eq!(steps.iter().map(|step| step.executed).collect_vec(), vec![true, true, false, true, true, true, true]);
+        assert_eq!(steps[1].cond_stack, vec![OpCond::False]);
+        assert_eq!(steps[2].data, vec![0x05]);
+        assert!(steps[3].cond_stack.is_empty());
+        assert_eq!(steps[5].dstack, vec![vec![0xab, 0xcd], vec![0xab, 0xcd]]);
+        assert_eq!(steps[6].dstack, vec![vec![1]]);
+        assert!(steps.iter().all(|step| step.error.is_none() && step.script_index == 0));
+
+        // The failing opcode is the last traced step and carries the error
+        let script = [OpTrue, OpVerify, OpVerify];
+        let mut trace = trace::ExecutionTrace::new();
+        let mut vm =
+            TxScriptEngine::<VerifiableTransactionMock, SigHashReusedValuesUnsync>::from_script(&script, &reused_values, &sig_cache)
+                .with_tracer(&mut trace);
+        let result = vm.execute();
+        assert!(result.is_err());
+        assert_eq!(trace.steps.len(), 3);
+        assert_eq!(trace.steps.last().unwrap().error, result.err());
+    }
+
     #[test]
     fn test_check_pub_key_encode() {
         let test_cases = vec![
@@ -122,6 +122,15 @@ macro_rules! opcode_list {
             }
         }
 
+        /// Returns the identifier of the opcode with the given value (e.g. `OpCheckSig` for `0xac`)
+        pub fn opcode_name(opcode: u8) -> &'static str {
+            match opcode {
+                $(
+                    $num => stringify!($name),
+                )*
+            }
+        }
+
         #[cfg(test)]
         use crate::script_builder::{ScriptBuilder, ScriptBuilderResult};
 
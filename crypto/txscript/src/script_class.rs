@@ -1,4 +1,4 @@
-use crate::{opcodes, MAX_SCRIPT_PUBLIC_KEY_VERSION};
+use crate::{covenants::Covenant, opcodes, MAX_SCRIPT_PUBLIC_KEY_VERSION};
 use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_addresses::Version;
 use kaspa_consensus_core::tx::{ScriptPublicKey, ScriptPublicKeyVersion};
@@ -30,6 +30,21 @@ pub enum ScriptClass {
     ScriptHash,
 }
 
+/// Classes of P2SH redeem scripts matching a [covenant template](crate::covenants)
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum RedeemScriptClass {
+    /// None of the covenant templates
+    NonStandard,
+    /// [`TimelockedVault`](crate::covenants::TimelockedVault) covenant
+    TimelockedVault,
+    /// [`Htlc`](crate::covenants::Htlc) covenant
+    Htlc,
+    /// [`RateLimitedSpend`](crate::covenants::RateLimitedSpend) covenant
+    RateLimitedSpend,
+    /// [`Escrow`](crate::covenants::Escrow) covenant
+    Escrow,
+}
+
 const NON_STANDARD: &str = "nonstandard";
 const PUB_KEY: &str = "pubkey";
 const PUB_KEY_ECDSA: &str = "pubkeyecdsa";
@@ -81,6 +96,21 @@ impl ScriptClass {
         (script_public_key[34] == opcodes::codes::OpEqual)
     }
 
+    /// Classifies a P2SH redeem script against the [covenant templates](crate::covenants).
+    ///
+    /// Covenants are paid to via pay-to-script-hash, so their script public key only commits to the hash
+    /// of the redeem script and [`ScriptClass::from_script`] can only report them as [`ScriptClass::ScriptHash`].
+    /// The redeem script must be known beforehand, or taken from the signature script of a spending input.
+    pub fn classify_redeem_script(redeem_script: &[u8]) -> RedeemScriptClass {
+        match Covenant::from_redeem_script(redeem_script) {
+            Some(Covenant::TimelockedVault(_)) => RedeemScriptClass::TimelockedVault,
+            Some(Covenant::Htlc(_)) => RedeemScriptClass::Htlc,
+            Some(Covenant::RateLimitedSpend(_)) => RedeemScriptClass::RateLimitedSpend,
+            Some(Covenant::Escrow(_)) => RedeemScriptClass::Escrow,
+            None => RedeemScriptClass::NonStandard,
+        }
+    }
+
     fn as_str(&self) -> &'static str {
         match self {
             ScriptClass::NonStandard => NON_STANDARD,
@@ -106,6 +136,18 @@ impl Display for ScriptClass {
     }
 }
 
+impl Display for RedeemScriptClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            RedeemScriptClass::NonStandard => NON_STANDARD,
+            RedeemScriptClass::TimelockedVault => "timelockedvault",
+            RedeemScriptClass::Htlc => "htlc",
+            RedeemScriptClass::RateLimitedSpend => "ratelimitedspend",
+            RedeemScriptClass::Escrow => "escrow",
+        })
+    }
+}
+
 impl FromStr for ScriptClass {
     type Err = Error;
 
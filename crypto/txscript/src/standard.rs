@@ -10,8 +10,10 @@ use kaspa_txscript_errors::TxScriptError;
 use smallvec::SmallVec;
 use std::iter::once;
 
+pub mod covenants;
 mod multisig;
 
+pub use covenants::{Covenant, CovenantTemplate, Error as CovenantError};
 pub use multisig::{multisig_redeem_script, multisig_redeem_script_ecdsa, Error as MultisigCreateError};
 
 /// Creates a new script to pay a transaction output to a 32-byte pubkey.
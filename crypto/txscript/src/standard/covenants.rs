@@ -0,0 +1,666 @@
+//!
+//! Parameterized covenant templates.
+//!
+//! Each template describes a redeem script to be paid to via pay-to-script-hash. Templates can
+//! be built from their parameters, detected (and their parameters recovered) from a redeem script,
+//! and provide the signature scripts matching each of their spending paths.
+//!
+//! Signatures passed to the signature script builders are 65 bytes long: a 64 bytes Schnorr
+//! signature followed by the sighash type.
+//!
+
+use crate::{
+    data_stack::OpcodeData,
+    opcodes::codes::{
+        self, Op2, Op3, OpCheckLockTimeVerify, OpCheckMultiSig, OpCheckSequenceVerify, OpCheckSig, OpCheckSigVerify, OpElse, OpEndIf,
+        OpEqualVerify, OpFalse, OpGreaterThan, OpIf, OpLessThanOrEqual, OpSHA256, OpSize, OpSub, OpTrue, OpTxInputAmount,
+        OpTxInputIndex, OpTxInputSpk, OpTxOutputAmount, OpTxOutputSpk,
+    },
+    opcodes::{OP_DATA_MAX_VAL, OP_DATA_MIN_VAL},
+    pay_to_script_hash_script,
+    script_builder::{ScriptBuilder, ScriptBuilderError},
+    SEQUENCE_LOCK_TIME_MASK,
+};
+use kaspa_addresses::{Address, Prefix};
+use kaspa_consensus_core::tx::ScriptPublicKey;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Length of the preimage revealed when claiming an [`Htlc`]
+pub const HTLC_PREIMAGE_LEN: usize = 32;
+
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum Error {
+    #[error(transparent)]
+    ScriptBuilderError(#[from] ScriptBuilderError),
+
+    #[error("relative lock {0} exceeds the maximum sequence lock of {SEQUENCE_LOCK_TIME_MASK}")]
+    InvalidRelativeLock(u64),
+
+    #[error("amount {0} cannot be represented as a positive script number")]
+    InvalidAmount(u64),
+
+    #[error("the preimage is {0} bytes long while {HTLC_PREIMAGE_LEN} bytes are expected")]
+    InvalidPreimageLength(usize),
+
+    #[error("expected {0} signatures but got {1}")]
+    InvalidSignatureCount(usize, usize),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Behavior shared by all covenant templates
+pub trait CovenantTemplate: Sized {
+    /// Builds the redeem script of the covenant
+    fn redeem_script(&self) -> Result<Vec<u8>>;
+
+    /// Recovers the covenant parameters from a redeem script. Returns `None` if the script is not an exact
+    /// instance of the template
+    fn from_redeem_script(redeem_script: &[u8]) -> Option<Self>;
+
+    /// The pay-to-script-hash script public key locking funds to the covenant
+    fn script_public_key(&self) -> Result<ScriptPublicKey> {
+        Ok(pay_to_script_hash_script(&self.redeem_script()?))
+    }
+
+    /// The pay-to-script-hash address locking funds to the covenant
+    fn address(&self, prefix: Prefix) -> Result<Address> {
+        let script_public_key = self.script_public_key()?;
+        Ok(crate::extract_script_pub_key_address(&script_public_key, prefix).expect("a P2SH script is standard"))
+    }
+}
+
+/// Funds spendable by the recovery key at any time, or by the owner key once `delay` (a relative
+/// DAA score lock, enforced through the input sequence) has elapsed since the funds were deposited.
+///
+/// ```text
+/// OP_IF
+///     <recovery> OP_CHECKSIG
+/// OP_ELSE
+///     <delay> OP_CHECKSEQUENCEVERIFY <owner> OP_CHECKSIG
+/// OP_ENDIF
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelockedVault {
+    pub owner: [u8; 32],
+    pub recovery: [u8; 32],
+    pub delay: u64,
+}
+
+impl TimelockedVault {
+    pub fn new(owner: [u8; 32], recovery: [u8; 32], delay: u64) -> Result<Self> {
+        check_relative_lock(delay)?;
+        Ok(Self { owner, recovery, delay })
+    }
+
+    /// Signature script spending through the recovery key
+    pub fn recovery_signature_script(&self, signature: &[u8]) -> Result<Vec<u8>> {
+        signature_script(&[signature], Some(true), &self.redeem_script()?)
+    }
+
+    /// Signature script spending through the owner key. The input sequence must be at least `delay`
+    pub fn owner_signature_script(&self, signature: &[u8]) -> Result<Vec<u8>> {
+        signature_script(&[signature], Some(false), &self.redeem_script()?)
+    }
+}
+
+impl CovenantTemplate for TimelockedVault {
+    fn redeem_script(&self) -> Result<Vec<u8>> {
+        Ok(ScriptBuilder::new()
+            .add_op(OpIf)?
+            .add_data(&self.recovery)?
+            .add_op(OpCheckSig)?
+            .add_op(OpElse)?
+            .add_sequence(self.delay)?
+            .add_op(OpCheckSequenceVerify)?
+            .add_data(&self.owner)?
+            .add_op(OpCheckSig)?
+            .add_op(OpEndIf)?
+            .drain())
+    }
+
+    fn from_redeem_script(redeem_script: &[u8]) -> Option<Self> {
+        let pushes = tokenize(redeem_script, 9)?;
+        let vault = Self { recovery: key(&pushes[1])?, delay: decode_u64(&pushes[4])?, owner: key(&pushes[6])? };
+        vault.matches(redeem_script)
+    }
+}
+
+/// Hash time locked contract, the building block of atomic swaps: the receiver claims the funds by
+/// revealing the SHA256 preimage of `payment_hash`, or the refund key takes them back once the
+/// transaction lock time reaches `lock_time` (a DAA score or a timestamp in milliseconds).
+///
+/// ```text
+/// OP_IF
+///     OP_SIZE <32> OP_EQUALVERIFY OP_SHA256 <payment_hash> OP_EQUALVERIFY <receiver> OP_CHECKSIG
+/// OP_ELSE
+///     <lock_time> OP_CHECKLOCKTIMEVERIFY <refund> OP_CHECKSIG
+/// OP_ENDIF
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Htlc {
+    pub receiver: [u8; 32],
+    pub refund: [u8; 32],
+    pub payment_hash: [u8; 32],
+    pub lock_time: u64,
+}
+
+impl Htlc {
+    pub fn new(receiver: [u8; 32], refund: [u8; 32], payment_hash: [u8; 32], lock_time: u64) -> Self {
+        Self { receiver, refund, payment_hash, lock_time }
+    }
+
+    /// Computes the payment hash matching a preimage
+    pub fn payment_hash(preimage: &[u8]) -> [u8; 32] {
+        Sha256::digest(preimage).into()
+    }
+
+    /// Signature script claiming the funds by revealing the preimage
+    pub fn claim_signature_script(&self, signature: &[u8], preimage: &[u8]) -> Result<Vec<u8>> {
+        if preimage.len() != HTLC_PREIMAGE_LEN {
+            return Err(Error::InvalidPreimageLength(preimage.len()));
+        }
+        signature_script(&[signature, preimage], Some(true), &self.redeem_script()?)
+    }
+
+    /// Recovers the preimage revealed by a signature script claiming the funds of this contract.
+    /// Returns `None` if the signature script is not a claim of this contract
+    pub fn extract_preimage(&self, signature_script: &[u8]) -> Option<[u8; HTLC_PREIMAGE_LEN]> {
+        let pushes = tokenize(signature_script, 4)?;
+        if pushes[2] != [1] || pushes[3] != self.redeem_script().ok()? {
+            return None;
+        }
+        let preimage: [u8; HTLC_PREIMAGE_LEN] = pushes[1].as_slice().try_into().ok()?;
+        (Self::payment_hash(&preimage) == self.payment_hash).then_some(preimage)
+    }
+
+    /// Signature script refunding the funds. The transaction lock time must be at least `lock_time`
+    /// and the input sequence must not be finalized
+    pub fn refund_signature_script(&self, signature: &[u8]) -> Result<Vec<u8>> {
+        signature_script(&[signature], Some(false), &self.redeem_script()?)
+    }
+}
+
+impl CovenantTemplate for Htlc {
+    fn redeem_script(&self) -> Result<Vec<u8>> {
+        Ok(ScriptBuilder::new()
+            .add_op(OpIf)?
+            .add_op(OpSize)?
+            .add_i64(HTLC_PREIMAGE_LEN as i64)?
+            .add_op(OpEqualVerify)?
+            .add_op(OpSHA256)?
+            .add_data(&self.payment_hash)?
+            .add_op(OpEqualVerify)?
+            .add_data(&self.receiver)?
+            .add_op(OpCheckSig)?
+            .add_op(OpElse)?
+            .add_lock_time(self.lock_time)?
+            .add_op(OpCheckLockTimeVerify)?
+            .add_data(&self.refund)?
+            .add_op(OpCheckSig)?
+            .add_op(OpEndIf)?
+            .drain())
+    }
+
+    fn from_redeem_script(redeem_script: &[u8]) -> Option<Self> {
+        let pushes = tokenize(redeem_script, 15)?;
+        let htlc = Self {
+            payment_hash: key(&pushes[5])?,
+            receiver: key(&pushes[7])?,
+            lock_time: decode_u64(&pushes[10])?,
+            refund: key(&pushes[12])?,
+        };
+        htlc.matches(redeem_script)
+    }
+}
+
+/// Funds the owner can only withdraw by steps of at most `max_amount`, once every `period` (a relative
+/// DAA score lock). Unless the whole balance is withdrawn, the output at the same index as the spent input
+/// must pay the change back to the covenant itself.
+///
+/// ```text
+/// <period> OP_CHECKSEQUENCEVERIFY <owner> OP_CHECKSIGVERIFY
+/// OP_TXINPUTINDEX OP_TXINPUTAMOUNT <max_amount> OP_GREATERTHAN
+/// OP_IF
+///     OP_TXINPUTINDEX OP_TXINPUTSPK OP_TXINPUTINDEX OP_TXOUTPUTSPK OP_EQUALVERIFY
+///     OP_TXINPUTINDEX OP_TXINPUTAMOUNT OP_TXINPUTINDEX OP_TXOUTPUTAMOUNT OP_SUB <max_amount> OP_LESSTHANOREQUAL
+/// OP_ELSE
+///     OP_TRUE
+/// OP_ENDIF
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitedSpend {
+    pub owner: [u8; 32],
+    pub max_amount: u64,
+    pub period: u64,
+}
+
+impl RateLimitedSpend {
+    pub fn new(owner: [u8; 32], max_amount: u64, period: u64) -> Result<Self> {
+        if max_amount == 0 || max_amount > i64::MAX as u64 {
+            return Err(Error::InvalidAmount(max_amount));
+        }
+        check_relative_lock(period)?;
+        Ok(Self { owner, max_amount, period })
+    }
+
+    /// Signature script withdrawing from the covenant. The input sequence must be at least `period`
+    pub fn signature_script(&self, signature: &[u8]) -> Result<Vec<u8>> {
+        signature_script(&[signature], None, &self.redeem_script()?)
+    }
+}
+
+impl CovenantTemplate for RateLimitedSpend {
+    fn redeem_script(&self) -> Result<Vec<u8>> {
+        let max_amount = i64::try_from(self.max_amount).map_err(|_| Error::InvalidAmount(self.max_amount))?;
+        Ok(ScriptBuilder::new()
+            .add_sequence(self.period)?
+            .add_op(OpCheckSequenceVerify)?
+            .add_data(&self.owner)?
+            .add_op(OpCheckSigVerify)?
+            .add_ops(&[OpTxInputIndex, OpTxInputAmount])?
+            .add_i64(max_amount)?
+            .add_op(OpGreaterThan)?
+            .add_op(OpIf)?
+            .add_ops(&[OpTxInputIndex, OpTxInputSpk, OpTxInputIndex, OpTxOutputSpk, OpEqualVerify])?
+            .add_ops(&[OpTxInputIndex, OpTxInputAmount, OpTxInputIndex, OpTxOutputAmount, OpSub])?
+            .add_i64(max_amount)?
+            .add_op(OpLessThanOrEqual)?
+            .add_op(OpElse)?
+            .add_op(OpTrue)?
+            .add_op(OpEndIf)?
+            .drain())
+    }
+
+    fn from_redeem_script(redeem_script: &[u8]) -> Option<Self> {
+        let pushes = tokenize(redeem_script, 24)?;
+        let max_amount = OpcodeData::<i64>::deserialize(&pushes[6]).ok().and_then(|amount| u64::try_from(amount).ok())?;
+        let covenant = Self { period: decode_u64(&pushes[0])?, owner: key(&pushes[2])?, max_amount };
+        covenant.matches(redeem_script)
+    }
+}
+
+/// Escrow between a buyer and a seller: any two of the buyer, the seller and the arbiter can release
+/// the funds, and the buyer alone gets refunded once the transaction lock time reaches `timeout`.
+///
+/// ```text
+/// OP_IF
+///     OP_2 <buyer> <seller> <arbiter> OP_3 OP_CHECKMULTISIG
+/// OP_ELSE
+///     <timeout> OP_CHECKLOCKTIMEVERIFY <buyer> OP_CHECKSIG
+/// OP_ENDIF
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Escrow {
+    pub buyer: [u8; 32],
+    pub seller: [u8; 32],
+    pub arbiter: [u8; 32],
+    pub timeout: u64,
+}
+
+impl Escrow {
+    /// Number of signatures required to release the funds
+    pub const RELEASE_SIGNATURES: usize = 2;
+
+    pub fn new(buyer: [u8; 32], seller: [u8; 32], arbiter: [u8; 32], timeout: u64) -> Self {
+        Self { buyer, seller, arbiter, timeout }
+    }
+
+    /// The keys in the order their signatures must appear in the release signature script
+    pub fn keys(&self) -> [[u8; 32]; 3] {
+        [self.buyer, self.seller, self.arbiter]
+    }
+
+    /// Signature script releasing the funds. Signatures must follow the order of [`Escrow::keys`]
+    pub fn release_signature_script(&self, signatures: &[&[u8]]) -> Result<Vec<u8>> {
+        if signatures.len() != Self::RELEASE_SIGNATURES {
+            return Err(Error::InvalidSignatureCount(Self::RELEASE_SIGNATURES, signatures.len()));
+        }
+        signature_script(signatures, Some(true), &self.redeem_script()?)
+    }
+
+    /// Signature script refunding the buyer. The transaction lock time must be at least `timeout`
+    /// and the input sequence must not be finalized
+    pub fn refund_signature_script(&self, signature: &[u8]) -> Result<Vec<u8>> {
+        signature_script(&[signature], Some(false), &self.redeem_script()?)
+    }
+}
+
+impl CovenantTemplate for Escrow {
+    fn redeem_script(&self) -> Result<Vec<u8>> {
+        Ok(ScriptBuilder::new()
+            .add_op(OpIf)?
+            .add_op(Op2)?
+            .add_data(&self.buyer)?
+            .add_data(&self.seller)?
+            .add_data(&self.arbiter)?
+            .add_op(Op3)?
+            .add_op(OpCheckMultiSig)?
+            .add_op(OpElse)?
+            .add_lock_time(self.timeout)?
+            .add_op(OpCheckLockTimeVerify)?
+            .add_data(&self.buyer)?
+            .add_op(OpCheckSig)?
+            .add_op(OpEndIf)?
+            .drain())
+    }
+
+    fn from_redeem_script(redeem_script: &[u8]) -> Option<Self> {
+        let pushes = tokenize(redeem_script, 13)?;
+        let escrow =
+            Self { buyer: key(&pushes[2])?, seller: key(&pushes[3])?, arbiter: key(&pushes[4])?, timeout: decode_u64(&pushes[8])? };
+        escrow.matches(redeem_script)
+    }
+}
+
+/// A redeem script detected as one of the covenant templates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Covenant {
+    TimelockedVault(TimelockedVault),
+    Htlc(Htlc),
+    RateLimitedSpend(RateLimitedSpend),
+    Escrow(Escrow),
+}
+
+impl Covenant {
+    pub fn from_redeem_script(redeem_script: &[u8]) -> Option<Self> {
+        TimelockedVault::from_redeem_script(redeem_script)
+            .map(Self::TimelockedVault)
+            .or_else(|| Htlc::from_redeem_script(redeem_script).map(Self::Htlc))
+            .or_else(|| RateLimitedSpend::from_redeem_script(redeem_script).map(Self::RateLimitedSpend))
+            .or_else(|| Escrow::from_redeem_script(redeem_script).map(Self::Escrow))
+    }
+}
+
+trait Matches: CovenantTemplate {
+    /// Returns the template if it rebuilds exactly into `redeem_script`
+    fn matches(self, redeem_script: &[u8]) -> Option<Self> {
+        self.redeem_script().is_ok_and(|script| script == redeem_script).then_some(self)
+    }
+}
+
+impl<T: CovenantTemplate> Matches for T {}
+
+fn check_relative_lock(lock: u64) -> Result<()> {
+    match lock > SEQUENCE_LOCK_TIME_MASK {
+        true => Err(Error::InvalidRelativeLock(lock)),
+        false => Ok(()),
+    }
+}
+
+/// Builds a P2SH signature script pushing `items`, then the optional branch selector, then the redeem script
+fn signature_script(items: &[&[u8]], branch: Option<bool>, redeem_script: &[u8]) -> Result<Vec<u8>> {
+    let mut builder = ScriptBuilder::new();
+    for item in items {
+        builder.add_data(item)?;
+    }
+    if let Some(branch) = branch {
+        builder.add_op(if branch { OpTrue } else { OpFalse })?;
+    }
+    builder.add_data(redeem_script)?;
+    Ok(builder.drain())
+}
+
+/// Splits a script into the data each of its opcodes pushes, returning `None` if the script is malformed
+/// or doesn't consist of exactly `expected_len` opcodes. Non-push opcodes map to empty data: templates
+/// are verified by rebuilding them, so only the positions of their parameters matter here.
+fn tokenize(script: &[u8], expected_len: usize) -> Option<Vec<Vec<u8>>> {
+    let mut pushes = Vec::with_capacity(expected_len);
+    let mut offset = 0;
+    while offset < script.len() {
+        if pushes.len() == expected_len {
+            return None;
+        }
+        let opcode = script[offset];
+        offset += 1;
+        let data_len = match opcode {
+            OP_DATA_MIN_VAL..=OP_DATA_MAX_VAL => opcode as usize,
+            codes::OpPushData1 | codes::OpPushData2 | codes::OpPushData4 => {
+                let size = match opcode {
+                    codes::OpPushData1 => 1,
+                    codes::OpPushData2 => 2,
+                    _ => 4,
+                };
+                let len = decode_u64(script.get(offset..offset + size)?)? as usize;
+                offset += size;
+                len
+            }
+            codes::Op1Negate => {
+                pushes.push(vec![0x81]);
+                continue;
+            }
+            codes::Op1..=codes::Op16 => {
+                pushes.push(vec![opcode - (codes::Op1 - 1)]);
+                continue;
+            }
+            _ => {
+                pushes.push(vec![]);
+                continue;
+            }
+        };
+        pushes.push(script.get(offset..offset + data_len)?.to_vec());
+        offset += data_len;
+    }
+    (pushes.len() == expected_len).then_some(pushes)
+}
+
+fn key(data: &[u8]) -> Option<[u8; 32]> {
+    data.try_into().ok()
+}
+
+fn decode_u64(data: &[u8]) -> Option<u64> {
+    (data.len() <= 8).then(|| data.iter().rev().fold(0u64, |value, &byte| (value << 8) | byte as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        caches::Cache,
+        script_class::{RedeemScriptClass, ScriptClass},
+        TxScriptEngine, MAX_TX_IN_SEQUENCE_NUM,
+    };
+    use kaspa_consensus_core::{
+        hashing::{
+            sighash::{calc_schnorr_signature_hash, SigHashReusedValuesUnsync},
+            sighash_type::SIG_HASH_ALL,
+        },
+        subnets::SubnetworkId,
+        tx::{
+            MutableTransaction, Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry,
+            VerifiableTransaction,
+        },
+    };
+    use kaspa_txscript_errors::TxScriptError;
+    use secp256k1::Keypair;
+
+    fn keypair(i: u8) -> Keypair {
+        Keypair::from_seckey_slice(secp256k1::SECP256K1, &[i; 32]).unwrap()
+    }
+
+    fn xonly(keypair: &Keypair) -> [u8; 32] {
+        keypair.x_only_public_key().0.serialize()
+    }
+
+    /// A transaction spending a covenant UTXO of `amount`, with a single output of `output_amount` paying to `output_spk`
+    fn spending_tx(
+        spk: &ScriptPublicKey,
+        amount: u64,
+        output_amount: u64,
+        output_spk: ScriptPublicKey,
+        lock_time: u64,
+        sequence: u64,
+        sig_op_count: u8,
+    ) -> MutableTransaction<Transaction> {
+        let input =
+            TransactionInput::new(TransactionOutpoint::new(TransactionId::from_u64_word(1), 0), vec![], sequence, sig_op_count);
+        let output = TransactionOutput::new(output_amount, output_spk);
+        let tx = Transaction::new(0, vec![input], vec![output], lock_time, SubnetworkId::from_bytes([0; 20]), 0, vec![]);
+        MutableTransaction::with_entries(tx, vec![UtxoEntry::new(amount, spk.clone(), 0, false)])
+    }
+
+    fn sign(tx: &MutableTransaction<Transaction>, keypair: &Keypair) -> Vec<u8> {
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let sig_hash = calc_schnorr_signature_hash(&tx.as_verifiable(), 0, SIG_HASH_ALL, &reused_values);
+        let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
+        let signature: [u8; 64] = *keypair.sign_schnorr(msg).as_ref();
+        signature.into_iter().chain(std::iter::once(SIG_HASH_ALL.to_u8())).collect()
+    }
+
+    fn execute(mut tx: MutableTransaction<Transaction>, signature_script: Vec<u8>) -> std::result::Result<(), TxScriptError> {
+        tx.tx.inputs[0].signature_script = signature_script;
+        let sig_cache = Cache::new(10_000);
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let verifiable = tx.as_verifiable();
+        let (input, entry) = verifiable.populated_input(0);
+        TxScriptEngine::from_transaction_input(&verifiable, input, 0, entry, &reused_values, &sig_cache).execute()
+    }
+
+    #[test]
+    fn test_timelocked_vault() {
+        let (owner, recovery) = (keypair(1), keypair(2));
+        let vault = TimelockedVault::new(xonly(&owner), xonly(&recovery), 1000).unwrap();
+        let redeem_script = vault.redeem_script().unwrap();
+        assert_eq!(TimelockedVault::from_redeem_script(&redeem_script), Some(vault.clone()));
+        assert_eq!(Covenant::from_redeem_script(&redeem_script), Some(Covenant::TimelockedVault(vault.clone())));
+        assert_eq!(ScriptClass::classify_redeem_script(&redeem_script), RedeemScriptClass::TimelockedVault);
+        assert_eq!(ScriptClass::from_script(&vault.script_public_key().unwrap()), ScriptClass::ScriptHash);
+        assert!(TimelockedVault::new(xonly(&owner), xonly(&recovery), SEQUENCE_LOCK_TIME_MASK + 1).is_err());
+
+        let spk = vault.script_public_key().unwrap();
+        let dest = ScriptPublicKey::from_vec(0, vec![OpTrue]);
+
+        // The recovery key can spend right away
+        let tx = spending_tx(&spk, 1000, 900, dest.clone(), 0, 0, 1);
+        let signature = sign(&tx, &recovery);
+        assert_eq!(execute(tx, vault.recovery_signature_script(&signature).unwrap()), Ok(()));
+
+        // The owner needs the relative lock to be satisfied
+        let tx = spending_tx(&spk, 1000, 900, dest.clone(), 0, 999, 1);
+        let signature = sign(&tx, &owner);
+        assert!(matches!(execute(tx, vault.owner_signature_script(&signature).unwrap()), Err(TxScriptError::UnsatisfiedLockTime(_))));
+        let tx = spending_tx(&spk, 1000, 900, dest.clone(), 0, 1000, 1);
+        let signature = sign(&tx, &owner);
+        assert_eq!(execute(tx, vault.owner_signature_script(&signature).unwrap()), Ok(()));
+
+        // The owner key cannot use the recovery path
+        let tx = spending_tx(&spk, 1000, 900, dest, 0, 0, 1);
+        let signature = sign(&tx, &owner);
+        assert_eq!(execute(tx, vault.recovery_signature_script(&signature).unwrap()), Err(TxScriptError::EvalFalse));
+    }
+
+    #[test]
+    fn test_htlc() {
+        let (receiver, refund) = (keypair(1), keypair(2));
+        let preimage = [7u8; 32];
+        let htlc = Htlc::new(xonly(&receiver), xonly(&refund), Htlc::payment_hash(&preimage), 5000);
+        let redeem_script = htlc.redeem_script().unwrap();
+        assert_eq!(Htlc::from_redeem_script(&redeem_script), Some(htlc.clone()));
+        assert_eq!(Covenant::from_redeem_script(&redeem_script), Some(Covenant::Htlc(htlc.clone())));
+        assert_eq!(ScriptClass::classify_redeem_script(&redeem_script), RedeemScriptClass::Htlc);
+
+        let spk = htlc.script_public_key().unwrap();
+        let dest = ScriptPublicKey::from_vec(0, vec![OpTrue]);
+
+        // Claim with the right preimage
+        let tx = spending_tx(&spk, 1000, 900, dest.clone(), 0, MAX_TX_IN_SEQUENCE_NUM, 1);
+        let signature = sign(&tx, &receiver);
+        assert_eq!(execute(tx.clone(), htlc.claim_signature_script(&signature, &preimage).unwrap()), Ok(()));
+        assert_eq!(htlc.extract_preimage(&htlc.claim_signature_script(&signature, &preimage).unwrap()), Some(preimage));
+        assert_eq!(htlc.extract_preimage(&htlc.claim_signature_script(&signature, &[8u8; 32]).unwrap()), None);
+        assert_eq!(htlc.extract_preimage(&htlc.refund_signature_script(&signature).unwrap()), None);
+        assert_eq!(execute(tx, htlc.claim_signature_script(&signature, &[8u8; 32]).unwrap()), Err(TxScriptError::VerifyError));
+        assert_eq!(htlc.claim_signature_script(&signature, &[7u8; 31]), Err(Error::InvalidPreimageLength(31)));
+
+        // Refund only after the lock time
+        let tx = spending_tx(&spk, 1000, 900, dest.clone(), 4999, 0, 1);
+        let signature = sign(&tx, &refund);
+        assert!(matches!(execute(tx, htlc.refund_signature_script(&signature).unwrap()), Err(TxScriptError::UnsatisfiedLockTime(_))));
+        let tx = spending_tx(&spk, 1000, 900, dest, 5000, 0, 1);
+        let signature = sign(&tx, &refund);
+        assert_eq!(execute(tx, htlc.refund_signature_script(&signature).unwrap()), Ok(()));
+    }
+
+    #[test]
+    fn test_rate_limited_spend() {
+        let owner = keypair(1);
+        let covenant = RateLimitedSpend::new(xonly(&owner), 100, 10).unwrap();
+        let redeem_script = covenant.redeem_script().unwrap();
+        assert_eq!(RateLimitedSpend::from_redeem_script(&redeem_script), Some(covenant.clone()));
+        assert_eq!(ScriptClass::classify_redeem_script(&redeem_script), RedeemScriptClass::RateLimitedSpend);
+        assert_eq!(RateLimitedSpend::new(xonly(&owner), 0, 10), Err(Error::InvalidAmount(0)));
+
+        let spk = covenant.script_public_key().unwrap();
+        let other = ScriptPublicKey::from_vec(0, vec![OpTrue]);
+
+        // Withdrawing at most `max_amount` while paying the change back to the covenant
+        let tx = spending_tx(&spk, 1000, 900, spk.clone(), 0, 10, 1);
+        let signature = sign(&tx, &owner);
+        assert_eq!(execute(tx, covenant.signature_script(&signature).unwrap()), Ok(()));
+
+        // Withdrawing too much
+        let tx = spending_tx(&spk, 1000, 899, spk.clone(), 0, 10, 1);
+        let signature = sign(&tx, &owner);
+        assert_eq!(execute(tx, covenant.signature_script(&signature).unwrap()), Err(TxScriptError::EvalFalse));
+
+        // Change not paid back to the covenant
+        let tx = spending_tx(&spk, 1000, 900, other.clone(), 0, 10, 1);
+        let signature = sign(&tx, &owner);
+        assert_eq!(execute(tx, covenant.signature_script(&signature).unwrap()), Err(TxScriptError::VerifyError));
+
+        // Too early
+        let tx = spending_tx(&spk, 1000, 900, spk.clone(), 0, 9, 1);
+        let signature = sign(&tx, &owner);
+        assert!(matches!(execute(tx, covenant.signature_script(&signature).unwrap()), Err(TxScriptError::UnsatisfiedLockTime(_))));
+
+        // A balance below `max_amount` can be withdrawn entirely
+        let tx = spending_tx(&spk, 100, 90, other, 0, 10, 1);
+        let signature = sign(&tx, &owner);
+        assert_eq!(execute(tx, covenant.signature_script(&signature).unwrap()), Ok(()));
+    }
+
+    #[test]
+    fn test_escrow() {
+        let (buyer, seller, arbiter) = (keypair(1), keypair(2), keypair(3));
+        let escrow = Escrow::new(xonly(&buyer), xonly(&seller), xonly(&arbiter), 5000);
+        let redeem_script = escrow.redeem_script().unwrap();
+        assert_eq!(Escrow::from_redeem_script(&redeem_script), Some(escrow.clone()));
+        assert_eq!(ScriptClass::classify_redeem_script(&redeem_script), RedeemScriptClass::Escrow);
+
+        let spk = escrow.script_public_key().unwrap();
+        let dest = ScriptPublicKey::from_vec(0, vec![OpTrue]);
+
+        // Seller and arbiter release the funds
+        let tx = spending_tx(&spk, 1000, 900, dest.clone(), 0, MAX_TX_IN_SEQUENCE_NUM, 3);
+        let signatures = [sign(&tx, &seller), sign(&tx, &arbiter)];
+        let signature_script = escrow.release_signature_script(&[&signatures[0], &signatures[1]]).unwrap();
+        assert_eq!(execute(tx.clone(), signature_script), Ok(()));
+        assert_eq!(escrow.release_signature_script(&[&signatures[0]]), Err(Error::InvalidSignatureCount(2, 1)));
+
+        // The buyer alone gets refunded after the timeout
+        let tx = spending_tx(&spk, 1000, 900, dest, 5000, 0, 1);
+        let signature = sign(&tx, &buyer);
+        assert_eq!(execute(tx, escrow.refund_signature_script(&signature).unwrap()), Ok(()));
+    }
+
+    #[test]
+    fn test_detection_rejects_variations() {
+        let vault = TimelockedVault::new([1; 32], [2; 32], 1000).unwrap();
+        let mut redeem_script = vault.redeem_script().unwrap();
+        assert!(Covenant::from_redeem_script(&redeem_script).is_some());
+
+        // Trailing opcode
+        redeem_script.push(OpTrue);
+        assert!(Covenant::from_redeem_script(&redeem_script).is_none());
+        redeem_script.pop();
+
+        // Swapped opcode
+        *redeem_script.last_mut().unwrap() = OpElse;
+        assert!(Covenant::from_redeem_script(&redeem_script).is_none());
+
+        // Truncated script
+        assert!(Covenant::from_redeem_script(&redeem_script[..10]).is_none());
+        assert!(Covenant::from_redeem_script(&[]).is_none());
+        assert_eq!(ScriptClass::classify_redeem_script(&redeem_script), RedeemScriptClass::NonStandard);
+    }
+}
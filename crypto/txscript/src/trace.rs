@@ -0,0 +1,88 @@
+//!
+//! Script execution tracing.
+//!
+//! A [`ScriptTracer`] attached to a [`TxScriptEngine`](crate::TxScriptEngine) is notified after every
+//! opcode the engine processes, with the resulting state of the main, alt and condition stacks.
+//! [`ExecutionTrace`] is a tracer which simply records all steps for later inspection.
+//!
+
+use crate::opcodes::OpCond;
+use kaspa_txscript_errors::TxScriptError;
+
+/// Index of the signature script in [`TraceStep::script_index`] when validating a transaction input
+pub const SIGNATURE_SCRIPT_INDEX: usize = 0;
+/// Index of the script public key in [`TraceStep::script_index`] when validating a transaction input
+pub const SCRIPT_PUBLIC_KEY_INDEX: usize = 1;
+/// Index of the redeem script of a P2SH spend in [`TraceStep::script_index`]
+pub const REDEEM_SCRIPT_INDEX: usize = 2;
+
+/// The engine state right after processing an opcode
+pub struct TraceStep<'s> {
+    /// Index of the executed script (see [`SIGNATURE_SCRIPT_INDEX`], [`SCRIPT_PUBLIC_KEY_INDEX`] and
+    /// [`REDEEM_SCRIPT_INDEX`]). For standalone scripts this is the position of the script in the engine
+    pub script_index: usize,
+    /// Byte offset of the opcode in the script
+    pub pc: usize,
+    pub opcode: u8,
+    /// Data pushed by the opcode, if any
+    pub data: &'s [u8],
+    /// False if the opcode was skipped because it is located in a non-taken conditional branch
+    pub executed: bool,
+    pub dstack: &'s [Vec<u8>],
+    pub astack: &'s [Vec<u8>],
+    pub cond_stack: &'s [OpCond],
+    /// The error the opcode failed with. The engine stops right after a failing step
+    pub error: Option<&'s TxScriptError>,
+}
+
+pub trait ScriptTracer {
+    fn on_step(&mut self, step: &TraceStep);
+}
+
+/// An owned copy of a [`TraceStep`]
+#[derive(Clone, Debug)]
+pub struct TraceEntry {
+    pub script_index: usize,
+    pub pc: usize,
+    pub opcode: u8,
+    pub data: Vec<u8>,
+    pub executed: bool,
+    pub dstack: Vec<Vec<u8>>,
+    pub astack: Vec<Vec<u8>>,
+    pub cond_stack: Vec<OpCond>,
+    pub error: Option<TxScriptError>,
+}
+
+impl From<&TraceStep<'_>> for TraceEntry {
+    fn from(step: &TraceStep) -> Self {
+        Self {
+            script_index: step.script_index,
+            pc: step.pc,
+            opcode: step.opcode,
+            data: step.data.to_vec(),
+            executed: step.executed,
+            dstack: step.dstack.to_vec(),
+            astack: step.astack.to_vec(),
+            cond_stack: step.cond_stack.to_vec(),
+            error: step.error.cloned(),
+        }
+    }
+}
+
+/// Records every step of a script execution
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionTrace {
+    pub steps: Vec<TraceEntry>,
+}
+
+impl ExecutionTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ScriptTracer for ExecutionTrace {
+    fn on_step(&mut self, step: &TraceStep) {
+        self.steps.push(step.into());
+    }
+}
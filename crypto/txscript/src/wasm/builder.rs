@@ -1,5 +1,5 @@
 use crate::result::Result;
-use crate::{script_builder as native, standard};
+use crate::{asm, script_builder as native, standard};
 use kaspa_consensus_core::tx::ScriptPublicKey;
 use kaspa_utils::hex::ToHex;
 use kaspa_wasm_core::hex::{HexViewConfig, HexViewConfigT};
@@ -58,6 +58,17 @@ impl ScriptBuilder {
         Ok(builder)
     }
 
+    /// Creates a new ScriptBuilder from the human-readable text form of a script,
+    /// e.g. `OP_DUP OP_BLAKE2B <hex> OP_EQUALVERIFY OP_CHECKSIG`.
+    #[wasm_bindgen(js_name = "fromAsm")]
+    pub fn from_asm(text: &str) -> Result<ScriptBuilder> {
+        let builder = ScriptBuilder::default();
+        let script = asm::assemble(text)?;
+        builder.inner_mut().script_mut().extend(&script);
+
+        Ok(builder)
+    }
+
     /// Pushes the passed opcode to the end of the script. The script will not
     /// be modified if pushing the opcode would cause the script to exceed the
     /// maximum allowed script engine size.
@@ -168,6 +179,14 @@ impl ScriptBuilder {
         Ok(generated_script.to_hex().into())
     }
 
+    /// Get the human-readable text form of the script.
+    #[wasm_bindgen(js_name = "toAsm")]
+    pub fn to_asm(&self) -> Result<String> {
+        let inner = self.inner();
+
+        Ok(asm::disassemble(inner.script())?)
+    }
+
     #[wasm_bindgen(js_name = "hexView")]
     pub fn hex_view(&self, args: Option<HexViewConfigT>) -> Result<String> {
         let inner = self.inner();
@@ -1,6 +1,8 @@
-use rocksdb::{DBWithThreadMode, MultiThreaded};
+use rocksdb::{DBWithThreadMode, MultiThreaded, WriteBatch};
 use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
+#[cfg(feature = "fault-injection")]
+use {crate::fault::FaultInjector, parking_lot::RwLock, std::sync::Arc};
 
 pub use conn_builder::ConnBuilder;
 use kaspa_utils::fd_budget::FDGuard;
@@ -13,11 +15,58 @@ mod rocksdb_preset;
 pub struct DB {
     inner: DBWithThreadMode<MultiThreaded>,
     _fd_guard: FDGuard,
+    #[cfg(feature = "fault-injection")]
+    fault_injector: RwLock<Option<Arc<FaultInjector>>>,
 }
 
 impl DB {
     pub fn new(inner: DBWithThreadMode<MultiThreaded>, fd_guard: FDGuard) -> Self {
-        Self { inner, _fd_guard: fd_guard }
+        Self {
+            inner,
+            _fd_guard: fd_guard,
+            #[cfg(feature = "fault-injection")]
+            fault_injector: Default::default(),
+        }
+    }
+
+    // The write methods below shadow the ones of the inner DB so that all writes go through the fault injector, if any
+
+    pub fn write(&self, batch: WriteBatch) -> Result<(), rocksdb::Error> {
+        #[cfg(feature = "fault-injection")]
+        if let Some(injector) = self.fault_injector.read().as_ref() {
+            return injector.write(&self.inner, batch);
+        }
+        self.inner.write(batch)
+    }
+
+    pub fn put<K, V>(&self, key: K, value: V) -> Result<(), rocksdb::Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        #[cfg(feature = "fault-injection")]
+        if let Some(injector) = self.fault_injector.read().as_ref() {
+            let mut batch = WriteBatch::default();
+            batch.put(key, value);
+            return injector.write(&self.inner, batch);
+        }
+        self.inner.put(key, value)
+    }
+
+    pub fn delete<K: AsRef<[u8]>>(&self, key: K) -> Result<(), rocksdb::Error> {
+        #[cfg(feature = "fault-injection")]
+        if let Some(injector) = self.fault_injector.read().as_ref() {
+            let mut batch = WriteBatch::default();
+            batch.delete(key);
+            return injector.write(&self.inner, batch);
+        }
+        self.inner.delete(key)
+    }
+
+    /// Installs an injector which all subsequent writes go through, or removes the current one if `None`
+    #[cfg(feature = "fault-injection")]
+    pub fn set_fault_injector(&self, injector: Option<Arc<FaultInjector>>) {
+        *self.fault_injector.write() = injector;
     }
 }
 
@@ -0,0 +1,229 @@
+//!
+//! Deterministic fault injection into DB writes, used by crash-recovery tests.
+//!
+//! An injector installed through [`DB::set_fault_injector`](crate::prelude::DB::set_fault_injector) counts the writes reaching the DB (a `WriteBatch`,
+//! or a single `put`/`delete`) and, once its [`FaultTrigger`] matches, applies its [`FaultAction`] to the
+//! matching write. From that point on the storage behaves as the disk of a dead process: every later write is
+//! silently dropped, so the DB directory can be reopened to check how consensus recovers from the interrupted state.
+//!
+
+use crate::registry::DatabaseStorePrefixes;
+use parking_lot::Mutex;
+use rocksdb::{DBWithThreadMode, MultiThreaded, WriteBatch, WriteBatchIterator, WriteOptions};
+
+/// Selects the write a fault is injected into. Writes are counted from the installation of the injector.
+#[derive(Debug, Clone, Copy)]
+pub enum FaultTrigger {
+    /// The `n`-th write (zero-based)
+    NthWrite(usize),
+    /// The `n`-th write (zero-based) touching at least one key of the store with `prefix`
+    NthWriteTo { prefix: DatabaseStorePrefixes, n: usize },
+}
+
+/// What happens to the write selected by the trigger
+#[derive(Debug, Clone, Copy)]
+pub enum FaultAction {
+    /// The write is rejected with a RocksDB error and none of its operations are applied
+    Fail,
+    /// Only the first `applied_ops` operations of the write are applied and the write reports success,
+    /// as if the process was killed midway through it. Note that RocksDB applies batches atomically, so
+    /// this simulates storage without such a guarantee. Range deletions of a partial batch are never applied.
+    Abort { applied_ops: usize },
+}
+
+/// Describes the write a fault was injected into
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InjectedFault {
+    /// The index of the faulty write among all writes counted by the injector
+    pub write_index: usize,
+    /// The number of put and delete operations of the faulty write
+    pub total_ops: usize,
+    /// The number of operations which were applied
+    pub applied_ops: usize,
+    /// The distinct store prefixes touched by the faulty write
+    pub prefixes: Vec<u8>,
+}
+
+#[derive(Default)]
+struct InjectorState {
+    writes: usize,
+    matching_writes: usize,
+    injected: Option<InjectedFault>,
+    dropped_writes: usize,
+}
+
+pub struct FaultInjector {
+    trigger: FaultTrigger,
+    action: FaultAction,
+    state: Mutex<InjectorState>,
+}
+
+enum BatchOp {
+    Put(Box<[u8]>, Box<[u8]>),
+    Delete(Box<[u8]>),
+}
+
+impl BatchOp {
+    fn key(&self) -> &[u8] {
+        match self {
+            BatchOp::Put(key, _) | BatchOp::Delete(key) => key,
+        }
+    }
+}
+
+#[derive(Default)]
+struct BatchOps(Vec<BatchOp>);
+
+impl WriteBatchIterator for BatchOps {
+    fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.0.push(BatchOp::Put(key.into(), value.into()));
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        self.0.push(BatchOp::Delete(key.into()));
+    }
+}
+
+impl FaultInjector {
+    pub fn new(trigger: FaultTrigger, action: FaultAction) -> Self {
+        Self { trigger, action, state: Default::default() }
+    }
+
+    /// The fault injected so far, if the trigger matched
+    pub fn injected(&self) -> Option<InjectedFault> {
+        self.state.lock().injected.clone()
+    }
+
+    /// The number of writes counted by the injector, including the faulty one
+    pub fn writes(&self) -> usize {
+        self.state.lock().writes
+    }
+
+    /// The number of writes dropped after the fault was injected
+    pub fn dropped_writes(&self) -> usize {
+        self.state.lock().dropped_writes
+    }
+
+    pub(crate) fn write(&self, db: &DBWithThreadMode<MultiThreaded>, batch: WriteBatch) -> Result<(), rocksdb::Error> {
+        // The lock is held while writing so that the order in which writes are counted is the order they reach the DB
+        let mut state = self.state.lock();
+        if state.injected.is_some() {
+            state.dropped_writes += 1;
+            return Ok(());
+        }
+
+        let mut ops = BatchOps::default();
+        batch.iterate(&mut ops);
+        let write_index = state.writes;
+        state.writes += 1;
+        let triggered = match self.trigger {
+            FaultTrigger::NthWrite(n) => write_index == n,
+            FaultTrigger::NthWriteTo { prefix, n } => {
+                if ops.0.iter().any(|op| op.key().first() == Some(&(prefix as u8))) {
+                    state.matching_writes += 1;
+                    state.matching_writes == n + 1
+                } else {
+                    false
+                }
+            }
+        };
+        if !triggered {
+            return db.write(batch);
+        }
+
+        let mut prefixes = ops.0.iter().filter_map(|op| op.key().first().copied()).collect::<Vec<_>>();
+        prefixes.sort_unstable();
+        prefixes.dedup();
+        let total_ops = ops.0.len();
+        let (result, applied_ops) = match self.action {
+            FaultAction::Fail => (db.write_opt(batch, &failing_write_options()), 0),
+            FaultAction::Abort { applied_ops } => {
+                let applied_ops = applied_ops.min(total_ops);
+                let mut partial = WriteBatch::default();
+                for op in ops.0.into_iter().take(applied_ops) {
+                    match op {
+                        BatchOp::Put(key, value) => partial.put(key, value),
+                        BatchOp::Delete(key) => partial.delete(key),
+                    }
+                }
+                (db.write(partial), applied_ops)
+            }
+        };
+        state.injected = Some(InjectedFault { write_index, total_ops, applied_ops, prefixes });
+        result
+    }
+}
+
+/// Write options which RocksDB rejects with an `InvalidArgument` error before applying anything
+fn failing_write_options() -> WriteOptions {
+    let mut options = WriteOptions::default();
+    options.set_sync(true);
+    options.disable_wal(true);
+    options
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{create_temp_db, prelude::ConnBuilder};
+    use std::sync::Arc;
+
+    fn key(prefix: DatabaseStorePrefixes, i: u8) -> [u8; 2] {
+        [prefix as u8, i]
+    }
+
+    fn batch(prefix: DatabaseStorePrefixes, ops: u8) -> WriteBatch {
+        let mut batch = WriteBatch::default();
+        for i in 0..ops {
+            batch.put(key(prefix, i), [i]);
+        }
+        batch
+    }
+
+    #[test]
+    fn test_fail_action() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let injector = Arc::new(FaultInjector::new(FaultTrigger::NthWrite(1), FaultAction::Fail));
+        db.set_fault_injector(Some(injector.clone()));
+
+        db.write(batch(DatabaseStorePrefixes::Statuses, 2)).unwrap();
+        assert!(db.write(batch(DatabaseStorePrefixes::VirtualState, 2)).is_err());
+        // The storage is frozen once the fault is injected
+        db.put(key(DatabaseStorePrefixes::Tips, 0), [0]).unwrap();
+
+        assert_eq!(
+            injector.injected(),
+            Some(InjectedFault {
+                write_index: 1,
+                total_ops: 2,
+                applied_ops: 0,
+                prefixes: vec![DatabaseStorePrefixes::VirtualState as u8]
+            })
+        );
+        assert_eq!(injector.dropped_writes(), 1);
+        db.set_fault_injector(None);
+        assert!(db.get(key(DatabaseStorePrefixes::Statuses, 1)).unwrap().is_some());
+        assert!(db.get(key(DatabaseStorePrefixes::VirtualState, 0)).unwrap().is_none());
+        assert!(db.get(key(DatabaseStorePrefixes::Tips, 0)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_abort_action() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let trigger = FaultTrigger::NthWriteTo { prefix: DatabaseStorePrefixes::VirtualState, n: 1 };
+        let injector = Arc::new(FaultInjector::new(trigger, FaultAction::Abort { applied_ops: 3 }));
+        db.set_fault_injector(Some(injector.clone()));
+
+        db.write(batch(DatabaseStorePrefixes::VirtualState, 1)).unwrap();
+        db.delete(key(DatabaseStorePrefixes::Statuses, 0)).unwrap();
+        assert!(injector.injected().is_none());
+        db.write(batch(DatabaseStorePrefixes::VirtualState, 5)).unwrap();
+
+        assert_eq!(injector.writes(), 3);
+        assert_eq!(injector.injected().unwrap().applied_ops, 3);
+        db.set_fault_injector(None);
+        for i in 0..5 {
+            assert_eq!(db.get(key(DatabaseStorePrefixes::VirtualState, i)).unwrap().is_some(), i < 3);
+        }
+    }
+}
@@ -6,6 +6,8 @@ mod item;
 mod key;
 mod writer;
 
+#[cfg(feature = "fault-injection")]
+pub mod fault;
 pub mod registry;
 mod set_access;
 pub mod utils;
@@ -1,5 +1,5 @@
 use crate::prelude::DB;
-use std::sync::Weak;
+use std::{path::PathBuf, sync::Weak};
 use tempfile::TempDir;
 
 #[derive(Default)]
@@ -18,6 +18,11 @@ impl DbLifetime {
     pub fn without_destroy(weak_db_ref: Weak<DB>) -> Self {
         Self { optional_tempdir: None, weak_db_ref }
     }
+
+    /// Returns the directory of the tracked DB, or `None` if the DB was already dropped
+    pub fn db_path(&self) -> Option<PathBuf> {
+        self.weak_db_ref.upgrade().map(|db| db.path().to_owned())
+    }
 }
 
 impl Drop for DbLifetime {
@@ -1,12 +1,13 @@
 use crate::indexed_utxos::{UtxoChanges, UtxoSetByScriptPublicKey};
 use derive_more::Display;
+use kaspa_consensus_core::ChainCursor;
 use kaspa_notify::{
     events::EventType,
     full_featured,
     notification::Notification as NotificationTrait,
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{AddressFilteredSubscription, OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         Subscription,
     },
 };
@@ -53,6 +54,14 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_address_filtered_subscription(
+        &self,
+        _subscription: &AddressFilteredSubscription,
+        _context: &SubscriptionContext,
+    ) -> Option<Self> {
+        Some(self.clone())
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -65,17 +74,24 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 pub struct UtxosChangedNotification {
     pub added: Arc<UtxoSetByScriptPublicKey>,
     pub removed: Arc<UtxoSetByScriptPublicKey>,
+    /// The sink of the virtual state the changes lead to, if known
+    pub cursor: Option<ChainCursor>,
 }
 
 impl From<UtxoChanges> for UtxosChangedNotification {
     fn from(item: UtxoChanges) -> Self {
-        Self { added: Arc::new(item.added), removed: Arc::new(item.removed) }
+        Self { added: Arc::new(item.added), removed: Arc::new(item.removed), cursor: None }
     }
 }
 
 impl UtxosChangedNotification {
     pub fn from_utxos_changed(utxos_changed: UtxoChanges) -> Self {
-        Self { added: Arc::new(utxos_changed.added), removed: Arc::new(utxos_changed.removed) }
+        Self { added: Arc::new(utxos_changed.added), removed: Arc::new(utxos_changed.removed), cursor: None }
+    }
+
+    pub fn with_cursor(mut self, cursor: ChainCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
     }
 
     pub(crate) fn apply_utxos_changed_subscription(
@@ -91,7 +107,7 @@ impl UtxosChangedNotification {
             if added.is_empty() && removed.is_empty() {
                 None
             } else {
-                Some(Self { added: Arc::new(added), removed: Arc::new(removed) })
+                Some(Self { added: Arc::new(added), removed: Arc::new(removed), cursor: self.cursor })
             }
         }
     }
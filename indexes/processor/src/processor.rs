@@ -94,8 +94,10 @@ impl Processor {
     ) -> IndexResult<UtxosChangedNotification> {
         trace!("[{IDENT}]: processing {:?}", notification);
         if let Some(utxoindex) = self.utxoindex.clone() {
-            let converted_notification: UtxosChangedNotification =
-                utxoindex.update(notification.accumulated_utxo_diff.clone(), notification.virtual_parents).await?.into();
+            let converted_notification = UtxosChangedNotification::from(
+                utxoindex.update(notification.accumulated_utxo_diff.clone(), notification.virtual_parents).await?,
+            )
+            .with_cursor(notification.cursor);
             debug!(
                 "IDXPRC, Creating UtxosChanged notifications with {} added and {} removed utxos",
                 converted_notification.added.len(),
@@ -130,7 +132,10 @@ mod tests {
     use super::*;
     use async_channel::{unbounded, Receiver, Sender};
     use kaspa_consensus::{config::Config, consensus::test_consensus::TestConsensus, params::DEVNET_PARAMS, test_helpers::*};
-    use kaspa_consensus_core::utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff};
+    use kaspa_consensus_core::{
+        utxo::{utxo_collection::UtxoCollection, utxo_diff::UtxoDiff},
+        ChainCursor,
+    };
     use kaspa_consensusmanager::ConsensusManager;
     use kaspa_database::create_temp_db;
     use kaspa_database::prelude::ConnBuilder;
@@ -183,6 +188,7 @@ mod tests {
         let test_notification = consensus_notification::UtxosChangedNotification::new(
             Arc::new(UtxoDiff { add: to_add_collection, remove: to_remove_collection }),
             Arc::new(generate_random_hashes(rng, 2)),
+            ChainCursor::default(),
         );
 
         pipeline.consensus_sender.send(ConsensusNotification::UtxosChanged(test_notification.clone())).await.expect("expected send");
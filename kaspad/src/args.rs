@@ -1,10 +1,13 @@
 use clap::{arg, Arg, ArgAction, Command};
 use kaspa_consensus_core::{
-    config::Config,
+    config::{Config, DEFAULT_NOTIFICATION_REPLAY_WINDOW},
     network::{NetworkId, NetworkType},
 };
 use kaspa_core::kaspad_env::version;
 use kaspa_notify::address::tracker::Tracker;
+use kaspa_p2p_mining::rules::{
+    MiningRulesConfig, DEFAULT_MAX_FINALITY_CONFLICT_DURATION, DEFAULT_MINING_RULES, DEFAULT_MIN_PEERS, MINING_RULES,
+};
 use kaspa_utils::networking::ContextualNetAddress;
 use kaspa_wrpc_server::address::WrpcNetAddress;
 use serde::Deserialize;
@@ -63,6 +66,15 @@ pub struct Args {
     pub max_tracked_addresses: usize,
     pub enable_unsynced_mining: bool,
     pub enable_mainnet_mining: bool,
+    #[serde(rename = "enable-mining-rule")]
+    pub enable_mining_rules: Vec<String>,
+    #[serde(rename = "disable-mining-rule")]
+    pub disable_mining_rules: Vec<String>,
+    pub mining_min_peers: usize,
+    /// Maximum sink age in seconds, defaulting to the finality duration
+    pub mining_max_sink_age: Option<u64>,
+    /// Maximum finality conflict duration in seconds
+    pub mining_max_finality_conflict: u64,
     pub testnet: bool,
     #[serde(rename = "netsuffix")]
     pub testnet_suffix: u32,
@@ -91,6 +103,15 @@ pub struct Args {
     pub disable_grpc: bool,
     pub ram_scale: f64,
     pub retention_period_days: Option<f64>,
+    pub record_fee_estimates: bool,
+    pub mempool_policy_file: Option<String>,
+    pub notification_replay_window: u64,
+    pub p2p_encryption: bool,
+    pub require_p2p_encryption: bool,
+    pub p2p_key_file: Option<String>,
+    #[serde(rename = "p2p-trusted-key")]
+    pub p2p_trusted_keys: Vec<String>,
+    pub dandelion: bool,
 
     pub override_params_file: Option<String>,
 
@@ -116,6 +137,11 @@ impl Default for Args {
             max_tracked_addresses: 0,
             enable_unsynced_mining: false,
             enable_mainnet_mining: true,
+            enable_mining_rules: vec![],
+            disable_mining_rules: vec![],
+            mining_min_peers: DEFAULT_MIN_PEERS,
+            mining_max_sink_age: None,
+            mining_max_finality_conflict: DEFAULT_MAX_FINALITY_CONFLICT_DURATION / 1000,
             testnet: false,
             testnet_suffix: 10,
             devnet: false,
@@ -148,6 +174,14 @@ impl Default for Args {
             disable_grpc: false,
             ram_scale: 1.0,
             retention_period_days: None,
+            record_fee_estimates: false,
+            mempool_policy_file: None,
+            notification_replay_window: DEFAULT_NOTIFICATION_REPLAY_WINDOW,
+            p2p_encryption: false,
+            require_p2p_encryption: false,
+            p2p_key_file: None,
+            p2p_trusted_keys: vec![],
+            dandelion: false,
             override_params_file: None,
             rocksdb_preset: None,
             rocksdb_wal_dir: None,
@@ -157,6 +191,16 @@ impl Default for Args {
 }
 
 impl Args {
+    pub fn mining_rules_config(&self) -> MiningRulesConfig {
+        MiningRulesConfig {
+            min_peers: self.mining_min_peers,
+            max_sink_age: self.mining_max_sink_age.map(|seconds| seconds * 1000),
+            max_finality_conflict_duration: self.mining_max_finality_conflict * 1000,
+            ..Default::default()
+        }
+        .with_rules(&self.enable_mining_rules, &self.disable_mining_rules)
+    }
+
     pub fn apply_to_config(&self, config: &mut Config) {
         config.utxoindex = self.utxoindex;
         config.disable_upnp = self.disable_upnp;
@@ -172,6 +216,8 @@ impl Args {
         config.externalip = self.externalip.map(|v| v.normalize(config.default_p2p_port()));
         config.ram_scale = self.ram_scale;
         config.retention_period_days = self.retention_period_days;
+        config.notification_replay_window = self.notification_replay_window;
+        config.dandelion_relay = self.dandelion;
 
         #[cfg(feature = "devnet-prealloc")]
         if let Some(num_prealloc_utxos) = self.num_prealloc_utxos {
@@ -335,6 +381,53 @@ pub fn cli() -> Command {
                 .hide(true)
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
+        .arg(
+            Arg::new("enable-mining-rule")
+                .long("enable-mining-rule")
+                .env("KASPAD_ENABLE_MINING_RULES")
+                .value_name("RULE")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .help(format!("Enable a mining rule, one of: {} (enabled by default: {}).", MINING_RULES.join(", "), DEFAULT_MINING_RULES.join(", "))),
+        )
+        .arg(
+            Arg::new("disable-mining-rule")
+                .long("disable-mining-rule")
+                .env("KASPAD_DISABLE_MINING_RULES")
+                .value_name("RULE")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .help("Disable a mining rule enabled by default."),
+        )
+        .arg(
+            Arg::new("mining-min-peers")
+                .long("mining-min-peers")
+                .env("KASPAD_MINING_MIN_PEERS")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(usize))
+                .help(format!("Number of active peers below which the min-peers mining rule blocks mining (default: {DEFAULT_MIN_PEERS}).")),
+        )
+        .arg(
+            Arg::new("mining-max-sink-age")
+                .long("mining-max-sink-age")
+                .env("KASPAD_MINING_MAX_SINK_AGE")
+                .require_equals(true)
+                .value_name("SECONDS")
+                .value_parser(clap::value_parser!(u64))
+                .help("Sink age above which the stale-sink mining rule blocks mining (default: the network finality duration)."),
+        )
+        .arg(
+            Arg::new("mining-max-finality-conflict")
+                .long("mining-max-finality-conflict")
+                .env("KASPAD_MINING_MAX_FINALITY_CONFLICT")
+                .require_equals(true)
+                .value_name("SECONDS")
+                .value_parser(clap::value_parser!(u64))
+                .help(format!(
+                    "Duration finality conflicts must be ongoing for before the finality-conflict mining rule blocks mining (default: {}).",
+                    DEFAULT_MAX_FINALITY_CONFLICT_DURATION / 1000
+                )),
+        )
         .arg(arg!(--utxoindex "Enable the UTXO index").env("KASPAD_UTXOINDEX"))
         .arg(
             Arg::new("max-tracked-addresses")
@@ -407,6 +500,44 @@ a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance
                 .value_parser(clap::value_parser!(f64))
                 .help("The number of total days of data to keep.")
         )
+        .arg(arg!(--"record-fee-estimates" "Record feerate estimations and realized transaction inclusion times for fee estimator backtesting").env("KASPAD_RECORD_FEE_ESTIMATES"))
+        .arg(
+            Arg::new("mempool-policy")
+                .long("mempool-policy")
+                .env("KASPAD_MEMPOOL_POLICY")
+                .require_equals(true)
+                .value_name("PATH")
+                .value_parser(clap::value_parser!(String))
+                .help("Path to a TOML file configuring additional mempool policies. The file can be reloaded at runtime through the ReloadMempoolPolicy RPC (requires --unsaferpc)."),
+        )
+        .arg(
+            Arg::new("notification-replay-window")
+                .long("notification-replay-window")
+                .env("KASPAD_NOTIFICATION_REPLAY_WINDOW")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(u64))
+                .help(format!("Maximum time span, in seconds, of missed chain changes replayed to a resumed wRPC subscription (default: {DEFAULT_NOTIFICATION_REPLAY_WINDOW}). Set to 0 to disable replay.")),
+        )
+        .arg(arg!(--"p2p-encryption" "Encrypt P2P connections with peers supporting it").env("KASPAD_P2P_ENCRYPTION"))
+        .arg(arg!(--"require-p2p-encryption" "Only accept P2P peers supporting encrypted connections (implies --p2p-encryption)").env("KASPAD_REQUIRE_P2P_ENCRYPTION"))
+        .arg(
+            Arg::new("p2p-key-file")
+                .long("p2p-key-file")
+                .env("KASPAD_P2P_KEY_FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("File holding the static key authenticating encrypted P2P connections, created if missing (default: p2p.key in the network application directory)."),
+        )
+        .arg(
+            Arg::new("p2p-trusted-keys")
+                .long("p2p-trusted-key")
+                .env("KASPAD_P2P_TRUSTED_KEYS")
+                .value_name("PUBKEY")
+                .action(ArgAction::Append)
+                .require_equals(true)
+                .help("Hex-encoded static public key of a trusted peer. When set, only encrypted connections with trusted peers are accepted."),
+        )
+        .arg(arg!(--dandelion "Relay transactions in Dandelion++ stem/fluff mode: locally submitted transactions are forwarded along a random path of peers before being diffused").env("KASPAD_DANDELION"))
         .arg(
             Arg::new("override-params-file")
                 .long("override-params-file")
@@ -502,6 +633,15 @@ impl Args {
             reset_db: arg_match_unwrap_or::<bool>(&m, "reset-db", defaults.reset_db),
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
+            enable_mining_rules: arg_match_many_unwrap_or::<String>(&m, "enable-mining-rule", defaults.enable_mining_rules),
+            disable_mining_rules: arg_match_many_unwrap_or::<String>(&m, "disable-mining-rule", defaults.disable_mining_rules),
+            mining_min_peers: arg_match_unwrap_or::<usize>(&m, "mining-min-peers", defaults.mining_min_peers),
+            mining_max_sink_age: m.get_one::<u64>("mining-max-sink-age").cloned().or(defaults.mining_max_sink_age),
+            mining_max_finality_conflict: arg_match_unwrap_or::<u64>(
+                &m,
+                "mining-max-finality-conflict",
+                defaults.mining_max_finality_conflict,
+            ),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
@@ -521,6 +661,18 @@ impl Args {
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
             retention_period_days: m.get_one::<f64>("retention-period-days").cloned().or(defaults.retention_period_days),
+            record_fee_estimates: arg_match_unwrap_or::<bool>(&m, "record-fee-estimates", defaults.record_fee_estimates),
+            mempool_policy_file: m.get_one::<String>("mempool-policy").cloned().or(defaults.mempool_policy_file),
+            notification_replay_window: arg_match_unwrap_or::<u64>(
+                &m,
+                "notification-replay-window",
+                defaults.notification_replay_window,
+            ),
+            p2p_encryption: arg_match_unwrap_or::<bool>(&m, "p2p-encryption", defaults.p2p_encryption),
+            require_p2p_encryption: arg_match_unwrap_or::<bool>(&m, "require-p2p-encryption", defaults.require_p2p_encryption),
+            p2p_key_file: m.get_one::<String>("p2p-key-file").cloned().or(defaults.p2p_key_file),
+            p2p_trusted_keys: arg_match_many_unwrap_or::<String>(&m, "p2p-trusted-keys", defaults.p2p_trusted_keys),
+            dandelion: arg_match_unwrap_or::<bool>(&m, "dandelion", defaults.dandelion),
 
             #[cfg(feature = "devnet-prealloc")]
             num_prealloc_utxos: m.get_one::<u64>("num-prealloc-utxos").cloned(),
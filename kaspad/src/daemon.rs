@@ -16,8 +16,11 @@ use kaspa_database::{
 };
 use kaspa_grpc_server::service::GrpcService;
 use kaspa_notify::{address::tracker::Tracker, subscription::context::SubscriptionContext};
-use kaspa_p2p_lib::Hub;
-use kaspa_p2p_mining::rule_engine::MiningRuleEngine;
+use kaspa_p2p_lib::{
+    noise::{load_or_create_static_key, parse_static_key},
+    EncryptionPolicy, Hub, TransportSecurity,
+};
+use kaspa_p2p_mining::{rule_engine::MiningRuleEngine, rules::MINING_RULES};
 use kaspa_rpc_service::service::RpcCoreService;
 use kaspa_txscript::caches::TxScriptCacheCounters;
 use kaspa_utils::git;
@@ -70,6 +73,7 @@ const UTXOINDEX_DB: &str = "utxoindex";
 const META_DB: &str = "meta";
 const META_DB_FILE_LIMIT: i32 = 5;
 const DEFAULT_LOG_DIR: &str = "logs";
+const P2P_KEY_FILE: &str = "p2p.key";
 
 fn get_home_dir() -> PathBuf {
     #[cfg(target_os = "windows")]
@@ -113,6 +117,14 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if let Some(key) = args.p2p_trusted_keys.iter().find(|key| parse_static_key(key).is_err()) {
+        return Err(ConfigError::InvalidP2pTrustedKey(key.clone()));
+    }
+    if let Some(name) =
+        args.enable_mining_rules.iter().chain(args.disable_mining_rules.iter()).find(|name| !MINING_RULES.contains(&name.as_str()))
+    {
+        return Err(ConfigError::UnknownMiningRule(name.clone(), MINING_RULES.join(", ")));
+    }
     Ok(())
 }
 
@@ -557,7 +569,7 @@ Do you confirm? (y/n)";
     let tick_service = Arc::new(TickService::new());
     let (notification_send, notification_recv) = unbounded();
     let max_tracked_addresses = if args.utxoindex && args.max_tracked_addresses > 0 { Some(args.max_tracked_addresses) } else { None };
-    let subscription_context = SubscriptionContext::with_options(max_tracked_addresses);
+    let subscription_context = SubscriptionContext::with_prefix(network.into(), max_tracked_addresses);
     let notification_root = Arc::new(ConsensusNotificationRoot::with_context(notification_send, subscription_context.clone()));
     let processing_counters = Arc::new(ProcessingCounters::default());
     let mining_counters = Arc::new(MiningCounters::default());
@@ -624,14 +636,23 @@ Do you confirm? (y/n)";
 
     let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db, tick_service.clone());
 
-    let mining_manager = MiningManagerProxy::new(Arc::new(MiningManager::new_with_extended_config(
+    let mut mining_manager = MiningManager::new_with_extended_config(
         config.target_time_per_block(),
         false,
         config.max_block_mass,
         config.ram_scale,
+        args.record_fee_estimates,
         config.block_template_cache_lifetime,
         mining_counters.clone(),
-    )));
+    )
+    .with_notification_root(notification_root.clone());
+    if let Some(path) = args.mempool_policy_file.as_ref() {
+        mining_manager = mining_manager.with_mempool_policy_file(PathBuf::from(path)).unwrap_or_else(|err| {
+            println!("{err}");
+            exit(1);
+        });
+    }
+    let mining_manager = MiningManagerProxy::new(Arc::new(mining_manager));
     let mining_monitor =
         Arc::new(MiningMonitor::new(mining_manager.clone(), mining_counters, tx_script_cache_counters.clone(), tick_service.clone()));
 
@@ -643,7 +664,31 @@ Do you confirm? (y/n)";
         tick_service.clone(),
         hub.clone(),
         mining_rules,
+        args.mining_rules_config(),
     ));
+    let encryption_policy = match (args.require_p2p_encryption || !args.p2p_trusted_keys.is_empty(), args.p2p_encryption) {
+        (true, _) => EncryptionPolicy::Required,
+        (false, true) => EncryptionPolicy::Opportunistic,
+        (false, false) => EncryptionPolicy::Disabled,
+    };
+    let transport_security = if encryption_policy == EncryptionPolicy::Disabled {
+        TransportSecurity::disabled()
+    } else {
+        let key_file =
+            args.p2p_key_file.as_ref().map(PathBuf::from).unwrap_or_else(|| app_dir.join(network.to_prefixed()).join(P2P_KEY_FILE));
+        let static_key = load_or_create_static_key(&key_file).unwrap_or_else(|err| {
+            println!("{}", ConfigError::P2pStaticKey(key_file.display().to_string(), err.to_string()));
+            exit(1);
+        });
+        let transport_security = TransportSecurity::new(encryption_policy, static_key);
+        info!("P2P encryption: {}, static public key: {}", encryption_policy, transport_security.static_public_key());
+        if args.p2p_trusted_keys.is_empty() {
+            transport_security
+        } else {
+            info!("P2P encryption: accepting {} trusted peer key(s) only", args.p2p_trusted_keys.len());
+            transport_security.with_trusted_keys(args.p2p_trusted_keys.iter().map(|key| parse_static_key(key).unwrap()))
+        }
+    };
     let flow_context = Arc::new(FlowContext::new(
         consensus_manager.clone(),
         address_manager,
@@ -653,6 +698,7 @@ Do you confirm? (y/n)";
         notification_root,
         hub.clone(),
         mining_rule_engine.clone(),
+        transport_security,
     ));
     let p2p_service = Arc::new(P2pService::new(
         flow_context.clone(),
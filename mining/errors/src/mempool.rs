@@ -78,6 +78,18 @@ pub enum RuleError {
 
     #[error("Rejected tx {0} from mempool due to incomputable storage mass")]
     RejectStorageMassIncomputable(TransactionId),
+
+    #[error("transaction {0} rejected by mempool policy {1}: {2}")]
+    RejectPolicy(TransactionId, String, String),
+
+    #[error("transaction {0} is already in the stem pool")]
+    RejectDuplicateStem(TransactionId),
+
+    #[error("output {0} already spent by transaction {1} in the stem pool")]
+    RejectDoubleSpendInStemPool(TransactionOutpoint, TransactionId),
+
+    #[error("number of transactions in stem pool has reached the maximum allowed ({0})")]
+    RejectStemPoolIsFull(usize),
 }
 
 impl From<NonStandardError> for RuleError {
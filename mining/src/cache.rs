@@ -54,7 +54,6 @@ impl BlockTemplateCache {
         Self { inner: Mutex::new(Inner::new(cache_lifetime)) }
     }
 
-    #[cfg(test)]
     pub(crate) fn clear(&self) {
         self.inner.lock().clear();
     }
@@ -0,0 +1,476 @@
+//! Optional recording of feerate estimations alongside the realized inclusion times of mempool transactions.
+//!
+//! The recorded dataset can be exported and replayed against alternative estimator parameters
+//! (see [`FeeEstimateHistory::replay`]) in order to evaluate the quality of the estimations.
+
+use super::{FeerateEstimations, FeerateEstimator};
+use kaspa_consensus_core::tx::TransactionId;
+use std::collections::VecDeque;
+
+/// A snapshot of the feerate estimator state along with the estimations it produced
+#[derive(Clone, Debug)]
+pub struct FeerateEstimateSample {
+    /// Unix time in milliseconds at which the sample was taken
+    pub timestamp: u64,
+    /// Virtual DAA score at which the sample was taken
+    pub daa_score: u64,
+    /// Total probability weight of the mempool ready transactions (see [`FeerateEstimator`])
+    pub total_weight: f64,
+    /// Amortized time in seconds between transaction inclusions (see [`FeerateEstimator`])
+    pub inclusion_interval: f64,
+    /// The minimum standard feerate applied when calculating the estimations
+    pub minimum_feerate: f64,
+    pub estimations: FeerateEstimations,
+}
+
+/// The realized inclusion of a mempool transaction
+#[derive(Clone, Debug)]
+pub struct TransactionInclusionRecord {
+    pub transaction_id: TransactionId,
+    pub fee: u64,
+    pub feerate: f64,
+    /// Unix time in milliseconds at which the transaction entered the mempool
+    pub added_timestamp: u64,
+    /// Virtual DAA score at which the transaction entered the mempool
+    pub added_daa_score: u64,
+    /// Unix time in milliseconds at which the mempool handled the block including the transaction
+    pub included_timestamp: u64,
+    /// DAA score of the block including the transaction
+    pub included_daa_score: u64,
+}
+
+impl TransactionInclusionRecord {
+    /// The realized time in seconds it took for the transaction to be included
+    pub fn inclusion_seconds(&self) -> f64 {
+        self.included_timestamp.saturating_sub(self.added_timestamp) as f64 / 1000.0
+    }
+}
+
+/// Alternative estimator parameters to be replayed against a recorded history
+#[derive(Clone, Copy, Debug)]
+pub struct FeeEstimateReplayParams {
+    /// Factor applied to the recorded total weight of every sample
+    pub total_weight_factor: f64,
+    /// Factor applied to the recorded inclusion interval of every sample
+    pub inclusion_interval_factor: f64,
+    /// Optional override of the target time per block. Defaults to the node target time per block
+    pub target_time_per_block_seconds: Option<f64>,
+}
+
+impl FeeEstimateReplayParams {
+    pub fn is_valid(&self) -> bool {
+        let is_positive = |x: f64| x.is_finite() && x > 0.0;
+        is_positive(self.total_weight_factor)
+            && is_positive(self.inclusion_interval_factor)
+            && self.target_time_per_block_seconds.is_none_or(is_positive)
+    }
+}
+
+impl Default for FeeEstimateReplayParams {
+    fn default() -> Self {
+        Self { total_weight_factor: 1.0, inclusion_interval_factor: 1.0, target_time_per_block_seconds: None }
+    }
+}
+
+/// Accuracy statistics of a replay of estimator parameters against a recorded history
+#[derive(Clone, Debug, Default)]
+pub struct FeeEstimateReplayReport {
+    /// Number of recorded inclusions which could be matched with a preceding estimate sample
+    pub evaluated_transactions: u64,
+    /// Mean of the inclusion times predicted for the feerate of each evaluated transaction
+    pub mean_predicted_seconds: f64,
+    /// Mean of the realized inclusion times
+    pub mean_realized_seconds: f64,
+    /// Mean of the absolute differences between predicted and realized inclusion times
+    pub mean_absolute_error_seconds: f64,
+    /// Fraction of transactions included no later than their predicted inclusion time
+    pub within_prediction_ratio: f64,
+    /// Fraction of transactions included no later than the estimated time of the highest bucket their feerate qualifies for.
+    /// Transactions paying less than the lowest bucket feerate are not counted.
+    pub within_bucket_ratio: f64,
+}
+
+/// The maximum number of samples, and of inclusions, returned by a single export
+pub const MAX_FEE_ESTIMATE_HISTORY_PAGE_SIZE: usize = 1000;
+
+/// Selects a page of the recorded history.
+///
+/// Every recorded sample and inclusion is assigned a sequence number, increasing by one with each record,
+/// so that a client can page through the history by passing the next sequence numbers of the previous export.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FeeEstimateHistoryQuery {
+    /// Unix time in milliseconds from which to export the history
+    pub from_timestamp: u64,
+    /// Sequence number of the first sample to export
+    pub from_sample_sequence: u64,
+    /// Sequence number of the first inclusion to export
+    pub from_inclusion_sequence: u64,
+    /// The maximum number of samples, and of inclusions, to export. Zero or values above
+    /// [`MAX_FEE_ESTIMATE_HISTORY_PAGE_SIZE`] are capped to the latter.
+    pub limit: usize,
+}
+
+impl FeeEstimateHistoryQuery {
+    fn page_size(&self) -> usize {
+        match self.limit {
+            0 => MAX_FEE_ESTIMATE_HISTORY_PAGE_SIZE,
+            limit => limit.min(MAX_FEE_ESTIMATE_HISTORY_PAGE_SIZE),
+        }
+    }
+}
+
+/// A page of the recorded history, optionally along with a replay report
+#[derive(Clone, Debug, Default)]
+pub struct FeeEstimateHistoryExport {
+    pub samples: Vec<FeerateEstimateSample>,
+    pub inclusions: Vec<TransactionInclusionRecord>,
+    /// Sequence number of the sample following the exported ones
+    pub next_sample_sequence: u64,
+    /// Sequence number of the inclusion following the exported ones
+    pub next_inclusion_sequence: u64,
+    /// Indicates whether samples or inclusions were left out of the export because of the page size
+    pub has_more: bool,
+    pub replay_report: Option<FeeEstimateReplayReport>,
+}
+
+/// A page of `items`, the first of which has sequence number `first_sequence`, along with the sequence
+/// number following the page and whether matching items were left out
+fn page<T: Clone>(
+    items: &VecDeque<T>,
+    first_sequence: u64,
+    from_sequence: u64,
+    page_size: usize,
+    predicate: impl Fn(&T) -> bool,
+) -> (Vec<T>, u64, bool) {
+    let skip = from_sequence.saturating_sub(first_sequence).min(items.len() as u64) as usize;
+    let mut matching = items.iter().enumerate().skip(skip).filter(|(_, item)| predicate(item));
+    let page = matching.by_ref().take(page_size).collect::<Vec<_>>();
+    let has_more = matching.next().is_some();
+    let next_sequence = match page.last() {
+        Some((index, _)) if has_more => first_sequence + *index as u64 + 1,
+        _ => (first_sequence + items.len() as u64).max(from_sequence),
+    };
+    (page.into_iter().map(|(_, item)| item.clone()).collect(), next_sequence, has_more)
+}
+
+/// The part of a recorded sample needed for replaying estimator parameters
+#[derive(Clone, Copy, Debug)]
+struct ReplaySample {
+    timestamp: u64,
+    total_weight: f64,
+    inclusion_interval: f64,
+    minimum_feerate: f64,
+}
+
+/// The part of a recorded inclusion needed for replaying estimator parameters
+#[derive(Clone, Copy, Debug)]
+struct ReplayInclusion {
+    added_timestamp: u64,
+    feerate: f64,
+    inclusion_seconds: f64,
+}
+
+/// A compact copy of the recorded history, allowing to replay estimator parameters without holding the history
+pub struct FeeEstimateReplaySnapshot {
+    target_time_per_block_seconds: f64,
+    samples: Vec<ReplaySample>,
+    inclusions: Vec<ReplayInclusion>,
+}
+
+/// A bounded in-memory record of feerate estimate samples and transaction inclusions
+pub struct FeeEstimateHistory {
+    sample_interval_milliseconds: u64,
+    maximum_samples: usize,
+    maximum_inclusions: usize,
+    target_time_per_block_seconds: f64,
+    last_sample_timestamp: Option<u64>,
+    samples: VecDeque<FeerateEstimateSample>,
+    inclusions: VecDeque<TransactionInclusionRecord>,
+    /// Sequence number of the oldest sample kept
+    first_sample_sequence: u64,
+    /// Sequence number of the oldest inclusion kept
+    first_inclusion_sequence: u64,
+}
+
+impl FeeEstimateHistory {
+    pub fn new(
+        sample_interval_milliseconds: u64,
+        maximum_samples: usize,
+        maximum_inclusions: usize,
+        target_time_per_block_seconds: f64,
+    ) -> Self {
+        Self {
+            sample_interval_milliseconds,
+            maximum_samples,
+            maximum_inclusions,
+            target_time_per_block_seconds,
+            last_sample_timestamp: None,
+            samples: VecDeque::new(),
+            inclusions: VecDeque::new(),
+            first_sample_sequence: 0,
+            first_inclusion_sequence: 0,
+        }
+    }
+
+    /// Indicates whether a new sample is due at time `now` (in milliseconds)
+    pub fn is_sample_due(&self, now: u64) -> bool {
+        self.last_sample_timestamp.is_none_or(|last| now >= last + self.sample_interval_milliseconds)
+    }
+
+    pub fn record_sample(&mut self, now: u64, daa_score: u64, estimator: &FeerateEstimator, minimum_feerate: f64) {
+        let sample = FeerateEstimateSample {
+            timestamp: now,
+            daa_score,
+            total_weight: estimator.total_weight,
+            inclusion_interval: estimator.inclusion_interval,
+            minimum_feerate,
+            estimations: estimator.calc_estimations(minimum_feerate),
+        };
+        if self.samples.len() >= self.maximum_samples {
+            self.samples.pop_front();
+            self.first_sample_sequence += 1;
+        }
+        self.samples.push_back(sample);
+        self.last_sample_timestamp = Some(now);
+    }
+
+    pub fn record_inclusion(&mut self, record: TransactionInclusionRecord) {
+        if self.inclusions.len() >= self.maximum_inclusions {
+            self.inclusions.pop_front();
+            self.first_inclusion_sequence += 1;
+        }
+        self.inclusions.push_back(record);
+    }
+
+    pub fn samples(&self) -> impl Iterator<Item = &FeerateEstimateSample> {
+        self.samples.iter()
+    }
+
+    pub fn inclusions(&self) -> impl Iterator<Item = &TransactionInclusionRecord> {
+        self.inclusions.iter()
+    }
+
+    /// Exports the page of samples taken and inclusions recorded selected by `query`. The replay report is left
+    /// empty, see [`Self::replay_snapshot`].
+    pub fn export(&self, query: FeeEstimateHistoryQuery) -> FeeEstimateHistoryExport {
+        let page_size = query.page_size();
+        let (samples, next_sample_sequence, more_samples) =
+            page(&self.samples, self.first_sample_sequence, query.from_sample_sequence, page_size, |sample| {
+                sample.timestamp >= query.from_timestamp
+            });
+        let (inclusions, next_inclusion_sequence, more_inclusions) =
+            page(&self.inclusions, self.first_inclusion_sequence, query.from_inclusion_sequence, page_size, |inclusion| {
+                inclusion.included_timestamp >= query.from_timestamp
+            });
+        FeeEstimateHistoryExport {
+            samples,
+            inclusions,
+            next_sample_sequence,
+            next_inclusion_sequence,
+            has_more: more_samples || more_inclusions,
+            replay_report: None,
+        }
+    }
+
+    /// Copies the data needed for replaying estimator parameters against the complete recorded history
+    pub fn replay_snapshot(&self) -> FeeEstimateReplaySnapshot {
+        FeeEstimateReplaySnapshot {
+            target_time_per_block_seconds: self.target_time_per_block_seconds,
+            samples: self
+                .samples
+                .iter()
+                .map(|sample| ReplaySample {
+                    timestamp: sample.timestamp,
+                    total_weight: sample.total_weight,
+                    inclusion_interval: sample.inclusion_interval,
+                    minimum_feerate: sample.minimum_feerate,
+                })
+                .collect(),
+            inclusions: self
+                .inclusions
+                .iter()
+                .map(|inclusion| ReplayInclusion {
+                    added_timestamp: inclusion.added_timestamp,
+                    feerate: inclusion.feerate,
+                    inclusion_seconds: inclusion.inclusion_seconds(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Replays `params` against the recorded history, see [`FeeEstimateReplaySnapshot::replay`]
+    pub fn replay(&self, params: FeeEstimateReplayParams) -> FeeEstimateReplayReport {
+        self.replay_snapshot().replay(params)
+    }
+}
+
+impl FeeEstimateReplaySnapshot {
+    /// Replays `params` against the recorded history. Each recorded inclusion is evaluated against
+    /// the latest sample taken before the transaction entered the mempool, with the sampled estimator
+    /// state altered by `params`.
+    pub fn replay(&self, params: FeeEstimateReplayParams) -> FeeEstimateReplayReport {
+        let target_time_per_block_seconds = params.target_time_per_block_seconds.unwrap_or(self.target_time_per_block_seconds);
+        let estimators = self
+            .samples
+            .iter()
+            .filter_map(|sample| {
+                let estimator = FeerateEstimator {
+                    total_weight: sample.total_weight * params.total_weight_factor,
+                    inclusion_interval: sample.inclusion_interval * params.inclusion_interval_factor,
+                    target_time_per_block_seconds,
+                };
+                // Skip estimator states made inconsistent by the replayed parameters
+                if estimator.inclusion_interval >= 1.0 || estimator.inclusion_interval >= target_time_per_block_seconds {
+                    return None;
+                }
+                let estimations = estimator.calc_estimations(sample.minimum_feerate);
+                Some((sample.timestamp, estimator, estimations))
+            })
+            .collect::<Vec<_>>();
+
+        let mut report = FeeEstimateReplayReport::default();
+        let (mut predicted_sum, mut realized_sum, mut error_sum) = (0f64, 0f64, 0f64);
+        let (mut within_prediction, mut within_bucket, mut bucket_qualified) = (0u64, 0u64, 0u64);
+        for inclusion in self.inclusions.iter() {
+            // Samples are recorded in chronological order
+            let index = estimators.partition_point(|(timestamp, _, _)| *timestamp <= inclusion.added_timestamp);
+            if index == 0 {
+                continue;
+            }
+            let (_, estimator, estimations) = &estimators[index - 1];
+            let predicted = estimator.feerate_to_time(inclusion.feerate);
+            let realized = inclusion.inclusion_seconds;
+
+            report.evaluated_transactions += 1;
+            predicted_sum += predicted;
+            realized_sum += realized;
+            error_sum += (predicted - realized).abs();
+            if realized <= predicted {
+                within_prediction += 1;
+            }
+            // Buckets are ordered by decreasing feerate, so the first qualifying bucket is the highest one
+            if let Some(bucket) = estimations.ordered_buckets().into_iter().find(|bucket| bucket.feerate <= inclusion.feerate) {
+                bucket_qualified += 1;
+                if realized <= bucket.estimated_seconds {
+                    within_bucket += 1;
+                }
+            }
+        }
+
+        if report.evaluated_transactions > 0 {
+            let count = report.evaluated_transactions as f64;
+            report.mean_predicted_seconds = predicted_sum / count;
+            report.mean_realized_seconds = realized_sum / count;
+            report.mean_absolute_error_seconds = error_sum / count;
+            report.within_prediction_ratio = within_prediction as f64 / count;
+        }
+        if bucket_qualified > 0 {
+            report.within_bucket_ratio = within_bucket as f64 / bucket_qualified as f64;
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inclusion(id: u64, feerate: f64, added_timestamp: u64, included_timestamp: u64) -> TransactionInclusionRecord {
+        TransactionInclusionRecord {
+            transaction_id: id.into(),
+            fee: (feerate * 2000.0) as u64,
+            feerate,
+            added_timestamp,
+            added_daa_score: added_timestamp / 100,
+            included_timestamp,
+            included_daa_score: included_timestamp / 100,
+        }
+    }
+
+    #[test]
+    fn test_history_bounds_and_sampling_interval() {
+        let estimator = FeerateEstimator::new(1000.0, 0.004, 1.0);
+        let mut history = FeeEstimateHistory::new(1000, 3, 2, 1.0);
+        assert!(history.is_sample_due(0));
+        for i in 0..5 {
+            history.record_sample(i * 1000, i * 10, &estimator, 1.0);
+            assert!(!history.is_sample_due(i * 1000 + 999));
+            assert!(history.is_sample_due(i * 1000 + 1000));
+        }
+        assert_eq!(history.samples().map(|s| s.timestamp).collect::<Vec<_>>(), vec![2000, 3000, 4000]);
+
+        for i in 0..3 {
+            history.record_inclusion(inclusion(i, 1.0, 0, 1000));
+        }
+        assert_eq!(history.inclusions().count(), 2);
+    }
+
+    #[test]
+    fn test_replay() {
+        let estimator = FeerateEstimator::new(1002283.659, 0.004, 1.0);
+        let mut history = FeeEstimateHistory::new(1000, 100, 100, 1.0);
+
+        // Inclusions preceding the first sample cannot be evaluated
+        history.record_inclusion(inclusion(1, 10.0, 500, 2000));
+        history.record_sample(1000, 10, &estimator, 1.0);
+
+        let high_feerate = estimator.calc_estimations(1.0).priority_bucket.feerate * 2.0;
+        let fast = estimator.feerate_to_time(high_feerate);
+        history.record_inclusion(inclusion(2, high_feerate, 1500, 1500 + (fast * 1000.0) as u64 / 2));
+        let low_feerate = 1.0;
+        let slow = estimator.feerate_to_time(low_feerate);
+        history.record_inclusion(inclusion(3, low_feerate, 1500, 1500 + (slow * 1000.0) as u64 * 2));
+
+        let report = history.replay(Default::default());
+        assert_eq!(report.evaluated_transactions, 2);
+        assert_eq!(report.within_prediction_ratio, 0.5);
+        assert!(report.mean_absolute_error_seconds > 0.0);
+
+        // Inflating the weight makes all predictions more pessimistic, so both transactions are now within predictions
+        let report = history.replay(FeeEstimateReplayParams { total_weight_factor: 10.0, ..Default::default() });
+        assert_eq!(report.evaluated_transactions, 2);
+        assert_eq!(report.within_prediction_ratio, 1.0);
+    }
+
+    #[test]
+    fn test_export_pages() {
+        let estimator = FeerateEstimator::new(1000.0, 0.004, 1.0);
+        let mut history = FeeEstimateHistory::new(1000, 4, 100, 1.0);
+        for i in 0..6 {
+            history.record_sample(i * 1000, i * 10, &estimator, 1.0);
+        }
+        // Inclusions of the same block share their timestamp, which sequence numbers allow to page through
+        for i in 0..5 {
+            history.record_inclusion(inclusion(i, 1.0, 0, 2000 + (i / 2) * 1000));
+        }
+
+        // The two oldest samples were dropped, so sample sequences start at 2
+        let query = FeeEstimateHistoryQuery { limit: 3, ..Default::default() };
+        let export = history.export(query);
+        assert_eq!(export.samples.iter().map(|s| s.timestamp).collect::<Vec<_>>(), vec![2000, 3000, 4000]);
+        assert_eq!(export.inclusions.len(), 3);
+        assert_eq!((export.next_sample_sequence, export.next_inclusion_sequence, export.has_more), (5, 3, true));
+
+        let query = FeeEstimateHistoryQuery {
+            from_sample_sequence: export.next_sample_sequence,
+            from_inclusion_sequence: export.next_inclusion_sequence,
+            ..query
+        };
+        let export = history.export(query);
+        assert_eq!(export.samples.iter().map(|s| s.timestamp).collect::<Vec<_>>(), vec![5000]);
+        assert_eq!(export.inclusions.iter().map(|i| i.transaction_id).collect::<Vec<_>>(), vec![3.into(), 4.into()]);
+        assert_eq!((export.next_sample_sequence, export.next_inclusion_sequence, export.has_more), (6, 5, false));
+
+        // An exhausted cursor yields an empty page and keeps its position
+        let query = FeeEstimateHistoryQuery { from_sample_sequence: 6, from_inclusion_sequence: 5, ..query };
+        let export = history.export(query);
+        assert!(export.samples.is_empty() && export.inclusions.is_empty());
+        assert_eq!((export.next_sample_sequence, export.next_inclusion_sequence, export.has_more), (6, 5, false));
+
+        // The timestamp filter applies along with the cursor
+        let export = history.export(FeeEstimateHistoryQuery { from_timestamp: 3000, ..Default::default() });
+        assert_eq!(export.samples.len(), 3);
+        assert_eq!(export.inclusions.len(), 3);
+        assert_eq!(history.export(FeeEstimateHistoryQuery { limit: 10_000, ..Default::default() }).samples.len(), 4);
+    }
+}
@@ -5,6 +5,8 @@ use crate::block_template::selector::ALPHA;
 use itertools::Itertools;
 use std::fmt::Display;
 
+pub mod history;
+
 /// A type representing fee/mass of a transaction in `sompi/gram` units.
 /// Given a feerate value recommendation, calculate the required fee by
 /// taking the transaction mass and multiplying it by feerate: `fee = feerate * mass(tx)`
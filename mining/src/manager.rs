@@ -2,10 +2,14 @@ use crate::{
     block_template::{builder::BlockTemplateBuilder, errors::BuilderError},
     cache::BlockTemplateCache,
     errors::MiningManagerResult,
-    feerate::{FeeEstimateVerbose, FeerateEstimations, FeerateEstimatorArgs},
+    feerate::{
+        history::{FeeEstimateHistoryExport, FeeEstimateHistoryQuery, FeeEstimateReplayParams},
+        FeeEstimateVerbose, FeerateEstimations, FeerateEstimatorArgs,
+    },
     mempool::{
         config::Config,
         model::tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        policy::{PolicyConfig, PolicyError},
         populate_entries_and_try_validate::{
             populate_mempool_transactions_in_parallel, validate_mempool_transaction, validate_mempool_transactions_in_parallel,
         },
@@ -13,7 +17,9 @@ use crate::{
         Mempool,
     },
     model::{
+        mempool_info::{MempoolInfo, MempoolTransactionGraph},
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
+        prioritised_tx::PrioritisedTransaction,
         topological_sort::IntoIterTopologically,
         tx_insert::TransactionInsertion,
         tx_query::TransactionQuery,
@@ -31,17 +37,19 @@ use kaspa_consensus_core::{
     errors::{block::RuleError as BlockRuleError, tx::TxRuleError},
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutput},
 };
+use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_consensusmanager::{spawn_blocking, ConsensusProxy};
 use kaspa_core::{debug, error, info, time::Stopwatch, warn};
 use kaspa_mining_errors::{manager::MiningManagerError, mempool::RuleError};
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 use tokio::sync::mpsc::UnboundedSender;
 
 pub struct MiningManager {
     config: Arc<Config>,
     block_template_cache: BlockTemplateCache,
     mempool: RwLock<Mempool>,
+    mempool_policy_file: Option<PathBuf>,
     counters: Arc<MiningCounters>,
 }
 
@@ -62,19 +70,35 @@ impl MiningManager {
         relay_non_std_transactions: bool,
         max_block_mass: u64,
         ram_scale: f64,
+        record_fee_estimate_history: bool,
         cache_lifetime: Option<u64>,
         counters: Arc<MiningCounters>,
     ) -> Self {
-        let config =
-            Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass).apply_ram_scale(ram_scale);
+        let config = Config::build_default(target_time_per_block, relay_non_std_transactions, max_block_mass)
+            .apply_ram_scale(ram_scale)
+            .apply_fee_estimate_history(record_fee_estimate_history);
         Self::with_config(config, cache_lifetime, counters)
     }
 
+    /// Sends the mempool transaction notifications through `notification_root`
+    pub fn with_notification_root(mut self, notification_root: Arc<ConsensusNotificationRoot>) -> Self {
+        self.mempool.get_mut().set_notification_root(notification_root);
+        self
+    }
+
+    /// Applies the mempool policies configured in the TOML file at `path` (see [`PolicyConfig`]).
+    /// The file is read again by [`Self::reload_mempool_policy`].
+    pub fn with_mempool_policy_file(mut self, path: PathBuf) -> Result<Self, PolicyError> {
+        self.mempool.get_mut().set_policies(PolicyConfig::load(&path)?);
+        self.mempool_policy_file = Some(path);
+        Ok(self)
+    }
+
     pub(crate) fn with_config(config: Config, cache_lifetime: Option<u64>, counters: Arc<MiningCounters>) -> Self {
         let config = Arc::new(config);
         let mempool = RwLock::new(Mempool::new(config.clone(), counters.clone()));
         let block_template_cache = BlockTemplateCache::new(cache_lifetime);
-        Self { config, block_template_cache, mempool, counters }
+        Self { config, block_template_cache, mempool, mempool_policy_file: None, counters }
     }
 
     pub fn get_block_template(&self, consensus: &dyn ConsensusApi, miner_data: &MinerData) -> MiningManagerResult<BlockTemplate> {
@@ -255,6 +279,63 @@ impl MiningManager {
         Ok(resp)
     }
 
+    /// Returns the page of the recorded fee estimate history selected by `query`, optionally replaying alternative
+    /// estimator parameters against the complete history. Returns `None` if fee estimate history recording is disabled.
+    pub(crate) fn get_fee_estimate_history(
+        &self,
+        query: FeeEstimateHistoryQuery,
+        replay_params: Option<FeeEstimateReplayParams>,
+    ) -> Option<FeeEstimateHistoryExport> {
+        let history = self.mempool.read().fee_estimate_history()?;
+        // Only the page and the replay data are copied under the history lock, the replay itself runs without it
+        let (mut export, replay_snapshot) = {
+            let history = history.read();
+            (history.export(query), replay_params.map(|_| history.replay_snapshot()))
+        };
+        export.replay_report = replay_params.zip(replay_snapshot).map(|(params, snapshot)| snapshot.replay(params));
+        Some(export)
+    }
+
+    /// Returns the aggregated mempool state along with a feerate histogram built upon the realtime feerate estimations,
+    /// and optionally the details of every transaction
+    pub(crate) fn get_mempool_info(&self, include_entries: bool) -> MempoolInfo {
+        let args = FeerateEstimatorArgs::new(self.config.network_blocks_per_second, self.config.maximum_mass_per_block);
+        let mempool = self.mempool.read();
+        let estimations = mempool.build_feerate_estimator(args).calc_estimations(self.config.minimum_feerate());
+        mempool.get_mempool_info(&estimations, include_entries)
+    }
+
+    /// Returns the dependency graph of a mempool transaction, or `None` if the transaction is not in the
+    /// transaction pool
+    pub(crate) fn get_mempool_transaction_graph(&self, transaction_id: &TransactionId) -> Option<MempoolTransactionGraph> {
+        self.mempool.read().get_transaction_graph(transaction_id)
+    }
+
+    /// Adds a virtual fee delta to a transaction, altering its priority in block templates and replacements
+    /// by fee, and returns the resulting cumulative delta of the transaction
+    pub(crate) fn prioritise_transaction(&self, consensus: &dyn ConsensusApi, transaction_id: TransactionId, fee_delta: i64) -> i64 {
+        let total = self.mempool.write().prioritise_transaction(consensus, transaction_id, fee_delta);
+        // Make sure the next block template reflects the new priority
+        self.block_template_cache.clear();
+        total
+    }
+
+    /// Returns the fee deltas registered through [`Self::prioritise_transaction`]
+    pub(crate) fn get_prioritised_transactions(&self) -> Vec<PrioritisedTransaction> {
+        self.mempool.read().get_prioritised_transactions()
+    }
+
+    /// Reloads the mempool policy file, returning a description of the active policies.
+    /// On error the current policies are kept.
+    pub fn reload_mempool_policy(&self) -> Result<Vec<String>, PolicyError> {
+        let path = self.mempool_policy_file.as_ref().ok_or(PolicyError::NoPolicyFile)?;
+        let policies = PolicyConfig::load(path)?;
+        let description = policies.describe();
+        self.mempool.write().set_policies(policies);
+        info!("Mempool policy reloaded from {}: {} active policies", path.display(), description.len());
+        Ok(description)
+    }
+
     /// Clears the block template cache, forcing the next call to get_block_template to build a new block template.
     #[cfg(test)]
     pub(crate) fn clear_block_template(&self) {
@@ -325,6 +406,53 @@ impl MiningManager {
         }
     }
 
+    /// Validates a transaction in Dandelion++ stem phase against the mempool and keeps it apart in the stem pool,
+    /// where it is not visible until [`Self::fluff_stem_transactions`] moves it into the mempool.
+    ///
+    /// Stem transactions may chain on each other but cannot be orphans.
+    pub fn validate_and_stem_transaction(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transaction: Transaction,
+        priority: Priority,
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<Arc<Transaction>> {
+        // read lock on mempool
+        let TransactionPreValidation { mut transaction, feerate_threshold } = self
+            .mempool
+            .read()
+            .pre_validate_and_populate_stem_transaction(consensus, MutableTransaction::from_tx(transaction), rbf_policy)?;
+        let args = TransactionValidationArgs::new(feerate_threshold);
+        // no lock on mempool
+        let validation_result = validate_mempool_transaction(consensus, &mut transaction, &args);
+        // write lock on mempool
+        Ok(self.mempool.write().post_validate_and_stem_transaction(validation_result, transaction, priority, rbf_policy)?)
+    }
+
+    /// Moves stem transactions into the mempool as they enter the fluff phase. Stem ancestors of the transactions
+    /// are moved as well since they get disclosed anyway. Every transaction is validated again, from scratch, and
+    /// the ones which became invalid in the meantime are dropped.
+    ///
+    /// Returns the transactions accepted into the mempool, including the unorphaned ones.
+    pub fn fluff_stem_transactions(&self, consensus: &dyn ConsensusApi, transaction_ids: &[TransactionId]) -> Vec<Arc<Transaction>> {
+        let stem_transactions = self.mempool.write().take_stem_transactions(transaction_ids);
+        let mut accepted_transactions = Vec::with_capacity(stem_transactions.len());
+        for stem in stem_transactions {
+            let transaction_id = stem.tx.id();
+            match self.validate_and_insert_mutable_transaction(
+                consensus,
+                MutableTransaction::new(stem.tx),
+                stem.priority,
+                Orphan::Forbidden,
+                stem.rbf_policy,
+            ) {
+                Ok(insertion) => accepted_transactions.extend(insertion.accepted),
+                Err(err) => debug!("Failed to fluff stem transaction {0}: {1}", transaction_id, err),
+            }
+        }
+        accepted_transactions
+    }
+
     fn validate_and_insert_unorphaned_transactions(
         &self,
         consensus: &dyn ConsensusApi,
@@ -867,6 +995,44 @@ impl MiningManagerProxy {
         consensus.clone().spawn_blocking(move |c| self.inner.get_realtime_feerate_estimations_verbose(c, prefix)).await
     }
 
+    /// Returns the page of the recorded fee estimate history selected by `query`, optionally replaying alternative
+    /// estimator parameters against the complete history. Returns `None` if fee estimate history recording is disabled.
+    pub async fn get_fee_estimate_history(
+        self,
+        query: FeeEstimateHistoryQuery,
+        replay_params: Option<FeeEstimateReplayParams>,
+    ) -> Option<FeeEstimateHistoryExport> {
+        spawn_blocking(move || self.inner.get_fee_estimate_history(query, replay_params)).await.unwrap()
+    }
+
+    /// Returns the aggregated mempool state along with a feerate histogram built upon the realtime feerate estimations,
+    /// and optionally the details of every transaction
+    pub async fn get_mempool_info(self, include_entries: bool) -> MempoolInfo {
+        spawn_blocking(move || self.inner.get_mempool_info(include_entries)).await.unwrap()
+    }
+
+    /// Returns the dependency graph of a mempool transaction, or `None` if the transaction is not in the
+    /// transaction pool
+    pub async fn get_mempool_transaction_graph(self, transaction_id: TransactionId) -> Option<MempoolTransactionGraph> {
+        spawn_blocking(move || self.inner.get_mempool_transaction_graph(&transaction_id)).await.unwrap()
+    }
+
+    /// Adds a virtual fee delta to a transaction, altering its priority in block templates and replacements
+    /// by fee, and returns the resulting cumulative delta of the transaction
+    pub async fn prioritise_transaction(self, consensus: &ConsensusProxy, transaction_id: TransactionId, fee_delta: i64) -> i64 {
+        consensus.clone().spawn_blocking(move |c| self.inner.prioritise_transaction(c, transaction_id, fee_delta)).await
+    }
+
+    /// Returns the registered fee deltas
+    pub async fn get_prioritised_transactions(self) -> Vec<PrioritisedTransaction> {
+        spawn_blocking(move || self.inner.get_prioritised_transactions()).await.unwrap()
+    }
+
+    /// Reloads the mempool policy file, returning a description of the active policies
+    pub async fn reload_mempool_policy(self) -> Result<Vec<String>, PolicyError> {
+        spawn_blocking(move || self.inner.reload_mempool_policy()).await.unwrap()
+    }
+
     /// Validates a transaction and adds it to the set of known transactions that have not yet been
     /// added to any block.
     ///
@@ -888,6 +1054,28 @@ impl MiningManagerProxy {
             .await
     }
 
+    /// Validates a transaction in Dandelion++ stem phase and keeps it apart in the stem pool
+    /// (see [`MiningManager::validate_and_stem_transaction`])
+    pub async fn validate_and_stem_transaction(
+        self,
+        consensus: &ConsensusProxy,
+        transaction: Transaction,
+        priority: Priority,
+        rbf_policy: RbfPolicy,
+    ) -> MiningManagerResult<Arc<Transaction>> {
+        consensus.clone().spawn_blocking(move |c| self.inner.validate_and_stem_transaction(c, transaction, priority, rbf_policy)).await
+    }
+
+    /// Moves stem transactions into the mempool as they enter the fluff phase
+    /// (see [`MiningManager::fluff_stem_transactions`])
+    pub async fn fluff_stem_transactions(
+        self,
+        consensus: &ConsensusProxy,
+        transaction_ids: Vec<TransactionId>,
+    ) -> Vec<Arc<Transaction>> {
+        consensus.clone().spawn_blocking(move |c| self.inner.fluff_stem_transactions(c, &transaction_ids)).await
+    }
+
     /// Validates a batch of transactions, handling iteratively only the independent ones, and
     /// adds those to the set of known transactions that have not yet been added to any block.
     ///
@@ -10,7 +10,7 @@ mod tests {
             model::frontier::selectors::TakeAllSelector,
             tx::{Orphan, Priority, RbfPolicy},
         },
-        model::{tx_insert::TransactionInsertion, tx_query::TransactionQuery},
+        model::{prioritised_tx::PrioritisedTransaction, tx_insert::TransactionInsertion, tx_query::TransactionQuery},
         testutils::consensus_mock::ConsensusMock,
         MiningCounters,
     };
@@ -29,8 +29,17 @@ mod tests {
             TransactionOutput, UtxoEntry,
         },
     };
+    use kaspa_consensus_notify::{
+        notification::{MempoolTransactionRemovalReason, Notification},
+        root::ConsensusNotificationRoot,
+    };
     use kaspa_hashes::Hash;
     use kaspa_mining_errors::mempool::RuleResult;
+    use kaspa_notify::{
+        listener::ListenerId,
+        scope::{MempoolTransactionAddedScope, MempoolTransactionRemovedScope, Scope},
+        subscriber::SubscriptionManager,
+    };
     use kaspa_txscript::{
         pay_to_address_script, pay_to_script_hash_signature_script,
         test_helpers::{create_transaction, create_transaction_with_change, op_true_script},
@@ -603,6 +612,129 @@ mod tests {
         }
     }
 
+    /// test_mempool_transaction_notifications verifies that the insertion and the removal of transactions
+    /// are notified through the notification root once subscribed.
+    #[tokio::test]
+    async fn test_mempool_transaction_notifications() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let (sender, receiver) = async_channel::unbounded();
+        let notification_root = Arc::new(ConsensusNotificationRoot::new(sender));
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters)
+            .with_notification_root(notification_root.clone());
+
+        // Without any subscription, no notification is sent
+        let transaction = create_transaction_with_utxo_entry(0, 0);
+        validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), transaction.clone()).unwrap();
+        assert!(receiver.is_empty(), "no notification should be sent without subscription");
+
+        notification_root
+            .start_notify(ListenerId::default(), Scope::MempoolTransactionAdded(MempoolTransactionAddedScope::default()))
+            .await
+            .unwrap();
+        notification_root
+            .start_notify(ListenerId::default(), Scope::MempoolTransactionRemoved(MempoolTransactionRemovedScope::default()))
+            .await
+            .unwrap();
+
+        let transaction = create_transaction_with_utxo_entry(1, 0);
+        validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), transaction.clone()).unwrap();
+        match receiver.try_recv() {
+            Ok(Notification::MempoolTransactionAdded(notification)) => {
+                assert_eq!(notification.transaction.id(), transaction.id());
+                assert_eq!(notification.fee, transaction.calculated_fee.unwrap());
+            }
+            other => panic!("expected a mempool transaction added notification but got {other:?}"),
+        }
+
+        let block_transactions = build_block_transactions(once(transaction.tx.as_ref()));
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &block_transactions);
+        assert!(result.is_ok());
+        match receiver.try_recv() {
+            Ok(Notification::MempoolTransactionRemoved(notification)) => {
+                assert_eq!(notification.transaction.id(), transaction.id());
+                assert_eq!(notification.reason, MempoolTransactionRemovalReason::Accepted);
+            }
+            other => panic!("expected a mempool transaction removed notification but got {other:?}"),
+        }
+        assert!(receiver.is_empty());
+    }
+
+    /// test_stem_transactions verifies that stem transactions are kept out of every mempool query and notification
+    /// until they get fluffed, and that they are dropped when a conflicting transaction enters the mempool.
+    #[tokio::test]
+    async fn test_stem_transactions() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let (sender, receiver) = async_channel::unbounded();
+        let notification_root = Arc::new(ConsensusNotificationRoot::new(sender));
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters)
+            .with_notification_root(notification_root.clone());
+        notification_root
+            .start_notify(ListenerId::default(), Scope::MempoolTransactionAdded(MempoolTransactionAddedScope::default()))
+            .await
+            .unwrap();
+
+        let (parent_tx, child_tx) = create_parent_and_children_transactions(&consensus, vec![500 * SOMPI_PER_KASPA]);
+        for transaction in [&parent_tx, &child_tx] {
+            let result = mining_manager.validate_and_stem_transaction(
+                consensus.as_ref(),
+                transaction.clone(),
+                Priority::Low,
+                RbfPolicy::Allowed,
+            );
+            assert!(result.is_ok(), "the stem pool should accept a transaction chained on a stem transaction");
+        }
+        let result =
+            mining_manager.validate_and_stem_transaction(consensus.as_ref(), child_tx.clone(), Priority::Low, RbfPolicy::Allowed);
+        assert!(matches!(into_mempool_result(result), Err(RuleError::RejectDuplicateStem(id)) if id == child_tx.id()));
+
+        // Stem transactions are invisible
+        for transaction in [&parent_tx, &child_tx] {
+            assert!(mining_manager.get_transaction(&transaction.id(), TransactionQuery::All).is_none());
+            assert!(!mining_manager.has_transaction(&transaction.id(), TransactionQuery::All));
+            assert!(mining_manager.get_mempool_transaction_graph(&transaction.id()).is_none());
+        }
+        let (transactions, orphans) = mining_manager.get_all_transactions(TransactionQuery::All);
+        assert!(transactions.is_empty() && orphans.is_empty());
+        let info = mining_manager.get_mempool_info(true);
+        assert_eq!(info.transaction_count, 0);
+        assert!(info.entries.is_empty());
+        assert!(receiver.is_empty(), "stem transactions should not be notified");
+
+        // Fluffing the child discloses its stem parent as well, parent first
+        let fluffed = mining_manager.fluff_stem_transactions(consensus.as_ref(), &[child_tx.id()]);
+        assert_eq!(fluffed.iter().map(|tx| tx.id()).collect_vec(), vec![parent_tx.id(), child_tx.id()]);
+        for transaction in [&parent_tx, &child_tx] {
+            assert!(mining_manager.has_transaction(&transaction.id(), TransactionQuery::TransactionsOnly));
+            match receiver.try_recv() {
+                Ok(Notification::MempoolTransactionAdded(notification)) => assert_eq!(notification.transaction.id(), transaction.id()),
+                other => panic!("expected a mempool transaction added notification but got {other:?}"),
+            }
+        }
+        assert_eq!(mining_manager.get_mempool_info(true).entries.len(), 2);
+        assert!(mining_manager.fluff_stem_transactions(consensus.as_ref(), &[child_tx.id()]).is_empty());
+
+        // A transaction entering the mempool drops the stem transactions it double spends
+        let (stem_tx, _) = create_parent_and_children_transactions(&consensus, vec![600 * SOMPI_PER_KASPA]);
+        let mut double_spend_tx = stem_tx.clone();
+        double_spend_tx.outputs[0].value -= 1;
+        double_spend_tx.finalize();
+        let result =
+            mining_manager.validate_and_stem_transaction(consensus.as_ref(), stem_tx.clone(), Priority::Low, RbfPolicy::Allowed);
+        assert!(result.is_ok());
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            double_spend_tx.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "a stem transaction should not prevent a double spend from entering the mempool");
+        assert!(mining_manager.fluff_stem_transactions(consensus.as_ref(), &[stem_tx.id()]).is_empty());
+        assert!(!mining_manager.has_transaction(&stem_tx.id(), TransactionQuery::All));
+    }
+
     #[test]
     /// test_double_spend_with_block verifies that any transactions which are now double spends as a result of the block's new transactions
     /// will be removed from the mempool.
@@ -1174,6 +1306,200 @@ mod tests {
         assert!(validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), too_big_tx.clone()).is_err());
     }
 
+    #[test]
+    fn test_mempool_introspection() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let config = Config::build_default(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS);
+        let mining_manager = MiningManager::with_config(config, None, counters);
+
+        // A low-priority chain parent -> child -> grandchild and an unrelated high-priority transaction
+        let (parent_tx, child_tx) = create_parent_and_children_transactions(&consensus, vec![500 * SOMPI_PER_KASPA]);
+        let grandchild_tx = create_transaction(&child_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let (high_priority_tx, _) = create_parent_and_children_transactions(&consensus, vec![600 * SOMPI_PER_KASPA]);
+        let chain = [parent_tx.clone(), child_tx.clone(), grandchild_tx.clone()];
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            chain.iter(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        validate_and_insert_transactions(
+            &mining_manager,
+            consensus.as_ref(),
+            once(&high_priority_tx),
+            Priority::High,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+
+        let info = mining_manager.get_mempool_info(false);
+        assert_eq!(info.transaction_count, 4);
+        assert_eq!(info.ready_transaction_count, 2);
+        assert_eq!(info.orphan_count, 0);
+        assert_eq!(info.total_fees, 4 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        assert_eq!(info.feerate_histogram.iter().map(|bucket| bucket.transaction_count).sum::<u64>(), 4);
+        assert_eq!(info.feerate_histogram.iter().map(|bucket| bucket.total_mass).sum::<u64>(), info.total_mass);
+        assert!(info.feerate_histogram.last().unwrap().estimated_seconds.is_none());
+        assert!(info.entries.is_empty());
+
+        let info = mining_manager.get_mempool_info(true);
+        assert_eq!(info.entries.len(), 4);
+        let entry = |id: TransactionId| info.entries.iter().find(|entry| entry.transaction_id == id).unwrap();
+        let (parent, child, grandchild, high_priority) =
+            (entry(parent_tx.id()), entry(child_tx.id()), entry(grandchild_tx.id()), entry(high_priority_tx.id()));
+        assert_eq!((parent.ancestor_count, parent.descendant_count, parent.eviction_rank), (0, 2, Some(0)));
+        assert_eq!((child.ancestor_count, child.descendant_count, child.eviction_rank), (1, 1, None));
+        assert_eq!((grandchild.ancestor_count, grandchild.descendant_count, grandchild.eviction_rank), (2, 0, None));
+        assert_eq!((high_priority.is_high_priority, high_priority.eviction_rank), (true, None));
+
+        let graph = mining_manager.get_mempool_transaction_graph(&child_tx.id()).unwrap();
+        assert_eq!(graph.nodes.iter().map(|node| node.transaction_id).collect_vec(), chain.iter().map(|tx| tx.id()).collect_vec());
+        assert_eq!(graph.nodes[0].parent_ids, vec![]);
+        assert_eq!(graph.nodes[1].parent_ids, vec![parent_tx.id()]);
+        assert_eq!(graph.nodes[2].parent_ids, vec![child_tx.id()]);
+
+        let graph = mining_manager.get_mempool_transaction_graph(&high_priority_tx.id()).unwrap();
+        assert_eq!(graph.nodes.len(), 1);
+        assert!(mining_manager.get_mempool_transaction_graph(&TransactionId::default()).is_none());
+    }
+
+    /// test_prioritise_transaction verifies that fee deltas are applied to the effective fee of transactions,
+    /// including those entering the mempool after the delta is set, that they are honored by replace by fee
+    /// without altering the reported fees and that they are dropped once the transaction leaves the mempool
+    #[test]
+    fn test_prioritise_transaction() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, MAX_BLOCK_MASS, None, counters);
+
+        let funding_tx = create_transaction_without_input(vec![500 * SOMPI_PER_KASPA]);
+        consensus.add_transaction(funding_tx.clone(), 1);
+        let low_fee_tx = create_transaction(&funding_tx, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let double_spending_tx = create_transaction(&funding_tx, 2 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let fee_delta = 10 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE as i64;
+
+        // A delta can be set before the transaction enters the mempool and accumulates
+        assert_eq!(mining_manager.prioritise_transaction(consensus.as_ref(), low_fee_tx.id(), fee_delta / 2), fee_delta / 2);
+        assert_eq!(mining_manager.prioritise_transaction(consensus.as_ref(), low_fee_tx.id(), fee_delta / 2), fee_delta);
+        assert_eq!(
+            mining_manager.get_prioritised_transactions(),
+            vec![PrioritisedTransaction { transaction_id: low_fee_tx.id(), fee_delta, in_mempool: false }]
+        );
+
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            low_fee_tx.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Forbidden,
+        );
+        assert!(result.is_ok(), "inserting a prioritised transaction should succeed");
+        assert!(mining_manager.get_prioritised_transactions()[0].in_mempool);
+        let info = mining_manager.get_mempool_info(true);
+        assert_eq!(info.total_fees, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        assert_eq!(info.entries[0].fee, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let estimations = mining_manager.get_realtime_feerate_estimations();
+
+        // The effective fee of the mempool transaction exceeds the fee of the double spend
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            double_spending_tx.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Mandatory,
+        );
+        assert!(result.is_err(), "a replacement by a transaction with a lower effective feerate should fail");
+
+        // Lowering the delta restores the regular replace by fee behavior and the delta of the replaced
+        // transaction is dropped
+        assert_eq!(mining_manager.prioritise_transaction(consensus.as_ref(), low_fee_tx.id(), 1 - fee_delta), 1);
+        // The feerate estimations rely on actual fees only
+        let (feerate, other_feerate) =
+            (estimations.priority_bucket.feerate, mining_manager.get_realtime_feerate_estimations().priority_bucket.feerate);
+        assert!((feerate - other_feerate).abs() < 1e-9 * feerate, "fee deltas should not alter the feerate estimations");
+        let result = mining_manager.validate_and_insert_transaction(
+            consensus.as_ref(),
+            double_spending_tx.clone(),
+            Priority::Low,
+            Orphan::Forbidden,
+            RbfPolicy::Mandatory,
+        );
+        assert!(result.is_ok(), "a replacement by a transaction with a higher effective feerate should succeed");
+        assert!(mining_manager.get_prioritised_transactions().is_empty());
+
+        // A deprioritised transaction has its delta dropped once accepted in a block
+        assert_eq!(mining_manager.prioritise_transaction(consensus.as_ref(), double_spending_tx.id(), -fee_delta), -fee_delta);
+        let info = mining_manager.get_mempool_info(false);
+        assert_eq!(info.total_fees, 2 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE);
+        let block_transactions = build_block_transactions(once(&double_spending_tx));
+        let result = mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &block_transactions);
+        assert!(result.is_ok());
+        assert!(mining_manager.get_prioritised_transactions().is_empty());
+    }
+
+    /// test_prioritised_transaction_in_block_template verifies that a prioritised transaction gets selected into a
+    /// block template having room for a single transaction
+    #[test]
+    fn test_prioritised_transaction_in_block_template() {
+        const TX_COUNT: usize = 3;
+        let txs = (0..TX_COUNT).map(|i| create_transaction_with_utxo_entry(i as u32, 0)).collect_vec();
+
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let tx_mass = txs[0].calculated_non_contextual_masses.unwrap().max();
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, tx_mass * 3 / 2, None, counters);
+        for tx in txs.iter() {
+            validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), tx.clone()).unwrap();
+        }
+
+        let prioritised_id = txs[TX_COUNT - 1].id();
+        mining_manager.prioritise_transaction(
+            consensus.as_ref(),
+            prioritised_id,
+            1_000 * DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE as i64,
+        );
+
+        let miner_data = get_miner_data(Prefix::Testnet);
+        let template = mining_manager.get_block_template(consensus.as_ref(), &miner_data).unwrap();
+        assert_eq!(template.block.transactions.iter().skip(1).map(|tx| tx.id()).collect_vec(), vec![prioritised_id]);
+    }
+
+    /// test_zeroing_fee_delta_in_block_template verifies that negative fee deltas cancelling the whole fee of
+    /// transactions do not break the sampling of a frontier holding many more transactions than a block template
+    #[test]
+    fn test_zeroing_fee_delta_in_block_template() {
+        const TX_COUNT: usize = 12;
+        let txs = (0..TX_COUNT).map(|i| create_transaction_with_utxo_entry(i as u32, 0)).collect_vec();
+
+        let consensus = Arc::new(ConsensusMock::new());
+        let counters = Arc::new(MiningCounters::default());
+        let tx_mass = txs[0].calculated_non_contextual_masses.unwrap().max();
+        let mining_manager = MiningManager::new(TARGET_TIME_PER_BLOCK, false, tx_mass * 3 / 2, None, counters);
+        for tx in txs.iter() {
+            validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), tx.clone()).unwrap();
+        }
+
+        let miner_data = get_miner_data(Prefix::Testnet);
+        let zeroing_delta = |tx: &MutableTransaction| -(tx.calculated_fee.unwrap() as i64);
+
+        // Zero out the fee of all transactions but one, then of all of them
+        for tx in txs.iter().skip(1) {
+            mining_manager.prioritise_transaction(consensus.as_ref(), tx.id(), zeroing_delta(tx));
+        }
+        for _ in 0..10 {
+            let template = mining_manager.get_block_template(consensus.as_ref(), &miner_data).unwrap();
+            assert_eq!(template.block.transactions.len(), 2, "the block template should hold a single transaction");
+        }
+        mining_manager.prioritise_transaction(consensus.as_ref(), txs[0].id(), 2 * zeroing_delta(&txs[0]));
+        for _ in 0..10 {
+            let template = mining_manager.get_block_template(consensus.as_ref(), &miner_data).unwrap();
+            assert_eq!(template.block.transactions.len(), 2, "the block template should hold a single transaction");
+        }
+    }
+
     fn validate_and_insert_mutable_transaction(
         mining_manager: &MiningManager,
         consensus: &dyn ConsensusApi,
@@ -11,9 +11,15 @@ pub(crate) const DEFAULT_ACCEPTED_TRANSACTION_EXPIRE_SCAN_INTERVAL_SECONDS: u64
 pub(crate) const DEFAULT_ORPHAN_EXPIRE_INTERVAL_SECONDS: u64 = 60;
 pub(crate) const DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 10;
 
+pub(crate) const DEFAULT_FEE_ESTIMATE_HISTORY_SAMPLE_INTERVAL_MILLISECONDS: u64 = 1000;
+pub(crate) const DEFAULT_FEE_ESTIMATE_HISTORY_MAXIMUM_SAMPLES: usize = 24 * 60 * 60;
+pub(crate) const DEFAULT_FEE_ESTIMATE_HISTORY_MAXIMUM_INCLUSIONS: usize = 1_000_000;
+
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS: u64 = 100_000;
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT: u64 = 500;
 
+pub(crate) const DEFAULT_MAXIMUM_STEM_TRANSACTION_COUNT: usize = 10_000;
+
 /// DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE specifies the minimum transaction fee for a transaction to be accepted to
 /// the mempool and relayed. It is specified in sompi per 1kg (or 1000 grams) of transaction mass.
 pub(crate) const DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE: u64 = 1000;
@@ -40,12 +46,17 @@ pub struct Config {
     pub orphan_expire_scan_interval_daa_score: u64,
     pub maximum_orphan_transaction_mass: u64,
     pub maximum_orphan_transaction_count: u64,
+    pub maximum_stem_transaction_count: usize,
     pub accept_non_standard: bool,
     pub maximum_mass_per_block: u64,
     pub minimum_relay_transaction_fee: u64,
     pub minimum_standard_transaction_version: u16,
     pub maximum_standard_transaction_version: u16,
     pub network_blocks_per_second: u64,
+    pub record_fee_estimate_history: bool,
+    pub fee_estimate_history_sample_interval_milliseconds: u64,
+    pub fee_estimate_history_maximum_samples: usize,
+    pub fee_estimate_history_maximum_inclusions: usize,
 }
 
 impl Config {
@@ -64,12 +75,17 @@ impl Config {
         orphan_expire_scan_interval_daa_score: u64,
         maximum_orphan_transaction_mass: u64,
         maximum_orphan_transaction_count: u64,
+        maximum_stem_transaction_count: usize,
         accept_non_standard: bool,
         maximum_mass_per_block: u64,
         minimum_relay_transaction_fee: u64,
         minimum_standard_transaction_version: u16,
         maximum_standard_transaction_version: u16,
         network_blocks_per_second: u64,
+        record_fee_estimate_history: bool,
+        fee_estimate_history_sample_interval_milliseconds: u64,
+        fee_estimate_history_maximum_samples: usize,
+        fee_estimate_history_maximum_inclusions: usize,
     ) -> Self {
         Self {
             maximum_transaction_count,
@@ -85,12 +101,17 @@ impl Config {
             orphan_expire_scan_interval_daa_score,
             maximum_orphan_transaction_mass,
             maximum_orphan_transaction_count,
+            maximum_stem_transaction_count,
             accept_non_standard,
             maximum_mass_per_block,
             minimum_relay_transaction_fee,
             minimum_standard_transaction_version,
             maximum_standard_transaction_version,
             network_blocks_per_second,
+            record_fee_estimate_history,
+            fee_estimate_history_sample_interval_milliseconds,
+            fee_estimate_history_maximum_samples,
+            fee_estimate_history_maximum_inclusions,
         }
     }
 
@@ -114,12 +135,17 @@ impl Config {
             orphan_expire_scan_interval_daa_score: DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS * 1000 / target_milliseconds_per_block,
             maximum_orphan_transaction_mass: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_MASS,
             maximum_orphan_transaction_count: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT,
+            maximum_stem_transaction_count: DEFAULT_MAXIMUM_STEM_TRANSACTION_COUNT,
             accept_non_standard: relay_non_std_transactions,
             maximum_mass_per_block: max_block_mass,
             minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
             minimum_standard_transaction_version: DEFAULT_MINIMUM_STANDARD_TRANSACTION_VERSION,
             maximum_standard_transaction_version: DEFAULT_MAXIMUM_STANDARD_TRANSACTION_VERSION,
             network_blocks_per_second: 1000 / target_milliseconds_per_block,
+            record_fee_estimate_history: false,
+            fee_estimate_history_sample_interval_milliseconds: DEFAULT_FEE_ESTIMATE_HISTORY_SAMPLE_INTERVAL_MILLISECONDS,
+            fee_estimate_history_maximum_samples: DEFAULT_FEE_ESTIMATE_HISTORY_MAXIMUM_SAMPLES,
+            fee_estimate_history_maximum_inclusions: DEFAULT_FEE_ESTIMATE_HISTORY_MAXIMUM_INCLUSIONS,
         }
     }
 
@@ -130,6 +156,12 @@ impl Config {
         self
     }
 
+    /// Enables the recording of feerate estimations and transaction inclusion times (see [`crate::feerate::history`])
+    pub fn apply_fee_estimate_history(mut self, record_fee_estimate_history: bool) -> Self {
+        self.record_fee_estimate_history = record_fee_estimate_history;
+        self
+    }
+
     /// Returns the minimum standard fee/mass ratio currently required by the mempool
     pub(crate) fn minimum_feerate(&self) -> f64 {
         // The parameter minimum_relay_transaction_fee is in sompi/kg units so divide by 1000 to get sompi/gram
@@ -1,16 +1,19 @@
-use crate::mempool::{
-    errors::RuleResult,
-    model::{
-        pool::Pool,
-        tx::{MempoolTransaction, TxRemovalReason},
+use crate::{
+    feerate::{history::TransactionInclusionRecord, FeerateEstimatorArgs},
+    mempool::{
+        errors::RuleResult,
+        model::{
+            pool::Pool,
+            tx::{MempoolTransaction, TxRemovalReason},
+        },
+        Mempool,
     },
-    Mempool,
 };
 use kaspa_consensus_core::{
     api::ConsensusApi,
     tx::{Transaction, TransactionId},
 };
-use kaspa_core::time::Stopwatch;
+use kaspa_core::time::{unix_now, Stopwatch};
 use std::{collections::HashSet, sync::atomic::Ordering};
 
 impl Mempool {
@@ -24,8 +27,11 @@ impl Mempool {
         let mut tx_accepted_counts = 0;
         let mut input_counts = 0;
         let mut output_counts = 0;
+        let now = unix_now();
+        self.sample_fee_estimate_history(now, block_daa_score);
         for transaction in block_transactions[1..].iter() {
             let transaction_id = transaction.id();
+            self.record_transaction_inclusion(&transaction_id, now, block_daa_score);
             // Rust rewrite: This behavior does differ from golang implementation.
             // If the transaction got accepted via a peer but is still an orphan here, do not remove
             // its redeemers in the orphan pool. We give those a chance to be unorphaned and included
@@ -34,7 +40,9 @@ impl Mempool {
                 self.remove_transaction(&transaction_id, false, TxRemovalReason::Accepted, "")?;
             }
             self.remove_double_spends(transaction)?;
+            self.stem_pool.remove_conflicts(transaction);
             self.orphan_pool.remove_orphan(&transaction_id, false, TxRemovalReason::Accepted, "")?;
+            self.transaction_pool.clear_fee_delta(&transaction_id);
             if self.accepted_transactions.add(transaction_id, block_daa_score) {
                 tx_accepted_counts += 1;
                 input_counts += transaction.inputs.len();
@@ -66,6 +74,31 @@ impl Mempool {
         self.transaction_pool.collect_expired_low_priority_transactions(consensus.get_virtual_daa_score())
     }
 
+    fn sample_fee_estimate_history(&mut self, now: u64, daa_score: u64) {
+        let Some(history) = self.fee_estimate_history.as_ref() else { return };
+        let mut history = history.write();
+        if history.is_sample_due(now) {
+            let args = FeerateEstimatorArgs::new(self.config.network_blocks_per_second, self.config.maximum_mass_per_block);
+            let estimator = self.transaction_pool.build_feerate_estimator(args);
+            history.record_sample(now, daa_score, &estimator, self.config.minimum_feerate());
+        }
+    }
+
+    fn record_transaction_inclusion(&mut self, transaction_id: &TransactionId, now: u64, block_daa_score: u64) {
+        let Some(history) = self.fee_estimate_history.as_ref() else { return };
+        if let Some(transaction) = self.transaction_pool.get(transaction_id) {
+            history.write().record_inclusion(TransactionInclusionRecord {
+                transaction_id: *transaction_id,
+                fee: transaction.mtx.calculated_fee.unwrap_or_default(),
+                feerate: transaction.mtx.calculated_feerate().unwrap_or_default(),
+                added_timestamp: transaction.added_at_time,
+                added_daa_score: transaction.added_at_daa_score,
+                included_timestamp: now,
+                included_daa_score: block_daa_score,
+            });
+        }
+    }
+
     fn remove_double_spends(&mut self, transaction: &Transaction) -> RuleResult<()> {
         let mut transactions_to_remove = HashSet::new();
         for input in transaction.inputs.iter() {
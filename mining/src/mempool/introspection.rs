@@ -0,0 +1,140 @@
+use crate::{
+    feerate::FeerateEstimations,
+    mempool::{
+        model::{
+            frontier::feerate_key::FeerateTransactionKey,
+            pool::{Pool, PoolIndex, TransactionsEdges},
+            tx::MempoolTransaction,
+        },
+        tx::Priority,
+        Mempool,
+    },
+    model::{
+        mempool_info::{FeerateHistogramBucket, MempoolEntryInfo, MempoolGraphNode, MempoolInfo, MempoolTransactionGraph},
+        topological_index::TopologicalIndex,
+        TransactionIdSet,
+    },
+};
+use kaspa_consensus_core::tx::TransactionId;
+use std::{collections::HashMap, iter::once};
+
+impl Mempool {
+    /// Returns the aggregated state of the mempool, the feerate histogram being built upon the buckets of `estimations`.
+    /// Fees and feerates are reported as actually paid, fee deltas being ignored.
+    ///
+    /// NOTE: when `include_entries` is set, the ancestors and descendants of every transaction get traversed, so the
+    /// complexity of this operation might become quadratic in the size of the mempool if it contains deeply chained
+    /// transactions. The RPC service hence only serves the entries in unsafe RPC mode.
+    pub(crate) fn get_mempool_info(&self, estimations: &FeerateEstimations, include_entries: bool) -> MempoolInfo {
+        let mut feerate_histogram = estimations
+            .ordered_buckets()
+            .into_iter()
+            .map(|bucket| FeerateHistogramBucket {
+                feerate: bucket.feerate,
+                estimated_seconds: Some(bucket.estimated_seconds),
+                transaction_count: 0,
+                total_mass: 0,
+            })
+            .chain(once(FeerateHistogramBucket { feerate: 0.0, estimated_seconds: None, transaction_count: 0, total_mass: 0 }))
+            .collect::<Vec<_>>();
+
+        let mut info = MempoolInfo {
+            transaction_count: self.transaction_pool.len() as u64,
+            ready_transaction_count: self.transaction_pool.ready_transaction_count() as u64,
+            orphan_count: self.orphan_pool.len() as u64,
+            ready_total_mass: self.transaction_pool.ready_transaction_total_mass(),
+            estimated_size: self.transaction_pool.get_estimated_size() as u64,
+            ..Default::default()
+        };
+        for transaction in self.transaction_pool.all().values() {
+            let key = FeerateTransactionKey::with_actual_fee(transaction);
+            info.total_mass += key.mass;
+            info.total_fees += key.fee;
+            // Buckets are sorted by descending feerate and the last one has a zero lower bound
+            let bucket = feerate_histogram.iter_mut().find(|bucket| key.feerate() >= bucket.feerate).unwrap();
+            bucket.transaction_count += 1;
+            bucket.total_mass += key.mass;
+        }
+        info.feerate_histogram = feerate_histogram;
+
+        if include_entries {
+            let eviction_ranks = self
+                .transaction_pool
+                .eviction_candidates()
+                .enumerate()
+                .map(|(rank, transaction)| (transaction.id(), rank as u64))
+                .collect::<HashMap<_, _>>();
+            info.entries = self
+                .transaction_pool
+                .all()
+                .values()
+                .map(|transaction| {
+                    let key = FeerateTransactionKey::with_actual_fee(transaction);
+                    let id = transaction.id();
+                    MempoolEntryInfo {
+                        transaction_id: id,
+                        fee: key.fee,
+                        mass: key.mass,
+                        feerate: key.feerate(),
+                        is_high_priority: transaction.priority == Priority::High,
+                        added_daa_score: transaction.added_at_daa_score,
+                        ancestor_count: self.transaction_pool.get_ancestor_ids_in_pool(&id).len() as u64,
+                        descendant_count: self.transaction_pool.get_redeemer_ids_in_pool(&id).len() as u64,
+                        eviction_rank: eviction_ranks.get(&id).copied(),
+                    }
+                })
+                .collect();
+            info.entries.sort_by(|a, b| b.feerate.total_cmp(&a.feerate));
+        }
+
+        info
+    }
+
+    /// Returns the dependency graph of a transaction pool transaction, or `None` if the transaction is not in the pool
+    pub(crate) fn get_transaction_graph(&self, transaction_id: &TransactionId) -> Option<MempoolTransactionGraph> {
+        if !self.transaction_pool.has(transaction_id) {
+            return None;
+        }
+
+        let transactions: TransactionIdSet = self
+            .transaction_pool
+            .get_ancestor_ids_in_pool(transaction_id)
+            .into_iter()
+            .chain(once(*transaction_id))
+            .chain(self.transaction_pool.get_redeemer_ids_in_pool(transaction_id))
+            .collect();
+        let parents: TransactionsEdges = transactions
+            .iter()
+            .map(|id| {
+                let parent_ids = self.transaction_pool.get_parent_ids_in_pool(id).into_iter().flatten();
+                (*id, parent_ids.filter(|parent_id| transactions.contains(parent_id)).copied().collect())
+            })
+            .collect();
+        let mut chained: TransactionsEdges = TransactionsEdges::new();
+        for (id, parent_ids) in parents.iter() {
+            for parent_id in parent_ids.iter() {
+                chained.entry(*parent_id).or_default().insert(*id);
+            }
+        }
+
+        // The graph is extracted from the pool DAG so it has no cycle
+        let sorted = PoolIndex::new(transactions, chained).topological_index().unwrap();
+        let nodes = sorted
+            .into_iter()
+            .map(|id| {
+                let transaction: &MempoolTransaction = self.transaction_pool.get(&id).unwrap();
+                let key = FeerateTransactionKey::with_actual_fee(transaction);
+                MempoolGraphNode {
+                    transaction_id: id,
+                    fee: key.fee,
+                    mass: key.mass,
+                    feerate: key.feerate(),
+                    is_high_priority: transaction.priority == Priority::High,
+                    parent_ids: parents[&id].iter().copied().collect(),
+                }
+            })
+            .collect();
+
+        Some(MempoolTransactionGraph { nodes })
+    }
+}
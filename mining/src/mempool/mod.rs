@@ -1,5 +1,5 @@
 use crate::{
-    feerate::{FeerateEstimator, FeerateEstimatorArgs},
+    feerate::{history::FeeEstimateHistory, FeerateEstimator, FeerateEstimatorArgs},
     model::{
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         tx_query::TransactionQuery,
@@ -9,24 +9,38 @@ use crate::{
 
 use self::{
     config::Config,
-    model::{accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
+    model::{
+        accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, stem_pool::StemPool,
+        transactions_pool::TransactionsPool, tx::TxRemovalReason,
+    },
+    policy::MempoolPolicies,
     tx::Priority,
 };
 use kaspa_consensus_core::{
     block::TemplateTransactionSelector,
-    tx::{MutableTransaction, TransactionId},
+    tx::{MutableTransaction, Transaction, TransactionId},
+};
+use kaspa_consensus_notify::{
+    notification::{MempoolTransactionAddedNotification, MempoolTransactionRemovedNotification, Notification},
+    root::ConsensusNotificationRoot,
 };
 use kaspa_core::time::Stopwatch;
+use kaspa_notify::{events::EventType, notifier::Notify};
+use parking_lot::RwLock;
 use std::sync::Arc;
 
 pub(crate) mod check_transaction_standard;
 pub mod config;
 pub mod errors;
 pub(crate) mod handle_new_block_transactions;
+pub(crate) mod introspection;
 pub(crate) mod model;
+pub mod policy;
 pub(crate) mod populate_entries_and_try_validate;
+pub(crate) mod prioritise_transaction;
 pub(crate) mod remove_transaction;
 pub(crate) mod replace_by_fee;
+pub(crate) mod stem_transaction;
 pub(crate) mod validate_and_insert_transaction;
 
 /// Mempool contains transactions intended to be inserted into a block and mined.
@@ -45,11 +59,17 @@ pub(crate) mod validate_and_insert_transaction;
 ///   rebroadcasts them once in a while.
 /// - Transactions received through P2P have **low-priority**. They expire after
 ///   60 seconds and are removed if not inserted in a block for mining.
+/// - Transactions in Dandelion++ stem phase are held in a separate stem pool which
+///   is not visible from outside until they are moved to the transaction pool.
 pub(crate) struct Mempool {
     config: Arc<Config>,
     transaction_pool: TransactionsPool,
     orphan_pool: OrphanPool,
+    stem_pool: StemPool,
     accepted_transactions: AcceptedTransactions,
+    fee_estimate_history: Option<Arc<RwLock<FeeEstimateHistory>>>,
+    policies: MempoolPolicies,
+    notification_root: Option<Arc<ConsensusNotificationRoot>>,
     counters: Arc<MiningCounters>,
 }
 
@@ -57,8 +77,55 @@ impl Mempool {
     pub(crate) fn new(config: Arc<Config>, counters: Arc<MiningCounters>) -> Self {
         let transaction_pool = TransactionsPool::new(config.clone());
         let orphan_pool = OrphanPool::new(config.clone());
+        let stem_pool = StemPool::new(config.clone());
         let accepted_transactions = AcceptedTransactions::new(config.clone());
-        Self { config, transaction_pool, orphan_pool, accepted_transactions, counters }
+        let fee_estimate_history = config.record_fee_estimate_history.then(|| {
+            Arc::new(RwLock::new(FeeEstimateHistory::new(
+                config.fee_estimate_history_sample_interval_milliseconds,
+                config.fee_estimate_history_maximum_samples,
+                config.fee_estimate_history_maximum_inclusions,
+                1.0 / config.network_blocks_per_second as f64,
+            )))
+        });
+        Self {
+            config,
+            transaction_pool,
+            orphan_pool,
+            stem_pool,
+            accepted_transactions,
+            fee_estimate_history,
+            policies: MempoolPolicies::default(),
+            notification_root: None,
+            counters,
+        }
+    }
+
+    pub(crate) fn set_notification_root(&mut self, notification_root: Arc<ConsensusNotificationRoot>) {
+        self.notification_root = Some(notification_root);
+    }
+
+    pub(crate) fn set_policies(&mut self, policies: MempoolPolicies) {
+        self.policies = policies;
+    }
+
+    /// Notifies the insertion of a transaction into the transaction pool
+    pub(crate) fn notify_transaction_added(&self, transaction: Arc<Transaction>, fee: u64) {
+        if let Some(ref root) = self.notification_root {
+            if root.has_subscription(EventType::MempoolTransactionAdded) {
+                let notification = MempoolTransactionAddedNotification::new(transaction, fee);
+                let _ = root.notify(Notification::MempoolTransactionAdded(notification));
+            }
+        }
+    }
+
+    /// Notifies the removal of a transaction from the transaction pool
+    pub(crate) fn notify_transaction_removed(&self, transaction: Arc<Transaction>, reason: &TxRemovalReason) {
+        if let Some(ref root) = self.notification_root {
+            if root.has_subscription(EventType::MempoolTransactionRemoved) {
+                let notification = MempoolTransactionRemovedNotification::new(transaction, reason.into());
+                let _ = root.notify(Notification::MempoolTransactionRemoved(notification));
+            }
+        }
     }
 
     pub(crate) fn get_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> Option<MutableTransaction> {
@@ -134,6 +201,12 @@ impl Mempool {
         self.transaction_pool.build_feerate_estimator(args)
     }
 
+    /// Returns the recorded fee estimate history, if recording is enabled. The history has its own lock
+    /// so that it can be exported without holding the mempool lock.
+    pub(crate) fn fee_estimate_history(&self) -> Option<Arc<RwLock<FeeEstimateHistory>>> {
+        self.fee_estimate_history.clone()
+    }
+
     pub(crate) fn all_transaction_ids_with_priority(&self, priority: Priority) -> Vec<TransactionId> {
         let _sw = Stopwatch::<15>::with_threshold("all_transaction_ids_with_priority op");
         self.transaction_pool.all_transaction_ids_with_priority(priority)
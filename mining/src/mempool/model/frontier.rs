@@ -90,6 +90,30 @@ impl Frontier {
         }
     }
 
+    /// Replaces the key of a frontier transaction, keeping the average transaction mass intact
+    pub fn update(&mut self, key: &FeerateTransactionKey, new_key: FeerateTransactionKey) -> bool {
+        if self.search_tree.remove(key) {
+            self.total_mass = self.total_mass - key.mass + new_key.mass;
+            self.search_tree.insert(new_key);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns a copy of the frontier where every key is mapped through `map_key`. Keys are expected to keep
+    /// their mass and the average transaction mass is preserved.
+    pub fn map_keys(&self, map_key: impl Fn(&FeerateTransactionKey) -> FeerateTransactionKey) -> Self {
+        let mut frontier = Self::new(self.target_time_per_block_seconds);
+        for key in self.search_tree.ascending_iter() {
+            let key = map_key(key);
+            frontier.total_mass += key.mass;
+            frontier.search_tree.insert(key);
+        }
+        frontier.average_transaction_mass = self.average_transaction_mass;
+        frontier
+    }
+
     pub fn remove(&mut self, key: &FeerateTransactionKey) -> bool {
         let mass = key.mass;
         if self.search_tree.remove(key) {
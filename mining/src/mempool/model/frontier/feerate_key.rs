@@ -1,5 +1,5 @@
 use crate::{block_template::selector::ALPHA, mempool::model::tx::MempoolTransaction};
-use kaspa_consensus_core::{mass::ContextualMasses, tx::Transaction};
+use kaspa_consensus_core::tx::Transaction;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -26,6 +26,11 @@ impl FeerateTransactionKey {
         Self { fee, mass, weight: (fee as f64 / mass as f64).powi(ALPHA), tx }
     }
 
+    /// Builds the key of a mempool transaction upon its actual fee, ignoring its fee delta
+    pub(crate) fn with_actual_fee(tx: &MempoolTransaction) -> Self {
+        Self::new(tx.fee(), tx.mass(), tx.mtx.tx.clone())
+    }
+
     pub fn feerate(&self) -> f64 {
         self.fee as f64 / self.mass as f64
     }
@@ -77,14 +82,8 @@ impl Ord for FeerateTransactionKey {
 
 impl From<&MempoolTransaction> for FeerateTransactionKey {
     fn from(tx: &MempoolTransaction) -> Self {
-        // NOTE: The code below is a mempool simplification reducing the various block mass units to a
-        //       single one-dimension value (making it easier to select transactions for block templates).
-        // Future mempool improvements are expected to refine this behavior and use the multi-dimension values
-        // in order to optimize and increase block space usage.
-        let mass = ContextualMasses::new(tx.mtx.tx.mass())
-            .max(tx.mtx.calculated_non_contextual_masses.expect("masses are expected to be calculated"));
-        let fee = tx.mtx.calculated_fee.expect("fee is expected to be populated");
-        Self::new(fee, mass, tx.mtx.tx.clone())
+        // The fee delta is applied so that prioritised transactions get selected accordingly
+        Self::new(tx.effective_fee(), tx.mass(), tx.mtx.tx.clone())
     }
 }
 
@@ -0,0 +1,219 @@
+use crate::mempool::{
+    config::Config,
+    errors::{RuleError, RuleResult},
+    tx::{Priority, RbfPolicy},
+};
+use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint, TransactionOutput};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+/// A transaction in Dandelion++ stem phase along with the arguments of its future insertion into the transaction pool
+pub(crate) struct StemTransaction {
+    pub(crate) tx: Arc<Transaction>,
+    pub(crate) priority: Priority,
+    pub(crate) rbf_policy: RbfPolicy,
+}
+
+/// Pool of transactions in Dandelion++ stem phase
+///
+/// Stem transactions are validated against the mempool but are kept apart from the transaction pool, so they
+/// are neither returned by mempool queries, nor notified, nor selected into block templates. They are moved
+/// into the transaction pool, and validated again, once they enter the fluff phase.
+///
+/// A stem transaction may spend outputs of the transaction pool and of other stem transactions, but a transaction
+/// of the transaction pool never depends on a stem transaction.
+pub(crate) struct StemPool {
+    config: Arc<Config>,
+    all_transactions: HashMap<TransactionId, StemTransaction>,
+    outpoint_owner_id: HashMap<TransactionOutpoint, TransactionId>,
+}
+
+impl StemPool {
+    pub(crate) fn new(config: Arc<Config>) -> Self {
+        Self { config, all_transactions: HashMap::default(), outpoint_owner_id: HashMap::default() }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.all_transactions.len()
+    }
+
+    pub(crate) fn has(&self, transaction_id: &TransactionId) -> bool {
+        self.all_transactions.contains_key(transaction_id)
+    }
+
+    /// Returns the output of a stem transaction
+    pub(crate) fn get_output(&self, outpoint: &TransactionOutpoint) -> Option<&TransactionOutput> {
+        self.all_transactions.get(&outpoint.transaction_id).and_then(|stem| stem.tx.outputs.get(outpoint.index as usize))
+    }
+
+    pub(crate) fn try_add(&mut self, transaction: &MutableTransaction, priority: Priority, rbf_policy: RbfPolicy) -> RuleResult<()> {
+        let transaction_id = transaction.id();
+        if self.has(&transaction_id) {
+            return Err(RuleError::RejectDuplicateStem(transaction_id));
+        }
+        for input in transaction.tx.inputs.iter() {
+            if let Some(owner_id) = self.outpoint_owner_id.get(&input.previous_outpoint) {
+                return Err(RuleError::RejectDoubleSpendInStemPool(input.previous_outpoint, *owner_id));
+            }
+        }
+        if self.len() >= self.config.maximum_stem_transaction_count {
+            return Err(RuleError::RejectStemPoolIsFull(self.config.maximum_stem_transaction_count));
+        }
+        for input in transaction.tx.inputs.iter() {
+            self.outpoint_owner_id.insert(input.previous_outpoint, transaction_id);
+        }
+        self.all_transactions.insert(transaction_id, StemTransaction { tx: transaction.tx.clone(), priority, rbf_policy });
+        Ok(())
+    }
+
+    fn remove(&mut self, transaction_id: &TransactionId) -> Option<StemTransaction> {
+        let stem = self.all_transactions.remove(transaction_id)?;
+        for input in stem.tx.inputs.iter() {
+            self.outpoint_owner_id.remove(&input.previous_outpoint);
+        }
+        Some(stem)
+    }
+
+    /// Removes the given transactions along with their stem ancestors, which are returned first
+    /// so that every transaction comes after its stem parents
+    pub(crate) fn take_with_ancestors(&mut self, transaction_ids: &[TransactionId]) -> Vec<StemTransaction> {
+        let mut ordered = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = transaction_ids.iter().rev().map(|&id| (id, false)).collect::<Vec<_>>();
+        while let Some((transaction_id, parents_visited)) = stack.pop() {
+            if parents_visited {
+                ordered.push(transaction_id);
+                continue;
+            }
+            let Some(stem) = self.all_transactions.get(&transaction_id) else { continue };
+            if !visited.insert(transaction_id) {
+                continue;
+            }
+            stack.push((transaction_id, true));
+            stack.extend(
+                stem.tx
+                    .inputs
+                    .iter()
+                    .map(|input| input.previous_outpoint.transaction_id)
+                    .filter(|parent_id| self.has(parent_id) && !visited.contains(parent_id))
+                    .map(|parent_id| (parent_id, false)),
+            );
+        }
+        ordered.iter().filter_map(|transaction_id| self.remove(transaction_id)).collect()
+    }
+
+    /// Removes the transactions made obsolete by `transaction` entering the transaction pool or the DAG:
+    /// its stem copy, and the stem transactions double spending any of its inputs along with their descendants.
+    /// Returns the ids of the removed transactions
+    pub(crate) fn remove_conflicts(&mut self, transaction: &Transaction) -> Vec<TransactionId> {
+        let transaction_id = transaction.id();
+        // Redeemers of the stem copy remain valid since its outputs are still available
+        let mut removed = self.remove(&transaction_id).map(|_| vec![transaction_id]).unwrap_or_default();
+        let mut double_spends = transaction
+            .inputs
+            .iter()
+            .filter_map(|input| self.outpoint_owner_id.get(&input.previous_outpoint).copied())
+            .filter(|owner_id| *owner_id != transaction_id)
+            .collect::<Vec<_>>();
+        while let Some(conflict_id) = double_spends.pop() {
+            let Some(stem) = self.remove(&conflict_id) else { continue };
+            removed.push(conflict_id);
+            double_spends.extend(
+                (0..stem.tx.outputs.len() as u32)
+                    .filter_map(|index| self.outpoint_owner_id.get(&TransactionOutpoint::new(conflict_id, index)).copied()),
+            );
+        }
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, TransactionInput},
+    };
+
+    fn transaction(inputs: &[TransactionOutpoint], outputs: usize) -> MutableTransaction {
+        let inputs = inputs.iter().map(|&outpoint| TransactionInput::new(outpoint, vec![], 0, 0)).collect();
+        let outputs = (0..outputs).map(|i| TransactionOutput::new(i as u64 + 1, ScriptPublicKey::default())).collect();
+        MutableTransaction::from_tx(Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]))
+    }
+
+    fn stem_pool(maximum_stem_transaction_count: usize) -> StemPool {
+        let config = Config { maximum_stem_transaction_count, ..Config::build_default(1000, false, 500_000) };
+        StemPool::new(Arc::new(config))
+    }
+
+    fn add(pool: &mut StemPool, transaction: &MutableTransaction) -> RuleResult<()> {
+        pool.try_add(transaction, Priority::Low, RbfPolicy::Forbidden)
+    }
+
+    #[test]
+    fn test_stem_pool_insertion() {
+        let mut pool = stem_pool(2);
+        let funding = TransactionOutpoint::new(TransactionId::from_u64_word(1), 0);
+        let parent = transaction(&[funding], 2);
+        add(&mut pool, &parent).unwrap();
+        assert_eq!(add(&mut pool, &parent), Err(RuleError::RejectDuplicateStem(parent.id())));
+        let double_spend = transaction(&[funding], 1);
+        assert_eq!(add(&mut pool, &double_spend), Err(RuleError::RejectDoubleSpendInStemPool(funding, parent.id())));
+
+        let child = transaction(&[TransactionOutpoint::new(parent.id(), 0)], 1);
+        add(&mut pool, &child).unwrap();
+        assert_eq!(pool.get_output(&TransactionOutpoint::new(parent.id(), 1)).map(|output| output.value), Some(2));
+        let other = transaction(&[TransactionOutpoint::new(TransactionId::from_u64_word(2), 0)], 1);
+        assert_eq!(add(&mut pool, &other), Err(RuleError::RejectStemPoolIsFull(2)));
+    }
+
+    #[test]
+    fn test_take_with_ancestors() {
+        let mut pool = stem_pool(10);
+        let grand_parent = transaction(&[TransactionOutpoint::new(TransactionId::from_u64_word(1), 0)], 2);
+        let parent = transaction(&[TransactionOutpoint::new(grand_parent.id(), 0)], 1);
+        let uncle = transaction(&[TransactionOutpoint::new(grand_parent.id(), 1)], 1);
+        let child = transaction(&[TransactionOutpoint::new(parent.id(), 0), TransactionOutpoint::new(uncle.id(), 0)], 1);
+        let unrelated = transaction(&[TransactionOutpoint::new(TransactionId::from_u64_word(2), 0)], 1);
+        for transaction in [&child, &unrelated, &uncle, &parent, &grand_parent] {
+            add(&mut pool, transaction).unwrap();
+        }
+
+        let taken = pool.take_with_ancestors(&[child.id()]).into_iter().map(|stem| stem.tx.id()).collect::<Vec<_>>();
+        assert_eq!(taken.len(), 4);
+        assert_eq!(taken[0], grand_parent.id());
+        assert_eq!(taken[3], child.id());
+        assert!(taken.contains(&parent.id()) && taken.contains(&uncle.id()));
+        assert_eq!(pool.len(), 1);
+        assert!(pool.take_with_ancestors(&[child.id()]).is_empty());
+    }
+
+    #[test]
+    fn test_remove_conflicts() {
+        let mut pool = stem_pool(10);
+        let funding = TransactionOutpoint::new(TransactionId::from_u64_word(1), 0);
+        let parent = transaction(&[funding], 1);
+        let child = transaction(&[TransactionOutpoint::new(parent.id(), 0)], 1);
+        let unrelated = transaction(&[TransactionOutpoint::new(TransactionId::from_u64_word(2), 0)], 1);
+        for transaction in [&parent, &child, &unrelated] {
+            add(&mut pool, transaction).unwrap();
+        }
+
+        // The stem copy of a transaction entering the mempool is dropped, but its redeemers remain valid
+        assert_eq!(pool.remove_conflicts(&parent.tx), vec![parent.id()]);
+        assert!(pool.has(&child.id()));
+        add(&mut pool, &parent).unwrap();
+
+        // A double spend invalidates the conflicting stem transaction along with its descendants
+        let double_spend = transaction(&[funding], 2);
+        let mut removed = pool.remove_conflicts(&double_spend.tx);
+        removed.sort();
+        let mut expected = vec![parent.id(), child.id()];
+        expected.sort();
+        assert_eq!(removed, expected);
+        assert_eq!(pool.len(), 1);
+        assert!(pool.has(&unrelated.id()));
+    }
+}
@@ -20,12 +20,12 @@ use kaspa_consensus_core::{
 };
 use kaspa_core::{debug, time::unix_now, trace};
 use std::{
-    collections::{hash_map::Keys, hash_set::Iter},
+    collections::{hash_map::Keys, hash_set::Iter, HashMap, VecDeque},
     iter::once,
     sync::Arc,
 };
 
-use super::frontier::Frontier;
+use super::frontier::{feerate_key::FeerateTransactionKey, Frontier};
 
 /// Pool of transactions to be included in a block template
 ///
@@ -77,6 +77,17 @@ pub(crate) struct TransactionsPool {
 
     /// Store of UTXOs
     utxo_set: MempoolUtxoSet,
+
+    /// Operator defined fee deltas, by transaction id. A delta can be registered before the
+    /// transaction enters the pool, in which case it gets applied on insertion.
+    fee_deltas: HashMap<TransactionId, FeeDelta>,
+}
+
+/// Fee delta of a transaction along with the virtual DAA score at which it was last updated
+#[derive(Clone, Copy)]
+struct FeeDelta {
+    amount: i64,
+    updated_at_daa_score: u64,
 }
 
 impl TransactionsPool {
@@ -92,6 +103,7 @@ impl TransactionsPool {
             last_expire_scan_time: unix_now(),
             utxo_set: MempoolUtxoSet::new(),
             estimated_size: 0,
+            fee_deltas: HashMap::new(),
         }
     }
 
@@ -110,8 +122,9 @@ impl TransactionsPool {
     }
 
     /// Add a mempool transaction to the pool
-    pub(crate) fn add_mempool_transaction(&mut self, transaction: MempoolTransaction, transaction_size: usize) -> RuleResult<()> {
+    pub(crate) fn add_mempool_transaction(&mut self, mut transaction: MempoolTransaction, transaction_size: usize) -> RuleResult<()> {
         let id = transaction.id();
+        transaction.fee_delta = self.get_fee_delta(&id);
 
         assert!(!self.all_transactions.contains_key(&id), "transaction {id} to be added already exists in the transactions pool");
         assert!(transaction.mtx.is_fully_populated(), "transaction {id} to be added in the transactions pool is not fully populated");
@@ -165,6 +178,8 @@ impl TransactionsPool {
         let removed_tx = self.all_transactions.remove(transaction_id).ok_or(RuleError::RejectMissingTransaction(*transaction_id))?;
 
         self.ready_transactions.remove(&(&removed_tx).into());
+        // Whatever the removal reason, the delta is not carried over to a later insertion of the transaction
+        self.fee_deltas.remove(transaction_id);
 
         // TODO: consider using `self.parent_transactions.get(transaction_id)`
         // The tradeoff to consider is whether it might be possible that a parent tx exists in the pool
@@ -195,6 +210,43 @@ impl TransactionsPool {
         }
     }
 
+    /// Adds `fee_delta` to the fee delta of a transaction, whether in the pool or not, and returns the resulting delta.
+    ///
+    /// If the transaction is ready, its key in the frontier is updated so that block templates honor the new delta.
+    /// The delta of a transaction missing from the pool expires if the transaction does not enter the pool within
+    /// the transaction expire interval.
+    pub(crate) fn add_fee_delta(&mut self, transaction_id: TransactionId, fee_delta: i64, virtual_daa_score: u64) -> i64 {
+        let total = self.get_fee_delta(&transaction_id).saturating_add(fee_delta);
+        if total == 0 {
+            self.fee_deltas.remove(&transaction_id);
+        } else {
+            self.fee_deltas.insert(transaction_id, FeeDelta { amount: total, updated_at_daa_score: virtual_daa_score });
+        }
+
+        if let Some(transaction) = self.all_transactions.get_mut(&transaction_id) {
+            // The frontier key depends on the fee so it must be replaced along with the delta
+            let key = FeerateTransactionKey::from(&*transaction);
+            transaction.fee_delta = total;
+            self.ready_transactions.update(&key, (&*transaction).into());
+        }
+        total
+    }
+
+    /// Returns the fee delta of a transaction, zero if none is set
+    pub(crate) fn get_fee_delta(&self, transaction_id: &TransactionId) -> i64 {
+        self.fee_deltas.get(transaction_id).map(|delta| delta.amount).unwrap_or_default()
+    }
+
+    /// Returns all the registered fee deltas
+    pub(crate) fn fee_deltas(&self) -> impl Iterator<Item = (&TransactionId, i64)> {
+        self.fee_deltas.iter().map(|(id, delta)| (id, delta.amount))
+    }
+
+    /// Forgets the fee delta of a transaction
+    pub(crate) fn clear_fee_delta(&mut self, transaction_id: &TransactionId) {
+        self.fee_deltas.remove(transaction_id);
+    }
+
     pub(crate) fn ready_transaction_count(&self) -> usize {
         self.ready_transactions.len()
     }
@@ -210,7 +262,17 @@ impl TransactionsPool {
 
     /// Builds a feerate estimator based on internal state of the ready transactions frontier
     pub(crate) fn build_feerate_estimator(&self, args: FeerateEstimatorArgs) -> FeerateEstimator {
-        self.ready_transactions.build_feerate_estimator(args)
+        // Fee deltas only alter the selection of transactions, so the estimations must rely on actual fees.
+        // Deltas being rare, the frontier is only rebuilt when a prioritised transaction is in the pool.
+        if !self.fee_deltas.keys().any(|id| self.all_transactions.contains_key(id)) {
+            return self.ready_transactions.build_feerate_estimator(args);
+        }
+        self.ready_transactions
+            .map_keys(|key| match self.all_transactions.get(&key.tx.id()) {
+                Some(transaction) if transaction.fee_delta != 0 => FeerateTransactionKey::with_actual_fee(transaction),
+                _ => key.clone(),
+            })
+            .build_feerate_estimator(args)
     }
 
     /// Returns the exceeding low-priority transactions having the lowest fee rates in order
@@ -236,12 +298,7 @@ impl TransactionsPool {
         let feerate_threshold = transaction.calculated_feerate().unwrap();
         let mut txs_to_remove = Vec::with_capacity(1); // Normally we expect a single removal
         let mut selection_overall_size = 0;
-        for tx in self
-            .ready_transactions
-            .ascending_iter()
-            .map(|tx| self.all_transactions.get(&tx.id()).unwrap())
-            .filter(|mtx| mtx.priority == Priority::Low)
-        {
+        for tx in self.eviction_candidates() {
             // TODO (optimization): inline the `has_parent_in_set` check within the redeemer traversal and exit early if possible
             let redeemers = self.get_redeemer_ids_in_pool(&tx.id()).into_iter().chain(once(tx.id())).collect::<TransactionIdSet>();
             if transaction.has_parent_in_set(&redeemers) {
@@ -278,6 +335,40 @@ impl TransactionsPool {
         Err(RuleError::RejectMempoolIsFull)
     }
 
+    /// Returns the transactions which may be evicted when the mempool is full, in eviction order:
+    /// ready low-priority transactions by ascending feerate
+    pub(crate) fn eviction_candidates(&self) -> impl Iterator<Item = &MempoolTransaction> {
+        self.ready_transactions
+            .ascending_iter()
+            .map(|tx| self.all_transactions.get(&tx.id()).unwrap())
+            .filter(|mtx| mtx.priority == Priority::Low)
+    }
+
+    /// Returns the ids of the parents of `transaction_id` existing in the pool
+    pub(crate) fn get_parent_ids_in_pool(&self, transaction_id: &TransactionId) -> Option<&TransactionIdSet> {
+        self.parent_transactions.get(transaction_id)
+    }
+
+    /// Returns the ids of all transactions `transaction_id` directly and indirectly depends on and existing in the pool.
+    ///
+    /// The transactions are traversed in BFS mode. The returned order is not guaranteed to be topological.
+    pub(crate) fn get_ancestor_ids_in_pool(&self, transaction_id: &TransactionId) -> Vec<TransactionId> {
+        let mut visited = TransactionIdSet::new();
+        let mut ancestors = vec![];
+        let mut queue = VecDeque::from([*transaction_id]);
+        while let Some(id) = queue.pop_front() {
+            if let Some(parents) = self.parent_transactions.get(&id) {
+                for parent_id in parents.iter() {
+                    if visited.insert(*parent_id) {
+                        ancestors.push(*parent_id);
+                        queue.push_back(*parent_id);
+                    }
+                }
+            }
+        }
+        ancestors
+    }
+
     pub(crate) fn get_estimated_size(&self) -> usize {
         self.estimated_size
     }
@@ -324,6 +415,12 @@ impl TransactionsPool {
         self.last_expire_scan_daa_score = virtual_daa_score;
         self.last_expire_scan_time = now;
 
+        // Drop the deltas of transactions which did not enter the pool in time
+        let expire_interval = self.config.transaction_expire_interval_daa_score;
+        self.fee_deltas.retain(|id, delta| {
+            self.all_transactions.contains_key(id) || virtual_daa_score <= delta.updated_at_daa_score + expire_interval
+        });
+
         // Never expire high priority transactions
         // Remove all transactions whose added_at_daa_score is older then transaction_expire_interval_daa_score
         self.all_transactions
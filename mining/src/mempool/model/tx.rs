@@ -1,5 +1,10 @@
 use crate::mempool::tx::{Priority, RbfPolicy};
-use kaspa_consensus_core::tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint};
+use kaspa_consensus_core::{
+    mass::ContextualMasses,
+    tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint},
+};
+use kaspa_consensus_notify::notification::MempoolTransactionRemovalReason;
+use kaspa_core::time::unix_now;
 use kaspa_mining_errors::mempool::RuleError;
 use std::{
     fmt::{Display, Formatter},
@@ -10,20 +15,54 @@ pub(crate) struct MempoolTransaction {
     pub(crate) mtx: MutableTransaction,
     pub(crate) priority: Priority,
     pub(crate) added_at_daa_score: u64,
+    /// Unix time in milliseconds at which the transaction was added
+    pub(crate) added_at_time: u64,
+    /// Virtual fee delta, in sompi, set by the operator to alter the priority of the transaction when
+    /// building block templates and when evaluating replacements. It never affects the actual fee.
+    pub(crate) fee_delta: i64,
 }
 
 impl MempoolTransaction {
     pub(crate) fn new(mtx: MutableTransaction, priority: Priority, added_at_daa_score: u64) -> Self {
         assert_eq!(mtx.tx.inputs.len(), mtx.entries.len());
-        Self { mtx, priority, added_at_daa_score }
+        Self { mtx, priority, added_at_daa_score, added_at_time: unix_now(), fee_delta: 0 }
     }
 
     pub(crate) fn id(&self) -> TransactionId {
         self.mtx.tx.id()
     }
 
+    /// Returns the feerate of the transaction, including its fee delta
     pub(crate) fn feerate(&self) -> f64 {
-        self.mtx.calculated_feerate().unwrap()
+        if self.fee_delta == 0 {
+            self.mtx.calculated_feerate().unwrap()
+        } else {
+            self.effective_fee() as f64 / self.mass() as f64
+        }
+    }
+
+    /// Returns the actual fee paid by the transaction
+    pub(crate) fn fee(&self) -> u64 {
+        self.mtx.calculated_fee.expect("fee is expected to be populated")
+    }
+
+    /// Returns the calculated fee of the transaction with its fee delta applied.
+    ///
+    /// The result is clamped to at least 1 sompi so that a negative delta never zeroes the
+    /// weight of the transaction key in the ready transactions frontier, which is sampled
+    /// over a strictly positive weight range.
+    pub(crate) fn effective_fee(&self) -> u64 {
+        self.fee().saturating_add_signed(self.fee_delta).max(1)
+    }
+
+    /// Returns the mass used for feerate computations, being the maximum of the contextual and non-contextual masses
+    pub(crate) fn mass(&self) -> u64 {
+        // NOTE: The code below is a mempool simplification reducing the various block mass units to a
+        //       single one-dimension value (making it easier to select transactions for block templates).
+        // Future mempool improvements are expected to refine this behavior and use the multi-dimension values
+        // in order to optimize and increase block space usage.
+        ContextualMasses::new(self.mtx.tx.mass())
+            .max(self.mtx.calculated_non_contextual_masses.expect("masses are expected to be calculated"))
     }
 }
 
@@ -105,6 +144,22 @@ impl TxRemovalReason {
     }
 }
 
+impl From<&TxRemovalReason> for MempoolTransactionRemovalReason {
+    fn from(item: &TxRemovalReason) -> Self {
+        match item {
+            TxRemovalReason::Accepted => MempoolTransactionRemovalReason::Accepted,
+            TxRemovalReason::ReplacedByFee => MempoolTransactionRemovalReason::ReplacedByFee,
+            TxRemovalReason::DoubleSpend => MempoolTransactionRemovalReason::DoubleSpend,
+            TxRemovalReason::MakingRoom => MempoolTransactionRemovalReason::Evicted,
+            TxRemovalReason::Expired => MempoolTransactionRemovalReason::Expired,
+            TxRemovalReason::InvalidInBlockTemplate | TxRemovalReason::RevalidationWithMissingOutpoints => {
+                MempoolTransactionRemovalReason::Invalid
+            }
+            TxRemovalReason::Muted | TxRemovalReason::Unorphaned => MempoolTransactionRemovalReason::Unspecified,
+        }
+    }
+}
+
 impl Display for TxRemovalReason {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.as_str())
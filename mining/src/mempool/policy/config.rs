@@ -0,0 +1,135 @@
+//! TOML configuration of the built-in mempool policies.
+//!
+//! ```toml
+//! # Maximum transaction payload size, in bytes
+//! max_payload_size = 1024
+//!
+//! # Script classes allowed in transaction outputs (pubkey, pubkeyecdsa, scripthash, nonstandard)
+//! allowed_script_classes = ["pubkey", "pubkeyecdsa", "scripthash"]
+//!
+//! # File listing blocked addresses and outpoints (`<transaction id>:<index>`), one per line.
+//! # Empty lines and lines starting with `#` are ignored. Relative paths are resolved
+//! # against the directory of the policy file.
+//! blocklist = "blocklist.txt"
+//!
+//! # Minimum feerate, in sompi/gram, of transactions carrying a payload of at least `payload_size` bytes
+//! [[minimum_feerate]]
+//! payload_size = 256
+//! feerate = 2.0
+//! ```
+
+use super::{AllowedScriptClasses, Blocklist, MaxPayloadSize, MempoolPolicies, MempoolPolicy, MinimumFeerateByPayloadSize};
+use kaspa_addresses::Address;
+use kaspa_consensus_core::tx::{TransactionId, TransactionOutpoint};
+use kaspa_txscript::{pay_to_address_script, script_class::ScriptClass};
+use serde::Deserialize;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum PolicyError {
+    #[error("no mempool policy file is configured")]
+    NoPolicyFile,
+
+    #[error("unable to read {0}: {1}")]
+    Io(PathBuf, std::io::Error),
+
+    #[error("invalid mempool policy file {0}: {1}")]
+    Toml(PathBuf, toml::de::Error),
+
+    #[error("unknown script class '{0}'")]
+    ScriptClass(String),
+
+    #[error("invalid minimum feerate {0} for payload size {1}")]
+    Feerate(f64, usize),
+
+    #[error("{0}:{1}: invalid blocklist entry '{2}'")]
+    BlocklistEntry(PathBuf, usize, String),
+}
+
+/// Minimum feerate required from a given payload size on.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct FeerateTier {
+    pub payload_size: usize,
+    /// Feerate in sompi/gram
+    pub feerate: f64,
+}
+
+/// Mempool policy file settings, every policy being optional.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyConfig {
+    pub max_payload_size: Option<usize>,
+    pub allowed_script_classes: Option<Vec<String>>,
+    pub blocklist: Option<PathBuf>,
+    #[serde(default)]
+    pub minimum_feerate: Vec<FeerateTier>,
+}
+
+impl PolicyConfig {
+    /// Reads the policy file at `path` and builds the policies it configures
+    pub fn load(path: &Path) -> Result<MempoolPolicies, PolicyError> {
+        let content = std::fs::read_to_string(path).map_err(|err| PolicyError::Io(path.to_path_buf(), err))?;
+        let config = toml::from_str::<PolicyConfig>(&content).map_err(|err| PolicyError::Toml(path.to_path_buf(), err))?;
+        config.build(path.parent().unwrap_or(Path::new(".")))
+    }
+
+    /// Builds the configured policies, resolving relative file paths against `base_dir`
+    pub fn build(&self, base_dir: &Path) -> Result<MempoolPolicies, PolicyError> {
+        let mut policies: Vec<Arc<dyn MempoolPolicy>> = vec![];
+
+        if let Some(max_payload_size) = self.max_payload_size {
+            policies.push(Arc::new(MaxPayloadSize(max_payload_size)));
+        }
+
+        if let Some(classes) = self.allowed_script_classes.as_ref() {
+            let classes = classes
+                .iter()
+                .map(|class| ScriptClass::from_str(class).map_err(|_| PolicyError::ScriptClass(class.clone())))
+                .collect::<Result<Vec<_>, _>>()?;
+            policies.push(Arc::new(AllowedScriptClasses(classes)));
+        }
+
+        if let Some(blocklist) = self.blocklist.as_ref() {
+            policies.push(Arc::new(load_blocklist(&base_dir.join(blocklist))?));
+        }
+
+        if !self.minimum_feerate.is_empty() {
+            if let Some(tier) = self.minimum_feerate.iter().find(|tier| !tier.feerate.is_finite() || tier.feerate < 0.0) {
+                return Err(PolicyError::Feerate(tier.feerate, tier.payload_size));
+            }
+            policies.push(Arc::new(MinimumFeerateByPayloadSize::new(self.minimum_feerate.clone())));
+        }
+
+        Ok(MempoolPolicies::new(policies))
+    }
+}
+
+fn load_blocklist(path: &Path) -> Result<Blocklist, PolicyError> {
+    let content = std::fs::read_to_string(path).map_err(|err| PolicyError::Io(path.to_path_buf(), err))?;
+    let mut script_public_keys = HashSet::new();
+    let mut outpoints = HashSet::new();
+    for (n, line) in content.lines().enumerate() {
+        let entry = line.trim();
+        if entry.is_empty() || entry.starts_with('#') {
+            continue;
+        }
+        let invalid = || PolicyError::BlocklistEntry(path.to_path_buf(), n + 1, entry.to_string());
+        if let Ok(address) = Address::try_from(entry) {
+            script_public_keys.insert(pay_to_address_script(&address));
+        } else if let Some((transaction_id, index)) = entry.rsplit_once(':') {
+            let transaction_id = TransactionId::from_str(transaction_id).map_err(|_| invalid())?;
+            let index = index.parse().map_err(|_| invalid())?;
+            outpoints.insert(TransactionOutpoint::new(transaction_id, index));
+        } else {
+            return Err(invalid());
+        }
+    }
+    Ok(Blocklist::new(script_public_keys, outpoints))
+}
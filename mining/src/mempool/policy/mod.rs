@@ -0,0 +1,266 @@
+//! Operator configurable mempool policies.
+//!
+//! Policies complement the hardcoded standardness rules of [`check_transaction_standard`](super::check_transaction_standard)
+//! with rules chosen by the node operator. They are evaluated on every transaction entering the mempool once its UTXO
+//! entries and fee are populated, regardless of the `accept_non_standard` setting.
+//!
+//! The built-in policies are configured through a TOML file (see [`PolicyConfig`]) which can be reloaded while the node
+//! is running. Transactions already in the mempool are not re-evaluated on reload.
+
+use crate::mempool::errors::{RuleError, RuleResult};
+use kaspa_consensus_core::tx::{MutableTransaction, ScriptPublicKey, TransactionOutpoint};
+use kaspa_txscript::script_class::ScriptClass;
+use std::{collections::HashSet, sync::Arc};
+
+pub mod config;
+
+pub use config::{FeerateTier, PolicyConfig, PolicyError};
+
+/// A rule evaluated on transactions entering the mempool.
+pub trait MempoolPolicy: Send + Sync {
+    /// Short name of the policy, reported in rejection reasons
+    fn name(&self) -> &'static str;
+
+    /// Human readable summary of the policy settings
+    fn describe(&self) -> String;
+
+    /// Checks a transaction with populated UTXO entries and fee, returning the rejection reason if any
+    fn check(&self, transaction: &MutableTransaction) -> Result<(), String>;
+}
+
+/// The set of policies applied by the mempool.
+#[derive(Clone, Default)]
+pub struct MempoolPolicies {
+    policies: Vec<Arc<dyn MempoolPolicy>>,
+}
+
+impl MempoolPolicies {
+    pub fn new(policies: Vec<Arc<dyn MempoolPolicy>>) -> Self {
+        Self { policies }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        self.policies.iter().map(|policy| format!("{}: {}", policy.name(), policy.describe())).collect()
+    }
+
+    pub(crate) fn check(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+        for policy in self.policies.iter() {
+            policy
+                .check(transaction)
+                .map_err(|reason| RuleError::RejectPolicy(transaction.id(), policy.name().to_string(), reason))?;
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions carrying a payload larger than the limit, in bytes.
+pub struct MaxPayloadSize(pub usize);
+
+impl MempoolPolicy for MaxPayloadSize {
+    fn name(&self) -> &'static str {
+        "max-payload-size"
+    }
+
+    fn describe(&self) -> String {
+        format!("{} bytes", self.0)
+    }
+
+    fn check(&self, transaction: &MutableTransaction) -> Result<(), String> {
+        let payload_size = transaction.tx.payload.len();
+        match payload_size > self.0 {
+            true => Err(format!("payload of {payload_size} bytes exceeds {} bytes", self.0)),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Only accepts transactions whose outputs are locked with one of the listed script classes.
+pub struct AllowedScriptClasses(pub Vec<ScriptClass>);
+
+impl MempoolPolicy for AllowedScriptClasses {
+    fn name(&self) -> &'static str {
+        "allowed-script-classes"
+    }
+
+    fn describe(&self) -> String {
+        self.0.iter().map(|class| class.to_string()).collect::<Vec<_>>().join(", ")
+    }
+
+    fn check(&self, transaction: &MutableTransaction) -> Result<(), String> {
+        for (i, output) in transaction.tx.outputs.iter().enumerate() {
+            let class = ScriptClass::from_script(&output.script_public_key);
+            if !self.0.contains(&class) {
+                return Err(format!("output {i} has disallowed script class {class}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions spending blocked outpoints or spending from or paying to blocked addresses.
+#[derive(Default)]
+pub struct Blocklist {
+    script_public_keys: HashSet<ScriptPublicKey>,
+    outpoints: HashSet<TransactionOutpoint>,
+}
+
+impl Blocklist {
+    pub fn new(script_public_keys: HashSet<ScriptPublicKey>, outpoints: HashSet<TransactionOutpoint>) -> Self {
+        Self { script_public_keys, outpoints }
+    }
+}
+
+impl MempoolPolicy for Blocklist {
+    fn name(&self) -> &'static str {
+        "blocklist"
+    }
+
+    fn describe(&self) -> String {
+        format!("{} addresses, {} outpoints", self.script_public_keys.len(), self.outpoints.len())
+    }
+
+    fn check(&self, transaction: &MutableTransaction) -> Result<(), String> {
+        for (i, (input, entry)) in transaction.tx.inputs.iter().zip(transaction.entries.iter()).enumerate() {
+            if self.outpoints.contains(&input.previous_outpoint) {
+                return Err(format!("input {i} spends blocked outpoint {}", input.previous_outpoint));
+            }
+            if entry.as_ref().is_some_and(|entry| self.script_public_keys.contains(&entry.script_public_key)) {
+                return Err(format!("input {i} spends from a blocked address"));
+            }
+        }
+        for (i, output) in transaction.tx.outputs.iter().enumerate() {
+            if self.script_public_keys.contains(&output.script_public_key) {
+                return Err(format!("output {i} pays to a blocked address"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Requires a minimum feerate, in sompi/gram, depending on the transaction payload size.
+pub struct MinimumFeerateByPayloadSize {
+    /// Tiers sorted by ascending payload size
+    tiers: Vec<FeerateTier>,
+}
+
+impl MinimumFeerateByPayloadSize {
+    pub fn new(mut tiers: Vec<FeerateTier>) -> Self {
+        tiers.sort_by_key(|tier| tier.payload_size);
+        Self { tiers }
+    }
+}
+
+impl MempoolPolicy for MinimumFeerateByPayloadSize {
+    fn name(&self) -> &'static str {
+        "minimum-feerate"
+    }
+
+    fn describe(&self) -> String {
+        self.tiers
+            .iter()
+            .map(|tier| format!("{} sompi/gram from {} bytes", tier.feerate, tier.payload_size))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn check(&self, transaction: &MutableTransaction) -> Result<(), String> {
+        let payload_size = transaction.tx.payload.len();
+        let Some(tier) = self.tiers.iter().rev().find(|tier| payload_size >= tier.payload_size) else {
+            return Ok(());
+        };
+        let feerate = transaction.calculated_feerate().unwrap_or_default();
+        match feerate < tier.feerate {
+            true => Err(format!(
+                "feerate {feerate:.2} is below the {} sompi/gram required for a payload of {payload_size} bytes",
+                tier.feerate
+            )),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::{Address, Prefix, Version};
+    use kaspa_consensus_core::{
+        mass::NonContextualMasses,
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{Transaction, TransactionId, TransactionInput, TransactionOutput, UtxoEntry},
+    };
+    use kaspa_txscript::pay_to_address_script;
+    use std::path::Path;
+
+    fn script_public_key(seed: u8) -> ScriptPublicKey {
+        pay_to_address_script(&Address::new(Prefix::Mainnet, Version::PubKey, &[seed; 32]))
+    }
+
+    fn transaction(payload_size: usize, fee: u64) -> MutableTransaction {
+        let outpoint = TransactionOutpoint::new(TransactionId::from_bytes([1; 32]), 0);
+        let input = TransactionInput::new(outpoint, vec![], 0, 1);
+        let output = TransactionOutput::new(1_000, script_public_key(2));
+        let tx = Transaction::new(0, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, vec![0; payload_size]);
+        let mut transaction =
+            MutableTransaction::with_entries(Arc::new(tx), vec![UtxoEntry::new(1_000 + fee, script_public_key(1), 0, false)]);
+        transaction.calculated_fee = Some(fee);
+        transaction.calculated_non_contextual_masses = Some(NonContextualMasses::new(1_000, 1_000));
+        transaction
+    }
+
+    #[test]
+    fn test_policy_config() {
+        let config: PolicyConfig = toml::from_str(
+            r#"
+            max_payload_size = 512
+            allowed_script_classes = ["scripthash"]
+
+            [[minimum_feerate]]
+            payload_size = 100
+            feerate = 2.0
+            "#,
+        )
+        .unwrap();
+        let policies = config.build(Path::new(".")).unwrap();
+        assert_eq!(policies.describe().len(), 3);
+
+        // outputs are P2PK while only P2SH is allowed
+        assert!(
+            matches!(policies.check(&transaction(0, 1_000)), Err(RuleError::RejectPolicy(_, name, _)) if name == "allowed-script-classes")
+        );
+
+        let config: PolicyConfig = toml::from_str("allowed_script_classes = [\"unknown\"]").unwrap();
+        assert!(matches!(config.build(Path::new(".")), Err(PolicyError::ScriptClass(_))));
+        assert!(toml::from_str::<PolicyConfig>("max_payload = 1").is_err());
+    }
+
+    #[test]
+    fn test_builtin_policies() {
+        let policy = MaxPayloadSize(100);
+        assert!(policy.check(&transaction(100, 0)).is_ok());
+        assert!(policy.check(&transaction(101, 0)).is_err());
+
+        let policy = MinimumFeerateByPayloadSize::new(vec![
+            FeerateTier { payload_size: 1_000, feerate: 10.0 },
+            FeerateTier { payload_size: 100, feerate: 2.0 },
+        ]);
+        assert!(policy.check(&transaction(0, 0)).is_ok());
+        assert!(policy.check(&transaction(100, 1_999)).is_err());
+        assert!(policy.check(&transaction(100, 2_000)).is_ok());
+        assert!(policy.check(&transaction(1_000, 2_000)).is_err());
+        assert!(policy.check(&transaction(1_000, 10_000)).is_ok());
+
+        let outpoint = TransactionOutpoint::new(TransactionId::from_bytes([1; 32]), 0);
+        assert!(Blocklist::new(HashSet::new(), HashSet::from([outpoint])).check(&transaction(0, 0)).is_err());
+        assert!(Blocklist::new(HashSet::from([script_public_key(1)]), HashSet::new()).check(&transaction(0, 0)).is_err());
+        assert!(Blocklist::new(HashSet::from([script_public_key(2)]), HashSet::new()).check(&transaction(0, 0)).is_err());
+        assert!(Blocklist::new(HashSet::from([script_public_key(3)]), HashSet::new()).check(&transaction(0, 0)).is_ok());
+
+        let policies = MempoolPolicies::default();
+        assert!(policies.is_empty());
+        assert!(policies.check(&transaction(10_000, 0)).is_ok());
+    }
+}
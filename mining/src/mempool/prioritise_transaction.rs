@@ -0,0 +1,39 @@
+use crate::{
+    mempool::{model::pool::Pool, Mempool},
+    model::prioritised_tx::PrioritisedTransaction,
+};
+use kaspa_consensus_core::{api::ConsensusApi, tx::TransactionId};
+use kaspa_core::info;
+
+impl Mempool {
+    /// Adds `fee_delta` to the fee delta of a transaction and returns the resulting delta.
+    ///
+    /// The transaction does not need to be in the mempool, the delta gets applied if it enters the
+    /// mempool before the transaction expire interval elapses. The delta is dropped once the transaction
+    /// is removed from the mempool, whether accepted in a block, evicted, expired or double spent.
+    pub(crate) fn prioritise_transaction(
+        &mut self,
+        consensus: &dyn ConsensusApi,
+        transaction_id: TransactionId,
+        fee_delta: i64,
+    ) -> i64 {
+        let total = self.transaction_pool.add_fee_delta(transaction_id, fee_delta, consensus.get_virtual_daa_score());
+        info!("Fee delta of transaction {} set to {} sompi", transaction_id, total);
+        total
+    }
+
+    /// Returns all the registered fee deltas, sorted by descending delta
+    pub(crate) fn get_prioritised_transactions(&self) -> Vec<PrioritisedTransaction> {
+        let mut prioritised = self
+            .transaction_pool
+            .fee_deltas()
+            .map(|(id, fee_delta)| PrioritisedTransaction {
+                transaction_id: *id,
+                fee_delta,
+                in_mempool: self.transaction_pool.has(id),
+            })
+            .collect::<Vec<_>>();
+        prioritised.sort_by(|a, b| b.fee_delta.cmp(&a.fee_delta).then(a.transaction_id.cmp(&b.transaction_id)));
+        prioritised
+    }
+}
@@ -16,6 +16,7 @@ impl Mempool {
         extra_info: &str,
     ) -> RuleResult<()> {
         if self.orphan_pool.has(transaction_id) {
+            self.transaction_pool.clear_fee_delta(transaction_id);
             return self.orphan_pool.remove_orphan(transaction_id, true, reason, extra_info).map(|_| ());
         }
 
@@ -33,6 +34,7 @@ impl Mempool {
         for tx_id in removed_transactions.iter() {
             // Remove the tx from the transaction pool and the UTXO set (handled within the pool)
             let tx = self.transaction_pool.remove_transaction(tx_id)?;
+            self.notify_transaction_removed(tx.mtx.tx.clone(), &reason);
             // Update/remove descendent orphan txs (depending on `remove_redeemers`)
             let txs = self.orphan_pool.update_orphans_after_transaction_removed(&tx, remove_redeemers)?;
             removed_orphans.extend(txs.into_iter().map(|x| x.id()));
@@ -4,7 +4,10 @@ use crate::mempool::{
     tx::RbfPolicy,
     Mempool,
 };
-use kaspa_consensus_core::tx::{MutableTransaction, Transaction};
+use kaspa_consensus_core::{
+    mass::ContextualMasses,
+    tx::{MutableTransaction, Transaction},
+};
 use std::sync::Arc;
 
 impl Mempool {
@@ -17,6 +20,22 @@ impl Mempool {
         &self,
         transaction: &MutableTransaction,
         rbf_policy: RbfPolicy,
+    ) -> RuleResult<Option<f64>> {
+        let threshold = self.get_replace_by_fee_feerate_threshold(transaction, rbf_policy)?;
+        // The threshold feeds the RBF fee/mass check of the mempool transaction validation (passed through
+        // `TransactionValidationArgs::feerate_threshold`), which compares it against the actual feerate of the
+        // transaction. A positive fee delta makes this check too strict so it is skipped, the effective feerates
+        // being compared anyway when executing the replacement.
+        if self.transaction_pool.get_fee_delta(&transaction.id()) > 0 {
+            return Ok(None);
+        }
+        Ok(threshold)
+    }
+
+    fn get_replace_by_fee_feerate_threshold(
+        &self,
+        transaction: &MutableTransaction,
+        rbf_policy: RbfPolicy,
     ) -> RuleResult<Option<f64>> {
         match rbf_policy {
             RbfPolicy::Forbidden => {
@@ -119,7 +138,7 @@ impl Mempool {
 
     fn get_double_spend_feerate(&self, double_spend: &DoubleSpend) -> RuleResult<f64> {
         let owner = self.transaction_pool.get_double_spend_owner(double_spend)?;
-        match owner.mtx.calculated_feerate() {
+        match owner.mtx.calculated_feerate().map(|_| owner.feerate()) {
             Some(double_spend_feerate) => Ok(double_spend_feerate),
             // Getting here is unexpected since a mempool owned tx should be populated with fee
             // and mass at this stage but nonetheless we fail gracefully
@@ -134,7 +153,7 @@ impl Mempool {
     ) -> RuleResult<&'a MempoolTransaction> {
         let owner = self.transaction_pool.get_double_spend_owner(double_spend)?;
         if let (Some(transaction_feerate), Some(double_spend_feerate)) =
-            (transaction.calculated_feerate(), owner.mtx.calculated_feerate())
+            (self.get_effective_feerate(transaction), owner.mtx.calculated_feerate().map(|_| owner.feerate()))
         {
             if transaction_feerate > double_spend_feerate {
                 return Ok(owner);
@@ -146,4 +165,14 @@ impl Mempool {
         // fee and mass at this stage but nonetheless we fail gracefully
         Err(double_spend.into())
     }
+
+    /// Returns the feerate of an incoming transaction, including its fee delta if any
+    fn get_effective_feerate(&self, transaction: &MutableTransaction) -> Option<f64> {
+        let fee_delta = self.transaction_pool.get_fee_delta(&transaction.id());
+        if fee_delta == 0 {
+            return transaction.calculated_feerate();
+        }
+        let mass = ContextualMasses::new(transaction.tx.mass()).max(transaction.calculated_non_contextual_masses?);
+        transaction.calculated_fee.map(|fee| fee.saturating_add_signed(fee_delta) as f64 / mass as f64)
+    }
 }
@@ -0,0 +1,70 @@
+use crate::mempool::{
+    errors::{RuleError, RuleResult},
+    model::{pool::Pool, stem_pool::StemTransaction, tx::TransactionPreValidation},
+    tx::{Priority, RbfPolicy},
+    Mempool,
+};
+use kaspa_consensus_core::{
+    api::ConsensusApi,
+    constants::UNACCEPTED_DAA_SCORE,
+    tx::{MutableTransaction, Transaction, TransactionId, UtxoEntry},
+};
+use std::sync::Arc;
+
+impl Mempool {
+    /// Pre-validates a transaction in stem phase the same way as a transaction entering the transaction pool,
+    /// except that its inputs may also spend outputs of other stem transactions
+    pub(crate) fn pre_validate_and_populate_stem_transaction(
+        &self,
+        consensus: &dyn ConsensusApi,
+        transaction: MutableTransaction,
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<TransactionPreValidation> {
+        let transaction_id = transaction.id();
+        if self.stem_pool.has(&transaction_id) {
+            return Err(RuleError::RejectDuplicateStem(transaction_id));
+        }
+        let mut pre_validation = self.pre_validate_and_populate_transaction(consensus, transaction, rbf_policy)?;
+        self.populate_stem_entries(&mut pre_validation.transaction);
+        Ok(pre_validation)
+    }
+
+    fn populate_stem_entries(&self, transaction: &mut MutableTransaction) {
+        for (i, input) in transaction.tx.inputs.iter().enumerate() {
+            if let Some(output) = self.stem_pool.get_output(&input.previous_outpoint) {
+                transaction.entries[i] =
+                    Some(UtxoEntry::new(output.value, output.script_public_key.clone(), UNACCEPTED_DAA_SCORE, false));
+            }
+        }
+    }
+
+    /// Adds a validated transaction to the stem pool. Stem transactions are never orphans.
+    pub(crate) fn post_validate_and_stem_transaction(
+        &mut self,
+        validation_result: RuleResult<()>,
+        transaction: MutableTransaction,
+        priority: Priority,
+        rbf_policy: RbfPolicy,
+    ) -> RuleResult<Arc<Transaction>> {
+        let transaction_id = transaction.id();
+        if self.transaction_pool.has(&transaction_id) {
+            return Err(RuleError::RejectDuplicate(transaction_id));
+        }
+        match validation_result {
+            Ok(_) => {}
+            Err(RuleError::RejectMissingOutpoint) => return Err(RuleError::RejectDisallowedOrphan(transaction_id)),
+            Err(err) => return Err(err),
+        }
+        self.validate_transaction_unacceptance(&transaction)?;
+        self.validate_transaction_in_context(&transaction)?;
+        // The mempool might have changed since pre-validation
+        let _ = self.get_replace_by_fee_constraint(&transaction, rbf_policy)?;
+        self.stem_pool.try_add(&transaction, priority, rbf_policy)?;
+        Ok(transaction.tx)
+    }
+
+    /// Removes the given transactions from the stem pool along with their stem ancestors, parents first
+    pub(crate) fn take_stem_transactions(&mut self, transaction_ids: &[TransactionId]) -> Vec<StemTransaction> {
+        self.stem_pool.take_with_ancestors(transaction_ids)
+    }
+}
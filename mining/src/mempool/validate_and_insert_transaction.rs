@@ -120,17 +120,17 @@ impl Mempool {
         );
 
         // Add the transaction to the mempool as a MempoolTransaction and return a clone of the embedded Arc<Transaction>
-        let accepted_transaction = self
-            .transaction_pool
-            .add_transaction(transaction, consensus.get_virtual_daa_score(), priority, transaction_size)?
-            .mtx
-            .tx
-            .clone();
+        let accepted_transaction =
+            &self.transaction_pool.add_transaction(transaction, consensus.get_virtual_daa_score(), priority, transaction_size)?.mtx;
+        let (accepted_transaction, fee) = (accepted_transaction.tx.clone(), accepted_transaction.calculated_fee.unwrap_or_default());
+        // Stem transactions conflicting with the accepted one can no longer enter the transaction pool
+        self.stem_pool.remove_conflicts(&accepted_transaction);
+        self.notify_transaction_added(accepted_transaction.clone(), fee);
         Ok(TransactionPostValidation { removed: removed_transaction, accepted: Some(accepted_transaction) })
     }
 
     /// Validates that the transaction wasn't already accepted into the DAG
-    fn validate_transaction_unacceptance(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+    pub(super) fn validate_transaction_unacceptance(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         // Reject if the transaction is registered as an accepted transaction
         let transaction_id = transaction.id();
         match self.accepted_transactions.has(&transaction_id) {
@@ -151,10 +151,11 @@ impl Mempool {
         Ok(())
     }
 
-    fn validate_transaction_in_context(&self, transaction: &MutableTransaction) -> RuleResult<()> {
+    pub(super) fn validate_transaction_in_context(&self, transaction: &MutableTransaction) -> RuleResult<()> {
         if !self.config.accept_non_standard {
             self.check_transaction_standard_in_context(transaction)?;
         }
+        self.policies.check(transaction)?;
         Ok(())
     }
 
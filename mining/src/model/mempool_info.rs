@@ -0,0 +1,74 @@
+use kaspa_consensus_core::tx::TransactionId;
+
+/// Aggregated state of the mempool, intended for diagnosing congestion
+#[derive(Clone, Debug, Default)]
+pub struct MempoolInfo {
+    /// Number of transactions in the transaction pool (orphans excluded)
+    pub transaction_count: u64,
+    /// Number of transactions having no parent in the mempool, thus ready to be included in a block template
+    pub ready_transaction_count: u64,
+    pub orphan_count: u64,
+    /// Sum of the masses of the transaction pool transactions
+    pub total_mass: u64,
+    /// Sum of the masses of the ready transactions
+    pub ready_total_mass: u64,
+    /// Sum of the fees of the transaction pool transactions
+    pub total_fees: u64,
+    /// Estimated memory size, in bytes, of the transaction pool
+    pub estimated_size: u64,
+    /// Transaction pool transactions grouped by the feerate buckets of the fee estimator, by descending feerate
+    pub feerate_histogram: Vec<FeerateHistogramBucket>,
+    /// Details of every transaction pool transaction, by descending feerate. Only filled on request
+    pub entries: Vec<MempoolEntryInfo>,
+}
+
+/// Transactions having a feerate between the bucket feerate and the feerate of the next higher bucket
+#[derive(Clone, Debug)]
+pub struct FeerateHistogramBucket {
+    /// Lower feerate bound of the bucket, in sompi/gram
+    pub feerate: f64,
+    /// Estimated inclusion time of the bucket feerate. `None` for the last bucket, which gathers
+    /// the transactions below the lowest feerate estimation
+    pub estimated_seconds: Option<f64>,
+    pub transaction_count: u64,
+    pub total_mass: u64,
+}
+
+/// Mempool related details of a transaction pool transaction
+#[derive(Clone, Debug)]
+pub struct MempoolEntryInfo {
+    pub transaction_id: TransactionId,
+    pub fee: u64,
+    pub mass: u64,
+    pub feerate: f64,
+    pub is_high_priority: bool,
+    /// Virtual DAA score at which the transaction entered the mempool
+    pub added_daa_score: u64,
+    /// Number of in-mempool transactions the transaction directly or indirectly spends from
+    pub ancestor_count: u64,
+    /// Number of in-mempool transactions directly or indirectly spending from the transaction
+    pub descendant_count: u64,
+    /// Position, starting at 0, of the transaction in the order low-priority transactions are evicted in
+    /// when the mempool is full. `None` if the transaction cannot be evicted, that is if it has high
+    /// priority or a parent in the mempool
+    pub eviction_rank: Option<u64>,
+}
+
+/// A transaction of a [`MempoolTransactionGraph`]
+#[derive(Clone, Debug)]
+pub struct MempoolGraphNode {
+    pub transaction_id: TransactionId,
+    pub fee: u64,
+    pub mass: u64,
+    pub feerate: f64,
+    pub is_high_priority: bool,
+    /// Parents of the transaction belonging to the graph
+    pub parent_ids: Vec<TransactionId>,
+}
+
+/// Dependency graph of a mempool transaction, made of the transaction and all its in-mempool
+/// ancestors and descendants, in topological order
+#[derive(Clone, Debug)]
+pub struct MempoolTransactionGraph {
+    pub nodes: Vec<MempoolGraphNode>,
+}
@@ -2,7 +2,9 @@ use kaspa_consensus_core::tx::TransactionId;
 use std::collections::HashSet;
 
 pub mod candidate_tx;
+pub mod mempool_info;
 pub mod owner_txs;
+pub mod prioritised_tx;
 pub mod topological_index;
 pub mod topological_sort;
 pub mod tx_insert;
@@ -0,0 +1,12 @@
+use kaspa_consensus_core::tx::TransactionId;
+
+/// Operator defined fee delta of a transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrioritisedTransaction {
+    pub transaction_id: TransactionId,
+    /// Virtual amount, in sompi, added to the fee of the transaction when selecting block template
+    /// transactions and when evaluating replacements by fee. Negative values deprioritize the transaction.
+    pub fee_delta: i64,
+    /// Whether the transaction is currently in the transaction pool
+    pub in_mempool: bool,
+}
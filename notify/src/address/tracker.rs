@@ -296,10 +296,9 @@ impl Inner {
         self.script_pub_keys.get_index(index as usize).map(|(spk, _)| spk)
     }
 
+    /// Returns the address of the script public key at `index`, if any and if the script is a standard one
     fn get_index_address(&self, index: Index, prefix: Prefix) -> Option<Address> {
-        self.script_pub_keys
-            .get_index(index as usize)
-            .map(|(spk, _)| extract_script_pub_key_address(spk, prefix).expect("is retro-convertible"))
+        self.script_pub_keys.get_index(index as usize).and_then(|(spk, _)| extract_script_pub_key_address(spk, prefix).ok())
     }
 
     fn get_or_insert(&mut self, spk: ScriptPublicKey) -> Result<Index> {
@@ -311,7 +310,9 @@ impl Inner {
                     trace!(
                         "AddressTracker insert #{} {}",
                         index,
-                        extract_script_pub_key_address(entry.key(), Prefix::Mainnet).unwrap()
+                        extract_script_pub_key_address(entry.key(), Prefix::Mainnet)
+                            .map(|address| address.to_string())
+                            .unwrap_or_else(|_| format!("{:?}", entry.key()))
                     );
                     let _ = *entry.insert(0);
 
@@ -480,17 +481,32 @@ impl Tracker {
     /// On success, returns the addresses that were actually inserted in the `Indexer`.
     ///
     /// Fails if the maximum capacity gets reached, leaving the tracker unchanged.
-    pub fn register<T: Indexer>(&self, indexes: &mut T, mut addresses: Vec<Address>) -> Result<Vec<Address>> {
+    pub fn register<T: Indexer>(&self, indexes: &mut T, addresses: Vec<Address>) -> Result<Vec<Address>> {
+        self.register_keys(indexes, addresses, pay_to_address_script)
+    }
+
+    /// Tries to register a `ScriptPublicKey` vector into an `Indexer`, allowing to track scripts having no address form.
+    ///
+    /// Behaves like [`Tracker::register`] otherwise.
+    pub fn register_script_public_keys<T: Indexer>(
+        &self,
+        indexes: &mut T,
+        script_public_keys: Vec<ScriptPublicKey>,
+    ) -> Result<Vec<ScriptPublicKey>> {
+        self.register_keys(indexes, script_public_keys, ScriptPublicKey::clone)
+    }
+
+    fn register_keys<T: Indexer, K>(&self, indexes: &mut T, mut keys: Vec<K>, to_spk: fn(&K) -> ScriptPublicKey) -> Result<Vec<K>> {
         let mut rollback: bool = false;
         {
             let mut counter: usize = 0;
             let mut inner = self.inner.write();
-            addresses.retain(|address| {
+            keys.retain(|key| {
                 counter += 1;
                 if counter % Self::ADDRESS_CHUNK_SIZE == 0 {
                     RwLockWriteGuard::bump(&mut inner);
                 }
-                let spk = pay_to_address_script(address);
+                let spk = to_spk(key);
                 match inner.get_or_insert(spk) {
                     Ok(index) => {
                         if indexes.insert(index) {
@@ -509,9 +525,9 @@ impl Tracker {
             });
         }
         match rollback {
-            false => Ok(addresses),
+            false => Ok(keys),
             true => {
-                let _ = self.unregister(indexes, addresses);
+                let _ = self.unregister_keys(indexes, keys, to_spk);
                 Err(Error::MaxCapacityReached)
             }
         }
@@ -522,18 +538,33 @@ impl Tracker {
     /// decreased.
     ///
     /// Returns the addresses that where successfully unregistered from the `Indexer`.
-    pub fn unregister<T: Indexer>(&self, indexes: &mut T, mut addresses: Vec<Address>) -> Vec<Address> {
+    pub fn unregister<T: Indexer>(&self, indexes: &mut T, addresses: Vec<Address>) -> Vec<Address> {
+        self.unregister_keys(indexes, addresses, pay_to_address_script)
+    }
+
+    /// Unregisters a `ScriptPublicKey` vector from an `Indexer`.
+    ///
+    /// Behaves like [`Tracker::unregister`] otherwise.
+    pub fn unregister_script_public_keys<T: Indexer>(
+        &self,
+        indexes: &mut T,
+        script_public_keys: Vec<ScriptPublicKey>,
+    ) -> Vec<ScriptPublicKey> {
+        self.unregister_keys(indexes, script_public_keys, ScriptPublicKey::clone)
+    }
+
+    fn unregister_keys<T: Indexer, K>(&self, indexes: &mut T, mut keys: Vec<K>, to_spk: fn(&K) -> ScriptPublicKey) -> Vec<K> {
         if indexes.is_empty() {
             vec![]
         } else {
             let mut counter: usize = 0;
             let mut inner = self.inner.write();
-            addresses.retain(|address| {
+            keys.retain(|key| {
                 counter += 1;
                 if counter % Self::ADDRESS_CHUNK_SIZE == 0 {
                     RwLockWriteGuard::bump(&mut inner);
                 }
-                let spk = pay_to_address_script(address);
+                let spk = to_spk(key);
                 if let Some((index, _)) = inner.get(&spk) {
                     if indexes.remove(index) {
                         inner.dec_count(index);
@@ -545,7 +576,7 @@ impl Tracker {
                     false
                 }
             });
-            addresses
+            keys
         }
     }
 
@@ -570,6 +601,23 @@ impl Tracker {
         addresses
     }
 
+    /// Splits the script public keys at `indexes` into the addresses of the standard scripts and the remaining
+    /// non-standard scripts
+    pub fn to_addresses_and_script_public_keys(&self, indexes: &[Index], prefix: Prefix) -> (Vec<Address>, Vec<ScriptPublicKey>) {
+        let mut addresses = Vec::with_capacity(indexes.len());
+        let mut script_public_keys = vec![];
+        for chunk in indexes.chunks(Self::ADDRESS_CHUNK_SIZE) {
+            let inner = self.inner.read();
+            chunk.iter().filter_map(|index| inner.get_index(*index)).for_each(|spk| {
+                match extract_script_pub_key_address(spk, prefix) {
+                    Ok(address) => addresses.push(address),
+                    Err(_) => script_public_keys.push(spk.clone()),
+                }
+            });
+        }
+        (addresses, script_public_keys)
+    }
+
     pub fn len(&self) -> usize {
         self.inner.read().len()
     }
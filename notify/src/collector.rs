@@ -128,7 +128,7 @@ mod tests {
         notifier::test_helpers::NotifyMock,
         subscription::{
             context::SubscriptionContext,
-            single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+            single::{AddressFilteredSubscription, OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         },
     };
     use derive_more::Display;
@@ -171,6 +171,10 @@ mod tests {
             unimplemented!()
         }
 
+        fn apply_address_filtered_subscription(&self, _: &AddressFilteredSubscription, _: &SubscriptionContext) -> Option<Self> {
+            unimplemented!()
+        }
+
         fn event_type(&self) -> EventType {
             unimplemented!()
         }
@@ -51,10 +51,12 @@ event_type_enum! {
         VirtualDaaScoreChanged,
         PruningPointUtxoSetOverride,
         NewBlockTemplate,
+        MempoolTransactionAdded,
+        MempoolTransactionRemoved,
     }
 }
 
-pub const EVENT_COUNT: usize = 9;
+pub const EVENT_COUNT: usize = 11;
 
 impl FromStr for EventType {
     type Err = Error;
@@ -70,6 +72,8 @@ impl FromStr for EventType {
             "virtual-daa-score-changed" => Ok(EventType::VirtualDaaScoreChanged),
             "pruning-point-utxo-set-override" => Ok(EventType::PruningPointUtxoSetOverride),
             "new-block-template" => Ok(EventType::NewBlockTemplate),
+            "mempool-transaction-added" => Ok(EventType::MempoolTransactionAdded),
+            "mempool-transaction-removed" => Ok(EventType::MempoolTransactionRemoved),
             _ => Err(Error::InvalidEventType(s.to_string())),
         }
     }
@@ -3,7 +3,7 @@ use crate::subscription::context::SubscriptionContext;
 use super::{
     events::EventType,
     subscription::{
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{AddressFilteredSubscription, OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         Single,
     },
 };
@@ -22,6 +22,12 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
     fn apply_utxos_changed_subscription(&self, subscription: &UtxosChangedSubscription, context: &SubscriptionContext)
         -> Option<Self>;
 
+    fn apply_address_filtered_subscription(
+        &self,
+        subscription: &AddressFilteredSubscription,
+        context: &SubscriptionContext,
+    ) -> Option<Self>;
+
     fn apply_subscription(&self, subscription: &dyn Single, context: &SubscriptionContext) -> Option<Self> {
         match subscription.event_type() {
             EventType::VirtualChainChanged => self.apply_virtual_chain_changed_subscription(
@@ -30,6 +36,15 @@ pub trait Notification: Clone + Debug + Display + Send + Sync + 'static {
             ),
             EventType::UtxosChanged => self
                 .apply_utxos_changed_subscription(subscription.as_any().downcast_ref::<UtxosChangedSubscription>().unwrap(), context),
+            event_type if AddressFilteredSubscription::supports(event_type) => {
+                match subscription.as_any().downcast_ref::<AddressFilteredSubscription>() {
+                    Some(subscription) => self.apply_address_filtered_subscription(subscription, context),
+                    // Subscriptions with no address filter are broadcast as overall subscriptions
+                    None => {
+                        self.apply_overall_subscription(subscription.as_any().downcast_ref::<OverallSubscription>().unwrap(), context)
+                    }
+                }
+            }
             _ => self.apply_overall_subscription(subscription.as_any().downcast_ref::<OverallSubscription>().unwrap(), context),
         }
     }
@@ -178,6 +193,18 @@ pub mod test_helpers {
             }
         }
 
+        fn apply_address_filtered_subscription(
+            &self,
+            subscription: &AddressFilteredSubscription,
+            _: &SubscriptionContext,
+        ) -> Option<Self> {
+            // Test notifications carry no transaction so no address filter can apply
+            match subscription.active() {
+                true => Some(self.clone()),
+                false => None,
+            }
+        }
+
         fn event_type(&self) -> EventType {
             self.into()
         }
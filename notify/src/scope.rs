@@ -2,7 +2,9 @@ use super::events::EventType;
 use borsh::{BorshDeserialize, BorshSerialize};
 use derive_more::Display;
 use kaspa_addresses::Address;
+use kaspa_consensus_core::tx::ScriptPublicKey;
 use serde::{Deserialize, Serialize};
+use std::io::Read;
 use workflow_serializer::prelude::*;
 
 macro_rules! scope_enum {
@@ -45,6 +47,8 @@ pub enum Scope {
     VirtualDaaScoreChanged,
     PruningPointUtxoSetOverride,
     NewBlockTemplate,
+    MempoolTransactionAdded,
+    MempoolTransactionRemoved,
 }
 }
 
@@ -56,7 +60,7 @@ impl Scope {
 
 impl Serializer for Scope {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Scope, self, writer)?;
         Ok(())
     }
@@ -64,25 +68,91 @@ impl Serializer for Scope {
 
 impl Deserializer for Scope {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        load!(Scope, reader)
+        let version = load!(u16, reader)?;
+        if version > 1 {
+            return load!(Scope, reader);
+        }
+        // Version 1 predates the address filter of BlockAddedScope which was then fieldless
+        let variant = load!(u8, reader)?;
+        match variant {
+            0 => Ok(Scope::BlockAdded(BlockAddedScope::default())),
+            _ => load!(Scope, &mut [variant].as_slice().chain(reader)),
+        }
     }
 }
 
-#[derive(Clone, Display, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
-pub struct BlockAddedScope {}
+/// Implements the traits shared by all the scopes filtering their notifications by a set of addresses
+/// and of script public keys, both sets being empty meaning no filter at all
+macro_rules! address_filtered_scope {
+    ($name:ident) => {
+        impl $name {
+            pub fn new(addresses: Vec<Address>, script_public_keys: Vec<ScriptPublicKey>) -> Self {
+                Self { addresses, script_public_keys }
+            }
+
+            /// Returns true if the scope has no filter
+            pub fn is_empty(&self) -> bool {
+                self.addresses.is_empty() && self.script_public_keys.is_empty()
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let addresses = match self.addresses.len() {
+                    0 => "all".to_string(),
+                    1 => format!("{}", self.addresses[0]),
+                    n => format!("{} addresses", n),
+                };
+                match self.script_public_keys.len() {
+                    0 => write!(f, "{} ({})", stringify!($name), addresses),
+                    n if self.addresses.is_empty() => write!(f, "{} ({} scripts)", stringify!($name), n),
+                    n => write!(f, "{} ({}, {} scripts)", stringify!($name), addresses, n),
+                }
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.addresses.len() == other.addresses.len()
+                    && self.addresses.iter().all(|x| other.addresses.contains(x))
+                    && self.script_public_keys.len() == other.script_public_keys.len()
+                    && self.script_public_keys.iter().all(|x| other.script_public_keys.contains(x))
+            }
+        }
+
+        impl Eq for $name {}
+    };
+}
+
+/// Scope of the BlockAdded notifications
+///
+/// When `addresses` or `script_public_keys` is not empty, only the blocks having a transaction output
+/// paying to one of them are notified.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct BlockAddedScope {
+    #[serde(default)]
+    pub addresses: Vec<Address>,
+    #[serde(default)]
+    pub script_public_keys: Vec<ScriptPublicKey>,
+}
+
+address_filtered_scope!(BlockAddedScope);
 
 impl Serializer for BlockAddedScope {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
+        store!(Vec<Address>, &self.addresses, writer)?;
+        store!(Vec<ScriptPublicKey>, &self.script_public_keys, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for BlockAddedScope {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        Ok(Self {})
+        let version = load!(u16, reader)?;
+        let addresses = if version > 1 { load!(Vec<Address>, reader)? } else { vec![] };
+        let script_public_keys = if version > 2 { load!(Vec<ScriptPublicKey>, reader)? } else { vec![] };
+        Ok(Self { addresses, script_public_keys })
     }
 }
 
@@ -266,3 +336,65 @@ impl Deserializer for NewBlockTemplateScope {
         Ok(Self {})
     }
 }
+
+/// Scope of the MempoolTransactionAdded notifications
+///
+/// When `addresses` or `script_public_keys` is not empty, only the transactions having an output paying
+/// to one of them are notified.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct MempoolTransactionAddedScope {
+    pub addresses: Vec<Address>,
+    #[serde(default)]
+    pub script_public_keys: Vec<ScriptPublicKey>,
+}
+
+address_filtered_scope!(MempoolTransactionAddedScope);
+
+impl Serializer for MempoolTransactionAddedScope {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &2, writer)?;
+        store!(Vec<Address>, &self.addresses, writer)?;
+        store!(Vec<ScriptPublicKey>, &self.script_public_keys, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for MempoolTransactionAddedScope {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = load!(u16, reader)?;
+        let addresses = load!(Vec<Address>, reader)?;
+        let script_public_keys = if version > 1 { load!(Vec<ScriptPublicKey>, reader)? } else { vec![] };
+        Ok(Self { addresses, script_public_keys })
+    }
+}
+
+/// Scope of the MempoolTransactionRemoved notifications
+///
+/// When `addresses` or `script_public_keys` is not empty, only the transactions having an output paying
+/// to one of them are notified.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct MempoolTransactionRemovedScope {
+    pub addresses: Vec<Address>,
+    #[serde(default)]
+    pub script_public_keys: Vec<ScriptPublicKey>,
+}
+
+address_filtered_scope!(MempoolTransactionRemovedScope);
+
+impl Serializer for MempoolTransactionRemovedScope {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &2, writer)?;
+        store!(Vec<Address>, &self.addresses, writer)?;
+        store!(Vec<ScriptPublicKey>, &self.script_public_keys, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for MempoolTransactionRemovedScope {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = load!(u16, reader)?;
+        let addresses = load!(Vec<Address>, reader)?;
+        let script_public_keys = if version > 1 { load!(Vec<ScriptPublicKey>, reader)? } else { vec![] };
+        Ok(Self { addresses, script_public_keys })
+    }
+}
@@ -18,6 +18,9 @@ impl ArrayBuilder {
                     listener_id,
                     utxos_changed_capacity.unwrap_or_default(),
                 )),
+                EventType::BlockAdded | EventType::MempoolTransactionAdded | EventType::MempoolTransactionRemoved => {
+                    Arc::new(single::AddressFilteredSubscription::new(event_type, single::UtxosChangedState::None, listener_id))
+                }
                 _ => Arc::new(single::OverallSubscription::new(event_type, false)),
             };
             subscription
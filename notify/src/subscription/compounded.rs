@@ -214,7 +214,7 @@ impl Compounded for UtxosChangedSubscription {
                         assert!(self.all > 0);
                         self.all -= 1;
                         if self.all == 0 {
-                            let addresses = self.to_addresses(Prefix::Mainnet, context);
+                            let addresses = self.to_addresses(context.prefix, context);
                             if !addresses.is_empty() {
                                 return Some(Mutation::new(Command::Start, UtxosChangedScope::new(addresses).into()));
                             } else {
@@ -240,7 +240,7 @@ impl Subscription for UtxosChangedSubscription {
     }
 
     fn scope(&self, context: &SubscriptionContext) -> Scope {
-        let addresses = if self.all > 0 { vec![] } else { self.to_addresses(Prefix::Mainnet, context) };
+        let addresses = if self.all > 0 { vec![] } else { self.to_addresses(context.prefix, context) };
         Scope::UtxosChanged(UtxosChangedScope::new(addresses))
     }
 }
@@ -289,8 +289,8 @@ mod tests {
     #[allow(clippy::redundant_clone)]
     fn test_overall_compounding() {
         let none = || Box::new(OverallSubscription::new(EventType::BlockAdded));
-        let add = || Mutation::new(Command::Start, Scope::BlockAdded(BlockAddedScope {}));
-        let remove = || Mutation::new(Command::Stop, Scope::BlockAdded(BlockAddedScope {}));
+        let add = || Mutation::new(Command::Start, Scope::BlockAdded(BlockAddedScope::default()));
+        let remove = || Mutation::new(Command::Stop, Scope::BlockAdded(BlockAddedScope::default()));
         let test = Test {
             name: "OverallSubscription 0 to 2 to 0",
             context: SubscriptionContext::new(),
@@ -6,6 +6,7 @@ use crate::{
         DynSubscription,
     },
 };
+use kaspa_addresses::Prefix;
 use std::{ops::Deref, sync::Arc};
 
 #[cfg(test)]
@@ -15,6 +16,9 @@ use kaspa_addresses::Address;
 pub struct SubscriptionContextInner {
     pub address_tracker: Tracker,
     pub utxos_changed_subscription_to_all: DynSubscription,
+
+    /// Prefix of the network, used when converting tracked script public keys back into addresses
+    pub prefix: Prefix,
 }
 
 impl SubscriptionContextInner {
@@ -25,10 +29,14 @@ impl SubscriptionContextInner {
     }
 
     pub fn with_options(max_addresses: Option<usize>) -> Self {
+        Self::with_prefix(Prefix::Mainnet, max_addresses)
+    }
+
+    pub fn with_prefix(prefix: Prefix, max_addresses: Option<usize>) -> Self {
         let address_tracker = Tracker::new(max_addresses);
         let utxos_changed_subscription_all =
             Arc::new(UtxosChangedSubscription::new(UtxosChangedState::All, Self::CONTEXT_LISTENER_ID));
-        Self { address_tracker, utxos_changed_subscription_to_all: utxos_changed_subscription_all }
+        Self { address_tracker, utxos_changed_subscription_to_all: utxos_changed_subscription_all, prefix }
     }
 
     #[cfg(test)]
@@ -36,7 +44,7 @@ impl SubscriptionContextInner {
         let address_tracker = Tracker::with_addresses(addresses);
         let utxos_changed_subscription_all =
             Arc::new(UtxosChangedSubscription::new(UtxosChangedState::All, Self::CONTEXT_LISTENER_ID));
-        Self { address_tracker, utxos_changed_subscription_to_all: utxos_changed_subscription_all }
+        Self { address_tracker, utxos_changed_subscription_to_all: utxos_changed_subscription_all, prefix: Prefix::Mainnet }
     }
 }
 
@@ -61,6 +69,12 @@ impl SubscriptionContext {
         Self { inner }
     }
 
+    /// Creates a context converting the tracked addresses back with `prefix`
+    pub fn with_prefix(prefix: Prefix, max_addresses: Option<usize>) -> Self {
+        let inner = Arc::new(SubscriptionContextInner::with_prefix(prefix, max_addresses));
+        Self { inner }
+    }
+
     #[cfg(test)]
     pub fn with_addresses(addresses: &[Address]) -> Self {
         let inner = Arc::new(SubscriptionContextInner::with_addresses(addresses));
@@ -3,7 +3,10 @@ use crate::{
     error::Result,
     events::EventType,
     listener::ListenerId,
-    scope::{Scope, UtxosChangedScope, VirtualChainChangedScope},
+    scope::{
+        BlockAddedScope, MempoolTransactionAddedScope, MempoolTransactionRemovedScope, Scope, UtxosChangedScope,
+        VirtualChainChangedScope,
+    },
     subscription::{
         context::SubscriptionContext, BroadcastingSingle, Command, DynSubscription, Mutation, MutationOutcome, MutationPolicies,
         Single, Subscription, UtxosChangedMutationPolicy,
@@ -184,7 +187,13 @@ enum UtxosChangedMutation {
 
 impl From<(Command, &UtxosChangedScope)> for UtxosChangedMutation {
     fn from((command, scope): (Command, &UtxosChangedScope)) -> Self {
-        match (command, scope.addresses.is_empty()) {
+        (command, scope.addresses.is_empty()).into()
+    }
+}
+
+impl From<(Command, bool)> for UtxosChangedMutation {
+    fn from((command, no_address): (Command, bool)) -> Self {
+        match (command, no_address) {
             (Command::Stop, true) => Self::None,
             (Command::Stop, false) => Self::Remove,
             (Command::Start, false) => Self::Add,
@@ -285,9 +294,29 @@ impl UtxosChangedSubscriptionData {
         context.address_tracker.unregister(&mut self.indexes, addresses)
     }
 
+    /// Splits the tracked script public keys into the addresses of the standard scripts and the remaining non-standard scripts
+    pub fn to_addresses_and_script_public_keys(&self, context: &SubscriptionContext) -> (Vec<Address>, Vec<ScriptPublicKey>) {
+        context.address_tracker.to_addresses_and_script_public_keys(&self.indexes.iter().copied().collect_vec(), context.prefix)
+    }
+
+    pub fn register_script_public_keys(
+        &mut self,
+        script_public_keys: Vec<ScriptPublicKey>,
+        context: &SubscriptionContext,
+    ) -> Result<Vec<ScriptPublicKey>> {
+        Ok(context.address_tracker.register_script_public_keys(&mut self.indexes, script_public_keys)?)
+    }
+
+    pub fn unregister_script_public_keys(
+        &mut self,
+        script_public_keys: Vec<ScriptPublicKey>,
+        context: &SubscriptionContext,
+    ) -> Vec<ScriptPublicKey> {
+        context.address_tracker.unregister_script_public_keys(&mut self.indexes, script_public_keys)
+    }
+
     pub fn unregister_indexes(&mut self, context: &SubscriptionContext) -> Vec<Address> {
-        // TODO: consider using a provided prefix
-        let removed = self.to_addresses(Prefix::Mainnet, context);
+        let removed = self.to_addresses(context.prefix, context);
         context.address_tracker.unregister_indexes(&mut self.indexes);
         removed
     }
@@ -562,8 +591,243 @@ impl Subscription for UtxosChangedSubscription {
     }
 
     fn scope(&self, context: &SubscriptionContext) -> Scope {
-        // TODO: consider using a provided prefix
-        UtxosChangedScope::new(self.data().to_addresses(Prefix::Mainnet, context)).into()
+        UtxosChangedScope::new(self.data().to_addresses(context.prefix, context)).into()
+    }
+}
+
+static ADDRESS_FILTERED_SUBSCRIPTIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Subscription to notifications optionally filtered by a set of addresses and script public keys
+///
+/// Used by the BlockAdded, MempoolTransactionAdded and MempoolTransactionRemoved event types.
+///
+/// Both sets are tracked as script public keys, including the scripts having no address form. They are registered in the [`SubscriptionContext`] address tracker like for [`UtxosChangedSubscription`],
+/// but unlike the latter it is never propagated to the parent notifier: the filtering always happens at listener level
+/// and the outcome mutations only report this subscription being started or stopped as a whole.
+#[derive(Debug)]
+pub struct AddressFilteredSubscription {
+    /// Event type of the subscription
+    event_type: EventType,
+
+    /// Mutable inner data
+    data: RwLock<UtxosChangedSubscriptionData>,
+
+    /// ID of the listener owning this subscription
+    ///
+    /// Along with the event type, this fully determines both equality and hash.
+    listener_id: ListenerId,
+}
+
+impl AddressFilteredSubscription {
+    pub fn new(event_type: EventType, state: UtxosChangedState, listener_id: ListenerId) -> Self {
+        assert!(Self::supports(event_type), "event type {event_type} does not support address filtering");
+        let data = RwLock::new(UtxosChangedSubscriptionData::with_capacity(state, 0));
+        let subscription = Self { event_type, data, listener_id };
+        trace!(
+            "AddressFilteredSubscription: {} in total (new {})",
+            ADDRESS_FILTERED_SUBSCRIPTIONS.fetch_add(1, Ordering::SeqCst) + 1,
+            subscription
+        );
+        subscription
+    }
+
+    #[cfg(test)]
+    pub fn with_addresses(
+        event_type: EventType,
+        active: bool,
+        addresses: Vec<Address>,
+        listener_id: ListenerId,
+        context: &SubscriptionContext,
+    ) -> Self {
+        let state = match (active, addresses.is_empty()) {
+            (false, _) => UtxosChangedState::None,
+            (true, false) => UtxosChangedState::Selected,
+            (true, true) => UtxosChangedState::All,
+        };
+        let subscription = Self::new(event_type, state, listener_id);
+        let _ = subscription.data_mut().register(addresses, context);
+        subscription
+    }
+
+    /// Returns true if notifications of `event_type` can be filtered by addresses
+    pub fn supports(event_type: EventType) -> bool {
+        matches!(event_type, EventType::BlockAdded | EventType::MempoolTransactionAdded | EventType::MempoolTransactionRemoved)
+    }
+
+    pub fn data(&self) -> RwLockReadGuard<'_, UtxosChangedSubscriptionData> {
+        self.data.read()
+    }
+
+    fn data_mut(&self) -> RwLockWriteGuard<'_, UtxosChangedSubscriptionData> {
+        self.data.write()
+    }
+
+    #[inline(always)]
+    pub fn state(&self) -> UtxosChangedState {
+        self.data().state
+    }
+
+    pub fn to_all(&self) -> bool {
+        matches!(self.data().state, UtxosChangedState::All)
+    }
+
+    fn scope_filter(scope: Scope) -> Option<(Vec<Address>, Vec<ScriptPublicKey>)> {
+        match scope {
+            Scope::BlockAdded(scope) => Some((scope.addresses, scope.script_public_keys)),
+            Scope::MempoolTransactionAdded(scope) => Some((scope.addresses, scope.script_public_keys)),
+            Scope::MempoolTransactionRemoved(scope) => Some((scope.addresses, scope.script_public_keys)),
+            _ => None,
+        }
+    }
+
+    fn scope_with_filter(&self, addresses: Vec<Address>, script_public_keys: Vec<ScriptPublicKey>) -> Scope {
+        match self.event_type {
+            EventType::BlockAdded => BlockAddedScope::new(addresses, script_public_keys).into(),
+            EventType::MempoolTransactionAdded => MempoolTransactionAddedScope::new(addresses, script_public_keys).into(),
+            EventType::MempoolTransactionRemoved => MempoolTransactionRemovedScope::new(addresses, script_public_keys).into(),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Clone for AddressFilteredSubscription {
+    fn clone(&self) -> Self {
+        let subscription = Self { event_type: self.event_type, data: RwLock::new(self.data().clone()), listener_id: self.listener_id };
+        trace!(
+            "AddressFilteredSubscription: {} in total (clone {})",
+            ADDRESS_FILTERED_SUBSCRIPTIONS.fetch_add(1, Ordering::SeqCst) + 1,
+            subscription
+        );
+        subscription
+    }
+}
+
+impl Display for AddressFilteredSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.event_type, self.data())
+    }
+}
+
+impl Drop for AddressFilteredSubscription {
+    fn drop(&mut self) {
+        trace!(
+            "AddressFilteredSubscription: {} in total (drop {})",
+            ADDRESS_FILTERED_SUBSCRIPTIONS.fetch_sub(1, Ordering::SeqCst) - 1,
+            self
+        );
+    }
+}
+
+impl PartialEq for AddressFilteredSubscription {
+    /// Equality is specifically bound to the event type and the listener ID
+    fn eq(&self, other: &Self) -> bool {
+        self.event_type == other.event_type && self.listener_id == other.listener_id
+    }
+}
+impl Eq for AddressFilteredSubscription {}
+
+impl Hash for AddressFilteredSubscription {
+    /// Hash is specifically bound to the event type and the listener ID
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.event_type.hash(state);
+        self.listener_id.hash(state);
+    }
+}
+
+impl Single for AddressFilteredSubscription {
+    fn apply_mutation(
+        &self,
+        current: &Arc<dyn Single>,
+        mutation: Mutation,
+        _: MutationPolicies,
+        context: &SubscriptionContext,
+    ) -> Result<MutationOutcome> {
+        assert_eq!(self.event_type(), mutation.event_type());
+        let command = mutation.command;
+        let Some((addresses, script_public_keys)) = Self::scope_filter(mutation.scope) else {
+            return Ok(MutationOutcome::new());
+        };
+        let no_filter = addresses.is_empty() && script_public_keys.is_empty();
+        // The parent notifier is only told about the subscription being started or stopped as a whole
+        let overall = |command: Command| vec![Mutation::new(command, self.event_type.into())];
+        let mut data = self.data_mut();
+        // Registers the filter of a subscription having none yet
+        let register = |data: &mut UtxosChangedSubscriptionData, addresses: Vec<Address>, script_public_keys: Vec<ScriptPublicKey>| {
+            data.register(addresses, context)?;
+            if let Err(err) = data.register_script_public_keys(script_public_keys, context) {
+                data.unregister_indexes(context);
+                return Err(err);
+            }
+            Result::Ok(())
+        };
+        let outcome = match (data.state, UtxosChangedMutation::from((command, no_filter))) {
+            (UtxosChangedState::None, UtxosChangedMutation::None | UtxosChangedMutation::Remove) => MutationOutcome::new(),
+            (UtxosChangedState::None, UtxosChangedMutation::Add) => {
+                register(&mut data, addresses, script_public_keys)?;
+                data.update_state(UtxosChangedState::Selected);
+                MutationOutcome::with_mutated(current.clone(), overall(Command::Start))
+            }
+            (UtxosChangedState::None, UtxosChangedMutation::All) => {
+                data.update_state(UtxosChangedState::All);
+                MutationOutcome::with_mutated(current.clone(), overall(Command::Start))
+            }
+            (UtxosChangedState::Selected, UtxosChangedMutation::None) => {
+                data.unregister_indexes(context);
+                data.update_state(UtxosChangedState::None);
+                MutationOutcome::with_mutated(current.clone(), overall(Command::Stop))
+            }
+            (UtxosChangedState::Selected, UtxosChangedMutation::Remove) => {
+                let removed =
+                    data.unregister(addresses, context).len() + data.unregister_script_public_keys(script_public_keys, context).len();
+                if removed > 0 && data.is_empty() {
+                    data.update_state(UtxosChangedState::None);
+                    MutationOutcome::with_mutated(current.clone(), overall(Command::Stop))
+                } else {
+                    MutationOutcome::new()
+                }
+            }
+            (UtxosChangedState::Selected, UtxosChangedMutation::Add) => {
+                let added = data.register(addresses, context)?;
+                if let Err(err) = data.register_script_public_keys(script_public_keys, context) {
+                    // Leave the subscription as it was before the mutation
+                    data.unregister(added, context);
+                    return Err(err);
+                }
+                MutationOutcome::new()
+            }
+            (UtxosChangedState::Selected, UtxosChangedMutation::All) => {
+                // The new state has to be reported so the subscription gets regrouped by the broadcasters
+                data.unregister_indexes(context);
+                data.update_state(UtxosChangedState::All);
+                MutationOutcome::with_mutated(current.clone(), vec![])
+            }
+            (UtxosChangedState::All, UtxosChangedMutation::None) => {
+                data.update_state(UtxosChangedState::None);
+                MutationOutcome::with_mutated(current.clone(), overall(Command::Stop))
+            }
+            (UtxosChangedState::All, UtxosChangedMutation::Remove | UtxosChangedMutation::All) => MutationOutcome::new(),
+            (UtxosChangedState::All, UtxosChangedMutation::Add) => {
+                register(&mut data, addresses, script_public_keys)?;
+                data.update_state(UtxosChangedState::Selected);
+                MutationOutcome::with_mutated(current.clone(), vec![])
+            }
+        };
+        Ok(outcome)
+    }
+}
+
+impl Subscription for AddressFilteredSubscription {
+    fn event_type(&self) -> EventType {
+        self.event_type
+    }
+
+    fn active(&self) -> bool {
+        self.state().active()
+    }
+
+    fn scope(&self, context: &SubscriptionContext) -> Scope {
+        let (addresses, script_public_keys) = self.data().to_addresses_and_script_public_keys(context);
+        self.scope_with_filter(addresses, script_public_keys)
     }
 }
 
@@ -577,6 +841,14 @@ impl BroadcastingSingle for DynSubscription {
                     false => self,
                 }
             }
+            event_type if AddressFilteredSubscription::supports(event_type) => {
+                let address_filtered_subscription = self.as_any().downcast_ref::<AddressFilteredSubscription>().unwrap();
+                match address_filtered_subscription.to_all() {
+                    // All the listeners with no address filter share the same broadcasting instance
+                    true => Arc::new(OverallSubscription::new(event_type, true)),
+                    false => self,
+                }
+            }
             _ => self,
         }
     }
@@ -767,7 +1039,7 @@ mod tests {
             Arc::new(OverallSubscription { event_type: EventType::BlockAdded, active })
         }
         fn m(command: Command) -> Mutation {
-            Mutation { command, scope: Scope::BlockAdded(BlockAddedScope {}) }
+            Mutation { command, scope: Scope::BlockAdded(BlockAddedScope::default()) }
         }
 
         // Subscriptions
@@ -1076,4 +1348,147 @@ mod tests {
         ]);
         tests.run(&context)
     }
+
+    #[test]
+    fn test_address_filtered_mutation() {
+        let context = SubscriptionContext::new();
+        let a_stock = get_3_addresses(true);
+
+        let av = |indexes: &[usize]| indexes.iter().map(|idx| (a_stock[*idx]).clone()).collect::<Vec<_>>();
+        let s = |active: bool, indexes: &[usize]| {
+            Arc::new(AddressFilteredSubscription::with_addresses(
+                EventType::MempoolTransactionAdded,
+                active,
+                av(indexes),
+                MutationTests::LISTENER_ID,
+                &context,
+            )) as DynSubscription
+        };
+        let m = |command: Command, indexes: &[usize]| -> Mutation {
+            Mutation { command, scope: Scope::MempoolTransactionAdded(MempoolTransactionAddedScope::new(av(indexes), vec![])) }
+        };
+
+        // Subscriptions
+        let none = || s(false, &[]);
+        let selected_0 = || s(true, &[0]);
+        let selected_01 = || s(true, &[0, 1]);
+        let all = || s(true, &[]);
+
+        // Mutations
+        let start_all = || m(Command::Start, &[]);
+        let stop_all = || m(Command::Stop, &[]);
+        let start_0 = || m(Command::Start, &[0]);
+        let start_1 = || m(Command::Start, &[1]);
+        let stop_0 = || m(Command::Stop, &[0]);
+        let stop_01 = || m(Command::Stop, &[0, 1]);
+
+        // Tests
+        let tests = MutationTests::new(vec![
+            MutationTest {
+                name: "AddressFilteredSubscription None to All (add all)",
+                state: none(),
+                mutation: start_all(),
+                new_state: all(),
+                outcome: MutationOutcome::with_mutated(all(), vec![start_all()]),
+            },
+            MutationTest {
+                name: "AddressFilteredSubscription None to Selected 0 (add set)",
+                state: none(),
+                mutation: start_0(),
+                new_state: selected_0(),
+                outcome: MutationOutcome::with_mutated(selected_0(), vec![start_all()]),
+            },
+            MutationTest {
+                name: "AddressFilteredSubscription None to None (stop set)",
+                state: none(),
+                mutation: stop_0(),
+                new_state: none(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "AddressFilteredSubscription Selected 0 to 01 (add set)",
+                state: selected_0(),
+                mutation: start_1(),
+                new_state: selected_01(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "AddressFilteredSubscription Selected 01 to 1 (remove subset)",
+                state: selected_01(),
+                mutation: stop_0(),
+                new_state: s(true, &[1]),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "AddressFilteredSubscription Selected 01 to None (remove set)",
+                state: selected_01(),
+                mutation: stop_01(),
+                new_state: none(),
+                outcome: MutationOutcome::with_mutated(none(), vec![stop_all()]),
+            },
+            MutationTest {
+                name: "AddressFilteredSubscription Selected 01 to All (add all)",
+                state: selected_01(),
+                mutation: start_all(),
+                new_state: all(),
+                outcome: MutationOutcome::with_mutated(all(), vec![]),
+            },
+            MutationTest {
+                name: "AddressFilteredSubscription All to Selected 0 (add set)",
+                state: all(),
+                mutation: start_0(),
+                new_state: selected_0(),
+                outcome: MutationOutcome::with_mutated(selected_0(), vec![]),
+            },
+            MutationTest {
+                name: "AddressFilteredSubscription All to All (remove set)",
+                state: all(),
+                mutation: stop_0(),
+                new_state: all(),
+                outcome: MutationOutcome::new(),
+            },
+            MutationTest {
+                name: "AddressFilteredSubscription All to None (remove all)",
+                state: all(),
+                mutation: stop_all(),
+                new_state: none(),
+                outcome: MutationOutcome::with_mutated(none(), vec![stop_all()]),
+            },
+        ]);
+        tests.run(&context)
+    }
+    #[test]
+    fn test_address_filtered_script_public_keys() {
+        let context = SubscriptionContext::with_prefix(Prefix::Testnet, None);
+        let address = Address::new(Prefix::Testnet, kaspa_addresses::Version::PubKey, &[1u8; 32]);
+        // OP_TRUE has no address form
+        let script = ScriptPublicKey::from_vec(0, vec![0x51]);
+        let address_script = kaspa_txscript::pay_to_address_script(&address);
+        let other_script = ScriptPublicKey::from_vec(0, vec![0x52]);
+
+        let mut subscription: DynSubscription =
+            Arc::new(AddressFilteredSubscription::new(EventType::BlockAdded, UtxosChangedState::None, MutationTests::LISTENER_ID));
+        let start = Mutation::new(Command::Start, BlockAddedScope::new(vec![address.clone()], vec![script.clone()]).into());
+        let outcome = subscription.mutate(start, Default::default(), &context).unwrap();
+        assert_eq!(outcome.mutations, vec![Mutation::new(Command::Start, EventType::BlockAdded.into())]);
+
+        let filtered = (*subscription).as_any().downcast_ref::<AddressFilteredSubscription>().unwrap();
+        assert!(filtered.data().contains(&script, &context), "the script should be tracked");
+        assert!(filtered.data().contains(&address_script, &context), "the address script should be tracked");
+        assert!(!filtered.data().contains(&other_script, &context), "an unregistered script should not be tracked");
+
+        // The scope gives the address back with the prefix of the context and the non-standard script as is
+        assert_eq!(subscription.scope(&context), BlockAddedScope::new(vec![address.clone()], vec![script.clone()]).into());
+
+        // Removing the address only keeps the subscription active on the script
+        let stop_address = Mutation::new(Command::Stop, BlockAddedScope::new(vec![address], vec![]).into());
+        let outcome = subscription.mutate(stop_address, Default::default(), &context).unwrap();
+        assert!(outcome.mutations.is_empty());
+        assert!(subscription.active());
+
+        let stop_script = Mutation::new(Command::Stop, BlockAddedScope::new(vec![], vec![script]).into());
+        let outcome = subscription.mutate(stop_script, Default::default(), &context).unwrap();
+        assert_eq!(outcome.mutations, vec![Mutation::new(Command::Stop, EventType::BlockAdded.into())]);
+        assert!(!subscription.active());
+    }
 }
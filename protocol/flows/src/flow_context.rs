@@ -1,4 +1,5 @@
 use crate::flowcontext::{
+    dandelion::{DandelionRelay, StemRoute, EMBARGO_CHECK_INTERVAL},
     orphans::{OrphanBlocksPool, OrphanOutput},
     process_queue::ProcessQueue,
     transactions::TransactionsSpread,
@@ -6,6 +7,7 @@ use crate::flowcontext::{
 use crate::{v7, v8};
 use async_trait::async_trait;
 use futures::future::join_all;
+use itertools::Itertools;
 use kaspa_addressmanager::AddressManager;
 use kaspa_connectionmanager::ConnectionManager;
 use kaspa_consensus_core::api::{BlockValidationFuture, BlockValidationFutures};
@@ -21,19 +23,23 @@ use kaspa_consensusmanager::{BlockProcessingBatch, ConsensusInstance, ConsensusM
 use kaspa_core::{
     debug, info,
     kaspad_env::{name, version},
-    task::tick::TickService,
+    task::tick::{TickReason, TickService},
 };
 use kaspa_core::{time::unix_now, warn};
 use kaspa_hashes::Hash;
 use kaspa_mining::mempool::tx::{Orphan, Priority};
-use kaspa_mining::{manager::MiningManagerProxy, mempool::tx::RbfPolicy};
+use kaspa_mining::{
+    errors::{MiningManagerError, MiningManagerResult},
+    manager::MiningManagerProxy,
+    mempool::{errors::RuleError as MempoolRuleError, tx::RbfPolicy},
+};
 use kaspa_notify::notifier::Notify;
 use kaspa_p2p_lib::{
-    common::ProtocolError,
+    common::{ProtocolError, SERVICE_STEM_RELAY},
     convert::model::version::Version,
     make_message,
-    pb::{kaspad_message::Payload, InvRelayBlockMessage},
-    ConnectionInitializer, Hub, KaspadHandshake, PeerKey, PeerProperties, Router,
+    pb::{kaspad_message::Payload, InvRelayBlockMessage, StemTransactionMessage},
+    ConnectionInitializer, Hub, KaspadHandshake, PeerKey, PeerProperties, Router, TransportSecurity,
 };
 use kaspa_p2p_mining::rule_engine::MiningRuleEngine;
 use kaspa_utils::iter::IterExtensions;
@@ -44,6 +50,7 @@ use std::time::Instant;
 use std::{collections::hash_map::Entry, fmt::Display};
 use std::{
     iter::once,
+    net::IpAddr,
     ops::Deref,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -237,6 +244,12 @@ pub struct FlowContextInner {
 
     // Mining rule engine
     mining_rule_engine: Arc<MiningRuleEngine>,
+
+    // Encryption settings of peer connections
+    transport_security: TransportSecurity,
+
+    // Dandelion++ stem relay state, set only if stem relay is enabled
+    dandelion: Option<Mutex<DandelionRelay>>,
 }
 
 #[derive(Clone)]
@@ -311,6 +324,7 @@ impl FlowContext {
         notification_root: Arc<ConsensusNotificationRoot>,
         hub: Hub,
         mining_rule_engine: Arc<MiningRuleEngine>,
+        transport_security: TransportSecurity,
     ) -> Self {
         let bps = config.bps() as usize;
         let orphan_resolution_range = BASELINE_ORPHAN_RESOLUTION_RANGE + (bps as f64).log2().ceil() as u32;
@@ -318,6 +332,7 @@ impl FlowContext {
         // The maximum amount of orphans allowed in the orphans pool. This number is an approximation
         // of how many orphans there can possibly be on average bounded by an upper bound.
         let max_orphans = (2u64.pow(orphan_resolution_range) as usize * config.ghostdag_k() as usize).min(MAX_ORPHANS_UPPER_BOUND);
+        let dandelion = config.dandelion_relay.then(|| Mutex::new(DandelionRelay::new()));
         Self {
             inner: Arc::new(FlowContextInner {
                 node_id: Uuid::new_v4().into(),
@@ -340,6 +355,8 @@ impl FlowContext {
                 max_orphans,
                 config,
                 mining_rule_engine,
+                transport_security,
+                dandelion,
             }),
         }
     }
@@ -360,6 +377,14 @@ impl FlowContext {
         if let Some(logger) = self.block_event_logger.as_ref() {
             logger.start();
         }
+        if self.dandelion.is_some() {
+            let ctx = self.clone();
+            tokio::spawn(async move {
+                while let TickReason::Wakeup = ctx.tick_service.tick(EMBARGO_CHECK_INTERVAL).await {
+                    ctx.fluff_expired_embargoes().await;
+                }
+            });
+        }
     }
 
     pub fn set_connection_manager(&self, connection_manager: Arc<ConnectionManager>) {
@@ -614,6 +639,11 @@ impl FlowContext {
         self.mining_rule_engine.should_mine(sink_daa_score_and_timestamp)
     }
 
+    /// Notifies the mining rule engine that the peer at `peer` presented a chain conflicting with the local finality point
+    pub fn report_finality_conflict(&self, peer: IpAddr) {
+        self.mining_rule_engine.report_finality_conflict(peer);
+    }
+
     /// Notifies that the UTXO set was reset due to pruning point change via IBD.
     pub fn on_pruning_point_utxoset_override(&self) {
         // Notifications from the flow context might be ignored if the inner channel is already closing
@@ -626,7 +656,9 @@ impl FlowContext {
         // TODO: call a handler function or a predefined registered service
     }
 
-    /// Adds the rpc-submitted transaction to the mempool and propagates it to peers.
+    /// Adds the rpc-submitted transaction to the mempool and propagates it to peers. If Dandelion relay is enabled,
+    /// the transaction goes through a stem phase first and only enters the mempool when diffused. Orphans cannot
+    /// be stemmed so, when allowed, they are added to the mempool right away.
     ///
     /// Transactions submitted through rpc are considered high priority. This definition does not affect the tx selection algorithm
     /// but only changes how we manage the lifetime of the tx. A high-priority tx does not expire and is repeatedly rebroadcasted to
@@ -637,6 +669,12 @@ impl FlowContext {
         transaction: Transaction,
         orphan: Orphan,
     ) -> Result<(), ProtocolError> {
+        if self.dandelion.is_some() {
+            match self.stem_transaction(consensus, transaction.clone(), Priority::High, RbfPolicy::Forbidden, None).await {
+                Err(MiningManagerError::MempoolError(MempoolRuleError::RejectDisallowedOrphan(_))) if orphan == Orphan::Allowed => {}
+                result => return Ok(result?),
+            }
+        }
         let transaction_insertion = self
             .mining_manager()
             .clone()
@@ -679,6 +717,102 @@ impl FlowContext {
         ))
     }
 
+    /// Relays a transaction in Dandelion++ stem phase. The transaction is validated against the mempool and kept
+    /// in the stem pool, out of sight of mempool queries, then either forwarded to a single stem relay and embargoed,
+    /// or diffused to all peers. `source` is the peer the transaction was received from, or `None` for a locally
+    /// submitted transaction.
+    ///
+    /// If stem relay is disabled, the transaction is added to the mempool and diffused right away.
+    pub async fn stem_transaction(
+        &self,
+        consensus: &ConsensusProxy,
+        transaction: Transaction,
+        priority: Priority,
+        rbf_policy: RbfPolicy,
+        source: Option<PeerKey>,
+    ) -> MiningManagerResult<()> {
+        let Some(dandelion) = self.dandelion.as_ref() else {
+            let transaction_insertion = self
+                .mining_manager()
+                .clone()
+                .validate_and_insert_transaction(consensus, transaction, priority, Orphan::Forbidden, rbf_policy)
+                .await?;
+            self.broadcast_transactions(transaction_insertion.accepted.iter().map(|x| x.id()), false).await;
+            return Ok(());
+        };
+        let transaction =
+            self.mining_manager().clone().validate_and_stem_transaction(consensus, transaction, priority, rbf_policy).await?;
+        let candidates = self
+            .hub
+            .active_peers()
+            .into_iter()
+            .filter(|peer| {
+                let properties = peer.properties();
+                peer.is_outbound() && !properties.disable_relay_tx && properties.services & SERVICE_STEM_RELAY != 0
+            })
+            .map(|peer| peer.key())
+            .collect_vec();
+        let route = {
+            let now = Instant::now();
+            let mut dandelion = dandelion.lock();
+            let route = dandelion.route(source, &candidates, now);
+            if let StemRoute::Stem(_) = route {
+                dandelion.embargo(transaction.id(), now);
+            }
+            route
+        };
+        match route {
+            StemRoute::Stem(relay) => {
+                let msg =
+                    make_message!(Payload::StemTransaction, StemTransactionMessage { transaction: Some((&*transaction).into()) });
+                if !matches!(self.hub.send(relay, msg).await, Ok(true)) {
+                    // The stem relay is gone, fall back to diffusion
+                    self.fluff_stem_transactions(consensus, vec![transaction.id()]).await;
+                }
+            }
+            StemRoute::Fluff => self.fluff_stem_transactions(consensus, vec![transaction.id()]).await,
+        }
+        Ok(())
+    }
+
+    /// Moves stem transactions, along with their stem ancestors, into the mempool and diffuses them to all peers
+    async fn fluff_stem_transactions(&self, consensus: &ConsensusProxy, transaction_ids: Vec<TransactionId>) {
+        let accepted = self.mining_manager().clone().fluff_stem_transactions(consensus, transaction_ids).await;
+        if let Some(dandelion) = self.dandelion.as_ref() {
+            let mut dandelion = dandelion.lock();
+            accepted.iter().for_each(|transaction| {
+                dandelion.lift_embargo(&transaction.id());
+            });
+        }
+        if !accepted.is_empty() {
+            self.broadcast_transactions(accepted.iter().map(|x| x.id()), false).await;
+        }
+    }
+
+    /// Notifies that the given transactions were announced by a peer, hence entered the fluff phase.
+    /// Embargoed transactions among them are moved into the mempool and diffused by this node as well
+    pub async fn on_fluffed_transactions(&self, consensus: &ConsensusProxy, transaction_ids: &[TransactionId]) {
+        let Some(dandelion) = self.dandelion.as_ref() else { return };
+        let lifted = {
+            let mut dandelion = dandelion.lock();
+            transaction_ids.iter().filter(|id| dandelion.lift_embargo(id)).copied().collect_vec()
+        };
+        if !lifted.is_empty() {
+            self.fluff_stem_transactions(consensus, lifted).await;
+        }
+    }
+
+    /// Diffuses the stem transactions which were not observed in fluff phase before their embargo expired
+    async fn fluff_expired_embargoes(&self) {
+        let Some(dandelion) = self.dandelion.as_ref() else { return };
+        let expired = dandelion.lock().expired_embargoes(Instant::now());
+        if !expired.is_empty() {
+            debug!("Dandelion embargo expired for {} transaction(s), diffusing", expired.len());
+            let session = self.consensus().unguarded_session();
+            self.fluff_stem_transactions(&session, expired).await;
+        }
+    }
+
     /// Returns true if the time has come for running the task cleaning mempool transactions.
     async fn should_run_mempool_scanning_task(&self) -> bool {
         self.transactions_spread.write().await.should_run_mempool_scanning_task()
@@ -702,8 +836,19 @@ impl FlowContext {
     ///
     /// The broadcast itself may happen only during a subsequent call to this function since it is done at most
     /// after a predefined interval or when the queue length is larger than the Inv message capacity.
+    ///
+    /// Transactions in Dandelion stem phase are never announced.
     pub async fn broadcast_transactions<I: IntoIterator<Item = TransactionId>>(&self, transaction_ids: I, should_throttle: bool) {
-        self.transactions_spread.write().await.broadcast_transactions(transaction_ids, should_throttle).await
+        match self.dandelion.as_ref() {
+            Some(dandelion) => {
+                let transaction_ids = {
+                    let dandelion = dandelion.lock();
+                    transaction_ids.into_iter().filter(|id| !dandelion.is_embargoed(id)).collect_vec()
+                };
+                self.transactions_spread.write().await.broadcast_transactions(transaction_ids, should_throttle).await
+            }
+            None => self.transactions_spread.write().await.broadcast_transactions(transaction_ids, should_throttle).await,
+        }
     }
 }
 
@@ -724,6 +869,10 @@ impl ConnectionInitializer for FlowContext {
         // Subnets are not currently supported
         let mut self_version_message = Version::new(local_address, self.node_id, network_name.clone(), None, PROTOCOL_VERSION);
         self_version_message.add_user_agent(name(), version(), &self.config.user_agent_comments);
+        self_version_message.services = self.transport_security.services();
+        if self.dandelion.is_some() {
+            self_version_message.services |= SERVICE_STEM_RELAY;
+        }
         // TODO: get number of live services
         // TODO: disable_relay_tx from config/cmd
 
@@ -747,6 +896,16 @@ impl ConnectionInitializer for FlowContext {
             return Err(ProtocolError::WrongNetwork(network_name, peer_version.network));
         }
 
+        // Encrypt the connection if both peers agree to. The exchanged version messages are bound to the encryption handshake
+        match self.transport_security.should_encrypt(peer_version.services) {
+            Some(true) => {
+                let peer_key = handshake.encrypt(&self.transport_security).await?;
+                debug!("P2P, connection with peer {} is encrypted, peer static key: {}", router, peer_key);
+            }
+            Some(false) => {}
+            None => return Err(ProtocolError::EncryptionRequired),
+        }
+
         debug!("protocol versions - self: {}, peer: {}", PROTOCOL_VERSION, peer_version.protocol_version);
 
         // Register all flows according to version
@@ -760,6 +919,7 @@ impl ConnectionInitializer for FlowContext {
         // Build and register the peer properties
         let peer_properties = Arc::new(PeerProperties {
             user_agent: peer_version.user_agent.to_owned(),
+            services: peer_version.services,
             advertised_protocol_version: peer_version.protocol_version,
             protocol_version: applied_protocol_version,
             disable_relay_tx: peer_version.disable_relay_tx,
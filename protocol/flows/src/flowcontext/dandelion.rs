@@ -0,0 +1,211 @@
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_p2p_lib::PeerKey;
+use rand::{seq::SliceRandom, Rng};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Probability for a node to act as a diffuser (fluff) for all stem transactions it receives during an epoch
+const FLUFF_PROBABILITY: f64 = 0.1;
+
+/// Duration of an epoch during which stem routes are kept stable
+const EPOCH_DURATION: Duration = Duration::from_secs(600);
+
+/// Number of outbound peers selected as stem relays per epoch
+const NUM_STEM_RELAYS: usize = 2;
+
+/// Minimal time a stem transaction is embargoed before the node diffuses it by itself
+const EMBARGO_BASE: Duration = Duration::from_secs(10);
+
+/// Mean of the random exponential delay added on top of [`EMBARGO_BASE`]. The randomness ensures that
+/// the first node of a black-holed stem to fluff is not necessarily the closest one to the source
+const EMBARGO_MEAN_DELAY: Duration = Duration::from_secs(20);
+
+/// Interval between consecutive checks for expired embargoes
+pub const EMBARGO_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The relay decision for a stem transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StemRoute {
+    /// Forward the transaction to the given peer only
+    Stem(PeerKey),
+    /// Diffuse the transaction to all peers
+    Fluff,
+}
+
+/// Dandelion++ relay state: stem routes of the current epoch and embargo timers of stem transactions.
+///
+/// See <https://arxiv.org/abs/1805.11060>
+pub struct DandelionRelay {
+    epoch_start: Option<Instant>,
+
+    /// Indicates whether the node diffuses all stem transactions it receives during this epoch
+    is_fluff_epoch: bool,
+
+    /// The outbound peers stem transactions are forwarded to during this epoch
+    relays: Vec<PeerKey>,
+
+    /// Maps stem sources to one of the relays, so that every source has a single stable route
+    routes: HashMap<PeerKey, PeerKey>,
+
+    /// Deadlines after which embargoed transactions are diffused by this node
+    embargoes: HashMap<TransactionId, Instant>,
+}
+
+impl DandelionRelay {
+    pub fn new() -> Self {
+        Self { epoch_start: None, is_fluff_epoch: false, relays: Vec::new(), routes: HashMap::new(), embargoes: HashMap::new() }
+    }
+
+    /// Starts a new epoch if the current one has expired or if any of its relays has disconnected.
+    /// `candidates` are the currently connected outbound peers supporting stem relay
+    fn refresh_epoch(&mut self, candidates: &[PeerKey], now: Instant) {
+        let expired = self.epoch_start.is_none_or(|start| now >= start + EPOCH_DURATION);
+        let lost_relay = self.relays.iter().any(|relay| !candidates.contains(relay));
+        let missing_relays = self.relays.len() < NUM_STEM_RELAYS.min(candidates.len());
+        if !expired && !lost_relay && !missing_relays {
+            return;
+        }
+        let rng = &mut rand::thread_rng();
+        self.epoch_start = Some(now);
+        self.is_fluff_epoch = rng.gen_bool(FLUFF_PROBABILITY);
+        self.relays = candidates.choose_multiple(rng, NUM_STEM_RELAYS).copied().collect();
+        self.routes.clear();
+    }
+
+    /// Decides where to relay a stem transaction. `source` is the peer the transaction was received from,
+    /// or `None` for locally submitted transactions. Local transactions are always stemmed (when possible)
+    /// so that the node does not give itself away by being the first to fluff them.
+    pub fn route(&mut self, source: Option<PeerKey>, candidates: &[PeerKey], now: Instant) -> StemRoute {
+        self.refresh_epoch(candidates, now);
+        if self.relays.is_empty() {
+            // Fallback: no peer can take the stem phase, hence we diffuse right away
+            return StemRoute::Fluff;
+        }
+        match source {
+            None => StemRoute::Stem(self.relays[0]),
+            Some(_) if self.is_fluff_epoch => StemRoute::Fluff,
+            Some(source) => {
+                let relays = &self.relays;
+                // Avoid routing a stem transaction straight back to its source
+                let route = *self.routes.entry(source).or_insert_with(|| {
+                    let others = relays.iter().copied().filter(|&relay| relay != source).collect::<Vec<_>>();
+                    others.choose(&mut rand::thread_rng()).copied().unwrap_or(source)
+                });
+                if route == source {
+                    StemRoute::Fluff
+                } else {
+                    StemRoute::Stem(route)
+                }
+            }
+        }
+    }
+
+    /// Embargoes a stem transaction. If the transaction is not observed in fluff phase before the embargo
+    /// expires, the node diffuses it by itself
+    pub fn embargo(&mut self, transaction_id: TransactionId, now: Instant) {
+        let delay = EMBARGO_MEAN_DELAY.mul_f64(-(1.0 - rand::thread_rng().gen::<f64>()).ln());
+        self.embargoes.entry(transaction_id).or_insert(now + EMBARGO_BASE + delay);
+    }
+
+    pub fn is_embargoed(&self, transaction_id: &TransactionId) -> bool {
+        self.embargoes.contains_key(transaction_id)
+    }
+
+    /// Lifts the embargo of a transaction. Returns true if the transaction was embargoed
+    pub fn lift_embargo(&mut self, transaction_id: &TransactionId) -> bool {
+        self.embargoes.remove(transaction_id).is_some()
+    }
+
+    /// Removes and returns all transactions whose embargo has expired
+    pub fn expired_embargoes(&mut self, now: Instant) -> Vec<TransactionId> {
+        let expired = self.embargoes.iter().filter(|(_, &deadline)| deadline <= now).map(|(&id, _)| id).collect::<Vec<_>>();
+        expired.iter().for_each(|id| {
+            self.embargoes.remove(id);
+        });
+        expired
+    }
+}
+
+impl Default for DandelionRelay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_utils::networking::IpAddress;
+    use std::net::{IpAddr, Ipv4Addr};
+    use uuid::Uuid;
+
+    fn peer(i: u8) -> PeerKey {
+        PeerKey::new(Uuid::new_v4().into(), IpAddress::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, i))))
+    }
+
+    #[test]
+    fn test_stem_routes() {
+        let now = Instant::now();
+        let mut relay = DandelionRelay::new();
+
+        // No candidates means falling back to fluff
+        assert_eq!(relay.route(None, &[], now), StemRoute::Fluff);
+
+        let candidates = (0..5).map(peer).collect::<Vec<_>>();
+        let StemRoute::Stem(local_route) = relay.route(None, &candidates, now) else { panic!("local transactions should be stemmed") };
+        assert!(candidates.contains(&local_route));
+        // Routes are stable during the epoch
+        assert_eq!(relay.route(None, &candidates, now + Duration::from_secs(1)), StemRoute::Stem(local_route));
+
+        let source = peer(100);
+        let first = relay.route(Some(source), &candidates, now);
+        assert_eq!(relay.route(Some(source), &candidates, now), first);
+        match first {
+            StemRoute::Stem(route) => assert!(relay.relays.contains(&route)),
+            StemRoute::Fluff => assert!(relay.is_fluff_epoch),
+        }
+
+        // Losing a relay starts a new epoch
+        let remaining = candidates.iter().filter(|&&p| p != local_route).copied().collect::<Vec<_>>();
+        let StemRoute::Stem(new_route) = relay.route(None, &remaining, now) else { panic!("local transactions should be stemmed") };
+        assert_ne!(new_route, local_route);
+
+        // Epoch expiration selects relays again
+        relay.route(None, &remaining, now + EPOCH_DURATION);
+        assert_eq!(relay.epoch_start, Some(now + EPOCH_DURATION));
+    }
+
+    #[test]
+    fn test_no_route_back_to_source() {
+        let now = Instant::now();
+        let mut relay = DandelionRelay::new();
+        let only = peer(1);
+        relay.is_fluff_epoch = false;
+        relay.route(None, &[only], now);
+        relay.is_fluff_epoch = false;
+        assert_eq!(relay.route(Some(only), &[only], now), StemRoute::Fluff);
+    }
+
+    #[test]
+    fn test_embargoes() {
+        let now = Instant::now();
+        let mut relay = DandelionRelay::new();
+        let (id1, id2) = (TransactionId::from_u64_word(1), TransactionId::from_u64_word(2));
+        relay.embargo(id1, now);
+        relay.embargo(id2, now);
+        assert!(relay.is_embargoed(&id1) && relay.is_embargoed(&id2));
+
+        // Nothing expires before the base embargo
+        assert!(relay.expired_embargoes(now + EMBARGO_BASE - Duration::from_millis(1)).is_empty());
+
+        assert!(relay.lift_embargo(&id1));
+        assert!(!relay.lift_embargo(&id1));
+        assert!(!relay.is_embargoed(&id1));
+
+        // The exponential delay is unbounded in theory, so use a very remote time
+        assert_eq!(relay.expired_embargoes(now + Duration::from_secs(100_000)), vec![id2]);
+        assert!(!relay.is_embargoed(&id2));
+    }
+}
@@ -288,6 +288,7 @@ impl IbdFlow {
             //
             // TODO (relaxed): consider performing additional actions on finality conflicts in addition
             // to disconnecting from the peer (e.g., banning, rpc notification)
+            self.ctx.report_finality_conflict(self.router.net_address().ip());
             return Err(ProtocolError::Other("peer is in a finality conflict with the local pruning point"));
         }
 
@@ -413,6 +414,7 @@ impl IbdFlow {
         // Check if past pruning points violate finality of current consensus
         if self.ctx.consensus().session().await.async_are_pruning_points_violating_finality(pruning_points.clone()).await {
             // TODO (relaxed): consider performing additional actions on finality conflicts in addition to disconnecting from the peer (e.g., banning, rpc notification)
+            self.ctx.report_finality_conflict(self.router.net_address().ip());
             return Err(ProtocolError::Other("pruning points are violating finality"));
         }
 
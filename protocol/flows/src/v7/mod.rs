@@ -11,7 +11,10 @@ use crate::v7::{
     request_pp_proof::RequestPruningPointProofFlow,
     request_pruning_point_and_anticone::PruningPointAndItsAnticoneRequestsFlow,
     request_pruning_point_utxo_set::RequestPruningPointUtxoSetFlow,
-    txrelay::flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+    txrelay::{
+        flow::{RelayTransactionsFlow, RequestTransactionsFlow},
+        stem::ReceiveStemTransactionsFlow,
+    },
 };
 use crate::{flow_context::FlowContext, flow_trait::Flow};
 use kaspa_p2p_lib::{convert::header::HeaderFormat, KaspadMessagePayloadType, Router, SharedIncomingRoute};
@@ -131,6 +134,11 @@ pub fn register(ctx: FlowContext, router: Arc<Router>) -> Vec<Box<dyn Flow>> {
             router.clone(),
             router.subscribe(vec![KaspadMessagePayloadType::RequestTransactions]),
         )),
+        Box::new(ReceiveStemTransactionsFlow::new(
+            ctx.clone(),
+            router.clone(),
+            router.subscribe(vec![KaspadMessagePayloadType::StemTransaction]),
+        )),
         Box::new(ReceiveAddressesFlow::new(ctx.clone(), router.clone(), router.subscribe(vec![KaspadMessagePayloadType::Addresses]))),
         Box::new(SendAddressesFlow::new(
             ctx.clone(),
@@ -120,6 +120,9 @@ impl RelayTransactionsFlow {
                 continue;
             }
 
+            // Stem transactions announced by other peers have entered the fluff phase
+            self.ctx.on_fluffed_transactions(&session, &inv).await;
+
             let requests = self.request_transactions(inv, throttling_state.should_throttle, &throttling_state.curr_snapshot).await?;
             self.receive_transactions(session, requests, throttling_state.should_throttle).await?;
         }
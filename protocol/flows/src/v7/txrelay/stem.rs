@@ -0,0 +1,62 @@
+use crate::{flow_context::FlowContext, flow_trait::Flow};
+use kaspa_consensus_core::tx::Transaction;
+use kaspa_mining::{
+    errors::MiningManagerError,
+    mempool::{
+        errors::RuleError,
+        tx::{Priority, RbfPolicy},
+    },
+};
+use kaspa_p2p_lib::{common::ProtocolError, dequeue, pb::kaspad_message::Payload, IncomingRoute, Router};
+use std::sync::Arc;
+
+/// Flow receiving transactions relayed in Dandelion++ stem phase. Received transactions are kept in the
+/// stem pool, out of the mempool, and are then either forwarded along the stem or diffused, according
+/// to the local Dandelion state (see `FlowContext::stem_transaction`).
+pub struct ReceiveStemTransactionsFlow {
+    ctx: FlowContext,
+    router: Arc<Router>,
+    incoming_route: IncomingRoute,
+}
+
+#[async_trait::async_trait]
+impl Flow for ReceiveStemTransactionsFlow {
+    fn router(&self) -> Option<Arc<Router>> {
+        Some(self.router.clone())
+    }
+
+    async fn start(&mut self) -> Result<(), ProtocolError> {
+        self.start_impl().await
+    }
+}
+
+impl ReceiveStemTransactionsFlow {
+    pub fn new(ctx: FlowContext, router: Arc<Router>, incoming_route: IncomingRoute) -> Self {
+        Self { ctx, router, incoming_route }
+    }
+
+    async fn start_impl(&mut self) -> Result<(), ProtocolError> {
+        loop {
+            let transaction: Transaction = dequeue!(self.incoming_route, Payload::StemTransaction)?.try_into()?;
+
+            let session = self.ctx.consensus().unguarded_session();
+
+            // Transaction relay is disabled if the node is out of sync
+            if !self.ctx.is_nearly_synced(&session).await {
+                continue;
+            }
+
+            // Stem transactions are never orphans since the stem source had them fully validated
+            let stem_result =
+                self.ctx.stem_transaction(&session, transaction, Priority::Low, RbfPolicy::Allowed, Some(self.router.key())).await;
+
+            match stem_result {
+                Ok(()) => {}
+                Err(MiningManagerError::MempoolError(RuleError::RejectInvalid(transaction_id))) => {
+                    return Err(ProtocolError::MisbehavingPeer(format!("rejected invalid stem transaction {}", transaction_id)));
+                }
+                Err(_) => {}
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    net::IpAddr,
     sync::{atomic::AtomicBool, Arc},
     time::{Duration, Instant},
 };
@@ -21,7 +22,14 @@ use kaspa_core::{
 };
 use kaspa_p2p_lib::Hub;
 
-use crate::rules::{mining_rule::MiningRule, sync_rate_rule::SyncRateRule, ExtraData};
+use crate::rules::{
+    finality_conflict_rule::FinalityConflictRule,
+    min_peers_rule::MinPeersRule,
+    mining_rule::{MiningRule, MiningRuleKind},
+    stale_sink_rule::StaleSinkRule,
+    sync_rate_rule::SyncRateRule,
+    ExtraData, MiningRulesConfig,
+};
 
 const RULE_ENGINE: &str = "mining-rule-engine";
 pub const SNAPSHOT_INTERVAL: u64 = 10;
@@ -31,12 +39,60 @@ pub struct MiningRuleEngine {
     config: Arc<Config>,
     processing_counters: Arc<ProcessingCounters>,
     tick_service: Arc<TickService>,
-    // Sync Rate Rule: Allow mining if sync rate is below threshold AND finality point is "recent" (defined below)
-    use_sync_rate_rule: Arc<AtomicBool>,
     consensus_manager: Arc<ConsensusManager>,
     hub: Hub,
     mining_rules: Arc<MiningRules>,
+    /// Enabled rules, checked periodically and taken into account when deciding whether to mine
     rules: Vec<Arc<dyn MiningRule>>,
+    /// Disabled rules, only kept for status reports
+    disabled_rules: Vec<Arc<dyn MiningRule>>,
+    finality_conflict_rule: Arc<FinalityConflictRule>,
+}
+
+/// State of a mining rule as reported by [`MiningRuleEngine::mining_status`]
+#[derive(Clone, Debug)]
+pub struct MiningRuleStatus {
+    pub name: &'static str,
+    pub kind: MiningRuleKind,
+    pub enabled: bool,
+    pub triggered: bool,
+    /// Explanation of the outcome of the last check of the rule
+    pub status: String,
+}
+
+/// Whether mining is currently allowed, why, and the state of every mining rule
+#[derive(Clone, Debug)]
+pub struct MiningStatus {
+    pub is_mining_allowed: bool,
+    pub reason: String,
+    pub is_nearly_synced: bool,
+    pub has_sufficient_peer_connectivity: bool,
+    pub rules: Vec<MiningRuleStatus>,
+}
+
+/// Outcome of the mining decision, by order of precedence
+enum MiningDecision<'a> {
+    NoPeerConnectivity,
+    BlockedByRule(&'a dyn MiningRule),
+    NearlySynced,
+    AllowedByRule(&'a dyn MiningRule),
+    NotSynced,
+}
+
+impl MiningDecision<'_> {
+    fn is_mining_allowed(&self) -> bool {
+        matches!(self, MiningDecision::NearlySynced | MiningDecision::AllowedByRule(_))
+    }
+
+    fn reason(&self) -> String {
+        match self {
+            MiningDecision::NoPeerConnectivity => "node is not connected to any peer".to_owned(),
+            MiningDecision::BlockedByRule(rule) => format!("blocked by rule {}: {}", rule.name(), rule.status()),
+            MiningDecision::NearlySynced => "node is nearly synced".to_owned(),
+            MiningDecision::AllowedByRule(rule) => format!("allowed by rule {}: {}", rule.name(), rule.status()),
+            MiningDecision::NotSynced => "node is not nearly synced".to_owned(),
+        }
+    }
 }
 
 impl MiningRuleEngine {
@@ -67,6 +123,7 @@ impl MiningRuleEngine {
 
                 let finality_point = session.async_finality_point().await;
                 let finality_point_timestamp = session.async_get_header(finality_point).await.unwrap().timestamp;
+                let sink_timestamp = session.async_get_sink_daa_score_timestamp().await.timestamp;
 
                 let extra_data = ExtraData {
                     finality_point_timestamp,
@@ -74,6 +131,8 @@ impl MiningRuleEngine {
                     has_sufficient_peer_connectivity: self.has_sufficient_peer_connectivity(),
                     finality_duration: self.config.finality_duration_in_milliseconds(),
                     elapsed_time,
+                    active_peers: self.hub.active_peers_len(),
+                    sink_timestamp,
                 };
 
                 trace!("Current Mining Rule: {:?}", self.mining_rules);
@@ -96,21 +155,82 @@ impl MiningRuleEngine {
         tick_service: Arc<TickService>,
         hub: Hub,
         mining_rules: Arc<MiningRules>,
+        rules_config: MiningRulesConfig,
     ) -> Self {
+        // Sync Rate Rule: Allow mining if sync rate is below threshold AND finality point is "recent" (defined below)
         let use_sync_rate_rule = Arc::new(AtomicBool::new(false));
-        let rules: Vec<Arc<dyn MiningRule + 'static>> = vec![Arc::new(SyncRateRule::new(use_sync_rate_rule.clone()))];
+        let max_sink_age = rules_config.max_sink_age.unwrap_or(config.finality_duration_in_milliseconds());
+        let finality_conflict_rule = Arc::new(FinalityConflictRule::new(rules_config.max_finality_conflict_duration));
+        let all_rules: Vec<Arc<dyn MiningRule + 'static>> = vec![
+            Arc::new(SyncRateRule::new(use_sync_rate_rule)),
+            Arc::new(MinPeersRule::new(rules_config.min_peers)),
+            Arc::new(StaleSinkRule::new(max_sink_age)),
+            finality_conflict_rule.clone(),
+        ];
+        let (rules, disabled_rules) = all_rules.into_iter().partition(|rule| rules_config.is_enabled(rule.name()));
 
-        Self { consensus_manager, config, processing_counters, tick_service, hub, use_sync_rate_rule, mining_rules, rules }
+        Self {
+            consensus_manager,
+            config,
+            processing_counters,
+            tick_service,
+            hub,
+            mining_rules,
+            rules,
+            disabled_rules,
+            finality_conflict_rule,
+        }
     }
 
     pub fn should_mine(&self, sink_daa_score_timestamp: DaaScoreTimestamp) -> bool {
-        if !self.has_sufficient_peer_connectivity() {
-            return false;
+        self.decide(sink_daa_score_timestamp).is_mining_allowed()
+    }
+
+    /// Returns whether mining is allowed given the sink, along with the reason and the state of all the rules
+    pub fn mining_status(&self, sink_daa_score_timestamp: DaaScoreTimestamp) -> MiningStatus {
+        let is_nearly_synced = self.is_nearly_synced(sink_daa_score_timestamp.clone());
+        let decision = self.decide(sink_daa_score_timestamp);
+        let rule_status = |rule: &Arc<dyn MiningRule>, enabled: bool| MiningRuleStatus {
+            name: rule.name(),
+            kind: rule.kind(),
+            enabled,
+            triggered: enabled && rule.is_triggered(),
+            status: if enabled { rule.status() } else { "disabled".to_owned() },
+        };
+        MiningStatus {
+            is_mining_allowed: decision.is_mining_allowed(),
+            reason: decision.reason(),
+            is_nearly_synced,
+            has_sufficient_peer_connectivity: self.has_sufficient_peer_connectivity(),
+            rules: self
+                .rules
+                .iter()
+                .map(|rule| rule_status(rule, true))
+                .chain(self.disabled_rules.iter().map(|rule| rule_status(rule, false)))
+                .collect(),
         }
+    }
 
-        let is_nearly_synced = self.is_nearly_synced(sink_daa_score_timestamp);
+    /// Records that the peer at `peer` presented a chain conflicting with the local finality point
+    pub fn report_finality_conflict(&self, peer: IpAddr) {
+        self.finality_conflict_rule.report_conflict(peer);
+    }
 
-        is_nearly_synced || self.use_sync_rate_rule.load(std::sync::atomic::Ordering::Relaxed)
+    fn decide(&self, sink_daa_score_timestamp: DaaScoreTimestamp) -> MiningDecision<'_> {
+        if !self.has_sufficient_peer_connectivity() {
+            return MiningDecision::NoPeerConnectivity;
+        }
+        let triggered = |kind| self.rules.iter().find(|rule| rule.kind() == kind && rule.is_triggered()).map(|rule| rule.as_ref());
+        if let Some(rule) = triggered(MiningRuleKind::Restrictive) {
+            return MiningDecision::BlockedByRule(rule);
+        }
+        if self.is_nearly_synced(sink_daa_score_timestamp) {
+            return MiningDecision::NearlySynced;
+        }
+        match triggered(MiningRuleKind::Permissive) {
+            Some(rule) => MiningDecision::AllowedByRule(rule),
+            None => MiningDecision::NotSynced,
+        }
     }
 
     /// In non-mining contexts, we consider the node synced if the sink is recent and it is connected
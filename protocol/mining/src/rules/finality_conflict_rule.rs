@@ -0,0 +1,171 @@
+use std::{collections::HashSet, net::IpAddr, sync::Mutex};
+
+use kaspa_consensus_core::api::counters::ProcessingCountersSnapshot;
+use kaspa_core::{time::unix_now, warn};
+
+use super::{
+    mining_rule::{MiningRule, MiningRuleKind, RuleState},
+    ExtraData,
+};
+
+pub const FINALITY_CONFLICT_RULE: &str = "finality-conflict";
+
+/// Time, in milliseconds, after which a finality conflict episode is considered over if no new conflict was reported
+const FINALITY_CONFLICT_EXPIRY: u64 = 5 * 60 * 1000;
+
+/// Minimum number of distinct peer addresses which must report conflicts during an episode for the rule to trigger,
+/// so that a single peer cannot block mining by repeatedly presenting a conflicting chain
+const MIN_FINALITY_CONFLICT_PEERS: usize = 3;
+
+/// A period during which finality conflicts keep being reported
+struct ConflictEpisode {
+    /// Timestamp of the first conflict
+    first: u64,
+    /// Timestamp of the last conflict
+    last: u64,
+    /// Addresses of the peers that presented a conflicting chain
+    peers: HashSet<IpAddr>,
+}
+
+/// FinalityConflictRule
+/// Block mining when peers keep presenting chains conflicting with the local finality point. A
+/// lasting conflict suggests the node might be on a minority fork, in which case mining on it would
+/// waste work and deepen the split.
+///
+/// Trigger: Finality conflicts were reported continuously for longer than the configured duration, by
+/// at least [`MIN_FINALITY_CONFLICT_PEERS`] distinct peer addresses
+/// Recovery: No finality conflict was reported for [`FINALITY_CONFLICT_EXPIRY`]
+pub struct FinalityConflictRule {
+    /// Minimum duration in milliseconds of a conflict episode before the rule triggers
+    max_conflict_duration: u64,
+    /// Ongoing conflict episode, if any
+    episode: Mutex<Option<ConflictEpisode>>,
+    state: RuleState,
+}
+
+impl FinalityConflictRule {
+    pub fn new(max_conflict_duration: u64) -> Self {
+        Self { max_conflict_duration, episode: Mutex::new(None), state: RuleState::new("no finality conflict") }
+    }
+
+    /// Records that the peer at `peer` presented a chain conflicting with the local finality point
+    pub fn report_conflict(&self, peer: IpAddr) {
+        self.report_conflict_at(peer, unix_now());
+    }
+
+    fn report_conflict_at(&self, peer: IpAddr, now: u64) {
+        let mut episode = self.episode.lock().unwrap();
+        let episode = episode.get_or_insert_with(|| ConflictEpisode { first: now, last: now, peers: HashSet::new() });
+        episode.last = now;
+        episode.peers.insert(peer);
+    }
+
+    fn check(&self, now: u64) {
+        let mut episode = self.episode.lock().unwrap();
+        if episode.as_ref().is_some_and(|episode| now.saturating_sub(episode.last) > FINALITY_CONFLICT_EXPIRY) {
+            *episode = None;
+        }
+        let (is_conflicting, status) = match episode.as_ref() {
+            Some(episode) => {
+                let duration = now.saturating_sub(episode.first);
+                let is_conflicting = duration >= self.max_conflict_duration && episode.peers.len() >= MIN_FINALITY_CONFLICT_PEERS;
+                let status = format!(
+                    "finality conflicts reported by {} peers for {}s, maximum is {}s",
+                    episode.peers.len(),
+                    duration / 1000,
+                    self.max_conflict_duration / 1000
+                );
+                (is_conflicting, status)
+            }
+            None => (false, "no finality conflict".to_owned()),
+        };
+
+        if self.state.update(is_conflicting, status) {
+            if is_conflicting {
+                warn!("Finality conflicts reported for over {}s", self.max_conflict_duration / 1000);
+            } else {
+                warn!("Finality conflict recovered");
+            }
+        }
+    }
+}
+
+impl MiningRule for FinalityConflictRule {
+    fn check_rule(&self, _delta: &ProcessingCountersSnapshot, _extra_data: &ExtraData) {
+        self.check(unix_now());
+    }
+
+    fn name(&self) -> &'static str {
+        FINALITY_CONFLICT_RULE
+    }
+
+    fn kind(&self) -> MiningRuleKind {
+        MiningRuleKind::Restrictive
+    }
+
+    fn is_triggered(&self) -> bool {
+        self.state.is_triggered()
+    }
+
+    fn status(&self) -> String {
+        self.state.status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn peer(i: u8) -> IpAddr {
+        IpAddr::V4(Ipv4Addr::new(10, 0, 0, i))
+    }
+
+    #[test]
+    fn test_finality_conflict_rule() {
+        let rule = FinalityConflictRule::new(60_000);
+        let start = unix_now();
+
+        rule.check(start);
+        assert!(!rule.is_triggered());
+
+        // Conflicts reported regularly by distinct peers, but for less than the maximum duration
+        for (i, offset) in (0..60_000).step_by(10_000).enumerate() {
+            rule.report_conflict_at(peer(i as u8), start + offset);
+            rule.check(start + offset);
+            assert!(!rule.is_triggered(), "Expected rule to not trigger on a short conflict");
+        }
+
+        rule.report_conflict_at(peer(0), start + 60_000);
+        rule.check(start + 60_000);
+        assert!(rule.is_triggered(), "Expected rule to trigger on a long lasting conflict");
+
+        // The episode is still ongoing until no conflict was reported for the expiry period
+        rule.check(start + 60_000 + FINALITY_CONFLICT_EXPIRY);
+        assert!(rule.is_triggered());
+        rule.check(start + 60_001 + FINALITY_CONFLICT_EXPIRY);
+        assert!(!rule.is_triggered(), "Expected rule to recover once conflicts stop being reported");
+
+        // A new conflict starts a new episode
+        rule.report_conflict_at(peer(0), start + 60_002 + FINALITY_CONFLICT_EXPIRY);
+        rule.check(start + 60_002 + FINALITY_CONFLICT_EXPIRY);
+        assert!(!rule.is_triggered());
+    }
+
+    #[test]
+    fn test_finality_conflict_rule_requires_distinct_peers() {
+        let rule = FinalityConflictRule::new(60_000);
+        let start = unix_now();
+
+        // A long lasting conflict reported by too few peers does not block mining
+        for offset in (0..=120_000).step_by(10_000) {
+            rule.report_conflict_at(peer((offset / 10_000 % 2) as u8), start + offset);
+            rule.check(start + offset);
+            assert!(!rule.is_triggered(), "Expected rule to not trigger on conflicts reported by too few peers");
+        }
+
+        rule.report_conflict_at(peer(2), start + 130_000);
+        rule.check(start + 130_000);
+        assert!(rule.is_triggered(), "Expected rule to trigger once enough distinct peers reported conflicts");
+    }
+}
@@ -0,0 +1,92 @@
+use kaspa_consensus_core::api::counters::ProcessingCountersSnapshot;
+use kaspa_core::warn;
+
+use super::{
+    mining_rule::{MiningRule, MiningRuleKind, RuleState},
+    ExtraData,
+};
+
+pub const MIN_PEERS_RULE: &str = "min-peers";
+
+/// MinPeersRule
+/// Block mining when the node is connected to too few peers, its view of the DAG being then
+/// more likely to be partial or isolated.
+///
+/// Trigger: The number of active peers is below the configured minimum
+/// Recovery: The number of active peers reaches the minimum
+pub struct MinPeersRule {
+    min_peers: usize,
+    state: RuleState,
+}
+
+impl MinPeersRule {
+    pub fn new(min_peers: usize) -> Self {
+        Self { min_peers, state: RuleState::new("not checked yet") }
+    }
+}
+
+impl MiningRule for MinPeersRule {
+    fn check_rule(&self, _delta: &ProcessingCountersSnapshot, extra_data: &ExtraData) {
+        let is_below = extra_data.active_peers < self.min_peers;
+        let status = format!("{} active peers, minimum is {}", extra_data.active_peers, self.min_peers);
+
+        if self.state.update(is_below, status) {
+            if is_below {
+                warn!("Active peers {} are below minimum: {}", extra_data.active_peers, self.min_peers);
+            } else {
+                warn!("Active peers {} recovered: {}", extra_data.active_peers, self.min_peers);
+            }
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        MIN_PEERS_RULE
+    }
+
+    fn kind(&self) -> MiningRuleKind {
+        MiningRuleKind::Restrictive
+    }
+
+    fn is_triggered(&self) -> bool {
+        self.state.is_triggered()
+    }
+
+    fn status(&self) -> String {
+        self.state.status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn extra_data(active_peers: usize) -> ExtraData {
+        ExtraData {
+            finality_point_timestamp: 0,
+            target_time_per_block: 100,
+            has_sufficient_peer_connectivity: active_peers > 0,
+            finality_duration: 1000,
+            elapsed_time: Duration::from_secs(10),
+            active_peers,
+            sink_timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn test_min_peers_rule() {
+        let rule = MinPeersRule::new(3);
+        let delta = ProcessingCountersSnapshot::default();
+
+        rule.check_rule(&delta, &extra_data(3));
+        assert!(!rule.is_triggered());
+
+        rule.check_rule(&delta, &extra_data(2));
+        assert!(rule.is_triggered(), "Expected rule to trigger below the minimum number of peers");
+        rule.check_rule(&delta, &extra_data(1));
+        assert!(rule.is_triggered());
+
+        rule.check_rule(&delta, &extra_data(8));
+        assert!(!rule.is_triggered(), "Expected rule to recover once enough peers are connected");
+    }
+}
@@ -1,7 +1,85 @@
+use std::{
+    fmt::Display,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
+};
+
 use kaspa_consensus_core::api::counters::ProcessingCountersSnapshot;
 
 use super::ExtraData;
 
+/// How a triggered rule affects the mining decision
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MiningRuleKind {
+    /// When triggered, the rule allows mining even if the node is not nearly synced
+    Permissive,
+    /// When triggered, the rule blocks mining even if the node is synced
+    Restrictive,
+}
+
+impl MiningRuleKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MiningRuleKind::Permissive => "permissive",
+            MiningRuleKind::Restrictive => "restrictive",
+        }
+    }
+}
+
+impl Display for MiningRuleKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 pub trait MiningRule: Send + Sync + 'static {
     fn check_rule(&self, delta: &ProcessingCountersSnapshot, extra_data: &ExtraData);
+
+    /// Unique name identifying the rule in the node configuration and in status reports
+    fn name(&self) -> &'static str;
+
+    fn kind(&self) -> MiningRuleKind;
+
+    /// Returns whether the rule conditions were met on the last check
+    fn is_triggered(&self) -> bool;
+
+    /// Returns a human readable explanation of the outcome of the last check
+    fn status(&self) -> String;
+}
+
+/// Outcome of the last check of a rule, backing [`MiningRule::is_triggered`] and [`MiningRule::status`]
+pub struct RuleState {
+    triggered: Arc<AtomicBool>,
+    status: RwLock<String>,
+}
+
+impl RuleState {
+    pub fn new(status: &str) -> Self {
+        Self::with_flag(Arc::new(AtomicBool::new(false)), status)
+    }
+
+    /// Builds a state whose triggered flag is shared with the caller
+    pub fn with_flag(triggered: Arc<AtomicBool>, status: &str) -> Self {
+        Self { triggered, status: RwLock::new(status.to_owned()) }
+    }
+
+    /// Records the outcome of a check and returns whether the rule switched between triggered and recovered
+    pub fn update(&self, triggered: bool, status: String) -> bool {
+        self.set_status(status);
+        self.triggered.compare_exchange(!triggered, triggered, Ordering::Relaxed, Ordering::Relaxed).is_ok()
+    }
+
+    pub fn set_status(&self, status: String) {
+        *self.status.write().unwrap() = status;
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.triggered.load(Ordering::Relaxed)
+    }
+
+    pub fn status(&self) -> String {
+        self.status.read().unwrap().clone()
+    }
 }
@@ -1,13 +1,77 @@
 use std::time::Duration;
 
+use self::{
+    finality_conflict_rule::FINALITY_CONFLICT_RULE, min_peers_rule::MIN_PEERS_RULE, stale_sink_rule::STALE_SINK_RULE,
+    sync_rate_rule::SYNC_RATE_RULE,
+};
+
+pub mod finality_conflict_rule;
+pub mod min_peers_rule;
+pub mod stale_sink_rule;
 pub mod sync_rate_rule;
 
 pub mod mining_rule;
 
+/// Names of all the built-in mining rules
+pub const MINING_RULES: [&str; 4] = [SYNC_RATE_RULE, MIN_PEERS_RULE, STALE_SINK_RULE, FINALITY_CONFLICT_RULE];
+
+/// Names of the mining rules enabled by default
+pub const DEFAULT_MINING_RULES: [&str; 1] = [SYNC_RATE_RULE];
+
+pub const DEFAULT_MIN_PEERS: usize = 3;
+
+/// Default duration, in milliseconds, finality conflicts must be ongoing for before mining gets blocked
+pub const DEFAULT_MAX_FINALITY_CONFLICT_DURATION: u64 = 10 * 60 * 1000;
+
 pub struct ExtraData {
     pub finality_point_timestamp: u64,
     pub target_time_per_block: u64,
     pub has_sufficient_peer_connectivity: bool,
     pub finality_duration: u64,
     pub elapsed_time: Duration,
+    pub active_peers: usize,
+    pub sink_timestamp: u64,
+}
+
+/// Selection and parameters of the rules run by the [`MiningRuleEngine`](crate::rule_engine::MiningRuleEngine)
+#[derive(Clone, Debug)]
+pub struct MiningRulesConfig {
+    /// Names of the enabled rules, see [`MINING_RULES`]
+    pub enabled_rules: Vec<String>,
+    /// Number of active peers below which the `min-peers` rule blocks mining
+    pub min_peers: usize,
+    /// Sink age, in milliseconds, above which the `stale-sink` rule blocks mining.
+    /// Defaults to the finality duration of the network.
+    pub max_sink_age: Option<u64>,
+    /// Duration, in milliseconds, finality conflicts must be ongoing for before the `finality-conflict` rule blocks mining
+    pub max_finality_conflict_duration: u64,
+}
+
+impl MiningRulesConfig {
+    /// Adds the `enable` rules to the enabled rules and then removes the `disable` ones.
+    /// Names not belonging to [`MINING_RULES`] are expected to be rejected beforehand.
+    pub fn with_rules(mut self, enable: &[String], disable: &[String]) -> Self {
+        for name in enable {
+            if !self.is_enabled(name) {
+                self.enabled_rules.push(name.clone());
+            }
+        }
+        self.enabled_rules.retain(|name| !disable.contains(name));
+        self
+    }
+
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.enabled_rules.iter().any(|enabled| enabled == name)
+    }
+}
+
+impl Default for MiningRulesConfig {
+    fn default() -> Self {
+        Self {
+            enabled_rules: DEFAULT_MINING_RULES.iter().map(|name| name.to_string()).collect(),
+            min_peers: DEFAULT_MIN_PEERS,
+            max_sink_age: None,
+            max_finality_conflict_duration: DEFAULT_MAX_FINALITY_CONFLICT_DURATION,
+        }
+    }
 }
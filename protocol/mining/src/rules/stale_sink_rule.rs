@@ -0,0 +1,84 @@
+use kaspa_consensus_core::api::counters::ProcessingCountersSnapshot;
+use kaspa_core::{time::unix_now, warn};
+
+use super::{
+    mining_rule::{MiningRule, MiningRuleKind, RuleState},
+    ExtraData,
+};
+
+pub const STALE_SINK_RULE: &str = "stale-sink";
+
+/// StaleSinkRule
+/// Block mining when the sink is too old, whatever the other rules conclude. Unlike the nearly
+/// synced condition, this one cannot be bypassed by the sync rate rule, so it bounds how far in the
+/// past a template may be built.
+///
+/// Trigger: The sink timestamp is older than the configured maximum age
+/// Recovery: The sink timestamp is recent again
+pub struct StaleSinkRule {
+    /// Maximum sink age in milliseconds
+    max_sink_age: u64,
+    state: RuleState,
+}
+
+impl StaleSinkRule {
+    pub fn new(max_sink_age: u64) -> Self {
+        Self { max_sink_age, state: RuleState::new("not checked yet") }
+    }
+
+    fn check(&self, sink_timestamp: u64, now: u64) {
+        let sink_age = now.saturating_sub(sink_timestamp);
+        let is_stale = sink_age > self.max_sink_age;
+        let status = format!("sink is {}s old, maximum is {}s", sink_age / 1000, self.max_sink_age / 1000);
+
+        if self.state.update(is_stale, status) {
+            if is_stale {
+                warn!("Sink age {}s is above maximum: {}s", sink_age / 1000, self.max_sink_age / 1000);
+            } else {
+                warn!("Sink age {}s recovered: {}s", sink_age / 1000, self.max_sink_age / 1000);
+            }
+        }
+    }
+}
+
+impl MiningRule for StaleSinkRule {
+    fn check_rule(&self, _delta: &ProcessingCountersSnapshot, extra_data: &ExtraData) {
+        self.check(extra_data.sink_timestamp, unix_now());
+    }
+
+    fn name(&self) -> &'static str {
+        STALE_SINK_RULE
+    }
+
+    fn kind(&self) -> MiningRuleKind {
+        MiningRuleKind::Restrictive
+    }
+
+    fn is_triggered(&self) -> bool {
+        self.state.is_triggered()
+    }
+
+    fn status(&self) -> String {
+        self.state.status()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_sink_rule() {
+        let rule = StaleSinkRule::new(60_000);
+        let now = unix_now();
+
+        rule.check(now - 10_000, now);
+        assert!(!rule.is_triggered());
+
+        rule.check(now - 60_001, now);
+        assert!(rule.is_triggered(), "Expected rule to trigger when the sink is older than the maximum age");
+
+        rule.check(now, now);
+        assert!(!rule.is_triggered(), "Expected rule to recover once the sink is recent again");
+    }
+}
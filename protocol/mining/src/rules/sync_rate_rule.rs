@@ -11,7 +11,12 @@ use kaspa_core::{time::unix_now, trace, warn};
 
 use crate::rule_engine::SNAPSHOT_INTERVAL;
 
-use super::{mining_rule::MiningRule, ExtraData};
+use super::{
+    mining_rule::{MiningRule, MiningRuleKind, RuleState},
+    ExtraData,
+};
+
+pub const SYNC_RATE_RULE: &str = "sync-rate";
 
 // within a 5 minute period, we expect sync rate less sensitive to sudden changes
 // but we use a lower threshold anyway because we want the warns to be less frequent
@@ -23,7 +28,8 @@ const SYNC_RATE_WINDOW_MAX_SIZE: usize = 5 * 60 / (SNAPSHOT_INTERVAL as usize);
 const SYNC_RATE_WINDOW_MIN_THRESHOLD: usize = 60 / (SNAPSHOT_INTERVAL as usize);
 
 pub struct SyncRateRule {
-    pub use_sync_rate_rule: Arc<AtomicBool>,
+    /// Outcome of the last check, the triggered flag being shared through `use_sync_rate_rule`
+    state: RuleState,
     sync_rate_samples: RwLock<VecDeque<(u64, u64)>>,
     total_expected_blocks: AtomicU64,
     total_received_blocks: AtomicU64,
@@ -32,7 +38,7 @@ pub struct SyncRateRule {
 impl SyncRateRule {
     pub fn new(use_sync_rate_rule: Arc<AtomicBool>) -> Self {
         Self {
-            use_sync_rate_rule,
+            state: RuleState::with_flag(use_sync_rate_rule, "collecting sync rate samples"),
             sync_rate_samples: RwLock::new(VecDeque::new()),
             total_expected_blocks: AtomicU64::new(0),
             total_received_blocks: AtomicU64::new(0),
@@ -75,6 +81,7 @@ impl MiningRule for SyncRateRule {
 
         if !self.update_sync_rate_window(received_blocks, expected_blocks) {
             // Don't process the sync rule if the window doesn't have enough samples to filter out noise
+            self.state.set_status("collecting sync rate samples".to_owned());
             return;
         }
 
@@ -94,24 +101,42 @@ impl MiningRule for SyncRateRule {
             expected_blocks,
         );
 
-        if is_finality_recent && rate < SYNC_RATE_THRESHOLD {
-            // if sync rate rule conditions are met:
-            if let Ok(false) = self.use_sync_rate_rule.compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed) {
+        let status = match (is_finality_recent, rate < SYNC_RATE_THRESHOLD) {
+            (true, true) => format!("sync rate {:.2} is below threshold {}", rate, SYNC_RATE_THRESHOLD),
+            (true, false) => format!("sync rate {:.2} is above threshold {}", rate, SYNC_RATE_THRESHOLD),
+            (false, _) => format!("finality point is old, sync rate {:.2}", rate),
+        };
+
+        // the sync rate rule conditions are met if the rate is low while the finality point is recent
+        let is_triggered = is_finality_recent && rate < SYNC_RATE_THRESHOLD;
+        if self.state.update(is_triggered, status) {
+            if is_triggered {
                 warn!("Sync rate {:.2} is below threshold: {}", rate, SYNC_RATE_THRESHOLD);
-            }
-        } else {
-            // else when sync rate conditions are not met:
-            if let Ok(true) = self.use_sync_rate_rule.compare_exchange(true, false, Ordering::Relaxed, Ordering::Relaxed) {
-                if !is_finality_recent {
-                    warn!("Sync rate {:.2} recovered: {} by entering IBD", rate, SYNC_RATE_THRESHOLD);
-                } else {
-                    warn!("Sync rate {:.2} recovered: {}", rate, SYNC_RATE_THRESHOLD);
-                }
             } else if !is_finality_recent {
-                trace!("Finality period is old. Timestamp: {}. Sync rate: {:.2}", extra_data.finality_point_timestamp, rate);
+                warn!("Sync rate {:.2} recovered: {} by entering IBD", rate, SYNC_RATE_THRESHOLD);
+            } else {
+                warn!("Sync rate {:.2} recovered: {}", rate, SYNC_RATE_THRESHOLD);
             }
+        } else if !is_finality_recent {
+            trace!("Finality period is old. Timestamp: {}. Sync rate: {:.2}", extra_data.finality_point_timestamp, rate);
         }
     }
+
+    fn name(&self) -> &'static str {
+        SYNC_RATE_RULE
+    }
+
+    fn kind(&self) -> MiningRuleKind {
+        MiningRuleKind::Permissive
+    }
+
+    fn is_triggered(&self) -> bool {
+        self.state.is_triggered()
+    }
+
+    fn status(&self) -> String {
+        self.state.status()
+    }
 }
 
 #[cfg(test)]
@@ -146,6 +171,8 @@ mod tests {
             finality_point_timestamp: unix_now(),
             finality_duration: 1000,
             has_sufficient_peer_connectivity: true,
+            active_peers: 8,
+            sink_timestamp: unix_now(),
         };
 
         // Sync rate should be at 1.0
@@ -196,6 +223,8 @@ mod tests {
             finality_point_timestamp: unix_now().saturating_sub(1000 * 3) - 1, // the millisecond right before timestamp is "old enough"
             finality_duration: 1000,
             has_sufficient_peer_connectivity: true,
+            active_peers: 8,
+            sink_timestamp: unix_now(),
         };
 
         for _ in 0..10 {
@@ -1,4 +1,4 @@
-use crate::{convert::error::ConversionError, core::peer::PeerKey, KaspadMessagePayloadType};
+use crate::{convert::error::ConversionError, core::peer::PeerKey, noise::NoiseError, KaspadMessagePayloadType};
 use kaspa_consensus_core::errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError};
 use kaspa_mining_errors::manager::MiningManagerError;
 use std::time::Duration;
@@ -7,6 +7,9 @@ use thiserror::Error;
 /// Default P2P communication timeout
 pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120); // 2 minutes
 
+/// Service bit advertised in the version message by nodes accepting Dandelion++ stem transactions
+pub const SERVICE_STEM_RELAY: u64 = 1 << 11;
+
 #[derive(Error, Debug, Clone)]
 pub enum ProtocolError {
     #[error("timeout expired after {0:?}")]
@@ -64,6 +67,15 @@ pub enum ProtocolError {
     #[error("peer {0} already exists")]
     PeerAlreadyExists(PeerKey),
 
+    #[error("noise handshake failed: {0}")]
+    NoiseError(#[from] NoiseError),
+
+    #[error("peer does not support encrypted transport which is required by this node")]
+    EncryptionRequired,
+
+    #[error("peer static key {0} is not trusted")]
+    UntrustedPeerKey(String),
+
     #[error("loopback connection - node is connecting to itself")]
     LoopbackConnection(PeerKey),
 
@@ -149,3 +149,11 @@ impl TryFrom<protowire::TransactionMessage> for Transaction {
         Ok(transaction)
     }
 }
+
+impl TryFrom<protowire::StemTransactionMessage> for Transaction {
+    type Error = ConversionError;
+
+    fn try_from(msg: protowire::StemTransactionMessage) -> Result<Self, Self::Error> {
+        msg.transaction.try_into_ex()
+    }
+}
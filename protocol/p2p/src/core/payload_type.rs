@@ -48,6 +48,9 @@ pub enum KaspadMessagePayloadType {
     RequestNextPruningPointAndItsAnticoneBlocks,
     BlockBody,
     RequestBlockBodies,
+    NoiseHandshake,
+    Encrypted,
+    StemTransaction,
 }
 
 impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
@@ -104,6 +107,9 @@ impl From<&KaspadMessagePayload> for KaspadMessagePayloadType {
             }
             KaspadMessagePayload::BlockBody(_) => KaspadMessagePayloadType::BlockBody,
             KaspadMessagePayload::RequestBlockBodies(_) => KaspadMessagePayloadType::RequestBlockBodies,
+            KaspadMessagePayload::NoiseHandshake(_) => KaspadMessagePayloadType::NoiseHandshake,
+            KaspadMessagePayload::Encrypted(_) => KaspadMessagePayloadType::Encrypted,
+            KaspadMessagePayload::StemTransaction(_) => KaspadMessagePayloadType::StemTransaction,
         }
     }
 }
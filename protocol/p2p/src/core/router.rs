@@ -1,11 +1,13 @@
 use crate::core::hub::HubEvent;
-use crate::pb::RejectMessage;
+use crate::noise::CipherState;
 use crate::pb::{kaspad_message::Payload as KaspadMessagePayload, KaspadMessage};
+use crate::pb::{EncryptedMessage, RejectMessage};
 use crate::{common::ProtocolError, KaspadMessagePayloadType};
 use crate::{make_message, Peer};
 use kaspa_core::{debug, error, info, trace, warn};
 use kaspa_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
+use prost::Message;
 use seqlock::SeqLock;
 use std::fmt::{Debug, Display};
 use std::net::SocketAddr;
@@ -139,6 +141,13 @@ pub struct Router {
 
     /// Used for managing router mutable state
     mutable_state: Mutex<RouterMutableState>,
+
+    /// Transport cipher for outgoing messages, set once the connection is encrypted.
+    /// The lock is held while pushing to the outgoing route so that nonces and message order agree
+    send_cipher: Mutex<Option<CipherState>>,
+
+    /// Transport cipher for incoming messages, set once the connection is encrypted
+    recv_cipher: Mutex<Option<CipherState>>,
 }
 
 impl Display for Router {
@@ -193,6 +202,8 @@ impl Router {
             outgoing_route,
             hub_sender,
             mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
+            send_cipher: Mutex::new(None),
+            recv_cipher: Mutex::new(None),
         });
 
         let router_clone = router.clone();
@@ -212,7 +223,7 @@ impl Router {
                     res = incoming_stream.message() => match res {
                         Ok(Some(msg)) => {
                             trace!("P2P msg: {:?}, router-id: {}, peer: {}", message_summary(&msg), router.identity(), router);
-                            match router.route_to_flow(msg) {
+                            match router.unwrap_incoming(msg).and_then(|msg| router.route_to_flow(msg)) {
                                 Ok(()) => {},
                                 Err(e) => {
                                     match e {
@@ -294,6 +305,21 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Indicates whether messages sent to this peer are encrypted
+    pub fn is_encrypted(&self) -> bool {
+        self.send_cipher.lock().is_some()
+    }
+
+    /// Sets the cipher decrypting all subsequent incoming messages
+    pub(crate) fn set_recv_cipher(&self, cipher: CipherState) {
+        *self.recv_cipher.lock() = Some(cipher);
+    }
+
+    /// Sets the cipher encrypting all subsequent outgoing messages
+    pub(crate) fn set_send_cipher(&self, cipher: CipherState) {
+        *self.send_cipher.lock() = Some(cipher);
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -361,6 +387,26 @@ impl Router {
         incoming_route
     }
 
+    /// Decrypts an incoming message if the connection is encrypted. Until the first encrypted message
+    /// is received, reject messages are still accepted in the clear since the peer might fail before
+    /// switching. Once the peer has switched, any message in the clear is a protocol violation
+    fn unwrap_incoming(&self, msg: KaspadMessage) -> Result<KaspadMessage, ProtocolError> {
+        let mut recv_cipher = self.recv_cipher.lock();
+        match (recv_cipher.as_mut(), msg.payload) {
+            (Some(cipher), Some(KaspadMessagePayload::Encrypted(encrypted))) => {
+                let plaintext =
+                    cipher.decrypt(&encrypted.ciphertext).map_err(|err| ProtocolError::MisbehavingPeer(err.to_string()))?;
+                KaspadMessage::decode(plaintext.as_slice())
+                    .map_err(|_| ProtocolError::MisbehavingPeer("malformed encrypted message".to_owned()))
+            }
+            (Some(cipher), payload @ Some(KaspadMessagePayload::Reject(_))) if !cipher.is_used() => {
+                Ok(KaspadMessage { payload, ..msg })
+            }
+            (None, payload) => Ok(KaspadMessage { payload, ..msg }),
+            (Some(_), _) => Err(ProtocolError::Other("received an unencrypted message on an encrypted connection")),
+        }
+    }
+
     /// Routes a message coming from the network to the corresponding registered flow
     pub fn route_to_flow(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
         if msg.payload.is_none() {
@@ -402,6 +448,14 @@ impl Router {
     /// Enqueues a locally-originated message to be sent to the network peer
     pub async fn enqueue(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
         assert!(msg.payload.is_some(), "Kaspad P2P message should always have a value");
+        let mut send_cipher = self.send_cipher.lock();
+        let msg = match send_cipher.as_mut() {
+            Some(cipher) => {
+                let ciphertext = cipher.encrypt(&msg.encode_to_vec())?;
+                make_message!(KaspadMessagePayload::Encrypted, EncryptedMessage { ciphertext })
+            }
+            None => msg,
+        };
         match self.outgoing_route.try_send(msg) {
             Ok(_) => Ok(()),
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
@@ -3,7 +3,7 @@ use crate::{
     core::adaptor::ConnectionInitializer,
     handshake::KaspadHandshake,
     pb::{self, VersionMessage},
-    IncomingRoute, KaspadMessagePayloadType, Router,
+    IncomingRoute, KaspadMessagePayloadType, Router, TransportSecurity,
 };
 use kaspa_core::{debug, time::unix_now, trace, warn};
 use std::sync::Arc;
@@ -88,12 +88,14 @@ impl EchoFlow {
 
 /// An example initializer, performing handshake and registering a simple echo flow
 #[derive(Default)]
-pub struct EchoFlowInitializer {}
+pub struct EchoFlowInitializer {
+    transport_security: TransportSecurity,
+}
 
-fn build_dummy_version_message() -> VersionMessage {
+fn build_dummy_version_message(services: u64) -> VersionMessage {
     pb::VersionMessage {
         protocol_version: 5,
-        services: 0,
+        services,
         timestamp: unix_now() as i64,
         address: None,
         id: Vec::from(Uuid::new_v4().as_bytes()),
@@ -106,7 +108,11 @@ fn build_dummy_version_message() -> VersionMessage {
 
 impl EchoFlowInitializer {
     pub fn new() -> Self {
-        EchoFlowInitializer {}
+        Self::default()
+    }
+
+    pub fn with_transport_security(transport_security: TransportSecurity) -> Self {
+        EchoFlowInitializer { transport_security }
     }
 }
 
@@ -124,12 +130,21 @@ impl ConnectionInitializer for EchoFlowInitializer {
         router.start();
 
         // Build the local version message
-        let self_version_message = build_dummy_version_message();
+        let self_version_message = build_dummy_version_message(self.transport_security.services());
 
         // Perform the handshake
         let peer_version_message = handshake.handshake(self_version_message).await?;
         debug!("protocol versions - self: {}, peer: {}", 5, peer_version_message.protocol_version);
 
+        // Encrypt the connection if both sides agree to
+        match self.transport_security.should_encrypt(peer_version_message.services) {
+            Some(true) => {
+                handshake.encrypt(&self.transport_security).await?;
+            }
+            Some(false) => {}
+            None => return Err(ProtocolError::EncryptionRequired),
+        }
+
         // Subscribe to remaining messages. In this example we simply subscribe to all messages with a single echo flow
         EchoFlow::register(router.clone()).await;
 
@@ -148,9 +163,10 @@ mod tests {
     use std::{str::FromStr, time::Duration};
 
     use super::*;
-    use crate::{Adaptor, Hub};
+    use crate::{Adaptor, EncryptionPolicy, Hub, Peer};
     use kaspa_core::debug;
     use kaspa_utils::networking::NetAddress;
+    use secp256k1::SecretKey;
 
     #[tokio::test]
     async fn test_handshake() {
@@ -201,4 +217,62 @@ mod tests {
         drop(adaptor2);
         tokio::time::sleep(std::time::Duration::from_secs(2)).await;
     }
+
+    fn transport_security(policy: EncryptionPolicy) -> TransportSecurity {
+        TransportSecurity::new(policy, SecretKey::new(&mut rand::thread_rng()))
+    }
+
+    fn get_free_net_address() -> NetAddress {
+        let socket = std::net::TcpListener::bind("[::1]:0").unwrap();
+        let address = socket.local_addr().unwrap();
+        drop(socket);
+        address.into()
+    }
+
+    async fn connect(security1: TransportSecurity, security2: TransportSecurity) -> Option<Vec<Peer>> {
+        let address1 = get_free_net_address();
+        let initializer1 = Arc::new(EchoFlowInitializer::with_transport_security(security1));
+        let adaptor1 = Adaptor::bidirectional(address1, Hub::new(), initializer1, Default::default()).unwrap();
+
+        let address2 = get_free_net_address();
+        let initializer2 = Arc::new(EchoFlowInitializer::with_transport_security(security2));
+        let adaptor2 = Adaptor::bidirectional(address2, Hub::new(), initializer2, Default::default()).unwrap();
+
+        let result = adaptor1.connect_peer_with_retries(address2.to_string(), 16, Duration::from_secs(1)).await;
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+        let peers = result.ok().map(|_| adaptor1.active_peers().into_iter().chain(adaptor2.active_peers()).collect());
+
+        adaptor1.close().await;
+        adaptor2.close().await;
+        peers
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_handshake() {
+        kaspa_core::log::try_init_logger("debug");
+
+        // Both sides opt in, so the connection is established over the encrypted transport
+        let peers = connect(transport_security(EncryptionPolicy::Opportunistic), transport_security(EncryptionPolicy::Required))
+            .await
+            .expect("peer connection failed");
+        assert_eq!(peers.len(), 2, "encrypted handshake failed");
+
+        // An opportunistic node falls back to plain transport
+        let peers = connect(transport_security(EncryptionPolicy::Opportunistic), transport_security(EncryptionPolicy::Disabled))
+            .await
+            .expect("peer connection failed");
+        assert_eq!(peers.len(), 2, "plain handshake failed");
+
+        // A node requiring encryption rejects plain peers
+        let peers = connect(transport_security(EncryptionPolicy::Disabled), transport_security(EncryptionPolicy::Required)).await;
+        assert!(peers.is_none_or(|peers| peers.is_empty()), "plain peer should be rejected");
+
+        // Whitelist mode only accepts trusted static keys
+        let (trusted, untrusted) = (transport_security(EncryptionPolicy::Required), transport_security(EncryptionPolicy::Required));
+        let whitelist = transport_security(EncryptionPolicy::Required).with_trusted_keys([trusted.static_public_key()]);
+        let peers = connect(trusted, whitelist.clone()).await.expect("peer connection failed");
+        assert_eq!(peers.len(), 2, "trusted peer should be accepted");
+        let peers = connect(untrusted, whitelist).await;
+        assert!(peers.is_none_or(|peers| peers.is_empty()), "untrusted peer should be rejected");
+    }
 }
@@ -1,9 +1,14 @@
 use std::time::Duration;
 
-use crate::pb::{kaspad_message::Payload, ReadyMessage, VerackMessage, VersionMessage};
+use crate::noise::HandshakeState;
+use crate::pb::{kaspad_message::Payload, NoiseHandshakeMessage, ReadyMessage, VerackMessage, VersionMessage};
 use crate::{common::ProtocolError, dequeue_with_timeout, make_message};
-use crate::{IncomingRoute, KaspadMessagePayloadType, Router};
+use crate::{IncomingRoute, KaspadMessagePayloadType, Router, TransportSecurity};
 use kaspa_core::debug;
+use secp256k1::PublicKey;
+
+/// Domain separator of the noise prologue, versioned along with its serialization
+const NOISE_PROLOGUE_TAG: &[u8] = b"kaspa-p2p-noise-v1";
 
 /// Implements the Kaspa peer-to-peer handshake protocol
 pub struct KaspadHandshake<'a> {
@@ -11,6 +16,9 @@ pub struct KaspadHandshake<'a> {
     version_receiver: IncomingRoute,
     verack_receiver: IncomingRoute,
     ready_receiver: IncomingRoute,
+    noise_receiver: IncomingRoute,
+    /// The version messages exchanged by the outbound and inbound sides, bound to the noise handshake
+    versions: Option<(VersionMessage, VersionMessage)>,
 }
 
 impl<'a> KaspadHandshake<'a> {
@@ -21,6 +29,8 @@ impl<'a> KaspadHandshake<'a> {
             version_receiver: router.subscribe(vec![KaspadMessagePayloadType::Version]),
             verack_receiver: router.subscribe(vec![KaspadMessagePayloadType::Verack]),
             ready_receiver: router.subscribe(vec![KaspadMessagePayloadType::Ready]),
+            noise_receiver: router.subscribe(vec![KaspadMessagePayloadType::NoiseHandshake]),
+            versions: None,
         }
     }
 
@@ -71,10 +81,110 @@ impl<'a> KaspadHandshake<'a> {
     pub async fn handshake(&mut self, self_version_message: VersionMessage) -> Result<VersionMessage, ProtocolError> {
         // Run both send and receive flows concurrently -- this is critical in order to avoid a handshake deadlock
         let (send_res, recv_res) = tokio::join!(
-            Self::send_version_flow(self.router, &mut self.verack_receiver, self_version_message),
+            Self::send_version_flow(self.router, &mut self.verack_receiver, self_version_message.clone()),
             Self::receive_version_flow(self.router, &mut self.version_receiver)
         );
         send_res?;
-        recv_res
+        let peer_version_message = recv_res?;
+        self.versions = Some(match self.router.is_outbound() {
+            true => (self_version_message, peer_version_message.clone()),
+            false => (peer_version_message.clone(), self_version_message),
+        });
+        Ok(peer_version_message)
+    }
+
+    async fn send_noise_message(&self, payload: Vec<u8>) -> Result<(), ProtocolError> {
+        self.router.enqueue(make_message!(Payload::NoiseHandshake, NoiseHandshakeMessage { payload })).await
+    }
+
+    async fn receive_noise_message(&mut self) -> Result<Vec<u8>, ProtocolError> {
+        Ok(dequeue_with_timeout!(self.noise_receiver, Payload::NoiseHandshake, Duration::from_secs(4))?.payload)
+    }
+
+    /// Runs a Noise XX handshake with the peer and switches the router to encrypted transport.
+    /// Should be called right after the version exchange, and only if both peers agreed to encrypt
+    /// (see [`TransportSecurity::should_encrypt`]). The outbound side acts as the Noise initiator.
+    ///
+    /// The exchanged version messages are used as prologue, so the handshake fails if any of them,
+    /// and in particular the advertised services, was tampered with on the way.
+    ///
+    /// Returns the authenticated static key of the peer.
+    pub async fn encrypt(&mut self, security: &TransportSecurity) -> Result<PublicKey, ProtocolError> {
+        debug!("starting noise handshake");
+        let (outbound_version, inbound_version) =
+            self.versions.as_ref().ok_or(ProtocolError::Other("noise handshake started before the version exchange"))?;
+        let prologue = noise_prologue(outbound_version, inbound_version);
+        let mut state = HandshakeState::new(self.router.is_outbound(), *security.static_keypair(), &prologue);
+        let remote_static = if self.router.is_outbound() {
+            self.send_noise_message(state.write_message(&[])?).await?;
+            state.read_message(&self.receive_noise_message().await?)?;
+            let last = state.write_message(&[])?;
+            let ciphers = state.into_transport()?;
+            // Incoming messages are encrypted from now on, however we keep sending in the clear until
+            // the responder confirms that it switched as well, otherwise it might fail decrypting our messages
+            self.router.set_recv_cipher(ciphers.recv);
+            self.send_noise_message(last).await?;
+            self.receive_noise_message().await?;
+            self.router.set_send_cipher(ciphers.send);
+            ciphers.remote_static
+        } else {
+            state.read_message(&self.receive_noise_message().await?)?;
+            self.send_noise_message(state.write_message(&[])?).await?;
+            state.read_message(&self.receive_noise_message().await?)?;
+            let ciphers = state.into_transport()?;
+            self.router.set_recv_cipher(ciphers.recv);
+            self.router.set_send_cipher(ciphers.send);
+            // Confirm the switch over the encrypted transport
+            self.send_noise_message(vec![]).await?;
+            ciphers.remote_static
+        };
+        if !security.is_trusted(&remote_static) {
+            return Err(ProtocolError::UntrustedPeerKey(remote_static.to_string()));
+        }
+        debug!("noise handshake completed, peer static key: {remote_static}");
+        Ok(remote_static)
+    }
+}
+
+/// Serializes the version messages of the outbound and inbound sides into the noise prologue.
+/// Only the fields both sides see unchanged are included, each variable length field being length-prefixed
+fn noise_prologue(outbound_version: &VersionMessage, inbound_version: &VersionMessage) -> Vec<u8> {
+    fn put_bytes(prologue: &mut Vec<u8>, bytes: &[u8]) {
+        prologue.extend((bytes.len() as u32).to_le_bytes());
+        prologue.extend(bytes);
+    }
+
+    let mut prologue = NOISE_PROLOGUE_TAG.to_vec();
+    for version in [outbound_version, inbound_version] {
+        prologue.extend(version.protocol_version.to_le_bytes());
+        prologue.extend(version.services.to_le_bytes());
+        prologue.extend(version.timestamp.to_le_bytes());
+        prologue.push(version.disable_relay_tx as u8);
+        put_bytes(&mut prologue, &version.id);
+        put_bytes(&mut prologue, version.network.as_bytes());
+        put_bytes(&mut prologue, version.user_agent.as_bytes());
+    }
+    prologue
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noise_prologue() {
+        let outbound = VersionMessage { services: 1 << 10, network: "kaspa-mainnet".to_owned(), ..Default::default() };
+        let inbound = VersionMessage { services: 1 << 10, network: "kaspa-mainnet".to_owned(), ..Default::default() };
+        let prologue = noise_prologue(&outbound, &inbound);
+
+        // A stripped service bit changes the prologue, hence fails the noise handshake
+        let stripped = VersionMessage { services: 0, ..inbound.clone() };
+        assert_ne!(noise_prologue(&outbound, &stripped), prologue);
+        // Fields are delimited so that bytes cannot be shifted from one field to another
+        let shifted = VersionMessage { network: "kaspa-main".to_owned(), user_agent: "net".to_owned(), ..outbound.clone() };
+        assert_ne!(noise_prologue(&shifted, &inbound), prologue);
+        // The roles are bound as well
+        let reversed = VersionMessage { timestamp: 1, ..inbound.clone() };
+        assert_ne!(noise_prologue(&outbound, &reversed), noise_prologue(&reversed, &outbound));
     }
 }
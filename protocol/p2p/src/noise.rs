@@ -0,0 +1,708 @@
+//! Optional encryption of the peer-to-peer transport.
+//!
+//! Peers which both advertise [`SERVICE_ENCRYPTED_TRANSPORT`] in their version message run a
+//! `Noise_XX_secp256k1_ChaChaPoly_SHA256` handshake right after the version exchange. The handshake
+//! authenticates both static keys and derives a pair of transport ciphers which are then used by the
+//! [`Router`](crate::Router) to wrap every subsequent message into an [`EncryptedMessage`](crate::pb::EncryptedMessage).
+//!
+//! The DH function follows the BOLT-8 convention of hashing the compressed shared point with SHA256.
+//! Transport messages are framed by gRPC, hence they are not bound to the Noise 65535 bytes message limit.
+//! No test vectors are published for the secp256k1 suites but the BOLT-8 ones, which share the same DH,
+//! cipher and hash with the XK pattern. The handshake and the transport ciphers are checked against the
+//! full BOLT-8 handshake and transport vectors by running the same message processing over that pattern.
+//!
+//! The version messages are bound to the handshake as prologue, so a peer whose advertised services were
+//! altered on the way fails the handshake. An on-path attacker stripping the service bit of both peers
+//! can however still keep [`EncryptionPolicy::Opportunistic`] peers in the clear; peers which must not fall
+//! back to plain transport should use [`EncryptionPolicy::Required`].
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use hmac::{Hmac, Mac};
+use secp256k1::{ecdh::SharedSecret, Keypair, PublicKey, SecretKey, SECP256K1};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashSet,
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// Service bit advertised in the version message by nodes willing to encrypt the connection
+pub const SERVICE_ENCRYPTED_TRANSPORT: u64 = 1 << 10;
+
+const PROTOCOL_NAME: &[u8] = b"Noise_XX_secp256k1_ChaChaPoly_SHA256";
+const HASH_LEN: usize = 32;
+const PUBLIC_KEY_LEN: usize = 33;
+const TAG_LEN: usize = 16;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NoiseError {
+    #[error("noise handshake message is malformed")]
+    MalformedMessage,
+
+    #[error("noise handshake message was written or read out of order")]
+    OutOfOrder,
+
+    #[error("invalid public key in noise handshake message")]
+    InvalidPublicKey,
+
+    #[error("decryption failed")]
+    Decryption,
+
+    #[error("cipher nonce space is exhausted")]
+    NonceExhausted,
+}
+
+type NoiseResult<T> = std::result::Result<T, NoiseError>;
+
+/// A Noise cipher state: a ChaCha20-Poly1305 key along with a strictly increasing nonce
+pub struct CipherState {
+    cipher: Option<ChaCha20Poly1305>,
+    nonce: u64,
+}
+
+impl std::fmt::Debug for CipherState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CipherState").field("has_key", &self.cipher.is_some()).field("nonce", &self.nonce).finish()
+    }
+}
+
+impl CipherState {
+    fn empty() -> Self {
+        Self { cipher: None, nonce: 0 }
+    }
+
+    fn new(key: &[u8; HASH_LEN]) -> Self {
+        Self { cipher: Some(ChaCha20Poly1305::new(Key::from_slice(key))), nonce: 0 }
+    }
+
+    fn next_nonce(&mut self) -> NoiseResult<Nonce> {
+        // Noise reserves the max nonce value
+        if self.nonce == u64::MAX {
+            return Err(NoiseError::NonceExhausted);
+        }
+        let mut nonce = [0u8; 12];
+        nonce[4..].copy_from_slice(&self.nonce.to_le_bytes());
+        self.nonce += 1;
+        Ok(*Nonce::from_slice(&nonce))
+    }
+
+    fn encrypt_with_ad(&mut self, ad: &[u8], plaintext: &[u8]) -> NoiseResult<Vec<u8>> {
+        if self.cipher.is_none() {
+            return Ok(plaintext.to_vec());
+        }
+        let nonce = self.next_nonce()?;
+        self.cipher.as_ref().unwrap().encrypt(&nonce, Payload { msg: plaintext, aad: ad }).map_err(|_| NoiseError::Decryption)
+    }
+
+    fn decrypt_with_ad(&mut self, ad: &[u8], ciphertext: &[u8]) -> NoiseResult<Vec<u8>> {
+        if self.cipher.is_none() {
+            return Ok(ciphertext.to_vec());
+        }
+        let nonce = self.next_nonce()?;
+        self.cipher.as_ref().unwrap().decrypt(&nonce, Payload { msg: ciphertext, aad: ad }).map_err(|_| NoiseError::Decryption)
+    }
+
+    /// Whether the cipher was already used for encrypting or decrypting a message
+    pub fn is_used(&self) -> bool {
+        self.nonce > 0
+    }
+
+    /// Encrypts a transport message
+    pub fn encrypt(&mut self, plaintext: &[u8]) -> NoiseResult<Vec<u8>> {
+        self.encrypt_with_ad(&[], plaintext)
+    }
+
+    /// Decrypts a transport message
+    pub fn decrypt(&mut self, ciphertext: &[u8]) -> NoiseResult<Vec<u8>> {
+        self.decrypt_with_ad(&[], ciphertext)
+    }
+}
+
+fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; HASH_LEN] {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// The Noise HKDF, returning two output keys
+fn hkdf(chaining_key: &[u8; HASH_LEN], input_key_material: &[u8]) -> ([u8; HASH_LEN], [u8; HASH_LEN]) {
+    let temp_key = hmac_sha256(chaining_key, &[input_key_material]);
+    let output1 = hmac_sha256(&temp_key, &[&[0x01]]);
+    let output2 = hmac_sha256(&temp_key, &[&output1, &[0x02]]);
+    (output1, output2)
+}
+
+fn dh(secret_key: &SecretKey, public_key: &PublicKey) -> [u8; HASH_LEN] {
+    SharedSecret::new(public_key, secret_key).secret_bytes()
+}
+
+struct SymmetricState {
+    chaining_key: [u8; HASH_LEN],
+    hash: [u8; HASH_LEN],
+    cipher: CipherState,
+}
+
+impl SymmetricState {
+    fn new(prologue: &[u8]) -> Self {
+        Self::with_protocol_name(PROTOCOL_NAME, prologue)
+    }
+
+    /// Initializes the state for a protocol whose name is longer than the hash, as is the case of all secp256k1 protocols
+    fn with_protocol_name(protocol_name: &[u8], prologue: &[u8]) -> Self {
+        debug_assert!(protocol_name.len() > HASH_LEN);
+        let hash: [u8; HASH_LEN] = Sha256::digest(protocol_name).into();
+        let mut state = Self { chaining_key: hash, hash, cipher: CipherState::empty() };
+        state.mix_hash(prologue);
+        state
+    }
+
+    fn mix_hash(&mut self, data: &[u8]) {
+        self.hash = Sha256::new().chain_update(self.hash).chain_update(data).finalize().into();
+    }
+
+    fn mix_key(&mut self, input_key_material: &[u8]) {
+        let (chaining_key, temp_key) = hkdf(&self.chaining_key, input_key_material);
+        self.chaining_key = chaining_key;
+        self.cipher = CipherState::new(&temp_key);
+    }
+
+    fn encrypt_and_hash(&mut self, plaintext: &[u8]) -> NoiseResult<Vec<u8>> {
+        let ciphertext = self.cipher.encrypt_with_ad(&self.hash, plaintext)?;
+        self.mix_hash(&ciphertext);
+        Ok(ciphertext)
+    }
+
+    fn decrypt_and_hash(&mut self, ciphertext: &[u8]) -> NoiseResult<Vec<u8>> {
+        let plaintext = self.cipher.decrypt_with_ad(&self.hash, ciphertext)?;
+        self.mix_hash(ciphertext);
+        Ok(plaintext)
+    }
+
+    fn split(&self) -> (CipherState, CipherState) {
+        let (key1, key2) = hkdf(&self.chaining_key, &[]);
+        (CipherState::new(&key1), CipherState::new(&key2))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Token {
+    E,
+    S,
+    EE,
+    ES,
+    SE,
+}
+
+/// The message tokens of a handshake pattern
+type Pattern = &'static [&'static [Token]];
+
+/// The XX handshake pattern:
+/// ```text
+/// -> e
+/// <- e, ee, s, es
+/// -> s, se
+/// ```
+const XX_PATTERN: Pattern = &[&[Token::E], &[Token::E, Token::EE, Token::S, Token::ES], &[Token::S, Token::SE]];
+
+/// The pair of ciphers resulting from a completed handshake
+pub struct TransportCiphers {
+    pub send: CipherState,
+    pub recv: CipherState,
+    /// The authenticated static key of the remote peer
+    pub remote_static: PublicKey,
+}
+
+/// The state of an in-progress Noise XX handshake
+pub struct HandshakeState {
+    initiator: bool,
+    pattern: Pattern,
+    symmetric: SymmetricState,
+    local_static: Keypair,
+    local_ephemeral: Option<Keypair>,
+    remote_static: Option<PublicKey>,
+    remote_ephemeral: Option<PublicKey>,
+    message_index: usize,
+}
+
+impl HandshakeState {
+    pub fn new(initiator: bool, local_static: Keypair, prologue: &[u8]) -> Self {
+        Self::with_pattern(initiator, XX_PATTERN, SymmetricState::new(prologue), local_static, None)
+    }
+
+    /// Builds the state of a pattern over an initialized symmetric state, with the pre-messages (if any)
+    /// already mixed in. Patterns other than XX are only run by the test vectors.
+    fn with_pattern(
+        initiator: bool,
+        pattern: Pattern,
+        symmetric: SymmetricState,
+        local_static: Keypair,
+        remote_static: Option<PublicKey>,
+    ) -> Self {
+        Self {
+            initiator,
+            pattern,
+            symmetric,
+            local_static,
+            local_ephemeral: None,
+            remote_static,
+            remote_ephemeral: None,
+            message_index: 0,
+        }
+    }
+
+    fn is_our_turn(&self) -> bool {
+        // The initiator writes the even messages of the pattern
+        (self.message_index % 2 == 0) == self.initiator
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.message_index == self.pattern.len()
+    }
+
+    fn mix_dh(&mut self, token: Token) -> NoiseResult<()> {
+        let e = self.local_ephemeral.as_ref().ok_or(NoiseError::OutOfOrder)?.secret_key();
+        let s = self.local_static.secret_key();
+        let re = self.remote_ephemeral.as_ref().ok_or(NoiseError::OutOfOrder);
+        let rs = self.remote_static.as_ref().ok_or(NoiseError::OutOfOrder);
+        let shared = match (token, self.initiator) {
+            (Token::EE, _) => dh(&e, re?),
+            (Token::ES, true) | (Token::SE, false) => dh(&e, rs?),
+            (Token::ES, false) | (Token::SE, true) => dh(&s, re?),
+            (Token::E, _) | (Token::S, _) => unreachable!(),
+        };
+        self.symmetric.mix_key(&shared);
+        Ok(())
+    }
+
+    /// Writes the next handshake message
+    pub fn write_message(&mut self, payload: &[u8]) -> NoiseResult<Vec<u8>> {
+        if self.is_finished() || !self.is_our_turn() {
+            return Err(NoiseError::OutOfOrder);
+        }
+        let mut message = Vec::new();
+        for &token in self.pattern[self.message_index] {
+            match token {
+                Token::E => {
+                    // A preset ephemeral key is only ever set by the test vectors
+                    let ephemeral = self.local_ephemeral.take().unwrap_or_else(|| Keypair::new(SECP256K1, &mut rand::thread_rng()));
+                    let public = ephemeral.public_key().serialize();
+                    self.symmetric.mix_hash(&public);
+                    message.extend_from_slice(&public);
+                    self.local_ephemeral = Some(ephemeral);
+                }
+                Token::S => {
+                    let public = self.local_static.public_key().serialize();
+                    message.extend(self.symmetric.encrypt_and_hash(&public)?);
+                }
+                dh_token => self.mix_dh(dh_token)?,
+            }
+        }
+        message.extend(self.symmetric.encrypt_and_hash(payload)?);
+        self.message_index += 1;
+        Ok(message)
+    }
+
+    /// Reads the next handshake message and returns its payload
+    pub fn read_message(&mut self, message: &[u8]) -> NoiseResult<Vec<u8>> {
+        if self.is_finished() || self.is_our_turn() {
+            return Err(NoiseError::OutOfOrder);
+        }
+        let mut rest = message;
+        for &token in self.pattern[self.message_index] {
+            match token {
+                Token::E => {
+                    if rest.len() < PUBLIC_KEY_LEN {
+                        return Err(NoiseError::MalformedMessage);
+                    }
+                    let (public, tail) = rest.split_at(PUBLIC_KEY_LEN);
+                    self.remote_ephemeral = Some(PublicKey::from_slice(public).map_err(|_| NoiseError::InvalidPublicKey)?);
+                    self.symmetric.mix_hash(public);
+                    rest = tail;
+                }
+                Token::S => {
+                    let len = PUBLIC_KEY_LEN + if self.symmetric.cipher.cipher.is_some() { TAG_LEN } else { 0 };
+                    if rest.len() < len {
+                        return Err(NoiseError::MalformedMessage);
+                    }
+                    let (encrypted, tail) = rest.split_at(len);
+                    let public = self.symmetric.decrypt_and_hash(encrypted)?;
+                    self.remote_static = Some(PublicKey::from_slice(&public).map_err(|_| NoiseError::InvalidPublicKey)?);
+                    rest = tail;
+                }
+                dh_token => self.mix_dh(dh_token)?,
+            }
+        }
+        let payload = self.symmetric.decrypt_and_hash(rest)?;
+        self.message_index += 1;
+        Ok(payload)
+    }
+
+    /// Consumes a finished handshake and returns the transport ciphers
+    pub fn into_transport(self) -> NoiseResult<TransportCiphers> {
+        if !self.is_finished() {
+            return Err(NoiseError::OutOfOrder);
+        }
+        let (initiator_to_responder, responder_to_initiator) = self.symmetric.split();
+        let (send, recv) = if self.initiator {
+            (initiator_to_responder, responder_to_initiator)
+        } else {
+            (responder_to_initiator, initiator_to_responder)
+        };
+        Ok(TransportCiphers {
+            send,
+            recv,
+            remote_static: self.remote_static.expect("the remote static key is either transmitted or known in advance"),
+        })
+    }
+}
+
+/// The local encryption policy of P2P connections
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EncryptionPolicy {
+    /// Never encrypt connections
+    #[default]
+    Disabled,
+    /// Encrypt connections with peers supporting it, fall back to plain connections otherwise
+    Opportunistic,
+    /// Reject peers which do not support encryption
+    Required,
+}
+
+impl Display for EncryptionPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            EncryptionPolicy::Disabled => "disabled",
+            EncryptionPolicy::Opportunistic => "opportunistic",
+            EncryptionPolicy::Required => "required",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Transport security settings shared by all P2P connections of a node
+#[derive(Clone)]
+pub struct TransportSecurity {
+    policy: EncryptionPolicy,
+    static_keypair: Keypair,
+    /// When set, only peers authenticating with one of these static keys are accepted
+    trusted_keys: Option<Arc<HashSet<PublicKey>>>,
+}
+
+impl TransportSecurity {
+    pub fn new(policy: EncryptionPolicy, static_secret: SecretKey) -> Self {
+        Self { policy, static_keypair: Keypair::from_secret_key(SECP256K1, &static_secret), trusted_keys: None }
+    }
+
+    /// Restricts connections to peers owning one of the given static keys. Implies [`EncryptionPolicy::Required`].
+    pub fn with_trusted_keys(self, trusted_keys: impl IntoIterator<Item = PublicKey>) -> Self {
+        Self { policy: EncryptionPolicy::Required, trusted_keys: Some(Arc::new(trusted_keys.into_iter().collect())), ..self }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(EncryptionPolicy::Disabled, SecretKey::new(&mut rand::thread_rng()))
+    }
+
+    pub fn policy(&self) -> EncryptionPolicy {
+        self.policy
+    }
+
+    pub fn static_keypair(&self) -> &Keypair {
+        &self.static_keypair
+    }
+
+    pub fn static_public_key(&self) -> PublicKey {
+        self.static_keypair.public_key()
+    }
+
+    pub fn is_whitelist(&self) -> bool {
+        self.trusted_keys.is_some()
+    }
+
+    /// The service bits to advertise in the local version message
+    pub fn services(&self) -> u64 {
+        match self.policy {
+            EncryptionPolicy::Disabled => 0,
+            EncryptionPolicy::Opportunistic | EncryptionPolicy::Required => SERVICE_ENCRYPTED_TRANSPORT,
+        }
+    }
+
+    /// Decides whether the connection should be encrypted given the peer advertised services.
+    /// Returns `None` if the peer must be rejected.
+    pub fn should_encrypt(&self, peer_services: u64) -> Option<bool> {
+        let peer_supports = peer_services & SERVICE_ENCRYPTED_TRANSPORT != 0;
+        match self.policy {
+            EncryptionPolicy::Disabled => Some(false),
+            EncryptionPolicy::Opportunistic => Some(peer_supports),
+            EncryptionPolicy::Required => peer_supports.then_some(true),
+        }
+    }
+
+    pub fn is_trusted(&self, remote_static: &PublicKey) -> bool {
+        self.trusted_keys.as_ref().is_none_or(|keys| keys.contains(remote_static))
+    }
+}
+
+impl Default for TransportSecurity {
+    fn default() -> Self {
+        Self::disabled()
+    }
+}
+
+/// Parses a hex-encoded compressed secp256k1 public key
+pub fn parse_static_key(hex_key: &str) -> Result<PublicKey, secp256k1::Error> {
+    PublicKey::from_str(hex_key.trim())
+}
+
+/// Loads the hex-encoded static secret key from `path`, creating and persisting a new random key if the file does not exist.
+/// The key file is only readable by its owner.
+pub fn load_or_create_static_key(path: &Path) -> io::Result<SecretKey> {
+    match fs::read_to_string(path) {
+        Ok(content) => {
+            let secret = SecretKey::from_str(content.trim()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            restrict_permissions(path)?;
+            Ok(secret)
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            let secret = SecretKey::new(&mut rand::thread_rng());
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            write_static_key(path, &secret)?;
+            Ok(secret)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Writes the key to a temporary file created with owner-only permissions, then moves it in place,
+/// so that the key file is never partially written nor readable by others
+fn write_static_key(path: &Path, secret: &SecretKey) -> io::Result<()> {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    let temp_path = PathBuf::from(temp_path);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(&temp_path)?;
+    // The mode only applies to newly created files, so a stale temporary file is restricted explicitly
+    restrict_permissions(&temp_path)?;
+    file.write_all(secret.display_secret().to_string().as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temp_path, path)
+}
+
+#[cfg(unix)]
+fn restrict_permissions(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let permissions = fs::metadata(path)?.permissions();
+    if permissions.mode() & 0o077 != 0 {
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keypair() -> Keypair {
+        Keypair::new(SECP256K1, &mut rand::thread_rng())
+    }
+
+    fn run_handshake(prologue_i: &[u8], prologue_r: &[u8]) -> NoiseResult<(TransportCiphers, TransportCiphers)> {
+        let (static_i, static_r) = (keypair(), keypair());
+        let mut initiator = HandshakeState::new(true, static_i, prologue_i);
+        let mut responder = HandshakeState::new(false, static_r, prologue_r);
+
+        let msg1 = initiator.write_message(&[])?;
+        responder.read_message(&msg1)?;
+        let msg2 = responder.write_message(&[])?;
+        initiator.read_message(&msg2)?;
+        let msg3 = initiator.write_message(b"payload")?;
+        assert_eq!(responder.read_message(&msg3)?, b"payload");
+
+        let (ciphers_i, ciphers_r) = (initiator.into_transport()?, responder.into_transport()?);
+        assert_eq!(ciphers_i.remote_static, static_r.public_key());
+        assert_eq!(ciphers_r.remote_static, static_i.public_key());
+        Ok((ciphers_i, ciphers_r))
+    }
+
+    #[test]
+    fn test_handshake_and_transport() {
+        let (mut ciphers_i, mut ciphers_r) = run_handshake(b"kaspa-mainnet", b"kaspa-mainnet").unwrap();
+
+        for i in 0..4u8 {
+            let ciphertext = ciphers_i.send.encrypt(&[i; 100]).unwrap();
+            assert_ne!(&ciphertext[..100], &[i; 100]);
+            assert_eq!(ciphers_r.recv.decrypt(&ciphertext).unwrap(), [i; 100]);
+
+            let ciphertext = ciphers_r.send.encrypt(&[i; 10]).unwrap();
+            assert_eq!(ciphers_i.recv.decrypt(&ciphertext).unwrap(), [i; 10]);
+        }
+
+        // Tampered ciphertexts are rejected
+        let mut ciphertext = ciphers_i.send.encrypt(b"hello").unwrap();
+        ciphertext[0] ^= 1;
+        assert_eq!(ciphers_r.recv.decrypt(&ciphertext), Err(NoiseError::Decryption));
+
+        // Out of order (replayed) messages are rejected since nonces are implicit
+        let first = ciphers_r.send.encrypt(b"first").unwrap();
+        let second = ciphers_r.send.encrypt(b"second").unwrap();
+        assert_eq!(ciphers_i.recv.decrypt(&second), Err(NoiseError::Decryption));
+        assert_eq!(ciphers_i.recv.decrypt(&first), Err(NoiseError::Decryption));
+    }
+
+    /// Runs the BOLT-8 handshake and transport test vectors. BOLT-8 uses the XK pattern, but the very same DH, HKDF,
+    /// cipher and hash as our XX handshake, and its acts are processed by the same handshake code. Each act is
+    /// prefixed by a version byte, and each transport message is sent as its encrypted length followed by its
+    /// encrypted body.
+    #[test]
+    fn test_bolt8_vectors() {
+        const XK_PATTERN: Pattern = &[&[Token::E, Token::ES], &[Token::E, Token::EE], &[Token::S, Token::SE]];
+        let keypair = |byte: &str| Keypair::from_secret_key(SECP256K1, &SecretKey::from_str(&byte.repeat(32)).unwrap());
+        let (static_i, static_r) = (keypair("11"), keypair("21"));
+        assert_eq!(
+            hex::encode(static_i.public_key().serialize()),
+            "034f355bdcb7cc0af728ef3cceb9615d90684bb5b2ca5f859ab0f0b704075871aa"
+        );
+        assert_eq!(
+            hex::encode(static_r.public_key().serialize()),
+            "028d7500dd4c12685d1f568b4c2b5048e8534b873319f3a8daa612b469132ec7f7"
+        );
+
+        // The responder static key is a pre-message of the XK pattern
+        let new_state = |initiator: bool, local_static: Keypair, ephemeral: Keypair| {
+            let mut symmetric = SymmetricState::with_protocol_name(b"Noise_XK_secp256k1_ChaChaPoly_SHA256", b"lightning");
+            symmetric.mix_hash(&static_r.public_key().serialize());
+            let remote_static = initiator.then(|| static_r.public_key());
+            let mut state = HandshakeState::with_pattern(initiator, XK_PATTERN, symmetric, local_static, remote_static);
+            state.local_ephemeral = Some(ephemeral);
+            state
+        };
+        let mut initiator = new_state(true, static_i, keypair("12"));
+        let mut responder = new_state(false, static_r, keypair("22"));
+        let act = |message: Vec<u8>| [&[0u8][..], &message].concat();
+
+        let act_one = act(initiator.write_message(&[]).unwrap());
+        assert_eq!(
+            hex::encode(&act_one),
+            "00036360e856310ce5d294e8be33fc807077dc56ac80d95d9cd4ddbd21325eff73f70df6086551151f58b8afe6c195782c6a"
+        );
+        assert_eq!(responder.read_message(&act_one[1..]).unwrap(), Vec::<u8>::new());
+
+        let act_two = act(responder.write_message(&[]).unwrap());
+        assert_eq!(
+            hex::encode(&act_two),
+            "0002466d7fcae563e5cb09a0d1870bb580344804617879a14949cf22285f1bae3f276e2470b93aac583c9ef6eafca3f730ae"
+        );
+        assert_eq!(initiator.read_message(&act_two[1..]).unwrap(), Vec::<u8>::new());
+
+        let act_three = act(initiator.write_message(&[]).unwrap());
+        assert_eq!(
+            hex::encode(&act_three),
+            "00b9e3a702e93e3a9948c2ed6e5fd7590a6e1c3a0344cfc9d5b57357049aa22355361aa02e55a8fc28fef5bd6d71ad0c38228dc68b1c466263b47fdf31e560e139ba"
+        );
+        assert_eq!(responder.read_message(&act_three[1..]).unwrap(), Vec::<u8>::new());
+
+        let (mut ciphers_i, mut ciphers_r) = (initiator.into_transport().unwrap(), responder.into_transport().unwrap());
+        assert_eq!(ciphers_r.remote_static, static_i.public_key());
+
+        // The transport keys of the initiator, checked against ciphers built over the expected keys
+        let key = |hex_key: &str| <[u8; HASH_LEN]>::try_from(hex::decode(hex_key).unwrap()).unwrap();
+        let mut expected_send = CipherState::new(&key("969ab31b4d288cedf6218839b27a3e2140827047f2c0f01bf5c04435d43511a9"));
+        let mut expected_recv = CipherState::new(&key("bb9020b8965f4df047e07f955f3c4b88418984aadc5cdb35096b9ea8fa5c3442"));
+        for _ in 0..2 {
+            let ciphertext = expected_recv.encrypt(b"key").unwrap();
+            assert_eq!(ciphers_i.recv.decrypt(&ciphertext).unwrap(), b"key");
+            assert_eq!(ciphers_r.send.encrypt(b"key").unwrap(), ciphertext);
+        }
+
+        // The first transport messages, sent before the BOLT-8 key rotation which happens after 1000 messages
+        let message = b"hello";
+        for expected in [
+            "cf2b30ddf0cf3f80e7c35a6e6730b59fe802473180f396d88a8fb0db8cbcf25d2f214cf9ea1d95",
+            "72887022101f0b6753e0c7de21657d35a4cb2a1f5cde2650528bbc8f837d0f0d7ad833b1a256a1",
+        ] {
+            let length = ciphers_i.send.encrypt(&(message.len() as u16).to_be_bytes()).unwrap();
+            let body = ciphers_i.send.encrypt(message).unwrap();
+            assert_eq!(hex::encode([length.as_slice(), &body].concat()), expected);
+            assert_eq!(expected_send.encrypt(&(message.len() as u16).to_be_bytes()).unwrap(), length);
+            assert_eq!(expected_send.encrypt(message).unwrap(), body);
+            assert_eq!(ciphers_r.recv.decrypt(&length).unwrap(), (message.len() as u16).to_be_bytes());
+            assert_eq!(ciphers_r.recv.decrypt(&body).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn test_static_key_file() {
+        let dir = std::env::temp_dir().join(format!("kaspa-p2p-key-{}", rand::random::<u64>()));
+        let path = dir.join("p2p.key");
+        let secret = load_or_create_static_key(&path).unwrap();
+        assert_eq!(load_or_create_static_key(&path).unwrap(), secret);
+        assert!(!dir.join("p2p.key.tmp").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+            // Keys written by former versions are restricted when loaded
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+            assert_eq!(load_or_create_static_key(&path).unwrap(), secret);
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_prologue_mismatch() {
+        assert!(run_handshake(b"kaspa-mainnet", b"kaspa-testnet-10").is_err());
+    }
+
+    #[test]
+    fn test_out_of_order() {
+        let mut initiator = HandshakeState::new(true, keypair(), &[]);
+        assert_eq!(initiator.read_message(&[0; 33]), Err(NoiseError::OutOfOrder));
+        let mut responder = HandshakeState::new(false, keypair(), &[]);
+        assert_eq!(responder.write_message(&[]).err(), Some(NoiseError::OutOfOrder));
+        assert_eq!(responder.read_message(&[2; 10]), Err(NoiseError::MalformedMessage));
+        assert!(initiator.into_transport().is_err());
+    }
+
+    #[test]
+    fn test_policy() {
+        let secret = SecretKey::new(&mut rand::thread_rng());
+        let opportunistic = TransportSecurity::new(EncryptionPolicy::Opportunistic, secret);
+        assert_eq!(opportunistic.should_encrypt(0), Some(false));
+        assert_eq!(opportunistic.should_encrypt(SERVICE_ENCRYPTED_TRANSPORT), Some(true));
+
+        let required = TransportSecurity::new(EncryptionPolicy::Required, secret);
+        assert_eq!(required.should_encrypt(0), None);
+        assert_eq!(required.should_encrypt(SERVICE_ENCRYPTED_TRANSPORT), Some(true));
+
+        let trusted = keypair().public_key();
+        let whitelist = TransportSecurity::new(EncryptionPolicy::Opportunistic, secret).with_trusted_keys([trusted]);
+        assert_eq!(whitelist.policy(), EncryptionPolicy::Required);
+        assert!(whitelist.is_trusted(&trusted));
+        assert!(!whitelist.is_trusted(&keypair().public_key()));
+        assert!(required.is_trusted(&keypair().public_key()));
+
+        assert_eq!(TransportSecurity::disabled().services(), 0);
+        assert_eq!(TransportSecurity::disabled().should_encrypt(SERVICE_ENCRYPTED_TRANSPORT), Some(false));
+    }
+}
@@ -0,0 +1,255 @@
+use itertools::Itertools;
+use kaspa_consensus_core::{
+    constants::{SOMPI_PER_KASPA, TX_VERSION},
+    subnets::SUBNETWORK_ID_NATIVE,
+    tx::{ScriptPublicKey, Transaction, TransactionInput, TransactionOutput},
+};
+use rand::RngCore;
+use secp256k1::rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    profile::TxKind,
+    wallet::{SpendKind, Utxo, UtxoPool, Wallet},
+};
+
+pub const DEFAULT_SEND_AMOUNT: u64 = 10 * SOMPI_PER_KASPA;
+const FEE_RATE: u64 = 10;
+
+/// Maximum number of inputs of all transaction kinds but consolidations
+const MAX_UTXOS: usize = 8;
+const MAX_CONSOLIDATION_UTXOS: usize = 32;
+
+pub struct TxConfig {
+    pub priority_fee: u64,
+    pub randomize_fee: bool,
+    pub payload_size: usize,
+    /// Number of outputs of fan-out transactions
+    pub fan_out: usize,
+    /// Number of source keys merged by fan-in transactions
+    pub fan_in: usize,
+    /// Destination of self transfers, defaulting to the wallet keys
+    pub to_script_public_key: Option<ScriptPublicKey>,
+}
+
+/// The inputs and outputs of a transaction to generate
+pub struct TxPlan {
+    pub kind: TxKind,
+    pub utxos: Vec<Utxo>,
+    pub outputs: Vec<TransactionOutput>,
+    /// Additional fee paid by a replacement of the transaction, if any
+    pub replacement_fee: Option<u64>,
+}
+
+pub struct GeneratedTx {
+    pub kind: TxKind,
+    pub tx: Transaction,
+    pub replacement: Option<Transaction>,
+    pub utxos_len: usize,
+    pub utxos_amount: u64,
+}
+
+pub fn required_fee(num_utxos: usize, num_outs: u64) -> u64 {
+    FEE_RATE * estimated_mass(num_utxos, num_outs)
+}
+
+fn estimated_mass(num_utxos: usize, num_outs: u64) -> u64 {
+    200 + 34 * num_outs + 1000 * (num_utxos as u64)
+}
+
+/// The minimum amount a transaction must send, keeping the outputs large enough for their storage mass to remain low
+fn min_send_amount(num_outs: usize) -> u64 {
+    DEFAULT_SEND_AMOUNT * num_outs.max(2) as u64 / 2
+}
+
+/// Selects the UTXOs and outputs of a transaction of `kind`, or returns `None` if there are not enough funds.
+///
+/// Kinds which cannot be generated with the current UTXO distribution fall back to kinds which move the wallet
+/// towards such a distribution: fan-ins require funds held by several keys and fall back to fan-outs, while
+/// consolidations require several UTXOs of a single key and fall back to self transfers.
+pub fn plan_tx(
+    kind: TxKind,
+    pool: &mut UtxoPool,
+    wallet: &Wallet,
+    config: &TxConfig,
+    maximize_inputs: bool,
+    rng: &mut impl Rng,
+) -> Option<TxPlan> {
+    let mut priority_fee =
+        if config.randomize_fee && config.priority_fee > 0 { rng.gen_range(0..config.priority_fee) } else { config.priority_fee };
+    let mut utxos = Vec::new();
+    let mut replacement_fee = None;
+
+    let destinations = match kind {
+        TxKind::SelfTransfer | TxKind::Rbf => {
+            let num_outs = if maximize_inputs || kind == TxKind::Rbf { 1 } else { 2 };
+            let destination = config
+                .to_script_public_key
+                .clone()
+                .unwrap_or_else(|| wallet.key(rng.gen_range(0..wallet.len())).script_public_key(SpendKind::PubKey));
+            if kind == TxKind::Rbf {
+                // The transaction must afford the fee of its replacement as well
+                let fee = required_fee(MAX_UTXOS, 1);
+                replacement_fee = Some(fee);
+                priority_fee += fee;
+            }
+            let take_largest = |pool: &mut UtxoPool| pool.take_largest(None, SpendKind::PubKey);
+            select_utxos(pool, &mut utxos, take_largest, num_outs, priority_fee, MAX_UTXOS, maximize_inputs)?;
+            vec![destination; num_outs]
+        }
+        TxKind::FanOut => {
+            let first = rng.gen_range(0..wallet.len());
+            let destinations =
+                (0..config.fan_out).map(|i| wallet.key((first + i) % wallet.len()).script_public_key(SpendKind::PubKey)).collect_vec();
+            let take_largest = |pool: &mut UtxoPool| pool.take_largest(None, SpendKind::PubKey);
+            select_utxos(pool, &mut utxos, take_largest, destinations.len(), priority_fee, MAX_UTXOS, false)?;
+            destinations
+        }
+        TxKind::FanIn => {
+            let mut funded_keys = pool.funded_keys(SpendKind::PubKey);
+            if funded_keys.len() < 2 {
+                return plan_tx(TxKind::FanOut, pool, wallet, config, maximize_inputs, rng);
+            }
+            funded_keys.shuffle(rng);
+            utxos.extend(
+                funded_keys
+                    .into_iter()
+                    .take(config.fan_in.clamp(2, MAX_UTXOS))
+                    .filter_map(|key| pool.take_largest(Some(key), SpendKind::PubKey)),
+            );
+            let take_largest = |pool: &mut UtxoPool| pool.take_largest(None, SpendKind::PubKey);
+            select_utxos(pool, &mut utxos, take_largest, 1, priority_fee, MAX_UTXOS, false)?;
+            vec![wallet.key(rng.gen_range(0..wallet.len())).script_public_key(SpendKind::PubKey)]
+        }
+        TxKind::Consolidation => {
+            let key = (0..wallet.len()).max_by_key(|&key| pool.count(key, SpendKind::PubKey)).unwrap();
+            if pool.count(key, SpendKind::PubKey) < 2 {
+                return plan_tx(TxKind::SelfTransfer, pool, wallet, config, maximize_inputs, rng);
+            }
+            let take_smallest = |pool: &mut UtxoPool| pool.take_smallest(key, SpendKind::PubKey);
+            select_utxos(pool, &mut utxos, take_smallest, 1, priority_fee, MAX_CONSOLIDATION_UTXOS, true)?;
+            vec![wallet.key(key).script_public_key(SpendKind::PubKey)]
+        }
+        TxKind::P2sh => {
+            let key = wallet.key(rng.gen_range(0..wallet.len()));
+            let take_largest = |pool: &mut UtxoPool| {
+                pool.take_largest(None, SpendKind::ScriptHash).or_else(|| pool.take_largest(None, SpendKind::PubKey))
+            };
+            select_utxos(pool, &mut utxos, take_largest, 2, priority_fee, MAX_UTXOS, false)?;
+            vec![key.script_public_key(SpendKind::ScriptHash), key.script_public_key(SpendKind::PubKey)]
+        }
+    };
+
+    // The fee of the replacement is deducted from the outputs of the replacement only
+    let fees = required_fee(utxos.len(), destinations.len() as u64) + priority_fee - replacement_fee.unwrap_or_default();
+    let amount = utxos.iter().map(|utxo| utxo.entry.amount).sum::<u64>() - fees;
+    let num_outs = destinations.len() as u64;
+    let outputs = destinations
+        .into_iter()
+        .map(|script_public_key| TransactionOutput { value: amount / num_outs, script_public_key })
+        .collect_vec();
+    Some(TxPlan { kind, utxos, outputs, replacement_fee })
+}
+
+/// Builds and signs the transaction, and its replacement if any, described by `plan`
+pub fn build_tx(plan: TxPlan, wallet: &Wallet, payload_size: usize) -> GeneratedTx {
+    let inputs = plan
+        .utxos
+        .iter()
+        .map(|utxo| TransactionInput { previous_outpoint: utxo.outpoint, signature_script: vec![], sequence: 0, sig_op_count: 1 })
+        .collect_vec();
+    let mut payload = vec![0u8; payload_size];
+    rand::thread_rng().fill_bytes(&mut payload);
+    let unsigned_tx = Transaction::new_non_finalized(TX_VERSION, inputs, plan.outputs, 0, SUBNETWORK_ID_NATIVE, 0, payload);
+
+    let replacement = plan.replacement_fee.map(|fee| {
+        let mut unsigned_replacement = unsigned_tx.clone();
+        unsigned_replacement.outputs[0].value -= fee;
+        let mut replacement = wallet.sign(unsigned_replacement, &plan.utxos);
+        replacement.finalize();
+        replacement
+    });
+    let mut tx = wallet.sign(unsigned_tx, &plan.utxos);
+    tx.finalize();
+
+    GeneratedTx {
+        kind: plan.kind,
+        tx,
+        replacement,
+        utxos_len: plan.utxos.len(),
+        utxos_amount: plan.utxos.iter().map(|utxo| utxo.entry.amount).sum(),
+    }
+}
+
+/// Takes UTXOs until they cover the sent amount and fees, or until `max_utxos` UTXOs are selected if `fill` is set
+fn select_utxos(
+    pool: &mut UtxoPool,
+    selected: &mut Vec<Utxo>,
+    mut take: impl FnMut(&mut UtxoPool) -> Option<Utxo>,
+    num_outs: usize,
+    priority_fee: u64,
+    max_utxos: usize,
+    fill: bool,
+) -> Option<()> {
+    loop {
+        let amount = selected.iter().map(|utxo| utxo.entry.amount).sum::<u64>();
+        let fee = required_fee(selected.len(), num_outs as u64);
+        if !selected.is_empty() && amount >= min_send_amount(num_outs) + fee + priority_fee && (!fill || selected.len() == max_utxos) {
+            return Some(());
+        }
+        if selected.len() == max_utxos {
+            return None;
+        }
+        match take(pool) {
+            Some(utxo) => selected.push(utxo),
+            // A partial consolidation is still a consolidation
+            None if fill && selected.len() > 1 && amount >= min_send_amount(num_outs) + fee + priority_fee => return Some(()),
+            None => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::Prefix;
+    use kaspa_consensus_core::{
+        tx::{TransactionOutpoint, UtxoEntry},
+        Hash,
+    };
+    use secp256k1::{rand::thread_rng, Keypair};
+
+    #[test]
+    fn test_plan_tx() {
+        let mut rng = thread_rng();
+        let wallet = Wallet::new(Keypair::new(secp256k1::SECP256K1, &mut rng), 4, Prefix::Testnet);
+        let config =
+            TxConfig { priority_fee: 0, randomize_fee: false, payload_size: 0, fan_out: 4, fan_in: 2, to_script_public_key: None };
+        let utxo = |i: u64| Utxo {
+            outpoint: TransactionOutpoint::new(Hash::from_u64_word(i), 0),
+            entry: UtxoEntry::new(1000 * SOMPI_PER_KASPA, wallet.key(0).script_public_key(SpendKind::PubKey), 0, false),
+            key: 0,
+            kind: SpendKind::PubKey,
+        };
+
+        // With a single funded key, fan-ins fall back to fan-outs spreading the funds over the wallet keys
+        let mut pool = UtxoPool::new(vec![utxo(1), utxo(2)]);
+        let plan = plan_tx(TxKind::FanIn, &mut pool, &wallet, &config, false, &mut rng).unwrap();
+        assert_eq!(plan.kind, TxKind::FanOut);
+        assert_eq!(plan.outputs.len(), 4);
+        assert_eq!(plan.outputs.iter().map(|output| wallet.owner(&output.script_public_key).unwrap().0).unique().count(), 4);
+
+        // A single UTXO cannot be consolidated
+        let plan = plan_tx(TxKind::Consolidation, &mut pool, &wallet, &config, false, &mut rng).unwrap();
+        assert_eq!(plan.kind, TxKind::SelfTransfer);
+        assert!(plan_tx(TxKind::SelfTransfer, &mut pool, &wallet, &config, false, &mut rng).is_none());
+
+        // Replacements pay a higher fee for the same inputs
+        let mut pool = UtxoPool::new(vec![utxo(3)]);
+        let plan = plan_tx(TxKind::Rbf, &mut pool, &wallet, &config, false, &mut rng).unwrap();
+        let generated = build_tx(plan, &wallet, 10);
+        let replacement = generated.replacement.unwrap();
+        assert_eq!(replacement.inputs[0].previous_outpoint, generated.tx.inputs[0].previous_outpoint);
+        assert_eq!(replacement.outputs[0].value + required_fee(MAX_UTXOS, 1), generated.tx.outputs[0].value);
+        assert_ne!(replacement.id(), generated.tx.id());
+    }
+}
@@ -0,0 +1,164 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_channel::Sender;
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_core::{info, warn};
+use kaspa_grpc_client::GrpcClient;
+use kaspa_notify::{listener::ListenerId, notifier::Notify, scope::VirtualChainChangedScope};
+use kaspa_rpc_core::{api::rpc::RpcApi, Notification};
+use parking_lot::Mutex;
+
+use crate::profile::TxKind;
+
+/// Forwards the notifications received by a client to a channel
+#[derive(Debug)]
+struct ChannelNotify {
+    sender: Sender<Notification>,
+}
+
+impl Notify<Notification> for ChannelNotify {
+    fn notify(&self, notification: Notification) -> kaspa_notify::error::Result<()> {
+        self.sender.try_send(notification)?;
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct KindStats {
+    submitted: u64,
+    failed: u64,
+    replaced: u64,
+    accepted: u64,
+    /// Delays from submission to acceptance (seconds)
+    latencies: Vec<f64>,
+}
+
+#[derive(Default)]
+struct TrackerInner {
+    in_flight: HashMap<TransactionId, (Instant, TxKind)>,
+    stats: BTreeMap<TxKind, KindStats>,
+    expired: u64,
+}
+
+/// Measures the delay from the submission of transactions to their acceptance by the virtual selected chain
+pub struct LatencyTracker {
+    inner: Mutex<TrackerInner>,
+    start: Instant,
+}
+
+impl Default for LatencyTracker {
+    fn default() -> Self {
+        Self { inner: Default::default(), start: Instant::now() }
+    }
+}
+
+impl LatencyTracker {
+    /// Subscribes `rpc_client` to virtual chain changes and feeds the accepted transactions to the tracker
+    pub async fn start(self: &Arc<Self>, rpc_client: &GrpcClient) {
+        let (sender, receiver) = async_channel::unbounded();
+        rpc_client.start(Some(Arc::new(ChannelNotify { sender }))).await;
+        rpc_client
+            .start_notify(ListenerId::default(), VirtualChainChangedScope::new(true).into())
+            .await
+            .expect("Critical error: failed to subscribe to virtual chain changes.");
+        let tracker = self.clone();
+        tokio::spawn(async move {
+            while let Ok(notification) = receiver.recv().await {
+                if let Notification::VirtualChainChanged(notification) = notification {
+                    tracker.accepted(notification.accepted_transaction_ids.iter().flat_map(|ids| ids.accepted_transaction_ids.iter()));
+                }
+            }
+        });
+    }
+
+    /// Registers a transaction about to be submitted
+    pub fn submitted(&self, id: TransactionId, kind: TxKind) {
+        let mut inner = self.inner.lock();
+        inner.in_flight.insert(id, (Instant::now(), kind));
+        inner.stats.entry(kind).or_default().submitted += 1;
+    }
+
+    /// Registers the rejection of a submitted transaction
+    pub fn failed(&self, id: TransactionId) {
+        let mut inner = self.inner.lock();
+        if let Some((_, kind)) = inner.in_flight.remove(&id) {
+            inner.stats.entry(kind).or_default().failed += 1;
+        }
+    }
+
+    /// Registers the replacement of a submitted transaction, which is then not expected to be accepted
+    pub fn replaced(&self, id: TransactionId) {
+        let mut inner = self.inner.lock();
+        if let Some((_, kind)) = inner.in_flight.remove(&id) {
+            inner.stats.entry(kind).or_default().replaced += 1;
+        }
+    }
+
+    fn accepted<'a>(&self, ids: impl Iterator<Item = &'a TransactionId>) {
+        let now = Instant::now();
+        let mut inner = self.inner.lock();
+        for id in ids {
+            if let Some((submitted, kind)) = inner.in_flight.remove(id) {
+                let stats = inner.stats.entry(kind).or_default();
+                stats.accepted += 1;
+                stats.latencies.push(now.duration_since(submitted).as_secs_f64());
+            }
+        }
+    }
+
+    /// Returns the number of submitted transactions which were neither accepted nor rejected yet
+    pub fn in_flight(&self) -> usize {
+        self.inner.lock().in_flight.len()
+    }
+
+    /// Stops tracking the transactions submitted more than `max_age` ago
+    pub fn expire(&self, max_age: Duration) {
+        let now = Instant::now();
+        let mut inner = self.inner.lock();
+        let before = inner.in_flight.len();
+        inner.in_flight.retain(|_, (submitted, _)| now.duration_since(*submitted) <= max_age);
+        inner.expired += (before - inner.in_flight.len()) as u64;
+    }
+
+    pub fn log_report(&self) {
+        let elapsed = self.start.elapsed().as_secs_f64();
+        let inner = self.inner.lock();
+        let submitted = inner.stats.values().map(|stats| stats.submitted).sum::<u64>();
+        let accepted = inner.stats.values().map(|stats| stats.accepted).sum::<u64>();
+        info!(
+            "Workload report after {:.0}s: {} transactions submitted ({:.1}/sec), {} accepted ({:.1}/sec), {} still unaccepted, {} expired",
+            elapsed,
+            submitted,
+            submitted as f64 / elapsed,
+            accepted,
+            accepted as f64 / elapsed,
+            inner.in_flight.len(),
+            inner.expired,
+        );
+        for (kind, stats) in inner.stats.iter() {
+            let mut latencies = stats.latencies.clone();
+            latencies.sort_by(f64::total_cmp);
+            let percentile =
+                |p: f64| latencies.get(((latencies.len() as f64 - 1.0) * p).round() as usize).copied().unwrap_or_default();
+            info!(
+                "\t{}: submitted: {}, accepted: {}, failed: {}, replaced: {}, acceptance latency (s): mean {:.2}, median {:.2}, p95 {:.2}, max {:.2}",
+                kind,
+                stats.submitted,
+                stats.accepted,
+                stats.failed,
+                stats.replaced,
+                if latencies.is_empty() { 0.0 } else { latencies.iter().sum::<f64>() / latencies.len() as f64 },
+                percentile(0.5),
+                percentile(0.95),
+                latencies.last().copied().unwrap_or_default(),
+            );
+        }
+        if submitted > 0 && accepted == 0 {
+            warn!("None of the submitted transactions was accepted by the virtual chain");
+        }
+    }
+}
@@ -1,14 +1,11 @@
-use std::{collections::HashMap, sync::Arc, time::Duration};
+use std::{collections::HashMap, pin::pin, sync::Arc, time::Duration};
 
-use clap::{Arg, ArgAction, Command};
+use clap::{builder::PossibleValuesParser, Arg, ArgAction, Command};
 use itertools::Itertools;
 use kaspa_addresses::{Address, Prefix, Version};
 use kaspa_consensus_core::{
     config::params::TESTNET_PARAMS,
-    constants::{SOMPI_PER_KASPA, TX_VERSION},
-    sign::sign,
-    subnets::SUBNETWORK_ID_NATIVE,
-    tx::{MutableTransaction, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
+    tx::{TransactionOutpoint, UtxoEntry},
 };
 use kaspa_core::{info, kaspad_env::version, time::unix_now, warn};
 use kaspa_grpc_client::{ClientPool, GrpcClient};
@@ -16,19 +13,27 @@ use kaspa_notify::subscription::context::SubscriptionContext;
 use kaspa_rpc_core::{api::rpc::RpcApi, notify::mode::NotificationMode, RpcUtxoEntry};
 use kaspa_txscript::pay_to_address_script;
 use parking_lot::Mutex;
-use rand::RngCore;
 use rayon::prelude::*;
-use secp256k1::{
-    rand::{thread_rng, Rng},
-    Keypair,
-};
+use secp256k1::{rand::thread_rng, Keypair};
 use tokio::time::{interval, Instant, MissedTickBehavior};
 
-const DEFAULT_SEND_AMOUNT: u64 = 10 * SOMPI_PER_KASPA;
-const FEE_RATE: u64 = 10;
+use crate::{
+    generator::{build_tx, plan_tx, GeneratedTx, TxConfig},
+    latency::LatencyTracker,
+    profile::{Profile, TpsCurve},
+    wallet::{Utxo, UtxoPool, Wallet},
+};
+
+mod generator;
+mod latency;
+mod profile;
+mod wallet;
+
 const MILLIS_PER_TICK: u64 = 10;
 const ADDRESS_PREFIX: Prefix = Prefix::Testnet;
 const ADDRESS_VERSION: Version = Version::PubKey;
+/// Maximum time to wait for the acceptance of the submitted transactions when stopping
+const ACCEPTANCE_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
 
 struct Stats {
     num_txs: usize,
@@ -48,6 +53,14 @@ pub struct Args {
     pub priority_fee: u64,
     pub randomize_fee: bool,
     pub payload_size: usize,
+    pub profile: Profile,
+    pub keys: usize,
+    pub fan_out: usize,
+    pub fan_in: usize,
+    pub tps_curve: String,
+    pub curve_period: f64,
+    pub curve_factor: Option<f64>,
+    pub duration: Option<u64>,
 }
 
 impl Args {
@@ -63,6 +76,14 @@ impl Args {
             priority_fee: m.get_one::<u64>("priority-fee").cloned().unwrap_or(0),
             randomize_fee: m.get_one::<bool>("randomize-fee").cloned().unwrap_or(false),
             payload_size: m.get_one::<usize>("payload-size").cloned().unwrap_or(0),
+            profile: m.get_one::<String>("profile").unwrap().parse().unwrap(),
+            keys: m.get_one::<usize>("keys").cloned().unwrap(),
+            fan_out: m.get_one::<usize>("fan-out").cloned().unwrap(),
+            fan_in: m.get_one::<usize>("fan-in").cloned().unwrap(),
+            tps_curve: m.get_one::<String>("tps-curve").cloned().unwrap(),
+            curve_period: m.get_one::<f64>("curve-period").cloned().unwrap(),
+            curve_factor: m.get_one::<f64>("curve-factor").cloned(),
+            duration: m.get_one::<u64>("duration").cloned(),
         }
     }
 }
@@ -97,7 +118,7 @@ pub fn cli() -> Command {
                 .help("The number of threads to use for TX generation. Set to 0 to use 1 thread per core. Default is 2."),
         )
         .arg(Arg::new("unleashed").long("unleashed").action(ArgAction::SetTrue).hide(true).help("Allow higher TPS"))
-        .arg(Arg::new("addr").long("to-addr").short('a').value_name("addr").help("address to send to"))
+        .arg(Arg::new("addr").long("to-addr").short('a').value_name("addr").help("address to send self transfers to"))
         .arg(
             Arg::new("priority-fee")
                 .long("priority-fee")
@@ -126,6 +147,68 @@ pub fn cli() -> Command {
                 .value_parser(clap::value_parser!(usize))
                 .help("Randomized payload size"),
         )
+        .arg(
+            Arg::new("profile")
+                .long("profile")
+                .value_name("profile")
+                .default_value("self-transfer")
+                .value_parser(PossibleValuesParser::new(Profile::NAMES))
+                .help("The kinds of transactions to generate. The mixed profile combines all of them."),
+        )
+        .arg(
+            Arg::new("keys")
+                .long("keys")
+                .value_name("keys")
+                .default_value("1")
+                .value_parser(clap::value_parser!(usize))
+                .help("The number of source keys, derived from the private key, to spread the funds over"),
+        )
+        .arg(
+            Arg::new("fan-out")
+                .long("fan-out")
+                .value_name("fan-out")
+                .default_value("10")
+                .value_parser(clap::value_parser!(usize))
+                .help("The number of outputs of fan-out transactions"),
+        )
+        .arg(
+            Arg::new("fan-in")
+                .long("fan-in")
+                .value_name("fan-in")
+                .default_value("4")
+                .value_parser(clap::value_parser!(usize))
+                .help("The number of source keys merged by fan-in transactions"),
+        )
+        .arg(
+            Arg::new("tps-curve")
+                .long("tps-curve")
+                .value_name("tps-curve")
+                .default_value("constant")
+                .value_parser(PossibleValuesParser::new(TpsCurve::NAMES))
+                .help("The evolution of the TPS over time, averaging to the target TPS"),
+        )
+        .arg(
+            Arg::new("curve-period")
+                .long("curve-period")
+                .value_name("seconds")
+                .default_value("60")
+                .value_parser(clap::value_parser!(f64))
+                .help("The period of the bursty and diurnal TPS curves"),
+        )
+        .arg(
+            Arg::new("curve-factor")
+                .long("curve-factor")
+                .value_name("curve-factor")
+                .value_parser(clap::value_parser!(f64))
+                .help("The burst factor of the bursty TPS curve (default 5), or the amplitude of the diurnal TPS curve (default 0.5)"),
+        )
+        .arg(
+            Arg::new("duration")
+                .long("duration")
+                .value_name("seconds")
+                .value_parser(clap::value_parser!(u64))
+                .help("Stop and report the workload statistics after the given time. Runs until interrupted if unset."),
+        )
 }
 
 async fn new_rpc_client(subscription_context: &SubscriptionContext, address: &str) -> GrpcClient {
@@ -144,18 +227,10 @@ async fn new_rpc_client(subscription_context: &SubscriptionContext, address: &st
 }
 
 struct ClientPoolArg {
-    tx: Transaction,
+    generated: GeneratedTx,
     stats: Arc<Mutex<Stats>>,
-    selected_utxos_len: usize,
-    selected_utxos_amount: u64,
-    pending_len: usize,
-    utxos_len: usize,
-}
-
-struct TxConfig {
-    priority_fee: u64,
-    randomize_fee: bool,
-    payload_size: usize,
+    tracker: Arc<LatencyTracker>,
+    available_utxos: usize,
 }
 
 #[tokio::main]
@@ -197,25 +272,45 @@ async fn main() {
         return;
     };
 
-    let kaspa_addr = Address::new(ADDRESS_PREFIX, ADDRESS_VERSION, &schnorr_key.x_only_public_key().0.serialize());
+    let wallet = Wallet::new(schnorr_key, args.keys, ADDRESS_PREFIX);
+    let kaspa_addr = wallet.key(0).address.clone();
 
-    let kaspa_to_addr = args.addr.as_ref().map_or_else(|| kaspa_addr.clone(), |addr_str| Address::try_from(addr_str.clone()).unwrap());
+    let kaspa_to_addr = args.addr.as_ref().map(|addr_str| Address::try_from(addr_str.clone()).unwrap());
 
     (args.payload_size <= 20000).then_some(()).expect("payload-size can be max 20000");
-
-    let tx_config = TxConfig { priority_fee: args.priority_fee, randomize_fee: args.randomize_fee, payload_size: args.payload_size };
+    (2..=100).contains(&args.fan_out).then_some(()).expect("fan-out must be between 2 and 100");
+    (args.fan_in >= 2).then_some(()).expect("fan-in must be at least 2");
+
+    let curve = TpsCurve::new(&args.tps_curve, args.curve_period, args.curve_factor).unwrap();
+
+    let tx_config = TxConfig {
+        priority_fee: args.priority_fee,
+        randomize_fee: args.randomize_fee,
+        payload_size: args.payload_size,
+        fan_out: args.fan_out,
+        fan_in: args.fan_in,
+        to_script_public_key: kaspa_to_addr.as_ref().map(pay_to_address_script),
+    };
 
     rayon::ThreadPoolBuilder::new().num_threads(args.threads as usize).build_global().unwrap();
 
     let mut log_message = format!(
         "Using Rothschild with:\n\
         \tprivate key: {}\n\
-        \tfrom address: {}",
+        \tfrom address: {}\n\
+        \tprofile: {}",
         schnorr_key.display_secret(),
-        String::from(&kaspa_addr)
+        String::from(&kaspa_addr),
+        args.profile,
     );
-    if args.addr.is_some() {
-        log_message.push_str(&format!("\n\tto address: {}", String::from(&kaspa_to_addr)));
+    if wallet.len() > 1 {
+        log_message.push_str(&format!("\n\tsource keys: {}", wallet.len()));
+    }
+    if let Some(kaspa_to_addr) = kaspa_to_addr.as_ref() {
+        log_message.push_str(&format!("\n\tto address: {}", String::from(kaspa_to_addr)));
+    }
+    if curve != TpsCurve::Constant {
+        log_message.push_str(&format!("\n\tTPS curve: {:?}", curve));
     }
     if args.priority_fee != 0 {
         log_message.push_str(&format!(
@@ -251,6 +346,9 @@ async fn main() {
         coinbase_maturity,
     );
 
+    let tracker = Arc::new(LatencyTracker::default());
+    tracker.start(&rpc_client).await;
+
     const CLIENT_POOL_SIZE: usize = 8;
     let mut rpc_clients = Vec::with_capacity(CLIENT_POOL_SIZE);
     for _ in 0..CLIENT_POOL_SIZE {
@@ -259,35 +357,48 @@ async fn main() {
 
     let submit_tx_pool = ClientPool::new(rpc_clients, 1000);
     let _ = submit_tx_pool.start(|c, arg: ClientPoolArg| async move {
-        let ClientPoolArg { tx, stats, selected_utxos_len, selected_utxos_amount, pending_len, utxos_len } = arg;
+        let ClientPoolArg { generated, stats, tracker, available_utxos } = arg;
+        let GeneratedTx { kind, tx, replacement, utxos_len, utxos_amount } = generated;
+        tracker.submitted(tx.id(), kind);
         match c.submit_transaction(tx.as_ref().into(), false).await {
             Ok(_) => {
-                let mut stats = stats.lock();
-                stats.num_txs += 1;
-                stats.num_utxos += selected_utxos_len;
-                stats.utxos_amount += selected_utxos_amount;
-                stats.num_outs += tx.outputs.len();
-                let now = unix_now();
-                let time_past = now - stats.since;
-                if time_past > 10_000 {
-                    info!(
-                        "Tx rate: {:.1}/sec, avg UTXO amount: {}, avg UTXOs per tx: {}, avg outs per tx: {}, estimated available UTXOs: {}",
-                        1000f64 * (stats.num_txs as f64) / (time_past as f64),
-                        stats.utxos_amount / stats.num_utxos as u64,
-                        stats.num_utxos / stats.num_txs,
-                        stats.num_outs / stats.num_txs,
-                        utxos_len.saturating_sub(pending_len),
-                    );
-                    stats.since = now;
-                    stats.num_txs = 0;
-                    stats.num_utxos = 0;
-                    stats.utxos_amount = 0;
-                    stats.num_outs = 0;
+                {
+                    let mut stats = stats.lock();
+                    stats.num_txs += 1;
+                    stats.num_utxos += utxos_len;
+                    stats.utxos_amount += utxos_amount;
+                    stats.num_outs += tx.outputs.len();
+                    let now = unix_now();
+                    let time_past = now - stats.since;
+                    if time_past > 10_000 {
+                        info!(
+                            "Tx rate: {:.1}/sec, avg UTXO amount: {}, avg UTXOs per tx: {}, avg outs per tx: {}, estimated available UTXOs: {}",
+                            1000f64 * (stats.num_txs as f64) / (time_past as f64),
+                            stats.utxos_amount / stats.num_utxos as u64,
+                            stats.num_utxos / stats.num_txs,
+                            stats.num_outs / stats.num_txs,
+                            available_utxos,
+                        );
+                        stats.since = now;
+                        stats.num_txs = 0;
+                        stats.num_utxos = 0;
+                        stats.utxos_amount = 0;
+                        stats.num_outs = 0;
+                    }
+                }
+                if let Some(replacement) = replacement {
+                    tracker.submitted(replacement.id(), kind);
+                    match c.submit_transaction_replacement(replacement.as_ref().into()).await {
+                        Ok(_) => tracker.replaced(tx.id()),
+                        Err(e) => {
+                            tracker.failed(replacement.id());
+                            warn!("RPC error when submitting replacement {} of {}: {}", replacement.id(), tx.id(), e);
+                        }
+                    }
                 }
             }
             Err(e) => {
-                let mut tx = tx;
-                tx.finalize();
+                tracker.failed(tx.id());
                 warn!("RPC error when submitting {}: {}", tx.id(), e);
             }
         }
@@ -295,44 +406,48 @@ async fn main() {
     });
     let tx_sender = submit_tx_pool.sender();
 
-    let target_tps = args.tps.min(if args.unleashed { u64::MAX } else { 100 });
-    let should_tick_per_second = target_tps * MILLIS_PER_TICK / 1000 == 0;
-    let avg_txs_per_tick = if should_tick_per_second { target_tps } else { target_tps * MILLIS_PER_TICK / 1000 };
-    let mut utxos = refresh_utxos(&rpc_client, kaspa_addr.clone(), &mut pending, coinbase_maturity).await;
-    let mut ticker = interval(Duration::from_millis(if should_tick_per_second { 1000 } else { MILLIS_PER_TICK }));
+    let target_tps = args.tps.min(if args.unleashed { u64::MAX } else { 100 }) as f64;
+    let mut utxos = refresh_utxos(&rpc_client, &wallet, &mut pending, coinbase_maturity).await;
+    let mut ticker = interval(Duration::from_millis(MILLIS_PER_TICK));
     ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
     let mut maximize_inputs = false;
     let mut last_refresh = unix_now();
-    // This allows us to keep track of the UTXOs we already tried to use for this period
-    // until the UTXOs are refreshed. At that point, this will be reset as well.
-    let mut next_available_utxo_index = 0;
-    // Tracker so we can try to send as close as possible to the target TPS
-    let mut remaining_txs_in_interval = target_tps;
+    let start = Instant::now();
+    let deadline = args.duration.map(|duration| start + Duration::from_secs(duration));
+    let mut shutdown = pin!(tokio::signal::ctrl_c());
+    // Transactions owed according to the TPS curve, so we can send as close as possible to the target TPS
+    let mut budget = 0f64;
+    let mut last_tick = start;
 
     loop {
-        ticker.tick().await;
-        maximize_inputs = should_maximize_inputs(maximize_inputs, &utxos, &pending);
-        let txs_to_send = if remaining_txs_in_interval > avg_txs_per_tick * 2 {
-            remaining_txs_in_interval -= avg_txs_per_tick;
-            avg_txs_per_tick
-        } else {
-            let count = remaining_txs_in_interval;
-            remaining_txs_in_interval = target_tps;
-            count
-        };
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = &mut shutdown => break,
+        }
+        let now = Instant::now();
+        if deadline.is_some_and(|deadline| now >= deadline) {
+            break;
+        }
+        maximize_inputs = should_maximize_inputs(maximize_inputs, utxos.len());
+        let rate = curve.rate(target_tps, now.duration_since(start).as_secs_f64());
+        // Time lost to refreshes and pauses is not made up for beyond a second worth of transactions
+        budget = (budget + rate * now.duration_since(last_tick).as_secs_f64()).min(rate.max(1.0));
+        last_tick = now;
+        let txs_to_send = budget.floor() as u64;
+        budget -= txs_to_send as f64;
 
         let now = unix_now();
         let has_funds = maybe_send_tx(
             txs_to_send,
             &tx_sender,
-            kaspa_to_addr.clone(),
+            args.profile,
             &mut utxos,
             &mut pending,
-            schnorr_key,
+            &wallet,
             stats.clone(),
+            tracker.clone(),
             maximize_inputs,
-            &mut next_available_utxo_index,
             &tx_config,
         )
         .await;
@@ -342,25 +457,27 @@ async fn main() {
         if !has_funds || now - last_refresh > 60_000 {
             info!("Refetching UTXO set");
             tokio::time::sleep(Duration::from_millis(100)).await; // We don't want this operation to be too frequent since its heavy on the node, so we wait some time before executing it.
-            utxos = refresh_utxos(&rpc_client, kaspa_addr.clone(), &mut pending, coinbase_maturity).await;
+            utxos = refresh_utxos(&rpc_client, &wallet, &mut pending, coinbase_maturity).await;
             last_refresh = unix_now();
-            next_available_utxo_index = 0;
             pause_if_mempool_is_full(&rpc_client).await;
         }
         clean_old_pending_outpoints(&mut pending);
+        tracker.expire(Duration::from_secs(3600));
+    }
+
+    info!("Stopping transaction generation, waiting for the acceptance of {} transactions", tracker.in_flight());
+    let drain_deadline = Instant::now() + ACCEPTANCE_DRAIN_TIMEOUT;
+    while tracker.in_flight() > 0 && Instant::now() < drain_deadline {
+        tokio::time::sleep(Duration::from_millis(500)).await;
     }
+    tracker.log_report();
 }
 
-fn should_maximize_inputs(
-    old_value: bool,
-    utxos: &[(TransactionOutpoint, UtxoEntry)],
-    pending: &HashMap<TransactionOutpoint, Instant>,
-) -> bool {
-    let estimated_utxos = if utxos.len() > pending.len() { utxos.len() - pending.len() } else { 0 };
-    if !old_value && estimated_utxos > 1_000_000 {
+fn should_maximize_inputs(old_value: bool, available_utxos: usize) -> bool {
+    if !old_value && available_utxos > 1_000_000 {
         info!("Starting to maximize inputs");
         true
-    } else if old_value && estimated_utxos < 500_000 {
+    } else if old_value && available_utxos < 500_000 {
         info!("Stopping to maximize inputs");
         false
     } else {
@@ -383,20 +500,21 @@ async fn pause_if_mempool_is_full(rpc_client: &GrpcClient) {
 
 async fn refresh_utxos(
     rpc_client: &GrpcClient,
-    kaspa_addr: Address,
+    wallet: &Wallet,
     pending: &mut HashMap<TransactionOutpoint, Instant>,
     coinbase_maturity: u64,
-) -> Vec<(TransactionOutpoint, UtxoEntry)> {
-    populate_pending_outpoints_from_mempool(rpc_client, kaspa_addr.clone(), pending).await;
-    fetch_spendable_utxos(rpc_client, kaspa_addr, coinbase_maturity, pending).await
+) -> UtxoPool {
+    let addresses = wallet.addresses();
+    populate_pending_outpoints_from_mempool(rpc_client, addresses.clone(), pending).await;
+    fetch_spendable_utxos(rpc_client, addresses, wallet, coinbase_maturity, pending).await
 }
 
 async fn populate_pending_outpoints_from_mempool(
     rpc_client: &GrpcClient,
-    kaspa_addr: Address,
+    addresses: Vec<Address>,
     pending_outpoints: &mut HashMap<TransactionOutpoint, Instant>,
 ) {
-    let entries = rpc_client.get_mempool_entries_by_addresses(vec![kaspa_addr], true, false).await.unwrap();
+    let entries = rpc_client.get_mempool_entries_by_addresses(addresses, true, false).await.unwrap();
     let now = Instant::now();
 
     for entry in entries {
@@ -410,23 +528,27 @@ async fn populate_pending_outpoints_from_mempool(
 
 async fn fetch_spendable_utxos(
     rpc_client: &GrpcClient,
-    kaspa_addr: Address,
+    addresses: Vec<Address>,
+    wallet: &Wallet,
     coinbase_maturity: u64,
     pending: &mut HashMap<TransactionOutpoint, Instant>,
-) -> Vec<(TransactionOutpoint, UtxoEntry)> {
-    let resp = rpc_client.get_utxos_by_addresses(vec![kaspa_addr]).await.unwrap();
+) -> UtxoPool {
+    let resp = rpc_client.get_utxos_by_addresses(addresses).await.unwrap();
     let dag_info = rpc_client.get_block_dag_info().await.unwrap();
 
-    let mut utxos = resp.into_iter()
+    let utxos = resp.into_iter()
         .filter(|entry| {
             is_utxo_spendable(&entry.utxo_entry, dag_info.virtual_daa_score, coinbase_maturity)
         })
         .map(|entry| (TransactionOutpoint::from(entry.outpoint), UtxoEntry::from(entry.utxo_entry)))
         // Eliminates UTXOs we already tried to spend so we don't try to spend them again in this period
         .filter(|(outpoint,_)| !pending.contains_key(outpoint))
-        .collect::<Vec<_>>();
-    utxos.sort_by(|a, b| b.1.amount.cmp(&a.1.amount));
-    utxos
+        .filter_map(|(outpoint, entry)| {
+            let (key, kind) = wallet.owner(&entry.script_public_key)?;
+            Some(Utxo { outpoint, entry, key, kind })
+        })
+        .collect_vec();
+    UtxoPool::new(utxos)
 }
 
 fn is_utxo_spendable(entry: &RpcUtxoEntry, virtual_daa_score: u64, coinbase_maturity: u64) -> bool {
@@ -441,67 +563,39 @@ fn is_utxo_spendable(entry: &RpcUtxoEntry, virtual_daa_score: u64, coinbase_matu
 async fn maybe_send_tx(
     txs_to_send: u64,
     tx_sender: &async_channel::Sender<ClientPoolArg>,
-    kaspa_addr: Address,
-    utxos: &mut [(TransactionOutpoint, UtxoEntry)],
+    profile: Profile,
+    utxos: &mut UtxoPool,
     pending: &mut HashMap<TransactionOutpoint, Instant>,
-    schnorr_key: Keypair,
+    wallet: &Wallet,
     stats: Arc<Mutex<Stats>>,
+    tracker: Arc<LatencyTracker>,
     maximize_inputs: bool,
-    next_available_utxo_index: &mut usize,
     tx_config: &TxConfig,
 ) -> bool {
-    let num_outs = if maximize_inputs { 1 } else { 2 };
-
-    let mut has_fund = false;
-
-    let selected_utxos_groups = (0..txs_to_send)
-        .map(|_| {
-            let (selected_utxos, selected_amount) =
-                select_utxos(utxos, DEFAULT_SEND_AMOUNT, num_outs, maximize_inputs, next_available_utxo_index, tx_config);
-            if selected_amount == 0 {
-                return None;
-            }
-
-            // If any iteration successfully selected UTXOs, we assume to still
-            // have funds in this tick
-            has_fund = true;
-
-            let now = Instant::now();
-            for input in selected_utxos.iter() {
-                pending.insert(input.0, now);
-            }
+    if txs_to_send == 0 {
+        return true;
+    }
 
-            Some((selected_utxos, selected_amount))
-        })
-        .collect::<Vec<_>>();
+    let mut rng = thread_rng();
+    let plans = (0..txs_to_send)
+        .filter_map(|_| plan_tx(profile.next_kind(&mut rng), utxos, wallet, tx_config, maximize_inputs, &mut rng))
+        .collect_vec();
 
-    if !has_fund {
+    // If any iteration successfully selected UTXOs, we assume to still have funds in this tick
+    if plans.is_empty() {
         return false;
     }
 
-    let txs = selected_utxos_groups
-        .into_par_iter()
-        .map(|utxo_option| {
-            if let Some((selected_utxos, selected_amount)) = utxo_option {
-                let tx = generate_tx(schnorr_key, &selected_utxos, selected_amount, num_outs, &kaspa_addr, tx_config.payload_size);
-
-                return Some((tx, selected_utxos.len(), selected_utxos.into_iter().map(|(_, entry)| entry.amount).sum::<u64>()));
-            }
+    let now = Instant::now();
+    for utxo in plans.iter().flat_map(|plan| plan.utxos.iter()) {
+        pending.insert(utxo.outpoint, now);
+    }
 
-            None
-        })
-        .collect::<Vec<_>>();
+    let txs = plans.into_par_iter().map(|plan| build_tx(plan, wallet, tx_config.payload_size)).collect::<Vec<_>>();
 
-    for (tx, selected_utxos_len, selected_utxos_amount) in txs.into_iter().flatten() {
+    for generated in txs {
         tx_sender
-            .send(ClientPoolArg {
-                tx,
-                stats: stats.clone(),
-                selected_utxos_len,
-                selected_utxos_amount,
-                pending_len: pending.len(),
-                utxos_len: utxos.len(),
-            })
+            .send(ClientPoolArg { generated, stats: stats.clone(), tracker: tracker.clone(), available_utxos: utxos.len() })
             .await
             .unwrap();
     }
@@ -513,75 +607,3 @@ fn clean_old_pending_outpoints(pending: &mut HashMap<TransactionOutpoint, Instan
     let now = Instant::now();
     pending.retain(|_, &mut time| now.duration_since(time) <= Duration::from_secs(3600));
 }
-
-fn required_fee(num_utxos: usize, num_outs: u64) -> u64 {
-    FEE_RATE * estimated_mass(num_utxos, num_outs)
-}
-
-fn estimated_mass(num_utxos: usize, num_outs: u64) -> u64 {
-    200 + 34 * num_outs + 1000 * (num_utxos as u64)
-}
-
-fn generate_tx(
-    schnorr_key: Keypair,
-    utxos: &[(TransactionOutpoint, UtxoEntry)],
-    send_amount: u64,
-    num_outs: u64,
-    kaspa_addr: &Address,
-    payload_size: usize,
-) -> Transaction {
-    let script_public_key = pay_to_address_script(kaspa_addr);
-    let inputs = utxos
-        .iter()
-        .map(|(op, _)| TransactionInput { previous_outpoint: *op, signature_script: vec![], sequence: 0, sig_op_count: 1 })
-        .collect_vec();
-
-    let outputs = (0..num_outs)
-        .map(|_| TransactionOutput { value: send_amount / num_outs, script_public_key: script_public_key.clone() })
-        .collect_vec();
-    let mut data = vec![0u8; payload_size];
-    rand::thread_rng().fill_bytes(&mut data);
-    let unsigned_tx = Transaction::new_non_finalized(TX_VERSION, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, data);
-    let signed_tx =
-        sign(MutableTransaction::with_entries(unsigned_tx, utxos.iter().map(|(_, entry)| entry.clone()).collect_vec()), schnorr_key);
-    signed_tx.tx
-}
-
-fn select_utxos(
-    utxos: &[(TransactionOutpoint, UtxoEntry)],
-    min_amount: u64,
-    num_outs: u64,
-    maximize_utxos: bool,
-    next_available_utxo_index: &mut usize,
-    tx_config: &TxConfig,
-) -> (Vec<(TransactionOutpoint, UtxoEntry)>, u64) {
-    const MAX_UTXOS: usize = 8;
-    let mut selected_amount: u64 = 0;
-    let mut selected = Vec::new();
-    let mut rng = thread_rng();
-
-    while next_available_utxo_index < &mut utxos.len() {
-        let (outpoint, entry) = utxos[*next_available_utxo_index].clone();
-        selected_amount += entry.amount;
-        selected.push((outpoint, entry));
-
-        let fee = required_fee(selected.len(), num_outs);
-        let priority_fee = if tx_config.randomize_fee && tx_config.priority_fee > 0 {
-            rng.gen_range(0..tx_config.priority_fee)
-        } else {
-            tx_config.priority_fee
-        };
-
-        *next_available_utxo_index += 1;
-
-        if selected_amount >= min_amount + fee + priority_fee && (!maximize_utxos || selected.len() == MAX_UTXOS) {
-            return (selected, selected_amount - fee - priority_fee);
-        }
-
-        if selected.len() > MAX_UTXOS {
-            return (vec![], 0);
-        }
-    }
-
-    (vec![], 0)
-}
@@ -0,0 +1,201 @@
+use std::{f64::consts::PI, fmt::Display, str::FromStr};
+
+use secp256k1::rand::Rng;
+
+/// The kinds of transactions rothschild generates
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TxKind {
+    /// Spends the largest UTXOs to one or two outputs
+    SelfTransfer,
+    /// Splits a large UTXO into many outputs spread over the source keys
+    FanOut,
+    /// Merges UTXOs held by several source keys into a single output
+    FanIn,
+    /// Merges many small UTXOs of a single source key into a single output
+    Consolidation,
+    /// A self transfer followed by a replacement paying a higher fee
+    Rbf,
+    /// Spends pay-to-script-hash UTXOs, paying back to both a script hash and a public key
+    P2sh,
+}
+
+impl TxKind {
+    pub const ALL: [TxKind; 6] =
+        [TxKind::SelfTransfer, TxKind::FanOut, TxKind::FanIn, TxKind::Consolidation, TxKind::Rbf, TxKind::P2sh];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TxKind::SelfTransfer => "self-transfer",
+            TxKind::FanOut => "fan-out",
+            TxKind::FanIn => "fan-in",
+            TxKind::Consolidation => "consolidation",
+            TxKind::Rbf => "rbf",
+            TxKind::P2sh => "p2sh",
+        }
+    }
+}
+
+impl Display for TxKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The mix of transaction kinds generated by a `mixed` profile, as (kind, weight) pairs
+const MIXED_PROFILE_WEIGHTS: [(TxKind, u32); 6] = [
+    (TxKind::SelfTransfer, 30),
+    (TxKind::FanOut, 15),
+    (TxKind::FanIn, 15),
+    (TxKind::Consolidation, 10),
+    (TxKind::Rbf, 15),
+    (TxKind::P2sh, 15),
+];
+
+/// A workload profile, i.e. the kinds of transactions to generate
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    Single(TxKind),
+    Mixed,
+}
+
+impl Profile {
+    pub const NAMES: [&'static str; 7] = ["self-transfer", "fan-out", "fan-in", "consolidation", "rbf", "p2sh", "mixed"];
+
+    pub fn next_kind(&self, rng: &mut impl Rng) -> TxKind {
+        match self {
+            Profile::Single(kind) => *kind,
+            Profile::Mixed => {
+                let total = MIXED_PROFILE_WEIGHTS.iter().map(|(_, weight)| weight).sum::<u32>();
+                let mut pick = rng.gen_range(0..total);
+                for (kind, weight) in MIXED_PROFILE_WEIGHTS {
+                    if pick < weight {
+                        return kind;
+                    }
+                    pick -= weight;
+                }
+                unreachable!()
+            }
+        }
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "mixed" {
+            return Ok(Profile::Mixed);
+        }
+        TxKind::ALL.into_iter().find(|kind| kind.name() == s).map(Profile::Single).ok_or_else(|| format!("unknown profile {s}"))
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Profile::Single(kind) => kind.fmt(f),
+            Profile::Mixed => f.write_str("mixed"),
+        }
+    }
+}
+
+/// The evolution of the target TPS over time
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TpsCurve {
+    Constant,
+    /// The first `1 / factor` of every period is sent at `factor` times the TPS, and the rest of the period is idle
+    Bursty {
+        period: f64,
+        factor: f64,
+    },
+    /// The TPS follows a sine wave of the given period, deviating by up to `amplitude` times the TPS
+    Diurnal {
+        period: f64,
+        amplitude: f64,
+    },
+}
+
+impl TpsCurve {
+    pub const NAMES: [&'static str; 3] = ["constant", "bursty", "diurnal"];
+
+    pub fn new(name: &str, period: f64, factor: Option<f64>) -> Result<Self, String> {
+        if period <= 0.0 {
+            return Err("the curve period must be positive".to_string());
+        }
+        match name {
+            "constant" => Ok(TpsCurve::Constant),
+            "bursty" => {
+                let factor = factor.unwrap_or(5.0);
+                if factor < 1.0 {
+                    return Err("the burst factor must be at least 1".to_string());
+                }
+                Ok(TpsCurve::Bursty { period, factor })
+            }
+            "diurnal" => {
+                let amplitude = factor.unwrap_or(0.5);
+                if !(0.0..=1.0).contains(&amplitude) {
+                    return Err("the diurnal amplitude must be within [0, 1]".to_string());
+                }
+                Ok(TpsCurve::Diurnal { period, amplitude })
+            }
+            _ => Err(format!("unknown TPS curve {name}")),
+        }
+    }
+
+    /// Returns the target TPS after `elapsed` seconds, the average target being `tps`
+    pub fn rate(&self, tps: f64, elapsed: f64) -> f64 {
+        match *self {
+            TpsCurve::Constant => tps,
+            TpsCurve::Bursty { period, factor } => {
+                if elapsed % period < period / factor {
+                    tps * factor
+                } else {
+                    0.0
+                }
+            }
+            TpsCurve::Diurnal { period, amplitude } => tps * (1.0 + amplitude * (2.0 * PI * elapsed / period).sin()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secp256k1::rand::thread_rng;
+
+    #[test]
+    fn test_profiles() {
+        for name in Profile::NAMES {
+            assert_eq!(name.parse::<Profile>().unwrap().to_string(), name);
+        }
+        assert!("unknown".parse::<Profile>().is_err());
+
+        let mut rng = thread_rng();
+        assert_eq!(Profile::Single(TxKind::FanIn).next_kind(&mut rng), TxKind::FanIn);
+        let kinds = (0..1000).map(|_| Profile::Mixed.next_kind(&mut rng)).collect::<std::collections::HashSet<_>>();
+        assert_eq!(kinds.len(), TxKind::ALL.len());
+    }
+
+    #[test]
+    fn test_tps_curves() {
+        let bursty = TpsCurve::new("bursty", 10.0, Some(4.0)).unwrap();
+        assert_eq!(bursty.rate(100.0, 1.0), 400.0);
+        assert_eq!(bursty.rate(100.0, 3.0), 0.0);
+        assert_eq!(bursty.rate(100.0, 11.0), 400.0);
+
+        let diurnal = TpsCurve::new("diurnal", 100.0, None).unwrap();
+        assert_eq!(diurnal.rate(100.0, 0.0), 100.0);
+        assert!((diurnal.rate(100.0, 25.0) - 150.0).abs() < 1e-9);
+        assert!((diurnal.rate(100.0, 75.0) - 50.0).abs() < 1e-9);
+
+        // The average rate over a period is the target TPS
+        for curve in [TpsCurve::Constant, bursty, diurnal] {
+            let average = (0..200).map(|i| curve.rate(100.0, i as f64 * 0.5)).sum::<f64>() / 200.0;
+            assert!((average - 100.0).abs() < 1e-6, "{curve:?}: {average}");
+        }
+
+        assert!(TpsCurve::new("bursty", 10.0, Some(0.5)).is_err());
+        assert!(TpsCurve::new("diurnal", 10.0, Some(2.0)).is_err());
+        assert!(TpsCurve::new("constant", 0.0, None).is_err());
+    }
+}
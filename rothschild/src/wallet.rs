@@ -0,0 +1,261 @@
+use std::collections::{HashMap, VecDeque};
+
+use itertools::Itertools;
+use kaspa_addresses::{Address, Prefix, Version};
+use kaspa_consensus_core::{
+    hashing::sighash_type::SIG_HASH_ALL,
+    sign::sign_input,
+    tx::{MutableTransaction, ScriptPublicKey, Transaction, TransactionOutpoint, UtxoEntry},
+};
+use kaspa_txscript::{
+    extract_script_pub_key_address, pay_to_address_script, pay_to_script_hash_script, pay_to_script_hash_signature_script,
+};
+use secp256k1::{Keypair, Scalar};
+
+/// The way a wallet UTXO is locked
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpendKind {
+    /// Pay to the public key of a wallet key
+    PubKey,
+    /// Pay to the hash of a redeem script checking the signature of a wallet key
+    ScriptHash,
+}
+
+pub struct WalletKey {
+    pub keypair: Keypair,
+    pub address: Address,
+    pub p2sh_address: Address,
+    redeem_script: Vec<u8>,
+}
+
+impl WalletKey {
+    fn new(keypair: Keypair, prefix: Prefix) -> Self {
+        let address = Address::new(prefix, Version::PubKey, &keypair.x_only_public_key().0.serialize());
+        let redeem_script = pay_to_address_script(&address).script().to_vec();
+        let p2sh_address = extract_script_pub_key_address(&pay_to_script_hash_script(&redeem_script), prefix).unwrap();
+        Self { keypair, address, p2sh_address, redeem_script }
+    }
+
+    pub fn script_public_key(&self, kind: SpendKind) -> ScriptPublicKey {
+        match kind {
+            SpendKind::PubKey => pay_to_address_script(&self.address),
+            SpendKind::ScriptHash => pay_to_script_hash_script(&self.redeem_script),
+        }
+    }
+}
+
+/// A set of source keys derived from a single private key. Key `i` is the private key tweaked by `i`, so that
+/// all the keys, and the funds they hold, are recoverable from the private key alone.
+pub struct Wallet {
+    keys: Vec<WalletKey>,
+    owners: HashMap<ScriptPublicKey, (usize, SpendKind)>,
+}
+
+impl Wallet {
+    pub fn new(primary: Keypair, num_keys: usize, prefix: Prefix) -> Self {
+        let keys = (0..num_keys.max(1) as u64)
+            .map(|i| {
+                if i == 0 {
+                    return WalletKey::new(primary, prefix);
+                }
+                let mut tweak = [0u8; 32];
+                tweak[24..].copy_from_slice(&i.to_be_bytes());
+                let secret_key = primary.secret_key().add_tweak(&Scalar::from_be_bytes(tweak).unwrap()).unwrap();
+                WalletKey::new(Keypair::from_secret_key(secp256k1::SECP256K1, &secret_key), prefix)
+            })
+            .collect_vec();
+        let owners = keys
+            .iter()
+            .enumerate()
+            .flat_map(|(i, key)| [SpendKind::PubKey, SpendKind::ScriptHash].map(|kind| (key.script_public_key(kind), (i, kind))))
+            .collect();
+        Self { keys, owners }
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn key(&self, index: usize) -> &WalletKey {
+        &self.keys[index]
+    }
+
+    /// Returns the P2PK and P2SH addresses of all the wallet keys
+    pub fn addresses(&self) -> Vec<Address> {
+        self.keys.iter().flat_map(|key| [key.address.clone(), key.p2sh_address.clone()]).collect()
+    }
+
+    /// Returns the wallet key index and spend kind of a script public key owned by the wallet
+    pub fn owner(&self, script_public_key: &ScriptPublicKey) -> Option<(usize, SpendKind)> {
+        self.owners.get(script_public_key).copied()
+    }
+
+    /// Signs every input of `tx`, spending `utxos` respectively, with the key owning the spent UTXO
+    pub fn sign(&self, tx: Transaction, utxos: &[Utxo]) -> Transaction {
+        let mut signable_tx = MutableTransaction::with_entries(tx, utxos.iter().map(|utxo| utxo.entry.clone()).collect_vec());
+        let signature_scripts = utxos
+            .iter()
+            .enumerate()
+            .map(|(i, utxo)| {
+                let key = &self.keys[utxo.key];
+                let signature = sign_input(&signable_tx.as_verifiable(), i, &key.keypair.secret_bytes(), SIG_HASH_ALL);
+                match utxo.kind {
+                    SpendKind::PubKey => signature,
+                    SpendKind::ScriptHash => pay_to_script_hash_signature_script(key.redeem_script.clone(), signature).unwrap(),
+                }
+            })
+            .collect_vec();
+        for (input, signature_script) in signable_tx.tx.inputs.iter_mut().zip(signature_scripts) {
+            input.signature_script = signature_script;
+        }
+        signable_tx.tx
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Utxo {
+    pub outpoint: TransactionOutpoint,
+    pub entry: UtxoEntry,
+    pub key: usize,
+    pub kind: SpendKind,
+}
+
+/// The spendable wallet UTXOs, bucketed by key and spend kind. UTXOs taken out of the pool are not returned to it,
+/// they are either spent or become available again on the next UTXO refresh.
+#[derive(Default)]
+pub struct UtxoPool {
+    // Each bucket is sorted by ascending amount
+    buckets: HashMap<(usize, SpendKind), VecDeque<Utxo>>,
+    len: usize,
+}
+
+impl UtxoPool {
+    pub fn new(utxos: Vec<Utxo>) -> Self {
+        let len = utxos.len();
+        let mut buckets: HashMap<_, Vec<_>> = HashMap::new();
+        for utxo in utxos {
+            buckets.entry((utxo.key, utxo.kind)).or_default().push(utxo);
+        }
+        let buckets = buckets
+            .into_iter()
+            .map(|(bucket, mut utxos)| {
+                utxos.sort_by_key(|utxo| utxo.entry.amount);
+                (bucket, utxos.into())
+            })
+            .collect();
+        Self { buckets, len }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the number of UTXOs of `kind` held by `key`
+    pub fn count(&self, key: usize, kind: SpendKind) -> usize {
+        self.buckets.get(&(key, kind)).map_or(0, |bucket| bucket.len())
+    }
+
+    /// Returns the keys holding UTXOs of `kind`
+    pub fn funded_keys(&self, kind: SpendKind) -> Vec<usize> {
+        self.buckets.iter().filter(|((_, k), bucket)| *k == kind && !bucket.is_empty()).map(|(&(key, _), _)| key).sorted().collect()
+    }
+
+    /// Takes the largest UTXO of `kind`, held by `key` or by any key if `key` is `None`
+    pub fn take_largest(&mut self, key: Option<usize>, kind: SpendKind) -> Option<Utxo> {
+        let bucket = match key {
+            Some(key) => self.buckets.get_mut(&(key, kind))?,
+            None => self
+                .buckets
+                .iter_mut()
+                .filter(|((_, k), bucket)| *k == kind && !bucket.is_empty())
+                .max_by_key(|(_, bucket)| bucket.back().unwrap().entry.amount)
+                .map(|(_, bucket)| bucket)?,
+        };
+        let utxo = bucket.pop_back()?;
+        self.len -= 1;
+        Some(utxo)
+    }
+
+    /// Takes the smallest UTXO of `kind` held by `key`
+    pub fn take_smallest(&mut self, key: usize, kind: SpendKind) -> Option<Utxo> {
+        let utxo = self.buckets.get_mut(&(key, kind))?.pop_front()?;
+        self.len -= 1;
+        Some(utxo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        constants::TX_VERSION,
+        hashing::sighash::SigHashReusedValuesUnsync,
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{TransactionInput, TransactionOutput, VerifiableTransaction},
+        Hash,
+    };
+    use kaspa_txscript::{caches::Cache, TxScriptEngine};
+    use secp256k1::rand::thread_rng;
+
+    #[test]
+    fn test_wallet_signing() {
+        let wallet = Wallet::new(Keypair::new(secp256k1::SECP256K1, &mut thread_rng()), 3, Prefix::Testnet);
+        assert_eq!(wallet.addresses().len(), 6);
+        assert_ne!(wallet.key(0).address, wallet.key(1).address);
+
+        // Spend a P2PK UTXO of one key along with a P2SH UTXO of another key
+        let utxos = [(0, SpendKind::PubKey), (2, SpendKind::ScriptHash)]
+            .into_iter()
+            .enumerate()
+            .map(|(i, (key, kind))| {
+                let script_public_key = wallet.key(key).script_public_key(kind);
+                assert_eq!(wallet.owner(&script_public_key), Some((key, kind)));
+                Utxo {
+                    outpoint: TransactionOutpoint::new(Hash::from_u64_word(i as u64 + 1), 0),
+                    entry: UtxoEntry::new(1000, script_public_key, 0, false),
+                    key,
+                    kind,
+                }
+            })
+            .collect_vec();
+        let inputs = utxos
+            .iter()
+            .map(|utxo| TransactionInput { previous_outpoint: utxo.outpoint, signature_script: vec![], sequence: 0, sig_op_count: 1 })
+            .collect_vec();
+        let outputs = vec![TransactionOutput { value: 1500, script_public_key: wallet.key(1).script_public_key(SpendKind::PubKey) }];
+        let tx = Transaction::new(TX_VERSION, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let tx = MutableTransaction::with_entries(wallet.sign(tx, &utxos), utxos.iter().map(|utxo| utxo.entry.clone()).collect());
+
+        let tx = tx.as_verifiable();
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let cache = Cache::new(10);
+        for (i, (input, entry)) in tx.populated_inputs().enumerate() {
+            let mut engine = TxScriptEngine::from_transaction_input(&tx, input, i, entry, &reused_values, &cache);
+            engine.execute().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_utxo_pool() {
+        let utxo = |key: usize, kind: SpendKind, amount: u64| Utxo {
+            outpoint: TransactionOutpoint::new(Hash::from_u64_word(amount), 0),
+            entry: UtxoEntry::new(amount, Default::default(), 0, false),
+            key,
+            kind,
+        };
+        let mut pool = UtxoPool::new(vec![
+            utxo(0, SpendKind::PubKey, 10),
+            utxo(0, SpendKind::PubKey, 30),
+            utxo(1, SpendKind::PubKey, 20),
+            utxo(1, SpendKind::ScriptHash, 50),
+        ]);
+        assert_eq!(pool.len(), 4);
+        assert_eq!(pool.funded_keys(SpendKind::PubKey), vec![0, 1]);
+        assert_eq!(pool.take_largest(None, SpendKind::PubKey).unwrap().entry.amount, 30);
+        assert_eq!(pool.take_largest(None, SpendKind::PubKey).unwrap().entry.amount, 20);
+        assert_eq!(pool.take_smallest(0, SpendKind::PubKey).unwrap().entry.amount, 10);
+        assert!(pool.take_largest(None, SpendKind::PubKey).is_none());
+        assert_eq!(pool.funded_keys(SpendKind::ScriptHash), vec![1]);
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -9,7 +9,7 @@ use kaspa_notify::{
     notification::{full_featured, Notification as NotificationTrait},
     subscription::{
         context::SubscriptionContext,
-        single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
+        single::{AddressFilteredSubscription, OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
         Subscription,
     },
 };
@@ -48,6 +48,12 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "MempoolTransactionAdded notification: {} outputs, fee {}", "_0.transaction.outputs.len()", "_0.fee")]
+    MempoolTransactionAdded(MempoolTransactionAddedNotification),
+
+    #[display(fmt = "MempoolTransactionRemoved notification: {} outputs, reason {}", "_0.transaction.outputs.len()", "_0.reason")]
+    MempoolTransactionRemoved(MempoolTransactionRemovedNotification),
 }
 }
 
@@ -64,6 +70,8 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::MempoolTransactionAdded(v) => to_value(&v),
+            Notification::MempoolTransactionRemoved(v) => to_value(&v),
         }
     }
 }
@@ -89,6 +97,7 @@ impl NotificationTrait for Notification {
                             removed_chain_block_hashes: payload.removed_chain_block_hashes.clone(),
                             added_chain_block_hashes: payload.added_chain_block_hashes.clone(),
                             accepted_transaction_ids: Arc::new(vec![]),
+                            cursor: payload.cursor,
                         }));
                     }
                 }
@@ -112,6 +121,28 @@ impl NotificationTrait for Notification {
         }
     }
 
+    fn apply_address_filtered_subscription(
+        &self,
+        subscription: &AddressFilteredSubscription,
+        context: &SubscriptionContext,
+    ) -> Option<Self> {
+        match subscription.active() {
+            true => match self {
+                Self::BlockAdded(notification) => {
+                    notification.apply_address_filtered_subscription(subscription, context).map(Self::BlockAdded)
+                }
+                Self::MempoolTransactionAdded(notification) => {
+                    notification.apply_address_filtered_subscription(subscription, context).map(Self::MempoolTransactionAdded)
+                }
+                Self::MempoolTransactionRemoved(notification) => {
+                    notification.apply_address_filtered_subscription(subscription, context).map(Self::MempoolTransactionRemoved)
+                }
+                _ => None,
+            },
+            false => None,
+        }
+    }
+
     fn event_type(&self) -> EventType {
         self.into()
     }
@@ -157,6 +188,14 @@ impl Serializer for Notification {
                 store!(u16, &8, writer)?;
                 serialize!(NewBlockTemplateNotification, notification, writer)?;
             }
+            Notification::MempoolTransactionAdded(notification) => {
+                store!(u16, &9, writer)?;
+                serialize!(MempoolTransactionAddedNotification, notification, writer)?;
+            }
+            Notification::MempoolTransactionRemoved(notification) => {
+                store!(u16, &10, writer)?;
+                serialize!(MempoolTransactionRemovedNotification, notification, writer)?;
+            }
         }
         Ok(())
     }
@@ -202,6 +241,14 @@ impl Deserializer for Notification {
                 let notification = deserialize!(NewBlockTemplateNotification, reader)?;
                 Ok(Notification::NewBlockTemplate(notification))
             }
+            9 => {
+                let notification = deserialize!(MempoolTransactionAddedNotification, reader)?;
+                Ok(Notification::MempoolTransactionAdded(notification))
+            }
+            10 => {
+                let notification = deserialize!(MempoolTransactionRemovedNotification, reader)?;
+                Ok(Notification::MempoolTransactionRemoved(notification))
+            }
             _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid variant")),
         }
     }
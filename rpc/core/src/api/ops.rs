@@ -27,6 +27,8 @@ pub enum RpcApiOps {
     // subscription management
     Subscribe = 3,
     Unsubscribe = 4,
+    /// Subscribe while replaying the notifications missed since a previous cursor
+    ResumeSubscription = 5,
 
     // ~~~
 
@@ -40,6 +42,8 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged = 16,
     NotifyVirtualChainChanged = 17,
     NotifySinkBlueScoreChanged = 18,
+    NotifyMempoolTransactionAdded = 19,
+    NotifyMempoolTransactionRemoved = 20,
 
     // Notification ops required by wRPC
 
@@ -54,6 +58,8 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification = 66,
     PruningPointUtxoSetOverrideNotification = 67,
     NewBlockTemplateNotification = 68,
+    MempoolTransactionAddedNotification = 69,
+    MempoolTransactionRemovedNotification = 70,
 
     // RPC methods
     /// Ping the node to check if connection is alive
@@ -72,7 +78,7 @@ pub enum RpcApiOps {
     GetCurrentNetwork = 116,
     /// Extracts a block out of the request message and attempts to add it to the DAG Returns an empty response or an error message
     SubmitBlock = 117,
-    /// Returns a "template" by which a miner can mine a new block
+    /// Returns a "template" by which a miner can mine a new block. A requested payout split is only recorded in the coinbase payload, the whole reward is paid on-chain to the pay address
     GetBlockTemplate = 118,
     /// Returns a list of all the addresses (IP, port) this Kaspad knows and a list of all addresses that are currently banned by this Kaspad
     GetPeerAddresses = 119,
@@ -140,6 +146,20 @@ pub enum RpcApiOps {
     GetUtxoReturnAddress = 150,
     /// Get Virtual Chain from Block V2
     GetVirtualChainFromBlockV2 = 151,
+    /// Get the recorded fee estimate history along with realized transaction inclusions
+    GetFeeEstimateHistory = 152,
+    /// Reload the mempool policy file
+    ReloadMempoolPolicy = 153,
+    /// Get aggregated mempool state, feerate histogram and per-transaction dependency and eviction details
+    GetMempoolInfo = 154,
+    /// Get the dependency graph of a mempool transaction
+    GetMempoolTransactionGraph = 155,
+    /// Apply a virtual fee delta to a transaction
+    PrioritiseTransaction = 156,
+    /// Get the transactions having a virtual fee delta
+    GetPrioritisedTransactions = 157,
+    /// Get whether mining is allowed and the state of the mining rules
+    GetMiningStatus = 158,
 }
 
 impl RpcApiOps {
@@ -155,8 +175,11 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifyMempoolTransactionAdded
+                | RpcApiOps::NotifyMempoolTransactionRemoved
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
+                | RpcApiOps::ResumeSubscription
         )
     }
 }
@@ -181,6 +204,8 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::MempoolTransactionAdded => RpcApiOps::MempoolTransactionAddedNotification,
+            EventType::MempoolTransactionRemoved => RpcApiOps::MempoolTransactionRemovedNotification,
         }
     }
 }
@@ -132,6 +132,18 @@ pub trait RpcApi: Sync + Send + AnySync {
     async fn get_block_template(&self, pay_address: RpcAddress, extra_data: RpcExtraData) -> RpcResult<GetBlockTemplateResponse> {
         self.get_block_template_call(None, GetBlockTemplateRequest::new(pay_address, extra_data)).await
     }
+
+    /// Request a current block template recording a weighted split of the block reward among `payouts`.
+    ///
+    /// The split is metadata only, the whole reward is paid on-chain to `pay_address`, see [`GetBlockTemplateRequest::payouts`].
+    async fn get_block_template_with_payouts(
+        &self,
+        pay_address: RpcAddress,
+        extra_data: RpcExtraData,
+        payouts: Vec<RpcCoinbasePayout>,
+    ) -> RpcResult<GetBlockTemplateResponse> {
+        self.get_block_template_call(None, GetBlockTemplateRequest::with_payouts(pay_address, extra_data, payouts)).await
+    }
     async fn get_block_template_call(
         &self,
         connection: Option<&DynRpcConnection>,
@@ -472,6 +484,93 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetFeeEstimateExperimentalRequest,
     ) -> RpcResult<GetFeeEstimateExperimentalResponse>;
 
+    /// Exports the first page of the fee estimate history recorded since `from_timestamp` (in milliseconds), optionally
+    /// replaying alternative estimator parameters against it. Further pages are requested through
+    /// [`GetFeeEstimateHistoryRequest::next_page`].
+    async fn get_fee_estimate_history(
+        &self,
+        from_timestamp: u64,
+        replay_params: Option<RpcFeeEstimateReplayParams>,
+    ) -> RpcResult<GetFeeEstimateHistoryResponse> {
+        self.get_fee_estimate_history_call(None, GetFeeEstimateHistoryRequest::new(from_timestamp, replay_params)).await
+    }
+    async fn get_fee_estimate_history_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetFeeEstimateHistoryRequest,
+    ) -> RpcResult<GetFeeEstimateHistoryResponse>;
+
+    /// Returns the aggregated mempool state along with a feerate histogram matching the fee estimator buckets.
+    /// If `include_entries` is set, the ancestor/descendant counts and eviction rank of every transaction are returned too,
+    /// which requires the node to run in unsafe RPC mode.
+    async fn get_mempool_info(&self, include_entries: bool) -> RpcResult<GetMempoolInfoResponse> {
+        self.get_mempool_info_call(None, GetMempoolInfoRequest { include_entries }).await
+    }
+    async fn get_mempool_info_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetMempoolInfoRequest,
+    ) -> RpcResult<GetMempoolInfoResponse>;
+
+    /// Returns the dependency graph made of a mempool transaction and all its mempool ancestors and descendants.
+    /// As the graph is not bounded, this requires the node to run in unsafe RPC mode.
+    async fn get_mempool_transaction_graph(&self, transaction_id: RpcTransactionId) -> RpcResult<GetMempoolTransactionGraphResponse> {
+        self.get_mempool_transaction_graph_call(None, GetMempoolTransactionGraphRequest { transaction_id }).await
+    }
+    async fn get_mempool_transaction_graph_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetMempoolTransactionGraphRequest,
+    ) -> RpcResult<GetMempoolTransactionGraphResponse>;
+
+    /// Adds a virtual fee delta, in sompi, to a transaction, whether already in the mempool or not, and returns the
+    /// resulting cumulative delta. The delta alters the priority of the transaction in block templates and replace by
+    /// fee evaluations without affecting its actual fee. Requires the node to run with unsafe RPC enabled.
+    async fn prioritise_transaction(
+        &self,
+        transaction_id: RpcTransactionId,
+        fee_delta: i64,
+    ) -> RpcResult<PrioritiseTransactionResponse> {
+        self.prioritise_transaction_call(None, PrioritiseTransactionRequest { transaction_id, fee_delta }).await
+    }
+    async fn prioritise_transaction_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: PrioritiseTransactionRequest,
+    ) -> RpcResult<PrioritiseTransactionResponse>;
+
+    /// Returns the transactions having a virtual fee delta.
+    /// Requires the node to run with unsafe RPC enabled.
+    async fn get_prioritised_transactions(&self) -> RpcResult<Vec<RpcPrioritisedTransaction>> {
+        Ok(self.get_prioritised_transactions_call(None, GetPrioritisedTransactionsRequest {}).await?.transactions)
+    }
+    async fn get_prioritised_transactions_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetPrioritisedTransactionsRequest,
+    ) -> RpcResult<GetPrioritisedTransactionsResponse>;
+
+    /// Returns whether the node currently allows mining, the reason of this decision and the state of every mining rule.
+    async fn get_mining_status(&self) -> RpcResult<GetMiningStatusResponse> {
+        self.get_mining_status_call(None, GetMiningStatusRequest {}).await
+    }
+    async fn get_mining_status_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetMiningStatusRequest,
+    ) -> RpcResult<GetMiningStatusResponse>;
+
+    /// Reloads the mempool policy file the node was started with and returns the active policies.
+    /// Requires the node to run with unsafe RPC enabled.
+    async fn reload_mempool_policy(&self) -> RpcResult<ReloadMempoolPolicyResponse> {
+        self.reload_mempool_policy_call(None, ReloadMempoolPolicyRequest {}).await
+    }
+    async fn reload_mempool_policy_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: ReloadMempoolPolicyRequest,
+    ) -> RpcResult<ReloadMempoolPolicyResponse>;
+
     ///
     async fn get_current_block_color(&self, hash: RpcHash) -> RpcResult<GetCurrentBlockColorResponse> {
         Ok(self.get_current_block_color_call(None, GetCurrentBlockColorRequest { hash }).await?)
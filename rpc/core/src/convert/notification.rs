@@ -2,7 +2,8 @@
 
 use crate::{
     convert::utxo::utxo_set_into_rpc, BlockAddedNotification, FinalityConflictNotification, FinalityConflictResolvedNotification,
-    NewBlockTemplateNotification, Notification, PruningPointUtxoSetOverrideNotification, RpcAcceptedTransactionIds,
+    MempoolTransactionAddedNotification, MempoolTransactionRemovedNotification, NewBlockTemplateNotification, Notification,
+    PruningPointUtxoSetOverrideNotification, RpcAcceptedTransactionIds, RpcMempoolTransactionRemovalReason,
     SinkBlueScoreChangedNotification, UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification,
 };
 use kaspa_consensus_notify::notification as consensus_notify;
@@ -31,6 +32,8 @@ impl From<&consensus_notify::Notification> for Notification {
             consensus_notify::Notification::VirtualDaaScoreChanged(msg) => Notification::VirtualDaaScoreChanged(msg.into()),
             consensus_notify::Notification::PruningPointUtxoSetOverride(msg) => Notification::PruningPointUtxoSetOverride(msg.into()),
             consensus_notify::Notification::NewBlockTemplate(msg) => Notification::NewBlockTemplate(msg.into()),
+            consensus_notify::Notification::MempoolTransactionAdded(msg) => Notification::MempoolTransactionAdded(msg.into()),
+            consensus_notify::Notification::MempoolTransactionRemoved(msg) => Notification::MempoolTransactionRemoved(msg.into()),
         }
     }
 }
@@ -65,6 +68,7 @@ impl From<&consensus_notify::VirtualChainChangedNotification> for VirtualChainCh
                     })
                     .collect()
             }),
+            cursor: Some(item.cursor.into()),
         }
     }
 }
@@ -112,6 +116,32 @@ impl From<&consensus_notify::NewBlockTemplateNotification> for NewBlockTemplateN
     }
 }
 
+impl From<&consensus_notify::MempoolTransactionAddedNotification> for MempoolTransactionAddedNotification {
+    fn from(item: &consensus_notify::MempoolTransactionAddedNotification) -> Self {
+        Self { transaction: Arc::new(item.transaction.as_ref().into()), fee: item.fee }
+    }
+}
+
+impl From<&consensus_notify::MempoolTransactionRemovedNotification> for MempoolTransactionRemovedNotification {
+    fn from(item: &consensus_notify::MempoolTransactionRemovedNotification) -> Self {
+        Self { transaction: Arc::new(item.transaction.as_ref().into()), reason: item.reason.into() }
+    }
+}
+
+impl From<consensus_notify::MempoolTransactionRemovalReason> for RpcMempoolTransactionRemovalReason {
+    fn from(item: consensus_notify::MempoolTransactionRemovalReason) -> Self {
+        match item {
+            consensus_notify::MempoolTransactionRemovalReason::Accepted => Self::Accepted,
+            consensus_notify::MempoolTransactionRemovalReason::ReplacedByFee => Self::ReplacedByFee,
+            consensus_notify::MempoolTransactionRemovalReason::DoubleSpend => Self::DoubleSpend,
+            consensus_notify::MempoolTransactionRemovalReason::Evicted => Self::Evicted,
+            consensus_notify::MempoolTransactionRemovalReason::Expired => Self::Expired,
+            consensus_notify::MempoolTransactionRemovalReason::Invalid => Self::Invalid,
+            consensus_notify::MempoolTransactionRemovalReason::Unspecified => Self::Unspecified,
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // index to rpc_core
 // ----------------------------------------------------------------------------
@@ -141,6 +171,10 @@ impl From<&index_notify::UtxosChangedNotification> for UtxosChangedNotification
     // This is not intended to be ever called because no address prefix is available.
     // Use kaspa_rpc_service::converter::index::IndexConverter instead.
     fn from(item: &index_notify::UtxosChangedNotification) -> Self {
-        Self { added: Arc::new(utxo_set_into_rpc(&item.added, None)), removed: Arc::new(utxo_set_into_rpc(&item.removed, None)) }
+        Self {
+            added: Arc::new(utxo_set_into_rpc(&item.added, None)),
+            removed: Arc::new(utxo_set_into_rpc(&item.removed, None)),
+            cursor: item.cursor.map(|cursor| cursor.into()),
+        }
     }
 }
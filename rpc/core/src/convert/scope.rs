@@ -1,7 +1,8 @@
 //! Conversion of Notification Scope related types
 
 use crate::{
-    NotifyBlockAddedRequest, NotifyFinalityConflictRequest, NotifyNewBlockTemplateRequest, NotifyPruningPointUtxoSetOverrideRequest,
+    NotifyBlockAddedRequest, NotifyFinalityConflictRequest, NotifyMempoolTransactionAddedRequest,
+    NotifyMempoolTransactionRemovedRequest, NotifyNewBlockTemplateRequest, NotifyPruningPointUtxoSetOverrideRequest,
     NotifySinkBlueScoreChangedRequest, NotifyUtxosChangedRequest, NotifyVirtualChainChangedRequest,
     NotifyVirtualDaaScoreChangedRequest,
 };
@@ -45,7 +46,9 @@ macro_rules! from {
     };
 }
 
-from!(BlockAdded);
+from!(item: BlockAdded, {
+    Self::new(item.addresses.clone(), item.script_public_keys.clone())
+});
 from!(item: VirtualChainChanged, {
     Self::new(item.include_accepted_transaction_ids)
 });
@@ -62,3 +65,9 @@ from!(SinkBlueScoreChanged);
 from!(VirtualDaaScoreChanged);
 from!(PruningPointUtxoSetOverride);
 from!(NewBlockTemplate);
+from!(item: MempoolTransactionAdded, {
+    Self::new(item.addresses.clone(), item.script_public_keys.clone())
+});
+from!(item: MempoolTransactionRemoved, {
+    Self::new(item.addresses.clone(), item.script_public_keys.clone())
+});
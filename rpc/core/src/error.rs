@@ -66,6 +66,27 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --record-fee-estimates argument.")]
+    NoFeeEstimateHistory,
+
+    #[error("Invalid fee estimate replay parameters: factors and target time must be positive.")]
+    InvalidFeeEstimateReplayParams,
+
+    #[error("Unable to reload the mempool policy: {0}")]
+    MempoolPolicyReload(String),
+
+    #[error("Notification replay is disabled. Run the node with a positive --notification-replay-window argument.")]
+    NotificationReplayDisabled,
+
+    #[error("Notification replay is only available when connected directly to a node.")]
+    NotificationReplayUnavailable,
+
+    #[error("Notification replay is not supported for {0} subscriptions.")]
+    NotificationReplayNotSupported(String),
+
+    #[error("Notification cursor {0} is unknown or out of the replay window. A full resync is required.")]
+    NotificationCursorOutOfWindow(RpcHash),
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
@@ -108,6 +129,9 @@ pub enum RpcError {
     #[error(transparent)]
     ConsensusError(#[from] kaspa_consensus_core::errors::consensus::ConsensusError),
 
+    #[error(transparent)]
+    CoinbaseError(#[from] kaspa_consensus_core::errors::coinbase::CoinbaseError),
+
     #[error(transparent)]
     ScriptClassError(#[from] kaspa_txscript::script_class::Error),
 
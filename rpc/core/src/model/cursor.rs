@@ -0,0 +1,36 @@
+use crate::RpcHash;
+use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_consensus_core::ChainCursor;
+use serde::{Deserialize, Serialize};
+
+/// Position of a chain-related notification along the selected chain.
+///
+/// A client keeps the cursor of the last notification it processed and hands it back
+/// through `RpcApiOps::ResumeSubscription` after a reconnection, in order to have the
+/// node replay the chain changes it missed in the meantime.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcNotificationCursor {
+    /// Hash of the sink once the notified changes are applied
+    pub chain_block_hash: RpcHash,
+    /// DAA score of the sink, increasing along the selected chain
+    pub daa_score: u64,
+}
+
+impl RpcNotificationCursor {
+    pub fn new(chain_block_hash: RpcHash, daa_score: u64) -> Self {
+        Self { chain_block_hash, daa_score }
+    }
+}
+
+impl From<ChainCursor> for RpcNotificationCursor {
+    fn from(item: ChainCursor) -> Self {
+        Self { chain_block_hash: item.hash, daa_score: item.daa_score }
+    }
+}
+
+impl From<RpcNotificationCursor> for ChainCursor {
+    fn from(item: RpcNotificationCursor) -> Self {
+        Self { hash: item.chain_block_hash, daa_score: item.daa_score }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::RpcTransactionId;
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
@@ -107,3 +108,127 @@ impl Deserializer for RpcFeeEstimateVerboseExperimentalData {
         })
     }
 }
+
+/// A recorded snapshot of the feerate estimator state along with the estimations it produced
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeerateEstimateSample {
+    /// Unix time in milliseconds at which the sample was taken
+    pub timestamp: u64,
+    /// Virtual DAA score at which the sample was taken
+    pub daa_score: u64,
+    /// Total probability weight of the mempool ready transactions
+    pub total_weight: f64,
+    /// Amortized time in seconds between transaction inclusions
+    pub inclusion_interval: f64,
+    /// The minimum standard feerate applied when calculating the estimations
+    pub minimum_feerate: f64,
+    pub estimate: RpcFeeEstimate,
+}
+
+impl Serializer for RpcFeerateEstimateSample {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.timestamp, writer)?;
+        store!(u64, &self.daa_score, writer)?;
+        store!(f64, &self.total_weight, writer)?;
+        store!(f64, &self.inclusion_interval, writer)?;
+        store!(f64, &self.minimum_feerate, writer)?;
+        serialize!(RpcFeeEstimate, &self.estimate, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcFeerateEstimateSample {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let timestamp = load!(u64, reader)?;
+        let daa_score = load!(u64, reader)?;
+        let total_weight = load!(f64, reader)?;
+        let inclusion_interval = load!(f64, reader)?;
+        let minimum_feerate = load!(f64, reader)?;
+        let estimate = deserialize!(RpcFeeEstimate, reader)?;
+        Ok(Self { timestamp, daa_score, total_weight, inclusion_interval, minimum_feerate, estimate })
+    }
+}
+
+/// The realized inclusion of a mempool transaction
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionInclusionRecord {
+    pub transaction_id: RpcTransactionId,
+    pub fee: u64,
+    pub feerate: f64,
+    /// Unix time in milliseconds at which the transaction entered the mempool
+    pub added_timestamp: u64,
+    /// Virtual DAA score at which the transaction entered the mempool
+    pub added_daa_score: u64,
+    /// Unix time in milliseconds at which the node handled the block including the transaction
+    pub included_timestamp: u64,
+    /// DAA score of the block including the transaction
+    pub included_daa_score: u64,
+}
+
+impl Serializer for RpcTransactionInclusionRecord {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(u64, &self.fee, writer)?;
+        store!(f64, &self.feerate, writer)?;
+        store!(u64, &self.added_timestamp, writer)?;
+        store!(u64, &self.added_daa_score, writer)?;
+        store!(u64, &self.included_timestamp, writer)?;
+        store!(u64, &self.included_daa_score, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcTransactionInclusionRecord {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let fee = load!(u64, reader)?;
+        let feerate = load!(f64, reader)?;
+        let added_timestamp = load!(u64, reader)?;
+        let added_daa_score = load!(u64, reader)?;
+        let included_timestamp = load!(u64, reader)?;
+        let included_daa_score = load!(u64, reader)?;
+        Ok(Self { transaction_id, fee, feerate, added_timestamp, added_daa_score, included_timestamp, included_daa_score })
+    }
+}
+
+/// Alternative estimator parameters to be replayed against the recorded fee estimate history
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeeEstimateReplayParams {
+    /// Factor applied to the recorded total weight of every sample
+    pub total_weight_factor: f64,
+    /// Factor applied to the recorded inclusion interval of every sample
+    pub inclusion_interval_factor: f64,
+    /// Optional override of the target time per block
+    pub target_time_per_block_seconds: Option<f64>,
+}
+
+impl Default for RpcFeeEstimateReplayParams {
+    fn default() -> Self {
+        Self { total_weight_factor: 1.0, inclusion_interval_factor: 1.0, target_time_per_block_seconds: None }
+    }
+}
+
+/// Accuracy statistics of a replay of estimator parameters against the recorded fee estimate history
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeeEstimateReplayReport {
+    /// Number of recorded inclusions which could be matched with a preceding estimate sample
+    pub evaluated_transactions: u64,
+    /// Mean of the inclusion times predicted for the feerate of each evaluated transaction
+    pub mean_predicted_seconds: f64,
+    /// Mean of the realized inclusion times
+    pub mean_realized_seconds: f64,
+    /// Mean of the absolute differences between predicted and realized inclusion times
+    pub mean_absolute_error_seconds: f64,
+    /// Fraction of transactions included no later than their predicted inclusion time
+    pub within_prediction_ratio: f64,
+    /// Fraction of transactions included no later than the estimated time of the highest bucket their feerate qualifies for
+    pub within_bucket_ratio: f64,
+}
@@ -1,6 +1,13 @@
 use super::RpcAddress;
 use super::RpcTransaction;
+use crate::RpcError;
+use crate::RpcTransactionId;
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
 use workflow_serializer::prelude::*;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -63,6 +70,202 @@ impl Deserializer for RpcMempoolEntryByAddress {
     }
 }
 
+/// Reason why a transaction left the mempool
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcMempoolTransactionRemovalReason {
+    Unspecified = 0,
+    Accepted = 1,
+    ReplacedByFee = 2,
+    DoubleSpend = 3,
+    Evicted = 4,
+    Expired = 5,
+    Invalid = 6,
+}
+
+impl RpcMempoolTransactionRemovalReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RpcMempoolTransactionRemovalReason::Unspecified => "unspecified",
+            RpcMempoolTransactionRemovalReason::Accepted => "accepted",
+            RpcMempoolTransactionRemovalReason::ReplacedByFee => "replaced-by-fee",
+            RpcMempoolTransactionRemovalReason::DoubleSpend => "double-spend",
+            RpcMempoolTransactionRemovalReason::Evicted => "evicted",
+            RpcMempoolTransactionRemovalReason::Expired => "expired",
+            RpcMempoolTransactionRemovalReason::Invalid => "invalid",
+        }
+    }
+}
+
+impl Display for RpcMempoolTransactionRemovalReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RpcMempoolTransactionRemovalReason {
+    type Err = RpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unspecified" => Ok(RpcMempoolTransactionRemovalReason::Unspecified),
+            "accepted" => Ok(RpcMempoolTransactionRemovalReason::Accepted),
+            "replaced-by-fee" => Ok(RpcMempoolTransactionRemovalReason::ReplacedByFee),
+            "double-spend" => Ok(RpcMempoolTransactionRemovalReason::DoubleSpend),
+            "evicted" => Ok(RpcMempoolTransactionRemovalReason::Evicted),
+            "expired" => Ok(RpcMempoolTransactionRemovalReason::Expired),
+            "invalid" => Ok(RpcMempoolTransactionRemovalReason::Invalid),
+            _ => Err(RpcError::General(format!("unknown mempool transaction removal reason: {s}"))),
+        }
+    }
+}
+
+/// Mempool transactions having a feerate between the bucket feerate and the feerate of the next higher bucket
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeerateHistogramBucket {
+    /// Lower feerate bound of the bucket, in sompi/gram
+    pub feerate: f64,
+    /// Estimated inclusion time of the bucket feerate. Undefined for the last bucket, which gathers
+    /// the transactions below the lowest feerate estimation
+    pub estimated_seconds: Option<f64>,
+    pub transaction_count: u64,
+    pub total_mass: u64,
+}
+
+/// Mempool related details of a transaction
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMempoolEntryInfo {
+    pub transaction_id: RpcTransactionId,
+    pub fee: u64,
+    pub mass: u64,
+    pub feerate: f64,
+    pub is_high_priority: bool,
+    /// Virtual DAA score at which the transaction entered the mempool
+    pub added_daa_score: u64,
+    /// Number of mempool transactions the transaction directly or indirectly spends from
+    pub ancestor_count: u64,
+    /// Number of mempool transactions directly or indirectly spending from the transaction
+    pub descendant_count: u64,
+    /// Position, starting at 0, of the transaction in the order low-priority transactions are evicted in
+    /// when the mempool is full. Undefined if the transaction cannot be evicted
+    pub eviction_rank: Option<u64>,
+}
+
+impl Serializer for RpcMempoolEntryInfo {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(u64, &self.fee, writer)?;
+        store!(u64, &self.mass, writer)?;
+        store!(f64, &self.feerate, writer)?;
+        store!(bool, &self.is_high_priority, writer)?;
+        store!(u64, &self.added_daa_score, writer)?;
+        store!(u64, &self.ancestor_count, writer)?;
+        store!(u64, &self.descendant_count, writer)?;
+        store!(Option<u64>, &self.eviction_rank, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcMempoolEntryInfo {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let fee = load!(u64, reader)?;
+        let mass = load!(u64, reader)?;
+        let feerate = load!(f64, reader)?;
+        let is_high_priority = load!(bool, reader)?;
+        let added_daa_score = load!(u64, reader)?;
+        let ancestor_count = load!(u64, reader)?;
+        let descendant_count = load!(u64, reader)?;
+        let eviction_rank = load!(Option<u64>, reader)?;
+        Ok(Self {
+            transaction_id,
+            fee,
+            mass,
+            feerate,
+            is_high_priority,
+            added_daa_score,
+            ancestor_count,
+            descendant_count,
+            eviction_rank,
+        })
+    }
+}
+
+/// A transaction of a mempool dependency graph
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMempoolGraphNode {
+    pub transaction_id: RpcTransactionId,
+    pub fee: u64,
+    pub mass: u64,
+    pub feerate: f64,
+    pub is_high_priority: bool,
+    /// Parents of the transaction belonging to the graph
+    pub parent_ids: Vec<RpcTransactionId>,
+}
+
+impl Serializer for RpcMempoolGraphNode {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(u64, &self.fee, writer)?;
+        store!(u64, &self.mass, writer)?;
+        store!(f64, &self.feerate, writer)?;
+        store!(bool, &self.is_high_priority, writer)?;
+        store!(Vec<RpcTransactionId>, &self.parent_ids, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcMempoolGraphNode {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let fee = load!(u64, reader)?;
+        let mass = load!(u64, reader)?;
+        let feerate = load!(f64, reader)?;
+        let is_high_priority = load!(bool, reader)?;
+        let parent_ids = load!(Vec<RpcTransactionId>, reader)?;
+        Ok(Self { transaction_id, fee, mass, feerate, is_high_priority, parent_ids })
+    }
+}
+
+/// An operator defined fee delta of a transaction
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPrioritisedTransaction {
+    pub transaction_id: RpcTransactionId,
+    /// Virtual amount, in sompi, added to the fee of the transaction for block template selection and replace by fee
+    pub fee_delta: i64,
+    /// Whether the transaction is currently in the mempool
+    pub in_mempool: bool,
+}
+
+impl Serializer for RpcPrioritisedTransaction {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(i64, &self.fee_delta, writer)?;
+        store!(bool, &self.in_mempool, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcPrioritisedTransaction {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let fee_delta = load!(i64, reader)?;
+        let in_mempool = load!(bool, reader)?;
+        Ok(Self { transaction_id, fee_delta, in_mempool })
+    }
+}
+
 cfg_if::cfg_if! {
     if #[cfg(feature = "wasm32-sdk")] {
         use wasm_bindgen::prelude::*;
@@ -2,7 +2,14 @@ use crate::model::*;
 use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_consensus_core::api::stats::BlockCount;
 use kaspa_core::debug;
-use kaspa_notify::subscription::{context::SubscriptionContext, single::UtxosChangedSubscription, Command};
+use kaspa_notify::{
+    scope::Scope,
+    subscription::{
+        context::SubscriptionContext,
+        single::{AddressFilteredSubscription, UtxosChangedSubscription},
+        Command,
+    },
+};
 use kaspa_utils::hex::ToHex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -123,18 +130,31 @@ pub struct GetBlockTemplateRequest {
     pub pay_address: RpcAddress,
     // TODO: replace with hex serialization
     pub extra_data: RpcExtraData,
+    /// Weighted split of the block reward among several addresses.
+    ///
+    /// The split is not paid on-chain: consensus pays the reward of a block to a single script, so the whole
+    /// reward is paid to `pay_address` and the split is only recorded as metadata in the coinbase payload. The
+    /// pool remains responsible for paying the shares out of that reward, as signaled by
+    /// [`GetBlockTemplateResponse::payouts_recorded_only`]. Empty if no split is requested.
+    #[serde(default)]
+    pub payouts: Vec<RpcCoinbasePayout>,
 }
 impl GetBlockTemplateRequest {
     pub fn new(pay_address: RpcAddress, extra_data: RpcExtraData) -> Self {
-        Self { pay_address, extra_data }
+        Self { pay_address, extra_data, payouts: vec![] }
+    }
+
+    pub fn with_payouts(pay_address: RpcAddress, extra_data: RpcExtraData, payouts: Vec<RpcCoinbasePayout>) -> Self {
+        Self { pay_address, extra_data, payouts }
     }
 }
 
 impl Serializer for GetBlockTemplateRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcAddress, &self.pay_address, writer)?;
         store!(RpcExtraData, &self.extra_data, writer)?;
+        serialize!(Vec<RpcCoinbasePayout>, &self.payouts, writer)?;
 
         Ok(())
     }
@@ -142,11 +162,12 @@ impl Serializer for GetBlockTemplateRequest {
 
 impl Deserializer for GetBlockTemplateRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let pay_address = load!(RpcAddress, reader)?;
         let extra_data = load!(RpcExtraData, reader)?;
+        let payouts = if version > 1 { deserialize!(Vec<RpcCoinbasePayout>, reader)? } else { vec![] };
 
-        Ok(Self { pay_address, extra_data })
+        Ok(Self { pay_address, extra_data, payouts })
     }
 }
 
@@ -160,13 +181,22 @@ pub struct GetBlockTemplateResponse {
     /// That is because when kaspad isn't in sync with the rest of the network there's a high
     /// chance the block will never be accepted, thus the solving effort would have been wasted.
     pub is_synced: bool,
+
+    /// Whether the payout split of the request was only recorded in the coinbase payload.
+    ///
+    /// No on-chain split takes place: consensus pays the reward of a block to a single script, so the whole reward
+    /// of the template goes to the pay address and the pool is responsible for settling the recorded split. False
+    /// if no split was requested.
+    #[serde(default)]
+    pub payouts_recorded_only: bool,
 }
 
 impl Serializer for GetBlockTemplateResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         serialize!(RpcRawBlock, &self.block, writer)?;
         store!(bool, &self.is_synced, writer)?;
+        store!(bool, &self.payouts_recorded_only, writer)?;
 
         Ok(())
     }
@@ -174,11 +204,12 @@ impl Serializer for GetBlockTemplateResponse {
 
 impl Deserializer for GetBlockTemplateResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let block = deserialize!(RpcRawBlock, reader)?;
         let is_synced = load!(bool, reader)?;
+        let payouts_recorded_only = if version > 1 { load!(bool, reader)? } else { false };
 
-        Ok(Self { block, is_synced })
+        Ok(Self { block, is_synced, payouts_recorded_only })
     }
 }
 
@@ -2622,6 +2653,419 @@ impl Deserializer for GetFeeEstimateExperimentalResponse {
     }
 }
 
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateHistoryRequest {
+    /// Unix time in milliseconds from which to export the recorded history
+    pub from_timestamp: u64,
+    /// Alternative estimator parameters to replay against the recorded history
+    pub replay_params: Option<RpcFeeEstimateReplayParams>,
+    /// Sequence number of the first sample to export, as returned by a previous call in `next_sample_sequence`
+    pub from_sample_sequence: u64,
+    /// Sequence number of the first inclusion to export, as returned by a previous call in `next_inclusion_sequence`
+    pub from_inclusion_sequence: u64,
+    /// The maximum number of samples, and of inclusions, to export. Zero means the maximum allowed by the node.
+    pub limit: u32,
+}
+
+impl GetFeeEstimateHistoryRequest {
+    pub fn new(from_timestamp: u64, replay_params: Option<RpcFeeEstimateReplayParams>) -> Self {
+        Self { from_timestamp, replay_params, ..Default::default() }
+    }
+
+    /// A request for the page following `response`, replaying no parameters
+    pub fn next_page(&self, response: &GetFeeEstimateHistoryResponse) -> Self {
+        Self {
+            from_sample_sequence: response.next_sample_sequence,
+            from_inclusion_sequence: response.next_inclusion_sequence,
+            replay_params: None,
+            ..self.clone()
+        }
+    }
+}
+
+impl Serializer for GetFeeEstimateHistoryRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &2, writer)?;
+        store!(u64, &self.from_timestamp, writer)?;
+        store!(Option<RpcFeeEstimateReplayParams>, &self.replay_params, writer)?;
+        store!(u64, &self.from_sample_sequence, writer)?;
+        store!(u64, &self.from_inclusion_sequence, writer)?;
+        store!(u32, &self.limit, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetFeeEstimateHistoryRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = load!(u16, reader)?;
+        let from_timestamp = load!(u64, reader)?;
+        let replay_params = load!(Option<RpcFeeEstimateReplayParams>, reader)?;
+        let (from_sample_sequence, from_inclusion_sequence, limit) =
+            if version > 1 { (load!(u64, reader)?, load!(u64, reader)?, load!(u32, reader)?) } else { (0, 0, 0) };
+        Ok(Self { from_timestamp, replay_params, from_sample_sequence, from_inclusion_sequence, limit })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetFeeEstimateHistoryResponse {
+    /// Feerate estimator samples, in chronological order
+    pub samples: Vec<RpcFeerateEstimateSample>,
+    /// Realized transaction inclusions, in chronological order
+    pub inclusions: Vec<RpcTransactionInclusionRecord>,
+    /// Accuracy report of the requested replay parameters, if any. The replay covers the complete recorded history.
+    pub replay_report: Option<RpcFeeEstimateReplayReport>,
+    /// Sequence number of the sample following the exported ones
+    pub next_sample_sequence: u64,
+    /// Sequence number of the inclusion following the exported ones
+    pub next_inclusion_sequence: u64,
+    /// Indicates whether records were left out because of the page size, in which case the next page
+    /// can be requested from the next sequence numbers
+    pub has_more: bool,
+}
+
+impl Serializer for GetFeeEstimateHistoryResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &2, writer)?;
+        serialize!(Vec<RpcFeerateEstimateSample>, &self.samples, writer)?;
+        serialize!(Vec<RpcTransactionInclusionRecord>, &self.inclusions, writer)?;
+        store!(Option<RpcFeeEstimateReplayReport>, &self.replay_report, writer)?;
+        store!(u64, &self.next_sample_sequence, writer)?;
+        store!(u64, &self.next_inclusion_sequence, writer)?;
+        store!(bool, &self.has_more, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetFeeEstimateHistoryResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = load!(u16, reader)?;
+        let samples = deserialize!(Vec<RpcFeerateEstimateSample>, reader)?;
+        let inclusions = deserialize!(Vec<RpcTransactionInclusionRecord>, reader)?;
+        let replay_report = load!(Option<RpcFeeEstimateReplayReport>, reader)?;
+        let (next_sample_sequence, next_inclusion_sequence, has_more) =
+            if version > 1 { (load!(u64, reader)?, load!(u64, reader)?, load!(bool, reader)?) } else { (0, 0, false) };
+        Ok(Self { samples, inclusions, replay_report, next_sample_sequence, next_inclusion_sequence, has_more })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadMempoolPolicyRequest {}
+
+impl Serializer for ReloadMempoolPolicyRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ReloadMempoolPolicyRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadMempoolPolicyResponse {
+    /// Descriptions of the policies now in effect
+    pub policies: Vec<String>,
+}
+
+impl Serializer for ReloadMempoolPolicyResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<String>, &self.policies, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ReloadMempoolPolicyResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let policies = load!(Vec<String>, reader)?;
+        Ok(Self { policies })
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolInfoRequest {
+    /// Include the details of every transaction pool transaction (unsafe RPC mode only)
+    pub include_entries: bool,
+}
+
+impl Serializer for GetMempoolInfoRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(bool, &self.include_entries, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMempoolInfoRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let include_entries = load!(bool, reader)?;
+        Ok(Self { include_entries })
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolInfoResponse {
+    /// Number of transactions in the transaction pool (orphans excluded)
+    pub transaction_count: u64,
+    /// Number of transactions having no parent in the mempool
+    pub ready_transaction_count: u64,
+    pub orphan_count: u64,
+    pub total_mass: u64,
+    pub ready_total_mass: u64,
+    pub total_fees: u64,
+    /// Estimated memory size, in bytes, of the transaction pool
+    pub estimated_size: u64,
+    /// Transactions grouped by the feerate buckets of the fee estimator, by descending feerate
+    pub feerate_histogram: Vec<RpcFeerateHistogramBucket>,
+    /// Details of every transaction pool transaction, by descending feerate, if requested
+    pub entries: Vec<RpcMempoolEntryInfo>,
+}
+
+impl Serializer for GetMempoolInfoResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.transaction_count, writer)?;
+        store!(u64, &self.ready_transaction_count, writer)?;
+        store!(u64, &self.orphan_count, writer)?;
+        store!(u64, &self.total_mass, writer)?;
+        store!(u64, &self.ready_total_mass, writer)?;
+        store!(u64, &self.total_fees, writer)?;
+        store!(u64, &self.estimated_size, writer)?;
+        store!(Vec<RpcFeerateHistogramBucket>, &self.feerate_histogram, writer)?;
+        serialize!(Vec<RpcMempoolEntryInfo>, &self.entries, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMempoolInfoResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_count = load!(u64, reader)?;
+        let ready_transaction_count = load!(u64, reader)?;
+        let orphan_count = load!(u64, reader)?;
+        let total_mass = load!(u64, reader)?;
+        let ready_total_mass = load!(u64, reader)?;
+        let total_fees = load!(u64, reader)?;
+        let estimated_size = load!(u64, reader)?;
+        let feerate_histogram = load!(Vec<RpcFeerateHistogramBucket>, reader)?;
+        let entries = deserialize!(Vec<RpcMempoolEntryInfo>, reader)?;
+        Ok(Self {
+            transaction_count,
+            ready_transaction_count,
+            orphan_count,
+            total_mass,
+            ready_total_mass,
+            total_fees,
+            estimated_size,
+            feerate_histogram,
+            entries,
+        })
+    }
+}
+
+/// Requests the dependency graph of a mempool transaction (unsafe RPC mode only, as the graph is not bounded)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolTransactionGraphRequest {
+    pub transaction_id: RpcTransactionId,
+}
+
+impl Serializer for GetMempoolTransactionGraphRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMempoolTransactionGraphRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        Ok(Self { transaction_id })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMempoolTransactionGraphResponse {
+    /// The transaction and all its mempool ancestors and descendants, in topological order
+    pub nodes: Vec<RpcMempoolGraphNode>,
+}
+
+impl Serializer for GetMempoolTransactionGraphResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcMempoolGraphNode>, &self.nodes, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMempoolTransactionGraphResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let nodes = deserialize!(Vec<RpcMempoolGraphNode>, reader)?;
+        Ok(Self { nodes })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrioritiseTransactionRequest {
+    pub transaction_id: RpcTransactionId,
+    /// Virtual amount, in sompi, added to the current fee delta of the transaction. Negative values deprioritize it.
+    pub fee_delta: i64,
+}
+
+impl Serializer for PrioritiseTransactionRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(i64, &self.fee_delta, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for PrioritiseTransactionRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let fee_delta = load!(i64, reader)?;
+        Ok(Self { transaction_id, fee_delta })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrioritiseTransactionResponse {
+    /// Resulting cumulative fee delta of the transaction
+    pub fee_delta: i64,
+}
+
+impl Serializer for PrioritiseTransactionResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(i64, &self.fee_delta, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for PrioritiseTransactionResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let fee_delta = load!(i64, reader)?;
+        Ok(Self { fee_delta })
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPrioritisedTransactionsRequest {}
+
+impl Serializer for GetPrioritisedTransactionsRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetPrioritisedTransactionsRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPrioritisedTransactionsResponse {
+    /// Transactions having a fee delta, by descending delta
+    pub transactions: Vec<RpcPrioritisedTransaction>,
+}
+
+impl Serializer for GetPrioritisedTransactionsResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcPrioritisedTransaction>, &self.transactions, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetPrioritisedTransactionsResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transactions = deserialize!(Vec<RpcPrioritisedTransaction>, reader)?;
+        Ok(Self { transactions })
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMiningStatusRequest {}
+
+impl Serializer for GetMiningStatusRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMiningStatusRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetMiningStatusResponse {
+    /// Whether block templates are currently reported as synced and submitted blocks accepted
+    pub is_mining_allowed: bool,
+    /// Explanation of the mining decision
+    pub reason: String,
+    pub is_nearly_synced: bool,
+    pub has_sufficient_peer_connectivity: bool,
+    /// State of every mining rule, enabled ones first
+    pub rules: Vec<RpcMiningRuleStatus>,
+}
+
+impl Serializer for GetMiningStatusResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(bool, &self.is_mining_allowed, writer)?;
+        store!(String, &self.reason, writer)?;
+        store!(bool, &self.is_nearly_synced, writer)?;
+        store!(bool, &self.has_sufficient_peer_connectivity, writer)?;
+        serialize!(Vec<RpcMiningRuleStatus>, &self.rules, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetMiningStatusResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let is_mining_allowed = load!(bool, reader)?;
+        let reason = load!(String, reader)?;
+        let is_nearly_synced = load!(bool, reader)?;
+        let has_sufficient_peer_connectivity = load!(bool, reader)?;
+        let rules = deserialize!(Vec<RpcMiningRuleStatus>, reader)?;
+        Ok(Self { is_mining_allowed, reason, is_nearly_synced, has_sufficient_peer_connectivity, rules })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetCurrentBlockColorRequest {
@@ -2813,31 +3257,50 @@ impl Deserializer for GetVirtualChainFromBlockV2Response {
 
 /// NotifyBlockAddedRequest registers this connection for blockAdded notifications.
 ///
+/// If `addresses` or `script_public_keys` is not empty, only the blocks having a transaction
+/// output paying to one of them are notified.
+///
 /// See: BlockAddedNotification
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NotifyBlockAddedRequest {
     pub command: Command,
+    #[serde(default)]
+    pub addresses: Vec<RpcAddress>,
+    #[serde(default)]
+    pub script_public_keys: Vec<RpcScriptPublicKey>,
 }
 impl NotifyBlockAddedRequest {
     pub fn new(command: Command) -> Self {
-        Self { command }
+        Self { command, addresses: vec![], script_public_keys: vec![] }
+    }
+
+    pub fn with_addresses(addresses: Vec<RpcAddress>, command: Command) -> Self {
+        Self { command, addresses, script_public_keys: vec![] }
+    }
+
+    pub fn with_script_public_keys(script_public_keys: Vec<RpcScriptPublicKey>, command: Command) -> Self {
+        Self { command, addresses: vec![], script_public_keys }
     }
 }
 
 impl Serializer for NotifyBlockAddedRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &3, writer)?;
         store!(Command, &self.command, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+        store!(Vec<RpcScriptPublicKey>, &self.script_public_keys, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for NotifyBlockAddedRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let command = load!(Command, reader)?;
-        Ok(Self { command })
+        let addresses = if version > 1 { load!(Vec<RpcAddress>, reader)? } else { vec![] };
+        let script_public_keys = if version > 2 { load!(Vec<RpcScriptPublicKey>, reader)? } else { vec![] };
+        Ok(Self { command, addresses, script_public_keys })
     }
 }
 
@@ -2869,6 +3332,27 @@ pub struct BlockAddedNotification {
     pub block: Arc<RpcBlock>,
 }
 
+impl BlockAddedNotification {
+    pub(crate) fn apply_address_filtered_subscription(
+        &self,
+        subscription: &AddressFilteredSubscription,
+        context: &SubscriptionContext,
+    ) -> Option<Self> {
+        (subscription.to_all() || self.block.transactions.iter().any(|tx| pays_to_subscription(tx, subscription, context)))
+            .then(|| self.clone())
+    }
+}
+
+/// Returns true if any output of `transaction` pays to an address of `subscription`
+fn pays_to_subscription(
+    transaction: &RpcTransaction,
+    subscription: &AddressFilteredSubscription,
+    context: &SubscriptionContext,
+) -> bool {
+    let subscription_data = subscription.data();
+    transaction.outputs.iter().any(|output| subscription_data.contains(&output.script_public_key, context))
+}
+
 impl Serializer for BlockAddedNotification {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
         store!(u16, &1, writer)?;
@@ -2951,28 +3435,33 @@ pub struct VirtualChainChangedNotification {
     pub removed_chain_block_hashes: Arc<Vec<RpcHash>>,
     pub added_chain_block_hashes: Arc<Vec<RpcHash>>,
     pub accepted_transaction_ids: Arc<Vec<RpcAcceptedTransactionIds>>,
+    /// Position of the sink once these changes are applied, usable for resuming the subscription
+    pub cursor: Option<RpcNotificationCursor>,
 }
 
 impl Serializer for VirtualChainChangedNotification {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Vec<RpcHash>, &self.removed_chain_block_hashes, writer)?;
         store!(Vec<RpcHash>, &self.added_chain_block_hashes, writer)?;
         store!(Vec<RpcAcceptedTransactionIds>, &self.accepted_transaction_ids, writer)?;
+        store!(Option<RpcNotificationCursor>, &self.cursor, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for VirtualChainChangedNotification {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let removed_chain_block_hashes = load!(Vec<RpcHash>, reader)?;
         let added_chain_block_hashes = load!(Vec<RpcHash>, reader)?;
         let accepted_transaction_ids = load!(Vec<RpcAcceptedTransactionIds>, reader)?;
+        let cursor = if version > 1 { load!(Option<RpcNotificationCursor>, reader)? } else { None };
         Ok(Self {
             removed_chain_block_hashes: removed_chain_block_hashes.into(),
             added_chain_block_hashes: added_chain_block_hashes.into(),
             accepted_transaction_ids: accepted_transaction_ids.into(),
+            cursor,
         })
     }
 }
@@ -3188,6 +3677,8 @@ impl Deserializer for NotifyUtxosChangedResponse {
 pub struct UtxosChangedNotification {
     pub added: Arc<Vec<RpcUtxosByAddressesEntry>>,
     pub removed: Arc<Vec<RpcUtxosByAddressesEntry>>,
+    /// Position of the sink of the virtual state these changes lead to, usable for resuming the subscription
+    pub cursor: Option<RpcNotificationCursor>,
 }
 
 impl UtxosChangedNotification {
@@ -3205,7 +3696,7 @@ impl UtxosChangedNotification {
                 None
             } else {
                 debug!("CRPC, Creating UtxosChanged notifications with {} added and {} removed utxos", added.len(), removed.len());
-                Some(Self { added: Arc::new(added), removed: Arc::new(removed) })
+                Some(Self { added: Arc::new(added), removed: Arc::new(removed), cursor: self.cursor })
             }
         }
     }
@@ -3222,19 +3713,21 @@ impl UtxosChangedNotification {
 
 impl Serializer for UtxosChangedNotification {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         serialize!(Vec<RpcUtxosByAddressesEntry>, &self.added, writer)?;
         serialize!(Vec<RpcUtxosByAddressesEntry>, &self.removed, writer)?;
+        store!(Option<RpcNotificationCursor>, &self.cursor, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for UtxosChangedNotification {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let added = deserialize!(Vec<RpcUtxosByAddressesEntry>, reader)?;
         let removed = deserialize!(Vec<RpcUtxosByAddressesEntry>, reader)?;
-        Ok(Self { added: added.into(), removed: removed.into() })
+        let cursor = if version > 1 { load!(Option<RpcNotificationCursor>, reader)? } else { None };
+        Ok(Self { added: added.into(), removed: removed.into(), cursor })
     }
 }
 
@@ -3536,6 +4029,215 @@ impl Deserializer for NewBlockTemplateNotification {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MempoolTransactionAddedNotification
+
+/// NotifyMempoolTransactionAddedRequest registers this connection for mempoolTransactionAdded notifications.
+///
+/// If `addresses` or `script_public_keys` is not empty, only the transactions having an output
+/// paying to one of them are notified.
+///
+/// See: MempoolTransactionAddedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionAddedRequest {
+    pub addresses: Vec<RpcAddress>,
+    #[serde(default)]
+    pub script_public_keys: Vec<RpcScriptPublicKey>,
+    pub command: Command,
+}
+
+impl NotifyMempoolTransactionAddedRequest {
+    pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
+        Self { addresses, script_public_keys: vec![], command }
+    }
+
+    pub fn with_script_public_keys(addresses: Vec<RpcAddress>, script_public_keys: Vec<RpcScriptPublicKey>, command: Command) -> Self {
+        Self { addresses, script_public_keys, command }
+    }
+}
+
+impl Serializer for NotifyMempoolTransactionAddedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &2, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+        store!(Command, &self.command, writer)?;
+        store!(Vec<RpcScriptPublicKey>, &self.script_public_keys, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyMempoolTransactionAddedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = load!(u16, reader)?;
+        let addresses = load!(Vec<RpcAddress>, reader)?;
+        let command = load!(Command, reader)?;
+        let script_public_keys = if version > 1 { load!(Vec<RpcScriptPublicKey>, reader)? } else { vec![] };
+        Ok(Self { addresses, script_public_keys, command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionAddedResponse {}
+
+impl Serializer for NotifyMempoolTransactionAddedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyMempoolTransactionAddedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// MempoolTransactionAddedNotification is sent whenever a transaction is accepted into the mempool.
+///
+/// See: NotifyMempoolTransactionAddedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTransactionAddedNotification {
+    pub transaction: Arc<RpcTransaction>,
+    pub fee: u64,
+}
+
+impl MempoolTransactionAddedNotification {
+    pub(crate) fn apply_address_filtered_subscription(
+        &self,
+        subscription: &AddressFilteredSubscription,
+        context: &SubscriptionContext,
+    ) -> Option<Self> {
+        (subscription.to_all() || pays_to_subscription(&self.transaction, subscription, context)).then(|| self.clone())
+    }
+}
+
+impl Serializer for MempoolTransactionAddedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcTransaction, &self.transaction, writer)?;
+        store!(u64, &self.fee, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for MempoolTransactionAddedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction = deserialize!(RpcTransaction, reader)?;
+        let fee = load!(u64, reader)?;
+        Ok(Self { transaction: transaction.into(), fee })
+    }
+}
+
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// MempoolTransactionRemovedNotification
+
+/// NotifyMempoolTransactionRemovedRequest registers this connection for mempoolTransactionRemoved notifications.
+///
+/// If `addresses` or `script_public_keys` is not empty, only the transactions having an output
+/// paying to one of them are notified.
+///
+/// See: MempoolTransactionRemovedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionRemovedRequest {
+    pub addresses: Vec<RpcAddress>,
+    #[serde(default)]
+    pub script_public_keys: Vec<RpcScriptPublicKey>,
+    pub command: Command,
+}
+
+impl NotifyMempoolTransactionRemovedRequest {
+    pub fn new(addresses: Vec<RpcAddress>, command: Command) -> Self {
+        Self { addresses, script_public_keys: vec![], command }
+    }
+
+    pub fn with_script_public_keys(addresses: Vec<RpcAddress>, script_public_keys: Vec<RpcScriptPublicKey>, command: Command) -> Self {
+        Self { addresses, script_public_keys, command }
+    }
+}
+
+impl Serializer for NotifyMempoolTransactionRemovedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &2, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+        store!(Command, &self.command, writer)?;
+        store!(Vec<RpcScriptPublicKey>, &self.script_public_keys, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyMempoolTransactionRemovedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let version = load!(u16, reader)?;
+        let addresses = load!(Vec<RpcAddress>, reader)?;
+        let command = load!(Command, reader)?;
+        let script_public_keys = if version > 1 { load!(Vec<RpcScriptPublicKey>, reader)? } else { vec![] };
+        Ok(Self { addresses, script_public_keys, command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifyMempoolTransactionRemovedResponse {}
+
+impl Serializer for NotifyMempoolTransactionRemovedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifyMempoolTransactionRemovedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// MempoolTransactionRemovedNotification is sent whenever a transaction leaves the mempool,
+/// either by being accepted by a block or by being discarded.
+///
+/// See: NotifyMempoolTransactionRemovedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolTransactionRemovedNotification {
+    pub transaction: Arc<RpcTransaction>,
+    pub reason: RpcMempoolTransactionRemovalReason,
+}
+
+impl MempoolTransactionRemovedNotification {
+    pub(crate) fn apply_address_filtered_subscription(
+        &self,
+        subscription: &AddressFilteredSubscription,
+        context: &SubscriptionContext,
+    ) -> Option<Self> {
+        (subscription.to_all() || pays_to_subscription(&self.transaction, subscription, context)).then(|| self.clone())
+    }
+}
+
+impl Serializer for MempoolTransactionRemovedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcTransaction, &self.transaction, writer)?;
+        store!(RpcMempoolTransactionRemovalReason, &self.reason, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for MempoolTransactionRemovedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let transaction = deserialize!(RpcTransaction, reader)?;
+        let reason = load!(RpcMempoolTransactionRemovalReason, reader)?;
+        Ok(Self { transaction: transaction.into(), reason })
+    }
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
@@ -3567,6 +4269,80 @@ impl Deserializer for SubscribeResponse {
     }
 }
 
+///
+///  wRPC request for RpcApiOps::ResumeSubscription
+///
+///  Subscribes to `scope` like RpcApiOps::Subscribe does, after having sent the notifications
+///  the connection missed since `cursor`. Only VirtualChainChanged and UtxosChanged scopes can
+///  be resumed. Replayed notifications may overlap with live ones around the time of the call.
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeSubscriptionRequest {
+    pub scope: Scope,
+    /// Cursor of the last notification processed by the client
+    pub cursor: RpcNotificationCursor,
+}
+
+impl ResumeSubscriptionRequest {
+    pub fn new(scope: Scope, cursor: RpcNotificationCursor) -> Self {
+        Self { scope, cursor }
+    }
+}
+
+impl Serializer for ResumeSubscriptionRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Scope, &self.scope, writer)?;
+        store!(RpcNotificationCursor, &self.cursor, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ResumeSubscriptionRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let scope = deserialize!(Scope, reader)?;
+        let cursor = load!(RpcNotificationCursor, reader)?;
+        Ok(Self { scope, cursor })
+    }
+}
+
+///
+///  wRPC response for RpcApiOps::ResumeSubscription request
+///
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeSubscriptionResponse {
+    id: u64,
+    /// Cursor reached by the replayed notifications
+    pub cursor: RpcNotificationCursor,
+}
+
+impl ResumeSubscriptionResponse {
+    pub fn new(id: u64, cursor: RpcNotificationCursor) -> Self {
+        Self { id, cursor }
+    }
+}
+
+impl Serializer for ResumeSubscriptionResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u64, &self.id, writer)?;
+        store!(RpcNotificationCursor, &self.cursor, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for ResumeSubscriptionResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let id = load!(u64, reader)?;
+        let cursor = load!(RpcNotificationCursor, reader)?;
+        Ok(Self { id, cursor })
+    }
+}
+
 ///
 ///  wRPC response for RpcApiOps::Unsubscribe request
 ///
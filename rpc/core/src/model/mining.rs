@@ -0,0 +1,116 @@
+use crate::{RpcAddress, RpcError};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+use workflow_serializer::prelude::*;
+
+/// How a triggered mining rule affects the mining decision
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+#[borsh(use_discriminant = true)]
+pub enum RpcMiningRuleKind {
+    /// Allows mining even if the node is not nearly synced
+    Permissive = 0,
+    /// Blocks mining even if the node is synced
+    Restrictive = 1,
+}
+
+impl RpcMiningRuleKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RpcMiningRuleKind::Permissive => "permissive",
+            RpcMiningRuleKind::Restrictive => "restrictive",
+        }
+    }
+}
+
+impl Display for RpcMiningRuleKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RpcMiningRuleKind {
+    type Err = RpcError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "permissive" => Ok(RpcMiningRuleKind::Permissive),
+            "restrictive" => Ok(RpcMiningRuleKind::Restrictive),
+            _ => Err(RpcError::General(format!("unknown mining rule kind: {s}"))),
+        }
+    }
+}
+
+/// State of a mining rule of the node
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMiningRuleStatus {
+    pub name: String,
+    pub kind: RpcMiningRuleKind,
+    pub enabled: bool,
+    /// Whether the rule conditions were met on the last check
+    pub triggered: bool,
+    /// Explanation of the outcome of the last check of the rule
+    pub status: String,
+}
+
+impl Serializer for RpcMiningRuleStatus {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(String, &self.name, writer)?;
+        store!(RpcMiningRuleKind, &self.kind, writer)?;
+        store!(bool, &self.enabled, writer)?;
+        store!(bool, &self.triggered, writer)?;
+        store!(String, &self.status, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcMiningRuleStatus {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let name = load!(String, reader)?;
+        let kind = load!(RpcMiningRuleKind, reader)?;
+        let enabled = load!(bool, reader)?;
+        let triggered = load!(bool, reader)?;
+        let status = load!(String, reader)?;
+        Ok(Self { name, kind, enabled, triggered, status })
+    }
+}
+
+/// A weighted share of the block reward requested in a block template
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcCoinbasePayout {
+    pub address: RpcAddress,
+    /// Share of the reward relative to the weights of the other payouts of the template request
+    pub weight: u32,
+}
+
+impl RpcCoinbasePayout {
+    pub fn new(address: RpcAddress, weight: u32) -> Self {
+        Self { address, weight }
+    }
+}
+
+impl Serializer for RpcCoinbasePayout {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcAddress, &self.address, writer)?;
+        store!(u32, &self.weight, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcCoinbasePayout {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let address = load!(RpcAddress, reader)?;
+        let weight = load!(u32, reader)?;
+        Ok(Self { address, weight })
+    }
+}
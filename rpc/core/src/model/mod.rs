@@ -4,12 +4,14 @@
 pub mod address;
 pub mod block;
 pub mod blue_work;
+pub mod cursor;
 pub mod feerate_estimate;
 pub mod hash;
 pub mod header;
 pub mod hex_cnv;
 pub mod mempool;
 pub mod message;
+pub mod mining;
 pub mod network;
 pub mod optional;
 pub mod peer;
@@ -22,12 +24,14 @@ pub mod verbosity;
 pub use address::*;
 pub use block::*;
 pub use blue_work::*;
+pub use cursor::*;
 pub use feerate_estimate::*;
 pub use hash::*;
 pub use header::*;
 pub use hex_cnv::*;
 pub use mempool::*;
 pub use message::*;
+pub use mining::*;
 pub use network::*;
 pub use optional::*;
 pub use peer::*;
@@ -9,6 +9,7 @@ mod mockery {
     use kaspa_consensus_core::tx::ScriptPublicKey;
     use kaspa_hashes::Hash;
     use kaspa_math::Uint192;
+    use kaspa_notify::scope::{Scope, VirtualChainChangedScope};
     use kaspa_notify::subscription::Command;
     use kaspa_rpc_macros::test_wrpc_serializer as test;
     use kaspa_txscript::script_class::ScriptClass;
@@ -641,7 +642,7 @@ mod mockery {
 
     impl Mock for GetBlockTemplateRequest {
         fn mock() -> Self {
-            GetBlockTemplateRequest { pay_address: mock(), extra_data: vec![4, 2] }
+            GetBlockTemplateRequest { pay_address: mock(), extra_data: vec![4, 2], payouts: vec![RpcCoinbasePayout::new(mock(), 7)] }
         }
     }
 
@@ -649,7 +650,7 @@ mod mockery {
 
     impl Mock for GetBlockTemplateResponse {
         fn mock() -> Self {
-            GetBlockTemplateResponse { block: mock(), is_synced: true }
+            GetBlockTemplateResponse { block: mock(), is_synced: true, payouts_recorded_only: true }
         }
     }
 
@@ -851,6 +852,12 @@ mod mockery {
 
     test!(GetVirtualChainFromBlockRequest);
 
+    impl Mock for RpcNotificationCursor {
+        fn mock() -> Self {
+            RpcNotificationCursor { chain_block_hash: mock(), daa_score: mock() }
+        }
+    }
+
     impl Mock for RpcAcceptedTransactionIds {
         fn mock() -> Self {
             RpcAcceptedTransactionIds { accepting_block_hash: mock(), accepted_transaction_ids: mock() }
@@ -1297,7 +1304,7 @@ mod mockery {
 
     impl Mock for NotifyBlockAddedRequest {
         fn mock() -> Self {
-            NotifyBlockAddedRequest { command: Command::Start }
+            NotifyBlockAddedRequest { command: Command::Start, addresses: mock(), script_public_keys: mock() }
         }
     }
 
@@ -1341,6 +1348,7 @@ mod mockery {
                 removed_chain_block_hashes: mock(),
                 added_chain_block_hashes: mock(),
                 accepted_transaction_ids: mock(),
+                cursor: mock(),
             }
         }
     }
@@ -1413,7 +1421,7 @@ mod mockery {
 
     impl Mock for UtxosChangedNotification {
         fn mock() -> Self {
-            UtxosChangedNotification { added: mock(), removed: mock() }
+            UtxosChangedNotification { added: mock(), removed: mock(), cursor: mock() }
         }
     }
 
@@ -1515,6 +1523,54 @@ mod mockery {
 
     test!(NewBlockTemplateNotification);
 
+    impl Mock for NotifyMempoolTransactionAddedRequest {
+        fn mock() -> Self {
+            NotifyMempoolTransactionAddedRequest { addresses: mock(), script_public_keys: mock(), command: Command::Start }
+        }
+    }
+
+    test!(NotifyMempoolTransactionAddedRequest);
+
+    impl Mock for NotifyMempoolTransactionAddedResponse {
+        fn mock() -> Self {
+            NotifyMempoolTransactionAddedResponse {}
+        }
+    }
+
+    test!(NotifyMempoolTransactionAddedResponse);
+
+    impl Mock for MempoolTransactionAddedNotification {
+        fn mock() -> Self {
+            MempoolTransactionAddedNotification { transaction: mock(), fee: mock() }
+        }
+    }
+
+    test!(MempoolTransactionAddedNotification);
+
+    impl Mock for NotifyMempoolTransactionRemovedRequest {
+        fn mock() -> Self {
+            NotifyMempoolTransactionRemovedRequest { addresses: mock(), script_public_keys: mock(), command: Command::Stop }
+        }
+    }
+
+    test!(NotifyMempoolTransactionRemovedRequest);
+
+    impl Mock for NotifyMempoolTransactionRemovedResponse {
+        fn mock() -> Self {
+            NotifyMempoolTransactionRemovedResponse {}
+        }
+    }
+
+    test!(NotifyMempoolTransactionRemovedResponse);
+
+    impl Mock for MempoolTransactionRemovedNotification {
+        fn mock() -> Self {
+            MempoolTransactionRemovedNotification { transaction: mock(), reason: RpcMempoolTransactionRemovalReason::DoubleSpend }
+        }
+    }
+
+    test!(MempoolTransactionRemovedNotification);
+
     impl Mock for SubscribeResponse {
         fn mock() -> Self {
             SubscribeResponse::new(mock())
@@ -1531,6 +1587,22 @@ mod mockery {
 
     test!(UnsubscribeResponse);
 
+    impl Mock for ResumeSubscriptionRequest {
+        fn mock() -> Self {
+            ResumeSubscriptionRequest::new(Scope::VirtualChainChanged(VirtualChainChangedScope::new(true)), mock())
+        }
+    }
+
+    test!(ResumeSubscriptionRequest);
+
+    impl Mock for ResumeSubscriptionResponse {
+        fn mock() -> Self {
+            ResumeSubscriptionResponse::new(mock(), mock())
+        }
+    }
+
+    test!(ResumeSubscriptionResponse);
+
     struct Misalign;
 
     impl Mock for Misalign {
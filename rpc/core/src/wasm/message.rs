@@ -846,6 +846,21 @@ declare! {
          * `extraData` can contain a user-supplied plain text or a byte array represented by `Uint8array`.
          */
         extraData? : string | Uint8Array;
+        /**
+         * Optional weighted split of the block reward. The split is not paid on-chain: the whole
+         * reward is paid to `payAddress` and the split is only recorded in the coinbase payload.
+         */
+        payouts? : ICoinbasePayout[];
+    }
+
+    /**
+     *
+     *
+     * @category Node RPC
+     */
+    export interface ICoinbasePayout {
+        address : Address | string;
+        weight : number;
     }
     "#,
 }
@@ -861,9 +876,11 @@ try_from! ( args: IGetBlockTemplateRequest, GetBlockTemplateRequest, {
     } else {
         Default::default()
     };
+    let payouts = if let Some(payouts) = args.try_get_value("payouts")? { from_value(payouts)? } else { Default::default() };
     Ok(GetBlockTemplateRequest {
         pay_address,
         extra_data,
+        payouts,
     })
 });
 
@@ -877,6 +894,11 @@ declare! {
      */
     export interface IGetBlockTemplateResponse {
         block : IRawBlock;
+        /**
+         * Whether the payout split of the request was only recorded in the coinbase payload,
+         * the whole reward being paid to the pay address.
+         */
+        payoutsRecordedOnly : boolean;
     }
     "#,
 }
@@ -278,6 +278,13 @@ impl RpcApi for GrpcClient {
     route!(get_current_block_color_call, GetCurrentBlockColor);
     route!(get_utxo_return_address_call, GetUtxoReturnAddress);
     route!(get_virtual_chain_from_block_v2_call, GetVirtualChainFromBlockV2);
+    route!(get_fee_estimate_history_call, GetFeeEstimateHistory);
+    route!(reload_mempool_policy_call, ReloadMempoolPolicy);
+    route!(get_mempool_info_call, GetMempoolInfo);
+    route!(get_mempool_transaction_graph_call, GetMempoolTransactionGraph);
+    route!(prioritise_transaction_call, PrioritiseTransaction);
+    route!(get_prioritised_transactions_call, GetPrioritisedTransactions);
+    route!(get_mining_status_call, GetMiningStatus);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
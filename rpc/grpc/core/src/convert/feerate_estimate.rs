@@ -1,6 +1,7 @@
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::RpcError;
+use kaspa_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -32,6 +33,48 @@ from!(item: &kaspa_rpc_core::RpcFeeEstimateVerboseExperimentalData, protowire::R
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcFeerateEstimateSample, protowire::RpcFeerateEstimateSample, {
+    Self {
+        timestamp: item.timestamp,
+        daa_score: item.daa_score,
+        total_weight: item.total_weight,
+        inclusion_interval: item.inclusion_interval,
+        minimum_feerate: item.minimum_feerate,
+        estimate: Some((&item.estimate).into()),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcTransactionInclusionRecord, protowire::RpcTransactionInclusionRecord, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        fee: item.fee,
+        feerate: item.feerate,
+        added_timestamp: item.added_timestamp,
+        added_daa_score: item.added_daa_score,
+        included_timestamp: item.included_timestamp,
+        included_daa_score: item.included_daa_score,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcFeeEstimateReplayParams, protowire::RpcFeeEstimateReplayParams, {
+    Self {
+        total_weight_factor: item.total_weight_factor,
+        inclusion_interval_factor: item.inclusion_interval_factor,
+        target_time_per_block_seconds: item.target_time_per_block_seconds,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcFeeEstimateReplayReport, protowire::RpcFeeEstimateReplayReport, {
+    Self {
+        evaluated_transactions: item.evaluated_transactions,
+        mean_predicted_seconds: item.mean_predicted_seconds,
+        mean_realized_seconds: item.mean_realized_seconds,
+        mean_absolute_error_seconds: item.mean_absolute_error_seconds,
+        within_prediction_ratio: item.within_prediction_ratio,
+        within_bucket_ratio: item.within_bucket_ratio,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -64,3 +107,48 @@ try_from!(item: &protowire::RpcFeeEstimateVerboseExperimentalData, kaspa_rpc_cor
         next_block_template_feerate_max: item.next_block_template_feerate_max,
     }
 });
+
+try_from!(item: &protowire::RpcFeerateEstimateSample, kaspa_rpc_core::RpcFeerateEstimateSample, {
+    Self {
+        timestamp: item.timestamp,
+        daa_score: item.daa_score,
+        total_weight: item.total_weight,
+        inclusion_interval: item.inclusion_interval,
+        minimum_feerate: item.minimum_feerate,
+        estimate: item.estimate
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcFeerateEstimateSample".to_string(), "estimate".to_string()))?
+            .try_into()?,
+    }
+});
+
+try_from!(item: &protowire::RpcTransactionInclusionRecord, kaspa_rpc_core::RpcTransactionInclusionRecord, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        fee: item.fee,
+        feerate: item.feerate,
+        added_timestamp: item.added_timestamp,
+        added_daa_score: item.added_daa_score,
+        included_timestamp: item.included_timestamp,
+        included_daa_score: item.included_daa_score,
+    }
+});
+
+try_from!(item: &protowire::RpcFeeEstimateReplayParams, kaspa_rpc_core::RpcFeeEstimateReplayParams, {
+    Self {
+        total_weight_factor: item.total_weight_factor,
+        inclusion_interval_factor: item.inclusion_interval_factor,
+        target_time_per_block_seconds: item.target_time_per_block_seconds,
+    }
+});
+
+try_from!(item: &protowire::RpcFeeEstimateReplayReport, kaspa_rpc_core::RpcFeeEstimateReplayReport, {
+    Self {
+        evaluated_transactions: item.evaluated_transactions,
+        mean_predicted_seconds: item.mean_predicted_seconds,
+        mean_realized_seconds: item.mean_realized_seconds,
+        mean_absolute_error_seconds: item.mean_absolute_error_seconds,
+        within_prediction_ratio: item.within_prediction_ratio,
+        within_bucket_ratio: item.within_bucket_ratio,
+    }
+});
@@ -65,6 +65,13 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(GetCurrentBlockColor);
     impl_into_kaspad_request!(GetUtxoReturnAddress);
     impl_into_kaspad_request!(GetVirtualChainFromBlockV2);
+    impl_into_kaspad_request!(GetFeeEstimateHistory);
+    impl_into_kaspad_request!(ReloadMempoolPolicy);
+    impl_into_kaspad_request!(GetMempoolInfo);
+    impl_into_kaspad_request!(GetMempoolTransactionGraph);
+    impl_into_kaspad_request!(PrioritiseTransaction);
+    impl_into_kaspad_request!(GetPrioritisedTransactions);
+    impl_into_kaspad_request!(GetMiningStatus);
 
     impl_into_kaspad_request!(NotifyBlockAdded);
     impl_into_kaspad_request!(NotifyNewBlockTemplate);
@@ -74,6 +81,8 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_request!(NotifyVirtualChainChanged);
     impl_into_kaspad_request!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_request!(NotifyMempoolTransactionAdded);
+    impl_into_kaspad_request!(NotifyMempoolTransactionRemoved);
 
     macro_rules! impl_into_kaspad_request {
         ($name:tt) => {
@@ -204,6 +213,13 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_response!(GetCurrentBlockColor);
     impl_into_kaspad_response!(GetUtxoReturnAddress);
     impl_into_kaspad_response!(GetVirtualChainFromBlockV2);
+    impl_into_kaspad_response!(GetFeeEstimateHistory);
+    impl_into_kaspad_response!(ReloadMempoolPolicy);
+    impl_into_kaspad_response!(GetMempoolInfo);
+    impl_into_kaspad_response!(GetMempoolTransactionGraph);
+    impl_into_kaspad_response!(PrioritiseTransaction);
+    impl_into_kaspad_response!(GetPrioritisedTransactions);
+    impl_into_kaspad_response!(GetMiningStatus);
 
     impl_into_kaspad_notify_response!(NotifyBlockAdded);
     impl_into_kaspad_notify_response!(NotifyNewBlockTemplate);
@@ -213,6 +229,8 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_notify_response!(NotifyVirtualChainChanged);
     impl_into_kaspad_notify_response!(NotifySinkBlueScoreChanged);
+    impl_into_kaspad_notify_response!(NotifyMempoolTransactionAdded);
+    impl_into_kaspad_notify_response!(NotifyMempoolTransactionRemoved);
 
     impl_into_kaspad_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
     impl_into_kaspad_notify_response!(NotifyPruningPointUtxoSetOverride, StopNotifyingPruningPointUtxoSetOverride);
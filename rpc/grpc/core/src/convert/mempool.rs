@@ -1,6 +1,7 @@
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::RpcError;
+use kaspa_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -18,6 +19,44 @@ from!(item: &kaspa_rpc_core::RpcMempoolEntryByAddress, protowire::RpcMempoolEntr
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcFeerateHistogramBucket, protowire::RpcFeerateHistogramBucket, {
+    Self {
+        feerate: item.feerate,
+        estimated_seconds: item.estimated_seconds,
+        transaction_count: item.transaction_count,
+        total_mass: item.total_mass,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcMempoolEntryInfo, protowire::RpcMempoolEntryInfo, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        fee: item.fee,
+        mass: item.mass,
+        feerate: item.feerate,
+        is_high_priority: item.is_high_priority,
+        added_daa_score: item.added_daa_score,
+        ancestor_count: item.ancestor_count,
+        descendant_count: item.descendant_count,
+        eviction_rank: item.eviction_rank,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcMempoolGraphNode, protowire::RpcMempoolGraphNode, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        fee: item.fee,
+        mass: item.mass,
+        feerate: item.feerate,
+        is_high_priority: item.is_high_priority,
+        parent_ids: item.parent_ids.iter().map(|x| x.to_string()).collect(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcPrioritisedTransaction, protowire::RpcPrioritisedTransaction, {
+    Self { transaction_id: item.transaction_id.to_string(), fee_delta: item.fee_delta, in_mempool: item.in_mempool }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -40,3 +79,41 @@ try_from!(item: &protowire::RpcMempoolEntryByAddress, kaspa_rpc_core::RpcMempool
         item.receiving.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
     )
 });
+
+try_from!(item: &protowire::RpcFeerateHistogramBucket, kaspa_rpc_core::RpcFeerateHistogramBucket, {
+    Self {
+        feerate: item.feerate,
+        estimated_seconds: item.estimated_seconds,
+        transaction_count: item.transaction_count,
+        total_mass: item.total_mass,
+    }
+});
+
+try_from!(item: &protowire::RpcMempoolEntryInfo, kaspa_rpc_core::RpcMempoolEntryInfo, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        fee: item.fee,
+        mass: item.mass,
+        feerate: item.feerate,
+        is_high_priority: item.is_high_priority,
+        added_daa_score: item.added_daa_score,
+        ancestor_count: item.ancestor_count,
+        descendant_count: item.descendant_count,
+        eviction_rank: item.eviction_rank,
+    }
+});
+
+try_from!(item: &protowire::RpcMempoolGraphNode, kaspa_rpc_core::RpcMempoolGraphNode, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        fee: item.fee,
+        mass: item.mass,
+        feerate: item.feerate,
+        is_high_priority: item.is_high_priority,
+        parent_ids: item.parent_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
+try_from!(item: &protowire::RpcPrioritisedTransaction, kaspa_rpc_core::RpcPrioritisedTransaction, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)?, fee_delta: item.fee_delta, in_mempool: item.in_mempool }
+});
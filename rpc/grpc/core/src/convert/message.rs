@@ -157,10 +157,16 @@ from!(item: &kaspa_rpc_core::GetBlockTemplateRequest, protowire::GetBlockTemplat
     Self {
         pay_address: (&item.pay_address).into(),
         extra_data: String::from_utf8(item.extra_data.clone()).expect("extra data has to be valid UTF-8"),
+        payouts: item.payouts.iter().map(|x| x.into()).collect(),
     }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetBlockTemplateResponse>, protowire::GetBlockTemplateResponseMessage, {
-    Self { block: Some((&item.block).into()), is_synced: item.is_synced, error: None }
+    Self {
+        block: Some((&item.block).into()),
+        is_synced: item.is_synced,
+        payouts_recorded_only: item.payouts_recorded_only,
+        error: None,
+    }
 });
 
 from!(item: &kaspa_rpc_core::GetBlockRequest, protowire::GetBlockRequestMessage, {
@@ -171,7 +177,11 @@ from!(item: RpcResult<&kaspa_rpc_core::GetBlockResponse>, protowire::GetBlockRes
 });
 
 from!(item: &kaspa_rpc_core::NotifyBlockAddedRequest, protowire::NotifyBlockAddedRequestMessage, {
-    Self { command: item.command.into() }
+    Self {
+        command: item.command.into(),
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        script_public_keys: item.script_public_keys.iter().map(|x| x.into()).collect(),
+    }
 });
 from!(RpcResult<&kaspa_rpc_core::NotifyBlockAddedResponse>, protowire::NotifyBlockAddedResponseMessage);
 
@@ -419,6 +429,74 @@ from!(item: RpcResult<&kaspa_rpc_core::GetFeeEstimateExperimentalResponse>, prot
         error: None
     }
 });
+from!(item: &kaspa_rpc_core::GetFeeEstimateHistoryRequest, protowire::GetFeeEstimateHistoryRequestMessage, {
+    Self {
+        from_timestamp: item.from_timestamp,
+        replay_params: item.replay_params.as_ref().map(|x| x.into()),
+        from_sample_sequence: item.from_sample_sequence,
+        from_inclusion_sequence: item.from_inclusion_sequence,
+        limit: item.limit,
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetFeeEstimateHistoryResponse>, protowire::GetFeeEstimateHistoryResponseMessage, {
+    Self {
+        samples: item.samples.iter().map(|x| x.into()).collect(),
+        inclusions: item.inclusions.iter().map(|x| x.into()).collect(),
+        replay_report: item.replay_report.as_ref().map(|x| x.into()),
+        next_sample_sequence: item.next_sample_sequence,
+        next_inclusion_sequence: item.next_inclusion_sequence,
+        has_more: item.has_more,
+        error: None
+    }
+});
+from!(&kaspa_rpc_core::ReloadMempoolPolicyRequest, protowire::ReloadMempoolPolicyRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::ReloadMempoolPolicyResponse>, protowire::ReloadMempoolPolicyResponseMessage, {
+    Self { policies: item.policies.clone(), error: None }
+});
+from!(item: &kaspa_rpc_core::GetMempoolInfoRequest, protowire::GetMempoolInfoRequestMessage, {
+    Self { include_entries: item.include_entries }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetMempoolInfoResponse>, protowire::GetMempoolInfoResponseMessage, {
+    Self {
+        transaction_count: item.transaction_count,
+        ready_transaction_count: item.ready_transaction_count,
+        orphan_count: item.orphan_count,
+        total_mass: item.total_mass,
+        ready_total_mass: item.ready_total_mass,
+        total_fees: item.total_fees,
+        estimated_size: item.estimated_size,
+        feerate_histogram: item.feerate_histogram.iter().map(|x| x.into()).collect(),
+        entries: item.entries.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+from!(item: &kaspa_rpc_core::GetMempoolTransactionGraphRequest, protowire::GetMempoolTransactionGraphRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetMempoolTransactionGraphResponse>, protowire::GetMempoolTransactionGraphResponseMessage, {
+    Self { nodes: item.nodes.iter().map(|x| x.into()).collect(), error: None }
+});
+from!(item: &kaspa_rpc_core::PrioritiseTransactionRequest, protowire::PrioritiseTransactionRequestMessage, {
+    Self { transaction_id: item.transaction_id.to_string(), fee_delta: item.fee_delta }
+});
+from!(item: RpcResult<&kaspa_rpc_core::PrioritiseTransactionResponse>, protowire::PrioritiseTransactionResponseMessage, {
+    Self { fee_delta: item.fee_delta, error: None }
+});
+from!(&kaspa_rpc_core::GetPrioritisedTransactionsRequest, protowire::GetPrioritisedTransactionsRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetPrioritisedTransactionsResponse>, protowire::GetPrioritisedTransactionsResponseMessage, {
+    Self { transactions: item.transactions.iter().map(|x| x.into()).collect(), error: None }
+});
+from!(&kaspa_rpc_core::GetMiningStatusRequest, protowire::GetMiningStatusRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetMiningStatusResponse>, protowire::GetMiningStatusResponseMessage, {
+    Self {
+        is_mining_allowed: item.is_mining_allowed,
+        reason: item.reason.clone(),
+        is_nearly_synced: item.is_nearly_synced,
+        has_sufficient_peer_connectivity: item.has_sufficient_peer_connectivity,
+        rules: item.rules.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
 
 from!(item: &kaspa_rpc_core::GetCurrentBlockColorRequest, protowire::GetCurrentBlockColorRequestMessage, {
     Self {
@@ -542,6 +620,24 @@ from!(item: &kaspa_rpc_core::NotifyUtxosChangedRequest, protowire::StopNotifying
 from!(RpcResult<&kaspa_rpc_core::NotifyUtxosChangedResponse>, protowire::NotifyUtxosChangedResponseMessage);
 from!(RpcResult<&kaspa_rpc_core::NotifyUtxosChangedResponse>, protowire::StopNotifyingUtxosChangedResponseMessage);
 
+from!(item: &kaspa_rpc_core::NotifyMempoolTransactionAddedRequest, protowire::NotifyMempoolTransactionAddedRequestMessage, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        script_public_keys: item.script_public_keys.iter().map(|x| x.into()).collect(),
+        command: item.command.into(),
+    }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyMempoolTransactionAddedResponse>, protowire::NotifyMempoolTransactionAddedResponseMessage);
+
+from!(item: &kaspa_rpc_core::NotifyMempoolTransactionRemovedRequest, protowire::NotifyMempoolTransactionRemovedRequestMessage, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.into()).collect(),
+        script_public_keys: item.script_public_keys.iter().map(|x| x.into()).collect(),
+        command: item.command.into(),
+    }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifyMempoolTransactionRemovedResponse>, protowire::NotifyMempoolTransactionRemovedResponseMessage);
+
 from!(item: &kaspa_rpc_core::NotifyPruningPointUtxoSetOverrideRequest, protowire::NotifyPruningPointUtxoSetOverrideRequestMessage, {
     Self { command: item.command.into() }
 });
@@ -624,7 +720,11 @@ impl TryFrom<&protowire::SubmitBlockResponseMessage> for kaspa_rpc_core::SubmitB
 }
 
 try_from!(item: &protowire::GetBlockTemplateRequestMessage, kaspa_rpc_core::GetBlockTemplateRequest, {
-    Self { pay_address: item.pay_address.clone().try_into()?, extra_data: RpcExtraData::from_iter(item.extra_data.bytes()) }
+    Self {
+        pay_address: item.pay_address.clone().try_into()?,
+        extra_data: RpcExtraData::from_iter(item.extra_data.bytes()),
+        payouts: item.payouts.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
 });
 try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<kaspa_rpc_core::GetBlockTemplateResponse>, {
     Self {
@@ -634,6 +734,7 @@ try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<kaspa_rpc
             .ok_or_else(|| RpcError::MissingRpcFieldError("GetBlockTemplateResponseMessage".to_string(), "block".to_string()))?
             .try_into()?,
         is_synced: item.is_synced,
+        payouts_recorded_only: item.payouts_recorded_only,
     }
 });
 
@@ -651,7 +752,11 @@ try_from!(item: &protowire::GetBlockResponseMessage, RpcResult<kaspa_rpc_core::G
 });
 
 try_from!(item: &protowire::NotifyBlockAddedRequestMessage, kaspa_rpc_core::NotifyBlockAddedRequest, {
-    Self { command: item.command.into() }
+    Self {
+        command: item.command.into(),
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        script_public_keys: item.script_public_keys.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
 });
 try_from!(&protowire::NotifyBlockAddedResponseMessage, RpcResult<kaspa_rpc_core::NotifyBlockAddedResponse>);
 
@@ -946,6 +1051,71 @@ try_from!(item: &protowire::GetFeeEstimateExperimentalResponseMessage, RpcResult
         verbose: item.verbose.as_ref().map(|x| x.try_into()).transpose()?
     }
 });
+try_from!(item: &protowire::GetFeeEstimateHistoryRequestMessage, kaspa_rpc_core::GetFeeEstimateHistoryRequest, {
+    Self {
+        from_timestamp: item.from_timestamp,
+        replay_params: item.replay_params.as_ref().map(|x| x.try_into()).transpose()?,
+        from_sample_sequence: item.from_sample_sequence,
+        from_inclusion_sequence: item.from_inclusion_sequence,
+        limit: item.limit,
+    }
+});
+try_from!(item: &protowire::GetFeeEstimateHistoryResponseMessage, RpcResult<kaspa_rpc_core::GetFeeEstimateHistoryResponse>, {
+    Self {
+        samples: item.samples.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        inclusions: item.inclusions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        replay_report: item.replay_report.as_ref().map(|x| x.try_into()).transpose()?,
+        next_sample_sequence: item.next_sample_sequence,
+        next_inclusion_sequence: item.next_inclusion_sequence,
+        has_more: item.has_more,
+    }
+});
+try_from!(&protowire::ReloadMempoolPolicyRequestMessage, kaspa_rpc_core::ReloadMempoolPolicyRequest);
+try_from!(item: &protowire::ReloadMempoolPolicyResponseMessage, RpcResult<kaspa_rpc_core::ReloadMempoolPolicyResponse>, {
+    Self { policies: item.policies.clone() }
+});
+try_from!(item: &protowire::GetMempoolInfoRequestMessage, kaspa_rpc_core::GetMempoolInfoRequest, {
+    Self { include_entries: item.include_entries }
+});
+try_from!(item: &protowire::GetMempoolInfoResponseMessage, RpcResult<kaspa_rpc_core::GetMempoolInfoResponse>, {
+    Self {
+        transaction_count: item.transaction_count,
+        ready_transaction_count: item.ready_transaction_count,
+        orphan_count: item.orphan_count,
+        total_mass: item.total_mass,
+        ready_total_mass: item.ready_total_mass,
+        total_fees: item.total_fees,
+        estimated_size: item.estimated_size,
+        feerate_histogram: item.feerate_histogram.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+try_from!(item: &protowire::GetMempoolTransactionGraphRequestMessage, kaspa_rpc_core::GetMempoolTransactionGraphRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)? }
+});
+try_from!(item: &protowire::GetMempoolTransactionGraphResponseMessage, RpcResult<kaspa_rpc_core::GetMempoolTransactionGraphResponse>, {
+    Self { nodes: item.nodes.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(item: &protowire::PrioritiseTransactionRequestMessage, kaspa_rpc_core::PrioritiseTransactionRequest, {
+    Self { transaction_id: RpcHash::from_str(&item.transaction_id)?, fee_delta: item.fee_delta }
+});
+try_from!(item: &protowire::PrioritiseTransactionResponseMessage, RpcResult<kaspa_rpc_core::PrioritiseTransactionResponse>, {
+    Self { fee_delta: item.fee_delta }
+});
+try_from!(&protowire::GetPrioritisedTransactionsRequestMessage, kaspa_rpc_core::GetPrioritisedTransactionsRequest);
+try_from!(item: &protowire::GetPrioritisedTransactionsResponseMessage, RpcResult<kaspa_rpc_core::GetPrioritisedTransactionsResponse>, {
+    Self { transactions: item.transactions.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+try_from!(&protowire::GetMiningStatusRequestMessage, kaspa_rpc_core::GetMiningStatusRequest);
+try_from!(item: &protowire::GetMiningStatusResponseMessage, RpcResult<kaspa_rpc_core::GetMiningStatusResponse>, {
+    Self {
+        is_mining_allowed: item.is_mining_allowed,
+        reason: item.reason.clone(),
+        is_nearly_synced: item.is_nearly_synced,
+        has_sufficient_peer_connectivity: item.has_sufficient_peer_connectivity,
+        rules: item.rules.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
 
 try_from!(item: &protowire::GetCurrentBlockColorRequestMessage, kaspa_rpc_core::GetCurrentBlockColorRequest, {
     Self {
@@ -1052,6 +1222,27 @@ try_from!(item: &protowire::StopNotifyingUtxosChangedRequestMessage, kaspa_rpc_c
 try_from!(&protowire::NotifyUtxosChangedResponseMessage, RpcResult<kaspa_rpc_core::NotifyUtxosChangedResponse>);
 try_from!(&protowire::StopNotifyingUtxosChangedResponseMessage, RpcResult<kaspa_rpc_core::NotifyUtxosChangedResponse>);
 
+try_from!(item: &protowire::NotifyMempoolTransactionAddedRequestMessage, kaspa_rpc_core::NotifyMempoolTransactionAddedRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        script_public_keys: item.script_public_keys.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        command: item.command.into(),
+    }
+});
+try_from!(&protowire::NotifyMempoolTransactionAddedResponseMessage, RpcResult<kaspa_rpc_core::NotifyMempoolTransactionAddedResponse>);
+
+try_from!(item: &protowire::NotifyMempoolTransactionRemovedRequestMessage, kaspa_rpc_core::NotifyMempoolTransactionRemovedRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        script_public_keys: item.script_public_keys.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        command: item.command.into(),
+    }
+});
+try_from!(
+    &protowire::NotifyMempoolTransactionRemovedResponseMessage,
+    RpcResult<kaspa_rpc_core::NotifyMempoolTransactionRemovedResponse>
+);
+
 try_from!(
     item: &protowire::NotifyPruningPointUtxoSetOverrideRequestMessage,
     kaspa_rpc_core::NotifyPruningPointUtxoSetOverrideRequest,
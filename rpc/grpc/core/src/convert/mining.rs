@@ -0,0 +1,40 @@
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::RpcError;
+use std::str::FromStr;
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcMiningRuleStatus, protowire::RpcMiningRuleStatus, {
+    Self {
+        name: item.name.clone(),
+        kind: item.kind.to_string(),
+        enabled: item.enabled,
+        triggered: item.triggered,
+        status: item.status.clone(),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcCoinbasePayout, protowire::RpcCoinbasePayout, {
+    Self { address: (&item.address).into(), weight: item.weight }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcMiningRuleStatus, kaspa_rpc_core::RpcMiningRuleStatus, {
+    Self {
+        name: item.name.clone(),
+        kind: kaspa_rpc_core::RpcMiningRuleKind::from_str(&item.kind)?,
+        enabled: item.enabled,
+        triggered: item.triggered,
+        status: item.status.clone(),
+    }
+});
+
+try_from!(item: &protowire::RpcCoinbasePayout, kaspa_rpc_core::RpcCoinbasePayout, {
+    Self { address: item.address.clone().try_into()?, weight: item.weight }
+});
@@ -8,6 +8,7 @@ pub mod kaspad;
 pub mod mempool;
 pub mod message;
 pub mod metrics;
+pub mod mining;
 pub mod notification;
 pub mod optional;
 pub mod peer;
@@ -1,5 +1,6 @@
 use crate::protowire::{
-    kaspad_response::Payload, BlockAddedNotificationMessage, KaspadResponse, NewBlockTemplateNotificationMessage, RpcNotifyCommand,
+    kaspad_response::Payload, BlockAddedNotificationMessage, KaspadResponse, MempoolTransactionAddedNotificationMessage,
+    MempoolTransactionRemovedNotificationMessage, NewBlockTemplateNotificationMessage, RpcNotificationCursor, RpcNotifyCommand,
 };
 use crate::protowire::{
     FinalityConflictNotificationMessage, FinalityConflictResolvedNotificationMessage, NotifyPruningPointUtxoSetOverrideRequestMessage,
@@ -34,6 +35,8 @@ from!(item: &kaspa_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(ref notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         },
+        Notification::MempoolTransactionAdded(ref notification) => Payload::MempoolTransactionAddedNotification(notification.into()),
+        Notification::MempoolTransactionRemoved(ref notification) => Payload::MempoolTransactionRemovedNotification(notification.into()),
     }
 });
 
@@ -41,14 +44,27 @@ from!(item: &kaspa_rpc_core::BlockAddedNotification, BlockAddedNotificationMessa
 
 from!(&kaspa_rpc_core::NewBlockTemplateNotification, NewBlockTemplateNotificationMessage);
 
+from!(item: &kaspa_rpc_core::MempoolTransactionAddedNotification, MempoolTransactionAddedNotificationMessage, {
+    Self { transaction: Some((&*item.transaction).into()), fee: item.fee }
+});
+
+from!(item: &kaspa_rpc_core::MempoolTransactionRemovedNotification, MempoolTransactionRemovedNotificationMessage, {
+    Self { transaction: Some((&*item.transaction).into()), reason: item.reason.to_string() }
+});
+
 from!(item: &kaspa_rpc_core::VirtualChainChangedNotification, VirtualChainChangedNotificationMessage, {
     Self {
         removed_chain_block_hashes: item.removed_chain_block_hashes.iter().map(|x| x.to_string()).collect(),
         added_chain_block_hashes: item.added_chain_block_hashes.iter().map(|x| x.to_string()).collect(),
         accepted_transaction_ids: item.accepted_transaction_ids.iter().map(|x| x.into()).collect(),
+        cursor: item.cursor.as_ref().map(|x| x.into()),
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcNotificationCursor, RpcNotificationCursor, {
+    Self { chain_block_hash: item.chain_block_hash.to_string(), daa_score: item.daa_score }
+});
+
 from!(item: &kaspa_rpc_core::FinalityConflictNotification, FinalityConflictNotificationMessage, {
     Self { violating_block_hash: item.violating_block_hash.to_string() }
 });
@@ -61,6 +77,7 @@ from!(item: &kaspa_rpc_core::UtxosChangedNotification, UtxosChangedNotificationM
     Self {
         added: item.added.iter().map(|x| x.into()).collect::<Vec<_>>(),
         removed: item.removed.iter().map(|x| x.into()).collect::<Vec<_>>(),
+        cursor: item.cursor.as_ref().map(|x| x.into()),
     }
 });
 
@@ -117,6 +134,12 @@ try_from!(item: &Payload, kaspa_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(ref notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::MempoolTransactionAddedNotification(ref notification) => {
+            Notification::MempoolTransactionAdded(notification.try_into()?)
+        }
+        Payload::MempoolTransactionRemovedNotification(ref notification) => {
+            Notification::MempoolTransactionRemoved(notification.try_into()?)
+        }
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -134,6 +157,34 @@ try_from!(item: &BlockAddedNotificationMessage, kaspa_rpc_core::BlockAddedNotifi
 
 try_from!(&NewBlockTemplateNotificationMessage, kaspa_rpc_core::NewBlockTemplateNotification);
 
+try_from!(item: &MempoolTransactionAddedNotificationMessage, kaspa_rpc_core::MempoolTransactionAddedNotification, {
+    Self {
+        transaction: Arc::new(
+            item.transaction
+                .as_ref()
+                .ok_or_else(|| {
+                    RpcError::MissingRpcFieldError("MempoolTransactionAddedNotificationMessage".to_string(), "transaction".to_string())
+                })?
+                .try_into()?,
+        ),
+        fee: item.fee,
+    }
+});
+
+try_from!(item: &MempoolTransactionRemovedNotificationMessage, kaspa_rpc_core::MempoolTransactionRemovedNotification, {
+    Self {
+        transaction: Arc::new(
+            item.transaction
+                .as_ref()
+                .ok_or_else(|| {
+                    RpcError::MissingRpcFieldError("MempoolTransactionRemovedNotificationMessage".to_string(), "transaction".to_string())
+                })?
+                .try_into()?,
+        ),
+        reason: item.reason.parse()?,
+    }
+});
+
 try_from!(item: &VirtualChainChangedNotificationMessage, kaspa_rpc_core::VirtualChainChangedNotification, {
     Self {
         removed_chain_block_hashes: Arc::new(
@@ -143,9 +194,14 @@ try_from!(item: &VirtualChainChangedNotificationMessage, kaspa_rpc_core::Virtual
             item.added_chain_block_hashes.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
         ),
         accepted_transaction_ids: Arc::new(item.accepted_transaction_ids.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?),
+        cursor: item.cursor.as_ref().map(|x| x.try_into()).transpose()?,
     }
 });
 
+try_from!(item: &RpcNotificationCursor, kaspa_rpc_core::RpcNotificationCursor, {
+    Self { chain_block_hash: RpcHash::from_str(&item.chain_block_hash)?, daa_score: item.daa_score }
+});
+
 try_from!(item: &FinalityConflictNotificationMessage, kaspa_rpc_core::FinalityConflictNotification, {
     Self { violating_block_hash: RpcHash::from_str(&item.violating_block_hash)? }
 });
@@ -158,6 +214,7 @@ try_from!(item: &UtxosChangedNotificationMessage, kaspa_rpc_core::UtxosChangedNo
     Self {
         added: Arc::new(item.added.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?),
         removed: Arc::new(item.removed.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?),
+        cursor: item.cursor.as_ref().map(|x| x.try_into()).transpose()?,
     }
 });
 
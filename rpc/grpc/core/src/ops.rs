@@ -89,6 +89,13 @@ pub enum KaspadPayloadOps {
     GetCurrentBlockColor,
     GetUtxoReturnAddress,
     GetVirtualChainFromBlockV2,
+    GetFeeEstimateHistory,
+    ReloadMempoolPolicy,
+    GetMempoolInfo,
+    GetMempoolTransactionGraph,
+    PrioritiseTransaction,
+    GetPrioritisedTransactions,
+    GetMiningStatus,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -99,6 +106,8 @@ pub enum KaspadPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifyMempoolTransactionAdded,
+    NotifyMempoolTransactionRemoved,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
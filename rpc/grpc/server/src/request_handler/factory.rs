@@ -83,6 +83,13 @@ impl Factory {
                 GetCurrentBlockColor,
                 GetUtxoReturnAddress,
                 GetVirtualChainFromBlockV2,
+                GetFeeEstimateHistory,
+                ReloadMempoolPolicy,
+                GetMempoolInfo,
+                GetMempoolTransactionGraph,
+                PrioritiseTransaction,
+                GetPrioritisedTransactions,
+                GetMiningStatus,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -91,6 +98,8 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifyMempoolTransactionAdded,
+                NotifyMempoolTransactionRemoved,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
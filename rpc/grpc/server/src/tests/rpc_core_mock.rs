@@ -362,6 +362,62 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_fee_estimate_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetFeeEstimateHistoryRequest,
+    ) -> RpcResult<GetFeeEstimateHistoryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_mempool_info_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMempoolInfoRequest,
+    ) -> RpcResult<GetMempoolInfoResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_mempool_transaction_graph_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMempoolTransactionGraphRequest,
+    ) -> RpcResult<GetMempoolTransactionGraphResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn prioritise_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: PrioritiseTransactionRequest,
+    ) -> RpcResult<PrioritiseTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_prioritised_transactions_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetPrioritisedTransactionsRequest,
+    ) -> RpcResult<GetPrioritisedTransactionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_mining_status_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMiningStatusRequest,
+    ) -> RpcResult<GetMiningStatusResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn reload_mempool_policy_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: ReloadMempoolPolicyRequest,
+    ) -> RpcResult<ReloadMempoolPolicyResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn get_utxo_return_address_call(
         &self,
         _connection: Option<&DynRpcConnection>,
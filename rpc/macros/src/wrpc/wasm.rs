@@ -162,7 +162,7 @@ impl ToTokens for RpcSubscriptions {
                 #[wasm_bindgen(js_name = #fn_subscribe_camel)]
                 pub async fn #fn_subscribe_snake(&self) -> Result<()> {
                     if let Some(listener_id) = self.listener_id() {
-                        self.inner.client.start_notify(listener_id, Scope::#scope(#sub_scope {})).await?;
+                        self.inner.client.start_notify(listener_id, Scope::#scope(#sub_scope::default())).await?;
                     } else {
                         workflow_log::log_error!("subscribe on a closed connection");
                     }
@@ -172,7 +172,7 @@ impl ToTokens for RpcSubscriptions {
                 #[wasm_bindgen(js_name = #fn_unsubscribe_camel)]
                 pub async fn #fn_unsubscribe_snake(&self) -> Result<()> {
                     if let Some(listener_id) = self.listener_id() {
-                        self.inner.client.stop_notify(listener_id, Scope::#scope(#sub_scope {})).await?;
+                        self.inner.client.stop_notify(listener_id, Scope::#scope(#sub_scope::default())).await?;
                     } else {
                         workflow_log::log_error!("unsubscribe on a closed connection");
                     }
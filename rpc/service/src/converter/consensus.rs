@@ -7,9 +7,10 @@ use kaspa_consensus_core::{
     hashing::tx::hash,
     header::Header,
     tx::{
-        MutableTransaction, SignableTransaction, Transaction, TransactionId, TransactionInput, TransactionOutput,
+        MutableTransaction, ScriptPublicKey, SignableTransaction, Transaction, TransactionId, TransactionInput, TransactionOutput,
         TransactionQueryResult, TransactionType, UtxoEntry,
     },
+    utxo::utxo_collection::UtxoCollection,
     ChainPath,
 };
 use kaspa_consensus_notify::notification::{self as consensus_notify, Notification as ConsensusNotification};
@@ -19,18 +20,22 @@ use kaspa_math::Uint256;
 use kaspa_mining::model::{owner_txs::OwnerTransactions, TransactionIdSet};
 use kaspa_notify::converter::Converter;
 use kaspa_rpc_core::{
-    BlockAddedNotification, Notification, RpcAcceptanceDataVerbosity, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData,
-    RpcChainBlockAcceptedTransactions, RpcError, RpcHash, RpcHeaderVerbosity, RpcMempoolEntry, RpcMempoolEntryByAddress,
-    RpcMergesetBlockAcceptanceDataVerbosity, RpcOptionalHeader, RpcOptionalTransaction, RpcOptionalTransactionInput,
-    RpcOptionalTransactionInputVerboseData, RpcOptionalTransactionOutput, RpcOptionalTransactionOutputVerboseData,
-    RpcOptionalTransactionVerboseData, RpcOptionalUtxoEntry, RpcOptionalUtxoEntryVerboseData, RpcResult, RpcTransaction,
-    RpcTransactionInput, RpcTransactionInputVerboseDataVerbosity, RpcTransactionInputVerbosity, RpcTransactionOutput,
-    RpcTransactionOutputVerboseData, RpcTransactionOutputVerboseDataVerbosity, RpcTransactionOutputVerbosity,
-    RpcTransactionVerboseData, RpcTransactionVerboseDataVerbosity, RpcTransactionVerbosity, RpcUtxoEntryVerboseDataVerbosity,
-    RpcUtxoEntryVerbosity,
+    BlockAddedNotification, MempoolTransactionAddedNotification, MempoolTransactionRemovedNotification, Notification,
+    RpcAcceptanceDataVerbosity, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData, RpcChainBlockAcceptedTransactions, RpcError,
+    RpcHash, RpcHeaderVerbosity, RpcMempoolEntry, RpcMempoolEntryByAddress, RpcMergesetBlockAcceptanceDataVerbosity,
+    RpcOptionalHeader, RpcOptionalTransaction, RpcOptionalTransactionInput, RpcOptionalTransactionInputVerboseData,
+    RpcOptionalTransactionOutput, RpcOptionalTransactionOutputVerboseData, RpcOptionalTransactionVerboseData, RpcOptionalUtxoEntry,
+    RpcOptionalUtxoEntryVerboseData, RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionInputVerboseDataVerbosity,
+    RpcTransactionInputVerbosity, RpcTransactionOutput, RpcTransactionOutputVerboseData, RpcTransactionOutputVerboseDataVerbosity,
+    RpcTransactionOutputVerbosity, RpcTransactionVerboseData, RpcTransactionVerboseDataVerbosity, RpcTransactionVerbosity,
+    RpcUtxoEntry, RpcUtxoEntryVerboseDataVerbosity, RpcUtxoEntryVerbosity, RpcUtxosByAddressesEntry,
 };
 use kaspa_txscript::{extract_script_pub_key_address, script_class::ScriptClass};
-use std::{collections::HashMap, fmt::Debug, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    sync::Arc,
+};
 /// Conversion of consensus_core to rpc_core structures
 pub struct ConsensusConverter {
     consensus_manager: Arc<ConsensusManager>,
@@ -121,6 +126,24 @@ impl ConsensusConverter {
         transaction_ids.iter().map(|x| self.get_mempool_entry(consensus, transactions.get(x).expect("transaction exists"))).collect()
     }
 
+    /// Converts a UTXO collection into RPC UTXO entries, keeping only the entries locked by
+    /// one of `script_public_keys` when some are provided.
+    pub fn get_utxos_by_addresses_entries(
+        &self,
+        utxos: &UtxoCollection,
+        script_public_keys: Option<&HashSet<ScriptPublicKey>>,
+    ) -> Vec<RpcUtxosByAddressesEntry> {
+        utxos
+            .iter()
+            .filter(|(_, entry)| script_public_keys.is_none_or(|keys| keys.contains(&entry.script_public_key)))
+            .map(|(outpoint, entry)| RpcUtxosByAddressesEntry {
+                address: extract_script_pub_key_address(&entry.script_public_key, self.config.prefix()).ok(),
+                outpoint: (*outpoint).into(),
+                utxo_entry: RpcUtxoEntry::new(entry.amount, entry.script_public_key.clone(), entry.block_daa_score, entry.is_coinbase),
+            })
+            .collect()
+    }
+
     /// Converts a consensus [`Transaction`] into an [`RpcTransaction`], optionally including verbose data.
     ///
     /// _GO-KASPAD: PopulateTransactionWithVerboseData
@@ -674,6 +697,19 @@ impl Converter for ConsensusConverter {
                 let block = Arc::new(self.get_block(&session, &msg.block, true, true).await.unwrap_or_else(|_| (&msg.block).into()));
                 Notification::BlockAdded(BlockAddedNotification { block })
             }
+            consensus_notify::Notification::MempoolTransactionAdded(msg) => {
+                let session = self.consensus_manager.consensus().unguarded_session();
+                let transaction = Arc::new(self.get_transaction(&session, &msg.transaction, None, true));
+                Notification::MempoolTransactionAdded(MempoolTransactionAddedNotification { transaction, fee: msg.fee })
+            }
+            consensus_notify::Notification::MempoolTransactionRemoved(msg) => {
+                let session = self.consensus_manager.consensus().unguarded_session();
+                let transaction = Arc::new(self.get_transaction(&session, &msg.transaction, None, true));
+                Notification::MempoolTransactionRemoved(MempoolTransactionRemovedNotification {
+                    transaction,
+                    reason: msg.reason.into(),
+                })
+            }
             _ => (&incoming).into(),
         }
     }
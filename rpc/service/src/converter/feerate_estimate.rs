@@ -1,7 +1,13 @@
-use kaspa_mining::feerate::{FeeEstimateVerbose, FeerateBucket, FeerateEstimations};
+use kaspa_mining::feerate::{
+    history::{
+        FeeEstimateHistoryExport, FeeEstimateReplayParams, FeeEstimateReplayReport, FeerateEstimateSample, TransactionInclusionRecord,
+    },
+    FeeEstimateVerbose, FeerateBucket, FeerateEstimations,
+};
 use kaspa_rpc_core::{
-    message::GetFeeEstimateExperimentalResponse as RpcFeeEstimateVerboseResponse, RpcFeeEstimate,
-    RpcFeeEstimateVerboseExperimentalData as RpcFeeEstimateVerbose, RpcFeerateBucket,
+    message::GetFeeEstimateExperimentalResponse as RpcFeeEstimateVerboseResponse, GetFeeEstimateHistoryResponse, RpcFeeEstimate,
+    RpcFeeEstimateReplayParams, RpcFeeEstimateReplayReport, RpcFeeEstimateVerboseExperimentalData as RpcFeeEstimateVerbose,
+    RpcFeerateBucket, RpcFeerateEstimateSample, RpcTransactionInclusionRecord,
 };
 
 pub trait FeerateBucketConverter {
@@ -47,3 +53,62 @@ impl FeeEstimateVerboseConverter for FeeEstimateVerbose {
         }
     }
 }
+
+pub trait FeeEstimateHistoryConverter {
+    fn into_rpc(self) -> GetFeeEstimateHistoryResponse;
+}
+
+impl FeeEstimateHistoryConverter for FeeEstimateHistoryExport {
+    fn into_rpc(self) -> GetFeeEstimateHistoryResponse {
+        GetFeeEstimateHistoryResponse {
+            samples: self.samples.into_iter().map(sample_into_rpc).collect(),
+            inclusions: self.inclusions.into_iter().map(inclusion_into_rpc).collect(),
+            replay_report: self.replay_report.map(replay_report_into_rpc),
+            next_sample_sequence: self.next_sample_sequence,
+            next_inclusion_sequence: self.next_inclusion_sequence,
+            has_more: self.has_more,
+        }
+    }
+}
+
+fn sample_into_rpc(sample: FeerateEstimateSample) -> RpcFeerateEstimateSample {
+    RpcFeerateEstimateSample {
+        timestamp: sample.timestamp,
+        daa_score: sample.daa_score,
+        total_weight: sample.total_weight,
+        inclusion_interval: sample.inclusion_interval,
+        minimum_feerate: sample.minimum_feerate,
+        estimate: sample.estimations.into_rpc(),
+    }
+}
+
+fn inclusion_into_rpc(inclusion: TransactionInclusionRecord) -> RpcTransactionInclusionRecord {
+    RpcTransactionInclusionRecord {
+        transaction_id: inclusion.transaction_id,
+        fee: inclusion.fee,
+        feerate: inclusion.feerate,
+        added_timestamp: inclusion.added_timestamp,
+        added_daa_score: inclusion.added_daa_score,
+        included_timestamp: inclusion.included_timestamp,
+        included_daa_score: inclusion.included_daa_score,
+    }
+}
+
+fn replay_report_into_rpc(report: FeeEstimateReplayReport) -> RpcFeeEstimateReplayReport {
+    RpcFeeEstimateReplayReport {
+        evaluated_transactions: report.evaluated_transactions,
+        mean_predicted_seconds: report.mean_predicted_seconds,
+        mean_realized_seconds: report.mean_realized_seconds,
+        mean_absolute_error_seconds: report.mean_absolute_error_seconds,
+        within_prediction_ratio: report.within_prediction_ratio,
+        within_bucket_ratio: report.within_bucket_ratio,
+    }
+}
+
+pub fn replay_params_from_rpc(params: RpcFeeEstimateReplayParams) -> FeeEstimateReplayParams {
+    FeeEstimateReplayParams {
+        total_weight_factor: params.total_weight_factor,
+        inclusion_interval_factor: params.inclusion_interval_factor,
+        target_time_per_block_seconds: params.target_time_per_block_seconds,
+    }
+}
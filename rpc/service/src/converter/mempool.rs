@@ -0,0 +1,70 @@
+use kaspa_mining::model::mempool_info::{
+    FeerateHistogramBucket, MempoolEntryInfo, MempoolGraphNode, MempoolInfo, MempoolTransactionGraph,
+};
+use kaspa_rpc_core::{
+    GetMempoolInfoResponse, GetMempoolTransactionGraphResponse, RpcFeerateHistogramBucket, RpcMempoolEntryInfo, RpcMempoolGraphNode,
+};
+
+pub trait MempoolInfoConverter {
+    fn into_rpc(self) -> GetMempoolInfoResponse;
+}
+
+impl MempoolInfoConverter for MempoolInfo {
+    fn into_rpc(self) -> GetMempoolInfoResponse {
+        GetMempoolInfoResponse {
+            transaction_count: self.transaction_count,
+            ready_transaction_count: self.ready_transaction_count,
+            orphan_count: self.orphan_count,
+            total_mass: self.total_mass,
+            ready_total_mass: self.ready_total_mass,
+            total_fees: self.total_fees,
+            estimated_size: self.estimated_size,
+            feerate_histogram: self.feerate_histogram.into_iter().map(histogram_bucket_into_rpc).collect(),
+            entries: self.entries.into_iter().map(entry_info_into_rpc).collect(),
+        }
+    }
+}
+
+pub trait MempoolTransactionGraphConverter {
+    fn into_rpc(self) -> GetMempoolTransactionGraphResponse;
+}
+
+impl MempoolTransactionGraphConverter for MempoolTransactionGraph {
+    fn into_rpc(self) -> GetMempoolTransactionGraphResponse {
+        GetMempoolTransactionGraphResponse { nodes: self.nodes.into_iter().map(graph_node_into_rpc).collect() }
+    }
+}
+
+fn histogram_bucket_into_rpc(bucket: FeerateHistogramBucket) -> RpcFeerateHistogramBucket {
+    RpcFeerateHistogramBucket {
+        feerate: bucket.feerate,
+        estimated_seconds: bucket.estimated_seconds,
+        transaction_count: bucket.transaction_count,
+        total_mass: bucket.total_mass,
+    }
+}
+
+fn entry_info_into_rpc(entry: MempoolEntryInfo) -> RpcMempoolEntryInfo {
+    RpcMempoolEntryInfo {
+        transaction_id: entry.transaction_id,
+        fee: entry.fee,
+        mass: entry.mass,
+        feerate: entry.feerate,
+        is_high_priority: entry.is_high_priority,
+        added_daa_score: entry.added_daa_score,
+        ancestor_count: entry.ancestor_count,
+        descendant_count: entry.descendant_count,
+        eviction_rank: entry.eviction_rank,
+    }
+}
+
+fn graph_node_into_rpc(node: MempoolGraphNode) -> RpcMempoolGraphNode {
+    RpcMempoolGraphNode {
+        transaction_id: node.transaction_id,
+        fee: node.fee,
+        mass: node.mass,
+        feerate: node.feerate,
+        is_high_priority: node.is_high_priority,
+        parent_ids: node.parent_ids,
+    }
+}
@@ -1,7 +1,10 @@
 //! Core server implementation for ClientAPI
 
 use super::collector::{CollectorFromConsensus, CollectorFromIndex};
-use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter};
+use crate::converter::feerate_estimate::{
+    replay_params_from_rpc, FeeEstimateConverter, FeeEstimateHistoryConverter, FeeEstimateVerboseConverter,
+};
+use crate::converter::mempool::{MempoolInfoConverter, MempoolTransactionGraphConverter};
 use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
 use async_trait::async_trait;
 use kaspa_consensus_core::api::counters::ProcessingCounters;
@@ -11,7 +14,7 @@ use kaspa_consensus_core::tx::{TransactionQueryResult, TransactionType};
 use kaspa_consensus_core::utxo::utxo_inquirer::UtxoInquirerError;
 use kaspa_consensus_core::{
     block::Block,
-    coinbase::MinerData,
+    coinbase::{CoinbasePayout, MinerData},
     config::Config,
     constants::MAX_SOMPI,
     network::NetworkType,
@@ -37,7 +40,7 @@ use kaspa_index_core::{
     connection::IndexChannelConnection, indexed_utxos::UtxoSetByScriptPublicKey, notification::Notification as IndexNotification,
     notifier::IndexNotifier,
 };
-use kaspa_mining::feerate::FeeEstimateVerbose;
+use kaspa_mining::feerate::{history::FeeEstimateHistoryQuery, FeeEstimateVerbose};
 use kaspa_mining::model::tx_query::TransactionQuery;
 use kaspa_mining::{manager::MiningManagerProxy, mempool::tx::Orphan};
 use kaspa_notify::listener::ListenerLifespan;
@@ -54,7 +57,7 @@ use kaspa_notify::{
 };
 use kaspa_p2p_flows::flow_context::FlowContext;
 use kaspa_p2p_lib::common::ProtocolError;
-use kaspa_p2p_mining::rule_engine::MiningRuleEngine;
+use kaspa_p2p_mining::{rule_engine::MiningRuleEngine, rules::mining_rule::MiningRuleKind};
 use kaspa_perf_monitor::{counters::CountersSnapshot, Monitor as PerfMonitor};
 use kaspa_rpc_core::{
     api::{
@@ -74,7 +77,7 @@ use kaspa_utils_tower::counters::TowerConnectionCounters;
 use kaspa_utxoindex::api::UtxoIndexProxy;
 use std::time::Duration;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     iter::once,
     sync::{atomic::Ordering, Arc},
     vec,
@@ -254,6 +257,94 @@ impl RpcCoreService {
         self.core_shutdown_request.listener.clone()
     }
 
+    /// Collects the notifications matching `scope` which a subscriber positioned at `cursor` missed,
+    /// along with the cursor reached once they are applied.
+    ///
+    /// Chain changes are rebuilt from the consensus stores, so only `VirtualChainChanged` and `UtxosChanged`
+    /// scopes can be replayed. UTXO changes are replayed per chain block and may repeat some changes already
+    /// notified to the subscriber from the virtual state.
+    pub async fn get_notification_replay(
+        &self,
+        scope: &Scope,
+        cursor: RpcNotificationCursor,
+    ) -> RpcResult<(Vec<Notification>, RpcNotificationCursor)> {
+        if self.config.notification_replay_window == 0 {
+            return Err(RpcError::NotificationReplayDisabled);
+        }
+        if let Scope::UtxosChanged(utxos_changed_scope) = scope {
+            if !self.config.unsafe_rpc && utxos_changed_scope.addresses.is_empty() {
+                return Err(RpcError::UnavailableInSafeMode);
+            }
+        }
+
+        let session = self.consensus_manager.consensus().session().await;
+        if session.async_is_consensus_in_transitional_ibd_state().await {
+            return Err(RpcError::ConsensusInTransitionalIbdState);
+        }
+
+        // Reject cursors which are unknown or too far behind the sink
+        let out_of_window = |_| RpcError::NotificationCursorOutOfWindow(cursor.chain_block_hash);
+        let window = self.config.notification_replay_window * 1000 / self.config.target_time_per_block();
+        let cursor_daa_score = session.async_get_header(cursor.chain_block_hash).await.map_err(out_of_window)?.daa_score;
+        let sink_daa_score = session.async_get_header(session.async_get_sink().await).await?.daa_score;
+        if sink_daa_score.saturating_sub(cursor_daa_score) > window {
+            return Err(RpcError::NotificationCursorOutOfWindow(cursor.chain_block_hash));
+        }
+
+        let chain_path = session.async_get_virtual_chain_from_block(cursor.chain_block_hash, None).await.map_err(out_of_window)?;
+        let Some(&sink) = chain_path.added.last() else {
+            return Ok((vec![], cursor));
+        };
+        let new_cursor = RpcNotificationCursor::new(sink, session.async_get_header(sink).await?.daa_score);
+
+        let notifications = match scope {
+            Scope::VirtualChainChanged(virtual_chain_changed_scope) => {
+                let accepted_transaction_ids = if virtual_chain_changed_scope.include_accepted_transaction_ids {
+                    self.consensus_converter.get_virtual_chain_accepted_transaction_ids(&session, &chain_path, None).await?
+                } else {
+                    vec![]
+                };
+                vec![Notification::VirtualChainChanged(VirtualChainChangedNotification {
+                    removed_chain_block_hashes: Arc::new(chain_path.removed),
+                    added_chain_block_hashes: Arc::new(chain_path.added),
+                    accepted_transaction_ids: Arc::new(accepted_transaction_ids),
+                    cursor: Some(new_cursor),
+                })]
+            }
+            Scope::UtxosChanged(utxos_changed_scope) => {
+                let script_public_keys = (!utxos_changed_scope.addresses.is_empty())
+                    .then(|| utxos_changed_scope.addresses.iter().map(pay_to_address_script).collect::<HashSet<_>>());
+                let mut notifications = vec![];
+                // Removed chain blocks are unwound in high-to-low order, before added chain blocks get applied in low-to-high order
+                for (hash, is_added) in
+                    chain_path.removed.iter().map(|&hash| (hash, false)).chain(chain_path.added.iter().map(|&hash| (hash, true)))
+                {
+                    let diff = session.async_get_chain_block_utxo_diff(hash).await?;
+                    let (added, removed) = if is_added { (&diff.add, &diff.remove) } else { (&diff.remove, &diff.add) };
+                    let added = self.consensus_converter.get_utxos_by_addresses_entries(added, script_public_keys.as_ref());
+                    let removed = self.consensus_converter.get_utxos_by_addresses_entries(removed, script_public_keys.as_ref());
+                    if added.is_empty() && removed.is_empty() {
+                        continue;
+                    }
+                    let cursor = if is_added {
+                        Some(RpcNotificationCursor::new(hash, session.async_get_header(hash).await?.daa_score))
+                    } else {
+                        None
+                    };
+                    notifications.push(Notification::UtxosChanged(UtxosChangedNotification {
+                        added: Arc::new(added),
+                        removed: Arc::new(removed),
+                        cursor,
+                    }));
+                }
+                notifications
+            }
+            _ => return Err(RpcError::NotificationReplayNotSupported(scope.event_type().to_string())),
+        };
+
+        Ok((notifications, new_cursor))
+    }
+
     async fn get_utxo_set_by_script_public_key<'a>(
         &self,
         addresses: impl Iterator<Item = &'a RpcAddress>,
@@ -381,7 +472,18 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
         let script_public_key = kaspa_txscript::pay_to_address_script(&request.pay_address);
         let extra_data = version().as_bytes().iter().chain(once(&(b'/'))).chain(&request.extra_data).cloned().collect::<Vec<_>>();
-        let miner_data: MinerData = MinerData::new(script_public_key, extra_data);
+        let mut miner_data: MinerData = MinerData::new(script_public_key, extra_data);
+        let payouts_recorded_only = !request.payouts.is_empty();
+        if payouts_recorded_only {
+            let mut payouts = Vec::with_capacity(request.payouts.len());
+            for payout in request.payouts.iter() {
+                if payout.address.prefix != self.config.prefix() {
+                    return Err(kaspa_addresses::AddressError::InvalidPrefix(payout.address.prefix.to_string()))?;
+                }
+                payouts.push(CoinbasePayout::new(kaspa_txscript::pay_to_address_script(&payout.address), payout.weight));
+            }
+            miner_data = session.encode_coinbase_payouts(miner_data, &payouts)?;
+        }
         let block_template = self.mining_manager.clone().get_block_template(&session, miner_data).await?;
 
         // Check coinbase tx payload length
@@ -395,6 +497,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
                 timestamp: block_template.selected_parent_timestamp,
                 daa_score: block_template.selected_parent_daa_score,
             }),
+            payouts_recorded_only,
         })
     }
 
@@ -872,6 +975,129 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         }
     }
 
+    async fn get_fee_estimate_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetFeeEstimateHistoryRequest,
+    ) -> RpcResult<GetFeeEstimateHistoryResponse> {
+        let replay_params = request.replay_params.map(replay_params_from_rpc);
+        if replay_params.is_some_and(|params| !params.is_valid()) {
+            return Err(RpcError::InvalidFeeEstimateReplayParams);
+        }
+        let query = FeeEstimateHistoryQuery {
+            from_timestamp: request.from_timestamp,
+            from_sample_sequence: request.from_sample_sequence,
+            from_inclusion_sequence: request.from_inclusion_sequence,
+            limit: request.limit as usize,
+        };
+        let history = self.mining_manager.clone().get_fee_estimate_history(query, replay_params).await;
+        history.map(|history| history.into_rpc()).ok_or(RpcError::NoFeeEstimateHistory)
+    }
+
+    async fn get_mempool_info_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetMempoolInfoRequest,
+    ) -> RpcResult<GetMempoolInfoResponse> {
+        // Listing the entries traverses the ancestors and descendants of every transaction under the mempool lock
+        if !self.config.unsafe_rpc && request.include_entries {
+            warn!("GetMempoolInfo RPC command called with include_entries while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        Ok(self.mining_manager.clone().get_mempool_info(request.include_entries).await.into_rpc())
+    }
+
+    async fn get_mempool_transaction_graph_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetMempoolTransactionGraphRequest,
+    ) -> RpcResult<GetMempoolTransactionGraphResponse> {
+        // The graph is unbounded, traversing all the mempool ancestors and descendants of the transaction under the mempool lock
+        if !self.config.unsafe_rpc {
+            warn!("GetMempoolTransactionGraph RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let graph = self.mining_manager.clone().get_mempool_transaction_graph(request.transaction_id).await;
+        graph.map(|graph| graph.into_rpc()).ok_or(RpcError::TransactionNotFound(request.transaction_id))
+    }
+
+    async fn prioritise_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: PrioritiseTransactionRequest,
+    ) -> RpcResult<PrioritiseTransactionResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("PrioritiseTransaction RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let session = self.consensus_manager.consensus().unguarded_session();
+        let fee_delta = self.mining_manager.clone().prioritise_transaction(&session, request.transaction_id, request.fee_delta).await;
+        Ok(PrioritiseTransactionResponse { fee_delta })
+    }
+
+    async fn get_prioritised_transactions_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetPrioritisedTransactionsRequest,
+    ) -> RpcResult<GetPrioritisedTransactionsResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("GetPrioritisedTransactions RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let transactions = self
+            .mining_manager
+            .clone()
+            .get_prioritised_transactions()
+            .await
+            .into_iter()
+            .map(|x| RpcPrioritisedTransaction { transaction_id: x.transaction_id, fee_delta: x.fee_delta, in_mempool: x.in_mempool })
+            .collect();
+        Ok(GetPrioritisedTransactionsResponse { transactions })
+    }
+
+    async fn get_mining_status_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMiningStatusRequest,
+    ) -> RpcResult<GetMiningStatusResponse> {
+        let sink_daa_score_timestamp =
+            self.consensus_manager.consensus().unguarded_session().async_get_sink_daa_score_timestamp().await;
+        let status = self.mining_rule_engine.mining_status(sink_daa_score_timestamp);
+        Ok(GetMiningStatusResponse {
+            is_mining_allowed: status.is_mining_allowed,
+            reason: status.reason,
+            is_nearly_synced: status.is_nearly_synced,
+            has_sufficient_peer_connectivity: status.has_sufficient_peer_connectivity,
+            rules: status
+                .rules
+                .into_iter()
+                .map(|rule| RpcMiningRuleStatus {
+                    name: rule.name.to_owned(),
+                    kind: match rule.kind {
+                        MiningRuleKind::Permissive => RpcMiningRuleKind::Permissive,
+                        MiningRuleKind::Restrictive => RpcMiningRuleKind::Restrictive,
+                    },
+                    enabled: rule.enabled,
+                    triggered: rule.triggered,
+                    status: rule.status,
+                })
+                .collect(),
+        })
+    }
+
+    async fn reload_mempool_policy_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: ReloadMempoolPolicyRequest,
+    ) -> RpcResult<ReloadMempoolPolicyResponse> {
+        if !self.config.unsafe_rpc {
+            warn!("ReloadMempoolPolicy RPC command called while node in safe RPC mode -- ignoring.");
+            return Err(RpcError::UnavailableInSafeMode);
+        }
+        let policies = self.mining_manager.clone().reload_mempool_policy().await;
+        policies.map(|policies| ReloadMempoolPolicyResponse { policies }).map_err(|err| RpcError::MempoolPolicyReload(err.to_string()))
+    }
+
     async fn get_utxo_return_address_call(
         &self,
         _connection: Option<&DynRpcConnection>,
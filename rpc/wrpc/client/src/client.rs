@@ -76,6 +76,8 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::MempoolTransactionAddedNotification,
+            RpcApiOps::MempoolTransactionRemovedNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -501,6 +503,28 @@ impl KaspaRpcClient {
         self.inner.notification_intake_channel.lock().unwrap().receiver.clone()
     }
 
+    /// Start sending notifications of some type to a listener, first replaying
+    /// the chain changes missed since `cursor` was notified.
+    ///
+    /// Replayed notifications may overlap with the first live ones so consumers
+    /// should rely on the notification cursors to discard duplicates.
+    /// Returns the cursor the replay ended at.
+    pub async fn resume_notify(
+        &self,
+        id: ListenerId,
+        scope: Scope,
+        cursor: RpcNotificationCursor,
+    ) -> RpcResult<RpcNotificationCursor> {
+        self.notifier().try_start_notify(id, scope.clone())?;
+        let response: Serializable<ResumeSubscriptionResponse> = self
+            .inner
+            .rpc_client
+            .call(RpcApiOps::ResumeSubscription, Serializable(ResumeSubscriptionRequest::new(scope, cursor)))
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(response.0.cursor)
+    }
+
     pub fn ctl(&self) -> &RpcCtl {
         &self.inner.rpc_ctl
     }
@@ -640,13 +664,18 @@ impl RpcApi for KaspaRpcClient {
             GetDaaScoreTimestampEstimate,
             GetFeeEstimate,
             GetFeeEstimateExperimental,
+            GetFeeEstimateHistory,
             GetHeaders,
             GetInfo,
             GetMempoolEntries,
             GetMempoolEntriesByAddresses,
             GetMempoolEntry,
+            GetMempoolInfo,
+            GetMempoolTransactionGraph,
             GetMetrics,
+            GetMiningStatus,
             GetPeerAddresses,
+            GetPrioritisedTransactions,
             GetServerInfo,
             GetSink,
             GetSinkBlueScore,
@@ -657,6 +686,8 @@ impl RpcApi for KaspaRpcClient {
             GetUtxosByAddresses,
             GetVirtualChainFromBlock,
             GetVirtualChainFromBlockV2,
+            PrioritiseTransaction,
+            ReloadMempoolPolicy,
             ResolveFinalityConflict,
             Shutdown,
             SubmitBlock,
@@ -53,14 +53,19 @@ impl Router {
                 GetDaaScoreTimestampEstimate,
                 GetFeeEstimate,
                 GetFeeEstimateExperimental,
+                GetFeeEstimateHistory,
                 GetHeaders,
                 GetInfo,
                 GetMempoolEntries,
                 GetMempoolEntriesByAddresses,
                 GetMempoolEntry,
+                GetMempoolInfo,
+                GetMempoolTransactionGraph,
                 GetMetrics,
+                GetMiningStatus,
                 GetConnections,
                 GetPeerAddresses,
+                GetPrioritisedTransactions,
                 GetServerInfo,
                 GetSink,
                 GetSinkBlueScore,
@@ -70,6 +75,8 @@ impl Router {
                 GetUtxosByAddresses,
                 GetVirtualChainFromBlock,
                 GetVirtualChainFromBlockV2,
+                PrioritiseTransaction,
+                ReloadMempoolPolicy,
                 ResolveFinalityConflict,
                 Shutdown,
                 SubmitBlock,
@@ -89,6 +96,19 @@ impl Router {
             }),
         );
 
+        interface.method(
+            RpcApiOps::ResumeSubscription,
+            workflow_rpc::server::Method::new(
+                move |manager: Server, connection: Connection, request: Serializable<ResumeSubscriptionRequest>| {
+                    Box::pin(async move {
+                        let ResumeSubscriptionRequest { scope, cursor } = request.into_inner();
+                        let cursor = manager.resume_subscription(&connection, scope, cursor).await.map_err(|err| err.to_string())?;
+                        Ok(Serializable(ResumeSubscriptionResponse::new(connection.id(), cursor)))
+                    })
+                },
+            ),
+        );
+
         interface.method(
             RpcApiOps::Unsubscribe,
             workflow_rpc::server::Method::new(move |manager: Server, connection: Connection, scope: Serializable<Scope>| {
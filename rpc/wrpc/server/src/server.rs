@@ -6,7 +6,7 @@ use crate::{
 };
 use kaspa_grpc_client::GrpcClient;
 use kaspa_notify::{
-    connection::ChannelType,
+    connection::{ChannelType, Connection as ConnectionT},
     events::EVENT_TYPE_ARRAY,
     listener::ListenerLifespan,
     notifier::Notifier,
@@ -17,7 +17,7 @@ use kaspa_notify::{
 use kaspa_rpc_core::{
     api::rpc::{DynRpcService, RpcApi},
     notify::{channel::NotificationChannel, connection::ChannelConnection, mode::NotificationMode},
-    Notification, RpcResult,
+    Notification, RpcError, RpcNotificationCursor, RpcResult,
 };
 use kaspa_rpc_service::service::RpcCoreService;
 use std::{
@@ -198,6 +198,37 @@ impl Server {
         Ok(())
     }
 
+    /// Subscribes the connection to `scope` after having sent it the notifications missed since `cursor`.
+    ///
+    /// The replay is performed up to the current sink, then the subscription is started and a second replay
+    /// covers the changes which occurred meanwhile. No notification can be missed this way, though some may be
+    /// received twice. Returns the cursor reached by the replay.
+    pub async fn resume_subscription(
+        &self,
+        connection: &Connection,
+        scope: Scope,
+        cursor: RpcNotificationCursor,
+    ) -> RpcResult<RpcNotificationCursor> {
+        let Some(rpc_core) = &self.inner.rpc_core else {
+            return Err(RpcError::NotificationReplayUnavailable);
+        };
+        workflow_log::log_trace!("notification resume[{}] {scope:?} from {cursor:?}", connection.id());
+        let (notifications, cursor) = rpc_core.service.get_notification_replay(&scope, cursor).await?;
+        Self::send_replay(connection, notifications).await?;
+        self.start_notify(connection, scope.clone()).await?;
+        let (notifications, cursor) = rpc_core.service.get_notification_replay(&scope, cursor).await?;
+        Self::send_replay(connection, notifications).await?;
+        Ok(cursor)
+    }
+
+    async fn send_replay(connection: &Connection, notifications: Vec<Notification>) -> RpcResult<()> {
+        let encoding = connection.encoding();
+        for notification in notifications.iter() {
+            connection.send(Connection::into_message(notification, &encoding)).await?;
+        }
+        Ok(())
+    }
+
     pub async fn stop_notify(&self, connection: &Connection, scope: Scope) -> RpcResult<()> {
         if let Some(listener_id) = connection.listener_id() {
             workflow_log::log_trace!("notification unsubscribe[0x{listener_id:x}] {scope:?}");
@@ -705,7 +705,7 @@ impl RpcClient {
                                     let notification_event = NotificationEvent::Notification(event_type);
                                     if let Some(handlers) = this.inner.notification_callbacks(notification_event) {
 
-                                        let UtxosChangedNotification { added, removed } = utxos_changed_notification;
+                                        let UtxosChangedNotification { added, removed, .. } = utxos_changed_notification;
                                         let added = js_sys::Array::from_iter(added.iter().map(UtxoEntryReference::from).map(JsValue::from));
                                         let removed = js_sys::Array::from_iter(removed.iter().map(UtxoEntryReference::from).map(JsValue::from));
                                         let notification = Object::new();
@@ -913,6 +913,14 @@ build_wrpc_wasm_bindgen_subscriptions!([
     /// New block template notification event is produced when a new block
     /// template is generated for mining in the Kaspa BlockDAG.
     NewBlockTemplate,
+    /// Manage subscription for a mempool transaction added notification event.
+    /// Mempool transaction added notification event is produced when a
+    /// transaction is accepted into the mempool of the Kaspa node.
+    MempoolTransactionAdded,
+    /// Manage subscription for a mempool transaction removed notification event.
+    /// Mempool transaction removed notification event is produced when a
+    /// transaction leaves the mempool of the Kaspa node.
+    MempoolTransactionRemoved,
 ]);
 
 // Build RPC method invocation functions. This macro
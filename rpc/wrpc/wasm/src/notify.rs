@@ -25,6 +25,8 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    MempoolTransactionAdded = "mempool-transaction-added",
+    MempoolTransactionRemoved = "mempool-transaction-removed",
 }
 
 /**
@@ -40,7 +42,9 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate
+    | IMempoolTransactionAdded
+    | IMempoolTransactionRemoved;
 
 /**
  * RPC notification event data map.
@@ -59,6 +63,8 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "mempool-transaction-added" : IMempoolTransactionAdded,
+    "mempool-transaction-removed" : IMempoolTransactionRemoved,
 }
 
 /**
@@ -84,6 +90,8 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeMempoolTransactionAdded},
+ * {@link RpcClient.subscribeMempoolTransactionRemoved},
  * 
  * @category Node RPC
  */
@@ -241,3 +249,33 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    IMempoolTransactionAdded,
+    r#"
+    /**
+     * Mempool transaction added notification event is produced when a
+     * transaction is accepted into the mempool of the Kaspa node.
+     * 
+     * @category Node RPC
+     */
+    export interface IMempoolTransactionAdded {
+        [key: string]: any;
+    }
+    "#,
+}
+
+declare! {
+    IMempoolTransactionRemoved,
+    r#"
+    /**
+     * Mempool transaction removed notification event is produced when a
+     * transaction leaves the mempool of the Kaspa node.
+     * 
+     * @category Node RPC
+     */
+    export interface IMempoolTransactionRemoved {
+        [key: string]: any;
+    }
+    "#,
+}
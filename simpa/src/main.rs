@@ -1,5 +1,5 @@
 use async_channel::unbounded;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use futures::{future::try_join_all, Future};
 use itertools::Itertools;
 use kaspa_alloc::init_allocator_with_default_settings;
@@ -21,20 +21,28 @@ use kaspa_consensus_core::{
 };
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_core::{
-    info,
+    error, info,
     task::{service::AsyncService, tick::TickService},
     time::unix_now,
     trace, warn,
 };
 use kaspa_database::prelude::ConnBuilder;
-use kaspa_database::{create_temp_db, load_existing_db};
+use kaspa_database::{create_temp_db, load_existing_db, utils::DbLifetime};
 use kaspa_hashes::Hash;
 use kaspa_perf_monitor::{builder::Builder, counters::CountersSnapshot};
 use kaspa_utils::fd_budget;
-use simulator::network::KaspaNetworkSimulator;
+use metrics::{
+    DagMetrics, MemoryPeaks, MetricsReport, PruningMetrics, SimulationSettings, StageSampler, Tolerance, ValidationMetrics,
+};
+use simulator::{
+    network::KaspaNetworkSimulator,
+    report::{SimulationReport, DEFAULT_CONFIRMATION_DEPTH},
+    scenario::{MinerConfig, Scenario},
+};
 use std::{collections::VecDeque, sync::Arc, time::Duration};
 
 mod blocks_json;
+mod metrics;
 pub mod simulator;
 
 /// Kaspa Network Simulator
@@ -133,6 +141,53 @@ struct Args {
 
     #[arg(long)]
     blocks_json_gz_output_path: Option<String>,
+
+    /// Scenario file (JSON, or YAML with a .yaml/.yml extension) describing the simulated network. Values
+    /// specified by the scenario override the corresponding arguments
+    #[arg(long)]
+    scenario: Option<String>,
+
+    /// Blue score depth at which blocks are considered confirmed in the simulation report
+    #[arg(long, default_value_t = DEFAULT_CONFIRMATION_DEPTH)]
+    confirmation_depth: u64,
+
+    /// Path to write a machine-readable JSON report of the simulation metrics to
+    #[arg(long)]
+    metrics_output: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Compares two metrics reports and exits with a non-zero code if the current report regressed
+    Compare {
+        /// Path of the baseline metrics report
+        baseline: String,
+
+        /// Path of the current metrics report
+        current: String,
+
+        /// Relative change by which a metric may get worse without being considered a regression
+        #[arg(long, default_value_t = 0.05)]
+        tolerance: f64,
+
+        /// Tolerance of specific metrics, as `<metric>=<tolerance>` where `<metric>` may also be a dot-separated
+        /// prefix of metric names (e.g. `memory=0.2`). May be repeated
+        #[arg(long = "metric-tolerance", value_parser = parse_metric_tolerance)]
+        metric_tolerances: Vec<(String, f64)>,
+
+        /// Compare reports produced with different simulation settings instead of failing
+        #[arg(long)]
+        allow_settings_mismatch: bool,
+    },
+}
+
+fn parse_metric_tolerance(s: &str) -> Result<(String, f64), String> {
+    let (metric, tolerance) = s.split_once('=').ok_or_else(|| format!("expected <metric>=<tolerance>, got {s}"))?;
+    let tolerance = tolerance.parse::<f64>().map_err(|err| format!("invalid tolerance {tolerance}: {err}"))?;
+    Ok((metric.to_string(), tolerance))
 }
 
 #[cfg(feature = "heap")]
@@ -164,16 +219,69 @@ fn main() {
     // Print package name and version
     info!("{} v{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"));
 
+    if let Some(Command::Compare { baseline, current, tolerance, metric_tolerances, allow_settings_mismatch }) = &args.command {
+        let tolerance = Tolerance::new(*tolerance, metric_tolerances.iter().cloned());
+        let regressions = compare_metrics(baseline, current, &tolerance, *allow_settings_mismatch);
+        std::process::exit(if regressions > 0 { 1 } else { 0 });
+    }
+
     main_impl(args);
 }
 
+/// Prints the comparison of two metrics reports and returns the number of regressions.
+///
+/// Reports produced with different settings are not comparable, so a settings mismatch exits with an error
+/// unless `allow_settings_mismatch` is set.
+fn compare_metrics(baseline_path: &str, current_path: &str, tolerance: &Tolerance, allow_settings_mismatch: bool) -> usize {
+    let baseline = MetricsReport::load(baseline_path).unwrap_or_else(|err| panic!("{err}"));
+    let current = MetricsReport::load(current_path).unwrap_or_else(|err| panic!("{err}"));
+    if baseline.settings != current.settings {
+        let mismatch =
+            format!("The compared reports were produced with different settings: {:?} vs. {:?}", baseline.settings, current.settings);
+        if !allow_settings_mismatch {
+            error!("{mismatch}. Pass --allow-settings-mismatch to compare them anyway");
+            std::process::exit(2);
+        }
+        warn!("{mismatch}");
+    }
+
+    let comparisons = metrics::compare(&baseline, &current, tolerance);
+    println!("{:<48} {:>16} {:>16} {:>9} {:>9}", "metric", "baseline", "current", "change", "tolerance");
+    for c in comparisons.iter() {
+        let (current, change) = match (c.current, c.change) {
+            (Some(current), Some(change)) => (format!("{current:.3}"), format!("{:.2}%", change * 100.0)),
+            _ => ("missing".to_string(), "-".to_string()),
+        };
+        println!(
+            "{:<48} {:>16.3} {:>16} {:>9} {:>8.2}%{}",
+            c.name,
+            c.baseline,
+            current,
+            change,
+            c.tolerance * 100.0,
+            if c.regression { "  REGRESSION" } else { "" }
+        );
+    }
+    let regressions = comparisons.iter().filter(|c| c.regression).count();
+    info!("Compared {} metrics, {} regressed", comparisons.len(), regressions);
+    regressions
+}
+
 fn main_impl(mut args: Args) {
     let rt = tokio::runtime::Runtime::new().unwrap();
 
-    let stop_perf_monitor = args.perf_metrics.then(|| {
+    let scenario = args.scenario.as_ref().map(|path| Scenario::load(path).unwrap_or_else(|err| panic!("{err}")));
+    if let Some(scenario) = scenario.as_ref() {
+        apply_scenario_to_args(scenario, &mut args);
+    }
+
+    let memory_peaks = Arc::new(MemoryPeaks::default());
+    let stop_perf_monitor = (args.perf_metrics || args.metrics_output.is_some()).then(|| {
         let ts = Arc::new(TickService::new());
 
+        let memory_peaks = memory_peaks.clone();
         let cb = move |counters: CountersSnapshot| {
+            memory_peaks.update(&counters);
             trace!("[{}] {}", kaspa_perf_monitor::SERVICE_NAME, counters.to_process_metrics_display());
             trace!("[{}] {}", kaspa_perf_monitor::SERVICE_NAME, counters.to_io_metrics_display());
             #[cfg(feature = "heap")]
@@ -204,7 +312,12 @@ fn main_impl(mut args: Args) {
     params.coinbase_maturity = 200;
     params.storage_mass_parameter = 10_000;
     let mut builder = ConfigBuilder::new(params)
-        .apply_args(|config| apply_args_to_consensus_params(&args, &mut config.params))
+        .apply_args(|config| apply_args_to_consensus_params(&args, scenario.as_ref(), &mut config.params))
+        .apply_args(|config| {
+            if let Some(override_params) = scenario.as_ref().and_then(|scenario| scenario.params.clone()) {
+                config.params = config.params.clone().override_params(override_params);
+            }
+        })
         .apply_args(|config| apply_args_to_perf_params(&args, &mut config.perf))
         .adjust_perf_params_to_consensus_params()
         .apply_args(|config| {
@@ -217,6 +330,17 @@ fn main_impl(mut args: Args) {
         builder = builder.set_archival();
     }
     let config = Arc::new(builder.build());
+    let mut metrics = MetricsReport::new(SimulationSettings {
+        scenario: scenario.as_ref().and_then(|scenario| scenario.name.clone()),
+        bps: args.bps,
+        delay: args.delay,
+        miners: args.miners,
+        txs_per_block: args.tpb,
+        ghostdag_k: config.ghostdag_k() as u64,
+        target_blocks: args.target_blocks,
+        sim_time: args.sim_time,
+        test_pruning: args.test_pruning,
+    });
     let default_fd = fd_budget::limit() / 2;
     let mut conn_builder = ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd);
     if let Some(rocksdb_files_limit) = args.rocksdb_files_limit {
@@ -233,7 +357,7 @@ fn main_impl(mut args: Args) {
     }
 
     // Load an existing consensus or run the simulation
-    let (consensus, _lifetime) = if let Some(input_dir) = args.input_dir {
+    let (consensus, lifetime) = if let Some(input_dir) = args.input_dir {
         let mut config = (*config).clone();
         config.process_genesis = false;
         let config = Arc::new(config);
@@ -259,10 +383,15 @@ fn main_impl(mut args: Args) {
         (consensus, lifetime)
     } else {
         let until = if args.target_blocks.is_none() { config.genesis.timestamp + args.sim_time * 1000 } else { u64::MAX }; // milliseconds
-        let mut sim = KaspaNetworkSimulator::new(args.delay, args.bps, args.target_blocks, config.clone(), args.output_dir);
+        let miners = match scenario.as_ref() {
+            Some(scenario) => scenario.miners.clone(),
+            None => vec![MinerConfig::default(); args.miners as usize],
+        };
+        let mut sim =
+            KaspaNetworkSimulator::new(args.delay, args.bps, args.target_blocks, config.clone(), args.output_dir, scenario.as_ref());
         let (consensus, handles, lifetime) = sim
             .init(
-                args.miners,
+                &miners,
                 args.tpb,
                 args.rocksdb_stats,
                 args.rocksdb_stats_period_sec,
@@ -272,8 +401,19 @@ fn main_impl(mut args: Args) {
             )
             .run(until);
         consensus.shutdown(handles);
+        let report = SimulationReport::new(
+            scenario.as_ref().and_then(|scenario| scenario.name.clone()),
+            &consensus,
+            config.genesis.hash,
+            &sim.stats().lock().unwrap(),
+            args.confirmation_depth,
+        );
+        report.log();
+        metrics.simulation = Some(report);
         (consensus, lifetime)
     };
+    metrics.db.simulation_bytes = db_size(&lifetime);
+    metrics.pruning = PruningMetrics::from_counters(&consensus.processing_counters().snapshot());
 
     if let Some(blocks_json_output_path) = args.blocks_json_gz_output_path {
         blocks_json::write_blocks_json(&config.params, &consensus, &blocks_json_output_path);
@@ -282,8 +422,9 @@ fn main_impl(mut args: Args) {
     if args.test_pruning {
         let hashes = topologically_ordered_hashes(&consensus, consensus.pruning_point(), false);
         let num_blocks = hashes.len();
-        let num_txs = print_stats(&consensus, &hashes, args.delay, args.bps, config.ghostdag_k());
-        info!("There are {num_blocks} blocks with {num_txs} transactions overall above the current pruning point");
+        let dag = dag_stats(&consensus, &hashes, args.delay, args.bps, config.ghostdag_k());
+        info!("There are {num_blocks} blocks with {} transactions overall above the current pruning point", dag.txs);
+        metrics.dag = Some(dag);
 
         if args.retention_period_days.is_some() {
             let hashes_retention = topologically_ordered_hashes(&consensus, consensus.get_retention_period_root(), false);
@@ -320,12 +461,13 @@ fn main_impl(mut args: Args) {
             }
         }
 
+        write_metrics(args.metrics_output.as_deref(), metrics, &memory_peaks);
         drop(consensus);
         return;
     }
 
     // Benchmark the DAG validation time
-    let (_lifetime2, db2) = create_temp_db!(ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd));
+    let (lifetime2, db2) = create_temp_db!(ConnBuilder::default().with_parallelism(num_cpus::get()).with_files_limit(default_fd));
     let (dummy_notification_sender, _) = unbounded();
     let notification_root = Arc::new(ConsensusNotificationRoot::new(dummy_notification_sender));
     let consensus2 = Arc::new(Consensus::new(
@@ -340,17 +482,46 @@ fn main_impl(mut args: Args) {
     ));
     let handles2 = consensus2.run_processors();
     if args.headers_first {
-        rt.block_on(validate(&consensus, &consensus2, &config, args.delay, args.bps, true));
+        let (_, validation) = rt.block_on(validate(&consensus, &consensus2, &config, args.delay, args.bps, true));
+        metrics.header_validation = Some(validation);
     }
-    rt.block_on(validate(&consensus, &consensus2, &config, args.delay, args.bps, false));
+    let (dag, validation) = rt.block_on(validate(&consensus, &consensus2, &config, args.delay, args.bps, false));
+    metrics.dag = Some(dag);
+    metrics.validation = Some(validation);
+    metrics.db.validation_bytes = db_size(&lifetime2);
     consensus2.shutdown(handles2);
     if let Some(stop_perf_monitor) = stop_perf_monitor {
         _ = rt.block_on(stop_perf_monitor);
     }
+    write_metrics(args.metrics_output.as_deref(), metrics, &memory_peaks);
     drop(consensus);
 }
 
-fn apply_args_to_consensus_params(args: &Args, params: &mut Params) {
+fn db_size(lifetime: &DbLifetime) -> Option<u64> {
+    lifetime.db_path().and_then(|path| metrics::dir_size(&path).ok())
+}
+
+fn write_metrics(path: Option<&str>, mut metrics: MetricsReport, memory_peaks: &MemoryPeaks) {
+    if let Some(path) = path {
+        metrics.memory = memory_peaks.metrics();
+        metrics.save(path).unwrap_or_else(|err| panic!("{err}"));
+        info!("Wrote the metrics report to {path}");
+    }
+}
+
+fn apply_scenario_to_args(scenario: &Scenario, args: &mut Args) {
+    args.bps = scenario.bps.unwrap_or(args.bps);
+    args.delay = scenario.delay.unwrap_or(args.delay);
+    args.sim_time = scenario.sim_time.unwrap_or(args.sim_time);
+    args.target_blocks = scenario.target_blocks.or(args.target_blocks);
+    args.daa_legacy = scenario.daa_legacy.unwrap_or(args.daa_legacy);
+    args.tpb = scenario.workload.txs_per_block.unwrap_or(args.tpb);
+    args.long_payload = scenario.workload.long_payload.unwrap_or(args.long_payload);
+    args.miners = scenario.miners.len() as u64;
+    info!("Running scenario {} with {} miners", scenario.name.as_deref().unwrap_or("<unnamed>"), args.miners);
+}
+
+fn apply_args_to_consensus_params(args: &Args, scenario: Option<&Scenario>, params: &mut Params) {
     // We have no actual PoW in the simulation, so the true max is most reflective,
     // however we avoid the actual max since it is reserved for the DB prefix scheme
     params.max_block_level = BlockLevel::MAX - 1;
@@ -363,7 +534,9 @@ fn apply_args_to_consensus_params(args: &Args, params: &mut Params) {
             params.past_median_time_window_size,
         );
     } else {
-        let max_delay = args.delay.max(NETWORK_DELAY_BOUND as f64);
+        // The slowest link of a scenario bounds the network delay
+        let delay = scenario.map_or(args.delay, |scenario| scenario.max_delay(args.delay));
+        let max_delay = delay.max(NETWORK_DELAY_BOUND as f64);
         let k = u64::max(calculate_ghostdag_k(2.0 * max_delay * args.bps, 0.05), params.ghostdag_k() as u64);
         let k = u64::min(k, KType::MAX as u64) as KType; // Clamp to KType::MAX
         params.ghostdag_k = k;
@@ -371,7 +544,7 @@ fn apply_args_to_consensus_params(args: &Args, params: &mut Params) {
         params.max_block_parents = u8::max((0.66 * k as f64) as u8, 10);
         params.target_time_per_block = (1000.0 / args.bps) as u64;
         params.merge_depth = (params.merge_depth as f64 * args.bps) as u64;
-        params.coinbase_maturity = (params.coinbase_maturity as f64 * f64::max(1.0, args.bps * args.delay * 0.25)) as u64;
+        params.coinbase_maturity = (params.coinbase_maturity as f64 * f64::max(1.0, args.bps * delay * 0.25)) as u64;
 
         if args.daa_legacy {
             // Scale DAA and median-time windows linearly with BPS
@@ -386,7 +559,7 @@ fn apply_args_to_consensus_params(args: &Args, params: &mut Params) {
             params.difficulty_sample_rate = (2.0 * args.bps) as u64;
         }
 
-        info!("2Dλ={}, GHOSTDAG K={}, DAA window size={}", 2.0 * args.delay * args.bps, k, params.difficulty_window_size);
+        info!("2Dλ={}, GHOSTDAG K={}, DAA window size={}", 2.0 * delay * args.bps, k, params.difficulty_window_size);
     }
     if args.test_pruning {
         params.pruning_proof_m = 16;
@@ -413,16 +586,25 @@ fn apply_args_to_perf_params(args: &Args, perf_params: &mut PerfParams) {
     }
 }
 
-async fn validate(src_consensus: &Consensus, dst_consensus: &Consensus, params: &Params, delay: f64, bps: f64, header_only: bool) {
+async fn validate(
+    src_consensus: &Consensus,
+    dst_consensus: &Consensus,
+    params: &Params,
+    delay: f64,
+    bps: f64,
+    header_only: bool,
+) -> (DagMetrics, ValidationMetrics) {
     let hashes = topologically_ordered_hashes(src_consensus, params.genesis.hash, false);
     let num_blocks = hashes.len();
-    let num_txs = print_stats(src_consensus, &hashes, delay, bps, params.ghostdag_k());
+    let dag = dag_stats(src_consensus, &hashes, delay, bps, params.ghostdag_k());
+    let num_txs = dag.txs as usize;
     if header_only {
         info!("Validating {num_blocks} headers...");
     } else {
         info!("Validating {num_blocks} blocks with {num_txs} transactions overall...");
     }
 
+    let sampler = StageSampler::start(dst_consensus.processing_counters().clone());
     let start = std::time::Instant::now();
     let chunks = hashes.into_iter().chunks(1000);
     let mut iter = chunks.into_iter();
@@ -451,6 +633,7 @@ async fn validate(src_consensus: &Consensus, dst_consensus: &Consensus, params:
     // Assert that at least one body tip was resolved with valid UTXO
     assert!(dst_consensus.body_tips().iter().copied().any(|h| dst_consensus.block_status(h) == BlockStatus::StatusUTXOValid));
     let elapsed = start.elapsed();
+    let stages = sampler.finish();
     info!(
         "Total validation time: {:?}, {} processing rate: {:.2} (b/s), transaction processing rate: {:.2} (t/s)",
         elapsed,
@@ -458,6 +641,14 @@ async fn validate(src_consensus: &Consensus, dst_consensus: &Consensus, params:
         num_blocks as f64 / elapsed.as_secs_f64(),
         num_txs as f64 / elapsed.as_secs_f64(),
     );
+    for stage in stages.iter() {
+        info!(
+            "Pipeline stage {}: {} processed within {:.2}s, rate: {:.2} (/s)",
+            stage.stage, stage.count, stage.elapsed_secs, stage.per_sec
+        );
+    }
+    let validation = ValidationMetrics::new(elapsed, num_blocks as u64, if header_only { 0 } else { num_txs as u64 }, stages);
+    (dag, validation)
 }
 
 fn submit_chunk(
@@ -495,7 +686,7 @@ pub(crate) fn topologically_ordered_hashes(src_consensus: &Consensus, genesis_ha
     vec
 }
 
-fn print_stats(src_consensus: &Consensus, hashes: &[Hash], delay: f64, bps: f64, k: KType) -> usize {
+fn dag_stats(src_consensus: &Consensus, hashes: &[Hash], delay: f64, bps: f64, k: KType) -> DagMetrics {
     let blues_mean = hashes.iter().map(|&h| src_consensus.ghostdag_store.get_data(h).unwrap().mergeset_blues.len()).sum::<usize>()
         as f64
         / hashes.len() as f64;
@@ -509,7 +700,7 @@ fn print_stats(src_consensus: &Consensus, hashes: &[Hash], delay: f64, bps: f64,
     let txs_mean = num_txs as f64 / hashes.len() as f64;
     info!("[DELAY={delay}, BPS={bps}, GHOSTDAG K={k}]");
     info!("[Average stats of generated DAG] blues: {blues_mean}, reds: {reds_mean}, parents: {parents_mean}, txs: {txs_mean}");
-    num_txs
+    DagMetrics { blocks: hashes.len() as u64, txs: num_txs as u64, blues_mean, reds_mean, parents_mean, txs_mean }
 }
 
 #[cfg(test)]
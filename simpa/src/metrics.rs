@@ -0,0 +1,454 @@
+//! Machine-readable simulation metrics, and their comparison for catching performance regressions.
+//!
+//! A run started with `--metrics-output <path>` writes a JSON [`MetricsReport`]. Two such reports are compared
+//! with `simpa compare <baseline> <current>`, which fails if any metric of the current report is worse than its
+//! baseline value by more than the configured relative tolerance or missing from the current report. Reports
+//! produced with different settings are only compared when `--allow-settings-mismatch` is passed.
+
+use crate::simulator::report::SimulationReport;
+use kaspa_consensus_core::api::counters::{ProcessingCounters, ProcessingCountersSnapshot};
+use kaspa_perf_monitor::counters::CountersSnapshot;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// Version of the report format, increased on breaking changes
+pub const METRICS_REPORT_VERSION: u32 = 1;
+
+/// Interval at which the validation pipeline counters are sampled
+const STAGE_SAMPLING_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Error, Debug)]
+pub enum MetricsError {
+    #[error("cannot access metrics report {0}: {1}")]
+    Io(String, std::io::Error),
+
+    #[error("cannot parse metrics report {0}: {1}")]
+    Json(String, serde_json::Error),
+
+    #[error("metrics report {0} has version {1} while version {METRICS_REPORT_VERSION} is expected")]
+    Version(String, u32),
+}
+
+pub type MetricsResult<T> = std::result::Result<T, MetricsError>;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MetricsReport {
+    pub version: u32,
+    pub simpa_version: String,
+    pub settings: SimulationSettings,
+    /// Statistics of the generated (or loaded) DAG
+    pub dag: Option<DagMetrics>,
+    /// Summary of the network simulation, missing when the DAG was loaded from an existing DB
+    pub simulation: Option<SimulationReport>,
+    /// Headers-first validation of the DAG by a fresh consensus, if requested
+    pub header_validation: Option<ValidationMetrics>,
+    /// Full validation of the DAG by a fresh consensus
+    pub validation: Option<ValidationMetrics>,
+    pub db: DbMetrics,
+    /// Missing if the process metrics were never sampled
+    pub memory: Option<MemoryMetrics>,
+    /// Missing if no pruning took place
+    pub pruning: Option<PruningMetrics>,
+}
+
+/// The settings a report was produced with. Reports produced with different settings are not comparable.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SimulationSettings {
+    pub scenario: Option<String>,
+    pub bps: f64,
+    pub delay: f64,
+    pub miners: u64,
+    pub txs_per_block: u64,
+    pub ghostdag_k: u64,
+    pub target_blocks: Option<u64>,
+    pub sim_time: u64,
+    pub test_pruning: bool,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DagMetrics {
+    pub blocks: u64,
+    pub txs: u64,
+    pub blues_mean: f64,
+    pub reds_mean: f64,
+    pub parents_mean: f64,
+    pub txs_mean: f64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ValidationMetrics {
+    pub elapsed_secs: f64,
+    pub blocks: u64,
+    pub txs: u64,
+    pub blocks_per_sec: f64,
+    pub txs_per_sec: f64,
+    pub stages: Vec<StageMetrics>,
+}
+
+impl ValidationMetrics {
+    pub fn new(elapsed: Duration, blocks: u64, txs: u64, stages: Vec<StageMetrics>) -> Self {
+        let elapsed_secs = elapsed.as_secs_f64();
+        Self {
+            elapsed_secs,
+            blocks,
+            txs,
+            blocks_per_sec: blocks as f64 / elapsed_secs,
+            txs_per_sec: txs as f64 / elapsed_secs,
+            stages,
+        }
+    }
+}
+
+/// Throughput of a single stage of the consensus processing pipeline
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct StageMetrics {
+    pub stage: String,
+    /// Number of items the stage processed
+    pub count: u64,
+    /// Time from the start of the validation until the stage processed its last item
+    pub elapsed_secs: f64,
+    pub per_sec: f64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DbMetrics {
+    pub simulation_bytes: Option<u64>,
+    pub validation_bytes: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MemoryMetrics {
+    pub peak_resident_bytes: u64,
+    pub peak_virtual_bytes: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PruningMetrics {
+    pub count: u64,
+    pub total_secs: f64,
+    pub mean_secs: f64,
+}
+
+impl PruningMetrics {
+    pub fn from_counters(snapshot: &ProcessingCountersSnapshot) -> Option<Self> {
+        (snapshot.pruning_counts > 0).then(|| {
+            let total_secs = snapshot.pruning_millis as f64 / 1000.0;
+            Self { count: snapshot.pruning_counts, total_secs, mean_secs: total_secs / snapshot.pruning_counts as f64 }
+        })
+    }
+}
+
+impl MetricsReport {
+    pub fn new(settings: SimulationSettings) -> Self {
+        Self { version: METRICS_REPORT_VERSION, simpa_version: env!("CARGO_PKG_VERSION").to_string(), settings, ..Default::default() }
+    }
+
+    pub fn load(path: &str) -> MetricsResult<Self> {
+        let content = std::fs::read_to_string(path).map_err(|err| MetricsError::Io(path.to_string(), err))?;
+        let report: Self = serde_json::from_str(&content).map_err(|err| MetricsError::Json(path.to_string(), err))?;
+        if report.version != METRICS_REPORT_VERSION {
+            return Err(MetricsError::Version(path.to_string(), report.version));
+        }
+        Ok(report)
+    }
+
+    pub fn save(&self, path: &str) -> MetricsResult<()> {
+        let content = serde_json::to_string_pretty(self).map_err(|err| MetricsError::Json(path.to_string(), err))?;
+        std::fs::write(path, content).map_err(|err| MetricsError::Io(path.to_string(), err))
+    }
+
+    /// Returns the comparable metrics of the report, named after their path in the JSON report
+    pub fn metrics(&self) -> Vec<Metric> {
+        use Direction::*;
+        let mut metrics = Vec::new();
+        let mut push = |name: String, value: f64, direction: Direction| metrics.push(Metric { name, value, direction });
+        if let Some(simulation) = self.simulation.as_ref() {
+            push("simulation.orphan_rate".into(), simulation.orphan_rate, LowerIsBetter);
+            push("simulation.red_rate".into(), simulation.red_rate, LowerIsBetter);
+            push("simulation.confirmation_times.median".into(), simulation.confirmation_times.median, LowerIsBetter);
+            push("simulation.confirmation_times.p95".into(), simulation.confirmation_times.p95, LowerIsBetter);
+        }
+        if let Some(dag) = self.dag.as_ref() {
+            push("dag.reds_mean".into(), dag.reds_mean, LowerIsBetter);
+        }
+        for (prefix, validation) in [("header_validation", &self.header_validation), ("validation", &self.validation)] {
+            let Some(validation) = validation.as_ref() else { continue };
+            push(format!("{prefix}.blocks_per_sec"), validation.blocks_per_sec, HigherIsBetter);
+            push(format!("{prefix}.txs_per_sec"), validation.txs_per_sec, HigherIsBetter);
+            for stage in validation.stages.iter() {
+                push(format!("{prefix}.stages.{}.per_sec", stage.stage), stage.per_sec, HigherIsBetter);
+            }
+        }
+        if let Some(bytes) = self.db.simulation_bytes {
+            push("db.simulation_bytes".into(), bytes as f64, LowerIsBetter);
+        }
+        if let Some(bytes) = self.db.validation_bytes {
+            push("db.validation_bytes".into(), bytes as f64, LowerIsBetter);
+        }
+        if let Some(memory) = self.memory.as_ref() {
+            push("memory.peak_resident_bytes".into(), memory.peak_resident_bytes as f64, LowerIsBetter);
+            push("memory.peak_virtual_bytes".into(), memory.peak_virtual_bytes as f64, LowerIsBetter);
+        }
+        if let Some(pruning) = self.pruning.as_ref() {
+            push("pruning.mean_secs".into(), pruning.mean_secs, LowerIsBetter);
+        }
+        metrics
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    HigherIsBetter,
+    LowerIsBetter,
+}
+
+#[derive(Clone, Debug)]
+pub struct Metric {
+    pub name: String,
+    pub value: f64,
+    pub direction: Direction,
+}
+
+/// Relative tolerances of the metric comparisons
+#[derive(Clone, Debug)]
+pub struct Tolerance {
+    default: f64,
+    overrides: HashMap<String, f64>,
+}
+
+impl Tolerance {
+    /// Creates tolerances where `overrides` maps metric names, or dot-separated prefixes of metric names, to the
+    /// tolerance of the matching metrics. The longest matching override applies.
+    pub fn new(default: f64, overrides: impl IntoIterator<Item = (String, f64)>) -> Self {
+        Self { default, overrides: overrides.into_iter().collect() }
+    }
+
+    pub fn of(&self, metric: &str) -> f64 {
+        let mut name = metric;
+        loop {
+            if let Some(&tolerance) = self.overrides.get(name) {
+                return tolerance;
+            }
+            match name.rfind('.') {
+                Some(index) => name = &name[..index],
+                None => return self.default,
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub name: String,
+    pub baseline: f64,
+    /// Value of the current report, `None` if the metric is missing from it
+    pub current: Option<f64>,
+    /// Relative change of the current value over the baseline value
+    pub change: Option<f64>,
+    pub tolerance: f64,
+    pub regression: bool,
+}
+
+/// Compares the metrics of the baseline report to the current ones. A baseline metric missing from the current
+/// report is a regression, since the run either failed to produce it or skipped the stage measuring it.
+pub fn compare(baseline: &MetricsReport, current: &MetricsReport, tolerance: &Tolerance) -> Vec<Comparison> {
+    let current_metrics = current.metrics().into_iter().map(|metric| (metric.name.clone(), metric)).collect::<HashMap<_, _>>();
+    baseline
+        .metrics()
+        .into_iter()
+        .map(|baseline| {
+            let tolerance = tolerance.of(&baseline.name);
+            let Some(metric) = current_metrics.get(&baseline.name) else {
+                return Comparison {
+                    name: baseline.name,
+                    baseline: baseline.value,
+                    current: None,
+                    change: None,
+                    tolerance,
+                    regression: true,
+                };
+            };
+            let change = if baseline.value == metric.value {
+                0.0
+            } else if baseline.value == 0.0 {
+                metric.value.signum() * f64::INFINITY
+            } else {
+                (metric.value - baseline.value) / baseline.value.abs()
+            };
+            let regression = match metric.direction {
+                Direction::HigherIsBetter => change < -tolerance,
+                Direction::LowerIsBetter => change > tolerance,
+            };
+            Comparison {
+                name: baseline.name,
+                baseline: baseline.value,
+                current: Some(metric.value),
+                change: Some(change),
+                tolerance,
+                regression,
+            }
+        })
+        .collect()
+}
+
+/// Peak process memory, tracked from the perf monitor samples
+#[derive(Default)]
+pub struct MemoryPeaks {
+    resident: AtomicU64,
+    virtual_: AtomicU64,
+}
+
+impl MemoryPeaks {
+    pub fn update(&self, counters: &CountersSnapshot) {
+        self.resident.fetch_max(counters.resident_set_size, Ordering::Relaxed);
+        self.virtual_.fetch_max(counters.virtual_memory_size, Ordering::Relaxed);
+    }
+
+    pub fn metrics(&self) -> Option<MemoryMetrics> {
+        let peak_resident_bytes = self.resident.load(Ordering::Relaxed);
+        (peak_resident_bytes > 0)
+            .then(|| MemoryMetrics { peak_resident_bytes, peak_virtual_bytes: self.virtual_.load(Ordering::Relaxed) })
+    }
+}
+
+/// Pipeline counters tracked by the stage sampler: (headers, bodies, virtual chain blocks)
+type StageCounts = [u64; 3];
+
+const STAGE_NAMES: [&str; 3] = ["header", "body", "virtual"];
+
+fn stage_counts(snapshot: &ProcessingCountersSnapshot) -> StageCounts {
+    [snapshot.header_counts, snapshot.body_counts, snapshot.chain_block_counts]
+}
+
+/// Samples the processing counters of a consensus in the background, in order to find when each pipeline stage
+/// completed its share of the processing
+pub struct StageSampler {
+    exit: Arc<AtomicBool>,
+    handle: JoinHandle<Vec<(Duration, StageCounts)>>,
+}
+
+impl StageSampler {
+    pub fn start(counters: Arc<ProcessingCounters>) -> Self {
+        let exit = Arc::new(AtomicBool::new(false));
+        let exit_clone = exit.clone();
+        let start = Instant::now();
+        let initial = stage_counts(&counters.snapshot());
+        let handle = std::thread::spawn(move || {
+            let mut samples = Vec::new();
+            loop {
+                // Sample once more after the exit signal, so that the final counts are always captured
+                let exiting = exit_clone.load(Ordering::Relaxed);
+                let counts = stage_counts(&counters.snapshot());
+                samples.push((start.elapsed(), std::array::from_fn(|i| counts[i] - initial[i])));
+                if exiting {
+                    break samples;
+                }
+                std::thread::sleep(STAGE_SAMPLING_INTERVAL);
+            }
+        });
+        Self { exit, handle }
+    }
+
+    /// Stops sampling and returns the throughput of each stage which processed any item
+    pub fn finish(self) -> Vec<StageMetrics> {
+        self.exit.store(true, Ordering::Relaxed);
+        let samples = self.handle.join().unwrap();
+        stage_metrics(&samples)
+    }
+}
+
+fn stage_metrics(samples: &[(Duration, StageCounts)]) -> Vec<StageMetrics> {
+    let Some((_, last)) = samples.last() else { return Vec::new() };
+    STAGE_NAMES
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| last[i] > 0)
+        .map(|(i, &stage)| {
+            let (elapsed, _) = samples.iter().find(|(_, counts)| counts[i] == last[i]).unwrap();
+            let elapsed_secs = elapsed.as_secs_f64();
+            StageMetrics { stage: stage.to_string(), count: last[i], elapsed_secs, per_sec: last[i] as f64 / elapsed_secs }
+        })
+        .collect()
+}
+
+/// Returns the total size of the files within `dir`
+pub fn dir_size(dir: &Path) -> std::io::Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size += if metadata.is_dir() { dir_size(&entry.path())? } else { metadata.len() };
+    }
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(blocks_per_sec: f64, db_bytes: u64) -> MetricsReport {
+        let mut report = MetricsReport::new(Default::default());
+        report.validation = Some(ValidationMetrics {
+            blocks_per_sec,
+            stages: vec![StageMetrics { stage: "header".to_string(), per_sec: blocks_per_sec, ..Default::default() }],
+            ..Default::default()
+        });
+        report.db.validation_bytes = Some(db_bytes);
+        report
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = report(100.0, 1000);
+        let tolerance = Tolerance::new(0.05, [("validation.stages".to_string(), 0.5), ("db".to_string(), 0.0)]);
+        assert_eq!(tolerance.of("validation.stages.header.per_sec"), 0.5);
+        assert_eq!(tolerance.of("validation.blocks_per_sec"), 0.05);
+
+        // Within tolerance, or improving
+        let comparisons = compare(&baseline, &report(96.0, 900), &tolerance);
+        assert_eq!(comparisons.len(), 4);
+        assert!(comparisons.iter().all(|comparison| !comparison.regression));
+
+        // Throughput drop beyond the default tolerance but within the stage override, and a DB growth
+        let comparisons = compare(&baseline, &report(60.0, 1001), &tolerance);
+        let regressions = comparisons.iter().filter(|comparison| comparison.regression).map(|c| c.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(regressions, vec!["validation.blocks_per_sec", "db.validation_bytes"]);
+
+        // A metric missing from the current report is a regression
+        let mut current = report(100.0, 1000);
+        current.db.validation_bytes = None;
+        let comparisons = compare(&baseline, &current, &tolerance);
+        let regressions = comparisons.iter().filter(|comparison| comparison.regression).collect::<Vec<_>>();
+        assert_eq!(regressions.len(), 1);
+        assert_eq!((regressions[0].name.as_str(), regressions[0].current), ("db.validation_bytes", None));
+
+        // The report survives a JSON round trip
+        let json = serde_json::to_string(&baseline).unwrap();
+        let parsed: MetricsReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.metrics().len(), baseline.metrics().len());
+    }
+
+    #[test]
+    fn test_stage_metrics() {
+        let samples = [
+            (Duration::from_secs(1), [50, 10, 0]),
+            (Duration::from_secs(2), [100, 60, 0]),
+            (Duration::from_secs(4), [100, 100, 0]),
+            (Duration::from_secs(5), [100, 100, 0]),
+        ];
+        let stages = stage_metrics(&samples);
+        assert_eq!(stages.len(), 2);
+        assert_eq!((stages[0].stage.as_str(), stages[0].elapsed_secs, stages[0].per_sec), ("header", 2.0, 50.0));
+        assert_eq!((stages[1].stage.as_str(), stages[1].elapsed_secs, stages[1].per_sec), ("body", 4.0, 25.0));
+    }
+}
@@ -13,7 +13,8 @@ use kaspa_consensus_core::tx::{
     MutableTransaction, ScriptPublicKey, ScriptVec, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry,
 };
 use kaspa_consensus_core::utxo::utxo_view::UtxoView;
-use kaspa_core::trace;
+use kaspa_core::{debug, trace};
+use kaspa_hashes::Hash;
 use kaspa_utils::sim::{Environment, Process, Resumption, Suspension};
 use rand::rngs::ThreadRng;
 use rand::Rng;
@@ -21,7 +22,11 @@ use rand_distr::{Distribution, Exp};
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use std::cmp::max;
 use std::iter::once;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+
+use super::report::SimulationStats;
+use super::scenario::MiningStrategy;
+use super::topology::Topology;
 
 struct OnetimeTxSelector {
     txs: Option<Vec<Transaction>>,
@@ -47,6 +52,19 @@ impl TemplateTransactionSelector for OnetimeTxSelector {
     }
 }
 
+/// Simulation settings shared by all miners
+#[derive(Clone)]
+pub struct SimulationContext {
+    pub topology: Arc<Topology>,
+    pub stats: Arc<Mutex<SimulationStats>>,
+    pub target_txs_per_block: u64,
+    pub target_blocks: Option<u64>,
+    pub long_payload: bool,
+    /// Whether every block is expected to be valid and eligible for the selected chain, which is the
+    /// case for a network of honest miners without partitions
+    pub strict: bool,
+}
+
 pub struct Miner {
     // ID
     pub(super) id: u64,
@@ -62,6 +80,17 @@ pub struct Miner {
     // UTXO data related to this miner
     possible_unspent_outpoints: IndexSet<TransactionOutpoint>,
 
+    // Network
+    topology: Arc<Topology>,
+    strategy: MiningStrategy,
+    withheld: Vec<Block>, // Own blocks kept private by a withholding miner
+    withheld_since: u64,  // Mining time of the oldest withheld block
+    deferred: Vec<Block>, // Blocks of other miners ignored while withholding
+    orphans: Vec<Block>,  // Blocks received before some of their parents
+    observed_sink: Hash,  // Sink of the observer, used for detecting reorgs
+    stats: Arc<Mutex<SimulationStats>>,
+    strict: bool,
+
     // Rand
     dist: Exp<f64>, // The time interval between Poisson(lambda) events distributes ~Exp(lambda)
     rng: ThreadRng,
@@ -85,13 +114,12 @@ impl Miner {
         id: u64,
         bps: f64,
         hashrate: f64,
+        strategy: MiningStrategy,
         sk: secp256k1::SecretKey,
         pk: secp256k1::PublicKey,
         consensus: Arc<Consensus>,
         params: &Params,
-        target_txs_per_block: u64,
-        target_blocks: Option<u64>,
-        long_payload: bool,
+        context: SimulationContext,
     ) -> Self {
         let (schnorr_public_key, _) = pk.x_only_public_key();
         let script_pub_key_script = once(0x20).chain(schnorr_public_key.serialize()).chain(once(0xac)).collect_vec(); // TODO: Use script builder when available to create p2pk properly
@@ -103,12 +131,21 @@ impl Miner {
             miner_data: MinerData::new(ScriptPublicKey::new(0, ScriptVec::from_slice(&script_pub_key_script_vec)), Vec::new()),
             secret_key: sk,
             possible_unspent_outpoints: IndexSet::new(),
+            topology: context.topology,
+            strategy,
+            withheld: Vec::new(),
+            withheld_since: 0,
+            deferred: Vec::new(),
+            orphans: Vec::new(),
+            observed_sink: params.genesis.hash,
+            stats: context.stats,
+            strict: context.strict,
             dist: Exp::new(bps * hashrate).unwrap(),
             rng: rand::thread_rng(),
             num_blocks: 0,
             sim_time: 0,
-            target_txs_per_block,
-            target_blocks,
+            target_txs_per_block: context.target_txs_per_block,
+            target_blocks: context.target_blocks,
             max_cached_outpoints: 10_000,
             mass_calculator: MassCalculator::new(
                 params.mass_per_tx_byte,
@@ -116,7 +153,7 @@ impl Miner {
                 params.mass_per_sig_op,
                 params.storage_mass_parameter,
             ),
-            long_payload,
+            long_payload: context.long_payload,
         }
     }
 
@@ -208,7 +245,21 @@ impl Miner {
 
     pub fn mine(&mut self, env: &mut Environment<Block>) -> Suspension {
         let block = self.build_new_block(env.now());
-        env.broadcast(self.id, block);
+        self.stats.lock().unwrap().mined_blocks += 1;
+        match self.strategy {
+            MiningStrategy::Honest => self.broadcast(env, block, true),
+            MiningStrategy::Withholding { release_after_blocks, max_withhold_time } => {
+                // The block is processed locally right away but kept private
+                env.send(0, self.id, block.clone());
+                if self.withheld.is_empty() {
+                    self.withheld_since = env.now();
+                }
+                self.withheld.push(block);
+                if self.withheld.len() as u64 >= release_after_blocks || self.withholding_expired(env, max_withhold_time) {
+                    self.release(env);
+                }
+            }
+        }
         self.sample_mining_interval()
     }
 
@@ -216,6 +267,31 @@ impl Miner {
         Suspension::Timeout(max((self.dist.sample(&mut self.rng) * 1000.0) as u64, 1))
     }
 
+    /// Sends `block` to the miners, delivery delays following the network topology
+    fn broadcast(&self, env: &mut Environment<Block>, block: Block, include_self: bool) {
+        let now = env.now();
+        let destinations = env.process_ids().copied().filter(|&id| include_self || id != self.id).collect_vec();
+        for destination in destinations {
+            env.send(self.topology.delivery_delay(now, self.id, destination), destination, block.clone());
+        }
+    }
+
+    fn withholding_expired(&self, env: &Environment<Block>, max_withhold_time: f64) -> bool {
+        !self.withheld.is_empty() && env.now() - self.withheld_since >= (max_withhold_time * 1000.0) as u64
+    }
+
+    /// Publishes the withheld blocks and catches up with the blocks of the other miners
+    fn release(&mut self, env: &mut Environment<Block>) {
+        let withheld = std::mem::take(&mut self.withheld);
+        self.stats.lock().unwrap().released_blocks += withheld.len() as u64;
+        for block in withheld {
+            self.broadcast(env, block, false);
+        }
+        for block in std::mem::take(&mut self.deferred) {
+            self.insert_block(block);
+        }
+    }
+
     fn process_block(&mut self, block: Block, env: &mut Environment<Block>) -> Suspension {
         for tx in block.transactions.iter() {
             for (i, output) in tx.outputs.iter().enumerate() {
@@ -228,14 +304,77 @@ impl Miner {
             }
         }
         if self.report_progress(env) {
-            Suspension::Halt
-        } else {
+            return Suspension::Halt;
+        }
+        if let MiningStrategy::Withholding { max_withhold_time, .. } = self.strategy {
+            if self.withholding_expired(env, max_withhold_time) {
+                self.release(env);
+            } else if !self.withheld.is_empty() && block.header.nonce != self.id {
+                // While withholding, the miner keeps building on its private blocks
+                self.deferred.push(block);
+                return Suspension::Idle;
+            }
+        }
+        self.insert_block(block);
+        Suspension::Idle
+    }
+
+    /// Inserts `block` into consensus, or keeps it aside until all its parents are inserted. Blocks can indeed
+    /// be received before their parents when links have different delays or when a partition ends.
+    fn insert_block(&mut self, block: Block) {
+        let mut pending = vec![block];
+        while let Some(block) = pending.pop() {
+            let parent_statuses =
+                block.header.direct_parents().iter().map(|&parent| self.consensus.get_block_status(parent)).collect_vec();
+            if parent_statuses.iter().any(|status| status.is_some_and(|status| status.is_invalid())) {
+                assert!(!self.strict, "block {} has an invalid parent", block.hash());
+                self.reject_block(&block, "invalid parent");
+                continue;
+            }
+            if !parent_statuses.iter().all(|status| status.is_some_and(|status| status.has_block_body())) {
+                self.orphans.push(block);
+                continue;
+            }
+
             let session = self.consensus.acquire_session();
-            let status = futures::executor::block_on(self.consensus.validate_and_insert_block(block).virtual_state_task).unwrap();
-            assert!(status.is_utxo_valid_or_pending());
+            let result = futures::executor::block_on(self.consensus.validate_and_insert_block(block.clone()).virtual_state_task);
             drop(session);
-            Suspension::Idle
+            match result {
+                Ok(status) => assert!(!self.strict || status.is_utxo_valid_or_pending()),
+                Err(err) => {
+                    assert!(!self.strict, "block {} was rejected: {}", block.hash(), err);
+                    self.reject_block(&block, &err.to_string());
+                    continue;
+                }
+            }
+            if self.id == 0 {
+                self.observe_sink();
+            }
+
+            // The inserted block might be the last missing parent of some orphans
+            pending.append(&mut self.orphans);
+        }
+    }
+
+    fn reject_block(&self, block: &Block, reason: &str) {
+        debug!("Miner {} rejected block {}: {}", self.id, block.hash(), reason);
+        if self.id == 0 {
+            self.stats.lock().unwrap().rejected_blocks += 1;
+        }
+    }
+
+    /// Records the depth of the reorg, if any, caused by the last block inserted by the observer
+    fn observe_sink(&mut self) {
+        let sink = self.consensus.get_sink();
+        if sink == self.observed_sink {
+            return;
+        }
+        if let Ok(chain_path) = self.consensus.get_virtual_chain_from_block(self.observed_sink, None) {
+            if !chain_path.removed.is_empty() {
+                self.stats.lock().unwrap().reorg_depths.push(chain_path.removed.len() as u64);
+            }
         }
+        self.observed_sink = sink;
     }
 
     fn report_progress(&mut self, env: &mut Environment<Block>) -> bool {
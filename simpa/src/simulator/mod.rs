@@ -1,2 +1,5 @@
 pub mod miner;
 pub mod network;
+pub mod report;
+pub mod scenario;
+pub mod topology;
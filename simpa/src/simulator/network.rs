@@ -2,10 +2,13 @@ use async_channel::unbounded;
 use kaspa_consensus_core::mining_rules::MiningRules;
 use kaspa_consensus_notify::root::ConsensusNotificationRoot;
 use kaspa_core::time::unix_now;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
 
-use super::miner::Miner;
+use super::miner::{Miner, SimulationContext};
+use super::report::SimulationStats;
+use super::scenario::{MinerConfig, Scenario};
+use super::topology::Topology;
 
 use kaspa_consensus::config::Config;
 use kaspa_consensus::consensus::Consensus;
@@ -25,14 +28,30 @@ pub struct KaspaNetworkSimulator {
     // Consensus instances
     consensuses: Vec<ConsensusWrapper>,
 
-    config: Arc<Config>,        // Consensus config
-    bps: f64,                   // Blocks per second
-    target_blocks: Option<u64>, // Target simulation blocks
-    output_dir: Option<String>, // Possible permanent output directory
+    config: Arc<Config>,                // Consensus config
+    bps: f64,                           // Blocks per second
+    target_blocks: Option<u64>,         // Target simulation blocks
+    output_dir: Option<String>,         // Possible permanent output directory
+    topology: Arc<Topology>,            // Message delivery model
+    strict: bool,                       // Whether all blocks are expected to be valid
+    stats: Arc<Mutex<SimulationStats>>, // Counters collected by the miners
 }
 
 impl KaspaNetworkSimulator {
-    pub fn new(delay: f64, bps: f64, target_blocks: Option<u64>, config: Arc<Config>, output_dir: Option<String>) -> Self {
+    /// Creates a simulator of a network with a uniform `delay` (seconds) between miners, or of the network
+    /// described by `scenario` if specified
+    pub fn new(
+        delay: f64,
+        bps: f64,
+        target_blocks: Option<u64>,
+        config: Arc<Config>,
+        output_dir: Option<String>,
+        scenario: Option<&Scenario>,
+    ) -> Self {
+        let topology = match scenario {
+            Some(scenario) => Topology::from_scenario(scenario, delay, config.genesis.timestamp),
+            None => Topology::uniform((delay * 1000.0) as u64),
+        };
         Self {
             simulation: Simulation::with_start_time((delay * 1000.0) as u64, config.genesis.timestamp),
             consensuses: Vec::new(),
@@ -40,12 +59,15 @@ impl KaspaNetworkSimulator {
             config,
             target_blocks,
             output_dir,
+            topology: Arc::new(topology),
+            strict: scenario.is_none(),
+            stats: Default::default(),
         }
     }
 
     pub fn init(
         &mut self,
-        num_miners: u64,
+        miners: &[MinerConfig],
         target_txs_per_block: u64,
         rocksdb_stats: bool,
         rocksdb_stats_period_sec: Option<u32>,
@@ -55,7 +77,17 @@ impl KaspaNetworkSimulator {
     ) -> &mut Self {
         let secp = secp256k1::Secp256k1::new();
         let mut rng = rand::thread_rng();
-        for i in 0..num_miners {
+        let num_miners = miners.len() as u64;
+        let total_hashrate = miners.iter().map(|miner| miner.hashrate).sum::<f64>();
+        let context = SimulationContext {
+            topology: self.topology.clone(),
+            stats: self.stats.clone(),
+            target_txs_per_block,
+            target_blocks: self.target_blocks,
+            long_payload,
+            strict: self.strict,
+        };
+        for (i, miner) in (0..num_miners).zip(miners.iter()) {
             let mut builder = ConnBuilder::default().with_files_limit(fd_budget::limit() / 2 / num_miners as i32);
             if let Some(rocksdb_files_limit) = rocksdb_files_limit {
                 builder = builder.with_files_limit(rocksdb_files_limit);
@@ -94,14 +126,13 @@ impl KaspaNetworkSimulator {
             let miner_process = Box::new(Miner::new(
                 i,
                 self.bps,
-                1f64 / num_miners as f64,
+                miner.hashrate / total_hashrate,
+                miner.strategy,
                 sk,
                 pk,
                 consensus.clone(),
                 &self.config,
-                target_txs_per_block,
-                self.target_blocks,
-                long_payload,
+                context.clone(),
             ));
             self.simulation.register(i, miner_process);
             self.consensuses.push((consensus, handles, lifetime));
@@ -109,6 +140,11 @@ impl KaspaNetworkSimulator {
         self
     }
 
+    /// Returns the counters collected by the miners
+    pub fn stats(&self) -> Arc<Mutex<SimulationStats>> {
+        self.stats.clone()
+    }
+
     pub fn run(&mut self, until: u64) -> ConsensusWrapper {
         self.simulation.run(until);
         for (consensus, handles, _) in self.consensuses.drain(1..) {
@@ -0,0 +1,188 @@
+use kaspa_consensus::{
+    consensus::Consensus,
+    model::stores::{ghostdag::GhostdagStoreReader, headers::HeaderStoreReader},
+};
+use kaspa_consensus_core::api::ConsensusApi;
+use kaspa_core::info;
+use kaspa_hashes::Hash;
+use serde::{Deserialize, Serialize};
+
+/// Blue score depth a merging chain block must reach for the blocks it merges to be considered confirmed
+pub const DEFAULT_CONFIRMATION_DEPTH: u64 = 10;
+
+/// Counters collected by the miners along the simulation.
+///
+/// Except for `mined_blocks`, the counters reflect the view of the first miner, referred to as the observer.
+#[derive(Default, Debug)]
+pub struct SimulationStats {
+    /// Number of blocks mined by all miners
+    pub mined_blocks: u64,
+    /// Number of blocks released by withholding miners
+    pub released_blocks: u64,
+    /// Number of received blocks which failed validation
+    pub rejected_blocks: u64,
+    /// Depth, in selected chain blocks, of every reorg of the observer selected chain
+    pub reorg_depths: Vec<u64>,
+}
+
+/// Summary of the values of a measurement
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct Distribution {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+impl Distribution {
+    pub fn new(mut values: Vec<f64>) -> Self {
+        if values.is_empty() {
+            return Default::default();
+        }
+        values.sort_by(f64::total_cmp);
+        let percentile = |p: f64| values[((values.len() - 1) as f64 * p).round() as usize];
+        Self {
+            count: values.len(),
+            mean: values.iter().sum::<f64>() / values.len() as f64,
+            median: percentile(0.5),
+            p95: percentile(0.95),
+            max: *values.last().unwrap(),
+        }
+    }
+}
+
+/// Summary of a simulation, computed from the final DAG of the observer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationReport {
+    pub scenario: Option<String>,
+    pub mined_blocks: u64,
+    pub released_blocks: u64,
+    pub rejected_blocks: u64,
+    /// Number of blocks merged by the observer selected chain, the sink included
+    pub merged_blocks: u64,
+    /// Fraction of the mined blocks which were not merged by the observer selected chain, be they rejected,
+    /// left out of the DAG or still in flight at the end of the simulation
+    pub orphan_rate: f64,
+    /// Fraction of the merged blocks which were colored red
+    pub red_rate: f64,
+    pub confirmation_depth: u64,
+    /// Delay (seconds) between the mining of a block and the mining of the first chain block reaching
+    /// `confirmation_depth` blue score confirmations over the chain block merging it
+    pub confirmation_times: Distribution,
+    pub reorg_count: usize,
+    pub max_reorg_depth: u64,
+    pub mean_reorg_depth: f64,
+}
+
+impl SimulationReport {
+    pub fn new(
+        scenario: Option<String>,
+        consensus: &Consensus,
+        genesis: Hash,
+        stats: &SimulationStats,
+        confirmation_depth: u64,
+    ) -> Self {
+        // Collect the selected chain, from the genesis up to the sink
+        let mut chain = vec![consensus.get_sink()];
+        while *chain.last().unwrap() != genesis {
+            let selected_parent = consensus.ghostdag_store.get_selected_parent(*chain.last().unwrap()).unwrap();
+            chain.push(selected_parent);
+        }
+        chain.reverse();
+
+        let blue_scores = chain.iter().map(|&h| consensus.ghostdag_store.get_blue_score(h).unwrap()).collect::<Vec<_>>();
+        let timestamps = chain.iter().map(|&h| consensus.headers_store.get_timestamp(h).unwrap()).collect::<Vec<_>>();
+
+        let mut blues = 0u64;
+        let mut reds = 0u64;
+        let mut confirmation_times = Vec::new();
+        let mut confirming = 0;
+        for (i, &chain_block) in chain.iter().enumerate().skip(1) {
+            let data = consensus.ghostdag_store.get_data(chain_block).unwrap();
+            blues += data.mergeset_blues.len() as u64;
+            reds += data.mergeset_reds.len() as u64;
+
+            // Blue scores increase along the chain, so the confirming chain block only moves forward
+            confirming = confirming.max(i);
+            while confirming < chain.len() && blue_scores[confirming] < blue_scores[i] + confirmation_depth {
+                confirming += 1;
+            }
+            if confirming == chain.len() {
+                continue;
+            }
+            for &merged in data.mergeset_blues.iter().chain(data.mergeset_reds.iter()).filter(|&&h| h != genesis) {
+                let timestamp = consensus.headers_store.get_timestamp(merged).unwrap();
+                confirmation_times.push(timestamps[confirming].saturating_sub(timestamp) as f64 / 1000.0);
+            }
+        }
+
+        // The chain mergesets cover the past of the sink, which contains the genesis but not the sink itself.
+        // The genesis not being mined, their total size is the number of merged mined blocks, the sink included
+        let merged_blocks = blues + reds;
+        let orphan_rate = if stats.mined_blocks > 0 {
+            stats.mined_blocks.saturating_sub(merged_blocks) as f64 / stats.mined_blocks as f64
+        } else {
+            0.0
+        };
+        let red_rate = if merged_blocks > 0 { reds as f64 / merged_blocks as f64 } else { 0.0 };
+
+        Self {
+            scenario,
+            mined_blocks: stats.mined_blocks,
+            released_blocks: stats.released_blocks,
+            rejected_blocks: stats.rejected_blocks,
+            merged_blocks,
+            orphan_rate,
+            red_rate,
+            confirmation_depth,
+            confirmation_times: Distribution::new(confirmation_times),
+            reorg_count: stats.reorg_depths.len(),
+            max_reorg_depth: stats.reorg_depths.iter().copied().max().unwrap_or_default(),
+            mean_reorg_depth: if stats.reorg_depths.is_empty() {
+                0.0
+            } else {
+                stats.reorg_depths.iter().sum::<u64>() as f64 / stats.reorg_depths.len() as f64
+            },
+        }
+    }
+
+    pub fn log(&self) {
+        if let Some(scenario) = self.scenario.as_ref() {
+            info!("[Simulation report] scenario: {scenario}");
+        }
+        info!(
+            "[Simulation report] mined blocks: {}, merged blocks: {}, rejected blocks: {}, released withheld blocks: {}",
+            self.mined_blocks, self.merged_blocks, self.rejected_blocks, self.released_blocks
+        );
+        info!("[Simulation report] orphan rate: {:.4}, red rate: {:.4}", self.orphan_rate, self.red_rate);
+        info!(
+            "[Simulation report] confirmation times at depth {} (s): mean {:.2}, median {:.2}, p95 {:.2}, max {:.2}",
+            self.confirmation_depth,
+            self.confirmation_times.mean,
+            self.confirmation_times.median,
+            self.confirmation_times.p95,
+            self.confirmation_times.max
+        );
+        info!(
+            "[Simulation report] reorgs: {}, max depth: {}, mean depth: {:.2}",
+            self.reorg_count, self.max_reorg_depth, self.mean_reorg_depth
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distribution() {
+        let distribution = Distribution::new((1..=100).rev().map(|x| x as f64).collect());
+        assert_eq!(distribution.count, 100);
+        assert_eq!(distribution.mean, 50.5);
+        assert_eq!(distribution.median, 51.0);
+        assert_eq!(distribution.p95, 95.0);
+        assert_eq!(distribution.max, 100.0);
+        assert_eq!(Distribution::new(vec![]).count, 0);
+    }
+}
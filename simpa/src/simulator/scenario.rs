@@ -0,0 +1,295 @@
+//! Declarative simulation scenarios.
+//!
+//! A scenario file (JSON or YAML, depending on the file extension) describes the simulated network:
+//! the miners and their hashrates and strategies, the delays of the links between them, timed network
+//! partitions and the transaction workload. Fields which are not specified fall back to the command line
+//! arguments. An example of a YAML scenario:
+//!
+//! ```yaml
+//! name: partition-and-withholding
+//! bps: 10
+//! delay: 0.5
+//! sim_time: 600
+//! miners:
+//!   - hashrate: 60
+//!   - hashrate: 25
+//!   - hashrate: 15
+//!     strategy: { type: withholding, release_after_blocks: 3, max_withhold_time: 5 }
+//! links:
+//!   - { from: 0, to: 1, delay: 1.5 }
+//! partitions:
+//!   - { start: 120, end: 180, groups: [[0], [1, 2]] }
+//! workload:
+//!   txs_per_block: 50
+//! ```
+
+use kaspa_consensus::params::OverrideParams;
+use serde::Deserialize;
+use std::{collections::HashSet, path::Path};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ScenarioError {
+    #[error("cannot read scenario file {0}: {1}")]
+    Io(String, std::io::Error),
+
+    #[error("cannot parse scenario file {0}: {1}")]
+    Json(String, serde_json::Error),
+
+    #[error("cannot parse scenario file {0}: {1}")]
+    Yaml(String, serde_yaml::Error),
+
+    #[error("invalid scenario: {0}")]
+    Invalid(String),
+}
+
+pub type ScenarioResult<T> = std::result::Result<T, ScenarioError>;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    /// Name reported along with the simulation results
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// Simulation blocks per second
+    #[serde(default)]
+    pub bps: Option<f64>,
+
+    /// Delay of the links which are not listed in `links` (seconds)
+    #[serde(default)]
+    pub delay: Option<f64>,
+
+    /// Target simulation time (seconds)
+    #[serde(default)]
+    pub sim_time: Option<u64>,
+
+    /// Target number of blocks the simulation should produce
+    #[serde(default)]
+    pub target_blocks: Option<u64>,
+
+    /// Use the legacy full-window DAA mechanism, i.e. simulate the network prior to Crescendo
+    #[serde(default)]
+    pub daa_legacy: Option<bool>,
+
+    /// Consensus params applied on top of the params derived from the simulation arguments
+    #[serde(default)]
+    pub params: Option<OverrideParams>,
+
+    pub miners: Vec<MinerConfig>,
+
+    #[serde(default)]
+    pub links: Vec<LinkConfig>,
+
+    #[serde(default)]
+    pub partitions: Vec<PartitionConfig>,
+
+    #[serde(default)]
+    pub workload: WorkloadConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MinerConfig {
+    /// Hashrate of the miner, relative to the hashrates of the other miners
+    #[serde(default = "default_hashrate")]
+    pub hashrate: f64,
+
+    #[serde(default)]
+    pub strategy: MiningStrategy,
+}
+
+fn default_hashrate() -> f64 {
+    1.0
+}
+
+impl Default for MinerConfig {
+    fn default() -> Self {
+        Self { hashrate: default_hashrate(), strategy: Default::default() }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum MiningStrategy {
+    /// Broadcasts mined blocks immediately and builds upon every received block
+    #[default]
+    Honest,
+
+    /// Keeps mined blocks private and ignores the blocks of the other miners while doing so, until either
+    /// `release_after_blocks` blocks are withheld or the oldest withheld block is `max_withhold_time` seconds old.
+    /// All the withheld blocks are then released at once.
+    Withholding { release_after_blocks: u64, max_withhold_time: f64 },
+}
+
+/// Delay of the link between two miners, overriding the scenario default delay
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LinkConfig {
+    pub from: u64,
+    pub to: u64,
+
+    /// Link delay (seconds)
+    pub delay: f64,
+
+    /// Whether the delay applies to both directions of the link
+    #[serde(default = "default_bidirectional")]
+    pub bidirectional: bool,
+}
+
+fn default_bidirectional() -> bool {
+    true
+}
+
+/// A time range during which miners of different groups cannot communicate. Blocks sent across
+/// groups during the partition are delivered once it ends. Miners which are not listed in any group
+/// form an additional group.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PartitionConfig {
+    /// Partition start, relative to the simulation start (seconds)
+    pub start: f64,
+
+    /// Partition end, relative to the simulation start (seconds)
+    pub end: f64,
+
+    pub groups: Vec<Vec<u64>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkloadConfig {
+    /// Target transactions per block
+    #[serde(default)]
+    pub txs_per_block: Option<u64>,
+
+    /// Whether transactions carry a large payload
+    #[serde(default)]
+    pub long_payload: Option<bool>,
+}
+
+impl Scenario {
+    /// Loads and validates a scenario file, parsed as YAML if it has a `.yaml` or `.yml` extension and as JSON otherwise
+    pub fn load(path: &str) -> ScenarioResult<Self> {
+        let content = std::fs::read_to_string(path).map_err(|err| ScenarioError::Io(path.to_string(), err))?;
+        let scenario = match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => Self::from_yaml(&content).map_err(|err| ScenarioError::Yaml(path.to_string(), err))?,
+            _ => Self::from_json(&content).map_err(|err| ScenarioError::Json(path.to_string(), err))?,
+        };
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn from_json(content: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(content)
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(content)
+    }
+
+    pub fn validate(&self) -> ScenarioResult<()> {
+        if self.miners.is_empty() {
+            return Err(ScenarioError::Invalid("at least one miner is required".to_string()));
+        }
+        let num_miners = self.miners.len() as u64;
+        for (i, miner) in self.miners.iter().enumerate() {
+            if !(miner.hashrate.is_finite() && miner.hashrate > 0.0) {
+                return Err(ScenarioError::Invalid(format!("miner {i} must have a positive hashrate")));
+            }
+            if let MiningStrategy::Withholding { release_after_blocks, max_withhold_time } = miner.strategy {
+                if release_after_blocks == 0 || !(max_withhold_time.is_finite() && max_withhold_time > 0.0) {
+                    return Err(ScenarioError::Invalid(format!(
+                        "withholding miner {i} must withhold at least one block for a positive time"
+                    )));
+                }
+            }
+        }
+        if self.delay.is_some_and(|delay| !(delay.is_finite() && delay >= 0.0)) {
+            return Err(ScenarioError::Invalid("the default delay cannot be negative".to_string()));
+        }
+        for link in self.links.iter() {
+            if link.from >= num_miners || link.to >= num_miners {
+                return Err(ScenarioError::Invalid(format!("link {} -> {} refers to an unknown miner", link.from, link.to)));
+            }
+            if !(link.delay.is_finite() && link.delay >= 0.0) {
+                return Err(ScenarioError::Invalid(format!("link {} -> {} has a negative delay", link.from, link.to)));
+            }
+        }
+        for partition in self.partitions.iter() {
+            if !(partition.start >= 0.0 && partition.start < partition.end && partition.end.is_finite()) {
+                return Err(ScenarioError::Invalid(format!(
+                    "partition [{}, {}] must start at a non negative time and end after its start",
+                    partition.start, partition.end
+                )));
+            }
+            let mut seen = HashSet::new();
+            for &id in partition.groups.iter().flatten() {
+                if id >= num_miners {
+                    return Err(ScenarioError::Invalid(format!("partition group refers to an unknown miner {id}")));
+                }
+                if !seen.insert(id) {
+                    return Err(ScenarioError::Invalid(format!("miner {id} belongs to several groups of the same partition")));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the maximum delay of the scenario links (seconds)
+    pub fn max_delay(&self, default_delay: f64) -> f64 {
+        self.links.iter().map(|link| link.delay).fold(self.delay.unwrap_or(default_delay), f64::max)
+    }
+
+    /// Returns the hashrate of each miner as a fraction of the total hashrate
+    pub fn hashrate_shares(&self) -> Vec<f64> {
+        let total = self.miners.iter().map(|miner| miner.hashrate).sum::<f64>();
+        self.miners.iter().map(|miner| miner.hashrate / total).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenario_parsing() {
+        let yaml = r#"
+name: partition
+bps: 10
+miners:
+  - hashrate: 3
+  - strategy: { type: withholding, release_after_blocks: 2, max_withhold_time: 4.5 }
+links:
+  - { from: 0, to: 1, delay: 3 }
+partitions:
+  - { start: 10, end: 20, groups: [[0], [1]] }
+workload:
+  txs_per_block: 5
+"#;
+        let scenario = Scenario::from_yaml(yaml).unwrap();
+        scenario.validate().unwrap();
+        assert_eq!(scenario.name.as_deref(), Some("partition"));
+        assert_eq!(scenario.miners[0].strategy, MiningStrategy::Honest);
+        assert_eq!(scenario.miners[1].strategy, MiningStrategy::Withholding { release_after_blocks: 2, max_withhold_time: 4.5 });
+        assert_eq!(scenario.hashrate_shares(), vec![0.75, 0.25]);
+        assert_eq!(scenario.max_delay(2.0), 3.0);
+        assert!(scenario.links[0].bidirectional);
+        assert_eq!(scenario.workload.txs_per_block, Some(5));
+
+        let json = r#"{ "miners": [{}], "params": { "max_block_mass": 1000000 } }"#;
+        let scenario = Scenario::from_json(json).unwrap();
+        assert_eq!(scenario.params.unwrap().max_block_mass, Some(1_000_000));
+
+        let invalid = [
+            r#"{ "miners": [] }"#,
+            r#"{ "miners": [{ "hashrate": 0 }] }"#,
+            r#"{ "miners": [{}], "links": [{ "from": 0, "to": 1, "delay": 1 }] }"#,
+            r#"{ "miners": [{}, {}], "partitions": [{ "start": 5, "end": 5, "groups": [[0], [1]] }] }"#,
+            r#"{ "miners": [{}, {}], "partitions": [{ "start": 5, "end": 6, "groups": [[0], [0, 1]] }] }"#,
+        ];
+        for json in invalid {
+            assert!(Scenario::from_json(json).unwrap().validate().is_err(), "{json}");
+        }
+    }
+}
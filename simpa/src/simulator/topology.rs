@@ -0,0 +1,113 @@
+use super::scenario::Scenario;
+use std::collections::HashMap;
+
+/// A time range during which the network is split into isolated groups of miners
+struct Partition {
+    start: u64, // (milliseconds)
+    end: u64,   // (milliseconds)
+    group_of: HashMap<u64, usize>,
+    default_group: usize, // The group of the miners which are not listed
+}
+
+impl Partition {
+    fn group(&self, id: u64) -> usize {
+        self.group_of.get(&id).copied().unwrap_or(self.default_group)
+    }
+
+    fn separates(&self, from: u64, to: u64) -> bool {
+        self.group(from) != self.group(to)
+    }
+}
+
+/// Delivery model of the messages exchanged by the simulated miners
+pub struct Topology {
+    default_delay: u64, // (milliseconds)
+    link_delays: HashMap<(u64, u64), u64>,
+    partitions: Vec<Partition>, // Sorted by start time
+}
+
+impl Topology {
+    /// A network where every message, including the ones a miner sends to itself, is delivered after `delay` milliseconds
+    pub fn uniform(delay: u64) -> Self {
+        Self { default_delay: delay, link_delays: HashMap::new(), partitions: Vec::new() }
+    }
+
+    /// Builds the topology of `scenario`, whose times are relative to `start_time` (milliseconds)
+    pub fn from_scenario(scenario: &Scenario, default_delay: f64, start_time: u64) -> Self {
+        let to_millis = |seconds: f64| (seconds * 1000.0) as u64;
+        let mut link_delays = HashMap::new();
+        for link in scenario.links.iter() {
+            link_delays.insert((link.from, link.to), to_millis(link.delay));
+            if link.bidirectional {
+                link_delays.insert((link.to, link.from), to_millis(link.delay));
+            }
+        }
+        let mut partitions = scenario
+            .partitions
+            .iter()
+            .map(|partition| Partition {
+                start: start_time + to_millis(partition.start),
+                end: start_time + to_millis(partition.end),
+                group_of: partition
+                    .groups
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(group, ids)| ids.iter().map(move |&id| (id, group)))
+                    .collect(),
+                default_group: partition.groups.len(),
+            })
+            .collect::<Vec<_>>();
+        partitions.sort_by_key(|partition| partition.start);
+        Self { default_delay: to_millis(scenario.delay.unwrap_or(default_delay)), link_delays, partitions }
+    }
+
+    /// Returns the delay after which a message sent at time `now` by miner `from` is delivered to miner `to`.
+    ///
+    /// A message crossing a partition is held until the partition ends, partitions being evaluated at the time
+    /// the message would otherwise leave.
+    pub fn delivery_delay(&self, now: u64, from: u64, to: u64) -> u64 {
+        let delay =
+            if from == to { self.default_delay } else { self.link_delays.get(&(from, to)).copied().unwrap_or(self.default_delay) };
+        let mut departure = now;
+        for partition in self.partitions.iter() {
+            if partition.start <= departure && departure < partition.end && partition.separates(from, to) {
+                departure = partition.end;
+            }
+        }
+        departure - now + delay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delivery_delay() {
+        let scenario = Scenario::from_json(
+            r#"{
+                "delay": 1,
+                "miners": [{}, {}, {}],
+                "links": [{ "from": 0, "to": 1, "delay": 0.2, "bidirectional": false }],
+                "partitions": [
+                    { "start": 10, "end": 20, "groups": [[0]] },
+                    { "start": 20, "end": 30, "groups": [[0, 1]] }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let start = 1_000_000;
+        let topology = Topology::from_scenario(&scenario, 2.0, start);
+
+        assert_eq!(topology.delivery_delay(start, 0, 1), 200);
+        assert_eq!(topology.delivery_delay(start, 1, 0), 1000);
+        assert_eq!(topology.delivery_delay(start, 2, 2), 1000);
+
+        // Partitioned messages leave when the partition ends, and consecutive partitions chain up
+        assert_eq!(topology.delivery_delay(start + 15_000, 1, 2), 1000);
+        assert_eq!(topology.delivery_delay(start + 15_000, 0, 1), 5_000 + 200);
+        assert_eq!(topology.delivery_delay(start + 15_000, 0, 2), 15_000 + 1000);
+        assert_eq!(topology.delivery_delay(start + 25_000, 1, 2), 5_000 + 1000);
+        assert_eq!(topology.delivery_delay(start + 30_000, 1, 2), 1000);
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    sync::Arc,
+    time::Duration,
+};
+
+use itertools::Itertools;
+use kaspa_addresses::Address;
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_core::info;
+use kaspa_grpc_client::GrpcClient;
+use kaspa_hashes::Hash;
+use kaspa_rpc_core::api::rpc::RpcApi;
+use kaspa_utils::networking::NetAddress;
+use kaspad_lib::args::Args;
+
+use crate::{
+    error::{Error, Result},
+    link::Link,
+    miner::{mine_block, Miner},
+    node::Node,
+    topology::Topology,
+};
+
+/// Interval at which the wait helpers poll the nodes
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Time given to the nodes to connect to each other when the cluster starts
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub struct ClusterBuilder {
+    num_nodes: usize,
+    topology: Topology,
+    args: Args,
+    fd_total_budget: i32,
+}
+
+impl ClusterBuilder {
+    /// A cluster of `num_nodes` simnet nodes linked as a line
+    pub fn new(num_nodes: usize) -> Self {
+        let args = Args {
+            simnet: true,
+            disable_upnp: true, // UPnP registration might take some time and is not needed for in-process nodes
+            disable_dns_seeding: true,
+            // The nodes only connect through the cluster links
+            outbound_target: 0,
+            enable_unsynced_mining: true,
+            block_template_cache_lifetime: Some(0),
+            unsafe_rpc: true,
+            ..Default::default()
+        };
+        Self { num_nodes, topology: Topology::Line, args, fd_total_budget: 10 }
+    }
+
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    pub fn utxoindex(mut self, utxoindex: bool) -> Self {
+        self.args.utxoindex = utxoindex;
+        self
+    }
+
+    /// The file descriptor budget of every node
+    pub fn fd_total_budget(mut self, fd_total_budget: i32) -> Self {
+        self.fd_total_budget = fd_total_budget;
+        self
+    }
+
+    /// Edits the arguments shared by all nodes. Ports and application directories are assigned per node.
+    pub fn apply_args<F>(mut self, edit_func: F) -> Self
+    where
+        F: Fn(&mut Args),
+    {
+        edit_func(&mut self.args);
+        self
+    }
+
+    /// Starts the nodes and waits for all the links to be connected
+    pub async fn build(self) -> Result<Cluster> {
+        let links = self.topology.links(self.num_nodes)?;
+        let mut nodes = Vec::with_capacity(self.num_nodes);
+        for index in 0..self.num_nodes {
+            nodes.push(Node::start(index, self.args.clone(), self.fd_total_budget).await?);
+        }
+        let mut cluster = Cluster { nodes, links: Vec::with_capacity(links.len()), miner: None };
+        for (from, to) in links {
+            let link = Link::start(from, to, cluster.nodes[to].p2p_address()).await?;
+            cluster.nodes[from].client().add_peer(NetAddress::from(link.address()).into(), true).await?;
+            cluster.links.push(link);
+        }
+        cluster.wait_for_links(CONNECT_TIMEOUT).await?;
+        info!("Started a cluster of {} nodes with {} links", cluster.len(), cluster.links.len());
+        Ok(cluster)
+    }
+}
+
+/// A set of in-process nodes connected through links which can be cut and restored
+pub struct Cluster {
+    // Fields are dropped in order, stopping the miner and the links before the nodes
+    miner: Option<Miner>,
+    links: Vec<Link>,
+    nodes: Vec<Node>,
+}
+
+impl Cluster {
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn node(&self, index: usize) -> Result<&Node> {
+        self.nodes.get(index).ok_or(Error::UnknownNode(index))
+    }
+
+    pub fn client(&self, index: usize) -> Result<Arc<GrpcClient>> {
+        Ok(self.node(index)?.client())
+    }
+
+    pub fn links(&self) -> &[Link] {
+        &self.links
+    }
+
+    fn link(&self, a: usize, b: usize) -> Result<&Link> {
+        self.links.iter().find(|link| link.connects(a, b)).ok_or(Error::UnknownLink(a, b))
+    }
+
+    /// Disconnects nodes `a` and `b` until the link is restored
+    pub fn cut_link(&self, a: usize, b: usize) -> Result<()> {
+        self.link(a, b)?.cut();
+        Ok(())
+    }
+
+    /// Restores the link between nodes `a` and `b` and has them reconnect
+    pub async fn restore_link(&self, a: usize, b: usize) -> Result<()> {
+        let link = self.link(a, b)?;
+        link.restore();
+        // Renewing the connection request has the node reconnect right away
+        self.nodes[link.from].client().add_peer(NetAddress::from(link.address()).into(), true).await?;
+        Ok(())
+    }
+
+    /// Splits the cluster into `groups`, cutting every link between nodes of distinct groups, and waits for the
+    /// nodes to disconnect. The nodes not listed in any group form an additional group.
+    pub async fn partition(&self, groups: &[&[usize]], timeout: Duration) -> Result<()> {
+        let mut group_of = HashMap::new();
+        for (group, nodes) in groups.iter().enumerate() {
+            for &node in nodes.iter() {
+                self.node(node)?;
+                group_of.insert(node, group);
+            }
+        }
+        let group = |node: usize| group_of.get(&node).copied().unwrap_or(groups.len());
+        for link in self.links.iter().filter(|link| group(link.from) != group(link.to)) {
+            link.cut();
+        }
+        self.wait_for_links(timeout).await
+    }
+
+    /// Restores all cut links and waits for the nodes to reconnect
+    pub async fn heal(&self, timeout: Duration) -> Result<()> {
+        for link in self.links.iter().filter(|link| !link.is_up()) {
+            self.restore_link(link.from, link.to).await?;
+        }
+        self.wait_for_links(timeout).await
+    }
+
+    /// Waits for every node to be connected to exactly the nodes it has an active link with
+    pub async fn wait_for_links(&self, timeout: Duration) -> Result<()> {
+        let expected_peers = (0..self.len())
+            .map(|node| self.links.iter().filter(|link| link.is_up() && (link.from == node || link.to == node)).count())
+            .collect_vec();
+        poll_until(timeout, "the cluster links to connect", || async {
+            for (node, &expected) in self.nodes.iter().zip(expected_peers.iter()) {
+                if node.client().get_connected_peer_info().await?.peer_info.len() != expected {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        })
+        .await
+    }
+
+    /// Waits for all the nodes of the cluster to agree on the sink and the block count
+    pub async fn wait_for_sync(&self, timeout: Duration) -> Result<()> {
+        self.wait_for_sync_of(&(0..self.len()).collect_vec(), timeout).await
+    }
+
+    /// Waits for the given nodes to agree on the sink and the block count
+    pub async fn wait_for_sync_of(&self, nodes: &[usize], timeout: Duration) -> Result<()> {
+        let clients = nodes.iter().map(|&node| self.client(node)).collect::<Result<Vec<_>>>()?;
+        poll_until(timeout, &format!("nodes {:?} to sync", nodes), || async {
+            let mut states = HashSet::new();
+            for client in clients.iter() {
+                let info = client.get_block_dag_info().await?;
+                states.insert((info.sink, info.block_count));
+            }
+            Ok(states.len() <= 1)
+        })
+        .await
+    }
+
+    /// Waits for the virtual chain of `node` to accept all the transactions of `tx_ids`
+    pub async fn wait_for_tx_acceptance(&self, node: usize, tx_ids: &[TransactionId], timeout: Duration) -> Result<()> {
+        let client = self.client(node)?;
+        let mut low = client.get_block_dag_info().await?.pruning_point_hash;
+        // Transactions accepted by each chain block, so that acceptances by blocks leaving the chain are discarded
+        let mut accepted_by_chain_block: HashMap<Hash, Vec<TransactionId>> = HashMap::new();
+        let mut pending = tx_ids.iter().copied().collect::<HashSet<_>>();
+        let description = format!("the acceptance of {} transactions by node {}", tx_ids.len(), node);
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let chain = client.get_virtual_chain_from_block(low, true, None).await?;
+            for removed in chain.removed_chain_block_hashes.iter() {
+                if let Some(accepted) = accepted_by_chain_block.remove(removed) {
+                    pending.extend(accepted.into_iter().filter(|id| tx_ids.contains(id)));
+                }
+            }
+            for accepted in chain.accepted_transaction_ids {
+                pending.retain(|id| !accepted.accepted_transaction_ids.contains(id));
+                accepted_by_chain_block.insert(accepted.accepting_block_hash, accepted.accepted_transaction_ids);
+            }
+            if let Some(&last) = chain.added_chain_block_hashes.last() {
+                low = last;
+            }
+            if pending.is_empty() {
+                return Ok(());
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(Error::Timeout(timeout, description));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Mines `count` blocks on `node` and returns their hashes
+    pub async fn mine_blocks(&self, node: usize, count: usize, pay_address: &Address) -> Result<Vec<Hash>> {
+        let client = self.client(node)?;
+        let mut hashes = Vec::with_capacity(count);
+        for _ in 0..count {
+            hashes.push(mine_block(&client, pay_address).await?);
+        }
+        Ok(hashes)
+    }
+
+    /// Starts mining `bps` blocks per second, spread over `nodes`, in the background.
+    /// Replaces the current background miner if any.
+    pub fn start_miner(&mut self, nodes: &[usize], bps: f64, pay_address: Address) -> Result<()> {
+        let clients = nodes.iter().map(|&node| self.client(node)).collect::<Result<Vec<_>>>()?;
+        self.miner = Some(Miner::start(clients, bps, pay_address));
+        Ok(())
+    }
+
+    /// Stops the background miner and returns the number of blocks it mined
+    pub async fn stop_miner(&mut self) -> u64 {
+        match self.miner.take() {
+            Some(miner) => miner.stop().await,
+            None => 0,
+        }
+    }
+
+    /// Stops the miner and the nodes
+    pub async fn shutdown(mut self) {
+        self.stop_miner().await;
+        self.links.clear();
+        for node in self.nodes.iter_mut() {
+            node.shutdown();
+        }
+    }
+}
+
+/// Polls `condition` until it holds, failing after `timeout`
+async fn poll_until<F, Fut>(timeout: Duration, description: &str, mut condition: F) -> Result<()>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<bool>>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if condition().await? {
+            return Ok(());
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return Err(Error::Timeout(timeout, description.to_string()));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use kaspa_hashes::Hash;
+use kaspa_rpc_core::RpcError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Rpc(#[from] RpcError),
+
+    #[error(transparent)]
+    Grpc(#[from] kaspa_grpc_client::error::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid topology: {0}")]
+    InvalidTopology(String),
+
+    #[error("node {0} is not part of the cluster")]
+    UnknownNode(usize),
+
+    #[error("nodes {0} and {1} are not linked")]
+    UnknownLink(usize, usize),
+
+    #[error("block {0} was rejected: {1}")]
+    BlockRejected(Hash, String),
+
+    #[error("timed out after {0:?} waiting for {1}")]
+    Timeout(Duration, String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,23 @@
+//!
+//! # Kaspa Testing
+//!
+//! In-process multi-node simnet clusters for integration tests and benchmarks.
+//!
+//! A [`Cluster`] runs N kaspad instances within the current process, connected according to a [`Topology`].
+//! Every link between two nodes goes through a local TCP relay which can be cut and restored, allowing to
+//! partition the network and heal it. An embedded CPU miner, along with helpers waiting for the nodes to
+//! sync or for transactions to get accepted, complete the toolbox.
+//!
+
+pub mod cluster;
+pub mod error;
+pub mod link;
+pub mod miner;
+pub mod node;
+pub mod topology;
+
+pub use cluster::{Cluster, ClusterBuilder};
+pub use error::{Error, Result};
+pub use kaspad_lib::args::Args;
+pub use node::Node;
+pub use topology::Topology;
@@ -0,0 +1,132 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
+};
+
+use parking_lot::Mutex;
+use tokio::{
+    io::copy_bidirectional,
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+use crate::error::Result;
+
+#[derive(Default)]
+struct LinkState {
+    up: bool,
+    relays: Vec<JoinHandle<()>>,
+}
+
+/// A P2P link between two nodes of a cluster.
+///
+/// Node `from` dials node `to` through a local TCP relay. Cutting the link drops the relayed connections and refuses
+/// new ones until the link is restored, which both nodes observe as a disconnection.
+pub struct Link {
+    pub from: usize,
+    pub to: usize,
+    address: SocketAddr,
+    state: Arc<Mutex<LinkState>>,
+    listener: JoinHandle<()>,
+}
+
+impl Link {
+    /// Starts relaying the connections received on a random local port to `target`
+    pub async fn start(from: usize, to: usize, target: SocketAddr) -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let address = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(LinkState { up: true, relays: vec![] }));
+        let listener_state = state.clone();
+        let listener = tokio::spawn(async move {
+            while let Ok((mut inbound, _)) = listener.accept().await {
+                let mut state = listener_state.lock();
+                if !state.up {
+                    // Dropping the accepted stream refuses the connection
+                    continue;
+                }
+                state.relays.retain(|relay| !relay.is_finished());
+                state.relays.push(tokio::spawn(async move {
+                    if let Ok(mut outbound) = TcpStream::connect(target).await {
+                        let _ = copy_bidirectional(&mut inbound, &mut outbound).await;
+                    }
+                }));
+            }
+        });
+        Ok(Self { from, to, address, state, listener })
+    }
+
+    /// The address node `from` dials to reach node `to`
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.state.lock().up
+    }
+
+    pub fn connects(&self, a: usize, b: usize) -> bool {
+        (self.from, self.to) == (a, b) || (self.from, self.to) == (b, a)
+    }
+
+    /// Drops the relayed connections and refuses new ones
+    pub fn cut(&self) {
+        let mut state = self.state.lock();
+        state.up = false;
+        state.relays.drain(..).for_each(|relay| relay.abort());
+    }
+
+    /// Accepts connections again. The dialing node is expected to be asked to reconnect.
+    pub fn restore(&self) {
+        self.state.lock().up = true;
+    }
+}
+
+impl Drop for Link {
+    fn drop(&mut self) {
+        self.listener.abort();
+        self.cut();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn test_link_cut_and_restore() {
+        // An echo server standing for the target node
+        let server = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let target = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = server.accept().await {
+                tokio::spawn(async move {
+                    let (mut reader, mut writer) = stream.split();
+                    let _ = tokio::io::copy(&mut reader, &mut writer).await;
+                });
+            }
+        });
+
+        async fn echo(stream: &mut TcpStream) -> Option<u8> {
+            stream.write_all(&[7]).await.ok()?;
+            let mut buf = [0u8; 1];
+            (stream.read(&mut buf).await.ok()? == 1).then_some(buf[0])
+        }
+
+        let link = Link::start(0, 1, target).await.unwrap();
+        let mut stream = TcpStream::connect(link.address()).await.unwrap();
+        assert_eq!(echo(&mut stream).await, Some(7));
+
+        // Cutting the link closes the relayed connection and refuses new ones
+        link.cut();
+        assert!(!link.is_up());
+        assert_eq!(echo(&mut stream).await, None);
+        let mut stream = TcpStream::connect(link.address()).await.unwrap();
+        assert_eq!(echo(&mut stream).await, None);
+
+        link.restore();
+        let mut stream = TcpStream::connect(link.address()).await.unwrap();
+        assert_eq!(echo(&mut stream).await, Some(7));
+        assert!(link.connects(1, 0));
+    }
+}
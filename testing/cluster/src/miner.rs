@@ -0,0 +1,101 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use kaspa_addresses::Address;
+use kaspa_consensus_core::header::Header;
+use kaspa_core::warn;
+use kaspa_grpc_client::GrpcClient;
+use kaspa_hashes::Hash;
+use kaspa_rpc_core::{api::rpc::RpcApi, SubmitBlockReport};
+use kaspa_utils::triggers::SingleTrigger;
+use rand::{seq::SliceRandom, thread_rng, Rng};
+use rand_distr::{Distribution, Exp};
+use tokio::task::JoinHandle;
+
+use crate::error::{Error, Result};
+
+/// Mines a block on top of the template of the node behind `client`, solving its proof of work on the CPU
+pub async fn mine_block(client: &GrpcClient, pay_address: &Address) -> Result<Hash> {
+    let mut block = client.get_block_template(pay_address.clone(), vec![]).await?.block;
+    let header: Header = (&block.header).try_into()?;
+    let (nonce, hash) = tokio::task::spawn_blocking(move || {
+        let state = kaspa_pow::State::new(&header);
+        // A random starting nonce keeps concurrent miners from producing the same block
+        let mut nonce = thread_rng().gen::<u64>();
+        while !state.check_pow(nonce).0 {
+            nonce = nonce.wrapping_add(1);
+        }
+        let mut header = header;
+        header.nonce = nonce;
+        header.finalize();
+        (nonce, header.hash)
+    })
+    .await
+    .unwrap();
+    block.header.nonce = nonce;
+    match client.submit_block(block, false).await?.report {
+        SubmitBlockReport::Success => Ok(hash),
+        SubmitBlockReport::Reject(reason) => Err(Error::BlockRejected(hash, format!("{:?}", reason))),
+    }
+}
+
+/// A background miner submitting blocks at a target rate to randomly picked nodes
+pub struct Miner {
+    shutdown: SingleTrigger,
+    mined_blocks: Arc<AtomicU64>,
+    task: Option<JoinHandle<()>>,
+}
+
+impl Miner {
+    /// Starts mining `bps` blocks per second on average, the delays between blocks following an exponential distribution
+    pub fn start(clients: Vec<Arc<GrpcClient>>, bps: f64, pay_address: Address) -> Self {
+        assert!(!clients.is_empty() && bps > 0.0);
+        let shutdown = SingleTrigger::new();
+        let mined_blocks = Arc::new(AtomicU64::new(0));
+        let distribution = Exp::new(bps).unwrap();
+        let listener = shutdown.listener.clone();
+        let counter = mined_blocks.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let delay = Duration::from_secs_f64(distribution.sample(&mut thread_rng()));
+                tokio::select! {
+                    biased;
+                    _ = listener.clone() => break,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+                let client = clients.choose(&mut thread_rng()).unwrap();
+                match mine_block(client, &pay_address).await {
+                    Ok(_) => {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                    }
+                    Err(err) => warn!("Cluster miner failed to mine a block: {}", err),
+                }
+            }
+        });
+        Self { shutdown, mined_blocks, task: Some(task) }
+    }
+
+    pub fn mined_blocks(&self) -> u64 {
+        self.mined_blocks.load(Ordering::Relaxed)
+    }
+
+    /// Stops mining, waiting for an ongoing block submission to complete, and returns the number of mined blocks
+    pub async fn stop(mut self) -> u64 {
+        self.shutdown.trigger.trigger();
+        if let Some(task) = self.task.take() {
+            let _ = task.await;
+        }
+        self.mined_blocks()
+    }
+}
+
+impl Drop for Miner {
+    fn drop(&mut self) {
+        self.shutdown.trigger.trigger();
+    }
+}
@@ -0,0 +1,139 @@
+use std::{
+    net::{Ipv4Addr, SocketAddr, TcpListener},
+    sync::Arc,
+    time::Duration,
+};
+
+use kaspa_consensus_core::network::NetworkId;
+use kaspa_core::{core::Core, task::runtime::AsyncRuntime};
+use kaspa_database::utils::get_kaspa_tempdir;
+use kaspa_grpc_client::GrpcClient;
+use kaspa_grpc_server::service::GrpcService;
+use kaspa_notify::subscription::context::SubscriptionContext;
+use kaspa_rpc_core::notify::mode::NotificationMode;
+use kaspa_rpc_service::service::RpcCoreService;
+use kaspad_lib::{args::Args, daemon::create_core_with_runtime};
+use tempfile::TempDir;
+
+use crate::error::{Error, Result};
+
+/// Maximum time a node is given to start its gRPC server
+const START_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A kaspad instance running within the current process
+pub struct Node {
+    pub index: usize,
+    pub network: NetworkId,
+    pub rpc_port: u16,
+    pub p2p_port: u16,
+    core: Arc<Core>,
+    rpc_core_service: Arc<RpcCoreService>,
+    client: Arc<GrpcClient>,
+    subscription_context: SubscriptionContext,
+    workers: Option<Vec<std::thread::JoinHandle<()>>>,
+    _appdir_tempdir: TempDir,
+}
+
+impl Node {
+    /// Starts a node with `args` on random local ports and a temporary application directory,
+    /// and connects its RPC client once the gRPC server is up
+    pub async fn start(index: usize, mut args: Args, fd_total_budget: i32) -> Result<Self> {
+        let [rpc_port, p2p_port, rpc_json_port, rpc_borsh_port] = free_ports()?;
+        args.rpclisten = Some(format!("0.0.0.0:{rpc_port}").try_into().unwrap());
+        args.listen = Some(format!("0.0.0.0:{p2p_port}").try_into().unwrap());
+        args.rpclisten_json = Some(format!("0.0.0.0:{rpc_json_port}").parse().unwrap());
+        args.rpclisten_borsh = Some(format!("0.0.0.0:{rpc_borsh_port}").parse().unwrap());
+        let appdir_tempdir = get_kaspa_tempdir();
+        args.appdir = Some(appdir_tempdir.path().to_str().unwrap().to_owned());
+
+        let (core, rpc_core_service) = create_core_with_runtime(&Default::default(), &args, fd_total_budget);
+        let async_service = &Arc::downcast::<AsyncRuntime>(core.find(AsyncRuntime::IDENT).unwrap().arc_any()).unwrap();
+        let grpc_server = &Arc::downcast::<GrpcService>(async_service.find(GrpcService::IDENT).unwrap().arc_any()).unwrap();
+        let grpc_server_started = grpc_server.started();
+        let workers = core.start();
+
+        let subscription_context = SubscriptionContext::with_prefix(args.network().into(), None);
+        let client = match tokio::time::timeout(START_TIMEOUT, grpc_server_started).await {
+            Ok(_) => connect_client(rpc_port, &subscription_context).await,
+            Err(_) => Err(Error::Timeout(START_TIMEOUT, format!("the gRPC server of node {index} to start"))),
+        };
+        let client = match client {
+            Ok(client) => Arc::new(client),
+            Err(err) => {
+                core.shutdown();
+                core.join(workers);
+                return Err(err);
+            }
+        };
+
+        Ok(Self {
+            index,
+            network: args.network(),
+            rpc_port,
+            p2p_port,
+            core,
+            rpc_core_service,
+            client,
+            subscription_context,
+            workers: Some(workers),
+            _appdir_tempdir: appdir_tempdir,
+        })
+    }
+
+    /// The RPC client of the node, shared by the cluster helpers
+    pub fn client(&self) -> Arc<GrpcClient> {
+        self.client.clone()
+    }
+
+    /// Connects an additional RPC client, e.g. for receiving notifications
+    pub async fn new_client(&self) -> Result<GrpcClient> {
+        connect_client(self.rpc_port, &self.subscription_context).await
+    }
+
+    /// The in-process RPC service of the node, giving direct access to its consensus, mempool and P2P components
+    pub fn rpc_core_service(&self) -> Arc<RpcCoreService> {
+        self.rpc_core_service.clone()
+    }
+
+    pub fn p2p_address(&self) -> SocketAddr {
+        (Ipv4Addr::LOCALHOST, self.p2p_port).into()
+    }
+
+    pub fn shutdown(&mut self) {
+        if let Some(workers) = self.workers.take() {
+            self.core.shutdown();
+            self.core.join(workers);
+        }
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        self.shutdown()
+    }
+}
+
+async fn connect_client(rpc_port: u16, subscription_context: &SubscriptionContext) -> Result<GrpcClient> {
+    Ok(GrpcClient::connect_with_args(
+        NotificationMode::Direct,
+        format!("grpc://localhost:{rpc_port}"),
+        Some(subscription_context.clone()),
+        false,
+        None,
+        false,
+        Some(500_000),
+        Default::default(),
+    )
+    .await?)
+}
+
+/// Asks the OS for `N` distinct free ports
+fn free_ports<const N: usize>() -> Result<[u16; N]> {
+    // The sockets are all kept open until the ports are collected so the OS allocates distinct ports
+    let sockets = (0..N).map(|_| TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0))).collect::<std::io::Result<Vec<_>>>()?;
+    let mut ports = [0; N];
+    for (port, socket) in ports.iter_mut().zip(sockets.iter()) {
+        *port = socket.local_addr()?.port();
+    }
+    Ok(ports)
+}
@@ -0,0 +1,69 @@
+use std::collections::HashSet;
+
+use crate::error::{Error, Result};
+
+/// The shape of the links between the nodes of a cluster
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Topology {
+    /// Node `i` is linked to node `i + 1`
+    Line,
+    /// A line whose last node is linked back to the first one
+    Ring,
+    /// All nodes are linked to node 0
+    Star,
+    /// Every pair of nodes is linked
+    Mesh,
+    /// An explicit list of `(from, to)` links, `from` being the node dialing `to`
+    Custom(Vec<(usize, usize)>),
+}
+
+impl Topology {
+    /// Returns the `(from, to)` links of this topology over `num_nodes` nodes
+    pub fn links(&self, num_nodes: usize) -> Result<Vec<(usize, usize)>> {
+        let links = match self {
+            Topology::Line => (1..num_nodes).map(|i| (i, i - 1)).collect(),
+            Topology::Ring if num_nodes < 3 => return Topology::Line.links(num_nodes),
+            Topology::Ring => (0..num_nodes).map(|i| ((i + 1) % num_nodes, i)).collect(),
+            Topology::Star => (1..num_nodes).map(|i| (i, 0)).collect(),
+            Topology::Mesh => (0..num_nodes).flat_map(|i| (0..i).map(move |j| (i, j))).collect(),
+            Topology::Custom(links) => {
+                let mut pairs = HashSet::with_capacity(links.len());
+                for &(from, to) in links.iter() {
+                    if from >= num_nodes || to >= num_nodes {
+                        return Err(Error::InvalidTopology(format!(
+                            "link ({from}, {to}) exceeds the {num_nodes} nodes of the cluster"
+                        )));
+                    }
+                    if from == to {
+                        return Err(Error::InvalidTopology(format!("node {from} is linked to itself")));
+                    }
+                    if !pairs.insert((from.min(to), from.max(to))) {
+                        return Err(Error::InvalidTopology(format!("nodes {from} and {to} are linked more than once")));
+                    }
+                }
+                links.clone()
+            }
+        };
+        Ok(links)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topology_links() {
+        assert_eq!(Topology::Line.links(3).unwrap(), vec![(1, 0), (2, 1)]);
+        assert_eq!(Topology::Ring.links(3).unwrap(), vec![(1, 0), (2, 1), (0, 2)]);
+        assert_eq!(Topology::Ring.links(2).unwrap(), vec![(1, 0)]);
+        assert_eq!(Topology::Star.links(4).unwrap(), vec![(1, 0), (2, 0), (3, 0)]);
+        assert_eq!(Topology::Mesh.links(4).unwrap().len(), 6);
+        assert!(Topology::Mesh.links(1).unwrap().is_empty());
+
+        assert_eq!(Topology::Custom(vec![(0, 2)]).links(3).unwrap(), vec![(0, 2)]);
+        assert!(Topology::Custom(vec![(0, 3)]).links(3).is_err());
+        assert!(Topology::Custom(vec![(1, 1)]).links(3).is_err());
+        assert!(Topology::Custom(vec![(0, 1), (1, 0)]).links(3).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use kaspa_addresses::{Address, Version};
+use kaspa_alloc::init_allocator_with_default_settings;
+use kaspa_rpc_core::{api::rpc::RpcApi, GetBlockDagInfoResponse};
+use kaspa_testing::{Cluster, ClusterBuilder, Topology};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+async fn dag_info(cluster: &Cluster, node: usize) -> GetBlockDagInfoResponse {
+    cluster.client(node).unwrap().get_block_dag_info().await.unwrap()
+}
+
+fn pay_address(cluster: &Cluster) -> Address {
+    Address::new(cluster.node(0).unwrap().network.into(), Version::PubKey, &[0; 32])
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn cluster_partition_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let cluster = ClusterBuilder::new(3).topology(Topology::Line).build().await.unwrap();
+    let pay_address = pay_address(&cluster);
+
+    // Blocks mined at one end of the line reach the other end
+    let hashes = cluster.mine_blocks(0, 5, &pay_address).await.unwrap();
+    cluster.wait_for_sync(TIMEOUT).await.unwrap();
+    assert_eq!(dag_info(&cluster, 2).await.sink, *hashes.last().unwrap());
+
+    // The coinbase transaction of a chain block is accepted by the next chain block
+    let block = cluster.client(2).unwrap().get_block(hashes[3], true).await.unwrap();
+    let coinbase_id = block.transactions[0].verbose_data.as_ref().unwrap().transaction_id;
+    cluster.wait_for_tx_acceptance(2, &[coinbase_id], TIMEOUT).await.unwrap();
+
+    // Both sides of a partition grow their own chain
+    cluster.partition(&[&[0], &[1, 2]], TIMEOUT).await.unwrap();
+    let side_a = cluster.mine_blocks(0, 3, &pay_address).await.unwrap();
+    let side_b = cluster.mine_blocks(2, 2, &pay_address).await.unwrap();
+    cluster.wait_for_sync_of(&[1, 2], TIMEOUT).await.unwrap();
+    assert_eq!(dag_info(&cluster, 0).await.sink, *side_a.last().unwrap());
+    assert_eq!(dag_info(&cluster, 1).await.sink, *side_b.last().unwrap());
+
+    // Once healed, new blocks make every node learn about the blocks of the other side
+    cluster.heal(TIMEOUT).await.unwrap();
+    cluster.mine_blocks(0, 1, &pay_address).await.unwrap();
+    cluster.wait_for_sync_of(&[1, 2], TIMEOUT).await.unwrap();
+    let merging_block = cluster.mine_blocks(2, 1, &pay_address).await.unwrap();
+    cluster.wait_for_sync(TIMEOUT).await.unwrap();
+    let info = dag_info(&cluster, 0).await;
+    assert_eq!(info.block_count, 12);
+    assert_eq!(info.sink, merging_block[0]);
+
+    cluster.shutdown().await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn cluster_miner_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let mut cluster = ClusterBuilder::new(4).topology(Topology::Ring).build().await.unwrap();
+    let pay_address = pay_address(&cluster);
+
+    cluster.start_miner(&[0, 2], 4.0, pay_address).unwrap();
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let mined_blocks = cluster.stop_miner().await;
+    assert!(mined_blocks > 0);
+
+    cluster.wait_for_sync(TIMEOUT).await.unwrap();
+    for node in 0..cluster.len() {
+        assert_eq!(dag_info(&cluster, node).await.block_count, mined_blocks);
+    }
+
+    cluster.shutdown().await;
+}
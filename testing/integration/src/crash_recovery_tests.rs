@@ -0,0 +1,340 @@
+//!
+//! Crash-recovery tests, restarting consensus from the DB state left by a fault injected into its writes
+//!
+
+use std::{
+    collections::VecDeque,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::PathBuf,
+    sync::Arc,
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use kaspa_alloc::init_allocator_with_default_settings;
+use kaspa_consensus::{
+    config::{Config, ConfigBuilder},
+    consensus::test_consensus::TestConsensus,
+    params::MAINNET_PARAMS,
+};
+use kaspa_consensus_core::{api::ConsensusApi, blockstatus::BlockStatus, errors::consensus::ConsensusError};
+use kaspa_core::info;
+use kaspa_database::{
+    fault::{FaultAction, FaultInjector, FaultTrigger, InjectedFault},
+    load_existing_db,
+    prelude::{ConnBuilder, DB},
+    registry::DatabaseStorePrefixes,
+    utils::get_kaspa_tempdir,
+};
+use kaspa_hashes::Hash;
+use rocksdb::checkpoint::Checkpoint;
+use tempfile::TempDir;
+
+/// Maximum time given to consensus to process a single block
+const BLOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum time given to the asynchronous processors to reach the fault once all blocks were submitted
+const FAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum time given to the restarted pruning processor to recover the pruning point UTXO set
+const PRUNING_RECOVERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The number of blocks added on top of the recovered sink to check that consensus makes progress
+const RECOVERY_BLOCKS: u64 = 20;
+
+/// How a restarted consensus handled the DB state left by a fault
+#[derive(Debug, PartialEq, Eq)]
+enum Recovery {
+    /// Consensus restarted and kept processing blocks on top of its sink
+    Recovered,
+    /// Consensus refused to open over the interrupted state with an inconsistent storage error
+    Refused,
+}
+
+/// Runs consensus over a fresh DB until a fault is injected, then restarts it from the resulting DB state
+struct CrashHarness {
+    config: Config,
+    tempdir: TempDir,
+}
+
+impl CrashHarness {
+    fn new(config: Config) -> Self {
+        Self { config, tempdir: get_kaspa_tempdir() }
+    }
+
+    fn db_dir(&self) -> PathBuf {
+        self.tempdir.path().join("consensus")
+    }
+
+    fn checkpoint_dir(&self) -> PathBuf {
+        self.tempdir.path().join("checkpoint")
+    }
+
+    /// Feeds a chain of `num_blocks` blocks to consensus until `injector` injects its fault, and saves a
+    /// checkpoint of the DB state at that point. The faulty consensus is abandoned, as a crashed process would be.
+    async fn run_until_fault(&self, injector: Arc<FaultInjector>, num_blocks: u64) -> InjectedFault {
+        let (db, consensus, wait_handles) = self.start(&injector);
+        self.process_chain(&consensus, &injector, num_blocks).await;
+        self.crash(db, consensus, wait_handles, &injector).await
+    }
+
+    /// Feeds a chain of `num_blocks` blocks to consensus and then performs the intrusive pruning point update of an
+    /// IBD pruning catch-up onto a header-only block on top of the sink, which `injector` is expected to fault.
+    async fn run_until_intrusive_update(&self, injector: Arc<FaultInjector>, num_blocks: u64) -> InjectedFault {
+        let (db, consensus, wait_handles) = self.start(&injector);
+        let sink = self.process_chain(&consensus, &injector, num_blocks).await;
+        assert!(injector.injected().is_none(), "the fault was injected before the intrusive pruning point update");
+
+        let new_pruning_point: Hash = (u64::MAX / 4).into();
+        let status = consensus.add_header_only_block_with_parents(new_pruning_point, vec![sink]).await.unwrap();
+        assert_eq!(status, BlockStatus::StatusHeaderOnly);
+        consensus
+            .consensus_clone()
+            .intrusive_pruning_point_store_writes(new_pruning_point, new_pruning_point, VecDeque::from([new_pruning_point]))
+            .unwrap();
+        self.crash(db, consensus, wait_handles, &injector).await
+    }
+
+    /// Opens consensus over a fresh DB and installs `injector` into its writes
+    fn start(&self, injector: &Arc<FaultInjector>) -> (Arc<DB>, Arc<TestConsensus>, Vec<JoinHandle<()>>) {
+        let db = ConnBuilder::default().with_db_path(self.db_dir()).with_files_limit(10).build().unwrap();
+        let (notification_sender, _) = async_channel::unbounded();
+        let consensus = Arc::new(TestConsensus::with_db(db.clone(), &self.config, notification_sender));
+        db.set_fault_injector(Some(injector.clone()));
+        let wait_handles = consensus.init();
+        (db, consensus, wait_handles)
+    }
+
+    /// Feeds a chain of up to `num_blocks` blocks to consensus, stopping early once `injector` injects its fault,
+    /// and returns the last block processed without a fault
+    async fn process_chain(&self, consensus: &Arc<TestConsensus>, injector: &FaultInjector, num_blocks: u64) -> Hash {
+        let mut parent = self.config.genesis.hash;
+        for i in 1..=num_blocks {
+            let hash: Hash = i.into();
+            let processed = process_block(consensus, hash, parent).await;
+            if injector.injected().is_some() {
+                break;
+            }
+            if let Err(reason) = processed {
+                panic!("block {hash} failed before any fault was injected: {reason}");
+            }
+            parent = hash;
+        }
+        parent
+    }
+
+    /// Waits for `injector` to inject its fault, saves a checkpoint of the DB state at that point and abandons the
+    /// faulty consensus, as a crashed process would be
+    async fn crash(
+        &self,
+        db: Arc<DB>,
+        consensus: Arc<TestConsensus>,
+        wait_handles: Vec<JoinHandle<()>>,
+        injector: &FaultInjector,
+    ) -> InjectedFault {
+        // Faults in the pruning processor might be reached only after the last block was processed
+        let start = std::time::Instant::now();
+        while injector.injected().is_none() {
+            assert!(start.elapsed() < FAULT_TIMEOUT, "the fault was never injected after {} writes", injector.writes());
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        let fault = injector.injected().unwrap();
+
+        // The storage is frozen from now on, so the checkpoint holds the state a crash would have left on disk
+        Checkpoint::new(&**db).unwrap().create_checkpoint(self.checkpoint_dir()).unwrap();
+        drop(db);
+        abandon(consensus, wait_handles);
+        fault
+    }
+
+    /// Restarts consensus from the checkpoint saved by [`Self::run_until_fault`] and checks that it either refuses
+    /// to open or recovers into a state it can keep extending. Any panic along the way fails the test.
+    async fn restart(&self) -> Recovery {
+        let (db_lifetime, db) = load_existing_db!(self.checkpoint_dir(), ConnBuilder::default().with_files_limit(10));
+        let (notification_sender, _) = async_channel::unbounded();
+        let consensus = match TestConsensus::try_with_db(db, &self.config, notification_sender) {
+            Ok(consensus) => Arc::new(consensus),
+            Err(ConsensusError::InconsistentStorage(reason)) => {
+                info!("Consensus refused to open: {reason}");
+                return Recovery::Refused;
+            }
+            Err(err) => panic!("consensus failed to open with an unexpected error: {err}"),
+        };
+        let wait_handles = consensus.init();
+
+        // A consensus interrupted during an IBD pruning catch-up is resumed by IBD, which imports the pruning point UTXO set
+        if !consensus.pruning_meta_stores.read().pruning_utxoset_stable_flag() {
+            let pruning_point = consensus.pruning_point();
+            assert_eq!(consensus.get_sink(), pruning_point, "the virtual state is expected to await the pruning point UTXO set");
+            consensus.shutdown(wait_handles);
+            drop(consensus);
+            drop(db_lifetime);
+            info!("Consensus awaits the UTXO set of the pruning point {pruning_point}");
+            return Recovery::Recovered;
+        }
+
+        let sink = consensus.get_sink();
+        assert!(
+            consensus.get_block_status(sink).is_some_and(|status| status.has_block_body()),
+            "the recovered sink {sink} is expected to have a body"
+        );
+        let mut parent = sink;
+        for i in 1..=RECOVERY_BLOCKS {
+            let hash: Hash = (u64::MAX / 2 + i).into();
+            process_block(&consensus, hash, parent)
+                .await
+                .unwrap_or_else(|reason| panic!("the recovered consensus failed to process block {hash}: {reason}"));
+            parent = hash;
+        }
+        // Early blocks might compete with chain blocks whose virtual commit was lost, so only the final chain is checked
+        assert_eq!(consensus.get_sink(), parent, "the blocks on top of the recovered sink are expected to form the selected chain");
+        assert_eq!(consensus.get_block_status(parent), Some(BlockStatus::StatusUTXOValid));
+
+        // Give the pruning processor time to finish recovering the pruning point UTXO set, which the sanity checks
+        // of the config verify against the pruning point UTXO commitment
+        let start = std::time::Instant::now();
+        while consensus.pruning_meta_stores.read().utxoset_position().unwrap() != consensus.pruning_point() {
+            assert!(start.elapsed() < PRUNING_RECOVERY_TIMEOUT, "the pruning point UTXO set was not recovered in time");
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        // Processor panics surface when joining them
+        consensus.shutdown(wait_handles);
+        drop(consensus);
+        drop(db_lifetime);
+        info!("Consensus recovered at sink {sink}");
+        Recovery::Recovered
+    }
+
+    /// Injects the fault described by `trigger` and `action` into the processing of `num_blocks` blocks and
+    /// asserts that the restarted consensus handles the resulting state as `expected`
+    async fn run(trigger: FaultTrigger, action: FaultAction, num_blocks: u64, expected: Recovery) {
+        init_allocator_with_default_settings();
+        kaspa_core::log::try_init_logger("INFO");
+
+        let harness = Self::new(pruning_config());
+        let injector = Arc::new(FaultInjector::new(trigger, action));
+        let fault = harness.run_until_fault(injector.clone(), num_blocks).await;
+        info!("Injected {:?} into write {:?}, dropping {} later writes", action, fault, injector.dropped_writes());
+        if let FaultAction::Abort { applied_ops } = action {
+            assert!(applied_ops < fault.total_ops, "the aborted write is expected to be applied partially, got {fault:?}");
+        }
+        let recovery = harness.restart().await;
+        assert_eq!(recovery, expected, "unexpected recovery after injecting {action:?} into write {fault:?}");
+    }
+
+    /// Crashes right after the intrusive pruning point update of an IBD pruning catch-up, before the UTXO set of the
+    /// new pruning point is imported, and asserts that the restarted consensus handles the resulting state as `expected`
+    async fn run_intrusive_update(num_blocks: u64, expected: Recovery) {
+        init_allocator_with_default_settings();
+        kaspa_core::log::try_init_logger("INFO");
+
+        let harness = Self::new(pruning_config());
+        // The update drops the stable flag of the pruning point UTXO set, so the crash follows its first write to the flag
+        let trigger = FaultTrigger::NthWriteTo { prefix: DatabaseStorePrefixes::PruningUtxosetSyncFlag, n: 0 };
+        let injector = Arc::new(FaultInjector::new(trigger, FaultAction::Abort { applied_ops: usize::MAX }));
+        let fault = harness.run_until_intrusive_update(injector.clone(), num_blocks).await;
+        info!("Crashed after write {:?}, dropping {} later writes", fault, injector.dropped_writes());
+        assert_eq!(fault.applied_ops, fault.total_ops, "the intrusive update is expected to be written in full, got {fault:?}");
+        let recovery = harness.restart().await;
+        assert_eq!(recovery, expected, "unexpected recovery after crashing past the intrusive update {fault:?}");
+    }
+}
+
+/// Submits a block with `parent` as its sole parent and waits for its virtual processing
+async fn process_block(consensus: &Arc<TestConsensus>, hash: Hash, parent: Hash) -> Result<BlockStatus, String> {
+    let consensus = consensus.clone();
+    // Processors dying on a fault drop their result channels, which makes the processing task panic
+    let task = tokio::spawn(async move { consensus.add_empty_utxo_valid_block_with_parents(hash, vec![parent]).await });
+    match tokio::time::timeout(BLOCK_TIMEOUT, task).await {
+        Ok(Ok(result)) => result.map_err(|err| err.to_string()),
+        Ok(Err(err)) => Err(format!("the processing task failed: {err}")),
+        Err(_) => Err(format!("timed out after {BLOCK_TIMEOUT:?}")),
+    }
+}
+
+/// Stops a consensus instance whose processors might have died on a fault, without waiting for them
+fn abandon(consensus: Arc<TestConsensus>, wait_handles: Vec<JoinHandle<()>>) {
+    std::thread::spawn(move || {
+        // Signaling fails if the first processor of the pipeline is gone
+        let _ = catch_unwind(AssertUnwindSafe(|| consensus.signal_exit()));
+        for handle in wait_handles {
+            let _ = handle.join();
+        }
+    });
+}
+
+/// A config with shallow finality and pruning depths so that a few hundred blocks exercise pruning. Sanity checks
+/// verify the pruning point UTXO set, and the virtual UTXO set is verified whenever consensus is opened.
+fn pruning_config() -> Config {
+    ConfigBuilder::new(MAINNET_PARAMS)
+        .skip_proof_of_work()
+        .enable_sanity_checks()
+        .verify_utxo_set_on_open()
+        .edit_consensus_params(|p| {
+            p.finality_depth = 2;
+            p.mergeset_size_limit = 2;
+            p.ghostdag_k = 2;
+            p.merge_depth = 3;
+            p.pruning_depth = 100;
+        })
+        .build()
+}
+
+/// The number of chain blocks needed for the pruning point of [`pruning_config`] to advance
+fn pruning_blocks() -> u64 {
+    let config = pruning_config();
+    config.pruning_depth() + config.finality_depth() + 100
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn virtual_commit_failure_test() {
+    let trigger = FaultTrigger::NthWriteTo { prefix: DatabaseStorePrefixes::VirtualState, n: 40 };
+    CrashHarness::run(trigger, FaultAction::Fail, 100, Recovery::Recovered).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn virtual_commit_crash_test() {
+    let trigger = FaultTrigger::NthWriteTo { prefix: DatabaseStorePrefixes::VirtualState, n: 40 };
+    CrashHarness::run(trigger, FaultAction::Abort { applied_ops: 0 }, 100, Recovery::Recovered).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn virtual_commit_partial_crash_test() {
+    // Only the first UTXO diff operation (or the virtual state itself) is written, so the virtual UTXO set or the
+    // virtual state no longer matches the selected chain
+    let trigger = FaultTrigger::NthWriteTo { prefix: DatabaseStorePrefixes::VirtualState, n: 40 };
+    CrashHarness::run(trigger, FaultAction::Abort { applied_ops: 1 }, 100, Recovery::Refused).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pruning_point_advance_crash_test() {
+    let trigger = FaultTrigger::NthWriteTo { prefix: DatabaseStorePrefixes::PruningPoint, n: 0 };
+    CrashHarness::run(trigger, FaultAction::Abort { applied_ops: 0 }, pruning_blocks(), Recovery::Recovered).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pruning_point_advance_partial_crash_test() {
+    // Only the first new past pruning point is written, leaving it beyond the index of the stored pruning point
+    let trigger = FaultTrigger::NthWriteTo { prefix: DatabaseStorePrefixes::PruningPoint, n: 0 };
+    CrashHarness::run(trigger, FaultAction::Abort { applied_ops: 1 }, pruning_blocks(), Recovery::Refused).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pruning_utxoset_crash_test() {
+    let trigger = FaultTrigger::NthWriteTo { prefix: DatabaseStorePrefixes::PruningUtxoset, n: 3 };
+    CrashHarness::run(trigger, FaultAction::Abort { applied_ops: 0 }, pruning_blocks(), Recovery::Recovered).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn pruning_utxoset_partial_crash_test() {
+    // The UTXO set position is written last, so recovery re-applies the partially written chain block diff
+    let trigger = FaultTrigger::NthWriteTo { prefix: DatabaseStorePrefixes::PruningUtxoset, n: 3 };
+    CrashHarness::run(trigger, FaultAction::Abort { applied_ops: 1 }, pruning_blocks(), Recovery::Recovered).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn intrusive_pruning_point_update_crash_test() {
+    // The virtual state points at a header-only pruning point whose UTXO set was never imported
+    CrashHarness::run_intrusive_update(pruning_blocks(), Recovery::Recovered).await;
+}
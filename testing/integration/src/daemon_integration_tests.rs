@@ -7,16 +7,32 @@ use crate::common::{
 use kaspa_addresses::Address;
 use kaspa_alloc::init_allocator_with_default_settings;
 use kaspa_consensus::params::SIMNET_PARAMS;
+use kaspa_consensus_core::constants::SOMPI_PER_KASPA;
 use kaspa_consensus_core::header::Header;
+use kaspa_consensus_core::tx::Transaction;
 use kaspa_consensusmanager::ConsensusManager;
 use kaspa_core::{task::runtime::AsyncRuntime, trace};
 use kaspa_grpc_client::GrpcClient;
-use kaspa_notify::scope::{BlockAddedScope, UtxosChangedScope, VirtualDaaScoreChangedScope};
-use kaspa_rpc_core::{api::rpc::RpcApi, Notification, RpcTransactionId};
+use kaspa_hashes::Hash;
+use kaspa_notify::{
+    connection::ChannelType,
+    listener::ListenerId,
+    scope::{BlockAddedScope, Scope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope},
+};
+use kaspa_rpc_core::{api::rpc::RpcApi, notify::connection::ChannelConnection, Notification, RpcNotificationCursor, RpcTransactionId};
 use kaspa_txscript::pay_to_address_script;
+use kaspa_wallet_core::{
+    account::htlc::{htlc_extract_secret, HtlcContract},
+    error::Error as WalletError,
+    prelude::*,
+    storage::keydata::PrvKeyDataVariantKind,
+};
+use kaspa_wrpc_client::{KaspaRpcClient, WrpcEncoding};
+use kaspa_wrpc_server::address::WrpcNetAddress;
 use kaspad_lib::args::Args;
 use rand::thread_rng;
 use std::{sync::Arc, time::Duration};
+use workflow_core::abortable::Abortable;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn daemon_sanity_test() {
@@ -245,7 +261,7 @@ async fn daemon_utxos_propagation_test() {
 
     // ...and subscribe each to some notifications
     for x in clients.iter_mut() {
-        x.start_notify(BlockAddedScope {}.into()).await.unwrap();
+        x.start_notify(BlockAddedScope::default().into()).await.unwrap();
         x.start_notify(UtxosChangedScope::new(vec![miner_address.clone(), user_address.clone()]).into()).await.unwrap();
         x.start_notify(VirtualDaaScoreChangedScope {}.into()).await.unwrap();
     }
@@ -379,3 +395,446 @@ async fn daemon_cleaning_test() {
     assert_eq!(async_runtime.strong_count(), 0);
     assert_eq!(core.strong_count(), 0);
 }
+
+/// `cargo test --release --package kaspa-testing-integration --lib -- daemon_integration_tests::daemon_dandelion_relay_test`
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn daemon_dandelion_relay_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO,kaspa_p2p_flows=debug");
+
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        utxoindex: true,
+        dandelion: true,
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+
+    let coinbase_maturity = SIMNET_PARAMS.coinbase_maturity();
+    let mut kaspads = (0..3).map(|_| Daemon::new_random_with_args(args.clone(), total_fd_limit)).collect::<Vec<_>>();
+    let mut rpc_clients = Vec::with_capacity(kaspads.len());
+    for kaspad in kaspads.iter_mut() {
+        rpc_clients.push(kaspad.start().await);
+    }
+
+    // Build a line topology kaspad #0 -> kaspad #1 -> kaspad #2 so that stem transactions submitted to
+    // kaspad #0 have a single possible first hop
+    for i in 0..kaspads.len() - 1 {
+        rpc_clients[i].add_peer(format!("127.0.0.1:{}", kaspads[i + 1].p2p_port).try_into().unwrap(), true).await.unwrap();
+    }
+    let check_client = rpc_clients[1].clone();
+    wait_for(
+        50,
+        40,
+        move || {
+            async fn peers_connected(client: GrpcClient) -> bool {
+                client.get_connected_peer_info().await.unwrap().peer_info.len() == 2
+            }
+            Box::pin(peers_connected(check_client.clone()))
+        },
+        "the nodes did not connect to each other",
+    )
+    .await;
+
+    // Mine enough blocks to kaspad #0 for the miner to have spendable coinbase UTXOs
+    let (miner_sk, miner_pk) = secp256k1::generate_keypair(&mut thread_rng());
+    let miner_address =
+        Address::new(kaspads[0].network.into(), kaspa_addresses::Version::PubKey, &miner_pk.x_only_public_key().0.serialize());
+    let miner_schnorr_key = secp256k1::Keypair::from_secret_key(secp256k1::SECP256K1, &miner_sk);
+    let (_user_sk, user_pk) = secp256k1::generate_keypair(&mut thread_rng());
+    let user_address =
+        Address::new(kaspads[0].network.into(), kaspa_addresses::Version::PubKey, &user_pk.x_only_public_key().0.serialize());
+
+    const EXTRA_BLOCKS: u64 = 10;
+    let total_blocks = coinbase_maturity + EXTRA_BLOCKS;
+    for _ in 0..total_blocks {
+        let template = rpc_clients[0].get_block_template(miner_address.clone(), vec![]).await.unwrap();
+        rpc_clients[0].submit_block(template.block, false).await.unwrap();
+    }
+    let check_client = rpc_clients[2].clone();
+    wait_for(
+        50,
+        200,
+        move || {
+            async fn blocks_relayed(client: GrpcClient, total_blocks: u64) -> bool {
+                client.get_block_dag_info().await.unwrap().block_count == total_blocks
+            }
+            Box::pin(blocks_relayed(check_client.clone(), total_blocks))
+        },
+        "the nodes did not relay all the mined blocks",
+    )
+    .await;
+
+    // Submit a transaction to kaspad #0. It travels along the stem and must eventually be diffused
+    // to all nodes, either by a fluffing node or by an embargo timer expiring
+    let utxos = fetch_spendable_utxos(&rpc_clients[0], miner_address.clone(), coinbase_maturity).await;
+    assert!(!utxos.is_empty());
+    let transaction =
+        generate_tx(miner_schnorr_key, &utxos[0..1], SIMNET_PARAMS.pre_deflationary_phase_base_subsidy / 2, 1, &user_address);
+    rpc_clients[0].submit_transaction((&transaction).into(), false).await.unwrap();
+
+    // Local transactions are always stemmed, so kaspad #0 keeps the transaction in its stem pool, out of sight,
+    // until it is diffused by a downstream node or its embargo expires (at least 10 seconds)
+    assert!(rpc_clients[0].get_mempool_entry(transaction.id(), true, false).await.is_err());
+    assert!(rpc_clients[0].get_mempool_entries(true, false).await.unwrap().is_empty());
+    assert_eq!(rpc_clients[0].get_info().await.unwrap().mempool_size, 0);
+
+    for client in rpc_clients.iter() {
+        let check_client = client.clone();
+        let transaction_id = transaction.id();
+        wait_for(
+            500,
+            240,
+            move || {
+                async fn transaction_in_mempool(client: GrpcClient, transaction_id: RpcTransactionId) -> bool {
+                    client.get_mempool_entry(transaction_id, false, false).await.is_ok()
+                }
+                Box::pin(transaction_in_mempool(check_client.clone(), transaction_id))
+            },
+            "the stem transaction was not relayed to all nodes",
+        )
+        .await;
+    }
+
+    for (client, kaspad) in rpc_clients.into_iter().zip(kaspads.iter_mut()) {
+        client.disconnect().await.unwrap();
+        drop(client);
+        kaspad.shutdown();
+    }
+}
+
+/// Creates a resident wallet holding a single keypair account, connected to the wRPC Borsh server of `kaspad`
+/// The URL of the wRPC Borsh listener of a daemon started with random ports
+fn borsh_url(kaspad: &Daemon) -> String {
+    let borsh_port = match kaspad.client_manager().args.read().rpclisten_borsh.clone().unwrap() {
+        WrpcNetAddress::Custom(address) => address.normalize(0).port,
+        _ => unreachable!("the daemon is configured with random ports"),
+    };
+    format!("ws://127.0.0.1:{borsh_port}")
+}
+
+async fn create_swap_wallet(kaspad: &Daemon, wallet_secret: &Secret) -> (Arc<Wallet>, Arc<dyn Account>) {
+    let url = borsh_url(kaspad);
+
+    let wallet =
+        Arc::new(Wallet::try_new(Wallet::resident_store().unwrap(), None, Some(kaspad.network)).unwrap().with_url(Some(&url)));
+    wallet.start().await.unwrap();
+    wallet
+        .create_wallet(wallet_secret, WalletCreateArgs::new(None, None, EncryptionKind::XChaCha20Poly1305, None, true))
+        .await
+        .unwrap();
+    let (secret_key, _) = secp256k1::generate_keypair(&mut thread_rng());
+    let prv_key_data_args =
+        PrvKeyDataCreateArgs::new(None, None, Secret::from(secret_key.secret_bytes().as_slice()), PrvKeyDataVariantKind::SecretKey);
+    let prv_key_data_id = wallet.create_prv_key_data(wallet_secret, prv_key_data_args).await.unwrap();
+    let account = wallet.create_account_keypair(wallet_secret, None, prv_key_data_id, None, false).await.unwrap();
+
+    let options =
+        ConnectOptions { block_async_connect: true, strategy: ConnectStrategy::Fallback, url: Some(url), ..Default::default() };
+    wallet.wrpc_client().unwrap().connect(Some(options)).await.unwrap();
+    account.clone().start().await.unwrap();
+    (wallet, account)
+}
+
+async fn mine_blocks(client: &GrpcClient, address: &Address, count: u64) {
+    for _ in 0..count {
+        let template = client.get_block_template(address.clone(), vec![]).await.unwrap();
+        client.submit_block(template.block, false).await.unwrap();
+    }
+}
+
+/// Mines blocks until the contract holds the expected amount
+async fn wait_for_contract_amount(
+    client: &GrpcClient,
+    miner: &Address,
+    account: &Arc<dyn Account>,
+    contract: &HtlcContract,
+    amount: u64,
+) {
+    for _ in 0..100 {
+        mine_blocks(client, miner, 1).await;
+        if account.clone().htlc_audit(contract.clone()).await.unwrap().amount() == amount {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("the contract {} did not reach the expected amount", contract.address);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn daemon_htlc_atomic_swap_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        utxoindex: true,
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+
+    let coinbase_maturity = SIMNET_PARAMS.coinbase_maturity();
+    let mut kaspad = Daemon::new_random_with_args(args, total_fd_limit);
+    let client = kaspad.start().await;
+
+    let wallet_secret = Secret::from("swap-test");
+    let (alice_wallet, alice) = create_swap_wallet(&kaspad, &wallet_secret).await;
+    let (bob_wallet, bob) = create_swap_wallet(&kaspad, &wallet_secret).await;
+    let alice_address = alice.receive_address().unwrap();
+    let bob_address = bob.receive_address().unwrap();
+
+    // Fund both parties and let their coinbase UTXOs mature
+    let (_, miner_pk) = secp256k1::generate_keypair(&mut thread_rng());
+    let miner_address =
+        Address::new(kaspad.network.into(), kaspa_addresses::Version::PubKey, &miner_pk.x_only_public_key().0.serialize());
+    mine_blocks(&client, &alice_address, 10).await;
+    mine_blocks(&client, &bob_address, 10).await;
+    mine_blocks(&client, &miner_address, coinbase_maturity + 10).await;
+    for account in [alice.clone(), bob.clone()] {
+        wait_for(
+            100,
+            200,
+            move || {
+                let account = account.clone();
+                Box::pin(async move { account.balance().is_some_and(|balance| balance.mature > 0) })
+            },
+            "the swap parties did not receive mature funds",
+        )
+        .await;
+    }
+
+    let abortable = Abortable::default();
+    let amount = 10 * SOMPI_PER_KASPA;
+    let virtual_daa_score = client.get_block_dag_info().await.unwrap().virtual_daa_score;
+
+    // Alice initiates the swap and Bob participates with a shorter lock time after auditing her contract
+    let initiation = alice
+        .clone()
+        .htlc_initiate(
+            bob_address.clone(),
+            amount,
+            virtual_daa_score + 2000,
+            None,
+            Fees::None,
+            wallet_secret.clone(),
+            None,
+            &abortable,
+        )
+        .await
+        .unwrap();
+    assert!(initiation.secret.is_some());
+    let alice_contract = initiation.contract;
+    wait_for_contract_amount(&client, &miner_address, &bob, &alice_contract, amount).await;
+
+    let audit = bob.clone().htlc_audit(alice_contract.clone()).await.unwrap();
+    assert_eq!(audit.contract.receiver_address(), bob_address);
+    assert_eq!(audit.contract.refund_address(), alice_address);
+    assert!(!audit.is_refundable());
+
+    let participation = bob
+        .clone()
+        .htlc_participate(
+            alice_address.clone(),
+            alice_contract.htlc.payment_hash,
+            amount,
+            virtual_daa_score + 1000,
+            None,
+            Fees::None,
+            wallet_secret.clone(),
+            None,
+            &abortable,
+        )
+        .await
+        .unwrap();
+    assert!(participation.secret.is_none());
+    let bob_contract = participation.contract;
+    wait_for_contract_amount(&client, &miner_address, &alice, &bob_contract, amount).await;
+
+    // A wrong secret is rejected, and the contracts cannot be refunded before their lock time
+    assert!(matches!(
+        alice.clone().htlc_redeem(&bob_contract, &[0u8; 32], 0, wallet_secret.clone(), None).await,
+        Err(WalletError::HtlcSecretMismatch)
+    ));
+    assert!(matches!(
+        alice.clone().htlc_refund(&alice_contract, 0, wallet_secret.clone(), None).await,
+        Err(WalletError::HtlcRefundLocked(..))
+    ));
+
+    // Alice redeems Bob's contract with the secret kept in her wallet storage, revealing it on chain
+    let secret = alice.clone().htlc_load_secret(&alice_contract.htlc.payment_hash, wallet_secret.clone()).await.unwrap().unwrap();
+    let redeem_id = alice.clone().htlc_redeem(&bob_contract, &secret, 0, wallet_secret.clone(), None).await.unwrap();
+
+    // Bob recovers the secret from Alice's redeem transaction and uses it to redeem her contract
+    let redeem_transaction: Transaction =
+        client.get_mempool_entry(redeem_id, false, false).await.unwrap().transaction.try_into().unwrap();
+    let revealed_secret = htlc_extract_secret(&bob_contract, &redeem_transaction).unwrap();
+    assert!(bob.clone().htlc_load_secret(&alice_contract.htlc.payment_hash, wallet_secret.clone()).await.unwrap().is_none());
+    wait_for_contract_amount(&client, &miner_address, &alice, &bob_contract, 0).await;
+    bob.clone().htlc_redeem(&alice_contract, &revealed_secret, 0, wallet_secret.clone(), None).await.unwrap();
+    wait_for_contract_amount(&client, &miner_address, &bob, &alice_contract, 0).await;
+
+    // An abandoned swap gets refunded once the lock time is reached
+    let virtual_daa_score = client.get_block_dag_info().await.unwrap().virtual_daa_score;
+    let abandoned = alice
+        .clone()
+        .htlc_initiate(bob_address.clone(), amount, virtual_daa_score + 50, None, Fees::None, wallet_secret.clone(), None, &abortable)
+        .await
+        .unwrap()
+        .contract;
+    wait_for_contract_amount(&client, &miner_address, &alice, &abandoned, amount).await;
+    mine_blocks(&client, &miner_address, 50).await;
+    assert!(alice.clone().htlc_audit(abandoned.clone()).await.unwrap().is_refundable());
+    alice.clone().htlc_refund(&abandoned, 0, wallet_secret.clone(), None).await.unwrap();
+    wait_for_contract_amount(&client, &miner_address, &alice, &abandoned, 0).await;
+
+    for wallet in [alice_wallet, bob_wallet] {
+        wallet.stop().await.unwrap();
+    }
+    client.disconnect().await.unwrap();
+    drop(client);
+    kaspad.shutdown();
+}
+
+/// A wRPC client whose notifications are forwarded to a channel
+struct WrpcListener {
+    client: KaspaRpcClient,
+    listener_id: ListenerId,
+    receiver: async_channel::Receiver<Notification>,
+}
+
+impl WrpcListener {
+    async fn connect(kaspad: &Daemon) -> Self {
+        let url = borsh_url(kaspad);
+        let client = KaspaRpcClient::new_with_args(WrpcEncoding::Borsh, Some(&url), None, Some(kaspad.network), None).unwrap();
+        let options =
+            ConnectOptions { block_async_connect: true, strategy: ConnectStrategy::Fallback, url: Some(url), ..Default::default() };
+        client.connect(Some(options)).await.unwrap();
+        let (sender, receiver) = async_channel::unbounded();
+        let listener_id = client.register_new_listener(ChannelConnection::new("replay test", sender, ChannelType::Persistent));
+        Self { client, listener_id, receiver }
+    }
+
+    async fn next(&self) -> Notification {
+        tokio::time::timeout(Duration::from_secs(10), self.receiver.recv()).await.expect("no notification was received").unwrap()
+    }
+
+    /// Drains the notifications until one carries a cursor pointing at `sink`, returning all of them
+    async fn until_cursor(&self, sink: Hash) -> Vec<Notification> {
+        let mut notifications = vec![];
+        loop {
+            let notification = self.next().await;
+            let cursor = match &notification {
+                Notification::VirtualChainChanged(notification) => notification.cursor,
+                Notification::UtxosChanged(notification) => notification.cursor,
+                _ => None,
+            };
+            notifications.push(notification);
+            if cursor.is_some_and(|cursor| cursor.chain_block_hash == sink) {
+                return notifications;
+            }
+        }
+    }
+}
+
+/// `cargo test --release --package kaspa-testing-integration --lib -- daemon_integration_tests::daemon_notification_replay_test`
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn daemon_notification_replay_test() {
+    init_allocator_with_default_settings();
+    kaspa_core::log::try_init_logger("INFO");
+
+    // Simnet targets 10 blocks per second, so a 2 seconds replay window spans 20 DAA
+    const REPLAY_WINDOW_DAA: u64 = 20;
+    let args = Args {
+        simnet: true,
+        unsafe_rpc: true,
+        enable_unsynced_mining: true,
+        disable_upnp: true, // UPnP registration might take some time and is not needed for this test
+        utxoindex: true,
+        notification_replay_window: 2,
+        ..Default::default()
+    };
+    let total_fd_limit = 10;
+    let mut kaspad = Daemon::new_random_with_args(args, total_fd_limit);
+    let client = kaspad.start().await;
+
+    let miner = Address::new(kaspad.network.into(), kaspa_addresses::Version::PubKey, &[0; 32]);
+    let vcc_scope = Scope::VirtualChainChanged(VirtualChainChangedScope::new(false));
+    let utxos_scope = Scope::UtxosChanged(UtxosChangedScope::new(vec![miner.clone()]));
+    mine_blocks(&client, &miner, 5).await;
+
+    // Follow the chain with a first client and keep the cursor of the last chain change it processed
+    let listener = WrpcListener::connect(&kaspad).await;
+    listener.client.start_notify(listener.listener_id, vcc_scope.clone()).await.unwrap();
+    mine_blocks(&client, &miner, 3).await;
+    let sink = client.get_sink().await.unwrap().sink;
+    let cursor = listener
+        .until_cursor(sink)
+        .await
+        .into_iter()
+        .filter_map(|notification| match notification {
+            Notification::VirtualChainChanged(notification) => notification.cursor,
+            _ => None,
+        })
+        .last()
+        .unwrap();
+    assert_eq!(cursor.chain_block_hash, sink);
+    assert_eq!(cursor.daa_score, client.get_block(sink, false).await.unwrap().header.daa_score);
+    listener.client.disconnect().await.unwrap();
+    drop(listener);
+
+    // The chain moves on while the client is away
+    mine_blocks(&client, &miner, 5).await;
+    let sink = client.get_sink().await.unwrap().sink;
+    let missed = client.get_virtual_chain_from_block(cursor.chain_block_hash, false, None).await.unwrap();
+    assert_eq!(missed.added_chain_block_hashes.last(), Some(&sink));
+
+    // A new client resuming from the cursor gets the missed chain changes replayed
+    let listener = WrpcListener::connect(&kaspad).await;
+    let resumed = listener.client.resume_notify(listener.listener_id, vcc_scope.clone(), cursor).await.unwrap();
+    assert_eq!(resumed.chain_block_hash, sink);
+    match listener.next().await {
+        Notification::VirtualChainChanged(notification) => {
+            assert!(notification.removed_chain_block_hashes.is_empty());
+            assert_eq!(*notification.added_chain_block_hashes, missed.added_chain_block_hashes);
+            assert_eq!(notification.cursor, Some(resumed));
+        }
+        notification => panic!("expected a replayed virtual chain change, got {notification:?}"),
+    }
+
+    // UTXO changes are replayed block by block, each carrying the cursor of the chain block it belongs to
+    let resumed = listener.client.resume_notify(listener.listener_id, utxos_scope.clone(), cursor).await.unwrap();
+    assert_eq!(resumed.chain_block_hash, sink);
+    let replayed = listener.until_cursor(sink).await;
+    let mut chain_blocks = missed.added_chain_block_hashes.iter();
+    for notification in replayed {
+        let Notification::UtxosChanged(notification) = notification else {
+            panic!("expected a replayed UTXO change, got {notification:?}");
+        };
+        let hash = notification.cursor.expect("replayed UTXO changes of added chain blocks carry a cursor").chain_block_hash;
+        assert!(chain_blocks.any(|&chain_block| chain_block == hash), "UTXO change of {hash} replayed out of chain order");
+        assert!(notification.added.iter().all(|entry| entry.address.as_ref() == Some(&miner)));
+    }
+    listener.client.disconnect().await.unwrap();
+    drop(listener);
+
+    // Once the cursor falls out of the replay window, resuming fails and a full resync is required
+    mine_blocks(&client, &miner, REPLAY_WINDOW_DAA + 10).await;
+    let listener = WrpcListener::connect(&kaspad).await;
+    let err = listener.client.resume_notify(listener.listener_id, vcc_scope.clone(), cursor).await.unwrap_err();
+    assert!(err.to_string().contains("out of the replay window"), "unexpected error: {err}");
+    let unknown = RpcNotificationCursor::new(Hash::from_u64_word(u64::MAX), cursor.daa_score);
+    let err = listener.client.resume_notify(listener.listener_id, vcc_scope, unknown).await.unwrap_err();
+    assert!(err.to_string().contains("out of the replay window"), "unexpected error: {err}");
+    listener.client.disconnect().await.unwrap();
+    drop(listener);
+
+    client.disconnect().await.unwrap();
+    drop(client);
+    kaspad.shutdown();
+}
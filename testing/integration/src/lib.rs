@@ -6,12 +6,18 @@ static ALLOC: dhat::Alloc = dhat::Alloc;
 pub mod common;
 pub mod tasks;
 
+#[cfg(test)]
+pub mod cluster_integration_tests;
+
 #[cfg(test)]
 pub mod consensus_integration_tests;
 
 #[cfg(test)]
 pub mod consensus_pipeline_tests;
 
+#[cfg(test)]
+pub mod crash_recovery_tests;
+
 #[cfg(test)]
 pub mod daemon_integration_tests;
 
@@ -11,8 +11,9 @@ use kaspa_hashes::Hash;
 use kaspa_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
-        BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
-        SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
+        BlockAddedScope, FinalityConflictScope, MempoolTransactionAddedScope, MempoolTransactionRemovedScope, NewBlockTemplateScope,
+        PruningPointUtxoSetOverrideScope, Scope, SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope,
+        VirtualDaaScoreChangedScope,
     },
 };
 use kaspa_rpc_core::{api::rpc::RpcApi, model::*, Notification};
@@ -104,17 +105,19 @@ async fn sanity_test() {
                     assert!(response.removed_chain_block_hashes.is_empty());
 
                     // Get a block template
-                    let GetBlockTemplateResponse { block, is_synced } = rpc_client
+                    let GetBlockTemplateResponse { block, is_synced, payouts_recorded_only } = rpc_client
                         .get_block_template_call(
                             None,
                             GetBlockTemplateRequest {
                                 pay_address: Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]),
                                 extra_data: Vec::new(),
+                                payouts: vec![],
                             },
                         )
                         .await
                         .unwrap();
                     assert!(!is_synced);
+                    assert!(!payouts_recorded_only);
 
                     // Compute the expected block hash for the received block
                     let header: Header = (&block.header).try_into().unwrap();
@@ -708,11 +711,82 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetFeeEstimateHistory => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The node is not running with --record-fee-estimates
+                    let response = rpc_client.get_fee_estimate_history(0, None).await;
+                    assert!(matches!(response, Err(kaspa_rpc_core::RpcError::General(_))));
+                })
+            }
+
+            KaspadPayloadOps::GetMempoolInfo => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_mempool_info(true).await.unwrap();
+                    assert_eq!(response.transaction_count as usize, response.entries.len());
+                    assert_eq!(
+                        response.feerate_histogram.iter().map(|bucket| bucket.transaction_count).sum::<u64>(),
+                        response.transaction_count
+                    );
+                })
+            }
+
+            KaspadPayloadOps::GetMempoolTransactionGraph => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_mempool_transaction_graph(0.into()).await;
+                    assert!(response.is_err());
+                })
+            }
+
+            KaspadPayloadOps::PrioritiseTransaction => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The delta of a transaction unknown to the mempool is registered until cancelled
+                    let transaction_id = 0.into();
+                    let response = rpc_client.prioritise_transaction(transaction_id, 1000).await.unwrap();
+                    assert_eq!(response.fee_delta, 1000);
+                    let prioritised = rpc_client.get_prioritised_transactions().await.unwrap();
+                    assert!(prioritised.iter().any(|x| x.transaction_id == transaction_id && x.fee_delta == 1000 && !x.in_mempool));
+                    let response = rpc_client.prioritise_transaction(transaction_id, -1000).await.unwrap();
+                    assert_eq!(response.fee_delta, 0);
+                })
+            }
+
+            KaspadPayloadOps::GetPrioritisedTransactions => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_prioritised_transactions().await;
+                    assert!(response.is_ok());
+                })
+            }
+
+            KaspadPayloadOps::GetMiningStatus => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_mining_status().await.unwrap();
+                    assert!(!response.reason.is_empty());
+                    // All the built-in rules are reported, whether enabled or not
+                    assert_eq!(response.rules.len(), 4);
+                    assert!(response.rules.iter().any(|rule| rule.name == "sync-rate" && rule.enabled));
+                })
+            }
+
+            KaspadPayloadOps::ReloadMempoolPolicy => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // The node is not running with --mempool-policy
+                    let response = rpc_client.reload_mempool_policy().await;
+                    assert!(matches!(response, Err(kaspa_rpc_core::RpcError::General(_))));
+                })
+            }
+
             KaspadPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
                 tst!(op, {
-                    rpc_client.start_notify(id, BlockAddedScope {}.into()).await.unwrap();
+                    rpc_client.start_notify(id, BlockAddedScope::default().into()).await.unwrap();
                 })
             }
 
@@ -769,6 +843,20 @@ async fn sanity_test() {
                         .unwrap();
                 })
             }
+            KaspadPayloadOps::NotifyMempoolTransactionAdded => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, MempoolTransactionAddedScope::default().into()).await.unwrap();
+                })
+            }
+            KaspadPayloadOps::NotifyMempoolTransactionRemoved => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, MempoolTransactionRemovedScope::default().into()).await.unwrap();
+                })
+            }
             KaspadPayloadOps::StopNotifyingUtxosChanged => {
                 let rpc_client = client.clone();
                 let id = listener_id;
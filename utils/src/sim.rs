@@ -83,6 +83,11 @@ impl<T: Clone> Environment<T> {
         self.now
     }
 
+    /// Returns the ids of the registered processes
+    pub fn process_ids(&self) -> impl Iterator<Item = &u64> {
+        self.process_ids.iter()
+    }
+
     pub fn send(&mut self, delay: u64, dest: u64, msg: T) {
         self.event_queue.push(Event::new(self.now + delay, dest, Some(msg)))
     }
@@ -0,0 +1,340 @@
+//!
+//! Hash time locked contracts (HTLC) for cross-chain atomic swaps.
+//!
+//! The initiator of a swap generates a secret and funds an [`Htlc`] paying the counterparty against
+//! that secret, refundable to the initiator once the lock time (a DAA score) is reached. The participant
+//! audits the contract and funds its own HTLC bound to the same secret hash with a shorter lock time.
+//! The initiator then redeems the participant contract, revealing the secret on chain, which in turn
+//! allows the participant to redeem the initiator contract.
+//!
+
+use crate::account::pskb::pskt_to_pending_transaction;
+use crate::imports::*;
+use crate::tx::{calc_minimum_required_transaction_relay_fee, Fees, GeneratorSummary, PaymentOutput};
+use kaspa_addresses::Version;
+use kaspa_consensus_core::tx::Transaction;
+use kaspa_hashes::Hash;
+use kaspa_rpc_core::RpcUtxosByAddressesEntry;
+use kaspa_txscript::covenants::{Htlc, HTLC_PREIMAGE_LEN};
+use kaspa_txscript::{pay_to_address_script, CovenantTemplate};
+use kaspa_wallet_pskt::input::InputBuilder;
+use kaspa_wallet_pskt::output::OutputBuilder;
+use kaspa_wallet_pskt::prelude::{Bundle, Finalizer, Inner, Signer, PSKT};
+use kaspa_wallet_pskt::pskt::Creator;
+use workflow_core::abortable::Abortable;
+
+/// The minimum DAA score span between the lock time of the participant contract and the lock time of the
+/// initiator contract (one hour at 10 BPS), leaving the participant time to redeem the initiator contract once
+/// the initiator reveals the secret, before the initiator can get refunded
+pub const HTLC_PARTICIPANT_LOCK_TIME_MARGIN: u64 = 36_000;
+
+/// A funded or to be funded HTLC along with its pay-to-script-hash address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtlcContract {
+    pub htlc: Htlc,
+    pub redeem_script: Vec<u8>,
+    pub address: Address,
+}
+
+impl HtlcContract {
+    pub fn try_new(htlc: Htlc, prefix: Prefix) -> Result<Self> {
+        Ok(Self { redeem_script: htlc.redeem_script()?, address: htlc.address(prefix)?, htlc })
+    }
+
+    /// Decodes a contract from its redeem script
+    pub fn try_from_redeem_script(redeem_script: &[u8], prefix: Prefix) -> Result<Self> {
+        let htlc = Htlc::from_redeem_script(redeem_script).ok_or(Error::HtlcInvalidContract)?;
+        Self::try_new(htlc, prefix)
+    }
+
+    /// The address able to redeem the contract by revealing the secret
+    pub fn receiver_address(&self) -> Address {
+        Address::new(self.address.prefix, Version::PubKey, &self.htlc.receiver)
+    }
+
+    /// The address able to get refunded once the lock time is reached
+    pub fn refund_address(&self) -> Address {
+        Address::new(self.address.prefix, Version::PubKey, &self.htlc.refund)
+    }
+
+    /// Checks that a participant contract locked until `lock_time` gets refundable safely after this initiator
+    /// contract can no longer be refunded, see [`HTLC_PARTICIPANT_LOCK_TIME_MARGIN`]
+    pub fn check_participant_lock_time(&self, lock_time: u64) -> Result<()> {
+        if lock_time.saturating_add(HTLC_PARTICIPANT_LOCK_TIME_MARGIN) > self.htlc.lock_time {
+            return Err(Error::HtlcUnsafeLockTime(lock_time, self.htlc.lock_time, HTLC_PARTICIPANT_LOCK_TIME_MARGIN));
+        }
+        Ok(())
+    }
+}
+
+/// Result of funding a contract: the contract, the secret if this side initiated the swap, and the funding transactions
+pub struct HtlcFunding {
+    pub contract: HtlcContract,
+    pub secret: Option<[u8; HTLC_PREIMAGE_LEN]>,
+    pub summary: GeneratorSummary,
+    pub transaction_ids: Vec<Hash>,
+}
+
+/// On-chain state of a contract
+pub struct HtlcAudit {
+    pub contract: HtlcContract,
+    pub utxos: Vec<RpcUtxosByAddressesEntry>,
+    pub virtual_daa_score: u64,
+}
+
+impl HtlcAudit {
+    /// Total amount locked in the contract
+    pub fn amount(&self) -> u64 {
+        self.utxos.iter().map(|entry| entry.utxo_entry.amount).sum()
+    }
+
+    /// True if the refund path can be used
+    pub fn is_refundable(&self) -> bool {
+        self.virtual_daa_score >= self.contract.htlc.lock_time
+    }
+}
+
+/// The spending path of a contract
+pub enum HtlcSpend<'a> {
+    Redeem { secret: &'a [u8] },
+    Refund,
+}
+
+/// Generates a random swap secret along with its hash
+pub fn generate_secret() -> ([u8; HTLC_PREIMAGE_LEN], [u8; 32]) {
+    let secret = rand::random::<[u8; HTLC_PREIMAGE_LEN]>();
+    (secret, Htlc::payment_hash(&secret))
+}
+
+/// Recovers the secret revealed by a transaction redeeming `contract`, allowing the counterparty of the
+/// redeeming party to redeem the other contract of the swap
+pub fn htlc_extract_secret(contract: &HtlcContract, transaction: &Transaction) -> Result<[u8; HTLC_PREIMAGE_LEN]> {
+    transaction
+        .inputs
+        .iter()
+        .find_map(|input| contract.htlc.extract_preimage(&input.signature_script))
+        .ok_or_else(|| Error::HtlcSecretNotRevealed(contract.address.to_string()))
+}
+
+/// Builds a contract paying `counterparty` against the preimage of `secret_hash`, refundable to the
+/// account receive address at `lock_time`, and funds it with `amount_sompi`
+pub(crate) async fn fund(
+    account: Arc<dyn Account>,
+    counterparty: &Address,
+    secret_hash: [u8; 32],
+    lock_time: u64,
+    amount_sompi: u64,
+    fee_rate: Option<f64>,
+    priority_fee_sompi: Fees,
+    wallet_secret: Secret,
+    payment_secret: Option<Secret>,
+    abortable: &Abortable,
+) -> Result<(HtlcContract, GeneratorSummary, Vec<Hash>)> {
+    let refund = account.receive_address()?;
+    let htlc = Htlc::new(schnorr_public_key(counterparty)?, schnorr_public_key(&refund)?, secret_hash, lock_time);
+    let contract = HtlcContract::try_new(htlc, account.wallet().address_prefix()?)?;
+
+    let destination = PaymentOutput::new(contract.address.clone(), amount_sompi).into();
+    let (summary, transaction_ids) =
+        account.send(destination, fee_rate, priority_fee_sompi, None, None, wallet_secret, payment_secret, abortable, None).await?;
+    Ok((contract, summary, transaction_ids))
+}
+
+/// Looks up the UTXOs locked in a contract
+pub(crate) async fn audit(account: Arc<dyn Account>, contract: HtlcContract) -> Result<HtlcAudit> {
+    let rpc = account.wallet().rpc_api();
+    let utxos = rpc.get_utxos_by_addresses(vec![contract.address.clone()]).await?;
+    let virtual_daa_score = rpc.get_block_dag_info().await?.virtual_daa_score;
+    Ok(HtlcAudit { contract, utxos, virtual_daa_score })
+}
+
+/// Spends all the UTXOs locked in a contract to the account receive address. The spending transaction
+/// is built as a PSKT, signed by the account and finalized with the signature script of the spending path
+pub(crate) async fn spend(
+    account: Arc<dyn Account>,
+    contract: &HtlcContract,
+    path: HtlcSpend<'_>,
+    priority_fee_sompi: u64,
+    wallet_secret: Secret,
+    payment_secret: Option<Secret>,
+) -> Result<Hash> {
+    let audit = audit(account.clone(), contract.clone()).await?;
+    if audit.utxos.is_empty() {
+        return Err(Error::HtlcNotFunded(contract.address.to_string()));
+    }
+    let signing_address = match path {
+        HtlcSpend::Redeem { secret } => {
+            if secret.len() != HTLC_PREIMAGE_LEN || Htlc::payment_hash(secret) != contract.htlc.payment_hash {
+                return Err(Error::HtlcSecretMismatch);
+            }
+            contract.receiver_address()
+        }
+        HtlcSpend::Refund => {
+            if !audit.is_refundable() {
+                return Err(Error::HtlcRefundLocked(contract.htlc.lock_time, audit.virtual_daa_score));
+            }
+            contract.refund_address()
+        }
+    };
+
+    let network_id = account.wallet().network_id()?;
+    let destination = account.receive_address()?;
+
+    // The fee depends on the mass of the signed transaction, so sign a first time with the priority fee alone,
+    // then sign again if the resulting transaction mass requires a higher fee
+    let mut fee = priority_fee_sompi;
+    let finalized = loop {
+        let pskt = build_spend_pskt(contract, &audit.utxos, &path, &destination, fee)?;
+        let bundle = account
+            .clone()
+            .pskb_sign(&Bundle::from(pskt), wallet_secret.clone(), payment_secret.clone(), Some(&signing_address))
+            .await?;
+        let pskt = PSKT::<Signer>::from(bundle.iter().next().cloned().ok_or(Error::PSKTFinalizationError)?);
+        let finalized = finalize_spend_pskt(pskt.finalizer(), contract, &path)?;
+
+        let mass = finalized
+            .clone()
+            .extractor()
+            .map_err(|err| Error::PendingTransactionFromPSKTError(err.to_string()))?
+            .extract_tx(&network_id.into())
+            .map_err(|err| Error::PendingTransactionFromPSKTError(err.to_string()))?
+            .tx
+            .mass();
+        let required_fee = calc_minimum_required_transaction_relay_fee(mass) + priority_fee_sompi;
+        if fee >= required_fee {
+            break finalized;
+        }
+        fee = required_fee;
+    };
+
+    let transaction = pskt_to_pending_transaction(finalized, network_id, account.change_address()?, None)?;
+    transaction.try_submit(&account.wallet().rpc_api()).await
+}
+
+fn build_spend_pskt(
+    contract: &HtlcContract,
+    utxos: &[RpcUtxosByAddressesEntry],
+    path: &HtlcSpend<'_>,
+    destination: &Address,
+    fee: u64,
+) -> Result<PSKT<Signer>> {
+    let total: u64 = utxos.iter().map(|entry| entry.utxo_entry.amount).sum();
+    if total <= fee {
+        return Err(Error::InsufficientFunds { additional_needed: fee - total, origin: "htlc" });
+    }
+    let refund = matches!(path, HtlcSpend::Refund);
+
+    let mut pskt = PSKT::<Creator>::default().constructor();
+    for entry in utxos {
+        let mut input = InputBuilder::default();
+        input
+            .utxo_entry(entry.utxo_entry.clone().into())
+            .previous_outpoint(entry.outpoint.into())
+            .sig_op_count(1)
+            .redeem_script(contract.redeem_script.clone());
+        if refund {
+            input.min_time(Some(contract.htlc.lock_time));
+        }
+        pskt = pskt.input(input.build().map_err(kaspa_wallet_pskt::error::Error::from)?);
+    }
+    let output = OutputBuilder::default()
+        .amount(total - fee)
+        .script_public_key(pay_to_address_script(destination))
+        .build()
+        .map_err(kaspa_wallet_pskt::error::Error::from)?;
+
+    let mut pskt = pskt.output(output).updater();
+    if refund {
+        // Lock times are only enforced for inputs with a non final sequence
+        for input_index in 0..utxos.len() {
+            pskt = pskt.set_sequence(0, input_index)?;
+        }
+    }
+    Ok(pskt.signer())
+}
+
+fn finalize_spend_pskt(pskt: PSKT<Finalizer>, contract: &HtlcContract, path: &HtlcSpend<'_>) -> Result<PSKT<Finalizer>> {
+    pskt.finalize_sync(|inner: &Inner| -> Result<Vec<Vec<u8>>> {
+        inner
+            .inputs
+            .iter()
+            .map(|input| {
+                let (_, signature) = input.partial_sigs.iter().next().ok_or(Error::PSKTFinalizationError)?;
+                let signature = signature.into_bytes().into_iter().chain([input.sighash_type.to_u8()]).collect::<Vec<_>>();
+                Ok(match path {
+                    HtlcSpend::Redeem { secret } => contract.htlc.claim_signature_script(&signature, secret)?,
+                    HtlcSpend::Refund => contract.htlc.refund_signature_script(&signature)?,
+                })
+            })
+            .collect()
+    })
+    .map_err(|_| Error::PSKTFinalizationError)
+}
+
+fn schnorr_public_key(address: &Address) -> Result<[u8; 32]> {
+    match address.version {
+        Version::PubKey => Ok(address.payload.as_slice().try_into().expect("a Schnorr public key is 32 bytes long")),
+        _ => Err(Error::HtlcUnsupportedAddress(address.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::{TransactionId, TransactionInput, TransactionOutpoint};
+
+    #[test]
+    fn test_contract_roundtrip() {
+        let (secret, secret_hash) = generate_secret();
+        assert_eq!(Htlc::payment_hash(&secret), secret_hash);
+
+        let htlc = Htlc::new([1; 32], [2; 32], secret_hash, 1_000_000);
+        let contract = HtlcContract::try_new(htlc, Prefix::Simnet).unwrap();
+        assert_eq!(HtlcContract::try_from_redeem_script(&contract.redeem_script, Prefix::Simnet).unwrap(), contract);
+        assert_eq!(contract.address.version, Version::ScriptHash);
+        assert_eq!(schnorr_public_key(&contract.receiver_address()).unwrap(), [1; 32]);
+        assert_eq!(schnorr_public_key(&contract.refund_address()).unwrap(), [2; 32]);
+        assert!(matches!(schnorr_public_key(&contract.address), Err(Error::HtlcUnsupportedAddress(_))));
+        assert!(matches!(HtlcContract::try_from_redeem_script(&[0x51], Prefix::Simnet), Err(Error::HtlcInvalidContract)));
+    }
+
+    #[test]
+    fn test_participant_lock_time() {
+        let lock_time = 1_000_000;
+        let initiator = HtlcContract::try_new(Htlc::new([1; 32], [2; 32], [0; 32], lock_time), Prefix::Simnet).unwrap();
+        assert!(initiator.check_participant_lock_time(lock_time - HTLC_PARTICIPANT_LOCK_TIME_MARGIN).is_ok());
+        assert!(matches!(
+            initiator.check_participant_lock_time(lock_time - HTLC_PARTICIPANT_LOCK_TIME_MARGIN + 1),
+            Err(Error::HtlcUnsafeLockTime(..))
+        ));
+        assert!(matches!(initiator.check_participant_lock_time(lock_time), Err(Error::HtlcUnsafeLockTime(..))));
+        assert!(matches!(initiator.check_participant_lock_time(u64::MAX), Err(Error::HtlcUnsafeLockTime(..))));
+    }
+
+    #[test]
+    fn test_extract_secret() {
+        let (secret, secret_hash) = generate_secret();
+        let contract = HtlcContract::try_new(Htlc::new([1; 32], [2; 32], secret_hash, 1_000_000), Prefix::Simnet).unwrap();
+        let other = HtlcContract::try_new(Htlc::new([1; 32], [2; 32], secret_hash, 1_000_001), Prefix::Simnet).unwrap();
+
+        let signature = [3u8; 65];
+        let input = |signature_script| {
+            TransactionInput::new(TransactionOutpoint::new(TransactionId::from_u64_word(1), 0), signature_script, 0, 1)
+        };
+        let mut transaction = Transaction::new(
+            0,
+            vec![input(vec![]), input(contract.htlc.claim_signature_script(&signature, &secret).unwrap())],
+            vec![],
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        assert_eq!(htlc_extract_secret(&contract, &transaction).unwrap(), secret);
+        assert!(matches!(htlc_extract_secret(&other, &transaction), Err(Error::HtlcSecretNotRevealed(_))));
+
+        transaction.inputs[1].signature_script = contract.htlc.refund_signature_script(&signature).unwrap();
+        assert!(matches!(htlc_extract_secret(&contract, &transaction), Err(Error::HtlcSecretNotRevealed(_))));
+    }
+}
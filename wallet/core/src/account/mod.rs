@@ -4,9 +4,11 @@
 //!
 
 pub mod descriptor;
+pub mod htlc;
 pub mod kind;
 pub mod pskb;
 pub mod variants;
+use htlc::{HtlcAudit, HtlcContract, HtlcFunding, HtlcSpend};
 use kaspa_hashes::Hash;
 use kaspa_wallet_pskt::bundle::Bundle;
 pub use kind::*;
@@ -19,16 +21,18 @@ pub use variants::*;
 use crate::derivation::build_derivate_paths;
 use crate::derivation::AddressDerivationManagerTrait;
 use crate::imports::*;
+use crate::signer::{DeviceSigner, ExternalSigner, SignerT};
 use crate::storage::account::AccountSettings;
 use crate::storage::AccountMetadata;
 use crate::storage::{PrvKeyData, PrvKeyDataId};
 use crate::tx::PaymentOutput;
-use crate::tx::{Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer};
+use crate::tx::{Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer, UtxoSelection};
 use crate::utxo::balance::{AtomicBalance, BalanceStrings};
 use crate::utxo::UtxoContextBinding;
 use kaspa_bip32::{ChildNumber, ExtendedPrivateKey, PrivateKey};
 use kaspa_consensus_client::UtxoEntry;
 use kaspa_consensus_client::UtxoEntryReference;
+use kaspa_consensus_core::tx::TransactionOutpoint;
 use kaspa_wallet_keys::derivation::gen0::WalletDerivationManagerV0;
 use workflow_core::abortable::Abortable;
 
@@ -169,6 +173,59 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok(())
     }
 
+    /// Outpoints excluded from transaction generation
+    fn frozen_utxos(&self) -> Vec<TransactionOutpoint> {
+        self.context().settings.frozen_utxos.clone()
+    }
+
+    /// Freezes (or unfreezes) `outpoints`, preventing the account from spending them
+    async fn freeze_utxos(&self, wallet_secret: &Secret, outpoints: &[TransactionOutpoint], freeze: bool) -> Result<()> {
+        {
+            let mut context = self.context();
+            let frozen = &mut context.settings.frozen_utxos;
+            if freeze {
+                frozen.extend(outpoints.iter().filter(|outpoint| !frozen.contains(outpoint)).cloned().collect::<Vec<_>>());
+            } else {
+                frozen.retain(|outpoint| !outpoints.contains(outpoint));
+            }
+        }
+
+        let account = self.to_storage()?;
+        self.wallet().store().as_account_store()?.store_single(&account, None).await?;
+
+        self.wallet().store().commit(wallet_secret).await?;
+        Ok(())
+    }
+
+    /// External signer the account is bound to, if any
+    fn external_signer(&self) -> Option<ExternalSigner> {
+        self.context().settings.external_signer.clone()
+    }
+
+    /// Binds the account to an external signer (hardware wallet, HSM, signing process)
+    /// or, with `None`, reverts to signing with the private key data held by the wallet
+    async fn set_external_signer(&self, wallet_secret: &Secret, external_signer: Option<ExternalSigner>) -> Result<()> {
+        self.context().settings.external_signer = external_signer;
+
+        let account = self.to_storage()?;
+        self.wallet().store().as_account_store()?.store_single(&account, None).await?;
+
+        self.wallet().store().commit(wallet_secret).await?;
+        Ok(())
+    }
+
+    /// Creates the transaction signer of the account: the external signer the account
+    /// is bound to, otherwise a [`Signer`] using the decrypted private key data.
+    async fn create_signer(self: Arc<Self>, wallet_secret: Secret, payment_secret: Option<Secret>) -> Result<Arc<dyn SignerT>> {
+        if let Some(external_signer) = self.external_signer() {
+            let device = external_signer.device(self.wallet())?;
+            Ok(Arc::new(DeviceSigner::new(device, Some(self.as_dyn_arc()))))
+        } else {
+            let keydata = self.prv_key_data(wallet_secret).await?;
+            Ok(Arc::new(Signer::new(self.as_dyn_arc(), keydata, payment_secret)))
+        }
+    }
+
     fn get_list_string(&self) -> Result<String> {
         let name = style(self.name_with_id()).blue();
         let balance = self.balance_as_strings(None)?;
@@ -194,7 +251,12 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Err(Error::ResidentAccount)
     }
 
+    /// Decrypts the private key data of the account. Fails for accounts bound to an
+    /// external signer, whose keys must not be used outside of the signer.
     async fn prv_key_data(&self, wallet_secret: Secret) -> Result<PrvKeyData> {
+        if let Some(external_signer) = self.external_signer() {
+            return Err(Error::ExternalSignerKeysRequired(external_signer.to_string()));
+        }
         let prv_key_data_id = self.prv_key_data_id()?;
 
         let keydata = self
@@ -320,8 +382,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         abortable: &Abortable,
         notifier: Option<GenerationNotifier>,
     ) -> Result<(GeneratorSummary, Vec<kaspa_hashes::Hash>)> {
-        let keydata = self.prv_key_data(wallet_secret).await?;
-        let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
+        let signer = self.clone().create_signer(wallet_secret, payment_secret).await?;
         let settings = GeneratorSettings::try_new_with_account(
             self.clone().as_dyn_arc(),
             PaymentDestination::Change,
@@ -334,7 +395,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         let mut stream = generator.stream();
         let mut ids = vec![];
         while let Some(transaction) = stream.try_next().await? {
-            transaction.try_sign()?;
+            transaction.try_sign().await?;
             ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
 
             if let Some(notifier) = notifier.as_ref() {
@@ -354,23 +415,26 @@ pub trait Account: AnySync + Send + Sync + 'static {
         fee_rate: Option<f64>,
         priority_fee_sompi: Fees,
         payload: Option<Vec<u8>>,
+        utxo_selection: Option<UtxoSelection>,
         wallet_secret: Secret,
         payment_secret: Option<Secret>,
         abortable: &Abortable,
         notifier: Option<GenerationNotifier>,
     ) -> Result<(GeneratorSummary, Vec<kaspa_hashes::Hash>)> {
-        let keydata = self.prv_key_data(wallet_secret).await?;
-        let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
+        let signer = self.clone().create_signer(wallet_secret, payment_secret).await?;
 
-        let settings =
+        let mut settings =
             GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, fee_rate, priority_fee_sompi, payload)?;
+        if let Some(utxo_selection) = utxo_selection.as_ref() {
+            settings = settings.utxo_selection(utxo_selection)?;
+        }
 
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
         let mut stream = generator.stream();
         let mut ids = vec![];
         while let Some(transaction) = stream.try_next().await? {
-            transaction.try_sign()?;
+            transaction.try_sign().await?;
             ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
 
             if let Some(notifier) = notifier.as_ref() {
@@ -506,6 +570,104 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok(ids)
     }
 
+    /// Initiate an atomic swap: generate a secret and lock `amount_sompi` in an HTLC redeemable by `counterparty`
+    /// against that secret, refundable to this account once the DAA score reaches `lock_time`.
+    async fn htlc_initiate(
+        self: Arc<Self>,
+        counterparty: Address,
+        amount_sompi: u64,
+        lock_time: u64,
+        fee_rate: Option<f64>,
+        priority_fee_sompi: Fees,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        abortable: &Abortable,
+    ) -> Result<HtlcFunding> {
+        let (secret, secret_hash) = htlc::generate_secret();
+        // The secret is persisted before funding the contract so that it cannot be lost along with the funds
+        let store = self.wallet().store();
+        store.as_htlc_secret_store()?.store(&wallet_secret, HtlcSecret::new(secret_hash, secret)).await?;
+        store.commit(&wallet_secret).await?;
+        let (contract, summary, transaction_ids) = htlc::fund(
+            self.clone().as_dyn_arc(),
+            &counterparty,
+            secret_hash,
+            lock_time,
+            amount_sompi,
+            fee_rate,
+            priority_fee_sompi,
+            wallet_secret,
+            payment_secret,
+            abortable,
+        )
+        .await?;
+        Ok(HtlcFunding { contract, secret: Some(secret), summary, transaction_ids })
+    }
+
+    /// Participate in an atomic swap: lock `amount_sompi` in an HTLC redeemable by `counterparty` against the
+    /// preimage of `secret_hash`, refundable to this account once the DAA score reaches `lock_time`.
+    async fn htlc_participate(
+        self: Arc<Self>,
+        counterparty: Address,
+        secret_hash: [u8; 32],
+        amount_sompi: u64,
+        lock_time: u64,
+        fee_rate: Option<f64>,
+        priority_fee_sompi: Fees,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        abortable: &Abortable,
+    ) -> Result<HtlcFunding> {
+        let (contract, summary, transaction_ids) = htlc::fund(
+            self.clone().as_dyn_arc(),
+            &counterparty,
+            secret_hash,
+            lock_time,
+            amount_sompi,
+            fee_rate,
+            priority_fee_sompi,
+            wallet_secret,
+            payment_secret,
+            abortable,
+        )
+        .await?;
+        Ok(HtlcFunding { contract, secret: None, summary, transaction_ids })
+    }
+
+    /// Load the secret of a swap initiated by this wallet from the wallet storage.
+    async fn htlc_load_secret(self: Arc<Self>, secret_hash: &[u8; 32], wallet_secret: Secret) -> Result<Option<[u8; 32]>> {
+        let secret = self.wallet().store().as_htlc_secret_store()?.load(&wallet_secret, secret_hash).await?;
+        Ok(secret.map(|secret| secret.secret))
+    }
+
+    /// Redeem the funds locked in an HTLC by revealing its secret.
+    async fn htlc_redeem(
+        self: Arc<Self>,
+        contract: &HtlcContract,
+        secret: &[u8],
+        priority_fee_sompi: u64,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+    ) -> Result<Hash> {
+        htlc::spend(self.as_dyn_arc(), contract, HtlcSpend::Redeem { secret }, priority_fee_sompi, wallet_secret, payment_secret).await
+    }
+
+    /// Refund the funds locked in an HTLC once its lock time has been reached.
+    async fn htlc_refund(
+        self: Arc<Self>,
+        contract: &HtlcContract,
+        priority_fee_sompi: u64,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+    ) -> Result<Hash> {
+        htlc::spend(self.as_dyn_arc(), contract, HtlcSpend::Refund, priority_fee_sompi, wallet_secret, payment_secret).await
+    }
+
+    /// Look up the funds locked in an HTLC and whether it can already be refunded.
+    async fn htlc_audit(self: Arc<Self>, contract: HtlcContract) -> Result<HtlcAudit> {
+        htlc::audit(self.as_dyn_arc(), contract).await
+    }
+
     async fn get_utxos(self: Arc<Self>, addresses: Option<Vec<Address>>, min_amount_sompi: Option<u64>) -> Result<Vec<UtxoEntry>> {
         let utxos = self.utxo_context().get_utxos(addresses, min_amount_sompi).await?;
         Ok(utxos)
@@ -524,8 +686,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         notifier: Option<GenerationNotifier>,
         guard: &WalletGuard,
     ) -> Result<(GeneratorSummary, Vec<kaspa_hashes::Hash>)> {
-        let keydata = self.prv_key_data(wallet_secret).await?;
-        let signer = Arc::new(Signer::new(self.clone().as_dyn_arc(), keydata, payment_secret));
+        let signer = self.clone().create_signer(wallet_secret, payment_secret).await?;
 
         let destination_account = self
             .wallet()
@@ -551,7 +712,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         let mut stream = generator.stream();
         let mut ids = vec![];
         while let Some(transaction) = stream.try_next().await? {
-            transaction.try_sign()?;
+            transaction.try_sign().await?;
             ids.push(transaction.try_submit(&self.wallet().rpc_api()).await?);
 
             if let Some(notifier) = notifier.as_ref() {
@@ -569,9 +730,14 @@ pub trait Account: AnySync + Send + Sync + 'static {
         fee_rate: Option<f64>,
         priority_fee_sompi: Fees,
         payload: Option<Vec<u8>>,
+        utxo_selection: Option<UtxoSelection>,
         abortable: &Abortable,
     ) -> Result<GeneratorSummary> {
-        let settings = GeneratorSettings::try_new_with_account(self.as_dyn_arc(), destination, fee_rate, priority_fee_sompi, payload)?;
+        let mut settings =
+            GeneratorSettings::try_new_with_account(self.as_dyn_arc(), destination, fee_rate, priority_fee_sompi, payload)?;
+        if let Some(utxo_selection) = utxo_selection.as_ref() {
+            settings = settings.utxo_selection(utxo_selection)?;
+        }
 
         let generator = Generator::try_new(settings, None, Some(abortable))?;
 
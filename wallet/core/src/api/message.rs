@@ -6,7 +6,8 @@
 //!
 
 use crate::imports::*;
-use crate::tx::{Fees, GeneratorSummary, PaymentDestination};
+use crate::signer::ExternalSigner;
+use crate::tx::{Fees, GeneratorSummary, PaymentDestination, PaymentOutputs, PayoutJob, UtxoSelection};
 use kaspa_addresses::Address;
 use kaspa_consensus_client::{TransactionOutpoint, UtxoEntry};
 use kaspa_rpc_core::RpcFeerateBucket;
@@ -503,6 +504,7 @@ pub struct AccountsSendRequest {
     pub fee_rate: Option<f64>,
     pub priority_fee_sompi: Fees,
     pub payload: Option<Vec<u8>>,
+    pub utxo_selection: Option<UtxoSelection>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -677,6 +679,7 @@ pub struct AccountsEstimateRequest {
     pub fee_rate: Option<f64>,
     pub priority_fee_sompi: Fees,
     pub payload: Option<Vec<u8>>,
+    pub utxo_selection: Option<UtxoSelection>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -785,6 +788,147 @@ pub struct TransactionsReplaceMetadataRequest {
 #[serde(rename_all = "camelCase")]
 pub struct TransactionsReplaceMetadataResponse {}
 
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionsExportRequest {
+    pub account_id: AccountId,
+    pub network_id: NetworkId,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionsExportResponse {
+    pub csv: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsEnumerateRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsEnumerateResponse {
+    pub labels: Vec<Label>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsUpdateRequest {
+    pub wallet_secret: Secret,
+    pub labels: Vec<Label>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsUpdateResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsImportRequest {
+    pub wallet_secret: Secret,
+    /// BIP-329 JSON Lines
+    pub data: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsImportResponse {
+    pub imported: u64,
+    pub skipped: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsExportRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelsExportResponse {
+    /// BIP-329 JSON Lines
+    pub data: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPlansCreateRequest {
+    pub wallet_secret: Secret,
+    pub account_id: AccountId,
+    pub name: Option<String>,
+    pub outputs: PaymentOutputs,
+    pub interval: PaymentInterval,
+    /// Maximum fee rate in sompi per gram of mass
+    pub max_fee_rate: Option<f64>,
+    /// Number of payments, `None` for an open-ended plan
+    pub runs: Option<u32>,
+    /// Unix time in milliseconds or DAA score (depending on the interval)
+    /// of the first payment, `None` to pay immediately.
+    pub start: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPlansCreateResponse {
+    pub plan: PaymentPlan,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPlansEnumerateRequest {
+    /// Only list the plans of the given account
+    pub account_id: Option<AccountId>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPlansEnumerateResponse {
+    pub plans: Vec<PaymentPlan>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPlansPauseRequest {
+    pub wallet_secret: Secret,
+    pub plan_id: PaymentPlanId,
+    /// `true` to pause the plan, `false` to resume it
+    pub pause: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPlansPauseResponse {
+    pub plan: PaymentPlan,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPlansCancelRequest {
+    pub wallet_secret: Secret,
+    pub plan_id: PaymentPlanId,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPlansCancelResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentSchedulerStartRequest {
+    pub wallet_secret: Secret,
+    pub payment_secret: Option<Secret>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentSchedulerStartResponse {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentSchedulerStopRequest {}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentSchedulerStopResponse {}
+
 // #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 // #[serde(rename_all = "camelCase")]
 // pub struct TransactionGetRequest {}
@@ -870,3 +1014,64 @@ pub struct AccountsCommitRevealRequest {
 pub struct AccountsCommitRevealResponse {
     pub transaction_ids: Vec<TransactionId>,
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsPayoutEstimateRequest {
+    pub account_id: AccountId,
+    pub outputs: PaymentOutputs,
+    pub fee_rate: Option<f64>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsPayoutEstimateResponse {
+    pub job: PayoutJob,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsPayoutSendRequest {
+    pub wallet_secret: Secret,
+    pub payment_secret: Option<Secret>,
+    pub fee_rate: Option<f64>,
+    pub job: PayoutJob,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsPayoutSendResponse {
+    /// The job updated by this step, to be persisted and supplied to the next call
+    pub job: PayoutJob,
+    /// Error that interrupted the payout, the job can be resumed by supplying it again
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsFreezeUtxosRequest {
+    pub account_id: AccountId,
+    pub wallet_secret: Secret,
+    pub outpoints: Vec<kaspa_consensus_core::tx::TransactionOutpoint>,
+    /// `true` to freeze the outpoints, `false` to unfreeze them
+    pub freeze: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsFreezeUtxosResponse {
+    pub frozen_utxos: Vec<kaspa_consensus_core::tx::TransactionOutpoint>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsSetExternalSignerRequest {
+    pub account_id: AccountId,
+    pub wallet_secret: Secret,
+    /// `None` reverts to signing with the private key data held by the wallet
+    pub external_signer: Option<ExternalSigner>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsSetExternalSignerResponse {}
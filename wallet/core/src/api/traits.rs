@@ -9,6 +9,7 @@
 
 use crate::api::message::*;
 use crate::imports::*;
+use crate::signer::ExternalSigner;
 use crate::storage::{PrvKeyData, PrvKeyDataId, PrvKeyDataInfo, WalletDescriptor};
 use crate::tx::GeneratorSummary;
 use workflow_core::channel::Receiver;
@@ -486,6 +487,61 @@ pub trait WalletApi: Send + Sync + AnySync {
     /// an error.
     async fn accounts_estimate_call(self: Arc<Self>, request: AccountsEstimateRequest) -> Result<AccountsEstimateResponse>;
 
+    /// Wrapper around [`accounts_freeze_utxos_call()`](Self::accounts_freeze_utxos_call)
+    async fn accounts_freeze_utxos(
+        self: Arc<Self>,
+        account_id: AccountId,
+        wallet_secret: Secret,
+        outpoints: Vec<kaspa_consensus_core::tx::TransactionOutpoint>,
+        freeze: bool,
+    ) -> Result<Vec<kaspa_consensus_core::tx::TransactionOutpoint>> {
+        Ok(self
+            .accounts_freeze_utxos_call(AccountsFreezeUtxosRequest { account_id, wallet_secret, outpoints, freeze })
+            .await?
+            .frozen_utxos)
+    }
+    /// Freeze or unfreeze account UTXOs. Frozen UTXOs are persisted in the account
+    /// settings and are never spent by the transaction generator. Returns an
+    /// [`AccountsFreezeUtxosResponse`] containing the resulting list of frozen outpoints.
+    async fn accounts_freeze_utxos_call(self: Arc<Self>, request: AccountsFreezeUtxosRequest) -> Result<AccountsFreezeUtxosResponse>;
+
+    /// Wrapper around [`accounts_set_external_signer_call()`](Self::accounts_set_external_signer_call)
+    async fn accounts_set_external_signer(
+        self: Arc<Self>,
+        account_id: AccountId,
+        wallet_secret: Secret,
+        external_signer: Option<ExternalSigner>,
+    ) -> Result<()> {
+        self.accounts_set_external_signer_call(AccountsSetExternalSignerRequest { account_id, wallet_secret, external_signer })
+            .await?;
+        Ok(())
+    }
+    /// Bind an account to an external signer (hardware wallet, HSM or signing process)
+    /// or revert it to signing with the private key data held by the wallet. Accounts
+    /// bound to an external signer do not decrypt private key data when sending funds.
+    async fn accounts_set_external_signer_call(
+        self: Arc<Self>,
+        request: AccountsSetExternalSignerRequest,
+    ) -> Result<AccountsSetExternalSignerResponse>;
+
+    /// Packs a list of payouts into mass-compliant batch transactions and estimates
+    /// their fees. Returns an [`AccountsPayoutEstimateResponse`] containing the
+    /// [`PayoutJob`](crate::tx::PayoutJob) to be supplied to
+    /// [`accounts_payout_send_call()`](Self::accounts_payout_send_call).
+    async fn accounts_payout_estimate_call(
+        self: Arc<Self>,
+        request: AccountsPayoutEstimateRequest,
+    ) -> Result<AccountsPayoutEstimateResponse>;
+
+    /// Performs the next step of a [`PayoutJob`](crate::tx::PayoutJob) and returns the
+    /// updated job, which the caller must persist before supplying it to the next call,
+    /// until the job is completed. A step either prepares the next batch, recording its
+    /// signed transactions in the job, or submits the batch prepared by the previous call,
+    /// posting [`Events::PayoutProgress`](crate::events::Events::PayoutProgress).
+    /// A job interrupted by an error is returned along with the error and can be resumed
+    /// by supplying it again: batches already submitted are skipped.
+    async fn accounts_payout_send_call(self: Arc<Self>, request: AccountsPayoutSendRequest) -> Result<AccountsPayoutSendResponse>;
+
     /// Wrapper around [`fee_rate_estimate_call()`](Self::fee_rate_estimate_call)
     async fn fee_rate_estimate(self: Arc<Self>) -> Result<FeeRateEstimateResponse> {
         Ok(self.fee_rate_estimate_call(FeeRateEstimateRequest {}).await?)
@@ -556,6 +612,86 @@ pub trait WalletApi: Send + Sync + AnySync {
         request: TransactionsReplaceMetadataRequest,
     ) -> Result<TransactionsReplaceMetadataResponse>;
 
+    /// Export the transaction history of an account as CSV suitable for accounting.
+    /// Wrapper around [`transactions_export_call()`](Self::transactions_export_call).
+    async fn transactions_export(self: Arc<Self>, account_id: AccountId, network_id: NetworkId) -> Result<String> {
+        Ok(self.transactions_export_call(TransactionsExportRequest { account_id, network_id }).await?.csv)
+    }
+
+    /// Export the transaction history of an account as CSV. Each row carries
+    /// the signed amount, the fee, the transaction label and the transaction note.
+    async fn transactions_export_call(self: Arc<Self>, request: TransactionsExportRequest) -> Result<TransactionsExportResponse>;
+
+    /// Wrapper around [`labels_enumerate_call()`](Self::labels_enumerate_call)
+    async fn labels_enumerate(self: Arc<Self>) -> Result<Vec<Label>> {
+        Ok(self.labels_enumerate_call(LabelsEnumerateRequest {}).await?.labels)
+    }
+
+    /// Enumerate the labels attached to addresses, outputs and transactions.
+    async fn labels_enumerate_call(self: Arc<Self>, request: LabelsEnumerateRequest) -> Result<LabelsEnumerateResponse>;
+
+    /// Wrapper around [`labels_update_call()`](Self::labels_update_call)
+    async fn labels_update(self: Arc<Self>, wallet_secret: Secret, labels: Vec<Label>) -> Result<()> {
+        self.labels_update_call(LabelsUpdateRequest { wallet_secret, labels }).await?;
+        Ok(())
+    }
+
+    /// Insert or replace labels. Supply a label with an empty text to remove it.
+    async fn labels_update_call(self: Arc<Self>, request: LabelsUpdateRequest) -> Result<LabelsUpdateResponse>;
+
+    /// Import labels from BIP-329 JSON Lines. Records of types not supported
+    /// by the wallet are skipped and reported in the response.
+    async fn labels_import_call(self: Arc<Self>, request: LabelsImportRequest) -> Result<LabelsImportResponse>;
+
+    /// Export all labels as BIP-329 JSON Lines.
+    async fn labels_export_call(self: Arc<Self>, request: LabelsExportRequest) -> Result<LabelsExportResponse>;
+
+    /// Create a scheduled or recurring payment plan. Payments are executed
+    /// while the payment scheduler is running, see
+    /// [`payment_scheduler_start_call()`](Self::payment_scheduler_start_call).
+    async fn payment_plans_create_call(self: Arc<Self>, request: PaymentPlansCreateRequest) -> Result<PaymentPlansCreateResponse>;
+
+    /// Wrapper around [`payment_plans_enumerate_call()`](Self::payment_plans_enumerate_call)
+    async fn payment_plans_enumerate(self: Arc<Self>, account_id: Option<AccountId>) -> Result<Vec<PaymentPlan>> {
+        Ok(self.payment_plans_enumerate_call(PaymentPlansEnumerateRequest { account_id }).await?.plans)
+    }
+
+    /// Enumerate payment plans, optionally filtered by account.
+    async fn payment_plans_enumerate_call(
+        self: Arc<Self>,
+        request: PaymentPlansEnumerateRequest,
+    ) -> Result<PaymentPlansEnumerateResponse>;
+
+    /// Wrapper around [`payment_plans_pause_call()`](Self::payment_plans_pause_call)
+    async fn payment_plans_pause(self: Arc<Self>, wallet_secret: Secret, plan_id: PaymentPlanId, pause: bool) -> Result<PaymentPlan> {
+        Ok(self.payment_plans_pause_call(PaymentPlansPauseRequest { wallet_secret, plan_id, pause }).await?.plan)
+    }
+
+    /// Pause or resume a payment plan.
+    async fn payment_plans_pause_call(self: Arc<Self>, request: PaymentPlansPauseRequest) -> Result<PaymentPlansPauseResponse>;
+
+    /// Wrapper around [`payment_plans_cancel_call()`](Self::payment_plans_cancel_call)
+    async fn payment_plans_cancel(self: Arc<Self>, wallet_secret: Secret, plan_id: PaymentPlanId) -> Result<()> {
+        self.payment_plans_cancel_call(PaymentPlansCancelRequest { wallet_secret, plan_id }).await?;
+        Ok(())
+    }
+
+    /// Cancel (remove) a payment plan.
+    async fn payment_plans_cancel_call(self: Arc<Self>, request: PaymentPlansCancelRequest) -> Result<PaymentPlansCancelResponse>;
+
+    /// Start executing due payment plans. The scheduler retains the supplied
+    /// secrets in memory until it is stopped or the wallet is closed.
+    async fn payment_scheduler_start_call(
+        self: Arc<Self>,
+        request: PaymentSchedulerStartRequest,
+    ) -> Result<PaymentSchedulerStartResponse>;
+
+    /// Stop executing payment plans and discard the scheduler secrets.
+    async fn payment_scheduler_stop_call(
+        self: Arc<Self>,
+        request: PaymentSchedulerStopRequest,
+    ) -> Result<PaymentSchedulerStopResponse>;
+
     // TODO
     async fn address_book_enumerate_call(
         self: Arc<Self>,
@@ -114,6 +114,21 @@ impl WalletApi for WalletClient {
         FeeRatePollerDisable,
         AccountsCommitReveal,
         AccountsCommitRevealManual,
+        AccountsFreezeUtxos,
+        AccountsSetExternalSigner,
+        AccountsPayoutEstimate,
+        AccountsPayoutSend,
+        TransactionsExport,
+        LabelsEnumerate,
+        LabelsUpdate,
+        LabelsImport,
+        LabelsExport,
+        PaymentPlansCreate,
+        PaymentPlansEnumerate,
+        PaymentPlansPause,
+        PaymentPlansCancel,
+        PaymentSchedulerStart,
+        PaymentSchedulerStop,
     ]}
 }
 
@@ -200,6 +215,21 @@ impl WalletServer {
         FeeRatePollerDisable,
         AccountsCommitReveal,
         AccountsCommitRevealManual,
+        AccountsFreezeUtxos,
+        AccountsSetExternalSigner,
+        AccountsPayoutEstimate,
+        AccountsPayoutSend,
+        TransactionsExport,
+        LabelsEnumerate,
+        LabelsUpdate,
+        LabelsImport,
+        LabelsExport,
+        PaymentPlansCreate,
+        PaymentPlansEnumerate,
+        PaymentPlansPause,
+        PaymentPlansCancel,
+        PaymentSchedulerStart,
+        PaymentSchedulerStop,
     ]}
 }
 
@@ -286,6 +286,9 @@ pub enum Error {
     #[error(transparent)]
     MultisigCreateError(#[from] kaspa_txscript::MultisigCreateError),
 
+    #[error(transparent)]
+    CovenantError(#[from] kaspa_txscript::CovenantError),
+
     #[error(transparent)]
     TxScriptError(#[from] kaspa_txscript_errors::TxScriptError),
 
@@ -342,6 +345,15 @@ pub enum Error {
     #[error(transparent)]
     Pskt(#[from] kaspa_wallet_pskt::error::Error),
 
+    #[error("External signer: {0}")]
+    ExternalSigner(String),
+
+    #[error("Signer device '{0}' is not registered")]
+    SignerDeviceNotFound(String),
+
+    #[error("This operation requires the account private keys, which are held by its external signer ({0})")]
+    ExternalSignerKeysRequired(String),
+
     #[error("Error generating pending transaction from PSKT: {0}")]
     PendingTransactionFromPSKTError(String),
 
@@ -374,6 +386,57 @@ pub enum Error {
 
     #[error("Failed to merge bundles")]
     CommitRevealBundleMergeError,
+
+    #[error("The script is not an HTLC contract")]
+    HtlcInvalidContract,
+
+    #[error("No funds are locked in the HTLC contract {0}")]
+    HtlcNotFunded(String),
+
+    #[error("The secret does not match the HTLC secret hash")]
+    HtlcSecretMismatch,
+
+    #[error("The HTLC contract is locked until DAA score {0} (current DAA score is {1})")]
+    HtlcRefundLocked(u64, u64),
+
+    #[error("HTLC parties must use Schnorr public key addresses, got {0}")]
+    HtlcUnsupportedAddress(String),
+
+    #[error("The transaction does not reveal the secret of the HTLC contract {0}")]
+    HtlcSecretNotRevealed(String),
+
+    #[error("The HTLC lock time {0} must precede the initiator contract lock time {1} by at least {2} DAA")]
+    HtlcUnsafeLockTime(u64, u64, u64),
+
+    #[error("Input index {0} is out of range")]
+    CovenantInputOutOfRange(usize),
+
+    #[error("Input {0} has no UTXO entry")]
+    CovenantMissingUtxoEntry(usize),
+
+    #[error("Input {0} does not spend the covenant")]
+    CovenantInputMismatch(usize),
+
+    #[error("Input {0} sequence must be at least {1}")]
+    CovenantSequenceLocked(usize, u64),
+
+    #[error("The transaction lock time must be at least {0}")]
+    CovenantLockTimeNotReached(u64),
+
+    #[error("Input {0} sequence must not be finalized for the lock time to apply")]
+    CovenantFinalizedSequence(usize),
+
+    #[error("The withdrawal exceeds the limit of {0} sompi")]
+    CovenantWithdrawalLimitExceeded(u64),
+
+    #[error("Output {0} must return the change to the covenant")]
+    CovenantMissingChange(usize),
+
+    #[error("The key is not an escrow party")]
+    EscrowUnknownParty,
+
+    #[error("The escrow must be released by two distinct parties")]
+    EscrowDuplicateParty,
 }
 
 impl From<Aborted> for Error {
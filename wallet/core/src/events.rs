@@ -6,7 +6,8 @@
 
 use crate::api::message::FeeRateEstimateBucket;
 use crate::imports::*;
-use crate::storage::{Hint, PrvKeyDataInfo, StorageDescriptor, TransactionRecord, WalletDescriptor};
+use crate::storage::{Hint, PaymentPlanId, PrvKeyDataInfo, StorageDescriptor, TransactionRecord, WalletDescriptor};
+use crate::tx::PayoutBatch;
 use crate::utxo::context::UtxoContextId;
 use transaction::TransactionRecordNotification;
 
@@ -235,6 +236,40 @@ pub enum Events {
         normal: FeeRateEstimateBucket,
         low: FeeRateEstimateBucket,
     },
+    /// A batch of a [`PayoutJob`](crate::tx::PayoutJob) has been submitted.
+    /// `index` is the position of the batch in the job.
+    #[serde(rename_all = "camelCase")]
+    PayoutProgress {
+        account_id: AccountId,
+        index: u32,
+        batch: PayoutBatch,
+        submitted: u32,
+        batches: u32,
+    },
+    /// A scheduled payment has been submitted to the network.
+    #[serde(rename_all = "camelCase")]
+    PaymentPlanExecuted {
+        plan_id: PaymentPlanId,
+        account_id: AccountId,
+        transaction_ids: Vec<TransactionId>,
+        amount: u64,
+        fees: u64,
+    },
+    /// A scheduled payment has been postponed because the network
+    /// fee rate exceeds the maximum fee rate of the payment plan.
+    #[serde(rename_all = "camelCase")]
+    PaymentPlanDeferred {
+        plan_id: PaymentPlanId,
+        account_id: AccountId,
+        fee_rate: f64,
+    },
+    /// A scheduled payment has failed and will be retried.
+    #[serde(rename_all = "camelCase")]
+    PaymentPlanFailed {
+        plan_id: PaymentPlanId,
+        account_id: AccountId,
+        message: String,
+    },
     /// A general wallet framework error, emitted when an unexpected
     /// error occurs within the wallet framework.
     Error {
@@ -300,6 +335,10 @@ pub enum EventKind {
     Balance,
     Metrics,
     FeeRate,
+    PayoutProgress,
+    PaymentPlanExecuted,
+    PaymentPlanDeferred,
+    PaymentPlanFailed,
     Error,
 }
 
@@ -338,6 +377,10 @@ impl From<&Events> for EventKind {
             Events::Balance { .. } => EventKind::Balance,
             Events::Metrics { .. } => EventKind::Metrics,
             Events::FeeRate { .. } => EventKind::FeeRate,
+            Events::PayoutProgress { .. } => EventKind::PayoutProgress,
+            Events::PaymentPlanExecuted { .. } => EventKind::PaymentPlanExecuted,
+            Events::PaymentPlanDeferred { .. } => EventKind::PaymentPlanDeferred,
+            Events::PaymentPlanFailed { .. } => EventKind::PaymentPlanFailed,
             Events::Error { .. } => EventKind::Error,
         }
     }
@@ -379,6 +422,10 @@ impl FromStr for EventKind {
             "balance" => Ok(EventKind::Balance),
             "metrics" => Ok(EventKind::Metrics),
             "fee-rate" => Ok(EventKind::FeeRate),
+            "payout-progress" => Ok(EventKind::PayoutProgress),
+            "payment-plan-executed" => Ok(EventKind::PaymentPlanExecuted),
+            "payment-plan-deferred" => Ok(EventKind::PaymentPlanDeferred),
+            "payment-plan-failed" => Ok(EventKind::PaymentPlanFailed),
             "error" => Ok(EventKind::Error),
             _ => Err(Error::custom("Invalid event kind")),
         }
@@ -428,6 +475,10 @@ impl std::fmt::Display for EventKind {
             EventKind::Balance => "balance",
             EventKind::Metrics => "metrics",
             EventKind::FeeRate => "fee-rate",
+            EventKind::PayoutProgress => "payout-progress",
+            EventKind::PaymentPlanExecuted => "payment-plan-executed",
+            EventKind::PaymentPlanDeferred => "payment-plan-deferred",
+            EventKind::PaymentPlanFailed => "payment-plan-failed",
             EventKind::Error => "error",
         };
 
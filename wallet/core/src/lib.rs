@@ -90,6 +90,7 @@ pub mod result;
 pub mod rpc;
 pub mod serializer;
 pub mod settings;
+pub mod signer;
 pub mod storage;
 pub mod tx;
 pub mod utils;
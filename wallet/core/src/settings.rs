@@ -23,6 +23,8 @@ pub enum WalletSettings {
     Server,
     #[describe("Wallet storage or file name (default 'kaspa')")]
     Wallet,
+    #[describe("Signing processes registered as signer devices (id: command)")]
+    Signers,
 }
 
 #[async_trait]
@@ -0,0 +1,202 @@
+//!
+//! Signing through devices that hold the account keys (hardware wallets, HSMs,
+//! external processes), using PSKT as the exchange format.
+//!
+
+use crate::derivation::build_derivate_paths;
+use crate::imports::*;
+use crate::signer::SignerT;
+use kaspa_bip32::{ChildNumber, KeyFingerprint};
+use kaspa_consensus_core::hashing::sighash::{calc_schnorr_signature_hash, SigHashReusedValuesUnsync};
+use kaspa_consensus_core::hashing::sighash_type::SIG_HASH_ALL;
+use kaspa_consensus_core::tx::{SignableTransaction, VerifiableTransaction};
+use kaspa_txscript::extract_script_pub_key_address;
+use kaspa_txscript::opcodes::codes::OpData65;
+use kaspa_txscript::script_class::ScriptClass;
+use kaspa_wallet_pskt::prelude::{Inner, KeySource, Signature, Signer, Version, PSKT};
+use secp256k1::{Message, Parity, PublicKey, XOnlyPublicKey};
+use std::iter;
+
+/// A device holding private keys outside of the wallet.
+#[async_trait]
+pub trait SignerDevice: Send + Sync + 'static {
+    /// Returns the PSKT with partial signatures added for its inputs. Inputs carry
+    /// BIP32 derivation hints when the signing account is derivation capable.
+    async fn sign(&self, pskt: PSKT<Signer>) -> Result<PSKT<Signer>>;
+}
+
+/// [`SignerT`] implementation delegating signatures to a [`SignerDevice`].
+///
+/// Signatures returned by the device are verified against the transaction
+/// before they are applied, so a faulty device cannot alter the transaction.
+/// Only P2PK (Schnorr) inputs are supported.
+pub struct DeviceSigner {
+    device: Arc<dyn SignerDevice>,
+    account: Option<Arc<dyn Account>>,
+}
+
+impl DeviceSigner {
+    pub fn new(device: Arc<dyn SignerDevice>, account: Option<Arc<dyn Account>>) -> Self {
+        Self { device, account }
+    }
+
+    /// Converts the transaction to a PSKT preserving the fields covered by the signature hash
+    fn to_pskt(&self, transaction: &SignableTransaction) -> Result<Inner> {
+        let mut inner = Inner::try_from((transaction.tx.clone(), transaction.as_verifiable().populated_inputs().collect::<Vec<_>>()))?;
+        inner.global.tx_version = transaction.tx.version;
+        inner.global.fallback_lock_time = Some(transaction.tx.lock_time);
+        if !transaction.tx.payload.is_empty() {
+            inner.global.version = Version::One;
+            inner.global.payload = Some(transaction.tx.payload.clone());
+        }
+
+        let key_sources = self.key_sources(transaction)?;
+        for ((input, transaction_input), key_source) in inner.inputs.iter_mut().zip(transaction.tx.inputs.iter()).zip(key_sources) {
+            input.sequence = Some(transaction_input.sequence);
+            if let Some((public_key, key_source)) = key_source {
+                input.bip32_derivations.insert(public_key, Some(key_source));
+            }
+        }
+
+        Ok(inner)
+    }
+
+    /// BIP32 derivation hints of the transaction inputs, allowing devices to locate
+    /// signing keys by path. The master key fingerprint is unknown to the wallet and
+    /// is left empty.
+    fn key_sources(&self, transaction: &SignableTransaction) -> Result<Vec<Option<(PublicKey, KeySource)>>> {
+        let no_hints = || vec![None; transaction.tx.inputs.len()];
+        let Some(account) = self.account.as_ref() else {
+            return Ok(no_hints());
+        };
+        let Ok(derivation) = account.clone().as_derivation_capable() else {
+            return Ok(no_hints());
+        };
+
+        let prefix = account.wallet().network_id()?.into();
+        let addresses = transaction
+            .entries
+            .iter()
+            .map(|entry| entry.as_ref().and_then(|entry| extract_script_pub_key_address(&entry.script_public_key, prefix).ok()))
+            .collect::<Vec<_>>();
+        let (receive_path, change_path) =
+            build_derivate_paths(&derivation.account_kind(), derivation.account_index(), derivation.cosigner_index())?;
+        let (receive, change) = derivation.derivation().addresses_indexes(&addresses.iter().flatten().collect::<Vec<_>>())?;
+
+        let mut paths = HashMap::new();
+        for (addresses, path) in [(receive, &receive_path), (change, &change_path)] {
+            for (address, index) in addresses {
+                let mut derivation_path = path.clone();
+                derivation_path.push(ChildNumber::new(index, false)?);
+                paths.insert(address, derivation_path);
+            }
+        }
+
+        Ok(addresses
+            .iter()
+            .zip(transaction.entries.iter())
+            .map(|(address, entry)| {
+                let derivation_path = paths.get(address.as_ref()?)?;
+                let public_key = schnorr_public_key(&entry.as_ref()?.script_public_key)?;
+                let key_source = KeySource::new(KeyFingerprint::default(), derivation_path.clone());
+                Some((PublicKey::from_x_only_public_key(public_key, Parity::Even), key_source))
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl SignerT for DeviceSigner {
+    async fn try_sign(&self, mut transaction: SignableTransaction, _addresses: &[Address]) -> Result<SignableTransaction> {
+        let signed = self.device.sign(PSKT::from(self.to_pskt(&transaction)?)).await?;
+
+        let signature_scripts = {
+            let verifiable = transaction.as_verifiable();
+            let reused_values = SigHashReusedValuesUnsync::new();
+            verifiable
+                .populated_inputs()
+                .enumerate()
+                .map(|(index, (input, entry))| {
+                    let signed_input = signed
+                        .inputs
+                        .get(index)
+                        .filter(|signed_input| signed_input.previous_outpoint == input.previous_outpoint)
+                        .ok_or_else(|| Error::ExternalSigner(format!("input {index} does not match the transaction")))?;
+                    let public_key = schnorr_public_key(&entry.script_public_key)
+                        .ok_or_else(|| Error::ExternalSigner(format!("input {index} is not a P2PK (Schnorr) input")))?;
+                    let signature = signed_input
+                        .partial_sigs
+                        .iter()
+                        .find_map(|(key, signature)| match signature {
+                            Signature::Schnorr(signature) if key.x_only_public_key().0 == public_key => Some(*signature),
+                            _ => None,
+                        })
+                        .ok_or_else(|| Error::ExternalSigner(format!("input {index} was not signed")))?;
+
+                    let hash = calc_schnorr_signature_hash(&verifiable, index, SIG_HASH_ALL, &reused_values);
+                    let message = Message::from_digest_slice(hash.as_bytes().as_slice())?;
+                    signature
+                        .verify(&message, &public_key)
+                        .map_err(|_| Error::ExternalSigner(format!("invalid signature for input {index}")))?;
+
+                    Ok(iter::once(OpData65).chain(signature.serialize()).chain([SIG_HASH_ALL.to_u8()]).collect::<Vec<u8>>())
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        for (input, signature_script) in transaction.tx.inputs.iter_mut().zip(signature_scripts) {
+            input.signature_script = signature_script;
+        }
+
+        Ok(transaction)
+    }
+}
+
+/// Public key of a P2PK (Schnorr) script
+fn schnorr_public_key(script_public_key: &ScriptPublicKey) -> Option<XOnlyPublicKey> {
+    match ScriptClass::from_script(script_public_key) {
+        ScriptClass::PubKey => XOnlyPublicKey::from_slice(&script_public_key.script()[1..33]).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::MockDevice;
+    use kaspa_consensus_core::subnets::SubnetworkId;
+    use kaspa_consensus_core::tx::{Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry};
+    use kaspa_txscript::pay_to_address_script;
+
+    fn unsigned_transaction(public_key: &XOnlyPublicKey) -> SignableTransaction {
+        let address = Address::new(Prefix::Testnet, kaspa_addresses::Version::PubKey, &public_key.serialize());
+        let script_public_key = pay_to_address_script(&address);
+        let inputs = (0..2)
+            .map(|index| TransactionInput::new(TransactionOutpoint::new(TransactionId::from_bytes([index; 32]), 0), vec![], 0, 1))
+            .collect();
+        let entries = (0..2).map(|_| UtxoEntry::new(100_000_000, script_public_key.clone(), 0, false)).collect();
+        let outputs = vec![TransactionOutput::new(150_000_000, script_public_key)];
+        let transaction = Transaction::new(0, inputs, outputs, 0, SubnetworkId::default(), 0, vec![]);
+        SignableTransaction::with_entries(transaction, entries)
+    }
+
+    #[tokio::test]
+    async fn test_device_signer() -> Result<()> {
+        let secret_key = secp256k1::SecretKey::from_slice(&[0x11; 32])?;
+        let (public_key, _) = secret_key.x_only_public_key(secp256k1::SECP256K1);
+
+        let device = Arc::new(MockDevice::new(&[secret_key]));
+        let signer = DeviceSigner::new(device.clone(), None);
+        let signed = signer.try_sign(unsigned_transaction(&public_key), &[]).await?;
+        assert_eq!(device.requests(), 1);
+        assert!(signed.tx.inputs.iter().all(|input| input.signature_script.len() == 66));
+        assert!(kaspa_consensus_core::sign::verify(&signed.as_verifiable()).is_ok());
+
+        // the device does not hold the key of the inputs
+        let other_key = secp256k1::SecretKey::from_slice(&[0x22; 32])?;
+        let signer = DeviceSigner::new(Arc::new(MockDevice::new(&[other_key])), None);
+        assert!(signer.try_sign(unsigned_transaction(&public_key), &[]).await.is_err());
+
+        Ok(())
+    }
+}
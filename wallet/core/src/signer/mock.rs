@@ -0,0 +1,59 @@
+//!
+//! Mock hardware signing device.
+//!
+
+use crate::imports::*;
+use crate::signer::SignerDevice;
+use kaspa_consensus_core::hashing::sighash::{calc_schnorr_signature_hash, SigHashReusedValuesUnsync};
+use kaspa_consensus_core::tx::VerifiableTransaction;
+use kaspa_wallet_pskt::prelude::{SignInputOk, Signature, Signer, PSKT};
+use secp256k1::{Keypair, Message};
+
+/// In-memory stand-in for a hardware wallet, intended for tests and for developing
+/// device integrations. It signs every input locked to one of its keys and refuses
+/// to sign transactions spending other inputs, like a device would.
+pub struct MockDevice {
+    keys: Vec<Keypair>,
+    requests: AtomicUsize,
+}
+
+impl MockDevice {
+    pub fn new(keys: &[secp256k1::SecretKey]) -> Self {
+        let keys = keys.iter().map(|key| Keypair::from_secret_key(secp256k1::SECP256K1, key)).collect();
+        Self { keys, requests: AtomicUsize::new(0) }
+    }
+
+    /// Number of signing requests received by the device
+    pub fn requests(&self) -> usize {
+        self.requests.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait]
+impl SignerDevice for MockDevice {
+    async fn sign(&self, pskt: PSKT<Signer>) -> Result<PSKT<Signer>> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let pskt = pskt.pass_signature_sync(|tx, sighash| -> std::result::Result<Vec<SignInputOk>, String> {
+            tx.as_verifiable()
+                .populated_inputs()
+                .enumerate()
+                .map(|(index, (_, entry))| {
+                    let script = entry.script_public_key.script();
+                    let keypair = self
+                        .keys
+                        .iter()
+                        .find(|keypair| script.get(1..33) == Some(keypair.x_only_public_key().0.serialize().as_slice()))
+                        .ok_or_else(|| format!("no key for input {index}"))?;
+                    let hash = calc_schnorr_signature_hash(&tx.as_verifiable(), index, sighash[index], &reused_values);
+                    let message = Message::from_digest_slice(hash.as_bytes().as_slice()).map_err(|err| err.to_string())?;
+                    let signature = keypair.sign_schnorr(message);
+                    Ok(SignInputOk { signature: Signature::Schnorr(signature), pub_key: keypair.public_key(), key_source: None })
+                })
+                .collect()
+        });
+
+        pskt.map_err(Error::ExternalSigner)
+    }
+}
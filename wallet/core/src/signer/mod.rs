@@ -0,0 +1,60 @@
+//!
+//! Pluggable transaction signers.
+//!
+//! Transactions produced by the [`Generator`](crate::tx::Generator) are signed
+//! through the [`SignerT`] trait. The following backends are available:
+//!
+//! - [`Signer`] signs with keys derived from the decrypted [`PrvKeyData`] held in memory.
+//! - [`DeviceSigner`] hands each transaction as a PSKT to a [`SignerDevice`] (hardware
+//!   wallet, HSM, external process) and applies the signatures it returns.
+//!
+//! An account is marked as using an external signer by storing an [`ExternalSigner`]
+//! descriptor in its settings (see [`Account::set_external_signer`]). The descriptor only
+//! names the device: the device itself is registered with the wallet by the application
+//! (see [`Wallet::register_signer_device`]), signing processes are configured in the local
+//! [`WalletSettings`](crate::settings::WalletSettings) rather than in the wallet file.
+//! Such accounts never decrypt private key data: operations that require the private
+//! keys fail with [`Error::ExternalSignerKeysRequired`].
+//!
+
+pub mod device;
+pub mod mock;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod process;
+
+pub use crate::tx::generator::signer::{KeydataSigner, Signer, SignerT};
+pub use device::{DeviceSigner, SignerDevice};
+pub use mock::MockDevice;
+#[cfg(not(target_arch = "wasm32"))]
+pub use process::{ProcessDevice, SignerProcess};
+
+use crate::imports::*;
+use std::fmt::{Display, Formatter};
+
+/// External signer an account is bound to, persisted in the account settings.
+///
+/// Only the identifier of the device is persisted, so that the wallet file never
+/// determines which program is executed on the host.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "kebab-case", tag = "kind")]
+pub enum ExternalSigner {
+    /// Device registered with [`Wallet::register_signer_device`] under this identifier
+    Device { id: String },
+}
+
+impl ExternalSigner {
+    /// Resolves the [`SignerDevice`] that signs for this descriptor
+    pub fn device(&self, wallet: &Wallet) -> Result<Arc<dyn SignerDevice>> {
+        match self {
+            ExternalSigner::Device { id } => wallet.signer_device(id).ok_or_else(|| Error::SignerDeviceNotFound(id.clone())),
+        }
+    }
+}
+
+impl Display for ExternalSigner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExternalSigner::Device { id } => write!(f, "device '{id}'"),
+        }
+    }
+}
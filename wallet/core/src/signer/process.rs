@@ -0,0 +1,162 @@
+//!
+//! Signing through an external process.
+//!
+
+use crate::imports::*;
+use crate::signer::SignerDevice;
+use kaspa_wallet_pskt::prelude::{Bundle, Signer, PSKT};
+use std::io::{Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Default time a signing process is given to return the signatures, leaving
+/// room for the user to confirm the transaction on a hardware wallet
+pub const DEFAULT_SIGNER_PROCESS_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Signing process configured in the local [`WalletSettings`](crate::settings::WalletSettings)
+/// and registered with the wallet as a [`ProcessDevice`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignerProcess {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Timeout in seconds, defaults to [`DEFAULT_SIGNER_PROCESS_TIMEOUT`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+impl SignerProcess {
+    pub fn device(&self) -> ProcessDevice {
+        let timeout = self.timeout.map(Duration::from_secs).unwrap_or(DEFAULT_SIGNER_PROCESS_TIMEOUT);
+        ProcessDevice::new(&self.command, &self.args).with_timeout(timeout)
+    }
+}
+
+impl std::fmt::Display for SignerProcess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.args.is_empty() {
+            write!(f, "{}", self.command)
+        } else {
+            write!(f, "{} {}", self.command, self.args.join(" "))
+        }
+    }
+}
+
+/// Signs with an external executable, such as a hardware wallet bridge or an HSM
+/// client. For each transaction the process receives a serialized single-PSKT
+/// [`Bundle`] on stdin and is expected to write the bundle with partial signatures
+/// to stdout and exit with a zero status. A process that does not exit within the
+/// timeout is killed.
+pub struct ProcessDevice {
+    command: String,
+    args: Vec<String>,
+    timeout: Duration,
+}
+
+impl ProcessDevice {
+    pub fn new(command: &str, args: &[String]) -> Self {
+        Self { command: command.to_string(), args: args.to_vec(), timeout: DEFAULT_SIGNER_PROCESS_TIMEOUT }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+/// Outcome of a signing process run
+enum ProcessOutput {
+    Exited { success: bool, status: String, stdout: Vec<u8>, stderr: Vec<u8> },
+    TimedOut,
+}
+
+fn run(command: String, args: Vec<String>, request: String, timeout: Duration) -> std::io::Result<ProcessOutput> {
+    let mut child = Command::new(command).args(args).stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+    // the pipes are served concurrently so that a process that does not read its input
+    // or writes a lot of output cannot block the wallet past the timeout
+    let mut stdin = child.stdin.take().expect("piped stdin");
+    // stdin is closed when dropped, signalling the end of the request
+    std::thread::spawn(move || stdin.write_all(request.as_bytes()));
+    let stdout = drain(child.stdout.take().expect("piped stdout"));
+    let stderr = drain(child.stderr.take().expect("piped stderr"));
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            kill(&mut child);
+            return Ok(ProcessOutput::TimedOut);
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    Ok(ProcessOutput::Exited { success: status.success(), status: status.to_string(), stdout, stderr })
+}
+
+fn drain<R: Read + Send + 'static>(mut reader: R) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = vec![];
+        reader.read_to_end(&mut buffer).ok();
+        buffer
+    })
+}
+
+fn kill(child: &mut Child) {
+    child.kill().ok();
+    child.wait().ok();
+}
+
+#[async_trait]
+impl SignerDevice for ProcessDevice {
+    async fn sign(&self, pskt: PSKT<Signer>) -> Result<PSKT<Signer>> {
+        let request = String::try_from(Bundle::from(pskt))?;
+
+        let (command, args, timeout) = (self.command.clone(), self.args.clone(), self.timeout);
+        let output = async_std::task::spawn_blocking(move || run(command, args, request, timeout))
+            .await
+            .map_err(|err| Error::ExternalSigner(format!("unable to run '{}': {err}", self.command)))?;
+
+        let stdout = match output {
+            ProcessOutput::TimedOut => {
+                return Err(Error::ExternalSigner(format!(
+                    "'{}' did not respond within {} seconds",
+                    self.command,
+                    self.timeout.as_secs()
+                )));
+            }
+            ProcessOutput::Exited { success: false, status, stderr, .. } => {
+                let stderr = String::from_utf8_lossy(&stderr);
+                return Err(Error::ExternalSigner(format!("'{}' failed ({status}): {}", self.command, stderr.trim())));
+            }
+            ProcessOutput::Exited { stdout, .. } => stdout,
+        };
+
+        let Bundle(mut inners) = Bundle::try_from(String::from_utf8_lossy(&stdout).trim())?;
+        match (inners.pop(), inners.is_empty()) {
+            (Some(inner), true) => Ok(PSKT::from(inner)),
+            _ => Err(Error::ExternalSigner(format!("'{}' must return a bundle with a single PSKT", self.command))),
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_timeout() {
+        let started = Instant::now();
+        let output = run("sleep".to_string(), vec!["10".to_string()], String::new(), Duration::from_millis(200)).unwrap();
+        assert!(matches!(output, ProcessOutput::TimedOut));
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        let output = run("cat".to_string(), vec![], "request".to_string(), Duration::from_secs(10)).unwrap();
+        assert!(matches!(output, ProcessOutput::Exited { success: true, stdout, .. } if stdout == b"request"));
+    }
+}
@@ -3,8 +3,10 @@
 //!
 
 use crate::imports::*;
+use crate::signer::ExternalSigner;
+use kaspa_consensus_core::tx::TransactionOutpoint;
 
-const ACCOUNT_SETTINGS_VERSION: u32 = 0;
+const ACCOUNT_SETTINGS_VERSION: u32 = 2;
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -13,6 +15,12 @@ pub struct AccountSettings {
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<Vec<u8>>,
+    /// Outpoints excluded from transaction generation (coin control)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub frozen_utxos: Vec<TransactionOutpoint>,
+    /// Signer holding the account keys outside of the wallet (hardware wallet, HSM, signing process)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_signer: Option<ExternalSigner>,
 }
 
 impl BorshSerialize for AccountSettings {
@@ -20,6 +28,8 @@ impl BorshSerialize for AccountSettings {
         BorshSerialize::serialize(&ACCOUNT_SETTINGS_VERSION, writer)?;
         BorshSerialize::serialize(&self.name, writer)?;
         BorshSerialize::serialize(&self.meta, writer)?;
+        BorshSerialize::serialize(&self.frozen_utxos, writer)?;
+        BorshSerialize::serialize(&self.external_signer, writer)?;
 
         Ok(())
     }
@@ -27,11 +37,13 @@ impl BorshSerialize for AccountSettings {
 
 impl BorshDeserialize for AccountSettings {
     fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> IoResult<Self> {
-        let _version: u32 = BorshDeserialize::deserialize_reader(reader)?;
+        let version: u32 = BorshDeserialize::deserialize_reader(reader)?;
         let name = BorshDeserialize::deserialize_reader(reader)?;
         let meta = BorshDeserialize::deserialize_reader(reader)?;
+        let frozen_utxos = if version > 0 { BorshDeserialize::deserialize_reader(reader)? } else { vec![] };
+        let external_signer = if version > 1 { BorshDeserialize::deserialize_reader(reader)? } else { None };
 
-        Ok(Self { name, meta })
+        Ok(Self { name, meta, frozen_utxos, external_signer })
     }
 }
 
@@ -0,0 +1,42 @@
+//!
+//! Secrets of the HTLC atomic swaps initiated by the wallet.
+//!
+//! Secrets are kept in the encrypted wallet payload and remain encrypted with the wallet
+//! secret in memory, the same way as private key data.
+//!
+
+use crate::imports::*;
+use kaspa_txscript::covenants::HTLC_PREIMAGE_LEN;
+
+/// Secret of an initiated swap, identified by its hash
+#[derive(Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct HtlcSecret {
+    pub secret_hash: [u8; 32],
+    pub secret: [u8; HTLC_PREIMAGE_LEN],
+}
+
+impl HtlcSecret {
+    pub fn new(secret_hash: [u8; 32], secret: [u8; HTLC_PREIMAGE_LEN]) -> Self {
+        Self { secret_hash, secret }
+    }
+}
+
+impl std::fmt::Debug for HtlcSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HtlcSecret").field("secret_hash", &self.secret_hash.as_slice().to_hex()).finish_non_exhaustive()
+    }
+}
+
+impl Zeroize for HtlcSecret {
+    fn zeroize(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl Drop for HtlcSecret {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for HtlcSecret {}
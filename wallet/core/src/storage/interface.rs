@@ -111,6 +111,31 @@ pub trait AddressBookStore: Send + Sync {
     }
 }
 
+#[async_trait]
+pub trait LabelStore: Send + Sync {
+    async fn load(&self) -> Result<Vec<Label>>;
+    async fn load_single(&self, reference: &LabelRef) -> Result<Option<Label>>;
+    /// Inserts or replaces labels; labels with an empty text are removed.
+    async fn store(&self, labels: Vec<Label>) -> Result<()>;
+}
+
+#[async_trait]
+pub trait PaymentPlanStore: Send + Sync {
+    async fn load(&self) -> Result<Vec<PaymentPlan>>;
+    async fn load_single(&self, id: &PaymentPlanId) -> Result<Option<PaymentPlan>>;
+    /// Inserts or replaces a payment plan.
+    async fn store(&self, plan: PaymentPlan) -> Result<()>;
+    async fn remove(&self, id: &PaymentPlanId) -> Result<()>;
+}
+
+#[async_trait]
+pub trait HtlcSecretStore: Send + Sync {
+    async fn load(&self, wallet_secret: &Secret, secret_hash: &[u8; 32]) -> Result<Option<HtlcSecret>>;
+    /// Inserts or replaces the secret matching the same hash.
+    async fn store(&self, wallet_secret: &Secret, secret: HtlcSecret) -> Result<()>;
+    async fn remove(&self, wallet_secret: &Secret, secret_hash: &[u8; 32]) -> Result<()>;
+}
+
 pub struct TransactionRangeResult {
     pub transactions: Vec<Arc<TransactionRecord>>,
     pub total: u64,
@@ -248,6 +273,9 @@ pub trait Interface: Send + Sync + AnySync {
     fn as_account_store(&self) -> Result<Arc<dyn AccountStore>>;
     fn as_address_book_store(&self) -> Result<Arc<dyn AddressBookStore>>;
     fn as_transaction_record_store(&self) -> Result<Arc<dyn TransactionRecordStore>>;
+    fn as_label_store(&self) -> Result<Arc<dyn LabelStore>>;
+    fn as_payment_plan_store(&self) -> Result<Arc<dyn PaymentPlanStore>>;
+    fn as_htlc_secret_store(&self) -> Result<Arc<dyn HtlcSecretStore>>;
 }
 
 downcast_sync!(dyn Interface);
@@ -0,0 +1,188 @@
+//!
+//! User labels attached to addresses, outputs and transactions.
+//!
+//! Labels serialize as [BIP-329](https://github.com/bitcoin/bips/blob/master/bip-0329.mediawiki)
+//! records, allowing them to be exported and imported as JSON Lines.
+//!
+
+use crate::imports::*;
+use kaspa_consensus_core::tx::{TransactionId, TransactionOutpoint};
+use std::fmt::{Display, Formatter};
+
+/// Object a [`Label`] is attached to.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum LabelRef {
+    Address(Address),
+    Output(TransactionOutpoint),
+    Transaction(TransactionId),
+}
+
+impl LabelRef {
+    /// BIP-329 record type
+    pub fn kind(&self) -> &'static str {
+        match self {
+            LabelRef::Address(_) => "addr",
+            LabelRef::Output(_) => "output",
+            LabelRef::Transaction(_) => "tx",
+        }
+    }
+
+    /// Creates a reference from a BIP-329 record type and reference string.
+    /// Returns `None` for record types not supported by the wallet.
+    pub fn try_from_record(kind: &str, reference: &str) -> Result<Option<Self>> {
+        let label_ref = match kind {
+            "addr" => LabelRef::Address(Address::try_from(reference)?),
+            "output" => {
+                let (transaction_id, index) =
+                    reference.split_once(':').ok_or_else(|| Error::custom(format!("Invalid output reference '{reference}'")))?;
+                let transaction_id = TransactionId::from_hex(transaction_id)?;
+                let index = index.parse::<u32>().map_err(|_| Error::custom(format!("Invalid output reference '{reference}'")))?;
+                LabelRef::Output(TransactionOutpoint::new(transaction_id, index))
+            }
+            "tx" => LabelRef::Transaction(TransactionId::from_hex(reference)?),
+            _ => return Ok(None),
+        };
+        Ok(Some(label_ref))
+    }
+}
+
+impl Display for LabelRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabelRef::Address(address) => write!(f, "{address}"),
+            LabelRef::Output(outpoint) => write!(f, "{}:{}", outpoint.transaction_id, outpoint.index),
+            LabelRef::Transaction(transaction_id) => write!(f, "{transaction_id}"),
+        }
+    }
+}
+
+impl FromStr for LabelRef {
+    type Err = Error;
+
+    /// Detects the reference type from its format: an address,
+    /// a `<transaction id>:<index>` outpoint or a transaction id.
+    fn from_str(s: &str) -> Result<Self> {
+        if let Ok(address) = Address::try_from(s) {
+            Ok(LabelRef::Address(address))
+        } else if s.contains(':') {
+            Ok(LabelRef::try_from_record("output", s)?.unwrap())
+        } else {
+            Ok(LabelRef::try_from_record("tx", s)?.unwrap())
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Bip329Record {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(rename = "ref")]
+    reference: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    origin: Option<String>,
+}
+
+/// User label; serializes as a BIP-329 record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(try_from = "Bip329Record", into = "Bip329Record")]
+pub struct Label {
+    pub reference: LabelRef,
+    pub label: String,
+    /// Optional key origin (descriptor) as defined by BIP-329
+    pub origin: Option<String>,
+}
+
+impl Label {
+    pub fn new(reference: LabelRef, label: String) -> Self {
+        Self { reference, label, origin: None }
+    }
+}
+
+impl From<Label> for Bip329Record {
+    fn from(label: Label) -> Self {
+        Bip329Record {
+            kind: label.reference.kind().to_string(),
+            reference: label.reference.to_string(),
+            label: Some(label.label),
+            origin: label.origin,
+        }
+    }
+}
+
+impl TryFrom<Bip329Record> for Label {
+    type Error = Error;
+    fn try_from(record: Bip329Record) -> Result<Self> {
+        let reference = LabelRef::try_from_record(&record.kind, &record.reference)?
+            .ok_or_else(|| Error::custom(format!("Unsupported label type '{}'", record.kind)))?;
+        Ok(Label { reference, label: record.label.unwrap_or_default(), origin: record.origin })
+    }
+}
+
+/// Serializes labels as BIP-329 JSON Lines
+pub fn export_bip329(labels: &[Label]) -> Result<String> {
+    let mut jsonl = String::new();
+    for label in labels {
+        jsonl.push_str(&serde_json::to_string(label)?);
+        jsonl.push('\n');
+    }
+    Ok(jsonl)
+}
+
+/// Parses BIP-329 JSON Lines. As required by BIP-329, records of types
+/// not supported by the wallet (`pubkey`, `input`, `xpub`) and records
+/// without a label are skipped; their count is returned with the labels.
+pub fn import_bip329(jsonl: &str) -> Result<(Vec<Label>, usize)> {
+    let mut labels = Vec::new();
+    let mut skipped = 0;
+    for (n, line) in jsonl.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let record: Bip329Record =
+            serde_json::from_str(line).map_err(|err| Error::custom(format!("Invalid label record at line {}: {err}", n + 1)))?;
+        match (LabelRef::try_from_record(&record.kind, &record.reference)?, record.label) {
+            (Some(reference), Some(label)) if !label.is_empty() => labels.push(Label { reference, label, origin: record.origin }),
+            _ => skipped += 1,
+        }
+    }
+    Ok((labels, skipped))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bip329_roundtrip() -> Result<()> {
+        let address = "kaspatest:qz7ulu4c25dh7fzec9zjyrmlhnkzrg4wmf89q7gzr3gfrsj3uz6xjceef60sd";
+        let txid = "41ea01e8e8a2a15fdf4ab4e6c5d9aa4d7e1c0c3e2b1a09f8e7d6c5b4a3928170";
+        let jsonl = format!(
+            r#"{{"type":"addr","ref":"{address}","label":"Donations"}}
+{{"type":"tx","ref":"{txid}","label":"Invoice #12","origin":"wpkh([d34db33f/84'/0'/0'])"}}
+{{"type":"output","ref":"{txid}:1","label":"Change"}}
+{{"type":"xpub","ref":"xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8","label":"Cold"}}
+{{"type":"tx","ref":"{txid}"}}
+"#
+        );
+
+        let (labels, skipped) = import_bip329(&jsonl)?;
+        assert_eq!(skipped, 2);
+        assert_eq!(labels.len(), 3);
+        assert_eq!(labels[0].reference, LabelRef::Address(Address::try_from(address)?));
+        assert_eq!(labels[1].origin.as_deref(), Some("wpkh([d34db33f/84'/0'/0'])"));
+        assert_eq!(labels[2].reference, LabelRef::Output(TransactionOutpoint::new(TransactionId::from_hex(txid)?, 1)));
+
+        let exported = export_bip329(&labels)?;
+        let (reimported, skipped) = import_bip329(&exported)?;
+        assert_eq!(skipped, 0);
+        assert_eq!(reimported, labels);
+
+        assert_eq!(format!("{txid}:1").parse::<LabelRef>()?, labels[2].reference);
+        assert_eq!(address.parse::<LabelRef>()?, labels[0].reference);
+
+        Ok(())
+    }
+}
@@ -16,6 +16,9 @@ pub struct Cache {
     pub accounts: Collection<AccountId, AccountStorage>,
     pub metadata: Collection<AccountId, AccountMetadata>,
     pub address_book: Vec<AddressBookEntry>,
+    pub labels: Vec<Label>,
+    pub payment_plans: Vec<PaymentPlan>,
+    pub htlc_secrets: Encrypted,
 }
 
 impl Cache {
@@ -34,8 +37,23 @@ impl Cache {
         let user_hint = wallet.user_hint;
         let wallet_title = wallet.title;
         let address_book = payload.0.address_book.into_iter().collect();
+        let labels = payload.0.labels;
+        let payment_plans = payload.0.payment_plans;
+        let htlc_secrets = Decrypted::new(payload.0.htlc_secrets).encrypt(secret, encryption_kind)?;
 
-        Ok(Cache { wallet_title, user_hint, encryption_kind, prv_key_data, prv_key_data_info, accounts, metadata, address_book })
+        Ok(Cache {
+            wallet_title,
+            user_hint,
+            encryption_kind,
+            prv_key_data,
+            prv_key_data_info,
+            accounts,
+            metadata,
+            address_book,
+            labels,
+            payment_plans,
+            htlc_secrets,
+        })
     }
 
     pub fn from_payload(
@@ -53,8 +71,32 @@ impl Cache {
         let accounts: Collection<AccountId, AccountStorage> = payload.accounts.try_into()?;
         let metadata: Collection<AccountId, AccountMetadata> = Collection::default();
         let address_book = payload.address_book.into_iter().collect();
+        let labels = payload.labels;
+        let payment_plans = payload.payment_plans;
+        let htlc_secrets = Decrypted::new(payload.htlc_secrets).encrypt(secret, encryption_kind)?;
+
+        Ok(Cache {
+            wallet_title,
+            user_hint,
+            encryption_kind,
+            prv_key_data,
+            prv_key_data_info,
+            accounts,
+            metadata,
+            address_book,
+            labels,
+            payment_plans,
+            htlc_secrets,
+        })
+    }
 
-        Ok(Cache { wallet_title, user_hint, encryption_kind, prv_key_data, prv_key_data_info, accounts, metadata, address_book })
+    /// Re-encrypts the private key data and the HTLC secrets with a new wallet secret
+    pub fn change_secret(&mut self, old_secret: &Secret, new_secret: &Secret) -> Result<()> {
+        let prv_key_data: Decrypted<PrvKeyDataMap> = self.prv_key_data.decrypt(old_secret)?;
+        let htlc_secrets: Decrypted<Vec<HtlcSecret>> = self.htlc_secrets.decrypt(old_secret)?;
+        self.prv_key_data.replace(prv_key_data.encrypt(new_secret, self.encryption_kind)?);
+        self.htlc_secrets.replace(htlc_secrets.encrypt(new_secret, self.encryption_kind)?);
+        Ok(())
     }
 
     pub fn to_wallet(
@@ -67,7 +109,13 @@ impl Cache {
         let accounts: Vec<AccountStorage> = (&self.accounts).try_into()?;
         let metadata: Vec<AccountMetadata> = (&self.metadata).try_into()?;
         let address_book = self.address_book.clone();
-        let payload = Payload::new(prv_key_data, accounts, address_book);
+        let htlc_secrets: Decrypted<Vec<HtlcSecret>> = self.htlc_secrets.decrypt(secret)?;
+        let payload = Payload {
+            labels: self.labels.clone(),
+            payment_plans: self.payment_plans.clone(),
+            htlc_secrets: htlc_secrets.unwrap(),
+            ..Payload::new(prv_key_data, accounts, address_book)
+        };
         let payload = Decrypted::new(payload).encrypt(secret, self.encryption_kind)?;
 
         Ok(WalletStorage {
@@ -6,7 +6,8 @@
 
 use crate::imports::*;
 use crate::storage::interface::{
-    AddressBookStore, CreateArgs, OpenArgs, StorageDescriptor, StorageStream, WalletDescriptor, WalletExportOptions,
+    AddressBookStore, CreateArgs, HtlcSecretStore, LabelStore, OpenArgs, PaymentPlanStore, StorageDescriptor, StorageStream,
+    WalletDescriptor, WalletExportOptions,
 };
 use crate::storage::local::cache::*;
 use crate::storage::local::streams::*;
@@ -155,18 +156,14 @@ impl LocalStoreInner {
         match &*self.storage() {
             Store::Resident => {
                 let mut cache = self.cache.write().unwrap();
-                let old_prv_key_data: Decrypted<PrvKeyDataMap> = cache.prv_key_data.decrypt(old_secret)?;
-                let new_prv_key_data = Decrypted::new(old_prv_key_data.unwrap()).encrypt(new_secret, cache.encryption_kind)?;
-                cache.prv_key_data.replace(new_prv_key_data);
+                cache.change_secret(old_secret, new_secret)?;
 
                 Ok(())
             }
             Store::Storage(ref storage) => {
                 let wallet = {
                     let mut cache = self.cache.write().unwrap();
-                    let old_prv_key_data: Decrypted<PrvKeyDataMap> = cache.prv_key_data.decrypt(old_secret)?;
-                    let new_prv_key_data = Decrypted::new(old_prv_key_data.unwrap()).encrypt(new_secret, cache.encryption_kind)?;
-                    cache.prv_key_data.replace(new_prv_key_data);
+                    cache.change_secret(old_secret, new_secret)?;
 
                     cache.to_wallet(None, new_secret)?
                 };
@@ -338,6 +335,18 @@ impl Interface for LocalStore {
         Ok(self.inner()?.transactions.clone())
     }
 
+    fn as_label_store(&self) -> Result<Arc<dyn LabelStore>> {
+        Ok(self.inner()?)
+    }
+
+    fn as_payment_plan_store(&self) -> Result<Arc<dyn PaymentPlanStore>> {
+        Ok(self.inner()?)
+    }
+
+    fn as_htlc_secret_store(&self) -> Result<Arc<dyn HtlcSecretStore>> {
+        Ok(self.inner()?)
+    }
+
     fn descriptor(&self) -> Option<WalletDescriptor> {
         self.inner.lock().unwrap().as_ref().map(|inner| inner.descriptor())
     }
@@ -626,3 +635,88 @@ impl AddressBookStore for LocalStoreInner {
         Ok(matches)
     }
 }
+
+#[async_trait]
+impl LabelStore for LocalStoreInner {
+    async fn load(&self) -> Result<Vec<Label>> {
+        Ok(self.cache.read().unwrap().labels.clone())
+    }
+
+    async fn load_single(&self, reference: &LabelRef) -> Result<Option<Label>> {
+        Ok(self.cache.read().unwrap().labels.iter().find(|label| &label.reference == reference).cloned())
+    }
+
+    async fn store(&self, labels: Vec<Label>) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        for label in labels {
+            let existing = cache.labels.iter().position(|existing| existing.reference == label.reference);
+            match (existing, label.label.is_empty()) {
+                (Some(index), true) => {
+                    cache.labels.remove(index);
+                }
+                (Some(index), false) => cache.labels[index] = label,
+                (None, true) => {}
+                (None, false) => cache.labels.push(label),
+            }
+        }
+        self.set_modified(true);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PaymentPlanStore for LocalStoreInner {
+    async fn load(&self) -> Result<Vec<PaymentPlan>> {
+        Ok(self.cache.read().unwrap().payment_plans.clone())
+    }
+
+    async fn load_single(&self, id: &PaymentPlanId) -> Result<Option<PaymentPlan>> {
+        Ok(self.cache.read().unwrap().payment_plans.iter().find(|plan| &plan.id == id).cloned())
+    }
+
+    async fn store(&self, plan: PaymentPlan) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        match cache.payment_plans.iter_mut().find(|existing| existing.id == plan.id) {
+            Some(existing) => *existing = plan,
+            None => cache.payment_plans.push(plan),
+        }
+        self.set_modified(true);
+        Ok(())
+    }
+
+    async fn remove(&self, id: &PaymentPlanId) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        cache.payment_plans.retain(|plan| &plan.id != id);
+        self.set_modified(true);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HtlcSecretStore for LocalStoreInner {
+    async fn load(&self, wallet_secret: &Secret, secret_hash: &[u8; 32]) -> Result<Option<HtlcSecret>> {
+        let secrets: Decrypted<Vec<HtlcSecret>> = self.cache.read().unwrap().htlc_secrets.decrypt(wallet_secret)?;
+        Ok(secrets.iter().find(|secret| &secret.secret_hash == secret_hash).cloned())
+    }
+
+    async fn store(&self, wallet_secret: &Secret, secret: HtlcSecret) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        let encryption_kind = cache.encryption_kind;
+        let mut secrets: Decrypted<Vec<HtlcSecret>> = cache.htlc_secrets.decrypt(wallet_secret)?;
+        secrets.retain(|existing| existing.secret_hash != secret.secret_hash);
+        secrets.push(secret);
+        cache.htlc_secrets.replace(secrets.encrypt(wallet_secret, encryption_kind)?);
+        self.set_modified(true);
+        Ok(())
+    }
+
+    async fn remove(&self, wallet_secret: &Secret, secret_hash: &[u8; 32]) -> Result<()> {
+        let mut cache = self.cache.write().unwrap();
+        let encryption_kind = cache.encryption_kind;
+        let mut secrets: Decrypted<Vec<HtlcSecret>> = cache.htlc_secrets.decrypt(wallet_secret)?;
+        secrets.retain(|existing| &existing.secret_hash != secret_hash);
+        cache.htlc_secrets.replace(secrets.encrypt(wallet_secret, encryption_kind)?);
+        self.set_modified(true);
+        Ok(())
+    }
+}
@@ -3,7 +3,7 @@
 //!
 
 use crate::imports::*;
-use crate::storage::{AddressBookEntry, PrvKeyData, PrvKeyDataId};
+use crate::storage::{AddressBookEntry, HtlcSecret, Label, PaymentPlan, PrvKeyData, PrvKeyDataId};
 use kaspa_bip32::Mnemonic;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
@@ -13,14 +13,28 @@ pub struct Payload {
     pub accounts: Vec<AccountStorage>,
     pub address_book: Vec<AddressBookEntry>,
     pub encrypt_transactions: Option<EncryptionKind>,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    #[serde(default)]
+    pub payment_plans: Vec<PaymentPlan>,
+    #[serde(default)]
+    pub htlc_secrets: Vec<HtlcSecret>,
 }
 
 impl Payload {
     const STORAGE_MAGIC: u32 = 0x41544144;
-    const STORAGE_VERSION: u32 = 0;
+    const STORAGE_VERSION: u32 = 3;
 
     pub fn new(prv_key_data: Vec<PrvKeyData>, accounts: Vec<AccountStorage>, address_book: Vec<AddressBookEntry>) -> Self {
-        Self { prv_key_data, accounts, address_book, encrypt_transactions: None }
+        Self {
+            prv_key_data,
+            accounts,
+            address_book,
+            encrypt_transactions: None,
+            labels: vec![],
+            payment_plans: vec![],
+            htlc_secrets: vec![],
+        }
     }
 }
 
@@ -29,6 +43,7 @@ impl ZeroizeOnDrop for Payload {}
 impl Zeroize for Payload {
     fn zeroize(&mut self) {
         self.prv_key_data.zeroize();
+        self.htlc_secrets.zeroize();
     }
 }
 
@@ -61,6 +76,9 @@ impl BorshSerialize for Payload {
         BorshSerialize::serialize(&self.accounts, writer)?;
         BorshSerialize::serialize(&self.address_book, writer)?;
         BorshSerialize::serialize(&self.encrypt_transactions, writer)?;
+        BorshSerialize::serialize(&self.labels, writer)?;
+        BorshSerialize::serialize(&self.payment_plans, writer)?;
+        BorshSerialize::serialize(&self.htlc_secrets, writer)?;
 
         Ok(())
     }
@@ -68,14 +86,17 @@ impl BorshSerialize for Payload {
 
 impl BorshDeserialize for Payload {
     fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> IoResult<Self> {
-        let StorageHeader { version: _, .. } =
+        let StorageHeader { version, .. } =
             StorageHeader::deserialize_reader(reader)?.try_magic(Self::STORAGE_MAGIC)?.try_version(Self::STORAGE_VERSION)?;
         let prv_key_data = BorshDeserialize::deserialize_reader(reader)?;
         let accounts = BorshDeserialize::deserialize_reader(reader)?;
         let address_book = BorshDeserialize::deserialize_reader(reader)?;
         let encrypt_transactions = BorshDeserialize::deserialize_reader(reader)?;
+        let labels = if version > 0 { BorshDeserialize::deserialize_reader(reader)? } else { vec![] };
+        let payment_plans = if version > 1 { BorshDeserialize::deserialize_reader(reader)? } else { vec![] };
+        let htlc_secrets = if version > 2 { BorshDeserialize::deserialize_reader(reader)? } else { vec![] };
 
-        Ok(Self { prv_key_data, accounts, address_book, encrypt_transactions })
+        Ok(Self { prv_key_data, accounts, address_book, encrypt_transactions, labels, payment_plans, htlc_secrets })
     }
 }
 
@@ -90,6 +111,12 @@ mod tests {
         let guard = StorageGuard::new(&storable_in);
         let _storable_out = guard.validate()?;
 
+        let storable_in = Payload { htlc_secrets: vec![HtlcSecret::new([1; 32], [2; 32])], ..Payload::new(vec![], vec![], vec![]) };
+        let guard = StorageGuard::new(&storable_in);
+        let storable_out = guard.validate()?;
+        assert_eq!(storable_out.htlc_secrets[0].secret_hash, [1; 32]);
+        assert_eq!(storable_out.htlc_secrets[0].secret, [2; 32]);
+
         Ok(())
     }
 }
@@ -8,11 +8,14 @@ pub mod account;
 pub mod address;
 pub mod binding;
 pub mod hint;
+pub mod htlc;
 pub mod id;
 pub mod interface;
 pub mod keydata;
+pub mod label;
 pub mod local;
 pub mod metadata;
+pub mod payment_plan;
 pub mod storable;
 pub mod transaction;
 
@@ -20,13 +23,17 @@ pub use account::{AccountSettings, AccountStorable, AccountStorage};
 pub use address::AddressBookEntry;
 pub use binding::{Binding, BindingT};
 pub use hint::Hint;
+pub use htlc::HtlcSecret;
 pub use id::IdT;
 pub use interface::{
-    AccountStore, Interface, PrvKeyDataStore, StorageDescriptor, TransactionRecordStore, WalletDescriptor, WalletExportOptions,
+    AccountStore, HtlcSecretStore, Interface, LabelStore, PaymentPlanStore, PrvKeyDataStore, StorageDescriptor,
+    TransactionRecordStore, WalletDescriptor, WalletExportOptions,
 };
 pub use keydata::{AssocPrvKeyDataIds, PrvKeyData, PrvKeyDataId, PrvKeyDataInfo, PrvKeyDataMap, PrvKeyDataPayload};
+pub use label::{Label, LabelRef};
 pub use local::interface::make_filename;
 pub use metadata::AccountMetadata;
+pub use payment_plan::{PaymentInterval, PaymentPlan, PaymentPlanId};
 pub use storable::Storable;
 pub use transaction::{TransactionData, TransactionId, TransactionKind, TransactionRecord};
 
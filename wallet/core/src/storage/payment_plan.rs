@@ -0,0 +1,320 @@
+//!
+//! Scheduled and recurring payment plans executed by the wallet
+//! [`PaymentScheduler`](crate::wallet::scheduler::PaymentScheduler).
+//!
+
+use crate::imports::*;
+use crate::tx::PaymentOutputs;
+use std::fmt::{Display, Formatter};
+
+/// Random identifier of a [`PaymentPlan`], displayed and serialized as hex.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, BorshSerialize, BorshDeserialize)]
+pub struct PaymentPlanId(pub u64);
+
+impl PaymentPlanId {
+    pub fn new() -> Self {
+        Self(rand::random())
+    }
+}
+
+impl Default for PaymentPlanId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Display for PaymentPlanId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl FromStr for PaymentPlanId {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        u64::from_str_radix(s, 16).map(Self).map_err(|_| Error::custom(format!("Invalid payment plan id '{s}'")))
+    }
+}
+
+impl Serialize for PaymentPlanId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for PaymentPlanId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let s = <String as Deserialize>::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Period between two payments of a [`PaymentPlan`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "kebab-case", tag = "kind", content = "value")]
+pub enum PaymentInterval {
+    /// Wall clock interval in seconds
+    Seconds(u64),
+    /// Interval in DAA score units
+    DaaScore(u64),
+}
+
+impl PaymentInterval {
+    /// Current position on the scale of the interval: unix time in milliseconds or DAA score
+    pub fn now(&self, unixtime_msec: u64, daa_score: u64) -> u64 {
+        match self {
+            PaymentInterval::Seconds(_) => unixtime_msec,
+            PaymentInterval::DaaScore(_) => daa_score,
+        }
+    }
+
+    fn period(&self) -> u64 {
+        match self {
+            PaymentInterval::Seconds(seconds) => seconds * 1000,
+            PaymentInterval::DaaScore(daa_score) => *daa_score,
+        }
+    }
+}
+
+impl Display for PaymentInterval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaymentInterval::Seconds(seconds) => write!(f, "{seconds} sec"),
+            PaymentInterval::DaaScore(daa_score) => write!(f, "{daa_score} DAA"),
+        }
+    }
+}
+
+/// Recurring payment to a fixed set of recipients.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentPlan {
+    pub id: PaymentPlanId,
+    pub account_id: AccountId,
+    pub name: Option<String>,
+    pub outputs: PaymentOutputs,
+    pub interval: PaymentInterval,
+    /// Maximum fee rate in sompi per gram of mass. Payments are
+    /// deferred while the network fee rate estimate is above it.
+    pub max_fee_rate: Option<f64>,
+    /// Number of payments left, `None` for an open-ended plan
+    pub remaining: Option<u32>,
+    /// Unix time in milliseconds or DAA score (depending on the interval) of the next payment
+    pub next: u64,
+    pub paused: bool,
+    /// Number of payments executed so far
+    pub executions: u32,
+    pub last_transaction_id: Option<TransactionId>,
+    /// Final transaction of a payment that was being submitted and is not yet
+    /// accounted for. It is checked against the node before paying again.
+    #[serde(default)]
+    pub pending_transaction_id: Option<TransactionId>,
+}
+
+impl PaymentPlan {
+    const STORAGE_MAGIC: u32 = 0x4e414c50;
+    const STORAGE_VERSION: u32 = 1;
+
+    pub fn new(
+        account_id: AccountId,
+        name: Option<String>,
+        outputs: PaymentOutputs,
+        interval: PaymentInterval,
+        max_fee_rate: Option<f64>,
+        remaining: Option<u32>,
+        next: u64,
+    ) -> Self {
+        Self {
+            id: PaymentPlanId::new(),
+            account_id,
+            name,
+            outputs,
+            interval,
+            max_fee_rate,
+            remaining,
+            next,
+            paused: false,
+            executions: 0,
+            last_transaction_id: None,
+            pending_transaction_id: None,
+        }
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.remaining == Some(0)
+    }
+
+    pub fn is_due(&self, unixtime_msec: u64, daa_score: u64) -> bool {
+        !self.paused && !self.is_completed() && self.interval.now(unixtime_msec, daa_score) >= self.next
+    }
+
+    /// Records a successful payment and schedules the next one. Periods missed
+    /// while the wallet was closed are skipped rather than paid retroactively.
+    pub fn advance(&mut self, transaction_id: Option<TransactionId>, unixtime_msec: u64, daa_score: u64) {
+        let now = self.interval.now(unixtime_msec, daa_score);
+        let period = self.interval.period().max(1);
+        if self.next <= now {
+            self.next += ((now - self.next) / period + 1) * period;
+        }
+        self.remaining = self.remaining.map(|remaining| remaining.saturating_sub(1));
+        self.executions += 1;
+        self.last_transaction_id = transaction_id;
+        self.pending_transaction_id = None;
+    }
+
+    /// Records the final transaction of a payment about to be submitted
+    pub fn begin(&mut self, transaction_id: TransactionId) {
+        self.pending_transaction_id = Some(transaction_id);
+    }
+
+    /// Settles a payment interrupted after [`begin`](Self::begin): the plan is
+    /// advanced if its transaction has reached the network, otherwise the payment
+    /// is still due. Returns the transaction id if the payment was made.
+    pub fn settle(&mut self, submitted: bool, unixtime_msec: u64, daa_score: u64) -> Option<TransactionId> {
+        let transaction_id = self.pending_transaction_id.take()?;
+        if submitted {
+            self.advance(Some(transaction_id), unixtime_msec, daa_score);
+            Some(transaction_id)
+        } else {
+            None
+        }
+    }
+}
+
+impl BorshSerialize for PaymentPlan {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        StorageHeader::new(Self::STORAGE_MAGIC, Self::STORAGE_VERSION).serialize(writer)?;
+        BorshSerialize::serialize(&self.id, writer)?;
+        BorshSerialize::serialize(&self.account_id, writer)?;
+        BorshSerialize::serialize(&self.name, writer)?;
+        BorshSerialize::serialize(&self.outputs, writer)?;
+        BorshSerialize::serialize(&self.interval, writer)?;
+        BorshSerialize::serialize(&self.max_fee_rate, writer)?;
+        BorshSerialize::serialize(&self.remaining, writer)?;
+        BorshSerialize::serialize(&self.next, writer)?;
+        BorshSerialize::serialize(&self.paused, writer)?;
+        BorshSerialize::serialize(&self.executions, writer)?;
+        BorshSerialize::serialize(&self.last_transaction_id, writer)?;
+        BorshSerialize::serialize(&self.pending_transaction_id, writer)?;
+
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for PaymentPlan {
+    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> IoResult<Self> {
+        let StorageHeader { version, .. } =
+            StorageHeader::deserialize_reader(reader)?.try_magic(Self::STORAGE_MAGIC)?.try_version(Self::STORAGE_VERSION)?;
+
+        Ok(Self {
+            id: BorshDeserialize::deserialize_reader(reader)?,
+            account_id: BorshDeserialize::deserialize_reader(reader)?,
+            name: BorshDeserialize::deserialize_reader(reader)?,
+            outputs: BorshDeserialize::deserialize_reader(reader)?,
+            interval: BorshDeserialize::deserialize_reader(reader)?,
+            max_fee_rate: BorshDeserialize::deserialize_reader(reader)?,
+            remaining: BorshDeserialize::deserialize_reader(reader)?,
+            next: BorshDeserialize::deserialize_reader(reader)?,
+            paused: BorshDeserialize::deserialize_reader(reader)?,
+            executions: BorshDeserialize::deserialize_reader(reader)?,
+            last_transaction_id: BorshDeserialize::deserialize_reader(reader)?,
+            pending_transaction_id: if version > 0 { BorshDeserialize::deserialize_reader(reader)? } else { None },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_storage_payment_plan() -> Result<()> {
+        let outputs = PaymentOutputs { outputs: vec![] };
+        let storable_in = PaymentPlan::new(
+            AccountId(kaspa_hashes::Hash::from_bytes([0; 32])),
+            Some("payroll".to_string()),
+            outputs,
+            PaymentInterval::Seconds(60),
+            None,
+            Some(3),
+            0,
+        );
+        let guard = StorageGuard::new(&storable_in);
+        let storable_out = guard.validate()?;
+        assert_eq!(storable_in.id, storable_out.id);
+        assert_eq!(storable_out.interval, PaymentInterval::Seconds(60));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_payment_plan_advance() {
+        let outputs = PaymentOutputs { outputs: vec![] };
+        let mut plan = PaymentPlan::new(
+            AccountId(kaspa_hashes::Hash::from_bytes([0; 32])),
+            None,
+            outputs,
+            PaymentInterval::DaaScore(100),
+            None,
+            Some(2),
+            1000,
+        );
+
+        assert!(!plan.is_due(0, 999));
+        assert!(plan.is_due(0, 1000));
+
+        // executed late: missed periods are skipped
+        plan.advance(None, 0, 1250);
+        assert_eq!(plan.next, 1300);
+        assert_eq!(plan.remaining, Some(1));
+
+        plan.paused = true;
+        assert!(!plan.is_due(0, 1300));
+        plan.paused = false;
+
+        plan.advance(None, 0, 1300);
+        assert_eq!(plan.next, 1400);
+        assert!(plan.is_completed());
+        assert!(!plan.is_due(0, 1400));
+    }
+
+    #[test]
+    fn test_payment_plan_interrupted() -> Result<()> {
+        let outputs = PaymentOutputs { outputs: vec![] };
+        let mut plan = PaymentPlan::new(
+            AccountId(kaspa_hashes::Hash::from_bytes([0; 32])),
+            None,
+            outputs,
+            PaymentInterval::DaaScore(100),
+            None,
+            None,
+            1000,
+        );
+        let transaction_id = TransactionId::from_u64_word(1);
+
+        // the submission failed or the wallet stopped before the plan was advanced:
+        // the payment is still due and its transaction is kept in storage
+        plan.begin(transaction_id);
+        let mut plan = StorageGuard::new(&plan).validate()?;
+        assert_eq!(plan.pending_transaction_id, Some(transaction_id));
+        assert!(plan.is_due(0, 1000));
+
+        // the transaction never reached the network: the payment is attempted again
+        let mut retry = plan.clone();
+        assert_eq!(retry.settle(false, 0, 1010), None);
+        assert!(retry.is_due(0, 1010));
+        assert_eq!(retry.executions, 0);
+        assert_eq!(retry.pending_transaction_id, None);
+
+        // the transaction was accepted: the plan advances without paying again
+        assert_eq!(plan.settle(true, 0, 1010), Some(transaction_id));
+        assert!(!plan.is_due(0, 1010));
+        assert_eq!(plan.executions, 1);
+        assert_eq!(plan.last_transaction_id, Some(transaction_id));
+        assert_eq!(plan.pending_transaction_id, None);
+        assert_eq!(plan.settle(true, 0, 1010), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+//!
+//! Export of the transaction history as CSV for accounting purposes.
+//!
+
+use crate::imports::*;
+use crate::utils::kaspa_suffix;
+use kaspa_consensus_core::constants::SOMPI_PER_KASPA;
+
+const CSV_HEADER: &str = "date,daa_score,transaction_id,kind,amount,fee,currency,label,note";
+
+/// Produces a CSV document with one row per transaction affecting the account balance.
+///
+/// Amounts are signed and expressed in KAS with 8 decimals. Reorg, stasis and change
+/// records are omitted as they do not represent balance changes. The `label` column is
+/// populated from the wallet transaction labels.
+pub fn transactions_to_csv(transactions: &[Arc<TransactionRecord>], labels: &[Label], network_type: NetworkType) -> String {
+    let currency = kaspa_suffix(&network_type);
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+
+    for record in transactions {
+        let (amount, fee) = match record.transaction_data() {
+            TransactionData::Incoming { .. } | TransactionData::TransferIncoming { .. } => (record.value() as i128, 0),
+            TransactionData::External { .. } => (-(record.value() as i128), 0),
+            TransactionData::Outgoing { fees, .. } | TransactionData::TransferOutgoing { fees, .. } => {
+                (-(record.value() as i128), *fees)
+            }
+            TransactionData::Batch { fees, .. } => (0, *fees),
+            TransactionData::Reorg { .. } | TransactionData::Stasis { .. } | TransactionData::Change { .. } => continue,
+        };
+
+        let date = record
+            .unixtime_msec()
+            .and_then(|msec| chrono::DateTime::from_timestamp_millis(msec as i64))
+            .map(|date| date.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_default();
+        let label = labels
+            .iter()
+            .find(|label| matches!(&label.reference, LabelRef::Transaction(id) if id == record.id()))
+            .map(|label| label.label.as_str())
+            .unwrap_or_default();
+
+        let row = [
+            date,
+            record.block_daa_score().to_string(),
+            record.id().to_string(),
+            record.kind().to_string(),
+            format_amount(amount),
+            format_amount(fee as i128),
+            currency.to_string(),
+            escape(label),
+            escape(record.note.as_deref().unwrap_or_default()),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Formats a sompi amount as KAS without floating point rounding
+fn format_amount(sompi: i128) -> String {
+    let sign = if sompi < 0 { "-" } else { "" };
+    let sompi = sompi.unsigned_abs();
+    let sompi_per_kaspa = SOMPI_PER_KASPA as u128;
+    format!("{sign}{}.{:08}", sompi / sompi_per_kaspa, sompi % sompi_per_kaspa)
+}
+
+/// Quotes a field as per RFC 4180 if it contains a separator, a quote or a line break
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_fields() {
+        assert_eq!(format_amount(1), "0.00000001");
+        assert_eq!(format_amount(-123_456_789_000), "-1234.56789000");
+        assert_eq!(format_amount(0), "0.00000000");
+        assert_eq!(escape("rent"), "rent");
+        assert_eq!(escape("rent, march"), "\"rent, march\"");
+        assert_eq!(escape("the \"big\" one"), "\"the \"\"big\"\" one\"");
+    }
+}
@@ -25,6 +25,7 @@ pub struct RpcCoreMock {
     ctl: RpcCtl,
     core_notifier: Arc<RpcCoreNotifier>,
     _sync_receiver: Receiver<()>,
+    mempool: Mutex<HashMap<RpcTransactionId, RpcMempoolEntry>>,
 }
 
 impl RpcCoreMock {
@@ -41,7 +42,7 @@ impl RpcCoreMock {
             policies,
             Some(sync_sender),
         ));
-        Self { core_notifier, _sync_receiver: sync_receiver, ctl: RpcCtl::new() }
+        Self { core_notifier, _sync_receiver: sync_receiver, ctl: RpcCtl::new(), mempool: Default::default() }
     }
 
     pub fn core_notifier(&self) -> Arc<RpcCoreNotifier> {
@@ -67,6 +68,12 @@ impl RpcCoreMock {
         self.core_notifier.join().await.expect("core notifier shutdown")
     }
 
+    /// Adds a transaction to the mempool served by [`RpcApi::get_mempool_entry_call`]
+    #[allow(dead_code)]
+    pub fn add_mempool_transaction(&self, transaction: &kaspa_consensus_core::tx::Transaction) {
+        self.mempool.lock().unwrap().insert(transaction.id(), RpcMempoolEntry::new(0, transaction.into(), false));
+    }
+
     // ---
 
     pub fn ctl(&self) -> RpcCtl {
@@ -178,9 +185,10 @@ impl RpcApi for RpcCoreMock {
     async fn get_mempool_entry_call(
         &self,
         _connection: Option<&DynRpcConnection>,
-        _request: GetMempoolEntryRequest,
+        request: GetMempoolEntryRequest,
     ) -> RpcResult<GetMempoolEntryResponse> {
-        Err(RpcError::NotImplemented)
+        let mempool_entry = self.mempool.lock().unwrap().get(&request.transaction_id).cloned();
+        mempool_entry.map(GetMempoolEntryResponse::new).ok_or(RpcError::TransactionNotFound(request.transaction_id))
     }
 
     async fn get_mempool_entries_call(
@@ -379,6 +387,62 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_fee_estimate_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetFeeEstimateHistoryRequest,
+    ) -> RpcResult<GetFeeEstimateHistoryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_mempool_info_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMempoolInfoRequest,
+    ) -> RpcResult<GetMempoolInfoResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_mempool_transaction_graph_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMempoolTransactionGraphRequest,
+    ) -> RpcResult<GetMempoolTransactionGraphResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn prioritise_transaction_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: PrioritiseTransactionRequest,
+    ) -> RpcResult<PrioritiseTransactionResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_prioritised_transactions_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetPrioritisedTransactionsRequest,
+    ) -> RpcResult<GetPrioritisedTransactionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_mining_status_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetMiningStatusRequest,
+    ) -> RpcResult<GetMiningStatusResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn reload_mempool_policy_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: ReloadMempoolPolicyRequest,
+    ) -> RpcResult<ReloadMempoolPolicyResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn get_utxo_return_address_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -0,0 +1,303 @@
+//!
+//! Signing of transaction inputs locked by the covenant templates of [`kaspa_txscript::covenants`].
+//!
+//! Each function checks that the input spends the expected covenant and that the transaction
+//! satisfies the lock time preconditions of the spending path before filling the signature script,
+//! so that an unspendable transaction is rejected here rather than by the node.
+//!
+
+use crate::error::Error;
+use crate::result::Result;
+use kaspa_consensus_core::hashing::sighash_type::SIG_HASH_ALL;
+use kaspa_consensus_core::sign::sign_input;
+use kaspa_consensus_core::tx::{ScriptPublicKey, SignableTransaction};
+use kaspa_txscript::covenants::{Escrow, Htlc, RateLimitedSpend, TimelockedVault};
+use kaspa_txscript::{CovenantTemplate, MAX_TX_IN_SEQUENCE_NUM, SEQUENCE_LOCK_TIME_DISABLED, SEQUENCE_LOCK_TIME_MASK};
+
+/// Spends a [`TimelockedVault`] input with the recovery key
+pub fn sign_vault_recovery(
+    tx: &mut SignableTransaction,
+    input_index: usize,
+    vault: &TimelockedVault,
+    private_key: &[u8; 32],
+) -> Result<()> {
+    check_covenant_input(tx, input_index, vault)?;
+    let signature = schnorr_signature(tx, input_index, private_key);
+    tx.tx.inputs[input_index].signature_script = vault.recovery_signature_script(&signature)?;
+    Ok(())
+}
+
+/// Spends a [`TimelockedVault`] input with the owner key once its delay has elapsed
+pub fn sign_vault_owner(
+    tx: &mut SignableTransaction,
+    input_index: usize,
+    vault: &TimelockedVault,
+    private_key: &[u8; 32],
+) -> Result<()> {
+    check_covenant_input(tx, input_index, vault)?;
+    check_relative_lock(tx, input_index, vault.delay)?;
+    let signature = schnorr_signature(tx, input_index, private_key);
+    tx.tx.inputs[input_index].signature_script = vault.owner_signature_script(&signature)?;
+    Ok(())
+}
+
+/// Claims an [`Htlc`] input by revealing the preimage of its payment hash
+pub fn sign_htlc_claim(
+    tx: &mut SignableTransaction,
+    input_index: usize,
+    htlc: &Htlc,
+    private_key: &[u8; 32],
+    preimage: &[u8],
+) -> Result<()> {
+    check_covenant_input(tx, input_index, htlc)?;
+    if Htlc::payment_hash(preimage) != htlc.payment_hash {
+        return Err(Error::HtlcSecretMismatch);
+    }
+    let signature = schnorr_signature(tx, input_index, private_key);
+    tx.tx.inputs[input_index].signature_script = htlc.claim_signature_script(&signature, preimage)?;
+    Ok(())
+}
+
+/// Refunds an [`Htlc`] input once its lock time has been reached
+pub fn sign_htlc_refund(tx: &mut SignableTransaction, input_index: usize, htlc: &Htlc, private_key: &[u8; 32]) -> Result<()> {
+    check_covenant_input(tx, input_index, htlc)?;
+    check_lock_time(tx, input_index, htlc.lock_time)?;
+    let signature = schnorr_signature(tx, input_index, private_key);
+    tx.tx.inputs[input_index].signature_script = htlc.refund_signature_script(&signature)?;
+    Ok(())
+}
+
+/// Withdraws from a [`RateLimitedSpend`] input. Unless the whole balance is withdrawn, the output at
+/// `input_index` must return at least the input amount minus `max_amount` to the covenant
+pub fn sign_rate_limited_spend(
+    tx: &mut SignableTransaction,
+    input_index: usize,
+    covenant: &RateLimitedSpend,
+    private_key: &[u8; 32],
+) -> Result<()> {
+    let (script_public_key, amount) = check_covenant_input(tx, input_index, covenant)?;
+    check_relative_lock(tx, input_index, covenant.period)?;
+    if amount > covenant.max_amount {
+        let change = tx.tx.outputs.get(input_index).filter(|output| output.script_public_key == script_public_key);
+        match change {
+            Some(output) if amount - output.value.min(amount) <= covenant.max_amount => {}
+            Some(_) => return Err(Error::CovenantWithdrawalLimitExceeded(covenant.max_amount)),
+            None => return Err(Error::CovenantMissingChange(input_index)),
+        }
+    }
+    let signature = schnorr_signature(tx, input_index, private_key);
+    tx.tx.inputs[input_index].signature_script = covenant.signature_script(&signature)?;
+    Ok(())
+}
+
+/// Releases an [`Escrow`] input with two of its three keys, in any order
+pub fn sign_escrow_release(
+    tx: &mut SignableTransaction,
+    input_index: usize,
+    escrow: &Escrow,
+    private_keys: [&[u8; 32]; 2],
+) -> Result<()> {
+    check_covenant_input(tx, input_index, escrow)?;
+    let keys = escrow.keys();
+    let mut signers = private_keys
+        .into_iter()
+        .map(|private_key| {
+            let keypair = secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, private_key)?;
+            let public_key = keypair.x_only_public_key().0.serialize();
+            let position = keys.iter().position(|key| *key == public_key).ok_or(Error::EscrowUnknownParty)?;
+            Ok((position, private_key))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    signers.sort_by_key(|(position, _)| *position);
+    if signers[0].0 == signers[1].0 {
+        return Err(Error::EscrowDuplicateParty);
+    }
+
+    // Signatures must follow the order of the escrow keys
+    let signatures = signers.iter().map(|(_, private_key)| schnorr_signature(tx, input_index, private_key)).collect::<Vec<_>>();
+    let signatures = signatures.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    tx.tx.inputs[input_index].signature_script = escrow.release_signature_script(&signatures)?;
+    Ok(())
+}
+
+/// Refunds an [`Escrow`] input to the buyer once its timeout has been reached
+pub fn sign_escrow_refund(tx: &mut SignableTransaction, input_index: usize, escrow: &Escrow, private_key: &[u8; 32]) -> Result<()> {
+    check_covenant_input(tx, input_index, escrow)?;
+    check_lock_time(tx, input_index, escrow.timeout)?;
+    let signature = schnorr_signature(tx, input_index, private_key);
+    tx.tx.inputs[input_index].signature_script = escrow.refund_signature_script(&signature)?;
+    Ok(())
+}
+
+/// Returns the signature followed by the sighash type, without the data push opcode
+fn schnorr_signature(tx: &SignableTransaction, input_index: usize, private_key: &[u8; 32]) -> Vec<u8> {
+    sign_input(&tx.as_verifiable(), input_index, private_key, SIG_HASH_ALL).split_off(1)
+}
+
+fn check_covenant_input(
+    tx: &SignableTransaction,
+    input_index: usize,
+    covenant: &impl CovenantTemplate,
+) -> Result<(ScriptPublicKey, u64)> {
+    if input_index >= tx.tx.inputs.len() {
+        return Err(Error::CovenantInputOutOfRange(input_index));
+    }
+    let entry = tx.entries[input_index].as_ref().ok_or(Error::CovenantMissingUtxoEntry(input_index))?;
+    let script_public_key = covenant.script_public_key()?;
+    if entry.script_public_key != script_public_key {
+        return Err(Error::CovenantInputMismatch(input_index));
+    }
+    Ok((script_public_key, entry.amount))
+}
+
+fn check_relative_lock(tx: &SignableTransaction, input_index: usize, lock: u64) -> Result<()> {
+    let sequence = tx.tx.inputs[input_index].sequence;
+    if sequence & SEQUENCE_LOCK_TIME_DISABLED != 0 || sequence & SEQUENCE_LOCK_TIME_MASK < lock {
+        return Err(Error::CovenantSequenceLocked(input_index, lock));
+    }
+    Ok(())
+}
+
+fn check_lock_time(tx: &SignableTransaction, input_index: usize, lock_time: u64) -> Result<()> {
+    if tx.tx.lock_time < lock_time {
+        return Err(Error::CovenantLockTimeNotReached(lock_time));
+    }
+    if tx.tx.inputs[input_index].sequence == MAX_TX_IN_SEQUENCE_NUM {
+        return Err(Error::CovenantFinalizedSequence(input_index));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::hashing::sighash::SigHashReusedValuesUnsync;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::{
+        Transaction, TransactionId, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry, VerifiableTransaction,
+    };
+    use kaspa_txscript::{caches::Cache, opcodes::codes::OpTrue, TxScriptEngine};
+    use kaspa_txscript_errors::TxScriptError;
+
+    fn xonly(private_key: &[u8; 32]) -> [u8; 32] {
+        secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, private_key).unwrap().x_only_public_key().0.serialize()
+    }
+
+    fn destination() -> ScriptPublicKey {
+        ScriptPublicKey::from_vec(0, vec![OpTrue])
+    }
+
+    /// A transaction spending a covenant UTXO of `amount`, with a single output of `output_amount` paying to `output_spk`
+    fn spending_tx(
+        spk: &ScriptPublicKey,
+        amount: u64,
+        output_amount: u64,
+        output_spk: ScriptPublicKey,
+        lock_time: u64,
+        sequence: u64,
+        sig_op_count: u8,
+    ) -> SignableTransaction {
+        let input =
+            TransactionInput::new(TransactionOutpoint::new(TransactionId::from_u64_word(1), 0), vec![], sequence, sig_op_count);
+        let output = TransactionOutput::new(output_amount, output_spk);
+        let tx = Transaction::new(0, vec![input], vec![output], lock_time, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        SignableTransaction::with_entries(tx, vec![UtxoEntry::new(amount, spk.clone(), 0, false)])
+    }
+
+    /// Runs the script engine over the first input
+    fn verify(tx: &SignableTransaction) -> std::result::Result<(), TxScriptError> {
+        let sig_cache = Cache::new(10_000);
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let verifiable = tx.as_verifiable();
+        let (input, entry) = verifiable.populated_input(0);
+        TxScriptEngine::from_transaction_input(&verifiable, input, 0, entry, &reused_values, &sig_cache).execute()
+    }
+
+    #[test]
+    fn test_sign_vault() {
+        let (owner, recovery) = ([1; 32], [2; 32]);
+        let vault = TimelockedVault::new(xonly(&owner), xonly(&recovery), 1000).unwrap();
+        let spk = vault.script_public_key().unwrap();
+
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 0, 0, 1);
+        sign_vault_recovery(&mut tx, 0, &vault, &recovery).unwrap();
+        assert_eq!(verify(&tx), Ok(()));
+        assert!(matches!(sign_vault_recovery(&mut tx, 1, &vault, &recovery), Err(Error::CovenantInputOutOfRange(1))));
+
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 0, 999, 1);
+        assert!(matches!(sign_vault_owner(&mut tx, 0, &vault, &owner), Err(Error::CovenantSequenceLocked(0, 1000))));
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 0, 1000, 1);
+        sign_vault_owner(&mut tx, 0, &vault, &owner).unwrap();
+        assert_eq!(verify(&tx), Ok(()));
+
+        // Neither path applies to another covenant or to an input without entry
+        let other = TimelockedVault::new(xonly(&owner), xonly(&recovery), 1001).unwrap();
+        assert!(matches!(sign_vault_owner(&mut tx, 0, &other, &owner), Err(Error::CovenantInputMismatch(0))));
+        tx.entries[0] = None;
+        assert!(matches!(sign_vault_recovery(&mut tx, 0, &vault, &recovery), Err(Error::CovenantMissingUtxoEntry(0))));
+    }
+
+    #[test]
+    fn test_sign_htlc() {
+        let (receiver, refund) = ([1; 32], [2; 32]);
+        let preimage = [7u8; 32];
+        let htlc = Htlc::new(xonly(&receiver), xonly(&refund), Htlc::payment_hash(&preimage), 5000);
+        let spk = htlc.script_public_key().unwrap();
+
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 0, MAX_TX_IN_SEQUENCE_NUM, 1);
+        assert!(matches!(sign_htlc_claim(&mut tx, 0, &htlc, &receiver, &[8u8; 32]), Err(Error::HtlcSecretMismatch)));
+        sign_htlc_claim(&mut tx, 0, &htlc, &receiver, &preimage).unwrap();
+        assert_eq!(verify(&tx), Ok(()));
+
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 4999, 0, 1);
+        assert!(matches!(sign_htlc_refund(&mut tx, 0, &htlc, &refund), Err(Error::CovenantLockTimeNotReached(5000))));
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 5000, MAX_TX_IN_SEQUENCE_NUM, 1);
+        assert!(matches!(sign_htlc_refund(&mut tx, 0, &htlc, &refund), Err(Error::CovenantFinalizedSequence(0))));
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 5000, 0, 1);
+        sign_htlc_refund(&mut tx, 0, &htlc, &refund).unwrap();
+        assert_eq!(verify(&tx), Ok(()));
+    }
+
+    #[test]
+    fn test_sign_rate_limited_spend() {
+        let owner = [1; 32];
+        let covenant = RateLimitedSpend::new(xonly(&owner), 100, 10).unwrap();
+        let spk = covenant.script_public_key().unwrap();
+
+        let mut tx = spending_tx(&spk, 1000, 900, spk.clone(), 0, 10, 1);
+        sign_rate_limited_spend(&mut tx, 0, &covenant, &owner).unwrap();
+        assert_eq!(verify(&tx), Ok(()));
+
+        let mut tx = spending_tx(&spk, 1000, 899, spk.clone(), 0, 10, 1);
+        assert!(matches!(sign_rate_limited_spend(&mut tx, 0, &covenant, &owner), Err(Error::CovenantWithdrawalLimitExceeded(100))));
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 0, 10, 1);
+        assert!(matches!(sign_rate_limited_spend(&mut tx, 0, &covenant, &owner), Err(Error::CovenantMissingChange(0))));
+        let mut tx = spending_tx(&spk, 1000, 900, spk.clone(), 0, 9, 1);
+        assert!(matches!(sign_rate_limited_spend(&mut tx, 0, &covenant, &owner), Err(Error::CovenantSequenceLocked(0, 10))));
+
+        // A balance below the limit can be withdrawn entirely
+        let mut tx = spending_tx(&spk, 100, 90, destination(), 0, 10, 1);
+        sign_rate_limited_spend(&mut tx, 0, &covenant, &owner).unwrap();
+        assert_eq!(verify(&tx), Ok(()));
+    }
+
+    #[test]
+    fn test_sign_escrow() {
+        let (buyer, seller, arbiter) = ([1; 32], [2; 32], [3; 32]);
+        let escrow = Escrow::new(xonly(&buyer), xonly(&seller), xonly(&arbiter), 5000);
+        let spk = escrow.script_public_key().unwrap();
+
+        // Keys are accepted in any order
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 0, MAX_TX_IN_SEQUENCE_NUM, 3);
+        sign_escrow_release(&mut tx, 0, &escrow, [&arbiter, &seller]).unwrap();
+        assert_eq!(verify(&tx), Ok(()));
+        assert!(matches!(sign_escrow_release(&mut tx, 0, &escrow, [&buyer, &buyer]), Err(Error::EscrowDuplicateParty)));
+        assert!(matches!(sign_escrow_release(&mut tx, 0, &escrow, [&buyer, &[4; 32]]), Err(Error::EscrowUnknownParty)));
+
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 4999, 0, 1);
+        assert!(matches!(sign_escrow_refund(&mut tx, 0, &escrow, &buyer), Err(Error::CovenantLockTimeNotReached(5000))));
+        let mut tx = spending_tx(&spk, 1000, 900, destination(), 5000, 0, 1);
+        sign_escrow_refund(&mut tx, 0, &escrow, &buyer).unwrap();
+        assert_eq!(verify(&tx), Ok(()));
+    }
+}
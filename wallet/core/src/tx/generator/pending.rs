@@ -242,9 +242,10 @@ impl PendingTransaction {
         Ok(())
     }
 
-    pub fn try_sign(&self) -> Result<()> {
+    pub async fn try_sign(&self) -> Result<()> {
         let signer = self.inner.generator.signer().as_ref().expect("no signer in tx generator");
-        let signed_tx = signer.try_sign(self.inner.signable_tx.lock()?.clone(), self.addresses())?;
+        let signable_tx = self.inner.signable_tx.lock()?.clone();
+        let signed_tx = signer.try_sign(signable_tx, self.addresses()).await?;
         *self.inner.signable_tx.lock().unwrap() = signed_tx;
         Ok(())
     }
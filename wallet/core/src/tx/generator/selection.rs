@@ -0,0 +1,335 @@
+//!
+//! UTXO selection strategies and coin control applied to the
+//! UTXO entries consumed by the [`Generator`](crate::tx::Generator).
+//!
+
+use crate::imports::*;
+use crate::tx::calc_minimum_required_transaction_relay_fee;
+use crate::utxo::UtxoEntryId;
+use kaspa_consensus_core::tx::TransactionOutpoint;
+
+/// Serialized size of a standard input and the mass of its signature operation
+const ESTIMATED_INPUT_MASS: u64 = 148 + 1000;
+/// Maximum number of branches explored by [`UtxoSelectionStrategy::BranchAndBound`]
+const BRANCH_AND_BOUND_MAX_TRIES: usize = 100_000;
+
+/// Order in which the [`Generator`](crate::tx::Generator) consumes the available UTXO entries
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UtxoSelectionStrategy {
+    /// Smallest entries first, consolidating the UTXO set (default order of the UTXO context)
+    #[default]
+    SmallestFirst,
+    /// Largest entries first, minimizing the number of inputs
+    LargestFirst,
+    /// Searches for a set of entries matching the payment amount closely enough to avoid
+    /// a change output, falling back to [`UtxoSelectionStrategy::LargestFirst`]
+    BranchAndBound,
+    /// Only spends entries of a single address, so that addresses are never linked together
+    SingleAddress,
+}
+
+impl std::fmt::Display for UtxoSelectionStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UtxoSelectionStrategy::SmallestFirst => write!(f, "smallest-first"),
+            UtxoSelectionStrategy::LargestFirst => write!(f, "largest-first"),
+            UtxoSelectionStrategy::BranchAndBound => write!(f, "branch-and-bound"),
+            UtxoSelectionStrategy::SingleAddress => write!(f, "single-address"),
+        }
+    }
+}
+
+impl FromStr for UtxoSelectionStrategy {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "smallest-first" | "smallest" => Ok(UtxoSelectionStrategy::SmallestFirst),
+            "largest-first" | "largest" => Ok(UtxoSelectionStrategy::LargestFirst),
+            "branch-and-bound" | "bnb" => Ok(UtxoSelectionStrategy::BranchAndBound),
+            "single-address" => Ok(UtxoSelectionStrategy::SingleAddress),
+            _ => Err(Error::custom(format!("Invalid UTXO selection strategy: {s}"))),
+        }
+    }
+}
+
+/// Coin control of a single transaction generation: the selection strategy along with
+/// the outpoints that must or must not be spent. Outpoints frozen in the account settings
+/// are always excluded.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UtxoSelection {
+    #[serde(default)]
+    pub strategy: UtxoSelectionStrategy,
+    /// Outpoints spent before any other entry
+    #[serde(default)]
+    pub include: Vec<TransactionOutpoint>,
+    /// Outpoints that are never spent
+    #[serde(default)]
+    pub exclude: Vec<TransactionOutpoint>,
+}
+
+impl UtxoSelection {
+    pub fn new(strategy: UtxoSelectionStrategy) -> Self {
+        Self { strategy, ..Default::default() }
+    }
+
+    pub fn include(mut self, outpoints: impl IntoIterator<Item = TransactionOutpoint>) -> Self {
+        self.include.extend(outpoints);
+        self
+    }
+
+    pub fn exclude(mut self, outpoints: impl IntoIterator<Item = TransactionOutpoint>) -> Self {
+        self.exclude.extend(outpoints);
+        self
+    }
+
+    /// Splits `entries` into the explicitly included entries and the remaining entries
+    /// ordered according to the selection strategy. `target` is the amount that needs
+    /// to be covered by the selected entries, if known.
+    pub(crate) fn apply(
+        &self,
+        entries: Vec<UtxoEntryReference>,
+        target: Option<u64>,
+        fee_rate: Option<f64>,
+    ) -> Result<(Vec<UtxoEntryReference>, Vec<UtxoEntryReference>)> {
+        let exclude = self.exclude.iter().cloned().map(UtxoEntryId::from).collect::<HashSet<_>>();
+        let include = self.include.iter().cloned().map(UtxoEntryId::from).collect::<HashSet<_>>();
+        if let Some(outpoint) = self.include.iter().find(|outpoint| exclude.contains(&UtxoEntryId::from(**outpoint))) {
+            return Err(Error::custom(format!("UTXO {outpoint} can not be both included and excluded")));
+        }
+
+        let (included, entries): (Vec<_>, Vec<_>) = entries
+            .into_iter()
+            .filter(|entry| !exclude.contains(entry.id_as_ref()))
+            .partition(|entry| include.contains(entry.id_as_ref()));
+        if included.len() != include.len() {
+            let found = included.iter().map(|entry| entry.id()).collect::<HashSet<_>>();
+            let missing = self.include.iter().find(|outpoint| !found.contains(&UtxoEntryId::from(**outpoint))).unwrap();
+            return Err(Error::custom(format!("UTXO {missing} is not available for spending")));
+        }
+
+        let target = target.map(|target| target.saturating_sub(included.iter().map(|entry| entry.amount()).sum()));
+        let entries = match self.strategy {
+            UtxoSelectionStrategy::SmallestFirst => sorted(entries, false),
+            UtxoSelectionStrategy::LargestFirst => sorted(entries, true),
+            UtxoSelectionStrategy::BranchAndBound => match target {
+                Some(target) if target > 0 => branch_and_bound(entries, target, fee_rate),
+                _ => sorted(entries, true),
+            },
+            UtxoSelectionStrategy::SingleAddress => single_address(entries, &included, target)?,
+        };
+
+        Ok((included, entries))
+    }
+}
+
+fn sorted(mut entries: Vec<UtxoEntryReference>, descending: bool) -> Vec<UtxoEntryReference> {
+    if descending {
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.amount()));
+    } else {
+        entries.sort_by_key(|entry| entry.amount());
+    }
+    entries
+}
+
+/// Estimated fee required to spend a standard input
+fn input_fee(fee_rate: Option<f64>) -> u64 {
+    let fee = calc_minimum_required_transaction_relay_fee(ESTIMATED_INPUT_MASS);
+    fee_rate.map(|fee_rate| fee.max((ESTIMATED_INPUT_MASS as f64 * fee_rate) as u64)).unwrap_or(fee)
+}
+
+/// Places first the entries whose value net of their spending fee matches `target` without
+/// exceeding it by more than the cost of a change output, then the remaining entries largest
+/// first. The fee of the transaction outputs is not part of `target`, so the generator may still
+/// consume the remaining entries if the matched set does not cover it.
+fn branch_and_bound(entries: Vec<UtxoEntryReference>, target: u64, fee_rate: Option<f64>) -> Vec<UtxoEntryReference> {
+    let input_fee = input_fee(fee_rate);
+    let entries = sorted(entries, true);
+    let values =
+        entries.iter().map(|entry| entry.amount().saturating_sub(input_fee)).take_while(|value| *value > 0).collect::<Vec<_>>();
+
+    match search(&values, target, input_fee) {
+        Some(selected) => {
+            let selected = selected.into_iter().collect::<HashSet<_>>();
+            let (mut head, tail): (Vec<_>, Vec<_>) = entries.into_iter().enumerate().partition(|(index, _)| selected.contains(index));
+            head.extend(tail);
+            head.into_iter().map(|(_, entry)| entry).collect()
+        }
+        None => entries,
+    }
+}
+
+/// Depth first search over `values` (sorted in descending order) for the subset whose sum is the
+/// closest to `target` within `[target, target + window]`. Returns the indexes of the subset.
+fn search(values: &[u64], target: u64, window: u64) -> Option<Vec<usize>> {
+    let mut available: u64 = values.iter().sum();
+    if available < target {
+        return None;
+    }
+
+    let mut value = 0;
+    let mut index = 0;
+    let mut selected: Vec<usize> = vec![];
+    let mut best: Option<(u64, Vec<usize>)> = None;
+
+    for _ in 0..BRANCH_AND_BOUND_MAX_TRIES {
+        let backtrack = if value + available < target || value > target + window {
+            true
+        } else if value >= target {
+            let excess = value - target;
+            if best.as_ref().map(|(best_excess, _)| excess < *best_excess).unwrap_or(true) {
+                best = Some((excess, selected.clone()));
+            }
+            if excess == 0 {
+                break;
+            }
+            true
+        } else {
+            false
+        };
+
+        if backtrack {
+            // exclude the last included value, restoring the values decided after it
+            let Some(last) = selected.pop() else { break };
+            while index > last + 1 {
+                index -= 1;
+                available += values[index];
+            }
+            value -= values[last];
+            index = last + 1;
+        } else {
+            available -= values[index];
+            value += values[index];
+            selected.push(index);
+            index += 1;
+        }
+    }
+
+    best.map(|(_, selected)| selected)
+}
+
+/// Keeps only the entries of a single address: the address of the included entries if any,
+/// otherwise the address holding the smallest balance covering `target`
+fn single_address(
+    entries: Vec<UtxoEntryReference>,
+    included: &[UtxoEntryReference],
+    target: Option<u64>,
+) -> Result<Vec<UtxoEntryReference>> {
+    let mut groups: HashMap<Option<Address>, Vec<UtxoEntryReference>> = HashMap::new();
+    for entry in entries {
+        groups.entry(entry.address()).or_default().push(entry);
+    }
+
+    let addresses = included.iter().map(|entry| entry.address()).collect::<HashSet<_>>();
+    let address = match addresses.len() {
+        0 => {
+            let totals = groups.iter().map(|(address, entries)| (address, entries.iter().map(|entry| entry.amount()).sum::<u64>()));
+            match target {
+                Some(target) => {
+                    let totals = totals.collect::<Vec<_>>();
+                    let covering = totals.iter().filter(|(_, total)| *total >= target).min_by_key(|(_, total)| *total);
+                    match covering {
+                        Some((address, _)) => (*address).clone(),
+                        None => {
+                            let largest = totals.iter().map(|(_, total)| *total).max().unwrap_or_default();
+                            return Err(Error::InsufficientFunds { additional_needed: target - largest, origin: "single-address" });
+                        }
+                    }
+                }
+                None => totals.max_by_key(|(_, total)| *total).and_then(|(address, _)| address.clone()),
+            }
+        }
+        1 => addresses.into_iter().next().unwrap(),
+        _ => return Err(Error::custom("Included UTXOs must belong to a single address")),
+    };
+
+    Ok(sorted(groups.remove(&address).unwrap_or_default(), true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::Version;
+
+    fn entries(amounts: &[u64]) -> Vec<UtxoEntryReference> {
+        amounts.iter().map(|amount| UtxoEntryReference::simulated(*amount)).collect()
+    }
+
+    fn amounts(entries: &[UtxoEntryReference]) -> Vec<u64> {
+        entries.iter().map(|entry| entry.amount()).collect()
+    }
+
+    fn outpoint(entry: &UtxoEntryReference) -> TransactionOutpoint {
+        entry.outpoint().into()
+    }
+
+    #[test]
+    fn test_ordering_strategies() {
+        let utxos = entries(&[300, 100, 200]);
+        let (_, smallest) = UtxoSelection::new(UtxoSelectionStrategy::SmallestFirst).apply(utxos.clone(), None, None).unwrap();
+        assert_eq!(amounts(&smallest), vec![100, 200, 300]);
+        let (_, largest) = UtxoSelection::new(UtxoSelectionStrategy::LargestFirst).apply(utxos, None, None).unwrap();
+        assert_eq!(amounts(&largest), vec![300, 200, 100]);
+    }
+
+    #[test]
+    fn test_coin_control() {
+        let utxos = entries(&[100, 200, 300, 400]);
+        let selection = UtxoSelection::default().include([outpoint(&utxos[2])]).exclude([outpoint(&utxos[0])]);
+        let (included, entries) = selection.apply(utxos.clone(), None, None).unwrap();
+        assert_eq!(amounts(&included), vec![300]);
+        assert_eq!(amounts(&entries), vec![200, 400]);
+
+        let conflicting = UtxoSelection::default().include([outpoint(&utxos[1])]).exclude([outpoint(&utxos[1])]);
+        assert!(conflicting.apply(utxos.clone(), None, None).is_err());
+
+        let missing = UtxoSelection::default().include([TransactionOutpoint::new(TransactionId::from_bytes([1; 32]), 0)]);
+        assert!(missing.apply(utxos, None, None).is_err());
+    }
+
+    #[test]
+    fn test_branch_and_bound() {
+        let fee = input_fee(None);
+        let utxos = entries(&[50_000 + fee, 30_000 + fee, 20_000 + fee, 10_000 + fee, 5_000 + fee]);
+        let selection = UtxoSelection::new(UtxoSelectionStrategy::BranchAndBound);
+
+        // 30_000 + 5_000 is an exact match while largest first would produce change
+        let (_, ordered) = selection.apply(utxos.clone(), Some(35_000), None).unwrap();
+        assert_eq!(amounts(&ordered[..2]), vec![30_000 + fee, 5_000 + fee]);
+        assert_eq!(amounts(&ordered[2..]), vec![50_000 + fee, 20_000 + fee, 10_000 + fee]);
+
+        // no match within the window falls back to largest first
+        let (_, ordered) = selection.apply(utxos, Some(200_000), None).unwrap();
+        assert_eq!(amounts(&ordered)[0], 50_000 + fee);
+
+        assert_eq!(search(&[10, 7, 5, 3], 8, 0), Some(vec![2, 3]));
+        assert_eq!(search(&[10, 7, 5, 3], 9, 1), Some(vec![0]));
+        assert_eq!(search(&[10, 7, 5], 30, 5), None);
+    }
+
+    #[test]
+    fn test_single_address() {
+        let address = |byte: u8| Address::new(Prefix::Testnet, Version::PubKey, &[byte; 32]);
+        let (a, b) = (address(1), address(2));
+        let utxos = vec![
+            UtxoEntryReference::simulated_with_address(100, &a),
+            UtxoEntryReference::simulated_with_address(150, &a),
+            UtxoEntryReference::simulated_with_address(400, &b),
+        ];
+        let selection = UtxoSelection::new(UtxoSelectionStrategy::SingleAddress);
+
+        let (_, entries) = selection.apply(utxos.clone(), Some(200), None).unwrap();
+        assert_eq!(amounts(&entries), vec![150, 100]);
+        let (_, entries) = selection.apply(utxos.clone(), Some(300), None).unwrap();
+        assert_eq!(amounts(&entries), vec![400]);
+        assert!(matches!(
+            selection.apply(utxos.clone(), Some(500), None),
+            Err(Error::InsufficientFunds { additional_needed: 100, .. })
+        ));
+
+        let (included, entries) = selection.clone().include([outpoint(&utxos[0])]).apply(utxos, Some(300), None).unwrap();
+        assert_eq!(amounts(&included), vec![100]);
+        assert_eq!(amounts(&entries), vec![150]);
+    }
+}
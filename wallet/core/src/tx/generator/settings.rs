@@ -6,8 +6,8 @@
 use crate::events::Events;
 use crate::imports::*;
 use crate::result::Result;
-use crate::tx::{Fees, PaymentDestination};
-use crate::utxo::{UtxoContext, UtxoEntryReference, UtxoIterator};
+use crate::tx::{Fees, PaymentDestination, UtxoSelection};
+use crate::utxo::{UtxoContext, UtxoEntryId, UtxoEntryReference, UtxoIterator};
 use kaspa_addresses::Address;
 use workflow_core::channel::Multiplexer;
 
@@ -72,7 +72,9 @@ impl GeneratorSettings {
         let sig_op_count = account.sig_op_count();
         let minimum_signatures = account.minimum_signatures();
 
-        let utxo_iterator = UtxoIterator::new(account.utxo_context());
+        // frozen UTXOs are never spent by the account
+        let frozen = account.frozen_utxos().into_iter().map(UtxoEntryId::from).collect::<HashSet<_>>();
+        let utxo_iterator = UtxoIterator::new(account.utxo_context()).filter(move |entry| !frozen.contains(entry.id_as_ref()));
 
         let settings = GeneratorSettings {
             network_id,
@@ -167,4 +169,19 @@ impl GeneratorSettings {
         self.destination_utxo_context = Some(destination_utxo_context.clone());
         self
     }
+
+    /// Applies coin control to the UTXO entries of the source iterator. Included entries
+    /// are consumed first, followed by the remaining entries in the order of the selection strategy.
+    pub fn utxo_selection(mut self, utxo_selection: &UtxoSelection) -> Result<Self> {
+        let entries = std::mem::replace(&mut self.utxo_iterator, Box::new(std::iter::empty())).collect::<Vec<_>>();
+        let target =
+            self.final_transaction_destination.amount().map(|amount| amount + self.final_transaction_priority_fee.additional());
+        let (included, entries) = utxo_selection.apply(entries, target, self.fee_rate)?;
+
+        self.utxo_iterator = Box::new(entries.into_iter());
+        if !included.is_empty() {
+            self.priority_utxo_entries.get_or_insert_with(Vec::new).extend(included);
+        }
+        Ok(self)
+    }
 }
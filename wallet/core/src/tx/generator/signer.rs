@@ -6,8 +6,11 @@ use crate::imports::*;
 use kaspa_bip32::PrivateKey;
 use kaspa_consensus_core::{sign::sign_with_multiple_v2, tx::SignableTransaction};
 
+/// Signs transactions produced by the [`Generator`](crate::tx::Generator).
+/// See [`crate::signer`] for the available backends.
+#[async_trait]
 pub trait SignerT: Send + Sync + 'static {
-    fn try_sign(&self, transaction: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction>;
+    async fn try_sign(&self, transaction: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction>;
 }
 
 struct Inner {
@@ -48,8 +51,9 @@ impl Signer {
     }
 }
 
+#[async_trait]
 impl SignerT for Signer {
-    fn try_sign(&self, mutable_tx: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction> {
+    async fn try_sign(&self, mutable_tx: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction> {
         self.ingest(addresses)?;
 
         let keys = self.inner.keys.lock().unwrap();
@@ -78,8 +82,9 @@ impl KeydataSigner {
     }
 }
 
+#[async_trait]
 impl SignerT for KeydataSigner {
-    fn try_sign(&self, mutable_tx: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction> {
+    async fn try_sign(&self, mutable_tx: SignableTransaction, addresses: &[Address]) -> Result<SignableTransaction> {
         let mut keys_for_signing = addresses.iter().map(|address| *self.inner.keys.get(address).unwrap()).collect::<Vec<_>>();
         // TODO - refactor for multisig
         let signable_tx = sign_with_multiple_v2(mutable_tx, &keys_for_signing).fully_signed()?;
@@ -3,13 +3,16 @@
 //!
 
 pub mod consensus;
+pub mod covenant;
 pub mod fees;
 pub mod generator;
 pub mod mass;
 pub mod payment;
+pub mod payout;
 
 pub use self::consensus::*;
 pub use self::fees::*;
 pub use self::generator::*;
 pub use self::mass::*;
 pub use self::payment::*;
+pub use self::payout::{PayoutBatch, PayoutJob, PreparedPayoutBatch};
@@ -0,0 +1,418 @@
+//!
+//! Batched payouts: packing of large lists of payments into
+//! mass-compliant transactions, with resumable submission.
+//!
+//! A [`PayoutJob`] splits the payouts into [`PayoutBatch`]es, each of
+//! which is submitted as a single payment (possibly preceded by compound
+//! transactions produced by the [`Generator`]). The job is serializable.
+//! A batch is first prepared, recording its signed transactions in the job,
+//! and only submitted once the caller has persisted the job, allowing an
+//! interrupted payout to be resumed without paying anyone twice.
+//!
+
+use crate::imports::*;
+use crate::tx::{
+    Fees, Generator, GeneratorSettings, MassCalculator, PaymentOutput, PaymentOutputs, PendingTransaction,
+    MAXIMUM_STANDARD_TRANSACTION_MASS,
+};
+use crate::utils::try_kaspa_str_to_sompi;
+use kaspa_consensus_core::tx::TransactionOutput;
+use kaspa_txscript::pay_to_address_script;
+use workflow_core::abortable::Abortable;
+
+/// Compute mass available to the outputs of a batch transaction, including the
+/// change output. Matches the limit enforced by the [`Generator`], the remainder
+/// of the transaction mass being left to the inputs.
+const BATCH_OUTPUTS_COMPUTE_MASS: u64 = MAXIMUM_STANDARD_TRANSACTION_MASS / 5 * 4;
+
+/// A set of payouts submitted as a single payment.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutBatch {
+    pub outputs: PaymentOutputs,
+    /// Compute mass of the batch transaction excluding its inputs
+    pub compute_mass: u64,
+    /// Storage mass (KIP-9) of the batch transaction, not accounting for the
+    /// inputs. Inputs can only lower the storage mass, this is an upper bound.
+    pub storage_mass: u64,
+    /// Estimated fees, replaced with the actual fees once the batch is submitted
+    pub fees: Option<u64>,
+    /// Transactions generated for this batch, recorded before they are submitted
+    pub transaction_ids: Vec<TransactionId>,
+    /// Transaction paying the batch outputs, recorded before it is submitted
+    pub final_transaction_id: Option<TransactionId>,
+    /// Set while the batch transactions are being submitted. A batch left pending
+    /// by an interruption is settled against the node before it is paid again.
+    pub pending: bool,
+}
+
+impl PayoutBatch {
+    pub fn amount(&self) -> u64 {
+        self.outputs.amount()
+    }
+
+    pub fn is_submitted(&self) -> bool {
+        !self.pending && !self.transaction_ids.is_empty()
+    }
+
+    /// Storage mass exceeds compute mass: the batch fees are driven by the
+    /// value of its outputs rather than by their number.
+    pub fn is_storage_mass_bound(&self) -> bool {
+        self.storage_mass > self.compute_mass
+    }
+}
+
+/// Payouts from an account packed into batches.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PayoutJob {
+    pub account_id: AccountId,
+    pub network_id: NetworkId,
+    pub batches: Vec<PayoutBatch>,
+}
+
+impl PayoutJob {
+    /// Packs the payouts into the minimum number of batches that satisfy
+    /// the compute and storage mass limits of a standard transaction.
+    ///
+    /// Payouts are placed first-fit in decreasing order of storage mass: low-value
+    /// (storage heavy) payouts are distributed first and higher-value payouts then
+    /// fill the remaining compute mass capacity of the batches.
+    pub fn try_new(account_id: AccountId, network_id: NetworkId, outputs: Vec<PaymentOutput>) -> Result<Self> {
+        if outputs.is_empty() {
+            return Err(Error::custom("No payouts supplied"));
+        }
+
+        let network_type = NetworkType::from(network_id);
+        let calc = MassCalculator::new(&network_id.into());
+
+        let mut payouts = outputs
+            .into_iter()
+            .map(|output| {
+                if output.amount == 0 {
+                    return Err(Error::custom(format!("Payout to {} has a zero amount", output.address)));
+                }
+                if NetworkType::try_from(output.address.prefix)? != network_type {
+                    return Err(Error::custom(format!("Payout address {} does not belong to {network_id}", output.address)));
+                }
+                let compute_mass = calc.calc_compute_mass_for_client_transaction_output(&TransactionOutput::new(
+                    output.amount,
+                    pay_to_address_script(&output.address),
+                ));
+                let storage_mass = calc.calc_storage_mass_output_harmonic_single(output.amount);
+                if storage_mass > MAXIMUM_STANDARD_TRANSACTION_MASS {
+                    return Err(Error::custom(format!(
+                        "Payout to {} is too small: its storage mass {storage_mass} exceeds the maximum standard transaction mass",
+                        output.address
+                    )));
+                }
+                Ok((output, compute_mass, storage_mass))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        payouts
+            .sort_by(|(_, compute_a, storage_a), (_, compute_b, storage_b)| storage_b.cmp(storage_a).then(compute_b.cmp(compute_a)));
+
+        let change_output_compute_mass = calc
+            .calc_compute_mass_for_client_transaction_output(&TransactionOutput::new(0, pay_to_address_script(&payouts[0].0.address)));
+        let overhead = calc.blank_transaction_compute_mass() + change_output_compute_mass;
+
+        let mut batches: Vec<PayoutBatch> = Vec::new();
+        for (output, compute_mass, storage_mass) in payouts {
+            let batch = batches.iter_mut().find(|batch| {
+                batch.compute_mass + compute_mass <= BATCH_OUTPUTS_COMPUTE_MASS
+                    && batch.storage_mass + storage_mass <= MAXIMUM_STANDARD_TRANSACTION_MASS
+            });
+            match batch {
+                Some(batch) => {
+                    batch.outputs.outputs.push(output);
+                    batch.compute_mass += compute_mass;
+                    batch.storage_mass += storage_mass;
+                }
+                None => batches.push(PayoutBatch {
+                    outputs: PaymentOutputs { outputs: vec![output] },
+                    compute_mass: overhead + compute_mass,
+                    storage_mass,
+                    fees: None,
+                    transaction_ids: vec![],
+                    final_transaction_id: None,
+                    pending: false,
+                }),
+            }
+        }
+
+        Ok(Self { account_id, network_id, batches })
+    }
+
+    /// Total amount of the payouts, excluding fees
+    pub fn amount(&self) -> u64 {
+        self.batches.iter().map(PayoutBatch::amount).sum()
+    }
+
+    /// Total of the estimated (pending batches) and actual (submitted batches) fees
+    pub fn fees(&self) -> u64 {
+        self.batches.iter().filter_map(|batch| batch.fees).sum()
+    }
+
+    pub fn payouts(&self) -> usize {
+        self.batches.iter().map(|batch| batch.outputs.outputs.len()).sum()
+    }
+
+    pub fn submitted(&self) -> usize {
+        self.batches.iter().filter(|batch| batch.is_submitted()).count()
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.batches.iter().all(PayoutBatch::is_submitted)
+    }
+
+    fn check_account(&self, account: &Arc<dyn Account>) -> Result<()> {
+        if account.id() != &self.account_id {
+            Err(Error::custom(format!("Payout job belongs to account {}", self.account_id.short())))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Estimates the fees of the pending batches. Each batch is estimated against the
+    /// current account UTXO set, so the estimate does not account for the UTXOs consumed
+    /// by the batches submitted before it.
+    pub async fn estimate(&mut self, account: &Arc<dyn Account>, fee_rate: Option<f64>, abortable: &Abortable) -> Result<()> {
+        self.check_account(account)?;
+
+        for batch in self.batches.iter_mut().filter(|batch| !batch.is_submitted()) {
+            let summary =
+                account.clone().estimate(batch.outputs.clone().into(), fee_rate, Fees::SenderPays(0), None, None, abortable).await?;
+            batch.fees = Some(summary.aggregate_fees());
+        }
+
+        Ok(())
+    }
+
+    /// Prepares the next pending batch: its transactions are generated and signed, and
+    /// recorded in the job with the batch marked as pending. The job must be persisted
+    /// before the batch is submitted with [`PayoutJob::submit_prepared`].
+    ///
+    /// A batch interrupted during a previous submission is only prepared again if its
+    /// final transaction did not reach the network, otherwise it is marked as submitted.
+    /// Returns `None` if the job is completed.
+    pub async fn prepare_next(
+        &mut self,
+        account: &Arc<dyn Account>,
+        fee_rate: Option<f64>,
+        wallet_secret: &Secret,
+        payment_secret: Option<&Secret>,
+        abortable: &Abortable,
+    ) -> Result<Option<PreparedPayoutBatch>> {
+        self.check_account(account)?;
+
+        let index = loop {
+            let Some(index) = self.batches.iter().position(|batch| !batch.is_submitted()) else {
+                return Ok(None);
+            };
+            let batch = &mut self.batches[index];
+            if !batch.pending {
+                break index;
+            }
+            let submitted = match batch.final_transaction_id.as_ref() {
+                Some(transaction_id) => account.wallet().is_transaction_submitted(account, transaction_id).await?,
+                None => false,
+            };
+            batch.pending = false;
+            if !submitted {
+                batch.transaction_ids.clear();
+                batch.final_transaction_id = None;
+                break index;
+            }
+        };
+
+        let signer = account.clone().create_signer(wallet_secret.clone(), payment_secret.cloned()).await?;
+        let settings = GeneratorSettings::try_new_with_account(
+            account.clone(),
+            self.batches[index].outputs.clone().into(),
+            fee_rate,
+            Fees::SenderPays(0),
+            None,
+        )?;
+        let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
+
+        let mut transactions = vec![];
+        let mut stream = generator.stream();
+        while let Some(transaction) = stream.try_next().await? {
+            transaction.try_sign().await?;
+            transactions.push(transaction);
+            yield_executor().await;
+        }
+
+        let batch = &mut self.batches[index];
+        batch.transaction_ids = transactions.iter().map(PendingTransaction::id).collect();
+        batch.final_transaction_id = transactions.iter().find(|transaction| transaction.is_final()).map(PendingTransaction::id);
+        batch.pending = true;
+
+        Ok(Some(PreparedPayoutBatch { index, transactions, fees: generator.summary().aggregate_fees() }))
+    }
+
+    /// Submits the transactions of a batch prepared by [`PayoutJob::prepare_next`].
+    /// On failure the batch is left pending and gets settled when prepared again.
+    /// Returns the index of the submitted batch.
+    pub async fn submit_prepared(&mut self, account: &Arc<dyn Account>, prepared: PreparedPayoutBatch) -> Result<usize> {
+        self.check_account(account)?;
+        if !prepared.belongs_to(self) {
+            return Err(Error::custom("Prepared payout batch does not belong to the payout job"));
+        }
+
+        let PreparedPayoutBatch { index, transactions, fees } = prepared;
+        for transaction in transactions {
+            transaction.try_submit(&account.wallet().rpc_api()).await?;
+            yield_executor().await;
+        }
+        let batch = &mut self.batches[index];
+        batch.fees = Some(fees);
+        batch.pending = false;
+
+        Ok(index)
+    }
+}
+
+/// Signed transactions of a [`PayoutBatch`], recorded in the [`PayoutJob`] but not submitted yet.
+pub struct PreparedPayoutBatch {
+    /// Index of the batch in the job
+    pub index: usize,
+    transactions: Vec<PendingTransaction>,
+    fees: u64,
+}
+
+impl PreparedPayoutBatch {
+    /// Returns true if `job` records this batch as pending with the same transactions
+    pub fn belongs_to(&self, job: &PayoutJob) -> bool {
+        job.batches.get(self.index).is_some_and(|batch| {
+            batch.pending
+                && batch.transaction_ids.len() == self.transactions.len()
+                && batch.transaction_ids.iter().zip(self.transactions.iter()).all(|(id, transaction)| *id == transaction.id())
+        })
+    }
+}
+
+/// Parses a list of payouts given either as CSV lines `<address>,<amount>` or as a
+/// JSON array of `{ "address": ..., "amount": ... }` objects. Amounts are in KAS.
+/// CSV lines starting with `#` and a leading header line are ignored.
+pub fn parse_payouts(data: &str) -> Result<Vec<PaymentOutput>> {
+    if data.trim_start().starts_with('[') {
+        #[derive(Deserialize)]
+        struct Payout {
+            address: String,
+            amount: serde_json::Value,
+        }
+
+        let payouts: Vec<Payout> = serde_json::from_str(data)?;
+        payouts
+            .into_iter()
+            .enumerate()
+            .map(|(n, Payout { address, amount })| {
+                let amount = match amount {
+                    serde_json::Value::String(amount) => amount,
+                    amount => amount.to_string(),
+                };
+                parse_payout(&address, &amount).map_err(|err| Error::custom(format!("Invalid payout at index {n}: {err}")))
+            })
+            .collect()
+    } else {
+        let mut payouts = Vec::new();
+        for (n, line) in data.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split([',', ';', '\t']).map(str::trim);
+            let (address, amount) = (fields.next().unwrap_or_default(), fields.next().unwrap_or_default());
+            // header line
+            if payouts.is_empty() && Address::try_from(address).is_err() && amount.parse::<f64>().is_err() {
+                continue;
+            }
+            payouts
+                .push(parse_payout(address, amount).map_err(|err| Error::custom(format!("Invalid payout at line {}: {err}", n + 1)))?);
+        }
+        Ok(payouts)
+    }
+}
+
+fn parse_payout(address: &str, amount: &str) -> Result<PaymentOutput> {
+    let address = Address::try_from(address)?;
+    let amount = try_kaspa_str_to_sompi(amount)?.ok_or_else(|| Error::custom("missing amount"))?;
+    Ok(PaymentOutput::new(address, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::constants::SOMPI_PER_KASPA;
+
+    const ADDRESS: &str = "kaspatest:qz7ulu4c25dh7fzec9zjyrmlhnkzrg4wmf89q7gzr3gfrsj3uz6xjceef60sd";
+
+    fn account_id() -> AccountId {
+        AccountId(kaspa_hashes::Hash::from_bytes([0; 32]))
+    }
+
+    #[test]
+    fn test_parse_payouts() -> Result<()> {
+        let csv = format!("address,amount\n# comment\n{ADDRESS},1.5\n\n{ADDRESS}; 0.00000001\n");
+        let payouts = parse_payouts(&csv)?;
+        assert_eq!(payouts.len(), 2);
+        assert_eq!(payouts[0].amount, 150_000_000);
+        assert_eq!(payouts[1].amount, 1);
+
+        let json = format!(r#"[{{"address":"{ADDRESS}","amount":2}},{{"address":"{ADDRESS}","amount":"0.5"}}]"#);
+        let payouts = parse_payouts(&json)?;
+        assert_eq!(payouts.iter().map(|payout| payout.amount).collect::<Vec<_>>(), vec![200_000_000, 50_000_000]);
+
+        assert!(parse_payouts(&format!("{ADDRESS},1\n{ADDRESS},abc")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_payout_packing() -> Result<()> {
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 10);
+        let address = Address::try_from(ADDRESS)?;
+
+        // large payouts are bound by compute mass
+        let outputs = (0..2_000).map(|_| PaymentOutput::new(address.clone(), 100 * SOMPI_PER_KASPA)).collect::<Vec<_>>();
+        let job = PayoutJob::try_new(account_id(), network_id, outputs)?;
+        assert_eq!(job.payouts(), 2_000);
+        assert_eq!(job.amount(), 2_000 * 100 * SOMPI_PER_KASPA);
+        assert!(job.batches.len() > 1);
+        assert!(job.batches.iter().all(|batch| batch.compute_mass <= BATCH_OUTPUTS_COMPUTE_MASS));
+
+        // small payouts are bound by storage mass: 1 KAS outputs weigh 10_000 storage mass each
+        let mut outputs = (0..40).map(|_| PaymentOutput::new(address.clone(), SOMPI_PER_KASPA)).collect::<Vec<_>>();
+        outputs.extend((0..100).map(|_| PaymentOutput::new(address.clone(), 1_000 * SOMPI_PER_KASPA)));
+        let job = PayoutJob::try_new(account_id(), network_id, outputs)?;
+        assert!(job.batches.iter().all(|batch| batch.storage_mass <= MAXIMUM_STANDARD_TRANSACTION_MASS));
+        assert_eq!(job.batches.len(), 5);
+        assert!(job.batches.iter().any(PayoutBatch::is_storage_mass_bound));
+        assert!(!job.is_completed());
+
+        // a single payout exceeding the storage mass limit on its own
+        assert!(PayoutJob::try_new(account_id(), network_id, vec![PaymentOutput::new(address.clone(), 1_000)]).is_err());
+
+        // address of a different network
+        let mainnet = NetworkId::new(NetworkType::Mainnet);
+        assert!(PayoutJob::try_new(account_id(), mainnet, vec![PaymentOutput::new(address, 1)]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_payout_batch_state() -> Result<()> {
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 10);
+        let address = Address::try_from(ADDRESS)?;
+        let mut job = PayoutJob::try_new(account_id(), network_id, vec![PaymentOutput::new(address, SOMPI_PER_KASPA)])?;
+        let transaction_id = TransactionId::from_u64_word(1);
+
+        // transactions recorded before an interrupted submission do not complete the batch
+        job.batches[0].pending = true;
+        job.batches[0].transaction_ids.push(transaction_id);
+        job.batches[0].final_transaction_id = Some(transaction_id);
+        let job = serde_json::from_str::<PayoutJob>(&serde_json::to_string(&job)?)?;
+        assert!(!job.batches[0].is_submitted());
+        assert!(!job.is_completed());
+        Ok(())
+    }
+}
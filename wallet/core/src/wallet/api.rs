@@ -7,11 +7,14 @@ use crate::events::Events;
 use crate::imports::*;
 use crate::result::Result;
 use crate::storage::interface::TransactionRangeResult;
+use crate::storage::label::{export_bip329, import_bip329};
+use crate::storage::transaction::transactions_to_csv;
 use crate::storage::Binding;
-use crate::tx::Fees;
+use crate::tx::{Fees, PayoutJob};
 use kaspa_rpc_core::RpcFeeEstimate;
 use kaspa_wallet_pskt::bundle::Bundle;
 use workflow_core::channel::Receiver;
+use workflow_core::time::unixtime_as_millis_u64;
 #[async_trait]
 impl WalletApi for super::Wallet {
     async fn register_notifications(self: Arc<Self>, _channel: Receiver<WalletNotification>) -> Result<u64> {
@@ -392,16 +395,25 @@ impl WalletApi for super::Wallet {
     }
 
     async fn accounts_send_call(self: Arc<Self>, request: AccountsSendRequest) -> Result<AccountsSendResponse> {
-        let AccountsSendRequest { account_id, wallet_secret, payment_secret, destination, fee_rate, priority_fee_sompi, payload } =
-            request;
+        let AccountsSendRequest {
+            account_id,
+            wallet_secret,
+            payment_secret,
+            destination,
+            fee_rate,
+            priority_fee_sompi,
+            payload,
+            utxo_selection,
+        } = request;
 
         let guard = self.guard();
         let guard = guard.lock().await;
         let account = self.get_account_by_id(&account_id, &guard).await?.ok_or(Error::AccountNotFound(account_id))?;
 
         let abortable = Abortable::new();
-        let (generator_summary, transaction_ids) =
-            account.send(destination, fee_rate, priority_fee_sompi, payload, wallet_secret, payment_secret, &abortable, None).await?;
+        let (generator_summary, transaction_ids) = account
+            .send(destination, fee_rate, priority_fee_sompi, payload, utxo_selection, wallet_secret, payment_secret, &abortable, None)
+            .await?;
 
         Ok(AccountsSendResponse { generator_summary, transaction_ids })
     }
@@ -591,7 +603,7 @@ impl WalletApi for super::Wallet {
     }
 
     async fn accounts_estimate_call(self: Arc<Self>, request: AccountsEstimateRequest) -> Result<AccountsEstimateResponse> {
-        let AccountsEstimateRequest { account_id, destination, fee_rate, priority_fee_sompi, payload } = request;
+        let AccountsEstimateRequest { account_id, destination, fee_rate, priority_fee_sompi, payload, utxo_selection } = request;
 
         let guard = self.guard();
         let guard = guard.lock().await;
@@ -610,12 +622,95 @@ impl WalletApi for super::Wallet {
 
         let abortable = Abortable::new();
         self.inner.estimation_abortables.lock().unwrap().insert(account_id, abortable.clone());
-        let result = account.estimate(destination, fee_rate, priority_fee_sompi, payload, &abortable).await;
+        let result = account.estimate(destination, fee_rate, priority_fee_sompi, payload, utxo_selection, &abortable).await;
         self.inner.estimation_abortables.lock().unwrap().remove(&account_id);
 
         Ok(AccountsEstimateResponse { generator_summary: result? })
     }
 
+    async fn accounts_payout_estimate_call(
+        self: Arc<Self>,
+        request: AccountsPayoutEstimateRequest,
+    ) -> Result<AccountsPayoutEstimateResponse> {
+        let AccountsPayoutEstimateRequest { account_id, outputs, fee_rate } = request;
+
+        let guard = self.guard();
+        let guard = guard.lock().await;
+        let account = self.get_account_by_id(&account_id, &guard).await?.ok_or(Error::AccountNotFound(account_id))?;
+
+        let mut job = PayoutJob::try_new(account_id, self.network_id()?, outputs.outputs)?;
+        job.estimate(&account, fee_rate, &Abortable::new()).await?;
+
+        Ok(AccountsPayoutEstimateResponse { job })
+    }
+
+    async fn accounts_payout_send_call(self: Arc<Self>, request: AccountsPayoutSendRequest) -> Result<AccountsPayoutSendResponse> {
+        let AccountsPayoutSendRequest { wallet_secret, payment_secret, fee_rate, mut job } = request;
+
+        let guard = self.guard();
+        let guard = guard.lock().await;
+        let account = self.get_account_by_id(&job.account_id, &guard).await?.ok_or(Error::AccountNotFound(job.account_id))?;
+
+        // A batch prepared by the previous call is only submitted if the caller supplies the job
+        // recording its transactions, which is then expected to have been persisted.
+        let prepared =
+            self.inner.prepared_payouts.lock().unwrap().remove(&job.account_id).filter(|prepared| prepared.belongs_to(&job));
+
+        // the job is returned on failure as well, as it records the transactions of the pending batch
+        let error = match prepared {
+            Some(prepared) => match job.submit_prepared(&account, prepared).await {
+                Ok(index) => {
+                    let progress = Events::PayoutProgress {
+                        account_id: job.account_id,
+                        index: index as u32,
+                        batch: job.batches[index].clone(),
+                        submitted: job.submitted() as u32,
+                        batches: job.batches.len() as u32,
+                    };
+                    self.notify(progress).await.err().map(|err| err.to_string())
+                }
+                Err(err) => Some(err.to_string()),
+            },
+            None => match job.prepare_next(&account, fee_rate, &wallet_secret, payment_secret.as_ref(), &Abortable::new()).await {
+                Ok(Some(prepared)) => {
+                    self.inner.prepared_payouts.lock().unwrap().insert(job.account_id, prepared);
+                    None
+                }
+                Ok(None) => None,
+                Err(err) => Some(err.to_string()),
+            },
+        };
+
+        Ok(AccountsPayoutSendResponse { job, error })
+    }
+
+    async fn accounts_freeze_utxos_call(self: Arc<Self>, request: AccountsFreezeUtxosRequest) -> Result<AccountsFreezeUtxosResponse> {
+        let AccountsFreezeUtxosRequest { account_id, wallet_secret, outpoints, freeze } = request;
+
+        let guard = self.guard();
+        let guard = guard.lock().await;
+
+        let account = self.get_account_by_id(&account_id, &guard).await?.ok_or(Error::AccountNotFound(account_id))?;
+        account.freeze_utxos(&wallet_secret, &outpoints, freeze).await?;
+
+        Ok(AccountsFreezeUtxosResponse { frozen_utxos: account.frozen_utxos() })
+    }
+
+    async fn accounts_set_external_signer_call(
+        self: Arc<Self>,
+        request: AccountsSetExternalSignerRequest,
+    ) -> Result<AccountsSetExternalSignerResponse> {
+        let AccountsSetExternalSignerRequest { account_id, wallet_secret, external_signer } = request;
+
+        let guard = self.guard();
+        let guard = guard.lock().await;
+
+        let account = self.get_account_by_id(&account_id, &guard).await?.ok_or(Error::AccountNotFound(account_id))?;
+        account.set_external_signer(&wallet_secret, external_signer).await?;
+
+        Ok(AccountsSetExternalSignerResponse {})
+    }
+
     async fn transactions_data_get_call(self: Arc<Self>, request: TransactionsDataGetRequest) -> Result<TransactionsDataGetResponse> {
         let TransactionsDataGetRequest { account_id, network_id, filter, start, end } = request;
 
@@ -659,6 +754,130 @@ impl WalletApi for super::Wallet {
         Ok(TransactionsReplaceMetadataResponse {})
     }
 
+    async fn transactions_export_call(self: Arc<Self>, request: TransactionsExportRequest) -> Result<TransactionsExportResponse> {
+        let TransactionsExportRequest { account_id, network_id } = request;
+
+        let binding = Binding::Account(account_id);
+        let store = self.store().as_transaction_record_store()?;
+        let mut transactions = store.transaction_data_iter(&binding, &network_id).await?.try_collect::<Vec<_>>().await?;
+        transactions.sort_by_key(|record| record.block_daa_score());
+        let labels = self.store().as_label_store()?.load().await?;
+
+        Ok(TransactionsExportResponse { csv: transactions_to_csv(&transactions, &labels, network_id.network_type()) })
+    }
+
+    async fn labels_enumerate_call(self: Arc<Self>, _request: LabelsEnumerateRequest) -> Result<LabelsEnumerateResponse> {
+        Ok(LabelsEnumerateResponse { labels: self.store().as_label_store()?.load().await? })
+    }
+
+    async fn labels_update_call(self: Arc<Self>, request: LabelsUpdateRequest) -> Result<LabelsUpdateResponse> {
+        let LabelsUpdateRequest { wallet_secret, labels } = request;
+
+        self.store().as_label_store()?.store(labels).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(LabelsUpdateResponse {})
+    }
+
+    async fn labels_import_call(self: Arc<Self>, request: LabelsImportRequest) -> Result<LabelsImportResponse> {
+        let LabelsImportRequest { wallet_secret, data } = request;
+
+        let (labels, skipped) = import_bip329(&data)?;
+        let imported = labels.len() as u64;
+        self.store().as_label_store()?.store(labels).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(LabelsImportResponse { imported, skipped: skipped as u64 })
+    }
+
+    async fn labels_export_call(self: Arc<Self>, _request: LabelsExportRequest) -> Result<LabelsExportResponse> {
+        let labels = self.store().as_label_store()?.load().await?;
+        Ok(LabelsExportResponse { data: export_bip329(&labels)? })
+    }
+
+    async fn payment_plans_create_call(self: Arc<Self>, request: PaymentPlansCreateRequest) -> Result<PaymentPlansCreateResponse> {
+        let PaymentPlansCreateRequest { wallet_secret, account_id, name, outputs, interval, max_fee_rate, runs, start } = request;
+
+        if outputs.outputs.is_empty() {
+            return Err(Error::custom("A payment plan requires at least one recipient"));
+        }
+        if runs == Some(0) {
+            return Err(Error::custom("A payment plan requires at least one payment"));
+        }
+
+        let guard = self.guard();
+        let guard = guard.lock().await;
+        self.get_account_by_id(&account_id, &guard).await?.ok_or(Error::AccountNotFound(account_id))?;
+
+        let next = start.unwrap_or_else(|| interval.now(unixtime_as_millis_u64(), self.current_daa_score().unwrap_or_default()));
+        let plan = PaymentPlan::new(account_id, name, outputs, interval, max_fee_rate, runs, next);
+        self.store().as_payment_plan_store()?.store(plan.clone()).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(PaymentPlansCreateResponse { plan })
+    }
+
+    async fn payment_plans_enumerate_call(
+        self: Arc<Self>,
+        request: PaymentPlansEnumerateRequest,
+    ) -> Result<PaymentPlansEnumerateResponse> {
+        let PaymentPlansEnumerateRequest { account_id } = request;
+
+        let plans = self.store().as_payment_plan_store()?.load().await?;
+        let plans = plans.into_iter().filter(|plan| account_id.is_none_or(|account_id| plan.account_id == account_id)).collect();
+
+        Ok(PaymentPlansEnumerateResponse { plans })
+    }
+
+    async fn payment_plans_pause_call(self: Arc<Self>, request: PaymentPlansPauseRequest) -> Result<PaymentPlansPauseResponse> {
+        let PaymentPlansPauseRequest { wallet_secret, plan_id, pause } = request;
+
+        let store = self.store().as_payment_plan_store()?;
+        let mut plan = store.load_single(&plan_id).await?.ok_or_else(|| Error::custom(format!("Payment plan {plan_id} not found")))?;
+        plan.paused = pause;
+        store.store(plan.clone()).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(PaymentPlansPauseResponse { plan })
+    }
+
+    async fn payment_plans_cancel_call(self: Arc<Self>, request: PaymentPlansCancelRequest) -> Result<PaymentPlansCancelResponse> {
+        let PaymentPlansCancelRequest { wallet_secret, plan_id } = request;
+
+        let store = self.store().as_payment_plan_store()?;
+        if store.load_single(&plan_id).await?.is_none() {
+            return Err(Error::custom(format!("Payment plan {plan_id} not found")));
+        }
+        store.remove(&plan_id).await?;
+        self.store().commit(&wallet_secret).await?;
+
+        Ok(PaymentPlansCancelResponse {})
+    }
+
+    async fn payment_scheduler_start_call(
+        self: Arc<Self>,
+        request: PaymentSchedulerStartRequest,
+    ) -> Result<PaymentSchedulerStartResponse> {
+        let PaymentSchedulerStartRequest { wallet_secret, payment_secret } = request;
+
+        if !self.is_open() {
+            return Err(Error::WalletNotOpen);
+        }
+        // validates the wallet secret
+        self.store().commit(&wallet_secret).await?;
+        self.scheduler().start(wallet_secret, payment_secret);
+
+        Ok(PaymentSchedulerStartResponse {})
+    }
+
+    async fn payment_scheduler_stop_call(
+        self: Arc<Self>,
+        _request: PaymentSchedulerStopRequest,
+    ) -> Result<PaymentSchedulerStopResponse> {
+        self.scheduler().stop();
+        Ok(PaymentSchedulerStopResponse {})
+    }
+
     async fn address_book_enumerate_call(
         self: Arc<Self>,
         _request: AddressBookEnumerateRequest,
@@ -12,6 +12,7 @@
 pub mod api;
 pub mod args;
 pub mod maps;
+pub mod scheduler;
 pub use args::*;
 
 use crate::account::ScanNotifier;
@@ -21,11 +22,16 @@ use crate::error::Error::Custom;
 use crate::factory::try_load_account;
 use crate::imports::*;
 use crate::settings::{SettingsStore, WalletSettings};
+use crate::signer::SignerDevice;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::signer::SignerProcess;
 use crate::storage::interface::{OpenArgs, StorageDescriptor};
 use crate::storage::local::interface::LocalStore;
 use crate::storage::local::Storage;
+use crate::tx::PreparedPayoutBatch;
 use crate::wallet::keydata::PrvKeyDataVariantKind;
 use crate::wallet::maps::ActiveAccountMap;
+use crate::wallet::scheduler::PaymentScheduler;
 use kaspa_bip32::{ExtendedKey, Language, Mnemonic, Prefix as KeyPrefix, WordCount};
 use kaspa_notify::{
     listener::ListenerId,
@@ -103,7 +109,11 @@ struct Inner {
     multiplexer: Multiplexer<Box<Events>>,
     wallet_bus: Channel<WalletBusMessage>,
     estimation_abortables: Mutex<HashMap<AccountId, Abortable>>,
+    /// Payout batches prepared by the wallet API, awaiting submission on the next payout call
+    prepared_payouts: Mutex<HashMap<AccountId, PreparedPayoutBatch>>,
     retained_contexts: Mutex<HashMap<String, Arc<Vec<u8>>>>,
+    scheduler: PaymentScheduler,
+    signer_devices: Mutex<HashMap<String, Arc<dyn SignerDevice>>>,
     // Mutex used to protect concurrent access to accounts at the wallet api level
     guard: Arc<AsyncMutex<()>>,
     account_guard: Arc<AsyncMutex<()>>,
@@ -170,7 +180,10 @@ impl Wallet {
                 utxo_processor: utxo_processor.clone(),
                 wallet_bus,
                 estimation_abortables: Mutex::new(HashMap::new()),
+                prepared_payouts: Mutex::new(HashMap::new()),
                 retained_contexts: Mutex::new(HashMap::new()),
+                scheduler: PaymentScheduler::default(),
+                signer_devices: Mutex::new(HashMap::new()),
                 guard: Arc::new(AsyncMutex::new(())),
                 account_guard: Arc::new(AsyncMutex::new(())),
             }),
@@ -225,6 +238,39 @@ impl Wallet {
         self.store().descriptor()
     }
 
+    pub fn scheduler(&self) -> &PaymentScheduler {
+        &self.inner.scheduler
+    }
+
+    /// Registers a device signing for accounts bound to [`ExternalSigner::Device`](crate::signer::ExternalSigner::Device) with the same `id`
+    pub fn register_signer_device(&self, id: &str, device: Arc<dyn SignerDevice>) {
+        self.inner.signer_devices.lock().unwrap().insert(id.to_string(), device);
+    }
+
+    pub fn unregister_signer_device(&self, id: &str) {
+        self.inner.signer_devices.lock().unwrap().remove(id);
+    }
+
+    pub fn signer_device(&self, id: &str) -> Option<Arc<dyn SignerDevice>> {
+        self.inner.signer_devices.lock().unwrap().get(id).cloned()
+    }
+
+    /// Signing processes configured in the local settings, registered as signer devices when the settings are loaded
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn signer_processes(&self) -> HashMap<String, SignerProcess> {
+        self.settings().get(WalletSettings::Signers).unwrap_or_default()
+    }
+
+    /// Configures a signing process in the local settings and registers it as the signer device `id`.
+    /// The process is kept out of the wallet file, accounts only refer to it by its identifier.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn register_signer_process(&self, id: &str, process: SignerProcess) -> Result<()> {
+        let mut processes = self.signer_processes();
+        self.register_signer_device(id, Arc::new(process.device()));
+        processes.insert(id.to_string(), process);
+        self.settings().set(WalletSettings::Signers, processes).await
+    }
+
     pub fn store(&self) -> &Arc<dyn Interface> {
         &self.inner.store
     }
@@ -237,6 +283,7 @@ impl Wallet {
     }
 
     pub async fn reset(self: &Arc<Self>, clear_legacy_cache: bool) -> Result<()> {
+        self.scheduler().stop();
         self.utxo_processor().cleanup().await?;
 
         self.select(None).await?;
@@ -590,6 +637,11 @@ impl Wallet {
             }
         }
 
+        #[cfg(not(target_arch = "wasm32"))]
+        for (id, process) in self.signer_processes() {
+            self.register_signer_device(&id, Arc::new(process.device()));
+        }
+
         Ok(())
     }
 
@@ -658,6 +710,19 @@ impl Wallet {
         Ok(self.network_id()?.into())
     }
 
+    /// Checks whether a transaction of the account has reached the network: it is either
+    /// still tracked as outgoing, recorded in the transaction history or in the node mempool
+    pub(crate) async fn is_transaction_submitted(&self, account: &Arc<dyn Account>, transaction_id: &TransactionId) -> Result<bool> {
+        if account.utxo_context().processor().outgoing().contains_key(transaction_id) {
+            return Ok(true);
+        }
+        let binding = Binding::Account(*account.id());
+        if self.store().as_transaction_record_store()?.load_single(&binding, &self.network_id()?, transaction_id).await.is_ok() {
+            return Ok(true);
+        }
+        Ok(self.rpc_api().get_mempool_entry(*transaction_id, false, false).await.is_ok())
+    }
+
     pub fn default_port(&self) -> Result<Option<u16>> {
         let network_type = self.network_id()?;
         if let Some(wrpc_client) = self.try_wrpc_client() {
@@ -1102,6 +1167,10 @@ impl Wallet {
                 }
             }
 
+            Events::DaaScoreChange { current_daa_score } => {
+                self.process_payment_plans(*current_daa_score).await?;
+            }
+
             _ => {}
         }
 
@@ -0,0 +1,287 @@
+//!
+//! Execution of scheduled and recurring payments ([`PaymentPlan`]).
+//!
+//! Payment plans are persisted in the wallet storage. The scheduler needs
+//! the wallet secret (and optional payment secret) to sign transactions;
+//! it retains them in memory from [`PaymentScheduler::start`] until it is
+//! stopped or the wallet is closed. Due payments are executed when the wallet
+//! is open and the node is synced, the outcome is posted as [`Events::PaymentPlanExecuted`],
+//! [`Events::PaymentPlanDeferred`] or [`Events::PaymentPlanFailed`].
+//!
+//! The final transaction of a payment is recorded in the plan and committed to
+//! storage before it is submitted. A payment interrupted before the plan was
+//! advanced is therefore settled on the next attempt: if its transaction has
+//! reached the network the plan is advanced without paying again.
+//!
+
+use crate::imports::*;
+use crate::storage::{PaymentPlan, PaymentPlanId};
+use crate::tx::{Fees, Generator, GeneratorSettings, PaymentDestination};
+use workflow_core::time::unixtime_as_millis_u64;
+
+/// Delay before a deferred or failed payment is attempted again
+const RETRY_DELAY_MSEC: u64 = 60_000;
+
+#[derive(Clone)]
+struct Secrets {
+    wallet_secret: Secret,
+    payment_secret: Option<Secret>,
+}
+
+#[derive(Default)]
+pub struct PaymentScheduler {
+    secrets: Mutex<Option<Secrets>>,
+    executing: Mutex<HashSet<PaymentPlanId>>,
+    retry_after: Mutex<HashMap<PaymentPlanId, u64>>,
+}
+
+impl PaymentScheduler {
+    pub fn is_running(&self) -> bool {
+        self.secrets.lock().unwrap().is_some()
+    }
+
+    pub(crate) fn start(&self, wallet_secret: Secret, payment_secret: Option<Secret>) {
+        self.secrets.lock().unwrap().replace(Secrets { wallet_secret, payment_secret });
+    }
+
+    pub(crate) fn stop(&self) {
+        self.secrets.lock().unwrap().take();
+        self.retry_after.lock().unwrap().clear();
+    }
+
+    fn secrets(&self) -> Option<Secrets> {
+        self.secrets.lock().unwrap().clone()
+    }
+
+    /// Marks the plan as executing unless it already is or is waiting for a retry
+    fn try_begin(&self, id: &PaymentPlanId, unixtime_msec: u64) -> bool {
+        if self.retry_after.lock().unwrap().get(id).is_some_and(|retry_after| *retry_after > unixtime_msec) {
+            return false;
+        }
+        self.executing.lock().unwrap().insert(*id)
+    }
+
+    fn end(&self, id: &PaymentPlanId) {
+        self.executing.lock().unwrap().remove(id);
+    }
+
+    fn retry_later(&self, id: &PaymentPlanId) {
+        self.retry_after.lock().unwrap().insert(*id, unixtime_as_millis_u64() + RETRY_DELAY_MSEC);
+    }
+}
+
+impl Wallet {
+    /// Executes the payment plans due at the given DAA score
+    pub(crate) async fn process_payment_plans(self: &Arc<Self>, daa_score: u64) -> Result<()> {
+        if !self.scheduler().is_running() || !self.is_open() || !self.is_synced() {
+            return Ok(());
+        }
+
+        let now = unixtime_as_millis_u64();
+        let plans = self.store().as_payment_plan_store()?.load().await?;
+        for plan in plans.into_iter().filter(|plan| plan.is_due(now, daa_score)) {
+            if !self.scheduler().try_begin(&plan.id, now) {
+                continue;
+            }
+
+            let this = self.clone();
+            spawn(async move {
+                let PaymentPlan { id, account_id, .. } = plan;
+                if let Err(err) = this.execute_payment_plan(plan, daa_score).await {
+                    this.scheduler().retry_later(&id);
+                    this.notify(Events::PaymentPlanFailed { plan_id: id, account_id, message: err.to_string() })
+                        .await
+                        .unwrap_or_else(|err| log_error!("Wallet::execute_payment_plan() notify error: {err}"));
+                }
+                this.scheduler().end(&id);
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn execute_payment_plan(self: &Arc<Self>, plan: PaymentPlan, daa_score: u64) -> Result<()> {
+        let Some(Secrets { wallet_secret, payment_secret }) = self.scheduler().secrets() else {
+            return Ok(());
+        };
+
+        let account = {
+            let guard = self.guard();
+            let guard = guard.lock().await;
+            self.get_account_by_id(&plan.account_id, &guard).await?.ok_or(Error::AccountNotFound(plan.account_id))?
+        };
+
+        let store = self.store().as_payment_plan_store()?;
+        if let Some(transaction_id) = plan.pending_transaction_id {
+            let submitted = self.is_transaction_submitted(&account, &transaction_id).await?;
+            let Some(mut current) = store.load_single(&plan.id).await? else {
+                return Ok(());
+            };
+            if current.settle(submitted, unixtime_as_millis_u64(), daa_score).is_some() {
+                store.store(current).await?;
+                self.store().commit(&wallet_secret).await?;
+                self.notify(Events::PaymentPlanExecuted {
+                    plan_id: plan.id,
+                    account_id: plan.account_id,
+                    transaction_ids: vec![transaction_id],
+                    amount: plan.outputs.amount(),
+                    fees: 0,
+                })
+                .await?;
+                return Ok(());
+            }
+            store.store(current).await?;
+            self.store().commit(&wallet_secret).await?;
+        }
+
+        let fee_rate = if let Some(max_fee_rate) = plan.max_fee_rate {
+            let estimate = self.rpc_api().get_fee_estimate().await?;
+            let fee_rate = estimate.normal_buckets.first().unwrap_or(&estimate.priority_bucket).feerate;
+            if fee_rate > max_fee_rate {
+                self.scheduler().retry_later(&plan.id);
+                self.notify(Events::PaymentPlanDeferred { plan_id: plan.id, account_id: plan.account_id, fee_rate }).await?;
+                return Ok(());
+            }
+            Some(fee_rate)
+        } else {
+            None
+        };
+
+        let signer = account.clone().create_signer(wallet_secret.clone(), payment_secret).await?;
+        let destination = PaymentDestination::from(plan.outputs.clone());
+        let settings = GeneratorSettings::try_new_with_account(account.clone(), destination, fee_rate, Fees::SenderPays(0), None)?;
+        let abortable = Abortable::default();
+        let generator = Generator::try_new(settings, Some(signer), Some(&abortable))?;
+
+        let mut stream = generator.stream();
+        let mut transaction_ids = vec![];
+        while let Some(transaction) = stream.try_next().await? {
+            transaction.try_sign().await?;
+            if transaction.is_final() {
+                // the plan may have been paused or cancelled while the payment was being prepared
+                let now = unixtime_as_millis_u64();
+                let mut current = match store.load_single(&plan.id).await? {
+                    Some(current) if current.is_due(now, daa_score) => current,
+                    _ => return Ok(()),
+                };
+                current.begin(transaction.id());
+                store.store(current).await?;
+                self.store().commit(&wallet_secret).await?;
+            }
+            // a failed submission leaves the plan due, the pending transaction is settled on retry
+            transaction_ids.push(transaction.try_submit(&self.rpc_api()).await?);
+            yield_executor().await;
+        }
+        let summary = generator.summary();
+
+        if let Some(mut current) = store.load_single(&plan.id).await? {
+            current.advance(summary.final_transaction_id(), unixtime_as_millis_u64(), daa_score);
+            store.store(current).await?;
+            self.store().commit(&wallet_secret).await?;
+        }
+
+        self.notify(Events::PaymentPlanExecuted {
+            plan_id: plan.id,
+            account_id: plan.account_id,
+            transaction_ids,
+            amount: plan.outputs.amount(),
+            fees: summary.aggregate_fees(),
+        })
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::PaymentInterval;
+    use crate::tests::RpcCoreMock;
+    use crate::tx::{PaymentOutput, PaymentOutputs};
+    use crate::wallet::keydata::PrvKeyDataVariantKind;
+    use kaspa_consensus_core::subnets::SUBNETWORK_ID_NATIVE;
+    use kaspa_consensus_core::tx::Transaction;
+
+    #[test]
+    fn test_payment_scheduler_state() {
+        let scheduler = PaymentScheduler::default();
+        let id = PaymentPlanId::new();
+        let other = PaymentPlanId::new();
+
+        // a plan executes once at a time
+        assert!(scheduler.try_begin(&id, 0));
+        assert!(!scheduler.try_begin(&id, 0));
+        assert!(scheduler.try_begin(&other, 0));
+        scheduler.end(&id);
+        assert!(scheduler.try_begin(&id, 0));
+
+        // a deferred or failed plan is not attempted again before its retry delay elapses
+        scheduler.retry_later(&id);
+        scheduler.end(&id);
+        let now = unixtime_as_millis_u64();
+        assert!(!scheduler.try_begin(&id, now));
+        assert!(scheduler.try_begin(&id, now + RETRY_DELAY_MSEC + 1000));
+        scheduler.end(&id);
+
+        // stopping the scheduler drops the secrets and the pending retries
+        scheduler.start(Secret::from("secret"), None);
+        assert!(scheduler.is_running());
+        scheduler.retry_later(&id);
+        scheduler.stop();
+        assert!(!scheduler.is_running());
+        assert!(scheduler.try_begin(&id, now));
+    }
+
+    async fn create_wallet(rpc: &Arc<RpcCoreMock>, wallet_secret: &Secret) -> Result<(Arc<Wallet>, Arc<dyn Account>)> {
+        let network_id = NetworkId::with_suffix(NetworkType::Testnet, 10);
+        let wallet = Arc::new(Wallet::try_with_rpc(Some(rpc.clone().into()), Wallet::resident_store()?, Some(network_id))?);
+        wallet.create_wallet(wallet_secret, WalletCreateArgs::new(None, None, EncryptionKind::XChaCha20Poly1305, None, true)).await?;
+        let prv_key_data_args =
+            PrvKeyDataCreateArgs::new(None, None, Secret::from([1u8; 32].as_slice()), PrvKeyDataVariantKind::SecretKey);
+        let prv_key_data_id = wallet.create_prv_key_data(wallet_secret, prv_key_data_args).await?;
+        let account = wallet.create_account_keypair(wallet_secret, None, prv_key_data_id, None, false).await?;
+        wallet.scheduler().start(wallet_secret.clone(), None);
+        Ok((wallet, account))
+    }
+
+    #[tokio::test]
+    async fn test_payment_plan_settlement() -> Result<()> {
+        let rpc = Arc::new(RpcCoreMock::new());
+        let wallet_secret = Secret::from("secret");
+        let (wallet, account) = create_wallet(&rpc, &wallet_secret).await?;
+        let store = wallet.store().as_payment_plan_store()?;
+
+        let outputs = PaymentOutputs { outputs: vec![PaymentOutput::new(account.receive_address()?, 100_000_000)] };
+        let mut plan = PaymentPlan::new(*account.id(), None, outputs, PaymentInterval::DaaScore(100), None, Some(2), 1000);
+
+        // the payment was interrupted after its transaction reached the node: the plan is advanced without
+        // paying again, which would fail as the account has no funds
+        let transaction = Transaction::new(0, vec![], vec![], 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        rpc.add_mempool_transaction(&transaction);
+        plan.begin(transaction.id());
+        store.store(plan.clone()).await?;
+        wallet.execute_payment_plan(plan.clone(), 1000).await?;
+
+        let settled = store.load_single(&plan.id).await?.unwrap();
+        assert_eq!(settled.pending_transaction_id, None);
+        assert_eq!(settled.last_transaction_id, Some(transaction.id()));
+        assert_eq!(settled.executions, 1);
+        assert_eq!(settled.remaining, Some(1));
+        assert!(!settled.is_due(0, 1000));
+
+        // the transaction of the next payment never reached the node: the payment is attempted again
+        let mut plan = settled;
+        plan.begin(TransactionId::from_u64_word(1));
+        store.store(plan.clone()).await?;
+        let result = wallet.execute_payment_plan(plan.clone(), plan.next).await;
+        assert!(matches!(result, Err(Error::InsufficientFunds { .. })), "unexpected result {result:?}");
+
+        let retried = store.load_single(&plan.id).await?.unwrap();
+        assert_eq!(retried.pending_transaction_id, None);
+        assert_eq!(retried.executions, 1);
+        assert!(retried.is_due(0, plan.next));
+
+        Ok(())
+    }
+}
@@ -4,7 +4,8 @@ use super::extensions::*;
 use crate::account::descriptor::IAccountDescriptor;
 use crate::api::message::*;
 use crate::imports::*;
-use crate::tx::{Fees, PaymentDestination, PaymentOutputs};
+use crate::signer::ExternalSigner;
+use crate::tx::{Fees, PaymentDestination, PaymentOutputs, PayoutJob, UtxoSelection};
 use crate::wasm::api::keydata::PrvKeyDataVariantKind;
 use crate::wasm::tx::fees::IFees;
 use crate::wasm::tx::GeneratorSummary;
@@ -1379,6 +1380,33 @@ try_from! ( args: AccountsCreateNewAddressResponse, IAccountsCreateNewAddressRes
 
 // ---
 
+declare! {
+    IUtxoSelection,
+    r#"
+    /**
+     * UTXO selection strategy and coin control used by the transaction generator.
+     *
+     * @category Wallet API
+     */
+    export interface IUtxoSelection {
+        /**
+         * One of `smallest-first` (default), `largest-first`, `branch-and-bound` or `single-address`.
+         */
+        strategy? : string;
+        /**
+         * Outpoints that must be spent by the transaction.
+         */
+        include? : ITransactionOutpoint[];
+        /**
+         * Outpoints that must not be spent by the transaction.
+         */
+        exclude? : ITransactionOutpoint[];
+    }
+    "#,
+}
+
+// ---
+
 declare! {
     IAccountsSendRequest,
     r#"
@@ -1416,6 +1444,10 @@ declare! {
          * If not supplied, the destination will be the change address resulting in a UTXO compound transaction.
          */
         destination? : IPaymentOutput[];
+        /**
+         * Optional UTXO selection strategy and coin control.
+         */
+        utxoSelection? : IUtxoSelection;
     }
     "#,
 }
@@ -1432,7 +1464,9 @@ try_from! ( args: IAccountsSendRequest, AccountsSendRequest, {
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
 
-    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, fee_rate, priority_fee_sompi, destination, payload })
+    let utxo_selection = args.try_get_value("utxoSelection")?.map(from_value::<UtxoSelection>).transpose()?;
+
+    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, fee_rate, priority_fee_sompi, destination, payload, utxo_selection })
 });
 
 declare! {
@@ -1774,6 +1808,7 @@ declare! {
         feeRate? : number;
         priorityFeeSompi : IFees | bigint;
         payload? : Uint8Array | string;
+        utxoSelection? : IUtxoSelection;
     }
     "#,
 }
@@ -1788,7 +1823,9 @@ try_from! ( args: IAccountsEstimateRequest, AccountsEstimateRequest, {
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
 
-    Ok(AccountsEstimateRequest { account_id, fee_rate, priority_fee_sompi, destination, payload })
+    let utxo_selection = args.try_get_value("utxoSelection")?.map(from_value::<UtxoSelection>).transpose()?;
+
+    Ok(AccountsEstimateRequest { account_id, fee_rate, priority_fee_sompi, destination, payload, utxo_selection })
 });
 
 declare! {
@@ -1813,6 +1850,268 @@ try_from! ( args: AccountsEstimateResponse, IAccountsEstimateResponse, {
 
 // ---
 
+declare! {
+    IAccountsFreezeUtxosRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsFreezeUtxosRequest {
+        accountId : HexString;
+        walletSecret : string;
+        /**
+         * Outpoints to freeze or unfreeze. Frozen UTXOs are never selected by the transaction generator.
+         */
+        outpoints : ITransactionOutpoint[];
+        /**
+         * `true` to freeze the outpoints, `false` to release them.
+         */
+        freeze : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsFreezeUtxosRequest, AccountsFreezeUtxosRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let outpoints = from_value::<Vec<kaspa_consensus_core::tx::TransactionOutpoint>>(args.get_value("outpoints")?)?;
+    let freeze = args.get_bool("freeze")?;
+    Ok(AccountsFreezeUtxosRequest { account_id, wallet_secret, outpoints, freeze })
+});
+
+declare! {
+    IAccountsFreezeUtxosResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsFreezeUtxosResponse {
+        /**
+         * All outpoints currently frozen in the account.
+         */
+        frozenUtxos : ITransactionOutpoint[];
+    }
+    "#,
+}
+
+try_from! ( args: AccountsFreezeUtxosResponse, IAccountsFreezeUtxosResponse, {
+    let response = IAccountsFreezeUtxosResponse::default();
+    response.set("frozenUtxos", &to_value(&args.frozen_utxos)?)?;
+    Ok(response)
+});
+
+declare! {
+    IExternalSigner,
+    r#"
+    /**
+     * External signer holding the keys of an account. Refers to a signer
+     * device registered with the wallet under the given id.
+     * 
+     * @category Wallet API
+     */
+    export type IExternalSigner =
+        { kind : "device", id : string };
+    "#,
+}
+
+declare! {
+    IAccountsSetExternalSignerRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsSetExternalSignerRequest {
+        accountId : HexString;
+        walletSecret : string;
+        /**
+         * External signer of the account, omit to sign with the wallet private key data.
+         */
+        externalSigner? : IExternalSigner;
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsSetExternalSignerRequest, AccountsSetExternalSignerRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let external_signer = args.try_get_value("externalSigner")?.map(from_value::<ExternalSigner>).transpose()?;
+    Ok(AccountsSetExternalSignerRequest { account_id, wallet_secret, external_signer })
+});
+
+declare! {
+    IAccountsSetExternalSignerResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsSetExternalSignerResponse { }
+    "#,
+}
+
+try_from! ( _args: AccountsSetExternalSignerResponse, IAccountsSetExternalSignerResponse, {
+    Ok(IAccountsSetExternalSignerResponse::default())
+});
+
+declare! {
+    IPayoutJob,
+    r#"
+    /**
+     * Payouts packed into mass-compliant batch transactions.
+     * Batches with transaction ids have been submitted.
+     * 
+     * @category Wallet API
+     */
+    export interface IPayoutJob {
+        accountId : HexString;
+        networkId : NetworkId;
+        batches : IPayoutBatch[];
+    }
+
+    /**
+     * Set of payouts submitted as a single payment.
+     * 
+     * @category Wallet API
+     */
+    export interface IPayoutBatch {
+        outputs : { outputs : IPaymentOutput[] };
+        /**
+         * Compute mass of the batch transaction excluding its inputs.
+         */
+        computeMass : bigint;
+        /**
+         * Upper bound of the storage mass of the batch transaction.
+         */
+        storageMass : bigint;
+        /**
+         * Estimated fees, or actual fees once the batch is submitted.
+         */
+        fees? : bigint;
+        /**
+         * Transactions of the batch, recorded before they are submitted.
+         */
+        transactionIds : HexString[];
+        finalTransactionId? : HexString;
+        /**
+         * Set while the batch is being submitted or if its submission was interrupted.
+         */
+        pending? : boolean;
+    }
+    "#,
+}
+
+declare! {
+    IAccountsPayoutEstimateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsPayoutEstimateRequest {
+        accountId : HexString;
+        outputs : IPaymentOutput[];
+        feeRate? : number;
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsPayoutEstimateRequest, AccountsPayoutEstimateRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let outputs = PaymentOutputs::try_owned_from(args.get_value("outputs")?)?;
+    let fee_rate = args.get_f64("feeRate").ok();
+    Ok(AccountsPayoutEstimateRequest { account_id, outputs, fee_rate })
+});
+
+declare! {
+    IAccountsPayoutEstimateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsPayoutEstimateResponse {
+        job : IPayoutJob;
+    }
+    "#,
+}
+
+try_from! ( args: AccountsPayoutEstimateResponse, IAccountsPayoutEstimateResponse, {
+    let response = IAccountsPayoutEstimateResponse::default();
+    response.set("job", &to_value(&args.job)?)?;
+    Ok(response)
+});
+
+declare! {
+    IAccountsPayoutSendRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsPayoutSendRequest {
+        walletSecret : string;
+        paymentSecret? : string;
+        feeRate? : number;
+        /**
+         * Payout job obtained from the payout estimate or returned by the previous call.
+         * Submitted batches are skipped.
+         */
+        job : IPayoutJob;
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsPayoutSendRequest, AccountsPayoutSendRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let payment_secret = args.try_get_secret("paymentSecret")?;
+    let fee_rate = args.get_f64("feeRate").ok();
+    let job = from_value::<PayoutJob>(args.get_value("job")?)?;
+    Ok(AccountsPayoutSendRequest { wallet_secret, payment_secret, fee_rate, job })
+});
+
+declare! {
+    IAccountsPayoutSendResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsPayoutSendResponse {
+        /**
+         * Payout job updated by this step. It must be persisted before being supplied
+         * to the next call, until all of its batches are submitted.
+         */
+        job : IPayoutJob;
+        /**
+         * Error that interrupted the payout. The job can be resumed by supplying it again.
+         */
+        error? : string;
+    }
+    "#,
+}
+
+try_from! ( args: AccountsPayoutSendResponse, IAccountsPayoutSendResponse, {
+    let response = IAccountsPayoutSendResponse::default();
+    response.set("job", &to_value(&args.job)?)?;
+    if let Some(error) = args.error {
+        response.set("error", &error.into())?;
+    }
+    Ok(response)
+});
+
+// ---
+
 declare! {
     IFeeRateEstimateBucket,
     r#"
@@ -2119,6 +2418,540 @@ try_from! ( _args: TransactionsReplaceMetadataResponse, ITransactionsReplaceMeta
 
 // ---
 
+declare! {
+    ITransactionsExportRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ITransactionsExportRequest {
+        accountId : HexString;
+        networkId : NetworkId | string;
+    }
+    "#,
+}
+
+try_from! ( args: ITransactionsExportRequest, TransactionsExportRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let network_id = args.get_network_id("networkId")?;
+    Ok(TransactionsExportRequest { account_id, network_id })
+});
+
+declare! {
+    ITransactionsExportResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ITransactionsExportResponse {
+        /**
+         * Transaction history formatted as CSV with the
+         * `date,daa_score,transaction_id,kind,amount,fee,currency,label,note` columns.
+         */
+        csv : string;
+    }
+    "#,
+}
+
+try_from! ( args: TransactionsExportResponse, ITransactionsExportResponse, {
+    let response = ITransactionsExportResponse::default();
+    response.set("csv", &args.csv.into())?;
+    Ok(response)
+});
+
+// ---
+
+declare! {
+    ILabel,
+    r#"
+    /**
+     * Label attached to an address (`addr`), an outpoint (`output`)
+     * formatted as `<transaction id>:<index>` or a transaction (`tx`).
+     * Labels follow the BIP-329 record format.
+     *
+     * @category Wallet API
+     */
+    export interface ILabel {
+        type : "addr" | "output" | "tx";
+        ref : string;
+        label : string;
+        origin? : string;
+    }
+    "#,
+}
+
+declare! {
+    ILabelsEnumerateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsEnumerateRequest { }
+    "#,
+}
+
+try_from! ( _args: ILabelsEnumerateRequest, LabelsEnumerateRequest, {
+    Ok(LabelsEnumerateRequest {})
+});
+
+declare! {
+    ILabelsEnumerateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsEnumerateResponse {
+        labels : ILabel[];
+    }
+    "#,
+}
+
+try_from! ( args: LabelsEnumerateResponse, ILabelsEnumerateResponse, {
+    let response = ILabelsEnumerateResponse::default();
+    response.set("labels", &to_value(&args.labels)?)?;
+    Ok(response)
+});
+
+declare! {
+    ILabelsUpdateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsUpdateRequest {
+        walletSecret : string;
+        /**
+         * Labels to insert or replace. A label with an empty text is removed.
+         */
+        labels : ILabel[];
+    }
+    "#,
+}
+
+try_from! ( args: ILabelsUpdateRequest, LabelsUpdateRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let labels = from_value::<Vec<Label>>(args.get_value("labels")?)?;
+    Ok(LabelsUpdateRequest { wallet_secret, labels })
+});
+
+declare! {
+    ILabelsUpdateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsUpdateResponse { }
+    "#,
+}
+
+try_from! ( _args: LabelsUpdateResponse, ILabelsUpdateResponse, {
+    Ok(ILabelsUpdateResponse::default())
+});
+
+declare! {
+    ILabelsImportRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsImportRequest {
+        walletSecret : string;
+        /**
+         * BIP-329 JSON Lines.
+         */
+        data : string;
+    }
+    "#,
+}
+
+try_from! ( args: ILabelsImportRequest, LabelsImportRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let data = args.get_string("data")?;
+    Ok(LabelsImportRequest { wallet_secret, data })
+});
+
+declare! {
+    ILabelsImportResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsImportResponse {
+        imported : number;
+        /**
+         * Number of records skipped because their type is not supported by the wallet.
+         */
+        skipped : number;
+    }
+    "#,
+}
+
+try_from! ( args: LabelsImportResponse, ILabelsImportResponse, {
+    let response = ILabelsImportResponse::default();
+    response.set("imported", &JsValue::from(args.imported as f64))?;
+    response.set("skipped", &JsValue::from(args.skipped as f64))?;
+    Ok(response)
+});
+
+declare! {
+    ILabelsExportRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsExportRequest { }
+    "#,
+}
+
+try_from! ( _args: ILabelsExportRequest, LabelsExportRequest, {
+    Ok(LabelsExportRequest {})
+});
+
+declare! {
+    ILabelsExportResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface ILabelsExportResponse {
+        /**
+         * BIP-329 JSON Lines.
+         */
+        data : string;
+    }
+    "#,
+}
+
+try_from! ( args: LabelsExportResponse, ILabelsExportResponse, {
+    let response = ILabelsExportResponse::default();
+    response.set("data", &args.data.into())?;
+    Ok(response)
+});
+
+declare! {
+    IPaymentPlan,
+    r#"
+    /**
+     * Scheduled or recurring payment executed by the wallet payment scheduler.
+     * 
+     * @category Wallet API
+     */
+    export interface IPaymentPlan {
+        id : string;
+        accountId : HexString;
+        name? : string;
+        outputs : IPaymentOutput[];
+        interval : IPaymentInterval;
+        /**
+         * Maximum fee rate in sompi per gram of mass.
+         */
+        maxFeeRate? : number;
+        /**
+         * Number of payments left, undefined for an open-ended plan.
+         */
+        remaining? : number;
+        /**
+         * Unix time in milliseconds or DAA score (depending on the interval) of the next payment.
+         */
+        next : bigint;
+        paused : boolean;
+        executions : number;
+        lastTransactionId? : HexString;
+    }
+
+    /**
+     * Period between two payments of a payment plan, in seconds or in DAA score units.
+     * 
+     * @category Wallet API
+     */
+    export interface IPaymentInterval {
+        kind : "seconds" | "daa-score";
+        value : number;
+    }
+    "#,
+}
+
+declare! {
+    IPaymentPlansCreateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentPlansCreateRequest {
+        walletSecret : string;
+        accountId : HexString;
+        name? : string;
+        outputs : IPaymentOutput[];
+        interval : IPaymentInterval;
+        /**
+         * Payments are deferred while the network fee rate is above this value.
+         */
+        maxFeeRate? : number;
+        /**
+         * Number of payments, undefined for an open-ended plan.
+         */
+        runs? : number;
+        /**
+         * Unix time in milliseconds or DAA score (depending on the interval)
+         * of the first payment. The first payment is executed immediately
+         * if not specified.
+         */
+        start? : bigint;
+    }
+    "#,
+}
+
+try_from! ( args: IPaymentPlansCreateRequest, PaymentPlansCreateRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let account_id = args.get_account_id("accountId")?;
+    let name = args.try_get_string("name")?;
+    let outputs = PaymentOutputs::try_owned_from(args.get_value("outputs")?)?;
+    let interval = from_value::<PaymentInterval>(args.get_value("interval")?)?;
+    let max_fee_rate = args.get_f64("maxFeeRate").ok();
+    let runs = args.get_u32("runs").ok();
+    let start = args.get_u64("start").ok();
+    Ok(PaymentPlansCreateRequest { wallet_secret, account_id, name, outputs, interval, max_fee_rate, runs, start })
+});
+
+declare! {
+    IPaymentPlansCreateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentPlansCreateResponse {
+        plan : IPaymentPlan;
+    }
+    "#,
+}
+
+try_from! ( args: PaymentPlansCreateResponse, IPaymentPlansCreateResponse, {
+    let response = IPaymentPlansCreateResponse::default();
+    response.set("plan", &to_value(&args.plan)?)?;
+    Ok(response)
+});
+
+declare! {
+    IPaymentPlansEnumerateRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentPlansEnumerateRequest {
+        /**
+         * Only list the plans of the given account.
+         */
+        accountId? : HexString;
+    }
+    "#,
+}
+
+try_from! ( args: IPaymentPlansEnumerateRequest, PaymentPlansEnumerateRequest, {
+    let account_id = args.try_get_value("accountId")?.map(|account_id| AccountId::try_from(&account_id)).transpose()?;
+    Ok(PaymentPlansEnumerateRequest { account_id })
+});
+
+declare! {
+    IPaymentPlansEnumerateResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentPlansEnumerateResponse {
+        plans : IPaymentPlan[];
+    }
+    "#,
+}
+
+try_from! ( args: PaymentPlansEnumerateResponse, IPaymentPlansEnumerateResponse, {
+    let response = IPaymentPlansEnumerateResponse::default();
+    response.set("plans", &to_value(&args.plans)?)?;
+    Ok(response)
+});
+
+declare! {
+    IPaymentPlansPauseRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentPlansPauseRequest {
+        walletSecret : string;
+        planId : string;
+        /**
+         * `true` to pause the plan, `false` to resume it.
+         */
+        pause : boolean;
+    }
+    "#,
+}
+
+try_from! ( args: IPaymentPlansPauseRequest, PaymentPlansPauseRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let plan_id = args.get_string("planId")?.parse()?;
+    let pause = args.get_bool("pause")?;
+    Ok(PaymentPlansPauseRequest { wallet_secret, plan_id, pause })
+});
+
+declare! {
+    IPaymentPlansPauseResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentPlansPauseResponse {
+        plan : IPaymentPlan;
+    }
+    "#,
+}
+
+try_from! ( args: PaymentPlansPauseResponse, IPaymentPlansPauseResponse, {
+    let response = IPaymentPlansPauseResponse::default();
+    response.set("plan", &to_value(&args.plan)?)?;
+    Ok(response)
+});
+
+declare! {
+    IPaymentPlansCancelRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentPlansCancelRequest {
+        walletSecret : string;
+        planId : string;
+    }
+    "#,
+}
+
+try_from! ( args: IPaymentPlansCancelRequest, PaymentPlansCancelRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let plan_id = args.get_string("planId")?.parse()?;
+    Ok(PaymentPlansCancelRequest { wallet_secret, plan_id })
+});
+
+declare! {
+    IPaymentPlansCancelResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentPlansCancelResponse { }
+    "#,
+}
+
+try_from! ( _args: PaymentPlansCancelResponse, IPaymentPlansCancelResponse, {
+    Ok(IPaymentPlansCancelResponse::default())
+});
+
+declare! {
+    IPaymentSchedulerStartRequest,
+    r#"
+    /**
+     * The payment scheduler retains the supplied secrets in memory
+     * until it is stopped or the wallet is closed.
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentSchedulerStartRequest {
+        walletSecret : string;
+        paymentSecret? : string;
+    }
+    "#,
+}
+
+try_from! ( args: IPaymentSchedulerStartRequest, PaymentSchedulerStartRequest, {
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let payment_secret = args.try_get_secret("paymentSecret")?;
+    Ok(PaymentSchedulerStartRequest { wallet_secret, payment_secret })
+});
+
+declare! {
+    IPaymentSchedulerStartResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentSchedulerStartResponse { }
+    "#,
+}
+
+try_from! ( _args: PaymentSchedulerStartResponse, IPaymentSchedulerStartResponse, {
+    Ok(IPaymentSchedulerStartResponse::default())
+});
+
+declare! {
+    IPaymentSchedulerStopRequest,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentSchedulerStopRequest { }
+    "#,
+}
+
+try_from! ( _args: IPaymentSchedulerStopRequest, PaymentSchedulerStopRequest, {
+    Ok(PaymentSchedulerStopRequest {})
+});
+
+declare! {
+    IPaymentSchedulerStopResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IPaymentSchedulerStopResponse { }
+    "#,
+}
+
+try_from! ( _args: PaymentSchedulerStopResponse, IPaymentSchedulerStopResponse, {
+    Ok(IPaymentSchedulerStopResponse::default())
+});
+
+// ---
+
 declare! {
     IAddressBookEnumerateRequest,
     r#"
@@ -61,4 +61,19 @@ declare_wasm_handlers!([
     FeeRatePollerDisable,
     AccountsCommitReveal,
     AccountsCommitRevealManual,
+    AccountsFreezeUtxos,
+    AccountsSetExternalSigner,
+    AccountsPayoutEstimate,
+    AccountsPayoutSend,
+    TransactionsExport,
+    LabelsEnumerate,
+    LabelsUpdate,
+    LabelsImport,
+    LabelsExport,
+    PaymentPlansCreate,
+    PaymentPlansEnumerate,
+    PaymentPlansPause,
+    PaymentPlansCancel,
+    PaymentSchedulerStart,
+    PaymentSchedulerStop,
 ]);
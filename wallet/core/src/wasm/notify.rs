@@ -132,6 +132,10 @@ cfg_if! {
             Balance = "balance",
             Error = "error",
             FeeRate = "fee-rate",
+            PayoutProgress = "payout-progress",
+            PaymentPlanExecuted = "payment-plan-executed",
+            PaymentPlanDeferred = "payment-plan-deferred",
+            PaymentPlanFailed = "payment-plan-failed",
         }
 
         /**
@@ -169,6 +173,10 @@ cfg_if! {
             "balance": IBalanceEvent,
             "error": IErrorEvent,
             "fee-rate": IFeeRateEvent,
+            "payout-progress": IPayoutProgressEvent,
+            "payment-plan-executed": IPaymentPlanExecutedEvent,
+            "payment-plan-deferred": IPaymentPlanDeferredEvent,
+            "payment-plan-failed": IPaymentPlanFailedEvent,
         }
         
         /**
@@ -332,6 +340,82 @@ declare! {
     "#,
 }
 
+#[cfg(feature = "wasm32-sdk")]
+declare! {
+    IPayoutProgressEvent,
+    r#"
+    /**
+     * Emitted by {@link Wallet} when a batch of a payout job has been submitted.
+     * 
+     * @category Wallet Events
+     */
+    export interface IPayoutProgressEvent {
+        accountId: HexString,
+        /**
+         * Position of the submitted batch in the payout job.
+         */
+        index: number,
+        batch: IPayoutBatch,
+        submitted: number,
+        batches: number,
+    }
+    "#,
+}
+
+#[cfg(feature = "wasm32-sdk")]
+declare! {
+    IPaymentPlanExecutedEvent,
+    r#"
+    /**
+     * Emitted by {@link Wallet} when a scheduled payment has been submitted.
+     * 
+     * @category Wallet Events
+     */
+    export interface IPaymentPlanExecutedEvent {
+        planId: string,
+        accountId: HexString,
+        transactionIds: HexString[],
+        amount: bigint,
+        fees: bigint,
+    }
+    "#,
+}
+
+#[cfg(feature = "wasm32-sdk")]
+declare! {
+    IPaymentPlanDeferredEvent,
+    r#"
+    /**
+     * Emitted by {@link Wallet} when a scheduled payment is postponed
+     * because the network fee rate exceeds the plan maximum fee rate.
+     * 
+     * @category Wallet Events
+     */
+    export interface IPaymentPlanDeferredEvent {
+        planId: string,
+        accountId: HexString,
+        feeRate: number,
+    }
+    "#,
+}
+
+#[cfg(feature = "wasm32-sdk")]
+declare! {
+    IPaymentPlanFailedEvent,
+    r#"
+    /**
+     * Emitted by {@link Wallet} when a scheduled payment fails. The payment is retried later.
+     * 
+     * @category Wallet Events
+     */
+    export interface IPaymentPlanFailedEvent {
+        planId: string,
+        accountId: HexString,
+        message: string,
+    }
+    "#,
+}
+
 #[cfg(feature = "wasm32-sdk")]
 declare! {
     IWalletCreateEvent,
@@ -0,0 +1,47 @@
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("{0}")]
+    Other(String),
+
+    #[error("Invalid account '{0}', expected <name>:<xpub>")]
+    InvalidAccount(String),
+
+    #[error("Duplicate account name '{0}'")]
+    DuplicateAccount(String),
+
+    #[error("Unknown account '{0}'")]
+    UnknownAccount(String),
+
+    #[error("Invalid HTTP request: {0}")]
+    InvalidRequest(&'static str),
+
+    #[error("A webhook secret is required, provide it with --webhook-secret-file or the {0} environment variable")]
+    MissingWebhookSecret(&'static str),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+
+    #[error(transparent)]
+    Wallet(#[from] kaspa_wallet_core::error::Error),
+
+    #[error(transparent)]
+    WalletKeys(#[from] kaspa_wallet_keys::error::Error),
+
+    #[error(transparent)]
+    Wrpc(#[from] kaspa_wrpc_client::error::Error),
+
+    #[error(transparent)]
+    NetworkId(#[from] kaspa_consensus_core::network::NetworkIdError),
+}
+
+impl From<String> for Error {
+    fn from(s: String) -> Self {
+        Error::Other(s)
+    }
+}
@@ -0,0 +1,91 @@
+mod error;
+mod result;
+mod server;
+mod state;
+mod watcher;
+mod webhook;
+
+use clap::Parser;
+use error::Error;
+use kaspa_consensus_core::network::NetworkId;
+use result::Result;
+use std::path::PathBuf;
+use watcher::{Watcher, WatcherArgs};
+use webhook::Webhook;
+use workflow_log::*;
+
+#[derive(Debug, Parser)]
+#[clap(name = "kaspa-wallet-watch")]
+#[clap(version)]
+struct Args {
+    /// Network id (mainnet, testnet-10, ...)
+    #[clap(long, default_value = "mainnet")]
+    network: String,
+    /// wRPC Borsh url of the node (defaults to the local node of the network)
+    #[clap(long)]
+    rpcserver: Option<String>,
+    /// Watch-only account as <name>:<xpub>, may be repeated
+    #[clap(long = "account", required = true)]
+    accounts: Vec<String>,
+    /// interface:port of the HTTP API
+    #[clap(long, default_value = "127.0.0.1:8190")]
+    listen: String,
+    /// URL receiving the payment notifications
+    #[clap(long)]
+    webhook_url: Option<String>,
+    /// File holding the secret used to sign the notifications with HMAC-SHA256,
+    /// the secret is otherwise read from the KASPA_WATCH_WEBHOOK_SECRET environment variable
+    #[clap(long)]
+    webhook_secret_file: Option<PathBuf>,
+    /// Depth in DAA score units after which a payment is reported as confirmed
+    #[clap(long, default_value_t = 100)]
+    confirmations: u64,
+    /// File persisting the derived receive addresses and the payment notifications
+    #[clap(long)]
+    state: Option<PathBuf>,
+}
+
+/// Environment variable holding the webhook secret when no secret file is provided
+const WEBHOOK_SECRET_ENV: &str = "KASPA_WATCH_WEBHOOK_SECRET";
+
+/// Reads the webhook secret, keeping it off the command line where other users could see it
+fn webhook_secret(file: Option<PathBuf>) -> Result<String> {
+    let secret = match file {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => std::env::var(WEBHOOK_SECRET_ENV).unwrap_or_default(),
+    };
+    let secret = secret.trim();
+    if secret.is_empty() {
+        return Err(Error::MissingWebhookSecret(WEBHOOK_SECRET_ENV));
+    }
+    Ok(secret.to_string())
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let Args { network, rpcserver, accounts, listen, webhook_url, webhook_secret_file, confirmations, state } = Args::parse();
+
+    let network_id: NetworkId = network.parse()?;
+    let rpc_url = rpcserver.unwrap_or_else(|| format!("wrpc://127.0.0.1:{}", network_id.network_type().default_borsh_rpc_port()));
+    let accounts = accounts
+        .into_iter()
+        .map(|account| match account.split_once(':') {
+            Some((name, xpub)) if !name.is_empty() && !xpub.is_empty() => Ok((name.to_string(), xpub.to_string())),
+            _ => Err(Error::InvalidAccount(account)),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if webhook_url.is_none() {
+        log_warn!("No webhook url provided, payments will only be logged");
+    }
+    let webhook = webhook_url.map(|url| Webhook::new(url, webhook_secret(webhook_secret_file)?)).transpose()?;
+
+    let watcher = Watcher::try_new(WatcherArgs { network_id, rpc_url, accounts, webhook, confirmations, state })?;
+    watcher.start().await?;
+
+    tokio::select! {
+        result = server::run(watcher.clone(), &listen) => result?,
+        _ = tokio::signal::ctrl_c() => log_info!("Shutting down..."),
+    }
+
+    watcher.stop().await
+}
@@ -0,0 +1,191 @@
+//!
+//! Minimal local HTTP API of the watch server.
+//!
+//! - `GET /accounts` lists the accounts with their balances
+//! - `GET /accounts/<name>` returns a single account
+//! - `GET /accounts/<name>/addresses` lists the derived receive addresses
+//! - `POST /accounts/<name>/addresses` derives and starts monitoring a new receive address
+//!
+//! The API is meant to be exposed to the merchant backend only and is not authenticated.
+//!
+
+use crate::error::Error;
+use crate::result::Result;
+use crate::watcher::Watcher;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use workflow_log::*;
+
+/// Upper bound of the size of the request line and headers
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+/// Upper bound of the size of the request body, none of the routes expecting one
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Route<'a> {
+    Accounts,
+    Account(&'a str),
+    Addresses(&'a str),
+    NewAddress(&'a str),
+}
+
+impl<'a> Route<'a> {
+    fn parse(method: &str, path: &'a str) -> Option<Self> {
+        let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+        match (method, segments.as_slice()) {
+            ("GET", ["accounts"]) => Some(Route::Accounts),
+            ("GET", ["accounts", name]) => Some(Route::Account(name)),
+            ("GET", ["accounts", name, "addresses"]) => Some(Route::Addresses(name)),
+            ("POST", ["accounts", name, "addresses"]) => Some(Route::NewAddress(name)),
+            _ => None,
+        }
+    }
+}
+
+pub async fn run(watcher: Arc<Watcher>, listen: &str) -> Result<()> {
+    let listener = TcpListener::bind(listen).await?;
+    log_info!("HTTP API listening on {listen}");
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let watcher = watcher.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(watcher, stream).await {
+                log_warn!("HTTP API error: {err}");
+            }
+        });
+    }
+}
+
+/// Reads a request up to the end of its body as announced by its `Content-Length` header,
+/// returning its head, being the request line and the headers
+async fn read_request<R: AsyncRead + Unpin>(stream: &mut R) -> Result<String> {
+    let mut buffer = vec![];
+    let mut chunk = [0; 4096];
+    let head_end = loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end;
+        }
+        if buffer.len() > MAX_HEAD_SIZE {
+            return Err(Error::InvalidRequest("headers too large"));
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(Error::InvalidRequest("connection closed before the end of the headers"));
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    };
+    let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+
+    let content_length = head
+        .lines()
+        .skip(1)
+        .filter_map(|line| line.split_once(':'))
+        .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+        .map(|(_, value)| value.trim().parse::<usize>().map_err(|_| Error::InvalidRequest("invalid content length")))
+        .transpose()?
+        .unwrap_or_default();
+    if content_length > MAX_BODY_SIZE {
+        return Err(Error::InvalidRequest("body too large"));
+    }
+    // the body is drained so that the response is not sent before the request has been fully received
+    let mut remaining = (head_end + 4 + content_length).saturating_sub(buffer.len());
+    while remaining > 0 {
+        let n = stream.read(&mut chunk[..remaining.min(4096)]).await?;
+        if n == 0 {
+            return Err(Error::InvalidRequest("connection closed before the end of the body"));
+        }
+        remaining -= n;
+    }
+    Ok(head)
+}
+
+async fn handle_connection(watcher: Arc<Watcher>, mut stream: TcpStream) -> Result<()> {
+    let request = match read_request(&mut stream).await {
+        Ok(request) => request,
+        Err(err @ Error::InvalidRequest(_)) => {
+            respond(&mut stream, "400 Bad Request", json!({ "error": err.to_string() })).await?;
+            return Err(err);
+        }
+        Err(err) => return Err(err),
+    };
+    let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+
+    let (status, body) = match Route::parse(method, path) {
+        Some(route) => match handle_route(&watcher, route).await {
+            Ok(body) => ("200 OK", body),
+            Err(err @ Error::UnknownAccount(_)) => ("404 Not Found", json!({ "error": err.to_string() })),
+            Err(err) => ("500 Internal Server Error", json!({ "error": err.to_string() })),
+        },
+        None => ("404 Not Found", json!({ "error": format!("No route for {method} {path}") })),
+    };
+    respond(&mut stream, status, body).await
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: Value) -> Result<()> {
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+async fn handle_route(watcher: &Watcher, route: Route<'_>) -> Result<Value> {
+    let value = match route {
+        Route::Accounts => serde_json::to_value(watcher.accounts().iter().map(|account| account.info()).collect::<Vec<_>>())?,
+        Route::Account(name) => serde_json::to_value(watcher.account(name)?.info())?,
+        Route::Addresses(name) => {
+            let addresses = watcher.account(name)?.addresses();
+            json!(addresses
+                .iter()
+                .enumerate()
+                .map(|(index, address)| json!({ "index": index, "address": address }))
+                .collect::<Vec<_>>())
+        }
+        Route::NewAddress(name) => {
+            let (index, address) = watcher.new_address(name).await?;
+            json!({ "index": index, "address": address })
+        }
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_parse() {
+        assert_eq!(Route::parse("GET", "/accounts"), Some(Route::Accounts));
+        assert_eq!(Route::parse("GET", "/accounts/shop/"), Some(Route::Account("shop")));
+        assert_eq!(Route::parse("GET", "/accounts/shop/addresses"), Some(Route::Addresses("shop")));
+        assert_eq!(Route::parse("POST", "/accounts/shop/addresses"), Some(Route::NewAddress("shop")));
+        assert_eq!(Route::parse("POST", "/accounts"), None);
+        assert_eq!(Route::parse("GET", "/"), None);
+    }
+
+    #[tokio::test]
+    async fn test_read_request() {
+        // the request spans several reads, and the body is consumed up to its announced length
+        let mut stream = (&b"POST /accounts/shop/addr"[..])
+            .chain(&b"esses HTTP/1.1\r\nHost: localhost\r\ncontent-length: 2\r\n\r"[..])
+            .chain(&b"\n{"[..])
+            .chain(&b"}"[..]);
+        let head = read_request(&mut stream).await.unwrap();
+        assert_eq!(head.lines().next(), Some("POST /accounts/shop/addresses HTTP/1.1"));
+        assert_eq!(stream.read(&mut [0; 1]).await.unwrap(), 0);
+
+        let mut stream = &b"GET /accounts HTTP/1.1\r\nContent-Length: 10\r\n\r\n{}"[..];
+        assert!(matches!(read_request(&mut stream).await, Err(Error::InvalidRequest(_))));
+        let mut stream = &b"GET /accounts HTTP/1.1\r\nContent-Length: x\r\n\r\n"[..];
+        assert!(matches!(read_request(&mut stream).await, Err(Error::InvalidRequest(_))));
+        let head = vec![b'a'; MAX_HEAD_SIZE + 1];
+        assert!(matches!(read_request(&mut head.as_slice()).await, Err(Error::InvalidRequest(_))));
+    }
+}
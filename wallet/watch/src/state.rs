@@ -0,0 +1,128 @@
+//!
+//! State of the watch server persisted across restarts.
+//!
+//! Besides the number of receive addresses derived for each account, the state records the payment
+//! events that have been emitted, so that payments rediscovered on reconnect are not reported twice,
+//! and the queue of events that are yet to be delivered to the webhook. Payments are forgotten once
+//! their accepting block is deeper than the retention depth.
+//!
+
+use crate::result::Result;
+use crate::webhook::{PaymentEvent, PaymentEventKind};
+use kaspa_wallet_core::storage::TransactionId;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Depth in DAA score units, past the confirmation depth, after which a payment is forgotten
+/// (about a week at 10 BPS). Payments accepted deeper than that are not reported when discovered.
+pub const NOTIFIED_RETENTION_DEPTH: u64 = 7 * 24 * 60 * 60 * 10;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifiedPayment {
+    /// DAA score of the block accepting the payment
+    pub block_daa_score: u64,
+    /// Events emitted so far
+    pub events: HashSet<PaymentEventKind>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct State {
+    /// Number of receive addresses derived for each account
+    pub indexes: HashMap<String, usize>,
+    /// Events emitted so far for each payment within the retention depth
+    pub notified: HashMap<TransactionId, NotifiedPayment>,
+    /// Events awaiting delivery, in emission order
+    pub queue: VecDeque<PaymentEvent>,
+}
+
+impl State {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Writes the state to a temporary file and moves it in place, so that
+    /// an interrupted write never leaves a truncated state file behind
+    pub fn store(&self, path: &Path) -> Result<()> {
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        let temp_path = PathBuf::from(temp_path);
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(&serde_json::to_vec_pretty(self)?)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&temp_path, path)?;
+        Ok(())
+    }
+
+    /// Records the event as emitted, returns `false` if it already was
+    pub fn mark_notified(&mut self, transaction_id: TransactionId, block_daa_score: u64, kind: PaymentEventKind) -> bool {
+        let payment = self.notified.entry(transaction_id).or_default();
+        payment.block_daa_score = block_daa_score;
+        let inserted = payment.events.insert(kind);
+        if inserted {
+            // a payment removed by a reorg and accepted again is reported anew, and vice versa
+            match kind {
+                PaymentEventKind::Reorg => {
+                    for kind in [PaymentEventKind::Incoming, PaymentEventKind::Mature, PaymentEventKind::Confirmed] {
+                        payment.events.remove(&kind);
+                    }
+                }
+                PaymentEventKind::Incoming => {
+                    payment.events.remove(&PaymentEventKind::Reorg);
+                }
+                _ => {}
+            }
+        }
+        inserted
+    }
+
+    /// Forgets the payments accepted before `block_daa_score`, returns `true` if any was
+    pub fn prune(&mut self, block_daa_score: u64) -> bool {
+        let len = self.notified.len();
+        self.notified.retain(|_, payment| payment.block_daa_score >= block_daa_score);
+        self.notified.len() != len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_store_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        assert!(State::load(&path).unwrap().indexes.is_empty());
+
+        let mut state = State::default();
+        state.indexes.insert("shop".to_string(), 3);
+
+        let id = TransactionId::from_u64_word(1);
+        assert!(state.mark_notified(id, 10, PaymentEventKind::Incoming));
+        assert!(!state.mark_notified(id, 10, PaymentEventKind::Incoming));
+        state.store(&path).unwrap();
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let mut state = State::load(&path).unwrap();
+        assert_eq!(state.indexes.get("shop"), Some(&3));
+        assert!(!state.mark_notified(id, 10, PaymentEventKind::Incoming));
+
+        // a reorg allows the payment to be reported again once it is accepted again
+        assert!(state.mark_notified(id, 10, PaymentEventKind::Reorg));
+        assert!(state.mark_notified(id, 12, PaymentEventKind::Incoming));
+        assert!(state.mark_notified(id, 12, PaymentEventKind::Reorg));
+
+        // payments are forgotten once accepted before the pruning score
+        assert!(!state.prune(12));
+        assert!(state.prune(13));
+        assert!(state.notified.is_empty());
+    }
+}
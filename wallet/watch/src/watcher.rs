@@ -0,0 +1,612 @@
+//!
+//! Watch-only accounts tracked by a standalone [`UtxoProcessor`].
+//!
+//! Each account derives its receive addresses from an extended public key and binds them to its own
+//! [`UtxoContext`]. The watcher listens to the processor events, maps them to the account owning the
+//! transaction and forwards them as [`PaymentEvent`] webhooks. Payments are additionally tracked until
+//! their accepting block reaches the configured confirmation depth.
+//!
+//! Emitted events are recorded in the [`State`] so that payments rediscovered after a reconnect or a
+//! restart are not reported twice. The state is persisted once per processor event, covering all the
+//! payment events it emits. Events are queued in the state and delivered one at a time, in the order
+//! they were emitted; an event that cannot be delivered is retried until it succeeds, holding back the
+//! events that follow it.
+//!
+
+use crate::error::Error;
+use crate::result::Result;
+use crate::state::{State, NOTIFIED_RETENTION_DEPTH};
+use crate::webhook::{PaymentEvent, PaymentEventKind, Webhook};
+use futures::{select, FutureExt};
+use kaspa_addresses::{Address, Prefix};
+use kaspa_consensus_core::network::NetworkId;
+use kaspa_hashes::Hash;
+use kaspa_wallet_core::events::Events;
+use kaspa_wallet_core::rpc::{ConnectOptions, ConnectStrategy, Rpc};
+use kaspa_wallet_core::storage::{Binding, TransactionData, TransactionId, TransactionRecord};
+use kaspa_wallet_core::utxo::{Balance, Maturity, UtxoContext, UtxoContextBinding, UtxoContextId, UtxoProcessor};
+use kaspa_wallet_keys::derivation::gen1::{PubkeyDerivationManager, WalletDerivationManager};
+use kaspa_wallet_keys::derivation::traits::WalletDerivationManagerTrait;
+use kaspa_wrpc_client::{KaspaRpcClient, WrpcEncoding};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use workflow_core::channel::DuplexChannel;
+use workflow_log::*;
+
+/// Upper bound of the delay between two delivery attempts of the same event
+const MAX_DELIVERY_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// A watch-only account deriving its receive addresses from an extended public key
+pub struct WatchAccount {
+    name: String,
+    derivation: WalletDerivationManager,
+    prefix: Prefix,
+    addresses: Mutex<Vec<Address>>,
+    utxo_context: UtxoContext,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInfo {
+    pub name: String,
+    pub receive_address_count: usize,
+    pub balance: Option<Balance>,
+}
+
+impl WatchAccount {
+    fn try_new(name: &str, xpub: &str, processor: &UtxoProcessor, prefix: Prefix) -> Result<Self> {
+        let derivation = WalletDerivationManager::from_extended_public_key_str(xpub, None)?;
+        let id = UtxoContextId::new(Hash::from_bytes(Sha256::digest(xpub.as_bytes()).into()));
+        let utxo_context = UtxoContext::new(processor, UtxoContextBinding::Id(id));
+        Ok(Self { name: name.to_string(), derivation, prefix, addresses: Mutex::new(vec![]), utxo_context })
+    }
+
+    pub fn id(&self) -> UtxoContextId {
+        self.utxo_context.id()
+    }
+
+    pub fn addresses(&self) -> Vec<Address> {
+        self.addresses.lock().unwrap().clone()
+    }
+
+    pub fn balance(&self) -> Option<Balance> {
+        self.utxo_context.balance()
+    }
+
+    pub fn info(&self) -> AccountInfo {
+        AccountInfo { name: self.name.clone(), receive_address_count: self.addresses.lock().unwrap().len(), balance: self.balance() }
+    }
+
+    fn derive_address(&self, index: u32) -> Result<Address> {
+        let public_key = self.derivation.receive_pubkey_manager().derive_pubkey(index)?;
+        Ok(PubkeyDerivationManager::create_address(&public_key, self.prefix, false)?)
+    }
+
+    /// Derives the addresses up to `count`, used to restore the account from the state file
+    fn restore(&self, count: usize) -> Result<()> {
+        let addresses = (0..count as u32).map(|index| self.derive_address(index)).collect::<Result<Vec<_>>>()?;
+        *self.addresses.lock().unwrap() = addresses;
+        Ok(())
+    }
+
+    /// Derives the next receive address and starts monitoring it
+    pub async fn new_address(&self) -> Result<(u32, Address)> {
+        let (index, address) = {
+            let mut addresses = self.addresses.lock().unwrap();
+            let index = addresses.len() as u32;
+            let address = self.derive_address(index)?;
+            addresses.push(address.clone());
+            (index, address)
+        };
+        // addresses derived while disconnected are registered once the processor starts
+        if self.utxo_context.processor().is_connected() {
+            self.utxo_context.scan_and_register_addresses(vec![address.clone()], None).await?;
+        }
+        Ok((index, address))
+    }
+
+    async fn register(&self) -> Result<()> {
+        let addresses = self.addresses();
+        if !addresses.is_empty() {
+            self.utxo_context.scan_and_register_addresses(addresses, None).await?;
+        }
+        Ok(())
+    }
+}
+
+pub struct WatcherArgs {
+    pub network_id: NetworkId,
+    pub rpc_url: String,
+    /// `(name, xpub)` pairs
+    pub accounts: Vec<(String, String)>,
+    pub webhook: Option<Webhook>,
+    pub confirmations: u64,
+    pub state: Option<PathBuf>,
+}
+
+pub struct Watcher {
+    rpc_client: Arc<KaspaRpcClient>,
+    processor: UtxoProcessor,
+    accounts: Vec<Arc<WatchAccount>>,
+    webhook: Option<Webhook>,
+    confirmations: u64,
+    state: Mutex<State>,
+    state_path: Option<PathBuf>,
+    /// Payments awaiting the confirmation depth
+    unconfirmed: Mutex<HashMap<TransactionId, (Arc<WatchAccount>, TransactionRecord)>>,
+    /// Signaled when an event is queued for delivery
+    delivery: Notify,
+    task_ctl: DuplexChannel,
+    delivery_task_ctl: DuplexChannel,
+}
+
+impl Watcher {
+    pub fn try_new(args: WatcherArgs) -> Result<Arc<Self>> {
+        let WatcherArgs { network_id, rpc_url, accounts, webhook, confirmations, state: state_path } = args;
+
+        let rpc_client =
+            Arc::new(KaspaRpcClient::new_with_args(WrpcEncoding::Borsh, Some(rpc_url.as_str()), None, Some(network_id), None)?);
+        let rpc = Rpc::new(rpc_client.clone(), rpc_client.ctl().clone());
+        let processor = UtxoProcessor::new(Some(rpc), Some(network_id), None, None);
+
+        let state = match state_path.as_ref() {
+            Some(path) => State::load(path)?,
+            None => State::default(),
+        };
+
+        let prefix = Prefix::from(network_id);
+        let mut watch_accounts: Vec<Arc<WatchAccount>> = vec![];
+        for (name, xpub) in accounts {
+            if watch_accounts.iter().any(|account| account.name == name) {
+                return Err(Error::DuplicateAccount(name));
+            }
+            let account = WatchAccount::try_new(&name, &xpub, &processor, prefix)?;
+            account.restore(state.indexes.get(&name).copied().unwrap_or_default())?;
+            watch_accounts.push(Arc::new(account));
+        }
+
+        Ok(Arc::new(Self {
+            rpc_client,
+            processor,
+            accounts: watch_accounts,
+            webhook,
+            confirmations,
+            state: Mutex::new(state),
+            state_path,
+            unconfirmed: Mutex::new(HashMap::new()),
+            delivery: Notify::new(),
+            task_ctl: DuplexChannel::oneshot(),
+            delivery_task_ctl: DuplexChannel::oneshot(),
+        }))
+    }
+
+    pub fn accounts(&self) -> &[Arc<WatchAccount>] {
+        &self.accounts
+    }
+
+    pub fn account(&self, name: &str) -> Result<&Arc<WatchAccount>> {
+        self.accounts.iter().find(|account| account.name == name).ok_or_else(|| Error::UnknownAccount(name.to_string()))
+    }
+
+    /// Derives the next receive address of an account and persists the derivation state
+    pub async fn new_address(&self, name: &str) -> Result<(u32, Address)> {
+        let result = self.account(name)?.new_address().await?;
+        self.store_state()?;
+        Ok(result)
+    }
+
+    fn store_state(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        state.indexes = self.accounts.iter().map(|account| (account.name.clone(), account.addresses.lock().unwrap().len())).collect();
+        self.persist(&state)
+    }
+
+    fn persist(&self, state: &State) -> Result<()> {
+        if let Some(path) = self.state_path.as_ref() {
+            state.store(path)?;
+        }
+        Ok(())
+    }
+
+    pub async fn start(self: &Arc<Self>) -> Result<()> {
+        self.start_delivery_task();
+        self.start_event_task().await?;
+        self.processor.start().await?;
+        let options = ConnectOptions { block_async_connect: false, strategy: ConnectStrategy::Retry, ..Default::default() };
+        self.rpc_client.connect(Some(options)).await?;
+        Ok(())
+    }
+
+    pub async fn stop(&self) -> Result<()> {
+        self.processor.stop().await?;
+        self.rpc_client.disconnect().await?;
+        self.task_ctl.signal(()).await.map_err(|err| Error::Other(err.to_string()))?;
+        if self.webhook.is_some() {
+            self.delivery_task_ctl.signal(()).await.map_err(|err| Error::Other(err.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Delivers the queued events one at a time, removing each event from
+    /// the persisted queue only once the webhook has acknowledged it
+    fn start_delivery_task(self: &Arc<Self>) {
+        let Some(webhook) = self.webhook.clone() else {
+            return;
+        };
+        let this = self.clone();
+        let task_ctl_receiver = self.delivery_task_ctl.request.receiver.clone();
+        let task_ctl_sender = self.delivery_task_ctl.response.sender.clone();
+
+        tokio::spawn(async move {
+            let mut delay = Duration::from_secs(1);
+            loop {
+                let next = this.state.lock().unwrap().queue.front().cloned();
+                let Some(event) = next else {
+                    select! {
+                        _ = task_ctl_receiver.recv().fuse() => break,
+                        _ = this.delivery.notified().fuse() => continue,
+                    }
+                };
+                match webhook.post(&event).await {
+                    Ok(()) => {
+                        delay = Duration::from_secs(1);
+                        let mut state = this.state.lock().unwrap();
+                        if state.queue.front() == Some(&event) {
+                            state.queue.pop_front();
+                        }
+                        // a backlog is persisted once drained, events delivered since the last
+                        // write are delivered again if the server stops in the meantime
+                        if state.queue.is_empty() {
+                            this.persist(&state).unwrap_or_else(|err| log_error!("Unable to store the state: {err}"));
+                        }
+                    }
+                    Err(err) => {
+                        this.persist(&this.state.lock().unwrap()).unwrap_or_else(|err| log_error!("Unable to store the state: {err}"));
+                        log_warn!(
+                            "Webhook delivery of {} {} failed, retrying in {}s: {err}",
+                            event.event.as_str(),
+                            event.transaction_id,
+                            delay.as_secs()
+                        );
+                        select! {
+                            _ = task_ctl_receiver.recv().fuse() => break,
+                            _ = tokio::time::sleep(delay).fuse() => {}
+                        }
+                        delay = (delay * 2).min(MAX_DELIVERY_RETRY_DELAY);
+                    }
+                }
+            }
+            task_ctl_sender.send(()).await.unwrap();
+        });
+    }
+
+    async fn start_event_task(self: &Arc<Self>) -> Result<()> {
+        let this = self.clone();
+        let events = self.processor.multiplexer().channel();
+        let task_ctl_receiver = self.task_ctl.request.receiver.clone();
+        let task_ctl_sender = self.task_ctl.response.sender.clone();
+
+        tokio::spawn(async move {
+            loop {
+                select! {
+                    _ = task_ctl_receiver.recv().fuse() => break,
+                    event = events.receiver.recv().fuse() => match event {
+                        Ok(event) => this.handle_event(*event).await.unwrap_or_else(|err| log_error!("{err}")),
+                        Err(err) => {
+                            log_error!("Event channel error: {err}");
+                            break;
+                        }
+                    }
+                }
+            }
+            events.close();
+            task_ctl_sender.send(()).await.unwrap();
+        });
+        Ok(())
+    }
+
+    /// Handles a processor event, persisting the state once if it emitted any payment event
+    async fn handle_event(&self, event: Events) -> Result<()> {
+        let mut changed = false;
+        match event {
+            Events::UtxoProcStart => {
+                log_info!("Connected to {}", self.rpc_client.url().unwrap_or_default());
+                for account in self.accounts.iter() {
+                    account.register().await?;
+                }
+            }
+            Events::UtxoProcStop => {
+                log_info!("Disconnected from the node");
+            }
+            Events::UtxoIndexNotEnabled { url } => {
+                log_error!("The node {} does not have the UTXO index enabled", url.unwrap_or_default());
+            }
+            Events::Pending { record } => {
+                if let Some(account) = self.account_by_binding(record.binding()) {
+                    changed |= self.notify(PaymentEventKind::Incoming, &account, &record);
+                    self.unconfirmed.lock().unwrap().insert(*record.id(), (account, record));
+                }
+            }
+            Events::Discovery { record } => {
+                // payments received while disconnected are reported with every stage they have already
+                // reached, skipping the ones emitted before and the payments past the retention depth
+                if let Some(account) = self.account_by_binding(record.binding()) {
+                    let current_daa_score = self.processor.current_daa_score().unwrap_or_default();
+                    let depth = self.depth(&record, current_daa_score);
+                    if depth <= self.confirmations.saturating_add(NOTIFIED_RETENTION_DEPTH) {
+                        changed |= self.notify(PaymentEventKind::Incoming, &account, &record);
+                        if matches!(record.maturity(current_daa_score), Maturity::Confirmed) {
+                            changed |= self.notify(PaymentEventKind::Mature, &account, &record);
+                        }
+                        if depth >= self.confirmations {
+                            changed |= self.notify(PaymentEventKind::Confirmed, &account, &record);
+                        } else {
+                            self.unconfirmed.lock().unwrap().insert(*record.id(), (account, record));
+                        }
+                    }
+                }
+            }
+            Events::Maturity { record } => {
+                if let Some(account) = self.account_by_binding(record.binding()) {
+                    changed |= self.notify(PaymentEventKind::Mature, &account, &record);
+                }
+            }
+            Events::Reorg { record } => {
+                if let Some(account) = self.account_by_binding(record.binding()) {
+                    self.unconfirmed.lock().unwrap().remove(record.id());
+                    changed |= self.notify(PaymentEventKind::Reorg, &account, &record);
+                }
+            }
+            Events::DaaScoreChange { current_daa_score } => {
+                let confirmed = {
+                    let mut unconfirmed = self.unconfirmed.lock().unwrap();
+                    let ids = unconfirmed
+                        .iter()
+                        .filter(|(_, (_, record))| self.depth(record, current_daa_score) >= self.confirmations)
+                        .map(|(id, _)| *id)
+                        .collect::<Vec<_>>();
+                    ids.into_iter().filter_map(|id| unconfirmed.remove(&id)).collect::<Vec<_>>()
+                };
+                for (account, record) in confirmed {
+                    changed |= self.notify(PaymentEventKind::Confirmed, &account, &record);
+                }
+                let retention_daa_score =
+                    current_daa_score.saturating_sub(self.confirmations.saturating_add(NOTIFIED_RETENTION_DEPTH));
+                changed |= self.state.lock().unwrap().prune(retention_daa_score);
+            }
+            _ => {}
+        }
+        if changed {
+            self.persist(&self.state.lock().unwrap())?;
+            self.delivery.notify_one();
+        }
+        Ok(())
+    }
+
+    fn account_by_binding(&self, binding: &Binding) -> Option<Arc<WatchAccount>> {
+        let id = match binding {
+            Binding::Custom(id) => *id,
+            Binding::Account(id) => id.into(),
+        };
+        self.accounts.iter().find(|account| account.id() == id).cloned()
+    }
+
+    fn depth(&self, record: &TransactionRecord, current_daa_score: u64) -> u64 {
+        current_daa_score.saturating_sub(record.block_daa_score())
+    }
+
+    /// Emits the event unless it has been emitted before, queueing it for delivery.
+    /// Returns `true` if the event was emitted, in which case the state is left to the
+    /// caller to persist.
+    fn notify(&self, kind: PaymentEventKind, account: &WatchAccount, record: &TransactionRecord) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if !state.mark_notified(*record.id(), record.block_daa_score(), kind) {
+            return false;
+        }
+
+        let addresses = match record.transaction_data() {
+            TransactionData::Incoming { utxo_entries, .. }
+            | TransactionData::External { utxo_entries, .. }
+            | TransactionData::Reorg { utxo_entries, .. } => {
+                let mut addresses = utxo_entries.iter().filter_map(|utxo| utxo.address.clone()).collect::<Vec<_>>();
+                addresses.sort();
+                addresses.dedup();
+                addresses
+            }
+            _ => vec![],
+        };
+        let current_daa_score = self.processor.current_daa_score().unwrap_or_default();
+        let event = PaymentEvent {
+            event: kind,
+            account: account.name.clone(),
+            transaction_id: *record.id(),
+            amount: record.value,
+            addresses,
+            block_daa_score: record.block_daa_score(),
+            confirmations: self.depth(record, current_daa_score),
+        };
+        log_info!("{} {} {} sompi to '{}'", kind.as_str(), event.transaction_id, event.amount, event.account);
+        if self.webhook.is_some() {
+            state.queue.push_back(event);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::webhook::{sign, EVENT_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+    use kaspa_consensus_client::{TransactionOutpoint, UtxoEntry};
+    use kaspa_consensus_core::network::NetworkType;
+    use kaspa_consensus_core::tx::ScriptPublicKey;
+    use kaspa_wallet_core::utxo::UtxoEntryReference;
+    use std::path::Path;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const KPUB: &str =
+        "kpub2HtoTgsG6e1c7ixJ6JY49otNSzhEKkwnH6bsPHLAXUdYnfEuYw9LnhT7uRzaS4LSeit2rzutV6z8Fs9usdEGKnNe6p1JxfP71mK8rbUfYWo";
+    const SECRET: &str = "webhook-secret";
+
+    fn new_watcher(state: &Path, webhook_url: &str, confirmations: u64) -> Arc<Watcher> {
+        Watcher::try_new(WatcherArgs {
+            network_id: NetworkId::new(NetworkType::Mainnet),
+            rpc_url: "wrpc://127.0.0.1:1".to_string(),
+            accounts: vec![("shop".to_string(), KPUB.to_string())],
+            webhook: Some(Webhook::new(webhook_url.to_string(), SECRET.to_string()).unwrap()),
+            confirmations,
+            state: Some(state.to_path_buf()),
+        })
+        .unwrap()
+    }
+
+    fn record(watcher: &Watcher, id: u64, addresses: &[Address]) -> TransactionRecord {
+        let transaction_id = TransactionId::from_u64_word(id);
+        let utxos = addresses
+            .iter()
+            .enumerate()
+            .map(|(index, address)| {
+                UtxoEntryReference::from(UtxoEntry {
+                    address: Some(address.clone()),
+                    outpoint: TransactionOutpoint::new(transaction_id, index as u32),
+                    amount: 1000,
+                    script_public_key: ScriptPublicKey::from_vec(0, vec![]),
+                    block_daa_score: 0,
+                    is_coinbase: false,
+                })
+            })
+            .collect::<Vec<_>>();
+        TransactionRecord::new_incoming(&watcher.accounts()[0].utxo_context, transaction_id, &utxos)
+    }
+
+    fn queued_events(watcher: &Watcher) -> Vec<PaymentEvent> {
+        watcher.state.lock().unwrap().queue.iter().cloned().collect()
+    }
+
+    fn kinds(watcher: &Watcher) -> Vec<(TransactionId, PaymentEventKind)> {
+        queued_events(watcher).iter().map(|event| (event.transaction_id, event.event)).collect()
+    }
+
+    #[tokio::test]
+    async fn test_discovery_notifies_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        let id = TransactionId::from_u64_word(1);
+
+        let watcher = new_watcher(&path, "http://127.0.0.1:1", 10);
+        let (_, first) = watcher.new_address("shop").await.unwrap();
+        let (_, second) = watcher.new_address("shop").await.unwrap();
+        let record = record(&watcher, 1, &[second.clone(), first.clone(), second.clone()]);
+
+        // a payment discovered on connect is reported, then confirmed once deep enough
+        watcher.handle_event(Events::Discovery { record: record.clone() }).await.unwrap();
+        watcher.handle_event(Events::DaaScoreChange { current_daa_score: 10 }).await.unwrap();
+        assert_eq!(kinds(&watcher), vec![(id, PaymentEventKind::Incoming), (id, PaymentEventKind::Confirmed)]);
+        let mut addresses = vec![first, second];
+        addresses.sort();
+        assert_eq!(queued_events(&watcher)[0].addresses, addresses);
+
+        // discovering it again on reconnect does not report it twice
+        watcher.handle_event(Events::Discovery { record: record.clone() }).await.unwrap();
+        watcher.handle_event(Events::DaaScoreChange { current_daa_score: 11 }).await.unwrap();
+        assert_eq!(queued_events(&watcher).len(), 2);
+
+        // neither does discovering it after a restart, and the undelivered events are kept
+        let watcher = new_watcher(&path, "http://127.0.0.1:1", 10);
+        assert_eq!(watcher.accounts()[0].addresses().len(), 2);
+        watcher.handle_event(Events::Discovery { record: record.clone() }).await.unwrap();
+        watcher.handle_event(Events::DaaScoreChange { current_daa_score: 12 }).await.unwrap();
+        assert_eq!(kinds(&watcher), vec![(id, PaymentEventKind::Incoming), (id, PaymentEventKind::Confirmed)]);
+
+        watcher.handle_event(Events::Reorg { record }).await.unwrap();
+        assert_eq!(kinds(&watcher).last(), Some(&(id, PaymentEventKind::Reorg)));
+
+        // the payment is forgotten once past the retention depth
+        watcher.handle_event(Events::DaaScoreChange { current_daa_score: 10 + NOTIFIED_RETENTION_DEPTH }).await.unwrap();
+        assert_eq!(State::load(&path).unwrap().notified.len(), 1);
+        watcher.handle_event(Events::DaaScoreChange { current_daa_score: 11 + NOTIFIED_RETENTION_DEPTH }).await.unwrap();
+        assert!(State::load(&path).unwrap().notified.is_empty());
+    }
+
+    /// Reads a single HTTP request, returning its headers and body
+    async fn read_request(stream: &mut tokio::net::TcpStream) -> (HashMap<String, String>, Vec<u8>) {
+        let mut buffer = vec![];
+        loop {
+            let mut chunk = [0; 4096];
+            let n = stream.read(&mut chunk).await.unwrap();
+            buffer.extend_from_slice(&chunk[..n]);
+            let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") else {
+                continue;
+            };
+            let head = String::from_utf8_lossy(&buffer[..end]).to_string();
+            let headers = head
+                .lines()
+                .skip(1)
+                .filter_map(|line| line.split_once(':'))
+                .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+                .collect::<HashMap<_, _>>();
+            let length = headers.get("content-length").map(|length| length.parse::<usize>().unwrap()).unwrap_or_default();
+            if buffer.len() >= end + 4 + length {
+                return (headers, buffer[end + 4..end + 4 + length].to_vec());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delivery_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+
+        // the receiver rejects the first request and accepts the following ones
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            for request in 0.. {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let (headers, body) = read_request(&mut stream).await;
+                let status = if request == 0 { "500 Internal Server Error" } else { "200 OK" };
+                stream
+                    .write_all(format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").as_bytes())
+                    .await
+                    .unwrap();
+                sender.send((headers, body)).unwrap();
+            }
+        });
+
+        let watcher = new_watcher(&path, &url, 0);
+        let (_, address) = watcher.new_address("shop").await.unwrap();
+        for id in [1, 2] {
+            watcher.handle_event(Events::Discovery { record: record(&watcher, id, std::slice::from_ref(&address)) }).await.unwrap();
+        }
+        let expected = kinds(&watcher);
+        assert_eq!(expected.len(), 4);
+
+        watcher.start_delivery_task();
+        let mut delivered = vec![];
+        for request in 0..5 {
+            let (headers, body) = receiver.recv().await.unwrap();
+            let timestamp = headers[&TIMESTAMP_HEADER.to_lowercase()].parse::<u64>().unwrap();
+            assert_eq!(headers[&SIGNATURE_HEADER.to_lowercase()], sign(SECRET.as_bytes(), timestamp, &body));
+            let event: PaymentEvent = serde_json::from_slice(&body).unwrap();
+            assert_eq!(headers[&EVENT_HEADER.to_lowercase()], event.event.as_str());
+            // the rejected event is retried before any other event is delivered
+            if request > 0 {
+                delivered.push((event.transaction_id, event.event));
+            } else {
+                assert_eq!((event.transaction_id, event.event), expected[0]);
+            }
+        }
+        assert_eq!(delivered, expected);
+
+        watcher.delivery_task_ctl.signal(()).await.unwrap();
+        assert!(queued_events(&watcher).is_empty());
+        assert!(State::load(&path).unwrap().queue.is_empty());
+    }
+}
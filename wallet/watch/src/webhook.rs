@@ -0,0 +1,111 @@
+//!
+//! Delivery of signed payment notifications to the merchant backend.
+//!
+
+use crate::result::Result;
+use hmac::{Hmac, Mac};
+use kaspa_addresses::Address;
+use kaspa_wallet_core::storage::TransactionId;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Header carrying the hex encoded HMAC-SHA256 of `<timestamp>.<body>`
+pub const SIGNATURE_HEADER: &str = "X-Kaspa-Signature";
+/// Header carrying the unix time in seconds at which the request was signed
+pub const TIMESTAMP_HEADER: &str = "X-Kaspa-Timestamp";
+/// Header carrying the payment event kind
+pub const EVENT_HEADER: &str = "X-Kaspa-Event";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaymentEventKind {
+    /// The payment has been accepted in a block
+    Incoming,
+    /// The payment has reached the wallet maturity period and can be spent
+    Mature,
+    /// The payment has reached the configured confirmation depth
+    Confirmed,
+    /// The payment has been removed by a reorg
+    Reorg,
+}
+
+impl PaymentEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentEventKind::Incoming => "incoming",
+            PaymentEventKind::Mature => "mature",
+            PaymentEventKind::Confirmed => "confirmed",
+            PaymentEventKind::Reorg => "reorg",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentEvent {
+    pub event: PaymentEventKind,
+    pub account: String,
+    pub transaction_id: TransactionId,
+    pub amount: u64,
+    pub addresses: Vec<Address>,
+    pub block_daa_score: u64,
+    /// Depth of the accepting block in DAA score units at the time of the event
+    pub confirmations: u64,
+}
+
+#[derive(Clone)]
+pub struct Webhook {
+    url: String,
+    secret: String,
+    client: reqwest::Client,
+}
+
+impl Webhook {
+    pub fn new(url: String, secret: String) -> Result<Self> {
+        let client = reqwest::Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+        Ok(Self { url, secret, client })
+    }
+
+    /// Posts a single event, signing it together with the current time so that
+    /// the receiver can reject replayed requests
+    pub async fn post(&self, event: &PaymentEvent) -> Result<()> {
+        let body = serde_json::to_vec(event)?;
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        self.client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, event.event.as_str())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, sign(self.secret.as_bytes(), timestamp, &body))
+            .body(body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Hex encoded HMAC-SHA256 of `<timestamp>.<body>` keyed with `secret`
+pub fn sign(secret: &[u8], timestamp: u64, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign() {
+        let body = b"what do ya want for nothing?";
+        assert_eq!(sign(b"Jefe", 1700000000, body), "1cdd0650c8be1cb0974b1788d458b1e781206cfef59b85faafc582d2e182c57e");
+        // the timestamp is covered by the signature
+        assert_ne!(sign(b"Jefe", 1700000001, body), sign(b"Jefe", 1700000000, body));
+    }
+}